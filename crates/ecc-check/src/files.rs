@@ -0,0 +1,84 @@
+//! Discovery of characteristic files.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use crate::Error;
+
+/// The file extensions of YAML files.
+pub const EXTENSIONS: &[&str] = &["yml", "yaml"];
+
+/// Returns whether a path has a YAML file extension.
+pub fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| EXTENSIONS.contains(&ext))
+}
+
+/// Gets the paths of all YAML files within a directory (sorted).
+pub fn files(root: &Path) -> Result<Vec<PathBuf>, Error> {
+    let mut result = Vec::new();
+
+    for extension in EXTENSIONS {
+        let pattern = format!("{}/**/*.{extension}", root.display());
+
+        for entry in glob::glob(&pattern).map_err(|source| Error::Pattern {
+            pattern: pattern.clone(),
+            source,
+        })? {
+            result.push(entry.map_err(|err| Error::Io {
+                path: err.path().to_path_buf(),
+                source: err.into_error(),
+            })?);
+        }
+    }
+
+    result.sort();
+    Ok(result)
+}
+
+/// Gets the paths of all YAML files within a set of files and directories
+/// (sorted and deduplicated).
+///
+/// Directories are searched recursively, whereas files are included as-is.
+/// Each path is then filtered by the `include` patterns (if any are provided)
+/// and the `exclude` patterns. Patterns are matched against both the full path
+/// and the path relative to the directory it was found in.
+pub fn select(
+    paths: &[PathBuf],
+    include: &[glob::Pattern],
+    exclude: &[glob::Pattern],
+) -> Result<Vec<PathBuf>, Error> {
+    let mut result = Vec::new();
+
+    for path in paths {
+        let (root, candidates) = if path.is_dir() {
+            (path.as_path(), files(path)?)
+        } else if path.is_file() {
+            (Path::new(""), vec![path.clone()])
+        } else {
+            return Err(Error::NotFound(path.clone()));
+        };
+
+        for candidate in candidates {
+            let relative = candidate.strip_prefix(root).unwrap_or(&candidate);
+            let matches = |pattern: &glob::Pattern| {
+                pattern.matches_path(&candidate) || pattern.matches_path(relative)
+            };
+
+            if !include.is_empty() && !include.iter().any(matches) {
+                continue;
+            }
+
+            if exclude.iter().any(matches) {
+                continue;
+            }
+
+            result.push(candidate);
+        }
+    }
+
+    result.sort();
+    result.dedup();
+    Ok(result)
+}
@@ -0,0 +1,683 @@
+//! Validation of composable characteristic trees.
+//!
+//! This crate contains the checks run by `ecc-cli check` so that they can be
+//! run by other tools (e.g., services and tests) without shelling out to the
+//! command line tool.
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! let report = ecc_check::check_tree(Path::new("ecc"), &Default::default())?;
+//!
+//! for file in &report.files {
+//!     for diagnostic in &file.diagnostics {
+//!         println!("{}: {}", file.path.display(), diagnostic.message);
+//!     }
+//! }
+//!
+//! assert!(report.passed());
+//! # Ok::<(), ecc_check::Error>(())
+//! ```
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ecc::Characteristic;
+use ecc::CharacteristicSet;
+use ecc::State;
+use ecc::common::EvidenceLevel;
+use ecc::common::Tag;
+use ecc::common::molecular::Symbols;
+use ecc::error::Code;
+
+pub mod files;
+pub mod lint;
+mod locate;
+pub mod report;
+pub mod span;
+mod validate;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+pub use report::Diagnostic;
+pub use report::FileReport;
+pub use report::Report;
+pub use report::Severity;
+
+/// An error that prevented a check from being completed.
+///
+/// Problems within the characteristics themselves are reported as
+/// [`Diagnostic`]s rather than errors.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A file could not be read.
+    #[error("reading file `{path}`: {source}", path = .path.display())]
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: std::io::Error,
+    },
+
+    /// A path does not exist.
+    #[error("path does not exist: {}", .0.display())]
+    NotFound(PathBuf),
+
+    /// A glob pattern was invalid.
+    #[error("resolving glob `{pattern}`: {source}")]
+    Pattern {
+        /// The pattern.
+        pattern: String,
+
+        /// The underlying error.
+        source: glob::PatternError,
+    },
+}
+
+/// Options for checking a tree.
+#[derive(Clone, Debug)]
+pub struct Options {
+    /// Only files matching at least one of these patterns are checked (if any
+    /// are provided).
+    pub include: Vec<glob::Pattern>,
+
+    /// Files matching any of these patterns are skipped.
+    pub exclude: Vec<glob::Pattern>,
+
+    /// Whether to report every schema violation within a file rather than
+    /// only the first.
+    ///
+    /// Each file is parsed into a value tree and every node is checked against
+    /// the schema, which is slower than deserializing the file directly.
+    pub all_errors: bool,
+
+    /// The limit on the length (in characters) of an option of a categorical
+    /// feature (see [`lint::OPTION_LENGTH`]).
+    pub max_option_length: usize,
+
+    /// The controlled vocabulary of tags (see [`lint::INVALID_TAG`]).
+    pub tags: Vec<Tag>,
+
+    /// The minimum completeness score required for characteristics in each
+    /// state (see [`lint::INCOMPLETE`]).
+    ///
+    /// Characteristics in states without a minimum are not scored.
+    pub min_completeness: BTreeMap<State, f64>,
+
+    /// The minimum evidence level required for adopted characteristics (see
+    /// [`lint::INSUFFICIENT_EVIDENCE`]).
+    ///
+    /// Evidence levels are only checked when a minimum is provided.
+    pub min_evidence_level: Option<EvidenceLevel>,
+
+    /// The HGNC symbol list that gene symbols are checked against (see
+    /// [`lint::UNRECOGNIZED_GENE_SYMBOL`]).
+    ///
+    /// Gene symbols are only checked when a list is provided.
+    pub gene_symbols: Option<Arc<Symbols>>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            all_errors: false,
+            max_option_length: lint::MAX_OPTION_LENGTH,
+            // SAFETY: every default tag is valid, so this will always unwrap.
+            tags: lint::DEFAULT_TAGS
+                .iter()
+                .map(|tag| tag.parse().unwrap())
+                .collect(),
+            min_completeness: BTreeMap::new(),
+            min_evidence_level: None,
+            gene_symbols: None,
+        }
+    }
+}
+
+/// Checks the contents of a characteristic file.
+pub fn check_str(
+    path: impl Into<PathBuf>,
+    source: impl Into<String>,
+    options: &Options,
+) -> FileReport {
+    let source = source.into();
+
+    let (characteristic, diagnostics) = match serde_yaml::from_str::<Characteristic>(&source) {
+        Ok(characteristic) => {
+            let diagnostics = lint::lint(&source, &characteristic, options);
+            (Some(characteristic), diagnostics)
+        }
+        Err(err) => {
+            let map = span::SpanMap::parse(&source);
+            let message = ecc::io::normalize(&err.to_string()).to_string();
+
+            let legacy = serde_yaml::from_str::<serde_yaml::Value>(&source)
+                .is_ok_and(|document| ecc::legacy::is_legacy(&document));
+
+            let diagnostics = match &map {
+                Some(map) if legacy => {
+                    let state = span::Path::default().join(span::Segment::Key("state".into()));
+                    let span = map.get(&state).map(|node| node.span.clone());
+
+                    vec![
+                        Diagnostic::error(
+                            "the characteristic uses the legacy flat format; run `ecc migrate` to \
+                             upgrade it",
+                            span,
+                        )
+                        .with_path(state)
+                        .with_code(Code::MIGRATE),
+                    ]
+                }
+                Some(map) if options.all_errors => validate::validate(&source, map),
+                _ => Vec::new(),
+            };
+
+            if !diagnostics.is_empty() {
+                return FileReport {
+                    path: path.into(),
+                    source,
+                    characteristic: None,
+                    diagnostics,
+                };
+            }
+
+            let diagnostic = match map
+                .as_ref()
+                .and_then(|map| locate::locate(&source, map, &err))
+            {
+                Some(node) => Diagnostic::error(message, Some(node.span.clone()))
+                    .with_path(node.path.clone())
+                    .with_code(Code::for_field(&node.path)),
+                None => {
+                    // NOTE: the document could not be parsed as YAML at all,
+                    // so only the location reported by `serde_yaml` (if any)
+                    // is available.
+                    let index = match err.location() {
+                        Some(location) => location.index(),
+                        None => source.len(),
+                    };
+
+                    Diagnostic::error(err.to_string(), Some(index..index)).with_code(Code::SYNTAX)
+                }
+            };
+
+            (None, vec![diagnostic])
+        }
+    };
+
+    FileReport {
+        path: path.into(),
+        source,
+        characteristic,
+        diagnostics,
+    }
+}
+
+/// Checks a single characteristic file.
+pub fn check_file(path: &Path, options: &Options) -> Result<FileReport, Error> {
+    let source = std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    Ok(check_str(path, source, options))
+}
+
+/// Checks the characteristic files within a set of files and directories.
+///
+/// See [`files::select()`] for how files are selected. The definitions of the
+/// characteristics are checked against every characteristic that was parsed
+/// (see [`lint::definitions()`]).
+pub fn check_paths(paths: &[PathBuf], options: &Options) -> Result<Report, Error> {
+    let mut files = files::select(paths, &options.include, &options.exclude)?
+        .iter()
+        .map(|path| check_file(path, options))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let characteristics = files
+        .iter()
+        .filter_map(|file| file.characteristic.clone())
+        .collect::<CharacteristicSet>();
+
+    for file in &mut files {
+        let Some(characteristic) = &file.characteristic else {
+            continue;
+        };
+
+        let diagnostics = lint::definitions(&file.source, characteristic, &characteristics);
+
+        if !diagnostics.is_empty() {
+            file.diagnostics.extend(diagnostics);
+            file.diagnostics
+                .sort_by_key(|d| d.span.as_ref().map(|span| span.start));
+        }
+    }
+
+    Ok(Report { files })
+}
+
+/// Checks every characteristic file within a directory.
+pub fn check_tree(root: &Path, options: &Options) -> Result<Report, Error> {
+    check_paths(&[root.to_path_buf()], options)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let report = check_str(
+            "valid.yml",
+            "state: draft\nname: A Characteristic Name\n",
+            &Options::default(),
+        );
+
+        assert!(report.passed());
+        assert!(report.diagnostics.is_empty());
+        assert_eq!(
+            report.characteristic.unwrap().name(),
+            Some("A Characteristic Name")
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nfoo: bar\n",
+            &Options::default(),
+        );
+
+        assert!(!report.passed());
+        assert!(report.characteristic.is_none());
+
+        let diagnostic = report.errors().next().unwrap();
+        assert!(diagnostic.message.contains("unknown field `foo`"));
+        assert_eq!(diagnostic.span, Some(18..21));
+        assert_eq!(diagnostic.path.as_ref().unwrap().to_string(), "foo");
+        assert_eq!(diagnostic.code, Some(Code::SCHEMA));
+    }
+
+    #[test]
+    fn codes() {
+        let code = |source: &str| {
+            let report = check_str("invalid.yml", source, &Options::default());
+            report.diagnostics[0].code
+        };
+
+        assert_eq!(
+            code(
+                "state: draft
+identifier: foo
+"
+            ),
+            Some(Code::IDENTIFIER)
+        );
+        assert_eq!(
+            code(
+                "state: draft
+rfc: foo
+"
+            ),
+            Some(Code::LINK)
+        );
+        assert_eq!(
+            code(
+                "state: [
+"
+            ),
+            Some(Code::SYNTAX)
+        );
+        assert_eq!(
+            code(
+                "state: draft
+translations:
+  en_US: Foo
+"
+            ),
+            Some(Code::LANGUAGE)
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nsensitivity: secret\n",
+            &Options::default(),
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "sensitivity"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("unknown sensitivity")
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nevidence_level: anecdotal\n",
+            &Options::default(),
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "evidence_level"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("unknown evidence level")
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nrelations:\n  - kind: replaces\n    target: ECC-MOLEC-000001\n",
+            &Options::default(),
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "relations[0].kind"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("unknown relation kind")
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nstewards:\n  - name: Jane Smith\n    github: '@jsmith'\n    email: \
+             jane.smith@example.com\n",
+            &Options {
+                all_errors: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "stewards[0].github"
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nmappings:\n  - predicate: exact-match\n    term: C3171\n",
+            &Options::default(),
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "mappings[0].term"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("invalid compact URI")
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nmolecular:\n  genes: [PAX5]\n  variants: [P80R]\n",
+            &Options {
+                all_errors: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "molecular.variants[0]"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("invalid HGVS expression")
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\ncytogenetics: [t(9;22)(q34;q11.2), t(9;23)(q34;q11)]\n",
+            &Options {
+                all_errors: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "cytogenetics[1]"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("invalid ISCN expression")
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\ndefinition: ECC-MORPH-000001 > \"Foo\"\n",
+            &Options {
+                all_errors: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "definition"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("`>` can only compare numbers")
+        );
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\nvalues:\n  kind: immunophenotype\n  markers: [CD19, cd34]\n",
+            &Options {
+                all_errors: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "values.markers[1]"
+        );
+        assert!(report.diagnostics[0].message.contains("invalid marker"));
+
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\napplicability:\n  specimen_types: [blood, saliva]\n",
+            &Options {
+                all_errors: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            report.diagnostics[0].path.as_ref().unwrap().to_string(),
+            "applicability.specimen_types[1]"
+        );
+        assert!(
+            report.diagnostics[0]
+                .message
+                .contains("unknown specimen type")
+        );
+
+        let options = Options {
+            all_errors: true,
+            ..Default::default()
+        };
+        let report = check_str(
+            "invalid.yml",
+            "state: draft\ntranslations:\n  de: Ein Name\n  en_US: Foo\n  fr: [Foo]\n",
+            &options,
+        );
+        let errors = report
+            .errors()
+            .map(|d| (d.path.as_ref().unwrap().to_string(), d.code))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            [
+                (String::from("translations.en_US"), Some(Code::LANGUAGE)),
+                (String::from("translations.fr"), Some(Code::SCHEMA)),
+            ]
+        );
+    }
+
+    /// Checks a source, returning the text and path of the first error.
+    fn locate(source: &str) -> (String, &str, String) {
+        let report = check_str("invalid.yml", source, &Options::default());
+        let diagnostic = report.errors().next().unwrap();
+
+        (
+            diagnostic.message.clone(),
+            &source[diagnostic.span.clone().unwrap()],
+            diagnostic.path.as_ref().unwrap().to_string(),
+        )
+    }
+
+    #[test]
+    fn spans() {
+        let (message, text, path) =
+            locate(
+                "state: draft\nvalues:\n  kind: categorical\n  options:\n    - a\n    - b\n    - \
+                 c\n    - {foo: bar}\n",
+            );
+        assert_eq!(message, "invalid type: map, expected a string");
+        assert_eq!(text, "{foo: bar}");
+        assert_eq!(path, "values.options[3]");
+
+        let (message, text, path) = locate(
+            "state: draft\nreferences:\n  - kind: manuscript\n    title: Foo\n    context: \
+             Bar.\n    url: https://example.com\n    highlighted: true\n",
+        );
+        assert!(message.starts_with("missing field `authors`"));
+        assert!(text.starts_with("kind: manuscript"));
+        assert_eq!(path, "references[0]");
+
+        let (message, text, path) = locate("state: proposed\nname: Foo\n");
+        assert!(message.starts_with("missing field"));
+        assert_eq!(text, "proposed");
+        assert_eq!(path, "state");
+
+        let (message, text, path) = locate("state: foo\n");
+        assert!(message.starts_with("unknown variant `foo`"));
+        assert_eq!(text, "foo");
+        assert_eq!(path, "state");
+    }
+
+    #[test]
+    fn other_kind() {
+        let values =
+            "values:\n  kind: other\n  description: A count of cells per high-power field.\n";
+
+        let report = check_str(
+            "other.yml",
+            format!("state: draft\nname: A Characteristic Name\n{values}"),
+            &Options::default(),
+        );
+        assert!(report.passed());
+        assert!(report.diagnostics.is_empty());
+
+        let source = format!(
+            "state: proposed\nidentifier: ECC-MORPH-000001\nname: A Characteristic \
+             Name\nrfc: https://github.com/stjudecloud/ecc/issues/1\ndescription: A \
+             description.\n{values}references:\n  - kind: manuscript\n    title: A Title\n    \
+             authors: A. Author\n    context: Some context.\n    url: https://example.com\n    \
+             highlighted: true\n"
+        );
+
+        // NOTE: only drafts can use other kinds of values regardless of the
+        // configured lints.
+        for all_errors in [false, true] {
+            let options = Options {
+                all_errors,
+                ..Default::default()
+            };
+            let report = check_str("other.yml", source.as_str(), &options);
+            assert!(!report.passed());
+            assert!(report.characteristic.is_none());
+
+            let diagnostic = report.errors().next().unwrap();
+            assert_eq!(diagnostic.lint, None);
+            assert!(
+                diagnostic
+                    .message
+                    .contains("only drafts can use `kind: other`")
+            );
+            assert_eq!(diagnostic.path.as_ref().unwrap().to_string(), "values.kind");
+            assert_eq!(&source[diagnostic.span.clone().unwrap()], "other");
+        }
+    }
+
+    #[test]
+    fn all_errors() {
+        let source = "state: proposed\nname: Foo\nfoo: bar\nrfc: not a url\nvalues:\n  kind: \
+                      categorical\n  options:\n    - a\n    - [b]\nreferences:\n  - kind: \
+                      preprint\n    title: Foo\n";
+        let options = Options {
+            all_errors: true,
+            ..Default::default()
+        };
+
+        let report = check_str("invalid.yml", source, &options);
+        assert!(report.characteristic.is_none());
+
+        let errors = report
+            .errors()
+            .map(|d| {
+                let message = d.message.split(',').next().unwrap();
+                format!("{}: {message}", d.path.as_ref().unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            [
+                ".: missing field `identifier`",
+                ".: missing field `description`",
+                "foo: unknown field `foo`",
+                "rfc: url parse error: `not a url`; relative URL without a base",
+                "values.options[1]: invalid type: sequence",
+                "references[0]: missing field `authors`",
+                "references[0]: missing field `context`",
+                "references[0]: missing field `url`",
+                "references[0]: missing field `highlighted`",
+            ]
+        );
+
+        let state = &source[report.diagnostics[0].span.clone().unwrap()];
+        assert_eq!(state, "proposed");
+
+        // NOTE: without the option, only the first error is reported.
+        let report = check_str("invalid.yml", source, &Options::default());
+        assert_eq!(report.errors().count(), 1);
+    }
+
+    #[test]
+    fn legacy() {
+        let source = "state: proposed\nrfc: https://github.com/stjudecloud/ecc/issues/1\n";
+        let report = check_str("legacy.yml", source, &Options::default());
+
+        let diagnostic = report.errors().next().unwrap();
+        assert!(diagnostic.message.contains("legacy flat format"));
+        assert_eq!(&source[diagnostic.span.clone().unwrap()], "proposed");
+    }
+
+    #[test]
+    fn tree() {
+        // NOTE: this is the root of the crate itself, not the workspace.
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../ecc");
+
+        let report = check_tree(&root, &Options::default()).unwrap();
+        assert!(!report.files.is_empty());
+        assert!(report.passed());
+        assert_eq!(report.characteristics().count(), report.files.len());
+
+        let missing = check_tree(&root.join("missing"), &Options::default()).unwrap_err();
+        assert!(matches!(missing, Error::NotFound(_)));
+    }
+}
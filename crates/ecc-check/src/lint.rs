@@ -0,0 +1,779 @@
+//! Lints for characteristics that match the schema but are likely mistakes.
+//!
+//! Each diagnostic reported here names the lint that produced it (see
+//! [`Diagnostic::lint`]) so that tools can change its severity or turn it off
+//! entirely.
+
+use ecc::Characteristic;
+use ecc::CharacteristicSet;
+use ecc::State;
+use ecc::common::expression;
+use ecc::common::molecular::Status;
+use ecc::common::value::Kind;
+use ecc::common::value::kind::fraction;
+use ecc::error::Code;
+use serde_yaml::Value;
+
+use crate::Diagnostic;
+use crate::Options;
+use crate::span::Path;
+use crate::span::Segment;
+use crate::span::SpanMap;
+
+/// A categorical feature with two options that form a yes/no pair.
+pub const CATEGORICAL_AS_BINARY: &str = "categorical-as-binary";
+
+/// A definition that refers to a characteristic that is unknown or is not
+/// adopted (only checked across a set; see [`definitions()`]).
+pub const DEFINITION_REFERENCE: &str = "definition-reference";
+
+/// A comparison within a definition that can never hold given the kind of its
+/// characteristic (only checked across a set; see [`definitions()`]).
+pub const DEFINITION_TYPE: &str = "definition-type";
+
+/// Options of a categorical feature that differ only by case or whitespace.
+pub const DUPLICATE_OPTIONS: &str = "duplicate-options";
+
+/// A fractional feature whose bounds are outside of its scale or are
+/// inverted.
+pub const FRACTION_BOUNDS: &str = "fraction-bounds";
+
+/// A characteristic whose completeness score is below the minimum required
+/// for its state (see [`ecc::completeness`]).
+pub const INCOMPLETE: &str = "incomplete";
+
+/// An adopted characteristic whose evidence level is below the minimum (only
+/// checked when a minimum is provided; see [`Options::min_evidence_level`]).
+pub const INSUFFICIENT_EVIDENCE: &str = "insufficient-evidence";
+
+/// A tag that is not within the controlled vocabulary or that is listed more
+/// than once.
+pub const INVALID_TAG: &str = "invalid-tag";
+
+/// An option of a categorical feature that is longer than the limit.
+pub const OPTION_LENGTH: &str = "option-length";
+
+/// A categorical feature with fewer than two options.
+pub const TOO_FEW_OPTIONS: &str = "too-few-options";
+
+/// A gene symbol that is not an approved HGNC symbol (only checked when a
+/// symbol list is provided; see [`Options::gene_symbols`]).
+pub const UNRECOGNIZED_GENE_SYMBOL: &str = "unrecognized-gene-symbol";
+
+/// A definition that can never hold for any sample.
+pub const UNSATISFIABLE_DEFINITION: &str = "unsatisfiable-definition";
+
+/// Every lint.
+pub const ALL: &[&str] = &[
+    CATEGORICAL_AS_BINARY,
+    DEFINITION_REFERENCE,
+    DEFINITION_TYPE,
+    DUPLICATE_OPTIONS,
+    FRACTION_BOUNDS,
+    INCOMPLETE,
+    INSUFFICIENT_EVIDENCE,
+    INVALID_TAG,
+    OPTION_LENGTH,
+    TOO_FEW_OPTIONS,
+    UNRECOGNIZED_GENE_SYMBOL,
+    UNSATISFIABLE_DEFINITION,
+];
+
+/// The default limit on the length (in characters) of an option of a
+/// categorical feature.
+pub const MAX_OPTION_LENGTH: usize = 64;
+
+/// The default controlled vocabulary of tags.
+pub const DEFAULT_TAGS: &[&str] = &[
+    "adult",
+    "germline",
+    "hematologic",
+    "neuro-oncology",
+    "pediatric",
+    "solid-tumor",
+    "somatic",
+];
+
+/// Pairs of options that describe a binary feature.
+const BINARY: &[(&str, &str)] = &[
+    ("yes", "no"),
+    ("y", "n"),
+    ("true", "false"),
+    ("present", "absent"),
+    ("positive", "negative"),
+    ("detected", "not detected"),
+    ("1", "0"),
+];
+
+/// Normalizes an option for comparison by lowercasing it and collapsing its
+/// whitespace.
+fn normalize(option: &str) -> String {
+    option
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Returns the path to a field within the permissible values.
+fn values(name: &str) -> Path {
+    Path::default()
+        .join(Segment::Key(String::from("values")))
+        .join(Segment::Key(name.to_string()))
+}
+
+/// Creates a diagnostic for a lint at a path.
+///
+/// Lints that describe characteristics that cannot be used as written are
+/// errors, and the rest are warnings.
+fn diagnostic(map: &SpanMap, path: Path, lint: &'static str, message: String) -> Diagnostic {
+    let span = map.get(&path).map(|node| node.span.clone());
+    let diagnostic = match lint {
+        DEFINITION_REFERENCE
+        | DEFINITION_TYPE
+        | FRACTION_BOUNDS
+        | INCOMPLETE
+        | INSUFFICIENT_EVIDENCE
+        | INVALID_TAG
+        | TOO_FEW_OPTIONS
+        | UNSATISFIABLE_DEFINITION => Diagnostic::error(message, span),
+        _ => Diagnostic::warning(message, span),
+    };
+
+    diagnostic
+        .with_code(Code::for_field(&path))
+        .with_path(path)
+        .with_lint(lint)
+}
+
+/// Lints the options of a categorical feature.
+///
+/// The options are read from the document rather than the characteristic so
+/// that they are reported in the order that they are written (and with their
+/// spans).
+fn options(document: &Value, map: &SpanMap, options: &Options) -> Vec<Diagnostic> {
+    let Some(values) = document
+        .get("values")
+        .and_then(|values| values.get("options"))
+        .and_then(Value::as_sequence)
+    else {
+        return Vec::new();
+    };
+
+    let path = self::values("options");
+    let diagnostic =
+        |path: Path, lint: &'static str, message: String| diagnostic(map, path, lint, message);
+
+    let mut diagnostics = Vec::new();
+
+    match values.len() {
+        0 => diagnostics.push(diagnostic(
+            path.clone(),
+            TOO_FEW_OPTIONS,
+            String::from(
+                "a categorical feature must have at least two options, but none are listed",
+            ),
+        )),
+        1 => diagnostics.push(diagnostic(
+            path.clone(),
+            TOO_FEW_OPTIONS,
+            String::from(
+                "a categorical feature must have at least two options, but only one is listed",
+            ),
+        )),
+        _ => {}
+    }
+
+    let values = values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, value)| value.as_str().map(|value| (i, value)))
+        .collect::<Vec<_>>();
+
+    if let [(_, a), (_, b)] = values.as_slice() {
+        let (a, b) = (normalize(a), normalize(b));
+
+        if BINARY
+            .iter()
+            .any(|&(x, y)| (a == x && b == y) || (a == y && b == x))
+        {
+            diagnostics.push(diagnostic(
+                path.clone(),
+                CATEGORICAL_AS_BINARY,
+                format!(
+                    "the options `{a}` and `{b}` describe a binary feature; use `kind: binary` \
+                     instead"
+                ),
+            ));
+        }
+    }
+
+    for (n, &(i, value)) in values.iter().enumerate() {
+        let item = path.join(Segment::Index(i));
+        let normalized = normalize(value);
+
+        if let Some((_, earlier)) = values[..n]
+            .iter()
+            .find(|(_, earlier)| normalize(earlier) == normalized)
+        {
+            diagnostics.push(diagnostic(
+                item.clone(),
+                DUPLICATE_OPTIONS,
+                format!("the option `{value}` differs from `{earlier}` only by case or whitespace"),
+            ));
+        }
+
+        let length = value.chars().count();
+
+        if length > options.max_option_length {
+            diagnostics.push(diagnostic(
+                item,
+                OPTION_LENGTH,
+                format!(
+                    "the option is {length} characters long, which is longer than the limit of {}",
+                    options.max_option_length
+                ),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// Lints the tags against the controlled vocabulary.
+fn tags(characteristic: &Characteristic, map: &SpanMap, options: &Options) -> Vec<Diagnostic> {
+    let path = Path::default().join(Segment::Key(String::from("tags")));
+    let tags = characteristic.tags();
+    let mut diagnostics = Vec::new();
+
+    for (i, tag) in tags.iter().enumerate() {
+        let item = path.join(Segment::Index(i));
+
+        if tags[..i].contains(tag) {
+            diagnostics.push(diagnostic(
+                map,
+                item,
+                INVALID_TAG,
+                format!("the tag `{tag}` is listed more than once"),
+            ));
+        } else if !options.tags.contains(tag) {
+            diagnostics.push(diagnostic(
+                map,
+                item,
+                INVALID_TAG,
+                format!(
+                    "the tag `{tag}` is not within the controlled vocabulary; expected one of {}",
+                    options
+                        .tags
+                        .iter()
+                        .map(|tag| format!("`{tag}`"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+            ));
+        }
+    }
+
+    diagnostics
+}
+
+/// Lints the gene symbols against the HGNC symbol list (if one is provided).
+fn genes(characteristic: &Characteristic, map: &SpanMap, options: &Options) -> Vec<Diagnostic> {
+    let (Some(symbols), Some(molecular)) = (&options.gene_symbols, characteristic.molecular())
+    else {
+        return Vec::new();
+    };
+
+    let path = Path::default()
+        .join(Segment::Key(String::from("molecular")))
+        .join(Segment::Key(String::from("genes")));
+
+    molecular
+        .genes()
+        .iter()
+        .enumerate()
+        .filter_map(|(i, gene)| {
+            let message = match symbols.status(gene) {
+                Status::Approved => return None,
+                Status::Previous(approved) => format!(
+                    "the gene symbol `{gene}` is a previous HGNC symbol; use the approved symbol \
+                     `{approved}` instead"
+                ),
+                Status::Alias(approved) => format!(
+                    "the gene symbol `{gene}` is an alias; use the approved HGNC symbol \
+                     `{approved}` instead"
+                ),
+                Status::Unrecognized => {
+                    format!("the gene symbol `{gene}` is not an approved HGNC symbol")
+                }
+            };
+
+            Some(diagnostic(
+                map,
+                path.join(Segment::Index(i)),
+                UNRECOGNIZED_GENE_SYMBOL,
+                message,
+            ))
+        })
+        .collect()
+}
+
+/// Lints the completeness of a characteristic against the minimum required for
+/// its state.
+fn completeness(
+    characteristic: &Characteristic,
+    map: &SpanMap,
+    options: &Options,
+) -> Option<Diagnostic> {
+    let state = characteristic.state();
+    let min = *options.min_completeness.get(&state)?;
+    let score = characteristic.completeness();
+
+    if score.value() >= min {
+        return None;
+    }
+
+    Some(diagnostic(
+        map,
+        Path::default().join(Segment::Key(String::from("state"))),
+        INCOMPLETE,
+        format!(
+            "the characteristic is {score} complete, but {state} characteristics must be at least \
+             {min:.2} complete; missing: {}",
+            score
+                .missing()
+                .iter()
+                .map(|criterion| format!("`{criterion}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    ))
+}
+
+/// Lints the evidence level of an adopted characteristic against the minimum.
+fn evidence(
+    characteristic: &Characteristic,
+    map: &SpanMap,
+    options: &Options,
+) -> Option<Diagnostic> {
+    let min = options.min_evidence_level?;
+
+    if characteristic.state() != State::Adopted {
+        return None;
+    }
+
+    let (field, message) = match characteristic.evidence_level() {
+        Some(level) if level >= min => return None,
+        Some(level) => (
+            "evidence_level",
+            format!(
+                "the evidence level is `{level}`, but adopted characteristics must be supported \
+                 by at least `{min}` evidence"
+            ),
+        ),
+        None => (
+            "state",
+            format!(
+                "adopted characteristics must be supported by at least `{min}` evidence, but no \
+                 `evidence_level` is given"
+            ),
+        ),
+    };
+
+    Some(diagnostic(
+        map,
+        Path::default().join(Segment::Key(String::from(field))),
+        INSUFFICIENT_EVIDENCE,
+        message,
+    ))
+}
+
+/// Lints the definition of a characteristic for contradictions.
+fn definition(characteristic: &Characteristic, map: &SpanMap) -> Option<Diagnostic> {
+    let definition = characteristic.definition()?;
+
+    if definition.is_satisfiable() {
+        return None;
+    }
+
+    Some(diagnostic(
+        map,
+        Path::default().join(Segment::Key(String::from("definition"))),
+        UNSATISFIABLE_DEFINITION,
+        String::from("the definition contradicts itself, so it can never hold for any sample"),
+    ))
+}
+
+/// Lints the definition of a characteristic against the characteristics that
+/// it refers to (see [`ecc::common::Expression::issues()`]).
+///
+/// Unlike the other lints, these require every characteristic within a set,
+/// so they are not run when checking a single file.
+pub fn definitions(
+    source: &str,
+    characteristic: &Characteristic,
+    characteristics: &CharacteristicSet,
+) -> Vec<Diagnostic> {
+    let Some(definition) = characteristic.definition() else {
+        return Vec::new();
+    };
+
+    let map = SpanMap::parse(source).unwrap_or_default();
+    let path = Path::default().join(Segment::Key(String::from("definition")));
+
+    definition
+        .issues(characteristics)
+        .into_iter()
+        .map(|issue| {
+            let lint = match issue {
+                expression::Issue::Incompatible { .. } => DEFINITION_TYPE,
+                _ => DEFINITION_REFERENCE,
+            };
+
+            diagnostic(&map, path.clone(), lint, issue.to_string())
+        })
+        .collect()
+}
+
+/// Lints a characteristic that matches the schema.
+///
+/// The diagnostics are ordered by their position within the document.
+pub(crate) fn lint(
+    source: &str,
+    characteristic: &Characteristic,
+    options: &Options,
+) -> Vec<Diagnostic> {
+    let Ok(document) = serde_yaml::from_str::<Value>(source) else {
+        return Vec::new();
+    };
+
+    let map = SpanMap::parse(source).unwrap_or_default();
+
+    let mut diagnostics = match characteristic.values() {
+        Some(Kind::Categorical { .. }) => self::options(&document, &map, options),
+        Some(Kind::Fraction {
+            scale, min, max, ..
+        }) => match fraction::validate(*scale, *min, *max) {
+            Ok(()) => Vec::new(),
+            Err(err) => {
+                let bound = match err {
+                    fraction::Error::OutOfRange { bound, .. } => bound,
+                    fraction::Error::Inverted { .. } => "min",
+                };
+
+                vec![diagnostic(
+                    &map,
+                    values(bound),
+                    FRACTION_BOUNDS,
+                    err.to_string(),
+                )]
+            }
+        },
+        _ => Vec::new(),
+    };
+
+    diagnostics.extend(tags(characteristic, &map, options));
+    diagnostics.extend(genes(characteristic, &map, options));
+    diagnostics.extend(completeness(characteristic, &map, options));
+    diagnostics.extend(evidence(characteristic, &map, options));
+    diagnostics.extend(definition(characteristic, &map));
+    diagnostics.sort_by_key(|d| d.span.as_ref().map(|span| span.start));
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use ecc::common::EvidenceLevel;
+    use ecc::common::molecular::Symbols;
+
+    use super::*;
+
+    /// Lints a categorical feature with the given options, returning the lint
+    /// and path of each diagnostic.
+    fn check(options: &[&str], max_option_length: usize) -> Vec<(&'static str, String)> {
+        let mut source = String::from("state: draft\nvalues:\n  kind: categorical\n  options:");
+
+        if options.is_empty() {
+            source.push_str(" []");
+        }
+
+        for option in options {
+            source.push_str(&format!("\n    - \"{option}\""));
+        }
+
+        let options = Options {
+            max_option_length,
+            ..Default::default()
+        };
+
+        let report = crate::check_str("lint.yml", source, &options);
+        assert!(report.characteristic.is_some());
+
+        report
+            .diagnostics
+            .iter()
+            .map(|d| (d.lint.unwrap(), d.path.as_ref().unwrap().to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn options() {
+        assert!(check(&["Low", "Intermediate", "High"], MAX_OPTION_LENGTH).is_empty());
+
+        assert_eq!(
+            check(&[], MAX_OPTION_LENGTH),
+            [(TOO_FEW_OPTIONS, String::from("values.options"))]
+        );
+        assert_eq!(
+            check(&["Present"], MAX_OPTION_LENGTH),
+            [(TOO_FEW_OPTIONS, String::from("values.options"))]
+        );
+        assert_eq!(
+            check(&["Yes", "No"], MAX_OPTION_LENGTH),
+            [(CATEGORICAL_AS_BINARY, String::from("values.options"))]
+        );
+        assert_eq!(
+            check(&["Not  Detected", "detected"], MAX_OPTION_LENGTH),
+            [(CATEGORICAL_AS_BINARY, String::from("values.options"))]
+        );
+        assert_eq!(
+            check(
+                &["Low", "High", "high ", "Very  low", "very low"],
+                MAX_OPTION_LENGTH
+            ),
+            [
+                (DUPLICATE_OPTIONS, String::from("values.options[2]")),
+                (DUPLICATE_OPTIONS, String::from("values.options[4]")),
+            ]
+        );
+        assert_eq!(
+            check(&["Low", "Intermediate", "High"], 5),
+            [(OPTION_LENGTH, String::from("values.options[1]"))]
+        );
+    }
+
+    #[test]
+    fn fraction_bounds() {
+        let source = "state: draft\nvalues:\n  kind: fraction\n  denominator_description: \
+                      Nucleated cells\n  scale: proportion\n  max: 20\n";
+        let report = crate::check_str("fraction.yml", source, &Options::default());
+        assert!(report.characteristic.is_some());
+
+        let diagnostic = report.errors().next().unwrap();
+        assert_eq!(diagnostic.lint, Some(FRACTION_BOUNDS));
+        assert_eq!(diagnostic.path.as_ref().unwrap().to_string(), "values.max");
+        assert_eq!(&source[diagnostic.span.clone().unwrap()], "20");
+        assert_eq!(
+            diagnostic.message,
+            "`max` is 20, but a proportion must be between 0 and 1"
+        );
+    }
+
+    #[test]
+    fn tags() {
+        let source =
+            "state: draft\ntags:\n  - pediatric\n  - solid-tumor\n  - pediatric\n  - teen\n";
+        let report = crate::check_str("tags.yml", source, &Options::default());
+        assert!(report.characteristic.is_some());
+
+        let diagnostics = report
+            .errors()
+            .map(|d| (d.lint.unwrap(), d.path.as_ref().unwrap().to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [
+                (INVALID_TAG, String::from("tags[2]")),
+                (INVALID_TAG, String::from("tags[3]")),
+            ]
+        );
+
+        let options = Options {
+            tags: vec!["teen".parse().unwrap()],
+            ..Default::default()
+        };
+        let report = crate::check_str("tags.yml", "state: draft\ntags: [teen]\n", &options);
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn genes() {
+        let source = "state: draft\nmolecular:\n  genes: [H3F3A, PAX5, H3]\n  variants: [p.K27M]\n";
+        let report = crate::check_str("genes.yml", source, &Options::default());
+        assert!(report.passed());
+        assert!(report.diagnostics.is_empty());
+
+        let options = Options {
+            gene_symbols: Some(Arc::new(Symbols::parse(
+                "symbol\tprev_symbol\nH3-3A\tH3F3A\nPAX5\t\n",
+            ))),
+            ..Default::default()
+        };
+        let report = crate::check_str("genes.yml", source, &options);
+        assert!(report.passed());
+
+        let diagnostics = report
+            .warnings()
+            .map(|d| (d.path.as_ref().unwrap().to_string(), d.message.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    String::from("molecular.genes[0]"),
+                    "the gene symbol `H3F3A` is a previous HGNC symbol; use the approved symbol \
+                     `H3-3A` instead"
+                ),
+                (
+                    String::from("molecular.genes[2]"),
+                    "the gene symbol `H3` is not an approved HGNC symbol"
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn completeness() {
+        let source = "state: draft\nname: A Characteristic Name\n";
+        let report = crate::check_str("draft.yml", source, &Options::default());
+        assert!(report.passed());
+
+        let options = Options {
+            min_completeness: [(State::Draft, 0.5)].into_iter().collect(),
+            ..Default::default()
+        };
+        let report = crate::check_str("draft.yml", source, &options);
+
+        let diagnostic = report.errors().next().unwrap();
+        assert_eq!(diagnostic.lint, Some(INCOMPLETE));
+        assert_eq!(&source[diagnostic.span.clone().unwrap()], "draft");
+        assert_eq!(
+            diagnostic.message,
+            "the characteristic is 0.00 complete, but draft characteristics must be at least 0.50 \
+             complete; missing: `required-fields`, `references`, `value-descriptions`"
+        );
+    }
+
+    #[test]
+    fn evidence() {
+        let source = "state: adopted\nidentifier: ECC-MORPH-000001\nname: A Characteristic \
+                      Name\nrfc: https://github.com/stjudecloud/ecc/issues/1\ndescription: A \
+                      description.\nvalues:\n  kind: categorical\n  options: [Low, \
+                      High]\nevidence_level: single-study\nadoption_date: 2024-01-01T00:00:00Z\n";
+        let report = crate::check_str("adopted.yml", source, &Options::default());
+        assert!(report.passed());
+
+        let options = Options {
+            min_evidence_level: Some(EvidenceLevel::MultipleStudies),
+            ..Default::default()
+        };
+        let report = crate::check_str("adopted.yml", source, &options);
+
+        let diagnostic = report.errors().next().unwrap();
+        assert_eq!(diagnostic.lint, Some(INSUFFICIENT_EVIDENCE));
+        assert_eq!(&source[diagnostic.span.clone().unwrap()], "single-study");
+        assert_eq!(
+            diagnostic.message,
+            "the evidence level is `single-study`, but adopted characteristics must be supported \
+             by at least `multiple-studies` evidence"
+        );
+
+        let source = source.replace("evidence_level: single-study\n", "");
+        let report = crate::check_str("adopted.yml", source.as_str(), &options);
+
+        let diagnostic = report.errors().next().unwrap();
+        assert_eq!(diagnostic.lint, Some(INSUFFICIENT_EVIDENCE));
+        assert_eq!(diagnostic.path.as_ref().unwrap().to_string(), "state");
+
+        let source = source
+            .replace("state: adopted", "state: proposed")
+            .replace("adoption_date: 2024-01-01T00:00:00Z\n", "");
+        let report = crate::check_str("proposed.yml", source.as_str(), &options);
+        assert!(report.passed());
+    }
+
+    #[test]
+    fn definitions() {
+        let source = "state: draft\ndefinition: ECC-MOLEC-000042 > 5 and ECC-MOLEC-000042 < 3\n";
+        let report = crate::check_str("definition.yml", source, &Options::default());
+        assert!(report.characteristic.is_some());
+
+        let diagnostic = report.errors().next().unwrap();
+        assert_eq!(diagnostic.lint, Some(UNSATISFIABLE_DEFINITION));
+        assert_eq!(diagnostic.path.as_ref().unwrap().to_string(), "definition");
+        assert_eq!(
+            &source[diagnostic.span.clone().unwrap()],
+            "ECC-MOLEC-000042 > 5 and ECC-MOLEC-000042 < 3"
+        );
+
+        let referenced = crate::check_str(
+            "referenced.yml",
+            "state: proposed\nidentifier: ECC-MORPH-000001\nname: A Characteristic \
+             Name\nrfc: https://github.com/stjudecloud/ecc/issues/1\ndescription: A \
+             description.\nvalues:\n  kind: categorical\n  options: [Low, High]\n",
+            &Options::default(),
+        );
+        let characteristics = referenced
+            .characteristic
+            .into_iter()
+            .collect::<CharacteristicSet>();
+
+        let source = "state: draft\ndefinition: ECC-MORPH-000001 = 1 or ECC-MORPH-000002\n";
+        let report = crate::check_str("definition.yml", source, &Options::default());
+        assert!(report.passed());
+
+        let diagnostics = super::definitions(
+            source,
+            report.characteristic.as_ref().unwrap(),
+            &characteristics,
+        )
+        .into_iter()
+        .map(|d| (d.lint.unwrap(), d.message))
+        .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [
+                (
+                    DEFINITION_REFERENCE,
+                    String::from(
+                        "`ECC-MORPH-000001` is proposed, but only adopted characteristics can be \
+                         referred to"
+                    )
+                ),
+                (
+                    DEFINITION_REFERENCE,
+                    String::from("`ECC-MORPH-000002` is not a known characteristic")
+                ),
+                (
+                    DEFINITION_TYPE,
+                    String::from(
+                        "`ECC-MORPH-000001 = 1` can never hold: `ECC-MORPH-000001` is \
+                         categorical, but `1` is a number"
+                    )
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn severities() {
+        let source = "state: draft\nvalues:\n  kind: categorical\n  options:\n    - Yes\n";
+        let report = crate::check_str("lint.yml", source, &Options::default());
+
+        assert!(!report.passed());
+        assert!(report.characteristic.is_some());
+
+        let diagnostic = report.errors().next().unwrap();
+        assert_eq!(diagnostic.lint, Some(TOO_FEW_OPTIONS));
+        assert_eq!(&source[diagnostic.span.clone().unwrap()], "- Yes");
+
+        let source =
+            "state: draft\nvalues:\n  kind: categorical\n  options:\n    - Yes\n    - No\n";
+        let report = crate::check_str("lint.yml", source, &Options::default());
+
+        assert!(report.passed());
+        assert_eq!(report.warnings().count(), 1);
+    }
+}
@@ -0,0 +1,39 @@
+//! Locating deserialization errors within a document.
+//!
+//! The path to the offending field is recovered by [`ecc::io::locate()`] and
+//! then resolved to its span within the source.
+
+use ecc::io::normalize;
+use serde_yaml::Value;
+
+use crate::span::Node;
+use crate::span::Path;
+use crate::span::Segment;
+use crate::span::SpanMap;
+
+/// Locates the node at which a deserialization error occurred.
+pub(crate) fn locate<'a>(
+    source: &str,
+    map: &'a SpanMap,
+    error: &serde_yaml::Error,
+) -> Option<&'a Node> {
+    // NOTE: when `serde_yaml` does report a location, it points directly at
+    // the offending node.
+    if let Some(location) = error.location() {
+        return map.innermost(location.index());
+    }
+
+    let document = serde_yaml::from_str::<Value>(source).ok()?;
+    let message = error.to_string();
+    let path = ecc::io::locate(&document, normalize(&message));
+
+    if path.is_root() {
+        // NOTE: problems with the document as a whole (e.g., a missing field)
+        // are attributed to the state, as it determines the required fields.
+        return map
+            .get(&Path::default().join(Segment::Key(String::from("state"))))
+            .or_else(|| map.get(&path));
+    }
+
+    map.get(&path)
+}
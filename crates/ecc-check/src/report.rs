@@ -0,0 +1,165 @@
+//! Reports produced by checking characteristics.
+
+use std::ops::Range;
+use std::path::PathBuf;
+
+use ecc::Characteristic;
+use ecc::error::Code;
+
+use crate::span::Path;
+
+/// The severity of a diagnostic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    /// The file is invalid.
+    Error,
+
+    /// The file is valid, but something should be looked at.
+    Warning,
+}
+
+/// A single problem found within a file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The severity.
+    pub severity: Severity,
+
+    /// A description of the problem.
+    pub message: String,
+
+    /// The byte range within the file that the problem relates to (if known).
+    pub span: Option<Range<usize>>,
+
+    /// The path to the field that the problem relates to (if known).
+    pub path: Option<Path>,
+
+    /// The code for the kind of problem (if known).
+    pub code: Option<Code>,
+
+    /// The name of the lint that reported the problem (if it was reported by
+    /// a lint rather than a schema violation).
+    ///
+    /// See [`crate::lint`] for the lints.
+    pub lint: Option<&'static str>,
+}
+
+impl Diagnostic {
+    /// Creates a new error diagnostic.
+    pub fn error(message: impl Into<String>, span: Option<Range<usize>>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            span,
+            path: None,
+            code: None,
+            lint: None,
+        }
+    }
+
+    /// Creates a new warning diagnostic.
+    pub fn warning(message: impl Into<String>, span: Option<Range<usize>>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            span,
+            path: None,
+            code: None,
+            lint: None,
+        }
+    }
+
+    /// Sets the path to the field that the problem relates to.
+    pub fn with_path(mut self, path: Path) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    /// Sets the code for the kind of problem.
+    pub fn with_code(mut self, code: Code) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the name of the lint that reported the problem.
+    pub fn with_lint(mut self, lint: &'static str) -> Self {
+        self.lint = Some(lint);
+        self
+    }
+}
+
+/// The results of checking a single file.
+#[derive(Clone, Debug)]
+pub struct FileReport {
+    /// The path to the file.
+    pub path: PathBuf,
+
+    /// The contents of the file.
+    ///
+    /// These are retained so that diagnostics can be rendered against the
+    /// source.
+    pub source: String,
+
+    /// The characteristic (if the file could be parsed).
+    pub characteristic: Option<Characteristic>,
+
+    /// The problems found within the file.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl FileReport {
+    /// Returns whether the file passed (i.e., no errors were reported).
+    pub fn passed(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Gets the error diagnostics.
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Error)
+    }
+
+    /// Gets the warning diagnostics.
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == Severity::Warning)
+    }
+}
+
+/// The results of checking a set of files.
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    /// The report for each file (in the order the files were checked).
+    pub files: Vec<FileReport>,
+}
+
+impl Report {
+    /// Returns whether every file passed.
+    pub fn passed(&self) -> bool {
+        self.files.iter().all(FileReport::passed)
+    }
+
+    /// Gets the number of files that passed.
+    pub fn passed_count(&self) -> usize {
+        self.files.iter().filter(|file| file.passed()).count()
+    }
+
+    /// Gets the number of files that failed.
+    pub fn failed_count(&self) -> usize {
+        self.files.len() - self.passed_count()
+    }
+
+    /// Gets the number of warnings across every file.
+    pub fn warning_count(&self) -> usize {
+        self.files.iter().map(|file| file.warnings().count()).sum()
+    }
+
+    /// Gets the characteristics that were successfully parsed along with the
+    /// paths to their files.
+    pub fn characteristics(&self) -> impl Iterator<Item = (&PathBuf, &Characteristic)> {
+        self.files
+            .iter()
+            .filter_map(|file| file.characteristic.as_ref().map(|c| (&file.path, c)))
+    }
+}
@@ -0,0 +1,375 @@
+//! Source spans for the nodes within a YAML document.
+//!
+//! `serde_yaml` only reports a location for some errors (and never for errors
+//! raised within internally tagged enums, which every characteristic is), so
+//! the document is parsed a second time to recover the byte range and the
+//! field path of every node.
+
+use std::ops::Range;
+
+pub use ecc::io::Path;
+pub use ecc::io::Segment;
+use yaml_rust2::parser::Event;
+use yaml_rust2::parser::MarkedEventReceiver;
+use yaml_rust2::parser::Parser;
+use yaml_rust2::scanner::Marker;
+use yaml_rust2::scanner::TScalarStyle;
+
+/// A node within a document.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Node {
+    /// The path to the node.
+    pub path: Path,
+
+    /// The byte range of the key (if the node is a value within a mapping).
+    pub key: Option<Range<usize>>,
+
+    /// The byte range of the value.
+    pub span: Range<usize>,
+
+    /// The column (in characters) that the value starts at.
+    pub column: usize,
+}
+
+/// A container that is currently being parsed.
+struct Frame {
+    /// The path to the container.
+    path: Path,
+
+    /// Whether the container is a mapping (as opposed to a sequence).
+    mapping: bool,
+
+    /// Whether the container uses flow style (`{}` or `[]`).
+    flow: bool,
+
+    /// The byte offset the container starts at (once known).
+    start: Option<usize>,
+
+    /// The column (in characters) the container starts at (once known).
+    column: usize,
+
+    /// The byte offset of the end of the last child.
+    end: usize,
+
+    /// The key (and its span) awaiting a value.
+    key: Option<(String, Range<usize>)>,
+
+    /// The number of items seen so far (for sequences).
+    items: usize,
+}
+
+/// Collects the events emitted by the parser.
+#[derive(Default)]
+struct Events(Vec<(Event, Marker)>);
+
+impl MarkedEventReceiver for Events {
+    fn on_event(&mut self, event: Event, marker: Marker) {
+        self.0.push((event, marker));
+    }
+}
+
+/// The nodes within a document along with their spans.
+#[derive(Clone, Debug, Default)]
+pub struct SpanMap {
+    /// The nodes in document order.
+    nodes: Vec<Node>,
+}
+
+impl SpanMap {
+    /// Parses the spans of the nodes within the first document of a source.
+    ///
+    /// Returns [`None`] if the source is not valid YAML.
+    pub fn parse(source: &str) -> Option<Self> {
+        let mut events = Events::default();
+        Parser::new_from_str(source).load(&mut events, false).ok()?;
+
+        // NOTE: the parser reports offsets in characters, whereas spans are
+        // reported in bytes.
+        let offsets = source
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(std::iter::once(source.len()))
+            .collect::<Vec<_>>();
+        let byte = |marker: &Marker| offsets[marker.index().min(offsets.len() - 1)];
+
+        let mut builder = Builder::default();
+
+        for (i, (event, marker)) in events.0.iter().enumerate() {
+            let start = byte(marker);
+
+            match event {
+                Event::MappingStart(..) | Event::SequenceStart(..) => {
+                    let flow = matches!(source[start..].chars().next(), Some('{' | '['));
+                    builder.open(
+                        matches!(event, Event::MappingStart(..)),
+                        flow,
+                        // NOTE: the marker for a block mapping points at the
+                        // first `:`, so the start is taken from the first key.
+                        (flow || matches!(event, Event::SequenceStart(..)))
+                            .then_some((start, marker.col())),
+                    );
+                }
+                Event::MappingEnd | Event::SequenceEnd => {
+                    let end = if builder.top_is_flow() {
+                        start + 1
+                    } else {
+                        start
+                    };
+                    builder.close(end);
+                }
+                Event::Scalar(value, style, ..) => {
+                    let next = events.0.get(i + 1).map(|(_, m)| byte(m)).unwrap_or(start);
+                    let end = scalar_end(source, start, next, value, *style);
+                    builder.leaf(value, start..end, marker.col());
+                }
+                Event::Alias(_) => builder.leaf("", start..start, marker.col()),
+                Event::DocumentEnd => break,
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            nodes: builder.nodes,
+        })
+    }
+
+    /// Gets the nodes in document order.
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Gets the node at a path.
+    pub fn get(&self, path: &Path) -> Option<&Node> {
+        self.nodes.iter().find(|node| &node.path == path)
+    }
+
+    /// Gets the most deeply nested node containing a byte offset.
+    pub fn innermost(&self, offset: usize) -> Option<&Node> {
+        self.nodes
+            .iter()
+            .filter(|node| {
+                let start = node
+                    .key
+                    .as_ref()
+                    .map(|k| k.start)
+                    .unwrap_or(node.span.start);
+                start <= offset && offset <= node.span.end
+            })
+            .max_by_key(|node| node.path.depth())
+    }
+}
+
+/// Finds the end of a scalar.
+///
+/// Single line scalars are measured directly. Otherwise, the scalar is
+/// assumed to extend up to the next event, excluding any trailing whitespace,
+/// comments, and indicators.
+fn scalar_end(source: &str, start: usize, next: usize, value: &str, style: TScalarStyle) -> usize {
+    let rest = &source[start..];
+
+    match style {
+        TScalarStyle::Plain if !value.contains('\n') && rest.starts_with(value) => {
+            return start + value.len();
+        }
+        TScalarStyle::SingleQuoted | TScalarStyle::DoubleQuoted => {
+            let quote = if style == TScalarStyle::SingleQuoted {
+                '\''
+            } else {
+                '"'
+            };
+            let mut chars = rest.char_indices().skip(1).peekable();
+
+            while let Some((i, c)) = chars.next() {
+                if c == '\\' && quote == '"' {
+                    chars.next();
+                } else if c == quote {
+                    if quote == '\'' && chars.peek().is_some_and(|(_, c)| *c == '\'') {
+                        chars.next();
+                    } else {
+                        return start + i + 1;
+                    }
+                }
+            }
+
+            return source.len();
+        }
+        _ => {}
+    }
+
+    let mut text = source[start..next.max(start)].trim_end();
+
+    loop {
+        let trimmed = text
+            .lines()
+            .rev()
+            .skip_while(|line| line.trim().is_empty() || line.trim_start().starts_with('#'))
+            .count();
+        let kept = text.lines().take(trimmed).collect::<Vec<_>>().join("\n");
+        let kept = kept
+            .trim_end()
+            .trim_end_matches(['-', ':', ',', '?'])
+            .trim_end();
+
+        if kept.len() == text.len() {
+            break;
+        }
+
+        text = &text[..kept.len()];
+    }
+
+    start + text.len()
+}
+
+/// Builds the nodes from a stream of events.
+#[derive(Default)]
+struct Builder {
+    /// The containers currently being parsed.
+    stack: Vec<Frame>,
+
+    /// The nodes that have been completed.
+    nodes: Vec<Node>,
+}
+
+impl Builder {
+    /// Returns whether the innermost container uses flow style.
+    fn top_is_flow(&self) -> bool {
+        self.stack.last().is_some_and(|frame| frame.flow)
+    }
+
+    /// Gets the path of the next node if it is a value (rather than a key).
+    fn next_path(&self) -> Option<Path> {
+        match self.stack.last() {
+            None => Some(Path::default()),
+            Some(frame) if frame.mapping => frame
+                .key
+                .as_ref()
+                .map(|(key, _)| frame.path.join(Segment::Key(key.clone()))),
+            Some(frame) => Some(frame.path.join(Segment::Index(frame.items))),
+        }
+    }
+
+    /// Records the start of a node within the innermost container.
+    fn begin(&mut self, start: usize, column: usize) {
+        if let Some(frame) = self.stack.last_mut() {
+            if frame.start.is_none() {
+                frame.start = Some(start);
+                frame.column = column;
+            }
+        }
+    }
+
+    /// Records a completed node within the innermost container.
+    fn complete(&mut self, key: Option<&str>, span: Range<usize>, column: usize) {
+        let path = self.next_path();
+
+        let Some(frame) = self.stack.last_mut() else {
+            self.nodes.push(Node {
+                path: Path::default(),
+                key: None,
+                span,
+                column,
+            });
+            return;
+        };
+
+        frame.end = span.end;
+
+        match path {
+            Some(path) => {
+                let key = if frame.mapping {
+                    frame.key.take().map(|(_, span)| span)
+                } else {
+                    frame.items += 1;
+                    None
+                };
+
+                self.nodes.push(Node {
+                    path,
+                    key,
+                    span,
+                    column,
+                });
+            }
+            None => frame.key = Some((key.unwrap_or_default().to_string(), span)),
+        }
+    }
+
+    /// Opens a container.
+    fn open(&mut self, mapping: bool, flow: bool, start: Option<(usize, usize)>) {
+        if let Some((start, column)) = start {
+            self.begin(start, column);
+        }
+
+        self.stack.push(Frame {
+            path: self.next_path().unwrap_or_default(),
+            mapping,
+            flow,
+            start: start.map(|(start, _)| start),
+            column: start.map(|(_, column)| column).unwrap_or_default(),
+            end: 0,
+            key: None,
+            items: 0,
+        });
+    }
+
+    /// Closes the innermost container.
+    fn close(&mut self, end: usize) {
+        // SAFETY: the parser guarantees that every end event is preceded by a
+        // matching start event.
+        let frame = self.stack.pop().unwrap();
+        let start = frame.start.unwrap_or(end);
+        let end = if frame.flow {
+            end
+        } else {
+            frame.end.max(start)
+        };
+
+        self.begin(start, frame.column);
+        self.complete(None, start..end, frame.column);
+    }
+
+    /// Records a scalar.
+    fn leaf(&mut self, value: &str, span: Range<usize>, column: usize) {
+        self.begin(span.start, column);
+        self.complete(Some(value), span, column);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spans() {
+        let source = "state: draft\nnäme: \"X y\"\nvalues:\n  kind: categorical\n  options:\n    \
+                      - a\n    - {x: 1}\ndescription: |\n  foo\n  bar\n\nreferences: []\n";
+        let map = SpanMap::parse(source).unwrap();
+
+        let text = |path: &str| {
+            let node = map
+                .nodes()
+                .iter()
+                .find(|node| node.path.to_string() == path)
+                .unwrap();
+            &source[node.span.clone()]
+        };
+
+        assert_eq!(text("."), source.trim_end());
+        assert_eq!(text("state"), "draft");
+        assert_eq!(text("näme"), "\"X y\"");
+        assert_eq!(
+            text("values"),
+            "kind: categorical\n  options:\n    - a\n    - {x: 1}"
+        );
+        assert_eq!(text("values.options"), "- a\n    - {x: 1}");
+        assert_eq!(text("values.options[0]"), "a");
+        assert_eq!(text("values.options[1]"), "{x: 1}");
+        assert_eq!(text("values.options[1].x"), "1");
+        assert_eq!(text("description"), "foo\n  bar");
+        assert_eq!(text("references"), "[]");
+
+        let node = map.innermost(source.find("x: 1").unwrap()).unwrap();
+        assert_eq!(node.path.to_string(), "values.options[1].x");
+        assert_eq!(&source[node.key.clone().unwrap()], "x");
+    }
+}
@@ -0,0 +1,620 @@
+//! Validation of every schema violation within a document.
+//!
+//! Deserialization stops at the first problem. Instead, the document is
+//! parsed into a value tree and each node is checked against the schema on its
+//! own so that every violation within the file can be reported in one pass.
+
+use chrono::DateTime;
+use chrono::Utc;
+use ecc::Characteristic;
+use ecc::Identifier;
+use ecc::common;
+use ecc::common::Applicability;
+use ecc::common::EvidenceLevel;
+use ecc::common::Expression;
+use ecc::common::Iscn;
+use ecc::common::Mapping;
+use ecc::common::Molecular;
+use ecc::common::Reference;
+use ecc::common::Relation;
+use ecc::common::Sensitivity;
+use ecc::common::Steward;
+use ecc::common::Tag;
+use ecc::common::applicability;
+use ecc::common::mapping;
+use ecc::common::molecular;
+use ecc::common::relation;
+use ecc::common::steward;
+use ecc::common::value::Kind;
+use ecc::common::value::kind::binary;
+use ecc::common::value::kind::fraction;
+use ecc::common::value::kind::immunophenotype;
+use ecc::common::value::kind::numerical;
+use ecc::error::Code;
+use ecc::field;
+use ecc::io::normalize;
+use ecc::migrate::SchemaVersion;
+use ecc::rfc;
+use ecc::text::LanguageTag;
+use ecc::text::Sentence;
+use ecc::text::language::Translations;
+use nonempty::NonEmpty;
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use url::Url;
+
+use crate::Diagnostic;
+use crate::span::Path;
+use crate::span::Segment;
+use crate::span::SpanMap;
+
+/// The fields shared by characteristics in every state.
+const COMMON: &[&str] = &[
+    "schema_version",
+    "name",
+    "translations",
+    "identifier",
+    "rfc",
+    "description",
+    "values",
+    "references",
+    "evidence_level",
+    "sensitivity",
+    "tags",
+    "relations",
+    "stewards",
+    "mappings",
+    "molecular",
+    "cytogenetics",
+    "definition",
+    "applicability",
+];
+
+/// A function that validates the node at a path.
+type Check<'a> = fn(&mut Validator<'a>, &Path, &Value);
+
+/// Validates a document.
+struct Validator<'a> {
+    /// The spans of the nodes within the document.
+    map: &'a SpanMap,
+
+    /// The problems found so far.
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> Validator<'a> {
+    /// Reports a problem with the node at a path.
+    fn report(&mut self, path: &Path, message: impl Into<String>) {
+        self.report_with_code(path, message, Code::for_field(path));
+    }
+
+    /// Reports a problem with the node at a path with a specific code.
+    fn report_with_code(&mut self, path: &Path, message: impl Into<String>, code: Code) {
+        let node = if path.is_root() {
+            // NOTE: problems with the document as a whole are attributed to
+            // the state, as it determines the required fields.
+            self.map
+                .get(&key(path, "state"))
+                .or_else(|| self.map.get(path))
+        } else {
+            self.map.get(path)
+        };
+
+        let diagnostic = Diagnostic::error(message, node.map(|node| node.span.clone()))
+            .with_path(path.clone())
+            .with_code(code);
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Checks that a value deserializes into a type.
+    fn leaf<T: DeserializeOwned>(&mut self, path: &Path, value: &Value) {
+        if let Err(err) = serde_yaml::from_value::<T>(value.clone()) {
+            self.report(path, normalize(&err.to_string()));
+        }
+    }
+
+    /// Checks the fields of a mapping.
+    ///
+    /// Fields are only checked if they are present. Missing fields are reported
+    /// if they are required.
+    fn fields(&mut self, path: &Path, value: &Value, fields: &[(&str, bool, Check<'a>)]) {
+        for (name, required, check) in fields {
+            match value.get(*name) {
+                // NOTE: optional fields may be explicitly set to null.
+                Some(Value::Null) if !required => {}
+                Some(field) => check(self, &key(path, name), field),
+                None if *required => self.report(path, format!("missing field `{name}`")),
+                None => {}
+            }
+        }
+    }
+
+    /// Validates a characteristic.
+    fn characteristic(&mut self, value: &Value) {
+        let root = Path::default();
+
+        let Some(mapping) = value.as_mapping() else {
+            return self.leaf::<Characteristic>(&root, value);
+        };
+
+        let state = match value.get("state") {
+            Some(Value::String(state))
+                if matches!(
+                    state.as_str(),
+                    "draft" | "proposed" | "provisional" | "adopted"
+                ) =>
+            {
+                state.as_str()
+            }
+            Some(_) => return self.leaf::<Characteristic>(&key(&root, "state"), value),
+            None => return self.report(&root, "missing field `state`"),
+        };
+
+        let mut allowed = vec!["state"];
+        allowed.extend(COMMON);
+
+        match state {
+            "draft" => allowed.extend(["created_at", "updated_at"]),
+            "adopted" => allowed.push("adoption_date"),
+            _ => {}
+        }
+
+        for field in mapping.keys() {
+            let name = match field {
+                Value::String(name) => name.as_str(),
+                _ => continue,
+            };
+
+            if !allowed.contains(&name) {
+                let expected = allowed
+                    .iter()
+                    .map(|field| format!("`{field}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                self.report(
+                    &key(&root, name),
+                    format!("unknown field `{name}`, expected one of {expected}"),
+                );
+            }
+        }
+
+        let required = state != "draft";
+        self.fields(
+            &root,
+            value,
+            &[
+                ("schema_version", false, Self::leaf::<SchemaVersion>),
+                ("name", required, Self::leaf::<String>),
+                ("translations", false, Self::translations),
+                ("identifier", required, Self::leaf::<Identifier>),
+                ("rfc", required, Self::leaf::<rfc::Link>),
+                ("description", required, Self::leaf::<String>),
+                ("values", required, Self::kind),
+                ("references", false, Self::references),
+                ("evidence_level", false, Self::leaf::<EvidenceLevel>),
+                ("sensitivity", false, Self::leaf::<Sensitivity>),
+                ("tags", false, Self::tags),
+                ("relations", false, Self::relations),
+                ("stewards", false, Self::stewards),
+                ("mappings", false, Self::mappings),
+                ("molecular", false, Self::molecular),
+                ("cytogenetics", false, Self::cytogenetics),
+                ("definition", false, Self::leaf::<Expression>),
+                ("applicability", false, Self::applicability),
+                ("created_at", false, Self::leaf::<DateTime<Utc>>),
+                ("updated_at", false, Self::leaf::<DateTime<Utc>>),
+                (
+                    "adoption_date",
+                    state == "adopted",
+                    Self::leaf::<DateTime<Utc>>,
+                ),
+            ],
+        );
+
+        // NOTE: only drafts can use values of a kind that the schema does not
+        // model.
+        if required
+            && value
+                .get("values")
+                .and_then(|values| values.get("kind"))
+                .and_then(Value::as_str)
+                == Some("other")
+        {
+            self.report(
+                &key(&key(&root, "values"), "kind"),
+                common::Error::OtherKind.to_string(),
+            );
+        }
+    }
+
+    /// Validates the translations of the name.
+    fn translations(&mut self, path: &Path, value: &Value) {
+        let Some(mapping) = value.as_mapping() else {
+            return self.leaf::<Translations>(path, value);
+        };
+
+        for (language, name) in mapping {
+            let Some(language) = language.as_str() else {
+                self.report(path, "the languages of translations must be strings");
+                continue;
+            };
+
+            let path = key(path, language);
+
+            if let Err(err) = language.parse::<LanguageTag>() {
+                self.report_with_code(&path, err.to_string(), Code::LANGUAGE);
+            }
+
+            self.leaf::<String>(&path, name);
+        }
+    }
+
+    /// Validates the tags.
+    fn tags(&mut self, path: &Path, value: &Value) {
+        let Some(tags) = value.as_sequence() else {
+            return self.leaf::<Vec<Tag>>(path, value);
+        };
+
+        for (i, tag) in tags.iter().enumerate() {
+            self.leaf::<Tag>(&index(path, i), tag);
+        }
+    }
+
+    /// Validates the relations to other characteristics.
+    fn relations(&mut self, path: &Path, value: &Value) {
+        let Some(relations) = value.as_sequence() else {
+            return self.leaf::<Vec<Relation>>(path, value);
+        };
+
+        for (i, relation) in relations.iter().enumerate() {
+            let path = index(path, i);
+
+            if !relation.is_mapping() {
+                self.leaf::<Relation>(&path, relation);
+                continue;
+            }
+
+            self.fields(
+                &path,
+                relation,
+                &[
+                    ("kind", true, Self::leaf::<relation::Kind>),
+                    ("target", true, Self::leaf::<Identifier>),
+                ],
+            );
+        }
+    }
+
+    /// Validates the stewards.
+    fn stewards(&mut self, path: &Path, value: &Value) {
+        let stewards = match value.as_sequence() {
+            Some(stewards) if !stewards.is_empty() => stewards,
+            _ => return self.leaf::<NonEmpty<Steward>>(path, value),
+        };
+
+        for (i, steward) in stewards.iter().enumerate() {
+            let path = index(path, i);
+
+            if !steward.is_mapping() {
+                self.leaf::<Steward>(&path, steward);
+                continue;
+            }
+
+            self.fields(
+                &path,
+                steward,
+                &[
+                    ("name", true, Self::leaf::<String>),
+                    ("github", true, Self::leaf::<steward::Handle>),
+                    ("email", true, Self::leaf::<String>),
+                ],
+            );
+        }
+    }
+
+    /// Validates the mappings to terms within external terminologies.
+    fn mappings(&mut self, path: &Path, value: &Value) {
+        let Some(mappings) = value.as_sequence() else {
+            return self.leaf::<Vec<Mapping>>(path, value);
+        };
+
+        for (i, mapping) in mappings.iter().enumerate() {
+            let path = index(path, i);
+
+            if !mapping.is_mapping() {
+                self.leaf::<Mapping>(&path, mapping);
+                continue;
+            }
+
+            self.fields(
+                &path,
+                mapping,
+                &[
+                    ("predicate", true, Self::leaf::<mapping::Predicate>),
+                    ("term", true, Self::leaf::<mapping::Curie>),
+                    ("label", false, Self::leaf::<String>),
+                ],
+            );
+        }
+    }
+
+    /// Validates the genes and variants.
+    fn molecular(&mut self, path: &Path, value: &Value) {
+        if !value.is_mapping() {
+            return self.leaf::<Molecular>(path, value);
+        }
+
+        self.fields(
+            path,
+            value,
+            &[
+                ("genes", true, Self::genes),
+                ("variants", false, Self::variants),
+            ],
+        );
+    }
+
+    /// Validates the gene symbols.
+    fn genes(&mut self, path: &Path, value: &Value) {
+        let genes = match value.as_sequence() {
+            Some(genes) if !genes.is_empty() => genes,
+            _ => return self.leaf::<NonEmpty<molecular::Symbol>>(path, value),
+        };
+
+        for (i, gene) in genes.iter().enumerate() {
+            self.leaf::<molecular::Symbol>(&index(path, i), gene);
+        }
+    }
+
+    /// Validates the HGVS expressions of the variants.
+    fn variants(&mut self, path: &Path, value: &Value) {
+        let Some(variants) = value.as_sequence() else {
+            return self.leaf::<Vec<molecular::hgvs::Expression>>(path, value);
+        };
+
+        for (i, variant) in variants.iter().enumerate() {
+            self.leaf::<molecular::hgvs::Expression>(&index(path, i), variant);
+        }
+    }
+
+    /// Validates the samples that the characteristic applies to.
+    fn applicability(&mut self, path: &Path, value: &Value) {
+        if !value.is_mapping() {
+            return self.leaf::<Applicability>(path, value);
+        }
+
+        self.fields(
+            path,
+            value,
+            &[
+                ("nodes", false, Self::nodes),
+                ("specimen_types", false, Self::specimen_types),
+                ("age_groups", false, Self::age_groups),
+            ],
+        );
+    }
+
+    /// Validates the names of the ontology nodes.
+    fn nodes(&mut self, path: &Path, value: &Value) {
+        let Some(nodes) = value.as_sequence() else {
+            return self.leaf::<Vec<String>>(path, value);
+        };
+
+        for (i, node) in nodes.iter().enumerate() {
+            self.leaf::<String>(&index(path, i), node);
+        }
+    }
+
+    /// Validates the specimen types.
+    fn specimen_types(&mut self, path: &Path, value: &Value) {
+        let Some(specimen_types) = value.as_sequence() else {
+            return self.leaf::<Vec<applicability::SpecimenType>>(path, value);
+        };
+
+        for (i, specimen_type) in specimen_types.iter().enumerate() {
+            self.leaf::<applicability::SpecimenType>(&index(path, i), specimen_type);
+        }
+    }
+
+    /// Validates the age groups.
+    fn age_groups(&mut self, path: &Path, value: &Value) {
+        let Some(age_groups) = value.as_sequence() else {
+            return self.leaf::<Vec<applicability::AgeGroup>>(path, value);
+        };
+
+        for (i, age_group) in age_groups.iter().enumerate() {
+            self.leaf::<applicability::AgeGroup>(&index(path, i), age_group);
+        }
+    }
+
+    /// Validates the ISCN expressions of the cytogenetic abnormalities.
+    fn cytogenetics(&mut self, path: &Path, value: &Value) {
+        let Some(abnormalities) = value.as_sequence() else {
+            return self.leaf::<Vec<Iscn>>(path, value);
+        };
+
+        for (i, abnormality) in abnormalities.iter().enumerate() {
+            self.leaf::<Iscn>(&index(path, i), abnormality);
+        }
+    }
+
+    /// Validates the permissible values.
+    fn kind(&mut self, path: &Path, value: &Value) {
+        if !value.is_mapping() {
+            return self.leaf::<Kind>(path, value);
+        }
+
+        match value.get("kind").map(|kind| kind.as_str()) {
+            Some(Some("binary")) => self.fields(
+                path,
+                value,
+                &[
+                    ("description", true, Self::binary),
+                    ("threshold", false, Self::threshold),
+                ],
+            ),
+            Some(Some("categorical")) => {
+                self.fields(path, value, &[("options", true, Self::options)])
+            }
+            Some(Some("fraction")) => self.fields(
+                path,
+                value,
+                &[
+                    ("denominator_description", true, Self::leaf::<String>),
+                    ("scale", true, Self::leaf::<fraction::Scale>),
+                    ("min", false, Self::leaf::<f64>),
+                    ("max", false, Self::leaf::<f64>),
+                ],
+            ),
+            Some(Some("immunophenotype")) => {
+                self.fields(path, value, &[("markers", true, Self::markers)])
+            }
+            Some(Some("numerical")) => self.fields(
+                path,
+                value,
+                &[
+                    ("type", true, Self::leaf::<numerical::Type>),
+                    ("units", true, Self::leaf::<String>),
+                ],
+            ),
+            Some(Some("other")) => {
+                self.fields(path, value, &[("description", true, Self::leaf::<String>)])
+            }
+            Some(_) => self.leaf::<Kind>(&key(path, "kind"), value),
+            None => self.report(path, "missing field `kind`"),
+        }
+    }
+
+    /// Validates the description of a binary feature.
+    fn binary(&mut self, path: &Path, value: &Value) {
+        if !value.is_mapping() {
+            return self.leaf::<binary::Description>(path, value);
+        }
+
+        self.fields(
+            path,
+            value,
+            &[("true", true, Self::field), ("false", true, Self::field)],
+        );
+    }
+
+    /// Validates the threshold that a binary feature is derived from.
+    fn threshold(&mut self, path: &Path, value: &Value) {
+        if !value.is_mapping() {
+            return self.leaf::<binary::Threshold>(path, value);
+        }
+
+        self.fields(
+            path,
+            value,
+            &[
+                ("source", true, Self::leaf::<Identifier>),
+                ("comparator", true, Self::leaf::<binary::Comparator>),
+                ("value", true, Self::leaf::<f64>),
+                ("units", true, Self::leaf::<String>),
+            ],
+        );
+    }
+
+    /// Validates a field description.
+    fn field(&mut self, path: &Path, value: &Value) {
+        if !value.is_mapping() {
+            return self.leaf::<field::Description>(path, value);
+        }
+
+        self.fields(
+            path,
+            value,
+            &[
+                ("summary", true, Self::leaf::<Sentence>),
+                ("details", true, Self::leaf::<Sentence>),
+            ],
+        );
+    }
+
+    /// Validates the options of a categorical feature.
+    fn options(&mut self, path: &Path, value: &Value) {
+        let Some(options) = value.as_sequence() else {
+            return self.leaf::<Vec<String>>(path, value);
+        };
+
+        for (i, option) in options.iter().enumerate() {
+            self.leaf::<String>(&index(path, i), option);
+        }
+    }
+
+    /// Validates the markers of an immunophenotypic feature.
+    fn markers(&mut self, path: &Path, value: &Value) {
+        let Some(markers) = value.as_sequence() else {
+            return self.leaf::<Vec<immunophenotype::Marker>>(path, value);
+        };
+
+        for (i, marker) in markers.iter().enumerate() {
+            self.leaf::<immunophenotype::Marker>(&index(path, i), marker);
+        }
+    }
+
+    /// Validates the references.
+    fn references(&mut self, path: &Path, value: &Value) {
+        let references = match value.as_sequence() {
+            Some(references) if !references.is_empty() => references,
+            _ => return self.leaf::<NonEmpty<Reference>>(path, value),
+        };
+
+        for (i, reference) in references.iter().enumerate() {
+            self.reference(&index(path, i), reference);
+        }
+    }
+
+    /// Validates a reference.
+    fn reference(&mut self, path: &Path, value: &Value) {
+        if !value.is_mapping() {
+            return self.leaf::<Reference>(path, value);
+        }
+
+        match value.get("kind").map(|kind| kind.as_str()) {
+            Some(Some("manuscript" | "preprint")) => self.fields(
+                path,
+                value,
+                &[
+                    ("title", true, Self::leaf::<String>),
+                    ("authors", true, Self::leaf::<String>),
+                    ("context", true, Self::leaf::<Sentence>),
+                    ("url", true, Self::leaf::<Url>),
+                    ("highlighted", true, Self::leaf::<bool>),
+                ],
+            ),
+            Some(_) => self.leaf::<Reference>(&key(path, "kind"), value),
+            None => self.report(path, "missing field `kind`"),
+        }
+    }
+}
+
+/// Returns the path to a field within a mapping.
+fn key(path: &Path, name: &str) -> Path {
+    path.join(Segment::Key(name.to_string()))
+}
+
+/// Returns the path to an item within a sequence.
+fn index(path: &Path, index: usize) -> Path {
+    path.join(Segment::Index(index))
+}
+
+/// Reports every schema violation within a document.
+///
+/// The diagnostics are ordered by their position within the document.
+pub(crate) fn validate(source: &str, map: &SpanMap) -> Vec<Diagnostic> {
+    let Ok(document) = serde_yaml::from_str::<Value>(source) else {
+        return Vec::new();
+    };
+
+    let mut validator = Validator {
+        map,
+        diagnostics: Vec::new(),
+    };
+    validator.characteristic(&document);
+
+    let mut diagnostics = validator.diagnostics;
+    diagnostics.sort_by_key(|d| d.span.as_ref().map(|span| span.start));
+    diagnostics
+}
@@ -0,0 +1,185 @@
+//! JavaScript bindings for in-browser validation.
+//!
+//! The bindings run exactly the same checks as `ecc-cli check` on a YAML
+//! string so that the submission form can give feedback before a pull request
+//! is ever opened. Nothing here touches the filesystem, so the crate can be
+//! built for `wasm32-unknown-unknown` with the `wasm` feature:
+//!
+//! ```bash
+//! wasm-pack build crates/ecc-check --target web --features wasm
+//! ```
+
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+use crate::Diagnostic;
+use crate::FileReport;
+use crate::Options;
+use crate::Severity;
+
+/// The path reported for characteristics that are not read from a file.
+const SOURCE: &str = "<input>";
+
+/// A position within the source (both one-based).
+#[derive(Debug, PartialEq, Eq, Serialize)]
+struct Position {
+    /// The line.
+    line: usize,
+
+    /// The column (in characters).
+    column: usize,
+}
+
+impl Position {
+    /// Gets the position of a byte offset within the source.
+    fn new(source: &str, offset: usize) -> Self {
+        // NOTE: spans always fall on character boundaries.
+        let before = &source[..offset.min(source.len())];
+        let line = before.matches('\n').count() + 1;
+        let start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        Self {
+            line,
+            column: before[start..].chars().count() + 1,
+        }
+    }
+}
+
+/// A problem found within the characteristic.
+#[derive(Debug, Serialize)]
+struct Problem {
+    /// The severity (`error` or `warning`).
+    severity: &'static str,
+
+    /// A description of the problem.
+    message: String,
+
+    /// The path to the field that the problem relates to (if known).
+    field: Option<String>,
+
+    /// The code for the kind of problem (e.g., `E0002`) if known.
+    code: Option<String>,
+
+    /// The name of the lint that reported the problem (if any).
+    lint: Option<&'static str>,
+
+    /// The start of the problem within the source (if known).
+    start: Option<Position>,
+
+    /// The end of the problem within the source (if known).
+    end: Option<Position>,
+}
+
+impl Problem {
+    /// Converts a diagnostic into a problem.
+    fn new(source: &str, diagnostic: &Diagnostic) -> Self {
+        Self {
+            severity: match diagnostic.severity {
+                Severity::Error => "error",
+                Severity::Warning => "warning",
+            },
+            message: diagnostic.message.clone(),
+            field: diagnostic.path.as_ref().map(|path| path.to_string()),
+            code: diagnostic.code.map(|code| code.to_string()),
+            lint: diagnostic.lint,
+            start: diagnostic
+                .span
+                .as_ref()
+                .map(|span| Position::new(source, span.start)),
+            end: diagnostic
+                .span
+                .as_ref()
+                .map(|span| Position::new(source, span.end)),
+        }
+    }
+}
+
+/// The result of parsing a characteristic.
+#[derive(Debug, Serialize)]
+struct Outcome {
+    /// Whether the characteristic is valid.
+    valid: bool,
+
+    /// The parsed characteristic (if it is valid).
+    characteristic: Option<ecc::Characteristic>,
+
+    /// The problems found.
+    problems: Vec<Problem>,
+}
+
+impl From<FileReport> for Outcome {
+    fn from(report: FileReport) -> Self {
+        Self {
+            valid: report.passed(),
+            problems: report
+                .diagnostics
+                .iter()
+                .map(|diagnostic| Problem::new(&report.source, diagnostic))
+                .collect(),
+            characteristic: report.characteristic,
+        }
+    }
+}
+
+/// Parses and checks a characteristic.
+fn outcome(yaml: &str, all_errors: bool) -> Outcome {
+    let options = Options {
+        all_errors,
+        ..Default::default()
+    };
+
+    Outcome::from(crate::check_str(SOURCE, yaml, &options))
+}
+
+/// Parses and checks a characteristic written in YAML.
+///
+/// Returns an object with the `valid`, `characteristic`, and `problems` keys.
+/// Each problem has the `severity`, `message`, `field`, `lint`, `start`, and
+/// `end` keys (positions have one-based `line` and `column` keys). If
+/// `all_errors` is set, every problem is reported rather than only the first.
+#[wasm_bindgen]
+pub fn parse_characteristic(yaml: &str, all_errors: Option<bool>) -> Result<JsValue, JsError> {
+    let outcome = outcome(yaml, all_errors.unwrap_or_default());
+    serde_wasm_bindgen::to_value(&outcome).map_err(|err| JsError::new(&err.to_string()))
+}
+
+/// Validates a characteristic name.
+///
+/// Returns a description of the problem or `undefined` if the name is valid.
+#[wasm_bindgen]
+pub fn validate_name(name: &str) -> Option<String> {
+    ecc::common::validate_name(name)
+        .err()
+        .map(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outcomes() {
+        let result = outcome("state: draft\nname: A Characteristic Name\n", false);
+        assert!(result.valid);
+        assert!(result.characteristic.is_some());
+
+        let result = outcome(
+            "state: draft\nname: A Characteristic Name\nfoo: bar\n",
+            false,
+        );
+        assert!(!result.valid);
+        assert_eq!(result.problems.len(), 1);
+        assert_eq!(result.problems[0].field.as_deref(), Some("foo"));
+        assert_eq!(
+            result.problems[0].start,
+            Some(Position { line: 3, column: 6 })
+        );
+    }
+
+    #[test]
+    fn names() {
+        assert!(validate_name("A Characteristic Name").is_none());
+        assert!(validate_name(" A Characteristic Name").is_some());
+        assert!(validate_name("").is_some());
+    }
+}
@@ -0,0 +1,319 @@
+//! Building of the publishable data bundle.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::Parser;
+use ecc::Characteristic;
+use ecc::State;
+use ecc::canonical;
+use ecc::common::Sensitivity;
+use ecc::common::Tag;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::info;
+
+use crate::config::Config;
+use crate::render;
+use crate::search::Index;
+use crate::tree;
+
+/// The directory within the bundle where characteristic pages are written.
+const CHARACTERISTICS_DIR: &str = "characteristics";
+
+/// Builds the publishable data bundle.
+///
+/// The bundle contains a JSON document and a Markdown page for each
+/// characteristic, an index with search metadata, a ranked search index (see
+/// [`crate::search`]), an export of the ontology (if provided) as both JSON and
+/// a compact binary artifact (see [`ontology::binary`]), and a manifest listing
+/// every file with its checksum.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml` (if any).
+    #[arg(long)]
+    ontology: Option<PathBuf>,
+
+    /// The version of the data release.
+    #[arg(long)]
+    data_version: Option<String>,
+
+    /// Whether to include characteristics in the `draft` state.
+    #[arg(long)]
+    include_drafts: bool,
+
+    /// The most restricted sensitivity to include in the bundle (`public`,
+    /// `controlled`, or `restricted`).
+    ///
+    /// Characteristics that are more restricted are left out of the bundle.
+    /// Defaults to the sensitivity configured in `ecc.toml` (or `public`).
+    #[arg(long)]
+    max_sensitivity: Option<Sensitivity>,
+
+    /// The directory to write the bundle to.
+    #[arg(short, long)]
+    output_directory: PathBuf,
+}
+
+/// An entry within the bundle index.
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    /// The identifier (if assigned).
+    identifier: Option<String>,
+
+    /// The name (if assigned).
+    name: Option<&'a str>,
+
+    /// The state.
+    state: State,
+
+    /// The kind of values.
+    kind: Option<&'static str>,
+
+    /// The tags (for faceting).
+    tags: &'a [Tag],
+
+    /// The adoption date (if adopted).
+    adoption_date: Option<String>,
+
+    /// The content hash of the characteristic.
+    content_hash: String,
+
+    /// The lowercased words within the name for searching.
+    keywords: Vec<String>,
+
+    /// The path to the JSON document relative to the bundle root.
+    json: String,
+
+    /// The path to the Markdown page relative to the bundle root.
+    markdown: String,
+}
+
+/// A file within the bundle manifest.
+#[derive(Deserialize, Serialize)]
+pub(crate) struct ManifestFile {
+    /// The path relative to the bundle root.
+    pub(crate) path: String,
+
+    /// The size of the file in bytes.
+    pub(crate) bytes: usize,
+
+    /// The SHA-256 checksum of the file (hex encoded).
+    pub(crate) sha256: String,
+
+    /// The content hash of the characteristic within the file (for
+    /// characteristic JSON documents).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) content_hash: Option<String>,
+}
+
+/// The name of the manifest within a bundle.
+pub(crate) const MANIFEST: &str = "manifest.json";
+
+/// The manifest for a bundle.
+#[derive(Serialize)]
+struct Manifest {
+    /// The version of the data release.
+    version: Option<String>,
+
+    /// The version of the tool that built the bundle.
+    tool_version: &'static str,
+
+    /// The files within the bundle.
+    files: Vec<ManifestFile>,
+}
+
+/// A bundle that is being written to disk.
+struct Bundle {
+    /// The root directory of the bundle.
+    root: PathBuf,
+
+    /// The files that have been written so far.
+    files: Vec<ManifestFile>,
+}
+
+impl Bundle {
+    /// Writes a file into the bundle and records it in the manifest.
+    fn write(&mut self, path: &str, contents: impl AsRef<[u8]>) -> anyhow::Result<()> {
+        let contents = contents.as_ref();
+        let full = self.root.join(path);
+
+        // SAFETY: the path is always joined onto the bundle root, so it will
+        // always have a parent.
+        std::fs::create_dir_all(full.parent().unwrap())
+            .with_context(|| format!("creating directory for {}", full.display()))?;
+        std::fs::write(&full, contents).with_context(|| format!("writing {}", full.display()))?;
+
+        self.files.push(ManifestFile {
+            path: path.to_string(),
+            bytes: contents.len(),
+            sha256: canonical::sha256(contents),
+            content_hash: None,
+        });
+
+        Ok(())
+    }
+
+    /// Writes a characteristic's JSON document into the bundle and records it
+    /// (along with its content hash) in the manifest.
+    fn write_characteristic(
+        &mut self,
+        path: &str,
+        characteristic: &Characteristic,
+    ) -> anyhow::Result<()> {
+        self.write(path, serde_json::to_vec_pretty(characteristic)?)?;
+
+        // SAFETY: a file was just recorded.
+        self.files.last_mut().unwrap().content_hash = Some(characteristic.content_hash());
+
+        Ok(())
+    }
+}
+
+/// Gets the file stem for a characteristic's pages.
+///
+/// This is the identifier when one has been assigned. Otherwise, it is the
+/// path of the source file relative to the characteristic directory (without
+/// its extension and with its directories joined by hyphens), so that drafts
+/// with the same file name in different categories get different pages (e.g.,
+/// `morph-foo` and `molec-foo`).
+fn stem(path: &Path, root: &Path, characteristic: &Characteristic) -> String {
+    match characteristic.identifier() {
+        Some(identifier) => identifier.to_string(),
+        None => tree::relative(&path.with_extension(""), root).replace('/', "-"),
+    }
+}
+
+/// Writes a bundle containing the provided characteristics to a directory.
+///
+/// The characteristics are expected to have been loaded from the `source`
+/// directory. The ontology is exported into the bundle when a directory is
+/// provided.
+pub fn write(
+    source: &Path,
+    characteristics: &[(PathBuf, Characteristic)],
+    ontology: Option<&Path>,
+    version: Option<String>,
+    root: PathBuf,
+) -> anyhow::Result<()> {
+    let mut bundle = Bundle {
+        root,
+        files: Vec::new(),
+    };
+    let mut index = Vec::new();
+    let mut stems = HashMap::new();
+
+    for (path, characteristic) in characteristics {
+        let stem = stem(path, source, characteristic);
+
+        if let Some(other) = stems.insert(stem.clone(), path) {
+            bail!(
+                "`{}` and `{}` would both be written to the `{stem}` pages",
+                other.display(),
+                path.display()
+            );
+        }
+
+        let json = format!("{CHARACTERISTICS_DIR}/{stem}.json");
+        let markdown = format!("{CHARACTERISTICS_DIR}/{stem}.md");
+
+        bundle.write_characteristic(&json, characteristic)?;
+        bundle.write(&markdown, render::markdown::render(characteristic))?;
+
+        index.push(IndexEntry {
+            identifier: characteristic.identifier().map(|i| i.to_string()),
+            name: characteristic.name(),
+            state: characteristic.state(),
+            kind: characteristic.values().map(|kind| kind.name()),
+            tags: characteristic.tags(),
+            adoption_date: characteristic.adoption_date().map(|d| d.to_rfc3339()),
+            content_hash: characteristic.content_hash(),
+            keywords: characteristic
+                .name()
+                .map(|name| {
+                    name.split_whitespace()
+                        .map(|word| word.to_lowercase())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            json,
+            markdown,
+        });
+    }
+
+    bundle.write("index.json", serde_json::to_vec_pretty(&index)?)?;
+
+    let nodes = match ontology {
+        Some(ontology) => {
+            // NOTE: the nodes are written depth first (with siblings in
+            // order) so that the bundle reads like the hierarchy.
+            let (graph, _) = tree::load_ontology_graph(ontology)?;
+            let nodes = graph.preorder().into_iter().cloned().collect::<Vec<_>>();
+            bundle.write("ontology.json", serde_json::to_vec_pretty(&nodes)?)?;
+            bundle.write("ontology.bin", graph.to_bytes())?;
+            nodes
+        }
+        None => Vec::new(),
+    };
+
+    // NOTE: the positions of the documents within the search index line up
+    // with the entries of `index.json` and `ontology.json`.
+    let search = Index::build(
+        &characteristics
+            .iter()
+            .map(|(_, characteristic)| characteristic.clone())
+            .collect::<Vec<_>>(),
+        &nodes,
+    );
+    bundle.write("search.json", serde_json::to_vec(&search)?)?;
+
+    let manifest = Manifest {
+        version,
+        tool_version: env!("CARGO_PKG_VERSION"),
+        files: bundle.files,
+    };
+
+    let path = bundle.root.join(MANIFEST);
+    std::fs::write(&path, serde_json::to_vec_pretty(&manifest)?)
+        .with_context(|| format!("writing {}", path.display()))?;
+
+    info!(
+        "built bundle with {} characteristics at `{}`",
+        characteristics.len(),
+        bundle.root.display()
+    );
+
+    Ok(())
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let source = config.characteristics(args.path);
+    let mut characteristics = tree::load(&source)?
+        .into_iter()
+        .filter(|(_, c)| args.include_drafts || !matches!(c, Characteristic::Draft { .. }))
+        .collect::<Vec<_>>();
+    tree::withhold(
+        &mut characteristics,
+        args.max_sensitivity
+            .unwrap_or(config.export.max_sensitivity),
+    );
+
+    write(
+        &source,
+        &characteristics,
+        config.ontology(args.ontology).as_deref(),
+        args.data_version,
+        args.output_directory,
+    )
+}
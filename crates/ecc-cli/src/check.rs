@@ -1,8 +1,13 @@
 //! Checking of a composable characteristic tree.
 
+use std::collections::BTreeSet;
 use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
+use std::sync::mpsc;
+use std::time::Duration;
 
+use anyhow::Context;
 use clap::Parser;
 use codespan_reporting::diagnostic::Diagnostic;
 use codespan_reporting::diagnostic::Label;
@@ -12,64 +17,736 @@ use codespan_reporting::term::termcolor::ColorChoice;
 use codespan_reporting::term::termcolor::StandardStream;
 use colored::Colorize as _;
 use ecc::Characteristic;
+use ecc::CharacteristicSet;
+use ecc_check::lint::DEFINITION_REFERENCE;
+use ecc_check::lint::DEFINITION_TYPE;
+use notify::RecursiveMode;
+use notify::Watcher as _;
 use tracing::info;
 
+use crate::Error;
+use crate::config::Config;
+use crate::config::SIMILAR_NAMES;
+use crate::config::Severity;
+use crate::net;
+use crate::tree;
+
+mod cache;
+mod links;
+
 /// Checks that a composable characteristic tree is valid.
+///
+/// Once every file is checked, characteristic names that are near misses of
+/// one another (e.g., names that differ only by punctuation) are reported by
+/// the `similar-names` lint, and definitions are checked against the
+/// characteristics that they refer to by the `definition-reference` and
+/// `definition-type` lints.
+///
+/// The exit code is `1` when any file fails validation (or there are more
+/// warnings than `--max-warnings` allows) and `2` when the check itself could
+/// not be completed (e.g., a file could not be read).
 #[derive(Parser)]
 pub struct Args {
-    /// The path to the composable characteristic directory.
-    path: PathBuf,
+    /// The characteristic files or directories to check.
+    ///
+    /// Directories are searched recursively for `.yml` and `.yaml` files.
+    /// Defaults to the directory configured in `ecc.toml`.
+    paths: Vec<PathBuf>,
+
+    /// Only checks files matching the glob (may be repeated).
+    ///
+    /// Globs are matched against both the full path and the path relative to
+    /// the directory being searched.
+    #[arg(long, value_name = "GLOB")]
+    include: Vec<glob::Pattern>,
+
+    /// Skips files matching the glob (may be repeated).
+    ///
+    /// Globs are matched against both the full path and the path relative to
+    /// the directory being searched.
+    #[arg(long, value_name = "GLOB")]
+    exclude: Vec<glob::Pattern>,
+
+    /// Reports every schema violation within each file rather than stopping
+    /// at the first.
+    #[arg(long)]
+    all_errors: bool,
+
+    /// Watches the directory and re-checks files as they change.
+    #[arg(long)]
+    watch: bool,
+
+    /// The number of milliseconds to wait for further changes before
+    /// re-checking files in watch mode.
+    #[arg(long, default_value_t = 200, requires = "watch")]
+    debounce: u64,
+
+    /// Clears the screen before re-checking files in watch mode.
+    #[arg(long, requires = "watch")]
+    clear: bool,
+
+    /// Verifies that every RFC and reference link resolves.
+    ///
+    /// This requires network access. Results are cached between runs.
+    #[arg(long, conflicts_with = "watch")]
+    verify_links: bool,
+
+    /// Skips the checks that require network access.
+    ///
+    /// With `--verify-links`, only links within the cache are verified and
+    /// the rest are skipped rather than failing as unreachable.
+    #[arg(long)]
+    offline: bool,
+
+    /// The path to the link verification cache.
+    ///
+    /// Defaults to the cache configured in `ecc.toml`.
+    #[arg(long, requires = "verify_links")]
+    link_cache: Option<PathBuf>,
+
+    /// Re-checks every file rather than skipping files that passed a previous
+    /// check and have not changed since.
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Fails the check if more than this number of warnings are reported.
+    #[arg(long, value_name = "N")]
+    max_warnings: Option<usize>,
 }
 
-/// The main method.
-pub fn main(args: Args) -> anyhow::Result<()> {
-    let paths = format!("{}/**/*.yml", args.path.display());
-    info!("characteristic glob: `{paths}`");
+/// A tally of the results of a check.
+#[derive(Default)]
+struct Summary {
+    /// The number of files that passed.
+    passed: usize,
+
+    /// The number of files that failed.
+    failed: usize,
+
+    /// The number of warnings reported.
+    warnings: usize,
+}
+
+impl Summary {
+    /// Prints the summary line.
+    fn print(&self) {
+        let failed = format!("{} failed", self.failed);
+        let warnings = format!("{} warnings", self.warnings);
+
+        println!(
+            "\n{} {} passed, {}, {}",
+            "Summary:".bold(),
+            self.passed,
+            if self.failed > 0 {
+                failed.red()
+            } else {
+                failed.normal()
+            },
+            if self.warnings > 0 {
+                warnings.yellow()
+            } else {
+                warnings.normal()
+            }
+        );
+    }
+}
+
+/// The results of verifying links.
+#[derive(Default)]
+struct LinkReport {
+    /// The files containing links that failed verification.
+    failed: BTreeSet<PathBuf>,
+
+    /// The number of warnings reported.
+    warnings: usize,
+
+    /// The number of links that were not verified because the check was
+    /// offline.
+    skipped: usize,
+}
+
+/// The outcome of checking a single characteristic file.
+enum Outcome {
+    /// The file is valid.
+    Passed {
+        /// The characteristic.
+        ///
+        /// This is only parsed if the file was actually checked (i.e., it was
+        /// not found within the cache).
+        characteristic: Option<Box<Characteristic>>,
+
+        /// The number of warnings reported.
+        warnings: usize,
+    },
+
+    /// The file is invalid.
+    Failed,
+}
+
+/// Gets the settings that the results of a check depend on.
+///
+/// Files that passed are only cached with these settings, as changing them
+/// (e.g., denying a lint) can make a file fail.
+fn settings(config: &Config) -> anyhow::Result<String> {
+    let mut settings = ecc_check::lint::ALL
+        .iter()
+        .map(|lint| format!("{lint}={:?}", config.severity(lint)))
+        .collect::<Vec<_>>();
+
+    settings.push(format!(
+        "max-option-length={}",
+        config.check.max_option_length
+    ));
+    settings.push(format!(
+        "tags={}",
+        config
+            .tags
+            .vocabulary
+            .iter()
+            .map(|tag| tag.as_str())
+            .collect::<Vec<_>>()
+            .join(";")
+    ));
+    settings.push(format!(
+        "min-completeness={}",
+        config
+            .check
+            .min_completeness
+            .iter()
+            .map(|(state, min)| format!("{state}:{min}"))
+            .collect::<Vec<_>>()
+            .join(";")
+    ));
+
+    if let Some(min) = config.check.min_evidence_level {
+        settings.push(format!("min-evidence-level={min}"));
+    }
+
+    // NOTE: the list is hashed (rather than keyed on its path) so that
+    // editing it invalidates the files that were checked against it.
+    if let Some(path) = &config.check.gene_symbols {
+        let contents = tree::read(&config.resolve(path))?;
+        settings.push(format!("gene-symbols={}", cache::hash(contents.as_bytes())));
+    }
+
+    Ok(settings.join(","))
+}
+
+/// Prints the diagnostics within a report.
+fn emit(ecc_file: &Path, report: &ecc_check::FileReport) -> anyhow::Result<()> {
+    let file = SimpleFile::new(ecc_file.display().to_string(), report.source.as_str());
+    let writer = StandardStream::stdout(ColorChoice::Always);
+
+    let config = term::Config {
+        ..Default::default()
+    };
+
+    for diagnostic in &report.diagnostics {
+        let span = diagnostic
+            .span
+            .clone()
+            .unwrap_or(report.source.len()..report.source.len());
 
+        let d = match diagnostic.severity {
+            ecc_check::Severity::Error => Diagnostic::error(),
+            ecc_check::Severity::Warning => Diagnostic::warning(),
+        };
+
+        let d = match &diagnostic.path {
+            Some(path) => d.with_message(format!("`{path}`: {}", diagnostic.message)),
+            None => d,
+        };
+
+        let d = match diagnostic.code {
+            Some(code) => d.with_code(code.to_string()),
+            None => d,
+        };
+
+        let d = match diagnostic.lint {
+            Some(lint) => d.with_notes(vec![format!("the lint `{lint}` reported this problem")]),
+            None => d,
+        };
+
+        let d = d.with_labels(vec![
+            Label::primary((), span).with_message(diagnostic.message.as_str()),
+        ]);
+
+        term::emit(&mut writer.lock(), &config, &file, &d)?;
+    }
+
+    Ok(())
+}
+
+/// Checks the contents of a single characteristic file.
+///
+/// The severity of each problem reported by a lint is set by the
+/// configuration, and problems reported by allowed lints are dropped. This
+/// only fails if the configured HGNC symbol list cannot be read.
+pub fn lint(
+    ecc_file: &Path,
+    source: String,
+    all_errors: bool,
+    config: &Config,
+) -> anyhow::Result<ecc_check::FileReport> {
+    let options = ecc_check::Options {
+        all_errors,
+        max_option_length: config.check.max_option_length,
+        tags: config.tags.vocabulary.clone(),
+        min_completeness: config.check.min_completeness.clone(),
+        min_evidence_level: config.check.min_evidence_level,
+        gene_symbols: config.gene_symbols()?,
+        ..Default::default()
+    };
+    let mut report = ecc_check::check_str(ecc_file, source, &options);
+    configure(&mut report.diagnostics, config);
+
+    Ok(report)
+}
+
+/// Sets the severity of each problem reported by a lint from the
+/// configuration, dropping problems reported by allowed lints.
+fn configure(diagnostics: &mut Vec<ecc_check::Diagnostic>, config: &Config) {
+    diagnostics.retain_mut(|diagnostic| {
+        let Some(lint) = diagnostic.lint else {
+            return true;
+        };
+
+        diagnostic.severity = match config.severity(lint) {
+            Severity::Allow => return false,
+            Severity::Warn => ecc_check::Severity::Warning,
+            Severity::Deny => ecc_check::Severity::Error,
+        };
+
+        true
+    });
+}
+
+/// Checks a single characteristic file and prints the result.
+///
+/// The severity of each problem reported by a lint is set by the
+/// configuration. If a cache is provided, files that passed a previous check
+/// and have not changed since are skipped, and files that pass without any
+/// warnings are added to it.
+fn check_file(
+    ecc_file: &Path,
+    all_errors: bool,
+    config: &Config,
+    cache: Option<&mut cache::Cache>,
+) -> anyhow::Result<Outcome> {
     let mut stdout = std::io::stdout();
-    let mut failed = false;
+    print!("{}.. ", ecc_file.display().to_string().bold());
+
+    let source = tree::read(ecc_file)?;
+    let hash = cache.as_ref().map(|_| cache::hash(source.as_bytes()));
+
+    if let (Some(cache), Some(hash)) = (&cache, &hash) {
+        if cache.passed(hash) {
+            println!("{} {}", "OK".green(), "(cached)".dimmed());
+            stdout.flush()?;
+            return Ok(Outcome::Passed {
+                characteristic: None,
+                warnings: 0,
+            });
+        }
+    }
+
+    let report = lint(ecc_file, source, all_errors, config)?;
+
+    if report.passed() {
+        let warnings = report.warnings().count();
+
+        if warnings == 0 {
+            if let (Some(cache), Some(hash)) = (cache, hash) {
+                cache.insert(hash);
+            }
+
+            println!("{}", "OK".green());
+        } else {
+            println!(
+                "{} {}\n",
+                "OK".green(),
+                format!("({warnings} warnings)").yellow()
+            );
+        }
+
+        stdout.flush()?;
+        emit(ecc_file, &report)?;
+
+        return Ok(Outcome::Passed {
+            characteristic: report.characteristic.map(Box::new),
+            warnings,
+        });
+    }
+
+    println!("{}\n", "FAIL".red());
+    stdout.flush()?;
+    emit(ecc_file, &report)?;
+
+    Ok(Outcome::Failed)
+}
+
+/// Watches a directory and re-checks characteristic files as they change.
+fn watch(args: &Args, paths: &[PathBuf], config: &Config) -> anyhow::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("creating file watcher")?;
+
+    for path in paths {
+        watcher
+            .watch(path, RecursiveMode::Recursive)
+            .with_context(|| format!("watching path: {}", path.display()))?;
+        info!("watching `{}` for changes", path.display());
+    }
+
+    let debounce = Duration::from_millis(args.debounce);
+
+    // NOTE: the loop ends when the watcher is dropped and the channel closes.
+    while let Ok(event) = rx.recv() {
+        let mut changed = BTreeSet::new();
+        let mut pending = Some(event);
+
+        // Gather all of the events that arrive within the debounce window so
+        // that a single save (which often emits several events) only triggers
+        // a single re-check.
+        while let Some(result) = pending {
+            // NOTE: reading a file during a check emits access events, so only
+            // events that could have changed the contents are considered.
+            let event = result
+                .ok()
+                .filter(|e| e.kind.is_create() || e.kind.is_modify());
+
+            if let Some(event) = event {
+                changed.extend(event.paths.into_iter().filter(|path| tree::is_yaml(path)));
+            }
+
+            pending = rx.recv_timeout(debounce).ok();
+        }
 
-    for result in glob::glob(&paths).expect("glob to resolve") {
-        let ecc_file = result.expect("file path to resolve");
-        print!("{}.. ", ecc_file.display().to_string().bold());
+        let changed = changed
+            .into_iter()
+            .filter(|path| path.is_file())
+            .collect::<Vec<_>>();
+        let changed = tree::select(&changed, &args.include, &args.exclude)?;
 
-        let contents = std::fs::read_to_string(&ecc_file).expect("file to be read");
+        if changed.is_empty() {
+            continue;
+        }
+
+        if args.clear {
+            print!("\x1B[2J\x1B[H");
+        }
 
-        match serde_yaml::from_str::<Characteristic>(&contents) {
-            Ok(_) => {
-                println!("{}", "OK".green());
-                stdout.flush().unwrap();
+        let mut summary = Summary::default();
+
+        for path in changed {
+            match check_file(&path, args.all_errors, config, None)? {
+                Outcome::Passed { warnings, .. } => {
+                    summary.passed += 1;
+                    summary.warnings += warnings;
+                }
+                Outcome::Failed => summary.failed += 1,
             }
-            Err(err) => {
-                failed = true;
+        }
 
-                println!("{}\n", "FAIL".red());
-                stdout.flush().unwrap();
+        summary.print();
+    }
 
-                let file = SimpleFile::new(ecc_file.display().to_string(), contents.clone());
+    Ok(())
+}
 
-                let index = match err.location() {
-                    Some(location) => location.index(),
-                    None => contents.len(),
-                };
+/// The results of a check across every characteristic.
+#[derive(Default)]
+struct SetReport {
+    /// The files that failed the check (i.e., a denied lint reported a
+    /// problem).
+    failed: BTreeSet<PathBuf>,
+
+    /// The number of warnings reported.
+    warnings: usize,
+}
+
+/// Reports the names of characteristics that are near misses of one another
+/// (see [`ecc::text::similar`]).
+fn similar_names(characteristics: &[(PathBuf, Characteristic)], config: &Config) -> SetReport {
+    let mut report = SetReport::default();
+
+    let named = characteristics
+        .iter()
+        .filter_map(|(path, characteristic)| characteristic.name().map(|name| (path, name)))
+        .collect::<Vec<_>>();
+    let names = named.iter().map(|(_, name)| *name).collect::<Vec<_>>();
+    let near_misses = ecc::text::similar::near_misses(&names, config.check.max_name_distance);
+
+    if near_misses.is_empty() {
+        return report;
+    }
+
+    println!("\n{}", "Comparing names..".bold());
+
+    for near_miss in near_misses {
+        let (first, second) = (named[near_miss.first].0, named[near_miss.second].0);
+
+        let status = match config.severity(SIMILAR_NAMES) {
+            Severity::Allow => continue,
+            Severity::Warn => {
+                report.warnings += 1;
+                "WARN".yellow()
+            }
+            Severity::Deny => {
+                report.failed.insert(first.clone());
+                report.failed.insert(second.clone());
+                "FAIL".red()
+            }
+        };
+
+        println!(
+            "{} and {}.. {status} ({})",
+            first.display(),
+            second.display(),
+            near_miss.describe(names[near_miss.first], names[near_miss.second])
+        );
+    }
+
+    report
+}
+
+/// Checks the definitions of the characteristics against the characteristics
+/// that they refer to (see [`ecc_check::lint::definitions()`]) and prints any
+/// problems.
+///
+/// References are resolved against the characteristics being checked.
+fn definitions(
+    characteristics: &[(PathBuf, Characteristic)],
+    config: &Config,
+) -> anyhow::Result<SetReport> {
+    let mut report = SetReport::default();
+
+    let defined = characteristics
+        .iter()
+        .filter(|(_, characteristic)| characteristic.definition().is_some())
+        .collect::<Vec<_>>();
+
+    if defined.is_empty() {
+        return Ok(report);
+    }
+
+    let set = characteristics
+        .iter()
+        .map(|(_, characteristic)| characteristic.clone())
+        .collect::<CharacteristicSet>();
+
+    println!("\n{}", "Checking definitions..".bold());
+
+    for (path, characteristic) in defined {
+        let source = tree::read(path)?;
+        let mut diagnostics = ecc_check::lint::definitions(&source, characteristic, &set);
+        configure(&mut diagnostics, config);
+
+        if diagnostics.is_empty() {
+            continue;
+        }
+
+        let file = ecc_check::FileReport {
+            path: path.clone(),
+            source,
+            characteristic: None,
+            diagnostics,
+        };
+
+        let status = if file.passed() {
+            report.warnings += file.warnings().count();
+            "WARN".yellow()
+        } else {
+            report.failed.insert(path.clone());
+            "FAIL".red()
+        };
+
+        println!("{}.. {status}\n", path.display().to_string().bold());
+        emit(path, &file)?;
+    }
+
+    Ok(report)
+}
 
-                let diagnostic = Diagnostic::error().with_labels(vec![
-                    Label::primary((), index..index).with_message(err.to_string()),
-                ]);
+/// Verifies the links within the characteristics and prints any problems.
+async fn verify_links(
+    characteristics: &[(PathBuf, Characteristic)],
+    cache: &Path,
+    offline: bool,
+    config: &Config,
+) -> anyhow::Result<LinkReport> {
+    let client = net::client(
+        config.links.timeout(),
+        net::Credentials::from_env(),
+        net::Mode::from_env(offline)?,
+    );
+    let mut verifier = links::Verifier::new(client, cache, &config.links);
+    let mut report = LinkReport::default();
 
-                let writer = StandardStream::stdout(ColorChoice::Always);
+    println!("\n{}", "Verifying links..".bold());
 
-                let config = term::Config {
-                    ..Default::default()
-                };
+    for (path, characteristic) in characteristics {
+        for url in links::links(characteristic) {
+            let outcome = verifier.verify(&url).await;
 
-                term::emit(&mut writer.lock(), &config, &file, &diagnostic)?;
+            if matches!(outcome, links::Outcome::Skipped) {
+                report.skipped += 1;
+                continue;
             }
+
+            let Some(lint) = outcome.lint() else {
+                continue;
+            };
+
+            let status = match config.severity(lint) {
+                Severity::Allow => continue,
+                Severity::Warn => {
+                    report.warnings += 1;
+                    "WARN".yellow()
+                }
+                Severity::Deny => {
+                    report.failed.insert(path.clone());
+                    "FAIL".red()
+                }
+            };
+
+            println!("{}: {url}.. {status} ({outcome})", path.display());
+        }
+    }
+
+    if report.skipped > 0 {
+        println!(
+            "{}",
+            format!("skipped {} uncached links (offline)", report.skipped).yellow()
+        );
+    }
+
+    verifier.save()?;
+    info!("saved link cache to `{}`", cache.display());
+
+    Ok(report)
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let paths = if args.paths.is_empty() {
+        vec![config.characteristics(None)]
+    } else {
+        args.paths.clone()
+    };
+
+    let files = tree::select(&paths, &args.include, &args.exclude)?;
+    info!("checking {} characteristic files", files.len());
+
+    if files.is_empty() {
+        return Err(Error::NoFiles {
+            kind: "characteristic",
+            paths,
+        }
+        .into());
+    }
+
+    let total = files.len();
+    let mut failed = BTreeSet::new();
+    let mut characteristics = Vec::new();
+    let mut summary = Summary::default();
+
+    // NOTE: comparing names and checking definitions requires every
+    // characteristic, including those within the cache.
+    let compare_names = config.severity(SIMILAR_NAMES) != Severity::Allow;
+    let check_definitions = [DEFINITION_REFERENCE, DEFINITION_TYPE]
+        .iter()
+        .any(|lint| config.severity(lint) != Severity::Allow);
+
+    let mut cache = if args.no_cache {
+        None
+    } else {
+        Some(cache::Cache::load(config.check_cache(), settings(config)?))
+    };
+
+    for ecc_file in files {
+        let outcome = check_file(&ecc_file, args.all_errors, config, cache.as_mut())?;
+
+        if let Outcome::Passed { warnings, .. } = outcome {
+            summary.warnings += warnings;
+        }
+
+        match outcome {
+            Outcome::Passed {
+                characteristic: Some(characteristic),
+                ..
+            } => characteristics.push((ecc_file, *characteristic)),
+            Outcome::Passed {
+                characteristic: None,
+                ..
+            } if args.verify_links || compare_names || check_definitions => {
+                let characteristic =
+                    ecc::io::from_str(&tree::read(&ecc_file)?).map_err(|source| {
+                        Error::Characteristic {
+                            path: ecc_file.clone(),
+                            source,
+                        }
+                    })?;
+                characteristics.push((ecc_file, characteristic));
+            }
+            Outcome::Passed {
+                characteristic: None,
+                ..
+            } => {}
+            Outcome::Failed => {
+                failed.insert(ecc_file);
+            }
+        }
+    }
+
+    if let Some(cache) = cache {
+        let path = cache.path().to_path_buf();
+        cache.save()?;
+        info!("saved check cache to `{}`", path.display());
+    }
+
+    if compare_names {
+        let report = similar_names(&characteristics, config);
+        failed.extend(report.failed);
+        summary.warnings += report.warnings;
+    }
+
+    if check_definitions {
+        let report = definitions(&characteristics, config)?;
+        failed.extend(report.failed);
+        summary.warnings += report.warnings;
+    }
+
+    if args.verify_links {
+        let cache = args
+            .link_cache
+            .clone()
+            .unwrap_or_else(|| config.link_cache());
+
+        let report = net::block_on(verify_links(&characteristics, &cache, args.offline, config))??;
+        failed.extend(report.failed);
+        summary.warnings += report.warnings;
+    }
+
+    summary.failed = failed.len();
+    summary.passed = total - summary.failed;
+    summary.print();
+
+    if args.watch {
+        return watch(&args, &paths, config);
+    }
+
+    if let Some(max) = args.max_warnings {
+        if summary.warnings > max {
+            println!(
+                "{}",
+                format!("too many warnings ({} > {max})", summary.warnings).red()
+            );
+            std::process::exit(crate::EXIT_FAILURE.into());
         }
     }
 
-    if failed {
-        std::process::exit(1);
+    if summary.failed > 0 {
+        std::process::exit(crate::EXIT_FAILURE.into());
     }
 
     Ok(())
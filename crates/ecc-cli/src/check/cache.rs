@@ -0,0 +1,112 @@
+//! A cache of the files that passed a previous check.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The version of the tool that wrote the cache.
+///
+/// The rules for validating characteristics change between versions, so a
+/// cache written by any other version is discarded.
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// The contents of the cache file.
+#[derive(Default, Serialize, Deserialize)]
+struct Contents {
+    /// The version of the tool that wrote the cache.
+    version: String,
+
+    /// The settings that the check was run with.
+    #[serde(default)]
+    settings: String,
+
+    /// The hashes of the contents of files that passed.
+    passed: BTreeSet<String>,
+}
+
+/// Hashes the contents of a file.
+pub fn hash(contents: &[u8]) -> String {
+    blake3::hash(contents).to_hex().to_string()
+}
+
+/// A cache of the files that passed a previous check, keyed on the hash of
+/// their contents.
+///
+/// Only passing files are cached: failing files are always re-checked so that
+/// their diagnostics are reported.
+pub struct Cache {
+    /// The path to the cache.
+    path: PathBuf,
+
+    /// The settings that the check is run with.
+    settings: String,
+
+    /// The hashes of the contents of files that passed.
+    passed: BTreeSet<String>,
+
+    /// Whether any hashes were added since the cache was loaded.
+    changed: bool,
+}
+
+impl Cache {
+    /// Loads the cache at the given path.
+    ///
+    /// A missing or unreadable cache (or one written by a different version
+    /// of the tool or with different settings) is treated as empty.
+    pub fn load(path: impl Into<PathBuf>, settings: String) -> Self {
+        let path = path.into();
+        let passed = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<Contents>(&contents).ok())
+            .filter(|contents| contents.version == VERSION && contents.settings == settings)
+            .map(|contents| contents.passed)
+            .unwrap_or_default();
+
+        Self {
+            path,
+            settings,
+            passed,
+            changed: false,
+        }
+    }
+
+    /// Gets whether a file with the given hash passed a previous check.
+    pub fn passed(&self, hash: &str) -> bool {
+        self.passed.contains(hash)
+    }
+
+    /// Records that a file with the given hash passed.
+    pub fn insert(&mut self, hash: String) {
+        self.changed |= self.passed.insert(hash);
+    }
+
+    /// Gets the path to the cache.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Writes the cache to disk (if it changed).
+    pub fn save(self) -> anyhow::Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating cache directory: {}", parent.display()))?;
+        }
+
+        let contents = Contents {
+            version: String::from(VERSION),
+            settings: self.settings,
+            passed: self.passed,
+        };
+
+        std::fs::write(&self.path, serde_json::to_string(&contents)?)
+            .with_context(|| format!("writing check cache: {}", self.path.display()))
+    }
+}
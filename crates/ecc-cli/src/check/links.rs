@@ -0,0 +1,281 @@
+//! Verification of the links within characteristics.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use chrono::DateTime;
+use chrono::Utc;
+use ecc::Characteristic;
+use ecc::common::Reference;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::debug;
+use url::Url;
+
+use crate::config;
+use crate::net;
+use crate::net::Client;
+use crate::net::Request;
+
+/// The maximum number of redirects to follow.
+const MAX_REDIRECTS: usize = 10;
+
+/// The hosts that serve DOIs.
+const DOI_HOSTS: &[&str] = &["doi.org", "dx.doi.org"];
+
+/// The outcome of verifying a link.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Outcome {
+    /// The link resolved successfully.
+    Ok,
+
+    /// The link points to a DOI that redirects to a different DOI.
+    DoiMismatch {
+        /// The DOI that the link redirected to.
+        location: String,
+    },
+
+    /// The link does not exist.
+    NotFound {
+        /// The status code returned.
+        status: u16,
+    },
+
+    /// The link returned an unexpected status code.
+    Status {
+        /// The status code returned.
+        status: u16,
+    },
+
+    /// The link could not be reached.
+    Unreachable {
+        /// The reason the link could not be reached.
+        reason: String,
+    },
+
+    /// The link was not verified because the client is offline (and the link
+    /// was not within the cache).
+    Skipped,
+}
+
+impl Outcome {
+    /// Gets the name of the lint that the outcome violates (if any).
+    ///
+    /// The severity of each lint is configurable (see [`config::LINTS`]).
+    pub fn lint(&self) -> Option<&'static str> {
+        match self {
+            Outcome::Ok | Outcome::Skipped => None,
+            Outcome::DoiMismatch { .. } => Some("doi-mismatch"),
+            Outcome::NotFound { .. } => Some("broken-link"),
+            Outcome::Status { .. } => Some("link-status"),
+            Outcome::Unreachable { .. } => Some("unreachable-link"),
+        }
+    }
+}
+
+impl std::fmt::Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Ok => write!(f, "ok"),
+            Outcome::DoiMismatch { location } => {
+                write!(f, "redirects to a different DOI: {location}")
+            }
+            Outcome::NotFound { status } => write!(f, "not found (status {status})"),
+            Outcome::Status { status } => write!(f, "unexpected status {status}"),
+            Outcome::Unreachable { reason } => write!(f, "unreachable: {reason}"),
+            Outcome::Skipped => write!(f, "skipped (offline)"),
+        }
+    }
+}
+
+/// A cached link verification result.
+#[derive(Serialize, Deserialize)]
+struct Entry {
+    /// The outcome.
+    outcome: Outcome,
+
+    /// When the link was checked.
+    checked_at: DateTime<Utc>,
+}
+
+/// Gets the DOI from a URL if the URL points to a DOI resolver.
+fn doi(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+
+    if DOI_HOSTS.contains(&host) {
+        Some(url.path().trim_start_matches('/').to_lowercase())
+    } else {
+        None
+    }
+}
+
+/// Gets all of the links within a characteristic.
+pub fn links(characteristic: &Characteristic) -> Vec<Url> {
+    let mut result = Vec::new();
+
+    if let Some(rfc) = characteristic.rfc() {
+        result.push(rfc.url().clone());
+    }
+
+    if let Some(references) = characteristic.references() {
+        result.extend(references.map(|reference| match reference {
+            Reference::Manuscript { url, .. } | Reference::Preprint { url, .. } => url.clone(),
+        }));
+    }
+
+    result
+}
+
+/// A link verifier with an on-disk cache.
+pub struct Verifier<C> {
+    /// The client used to request links.
+    client: C,
+
+    /// The path to the cache.
+    path: PathBuf,
+
+    /// The number of days a cached result remains valid.
+    ttl_days: i64,
+
+    /// Hosts whose links are never verified.
+    ignore_hosts: Vec<String>,
+
+    /// The cached results keyed by URL.
+    cache: HashMap<String, Entry>,
+}
+
+impl<C: Client> Verifier<C> {
+    /// Creates a new verifier backed by a cache at the given path.
+    ///
+    /// A missing or unreadable cache is treated as empty.
+    pub fn new(client: C, path: impl Into<PathBuf>, policy: &config::Links) -> Self {
+        let path = path.into();
+        let cache = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        Self {
+            client,
+            path,
+            ttl_days: policy.ttl_days,
+            ignore_hosts: policy.ignore_hosts.clone(),
+            cache,
+        }
+    }
+
+    /// Requests a URL and returns the status and the redirect location (if
+    /// any).
+    ///
+    /// Some servers do not support `HEAD` requests, so a `GET` request is made
+    /// when one is rejected.
+    async fn request(&self, url: &Url) -> Result<(u16, Option<String>), net::Error> {
+        let mut response = self.client.send(Request::head(url.clone())).await?;
+
+        if matches!(response.status, 403 | 405 | 501) {
+            response = self.client.send(Request::get(url.clone())).await?;
+        }
+
+        let location = response.header("location").map(String::from);
+        Ok((response.status, location))
+    }
+
+    /// Resolves a link over the network.
+    async fn resolve(&self, url: &Url) -> Outcome {
+        let original_doi = doi(url);
+        let mut current = url.clone();
+
+        for _ in 0..=MAX_REDIRECTS {
+            debug!("requesting `{current}`");
+
+            let (status, location) = match self.request(&current).await {
+                Ok(result) => result,
+                Err(net::Error::Offline { .. }) => return Outcome::Skipped,
+                Err(err) => {
+                    return Outcome::Unreachable {
+                        reason: err.to_string(),
+                    };
+                }
+            };
+
+            match (status, location) {
+                (300..=399, Some(location)) => {
+                    let next = match current.join(&location) {
+                        Ok(next) => next,
+                        Err(err) => {
+                            return Outcome::Unreachable {
+                                reason: format!("invalid redirect `{location}`: {err}"),
+                            };
+                        }
+                    };
+
+                    if let (Some(original), Some(redirected)) = (&original_doi, doi(&next)) {
+                        if original != &redirected {
+                            return Outcome::DoiMismatch {
+                                location: next.to_string(),
+                            };
+                        }
+                    }
+
+                    current = next;
+                }
+                (200..=299, _) => return Outcome::Ok,
+                (404 | 410, _) => return Outcome::NotFound { status },
+                (status, _) => return Outcome::Status { status },
+            }
+        }
+
+        Outcome::Unreachable {
+            reason: String::from("too many redirects"),
+        }
+    }
+
+    /// Verifies a link, consulting the cache first.
+    ///
+    /// Links to ignored hosts are always considered valid.
+    pub async fn verify(&mut self, url: &Url) -> Outcome {
+        if url
+            .host_str()
+            .is_some_and(|host| self.ignore_hosts.iter().any(|ignored| ignored == host))
+        {
+            return Outcome::Ok;
+        }
+
+        let now = Utc::now();
+
+        if let Some(entry) = self.cache.get(url.as_str()) {
+            if now - entry.checked_at < chrono::Duration::days(self.ttl_days) {
+                return entry.outcome.clone();
+            }
+        }
+
+        let outcome = self.resolve(url).await;
+
+        // Network failures are transient (and skipped links were never
+        // checked), so they are never cached.
+        if !matches!(outcome, Outcome::Unreachable { .. } | Outcome::Skipped) {
+            self.cache.insert(
+                url.to_string(),
+                Entry {
+                    outcome: outcome.clone(),
+                    checked_at: now,
+                },
+            );
+        }
+
+        outcome
+    }
+
+    /// Writes the cache to disk.
+    pub fn save(&self) -> anyhow::Result<()> {
+        if let Some(parent) = self.path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating cache directory: {}", parent.display()))?;
+        }
+
+        std::fs::write(&self.path, serde_json::to_vec_pretty(&self.cache)?)
+            .with_context(|| format!("writing link cache: {}", self.path.display()))
+    }
+}
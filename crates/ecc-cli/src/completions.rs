@@ -0,0 +1,34 @@
+//! Generation of shell completions.
+
+use std::io::Write as _;
+
+use anyhow::Context;
+use clap::CommandFactory as _;
+use clap::Parser;
+use clap_complete::Shell;
+
+/// Prints shell completions for the tool to stdout.
+///
+/// For example, to install completions for `bash`:
+///
+/// `ecc-cli completions bash > /etc/bash_completion.d/ecc-cli`
+#[derive(Parser)]
+pub struct Args {
+    /// The shell to generate completions for.
+    shell: Shell,
+}
+
+/// The main method.
+pub fn main(args: Args) -> anyhow::Result<()> {
+    let mut command = crate::Args::command();
+    let name = command.get_name().to_string();
+
+    // NOTE: the completions are buffered because `clap_complete` panics if
+    // writing fails (e.g., when the output is piped to a closed pipe).
+    let mut buffer = Vec::new();
+    clap_complete::generate(args.shell, &mut command, name, &mut buffer);
+
+    std::io::stdout()
+        .write_all(&buffer)
+        .context("writing completions")
+}
@@ -0,0 +1,546 @@
+//! Configuration loaded from an `ecc.toml` file.
+//!
+//! The configuration file is discovered by searching the current directory and
+//! its ancestors (stopping at the root of the git repository). Every field is
+//! optional: anything not specified falls back to the defaults used by the
+//! upstream ECC repository. Relative paths are resolved against the directory
+//! containing the configuration file.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::Context;
+use ecc::Category;
+use ecc::State;
+use ecc::common::EvidenceLevel;
+use ecc::common::Sensitivity;
+use ecc::common::Tag;
+use ecc::common::molecular::Symbols;
+use ontology::node::name::CasePolicy;
+use serde::Deserialize;
+use tracing::debug;
+
+use crate::Error;
+use crate::ecc::export;
+use crate::ontology::DEFAULT_MAX_PATH_LENGTH;
+use crate::ontology::Layout;
+use crate::ontology::PathPolicy;
+use crate::tree;
+
+/// The name of the configuration file.
+pub const FILE_NAME: &str = "ecc.toml";
+
+/// The lint that reports characteristic or node names that are near misses of
+/// one another.
+pub const SIMILAR_NAMES: &str = "similar-names";
+
+/// The lints that can be configured along with their default severities.
+pub const LINTS: &[(&str, Severity)] = &[
+    ("broken-link", Severity::Deny),
+    ("categorical-as-binary", Severity::Warn),
+    ("definition-reference", Severity::Deny),
+    ("definition-type", Severity::Deny),
+    ("doi-mismatch", Severity::Deny),
+    ("duplicate-options", Severity::Warn),
+    ("fraction-bounds", Severity::Deny),
+    ("incomplete", Severity::Deny),
+    ("insufficient-evidence", Severity::Deny),
+    ("invalid-tag", Severity::Deny),
+    ("link-status", Severity::Warn),
+    ("option-length", Severity::Warn),
+    (SIMILAR_NAMES, Severity::Warn),
+    ("too-few-options", Severity::Deny),
+    ("unrecognized-gene-symbol", Severity::Warn),
+    ("unreachable-link", Severity::Warn),
+    ("unsatisfiable-definition", Severity::Deny),
+];
+
+/// The severity of a lint.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// The lint is not reported.
+    Allow,
+
+    /// The lint is reported but does not cause a failure.
+    Warn,
+
+    /// The lint is reported and causes a failure.
+    Deny,
+}
+
+/// The directories within the repository.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Directories {
+    /// The composable characteristic directory.
+    pub characteristics: PathBuf,
+
+    /// The ontology directory.
+    pub ontology: Option<PathBuf>,
+
+    /// How node files are laid out within the ontology directory.
+    pub ontology_layout: Layout,
+
+    /// The limit on the length (in characters) of a node file's path within
+    /// the ontology directory.
+    pub ontology_max_path_length: usize,
+
+    /// The name of the directory for each category within the composable
+    /// characteristic directory.
+    pub categories: BTreeMap<Category, String>,
+}
+
+impl Default for Directories {
+    fn default() -> Self {
+        Self {
+            characteristics: PathBuf::from("ecc"),
+            ontology: None,
+            ontology_layout: Layout::default(),
+            ontology_max_path_length: DEFAULT_MAX_PATH_LENGTH,
+            categories: Category::ALL
+                .into_iter()
+                .map(|category| (category, category.code().to_lowercase()))
+                .collect(),
+        }
+    }
+}
+
+/// The policy for casing names.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Casing {
+    /// Words that are expected to be lowercase in addition to the built-in
+    /// list (e.g., `and`, `of`, `the`).
+    pub lowercase_words: Vec<String>,
+}
+
+impl Casing {
+    /// Gets the case policy for validating names.
+    pub fn policy(&self) -> CasePolicy {
+        CasePolicy::default().with_lowercase_words(&self.lowercase_words)
+    }
+}
+
+/// The controlled vocabulary of tags.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Tags {
+    /// The tags that characteristics can be labeled with.
+    pub vocabulary: Vec<Tag>,
+}
+
+impl Default for Tags {
+    fn default() -> Self {
+        Self {
+            vocabulary: ecc_check::Options::default().tags,
+        }
+    }
+}
+
+/// The policy for tracking drafts.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Drafts {
+    /// The number of days a draft can go without being updated before it is
+    /// reported as stale.
+    pub max_age_days: i64,
+}
+
+impl Default for Drafts {
+    fn default() -> Self {
+        Self { max_age_days: 90 }
+    }
+}
+
+/// The settings for the log of lifecycle transitions.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Events {
+    /// The path to the event log.
+    pub log: PathBuf,
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Self {
+            log: PathBuf::from(ecc::event::FILE_NAME),
+        }
+    }
+}
+
+/// The settings for checking characteristics.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Check {
+    /// The path to the cache of files that passed a previous check.
+    pub cache: PathBuf,
+
+    /// The limit on the length (in characters) of an option of a categorical
+    /// feature.
+    pub max_option_length: usize,
+
+    /// The number of edits within which two characteristic or node names are
+    /// reported as near misses by the `similar-names` lint.
+    pub max_name_distance: usize,
+
+    /// The minimum completeness score (between `0` and `1`) required for
+    /// characteristics in each state by the `incomplete` lint.
+    pub min_completeness: BTreeMap<State, f64>,
+
+    /// The minimum evidence level required for adopted characteristics by
+    /// the `insufficient-evidence` lint (unset by default).
+    pub min_evidence_level: Option<EvidenceLevel>,
+
+    /// The HGNC symbol list that gene symbols are checked against by the
+    /// `unrecognized-gene-symbol` lint (unset by default).
+    ///
+    /// This is either the HGNC complete set or a list of approved symbols
+    /// with one per line.
+    pub gene_symbols: Option<PathBuf>,
+}
+
+impl Default for Check {
+    fn default() -> Self {
+        Self {
+            cache: PathBuf::from(".ecc-cache/check.json"),
+            max_option_length: ecc_check::lint::MAX_OPTION_LENGTH,
+            max_name_distance: ecc::text::similar::MAX_DISTANCE,
+            min_completeness: BTreeMap::new(),
+            min_evidence_level: None,
+            gene_symbols: None,
+        }
+    }
+}
+
+/// The policy for verifying links.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Links {
+    /// The path to the link verification cache.
+    pub cache: PathBuf,
+
+    /// The number of days a cached result remains valid.
+    pub ttl_days: i64,
+
+    /// The number of seconds to wait for a single request.
+    pub timeout_secs: u64,
+
+    /// Hosts whose links are never verified (e.g., hosts that block automated
+    /// requests).
+    pub ignore_hosts: Vec<String>,
+}
+
+impl Links {
+    /// Gets the timeout for a single request.
+    pub fn timeout(&self) -> Duration {
+        Duration::from_secs(self.timeout_secs)
+    }
+}
+
+impl Default for Links {
+    fn default() -> Self {
+        Self {
+            cache: PathBuf::from(".ecc-cache/links.json"),
+            ttl_days: 7,
+            timeout_secs: 15,
+            ignore_hosts: Vec::new(),
+        }
+    }
+}
+
+/// The default settings for exports.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Export {
+    /// The format to export to.
+    pub format: export::Format,
+
+    /// The directory to write exported files to.
+    pub output_directory: Option<PathBuf>,
+
+    /// The most restricted sensitivity of the characteristics that are
+    /// exported (and built, released, or served).
+    pub max_sensitivity: Sensitivity,
+}
+
+impl Default for Export {
+    fn default() -> Self {
+        Self {
+            format: export::Format::Json,
+            output_directory: None,
+            max_sensitivity: Sensitivity::Public,
+        }
+    }
+}
+
+/// The settings for data releases.
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Release {
+    /// The path to the changelog.
+    pub changelog: PathBuf,
+
+    /// The prefix of the git tags that mark data releases.
+    pub tag_prefix: String,
+
+    /// The directory to write release bundles to.
+    ///
+    /// Each release is written to a subdirectory named after its version.
+    pub output_directory: PathBuf,
+}
+
+impl Default for Release {
+    fn default() -> Self {
+        Self {
+            changelog: PathBuf::from("CHANGELOG.md"),
+            tag_prefix: String::from("v"),
+            output_directory: PathBuf::from("release"),
+        }
+    }
+}
+
+/// The configuration.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The directory containing the configuration file.
+    ///
+    /// Relative paths within the configuration are resolved against this
+    /// directory.
+    #[serde(skip)]
+    root: PathBuf,
+
+    /// The directories within the repository.
+    pub directories: Directories,
+
+    /// The severity of each lint (keyed by lint name).
+    pub lints: BTreeMap<String, Severity>,
+
+    /// The policy for casing names.
+    pub casing: Casing,
+
+    /// The settings for checking characteristics.
+    pub check: Check,
+
+    /// The controlled vocabulary of tags.
+    pub tags: Tags,
+
+    /// The policy for tracking drafts.
+    pub drafts: Drafts,
+
+    /// The settings for the log of lifecycle transitions.
+    pub events: Events,
+
+    /// The policy for verifying links.
+    pub links: Links,
+
+    /// The default settings for exports.
+    pub export: Export,
+
+    /// The settings for data releases.
+    pub release: Release,
+
+    /// The HGNC symbol list (loaded on first use).
+    #[serde(skip)]
+    symbols: OnceLock<Option<Arc<Symbols>>>,
+}
+
+/// Searches the current directory and its ancestors for a configuration file.
+///
+/// The search stops at the root of the git repository (the first directory
+/// containing `.git`). The returned path is relative to the current directory.
+fn discover() -> anyhow::Result<Option<PathBuf>> {
+    let current = std::env::current_dir().context("getting the current directory")?;
+    let mut relative = PathBuf::new();
+
+    for dir in current.ancestors() {
+        let candidate = dir.join(FILE_NAME);
+
+        if candidate.is_file() {
+            return Ok(Some(relative.join(FILE_NAME)));
+        }
+
+        if dir.join(".git").exists() {
+            break;
+        }
+
+        relative.push("..");
+    }
+
+    Ok(None)
+}
+
+impl Config {
+    /// Loads the configuration.
+    ///
+    /// If a path is provided, the configuration is read from that file.
+    /// Otherwise, the configuration file is discovered (see the module-level
+    /// documentation). If no configuration file exists, the defaults are used.
+    pub fn load(path: Option<&Path>) -> anyhow::Result<Self> {
+        let path = match path {
+            Some(path) => path.to_path_buf(),
+            None => match discover()? {
+                Some(path) => path,
+                None => {
+                    debug!("no `{FILE_NAME}` found; using the default configuration");
+                    return Ok(Self::default());
+                }
+            },
+        };
+
+        debug!("loading configuration from `{}`", path.display());
+
+        let contents = tree::read(&path)?;
+        let mut config = match toml::from_str::<Self>(&contents) {
+            Ok(config) => config,
+            Err(source) => return Err(Error::Config { path, source }.into()),
+        };
+
+        if let Some(lint) = config
+            .lints
+            .keys()
+            .find(|lint| !LINTS.iter().any(|(name, _)| name == lint))
+        {
+            return Err(Error::UnknownLint {
+                lint: lint.clone(),
+                path,
+                expected: LINTS
+                    .iter()
+                    .map(|(name, _)| *name)
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            }
+            .into());
+        }
+
+        config.root = path.parent().map(Path::to_path_buf).unwrap_or_default();
+        Ok(config)
+    }
+
+    /// Gets the directory containing the configuration file.
+    ///
+    /// This is empty (i.e., the current directory) when no configuration file
+    /// was found.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Resolves a path from the configuration against the directory containing
+    /// the configuration file.
+    pub fn resolve(&self, path: &Path) -> PathBuf {
+        self.root.join(path)
+    }
+
+    /// Gets the composable characteristic directory, preferring the provided
+    /// path (typically from the command line) over the configuration.
+    pub fn characteristics(&self, path: Option<PathBuf>) -> PathBuf {
+        path.unwrap_or_else(|| self.resolve(&self.directories.characteristics))
+    }
+
+    /// Gets the ontology directory (if any), preferring the provided path
+    /// (typically from the command line) over the configuration.
+    pub fn ontology(&self, path: Option<PathBuf>) -> Option<PathBuf> {
+        path.or_else(|| {
+            self.directories
+                .ontology
+                .as_deref()
+                .map(|path| self.resolve(path))
+        })
+    }
+
+    /// Gets the ontology directory, failing if none is provided or
+    /// configured.
+    pub fn require_ontology(&self, path: Option<PathBuf>) -> Result<PathBuf, Error> {
+        self.ontology(path).ok_or(Error::NoOntology)
+    }
+
+    /// Gets where node files are written within the ontology directory.
+    pub fn ontology_paths(&self) -> PathPolicy {
+        PathPolicy {
+            layout: self.directories.ontology_layout,
+            max_length: self.directories.ontology_max_path_length,
+        }
+    }
+
+    /// Gets the path to the check cache.
+    pub fn check_cache(&self) -> PathBuf {
+        self.resolve(&self.check.cache)
+    }
+
+    /// Gets the HGNC symbol list (if one is configured).
+    ///
+    /// The list is read the first time it is requested.
+    pub fn gene_symbols(&self) -> anyhow::Result<Option<Arc<Symbols>>> {
+        if let Some(symbols) = self.symbols.get() {
+            return Ok(symbols.clone());
+        }
+
+        let symbols = match &self.check.gene_symbols {
+            Some(path) => {
+                let path = self.resolve(path);
+                let symbols = Symbols::parse(&tree::read(&path)?);
+                debug!(
+                    "loaded {} gene symbols from `{}`",
+                    symbols.len(),
+                    path.display()
+                );
+                Some(Arc::new(symbols))
+            }
+            None => None,
+        };
+
+        Ok(self.symbols.get_or_init(|| symbols).clone())
+    }
+
+    /// Gets the path to the event log.
+    pub fn event_log(&self) -> PathBuf {
+        self.resolve(&self.events.log)
+    }
+
+    /// Gets the path to the link verification cache.
+    pub fn link_cache(&self) -> PathBuf {
+        self.resolve(&self.links.cache)
+    }
+
+    /// Gets the name of the directory for a category.
+    pub fn category_directory(&self, category: Category) -> String {
+        self.directories
+            .categories
+            .get(&category)
+            .cloned()
+            .unwrap_or_else(|| category.code().to_lowercase())
+    }
+
+    /// Gets the category whose directory has the provided name.
+    pub fn category_from_directory(&self, name: &str) -> Option<Category> {
+        Category::ALL
+            .into_iter()
+            .find(|category| self.category_directory(*category) == name)
+    }
+
+    /// Infers the category of a characteristic from the directories in its
+    /// path.
+    pub fn infer_category(&self, path: &Path) -> Option<Category> {
+        path.parent()?.components().rev().find_map(|component| {
+            let name = component.as_os_str().to_str()?;
+            self.category_from_directory(name)
+                .or_else(|| name.parse::<Category>().ok())
+        })
+    }
+
+    /// Gets the severity of a lint.
+    pub fn severity(&self, lint: &str) -> Severity {
+        self.lints.get(lint).copied().unwrap_or_else(|| {
+            LINTS
+                .iter()
+                .find(|(name, _)| *name == lint)
+                .map(|(_, severity)| *severity)
+                .unwrap_or(Severity::Warn)
+        })
+    }
+}
@@ -0,0 +1,193 @@
+//! Coverage of the ontology by the composable characteristics.
+
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use clap::Parser;
+use colored::Colorize as _;
+use ecc::Characteristic;
+use ecc::State;
+use ontology::Graph;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::ecc::stats::Format;
+use crate::tree;
+
+/// Reports how the characteristics are bound to the ontology.
+///
+/// The characteristic tree is joined with the characteristic bindings declared
+/// on each ontology node, and the following are reported:
+///
+/// * adopted characteristics that no node is bound to,
+/// * leaf nodes that are not bound to any characteristic, and
+/// * bindings to characteristics that do not exist or are not adopted.
+///
+/// The exit code is `1` when any binding refers to a characteristic that does
+/// not exist or is not adopted.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    ecc: Option<PathBuf>,
+
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    ontology: Option<PathBuf>,
+
+    /// The format to print the report in.
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+/// A binding to a characteristic that does not exist or is not adopted.
+#[derive(Serialize)]
+struct InvalidBinding {
+    /// The name of the node.
+    node: String,
+
+    /// The identifier of the characteristic.
+    identifier: String,
+
+    /// The state of the characteristic (or [`None`] if it does not exist).
+    state: Option<State>,
+}
+
+/// The coverage report.
+#[derive(Serialize)]
+struct Report {
+    /// The number of adopted characteristics.
+    adopted: usize,
+
+    /// The number of leaf nodes within the ontology.
+    leaves: usize,
+
+    /// The adopted characteristics that no node is bound to.
+    unused: Vec<String>,
+
+    /// The leaf nodes that are not bound to any characteristic.
+    unbound: Vec<String>,
+
+    /// The bindings to characteristics that do not exist or are not adopted.
+    invalid: Vec<InvalidBinding>,
+}
+
+/// Computes the coverage report.
+fn compute(characteristics: &[(PathBuf, Characteristic)], graph: &Graph) -> Report {
+    let states = characteristics
+        .iter()
+        .filter_map(|(_, c)| Some((c.identifier()?.to_string(), c.state())))
+        .collect::<HashMap<_, _>>();
+
+    let mut bound = BTreeSet::new();
+    let mut leaves = 0;
+    let mut unbound = Vec::new();
+    let mut invalid = Vec::new();
+
+    for node in graph.nodes() {
+        let name = node.name().inner();
+
+        if graph.children(name).is_empty() {
+            leaves += 1;
+
+            if node.characteristics().is_empty() {
+                unbound.push(name.to_string());
+            }
+        }
+
+        for binding in node.characteristics() {
+            let identifier = binding.identifier().to_string();
+            let state = states.get(&identifier).copied();
+
+            if state != Some(State::Adopted) {
+                invalid.push(InvalidBinding {
+                    node: name.to_string(),
+                    identifier: identifier.clone(),
+                    state,
+                });
+            }
+
+            bound.insert(identifier);
+        }
+    }
+
+    let mut unused = states
+        .iter()
+        .filter(|(identifier, state)| **state == State::Adopted && !bound.contains(*identifier))
+        .map(|(identifier, _)| identifier.clone())
+        .collect::<Vec<_>>();
+    unused.sort();
+    unbound.sort();
+
+    Report {
+        adopted: states
+            .values()
+            .filter(|state| **state == State::Adopted)
+            .count(),
+        leaves,
+        unused,
+        unbound,
+        invalid,
+    }
+}
+
+/// Prints a section of the table.
+fn section(title: &str, items: impl ExactSizeIterator<Item = String>) {
+    println!("{} ({})", title.bold(), items.len());
+
+    for item in items {
+        println!("  {item}");
+    }
+
+    println!();
+}
+
+/// Prints the report as a table.
+fn table(report: &Report) {
+    println!(
+        "{} {} adopted characteristics, {} leaf nodes\n",
+        "Coverage:".bold(),
+        report.adopted,
+        report.leaves
+    );
+
+    section(
+        "Unused characteristics",
+        report.unused.iter().map(String::clone),
+    );
+    section(
+        "Unbound leaf nodes",
+        report.unbound.iter().map(String::clone),
+    );
+    section(
+        "Invalid bindings",
+        report.invalid.iter().map(|binding| match binding.state {
+            Some(state) => format!("{}: `{}` is {state}", binding.node, binding.identifier),
+            None => format!("{}: `{}` does not exist", binding.node, binding.identifier),
+        }),
+    );
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.ecc))?;
+    let (graph, _) = tree::load_ontology_graph(&config.require_ontology(args.ontology)?)?;
+
+    let report = compute(&characteristics, &graph);
+
+    match args.format {
+        Format::Table => table(&report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    if !report.invalid.is_empty() {
+        std::process::exit(crate::EXIT_FAILURE.into());
+    }
+
+    Ok(())
+}
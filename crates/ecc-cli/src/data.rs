@@ -0,0 +1,101 @@
+//! Facilities for working with data annotated using the ECC.
+
+use std::io::Cursor;
+use std::path::Path;
+
+use anyhow::Context;
+use clap::Parser;
+use clap::Subcommand;
+use ecc::CharacteristicSet;
+use ecc::dataset::DatasetManifest;
+
+use crate::config::Config;
+use crate::tree;
+
+mod check;
+mod classify;
+mod harmonize;
+mod manifest;
+
+/// The byte order mark that some spreadsheet programs prepend to files.
+const BOM: char = '\u{feff}';
+
+/// Work with data annotated using the composable characteristics.
+#[derive(Parser)]
+pub struct Args {
+    /// The command to run.
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The command to run.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Checks a cohort metadata file against the characteristics.
+    Check(check::Args),
+
+    /// Classifies the samples within a cohort metadata file.
+    Classify(classify::Args),
+
+    /// Harmonizes a cohort metadata file into canonical characteristic values.
+    Harmonize(harmonize::Args),
+
+    /// Suggests a dataset manifest for a cohort metadata file.
+    Manifest(manifest::Args),
+}
+
+/// Gets the delimiter of a table from its extension.
+///
+/// Files ending in `.csv` hold comma-separated values and all other files hold
+/// tab-separated values.
+fn delimiter(path: &Path) -> u8 {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some(extension) if extension.eq_ignore_ascii_case("csv") => b',',
+        _ => b'\t',
+    }
+}
+
+/// Opens a table for reading.
+fn reader(path: &Path) -> anyhow::Result<csv::Reader<Cursor<String>>> {
+    let contents = tree::read(path)?;
+
+    Ok(csv::ReaderBuilder::new()
+        .delimiter(delimiter(path))
+        .trim(csv::Trim::All)
+        .from_reader(Cursor::new(contents.trim_start_matches(BOM).to_string())))
+}
+
+/// Loads a dataset manifest and validates it against the characteristics.
+fn load_manifest(
+    path: &Path,
+    characteristics: &CharacteristicSet,
+) -> anyhow::Result<DatasetManifest> {
+    let contents = std::fs::read_to_string(path)
+        .with_context(|| format!("reading manifest: {}", path.display()))?;
+    let manifest = serde_yaml::from_str::<DatasetManifest>(&contents)
+        .with_context(|| format!("parsing manifest: {}", path.display()))?;
+
+    if let Err(errors) = manifest.validate_against(characteristics) {
+        let errors = errors
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+        anyhow::bail!(
+            "invalid manifest `{}`:\n  {}",
+            path.display(),
+            errors.join("\n  ")
+        );
+    }
+
+    Ok(manifest)
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    match args.command {
+        Command::Check(args) => check::main(args, config),
+        Command::Classify(args) => classify::main(args, config),
+        Command::Harmonize(args) => harmonize::main(args, config),
+        Command::Manifest(args) => manifest::main(args, config),
+    }
+}
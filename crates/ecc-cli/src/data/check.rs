@@ -0,0 +1,245 @@
+//! Checking of cohort metadata files.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize as _;
+use ecc::CharacteristicSet;
+use ecc::Identifier;
+use ecc::annotate;
+use ecc::annotate::Annotation;
+use ecc::annotate::AnnotationSet;
+use ecc::common::applicability::AgeGroup;
+use ecc::common::applicability::SpecimenType;
+use ecc::dataset::Column;
+use ecc::dataset::DatasetManifest;
+use tracing::info;
+
+use crate::config::Config;
+use crate::tree;
+
+/// Checks a cohort metadata file against the characteristics.
+///
+/// The file is a table with one row per sample. Each column header is mapped
+/// to a characteristic by its identifier (e.g., `ECC-MORPH-000001`) or its
+/// exact name (or by a dataset manifest, if provided), and every non-missing
+/// cell within a mapped column is checked against the characteristic's
+/// permissible values. Columns that do not map to a characteristic (e.g.,
+/// sample identifiers) are reported and skipped.
+///
+/// If the columns holding the specimen type (e.g., `bone-marrow`) or the age
+/// of the patient (in years or as an age group such as `child`) are named,
+/// every annotated characteristic must also apply to the sample (see the
+/// `applicability` of a characteristic).
+///
+/// Files ending in `.csv` are read as comma-separated values and all other
+/// files are read as tab-separated values. Problems are printed with the
+/// (one-based) line and column of the offending cell. The exit code is `1`
+/// when any cell fails validation.
+#[derive(Parser)]
+pub struct Args {
+    /// The cohort metadata file.
+    path: PathBuf,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    ecc: Option<PathBuf>,
+
+    /// The dataset manifest mapping the columns to characteristics.
+    ///
+    /// If not provided, columns are mapped by their headers.
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+
+    /// The column holding the type of specimen each sample was taken from.
+    #[arg(long, value_name = "COLUMN")]
+    specimen_type: Option<String>,
+
+    /// The column holding the age of the patient (in years or as an age
+    /// group) when each sample was taken.
+    #[arg(long, value_name = "COLUMN")]
+    age: Option<String>,
+}
+
+/// Maps the column headers to characteristics by identifier or exact name.
+pub(super) fn infer(
+    headers: &csv::StringRecord,
+    characteristics: &CharacteristicSet,
+) -> DatasetManifest {
+    let names = characteristics
+        .iter()
+        .filter_map(|c| Some((c.name()?, c.identifier()?)))
+        .collect::<HashMap<_, _>>();
+
+    let columns = headers
+        .iter()
+        .filter_map(|header| {
+            let identifier = match header.parse::<Identifier>() {
+                Ok(identifier) => characteristics.get(&identifier).map(|_| identifier),
+                Err(_) => names.get(header).map(|identifier| (*identifier).clone()),
+            }?;
+
+            Some(Column::new(header, identifier))
+        })
+        .collect();
+
+    DatasetManifest {
+        missing: Vec::new(),
+        columns,
+    }
+}
+
+/// Gets the column of the cell that an annotation error relates to.
+///
+/// Errors that relate to a sample rather than a single cell return [`None`].
+fn column(error: &annotate::Error, columns: &[Option<Identifier>]) -> Option<usize> {
+    let identifier = match error {
+        annotate::Error::UnknownCharacteristic(identifier)
+        | annotate::Error::MissingKind(identifier)
+        | annotate::Error::Duplicate(identifier)
+        | annotate::Error::InvalidValue { identifier, .. }
+        | annotate::Error::NotApplicable { identifier, .. } => identifier,
+        annotate::Error::MissingDependency { .. } | annotate::Error::MutuallyExclusive { .. } => {
+            return None;
+        }
+    };
+
+    columns
+        .iter()
+        .position(|column| column.as_ref() == Some(identifier))
+}
+
+/// Parses the age group of a patient from an age in years or the name of an
+/// age group.
+fn age_group(value: &str) -> Result<AgeGroup, String> {
+    match value.parse::<f64>() {
+        Ok(years) => AgeGroup::from_years(years)
+            .ok_or_else(|| format!("invalid age `{value}`: expected a non-negative number")),
+        Err(_) => value.parse::<AgeGroup>().map_err(|err| err.to_string()),
+    }
+}
+
+/// Finds the position of a named column.
+fn position(headers: &csv::StringRecord, name: Option<&str>) -> anyhow::Result<Option<usize>> {
+    name.map(|name| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .with_context(|| format!("column `{name}` does not exist"))
+    })
+    .transpose()
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.ecc))?
+        .into_iter()
+        .map(|(_, c)| c)
+        .collect::<CharacteristicSet>();
+
+    let mut reader = super::reader(&args.path)?;
+    let headers = reader.headers().context("reading the header row")?.clone();
+
+    let manifest = match &args.manifest {
+        Some(path) => super::load_manifest(path, &characteristics)?,
+        None => infer(&headers, &characteristics),
+    };
+
+    let columns = headers
+        .iter()
+        .map(|header| manifest.column(header).map(|c| c.identifier.clone()))
+        .collect::<Vec<_>>();
+    let specimen_type = position(&headers, args.specimen_type.as_deref())?;
+    let age = position(&headers, args.age.as_deref())?;
+    let file = args.path.display();
+
+    for (i, header) in headers.iter().enumerate() {
+        if columns[i].is_none() && specimen_type != Some(i) && age != Some(i) {
+            println!(
+                "{file}:1:{}: {}: column `{header}` does not map to a characteristic",
+                i + 1,
+                "warning".yellow()
+            );
+        }
+    }
+
+    let mut rows = 0;
+    let mut problems = 0;
+
+    for result in reader.records() {
+        let record = result.with_context(|| format!("reading file: {}", args.path.display()))?;
+        // SAFETY: records read from a string always have a position.
+        let line = record.position().unwrap().line();
+        rows += 1;
+
+        let annotations = record
+            .iter()
+            .zip(headers.iter().zip(&columns))
+            .filter(|(value, (header, _))| !manifest.is_missing(header, value))
+            .filter_map(|(value, (_, identifier))| {
+                identifier
+                    .as_ref()
+                    .map(|identifier| Annotation::new(identifier.clone(), value))
+            })
+            .collect::<AnnotationSet>();
+
+        let mut context = annotate::Context::default();
+        let known = |i: &usize| !manifest.is_missing(&headers[*i], &record[*i]);
+
+        if let Some(i) = specimen_type.filter(known) {
+            match record[i].parse::<SpecimenType>() {
+                Ok(value) => context.specimen_type = Some(value),
+                Err(err) => {
+                    problems += 1;
+                    println!("{file}:{line}:{}: {}: {err}", i + 1, "error".red());
+                }
+            }
+        }
+
+        if let Some(i) = age.filter(known) {
+            match age_group(&record[i]) {
+                Ok(value) => context.age_group = Some(value),
+                Err(err) => {
+                    problems += 1;
+                    println!("{file}:{line}:{}: {}: {err}", i + 1, "error".red());
+                }
+            }
+        }
+
+        let Err(errors) = annotations.validate_in(&characteristics, &context) else {
+            continue;
+        };
+
+        for error in errors {
+            problems += 1;
+
+            match column(&error, &columns) {
+                Some(column) => {
+                    println!("{file}:{line}:{}: {}: {error}", column + 1, "error".red())
+                }
+                None => println!("{file}:{line}: {}: {error}", "error".red()),
+            }
+        }
+    }
+
+    info!(
+        "checked {rows} rows against {} characteristics",
+        characteristics.len()
+    );
+
+    if problems == 0 {
+        println!("{} {rows} rows.. {}", "Checked".bold(), "OK".green());
+        return Ok(());
+    }
+
+    println!(
+        "{} {rows} rows.. {} ({problems} problems)",
+        "Checked".bold(),
+        "FAIL".red()
+    );
+    std::process::exit(crate::EXIT_FAILURE.into());
+}
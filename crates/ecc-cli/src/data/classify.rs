@@ -0,0 +1,273 @@
+//! Classification of the samples within a cohort metadata file.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize as _;
+use ecc::CharacteristicSet;
+use ecc::annotate;
+use ecc::annotate::Annotation;
+use ecc::annotate::AnnotationSet;
+use ontology::Graph;
+use ontology::classify::Classification;
+use tracing::info;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The flag for samples consistent with more than one node.
+const AMBIGUOUS: &str = "ambiguous";
+
+/// The flag for samples that partially contradict the bindings of a node.
+const CONFLICT: &str = "conflict";
+
+/// The flag for samples that could be classified more specifically if more
+/// characteristics were annotated.
+const INCOMPLETE: &str = "incomplete";
+
+/// The flag for samples that annotate characteristics that do not apply to
+/// the nodes they were classified under.
+const INAPPLICABLE: &str = "inapplicable";
+
+/// The flag for samples whose annotations are invalid (and were not
+/// classified).
+const INVALID: &str = "invalid";
+
+/// The flag for samples that do not match any bound node.
+const UNCLASSIFIED: &str = "unclassified";
+
+/// Classifies the samples within a cohort metadata file.
+///
+/// Each row is read as the annotations of a sample (mapping the columns to
+/// characteristics as `data check` does) and classified under the most
+/// specific ontology nodes whose characteristic bindings it is consistent
+/// with. The table is written with the following columns appended:
+///
+/// * `<prefix>_node`: the names of the assigned nodes (separated by `; `),
+/// * `<prefix>_code`: the short codes of the assigned nodes, and
+/// * `<prefix>_flags`: any of `ambiguous` (more than one node was assigned),
+///   `conflict` (a node's bindings were partially contradicted), `incomplete`
+///   (annotating more characteristics could refine the classification),
+///   `unclassified` (no bound node matched), `inapplicable` (a characteristic
+///   that does not apply to the assigned nodes was annotated), and `invalid`
+///   (the annotations are invalid and the sample was not classified).
+///
+/// A summary of the number of samples assigned to each node (and to its
+/// subtree) is printed once every row has been classified.
+#[derive(Parser)]
+pub struct Args {
+    /// The cohort metadata file.
+    path: PathBuf,
+
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    ontology: Option<PathBuf>,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    ecc: Option<PathBuf>,
+
+    /// The dataset manifest mapping the columns to characteristics.
+    ///
+    /// If not provided, columns are mapped by their headers.
+    #[arg(long)]
+    manifest: Option<PathBuf>,
+
+    /// The file to write the classified table to.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// The prefix of the appended column names.
+    #[arg(long, default_value = "ontology")]
+    prefix: String,
+}
+
+/// Gets the flags for a classification.
+fn flags(graph: &Graph, classification: &Classification<'_>) -> Vec<&'static str> {
+    let mut flags = Vec::new();
+
+    if classification.is_ambiguous() {
+        flags.push(AMBIGUOUS);
+    }
+
+    if !classification.conflicts.is_empty() {
+        flags.push(CONFLICT);
+    }
+
+    if !classification.undetermined.is_empty() {
+        flags.push(INCOMPLETE);
+    }
+
+    if classification.nodes == [graph.root()] {
+        flags.push(UNCLASSIFIED);
+    }
+
+    flags
+}
+
+/// Prints the number of samples assigned to each node in depth-first order.
+///
+/// Nodes without any samples assigned within their subtree are skipped.
+fn summarize(graph: &Graph, counts: &HashMap<&str, usize>, name: &str, depth: usize) {
+    let direct = counts.get(name).copied().unwrap_or_default();
+    let total = direct
+        + graph
+            .descendants(name)
+            .iter()
+            .filter_map(|node| counts.get(node.name().inner()))
+            .sum::<usize>();
+
+    if total == 0 {
+        return;
+    }
+
+    let label = format!("{}{name}", "  ".repeat(depth + 1));
+    println!("{label:<48}{direct:>6}{total:>8}");
+
+    for child in graph.children(name) {
+        summarize(graph, counts, child.name().inner(), depth + 1);
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.ecc))?
+        .into_iter()
+        .map(|(_, c)| c)
+        .collect::<CharacteristicSet>();
+    let (graph, _) = tree::load_ontology_graph(&config.require_ontology(args.ontology)?)?;
+
+    let mut reader = super::reader(&args.path)?;
+    let headers = reader.headers().context("reading the header row")?.clone();
+
+    let manifest = match &args.manifest {
+        Some(path) => super::load_manifest(path, &characteristics)?,
+        None => super::check::infer(&headers, &characteristics),
+    };
+
+    let columns = headers
+        .iter()
+        .map(|header| manifest.column(header).map(|c| c.identifier.clone()))
+        .collect::<Vec<_>>();
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(super::delimiter(&args.path))
+        .from_path(&args.output)
+        .with_context(|| format!("creating file: {}", args.output.display()))?;
+    writer.write_record(headers.iter().map(String::from).chain([
+        format!("{}_node", args.prefix),
+        format!("{}_code", args.prefix),
+        format!("{}_flags", args.prefix),
+    ]))?;
+
+    let mut rows = 0;
+    let mut counts = HashMap::<&str, usize>::new();
+    let mut flagged = HashMap::<&str, usize>::new();
+
+    for result in reader.records() {
+        let record = result.with_context(|| format!("reading file: {}", args.path.display()))?;
+        // SAFETY: records read from a string always have a position.
+        let line = record.position().unwrap().line();
+        rows += 1;
+
+        let annotations = record
+            .iter()
+            .zip(headers.iter().zip(&columns))
+            .filter(|(value, (header, _))| !manifest.is_missing(header, value))
+            .filter_map(|(value, (_, identifier))| {
+                identifier
+                    .as_ref()
+                    .map(|identifier| Annotation::new(identifier.clone(), value))
+            })
+            .collect::<AnnotationSet>();
+
+        let (nodes, codes, flags) = match annotations.validate_against(&characteristics) {
+            Ok(()) => {
+                let classification = ontology::classify::classify(&graph, &annotations);
+
+                for node in &classification.nodes {
+                    *counts.entry(node.name().inner()).or_default() += 1;
+                }
+
+                let mut flags = flags(&graph, &classification);
+                let context = annotate::Context {
+                    lineage: classification.lineage(&graph),
+                    ..Default::default()
+                };
+
+                if let Err(errors) = annotations.validate_in(&characteristics, &context) {
+                    for error in errors {
+                        warn!("{}:{line}: {error}", args.path.display());
+                    }
+
+                    flags.push(INAPPLICABLE);
+                }
+
+                (
+                    classification
+                        .nodes
+                        .iter()
+                        .map(|node| node.name().inner())
+                        .collect::<Vec<_>>(),
+                    classification
+                        .nodes
+                        .iter()
+                        .map(|node| node.code())
+                        .collect::<Vec<_>>(),
+                    flags,
+                )
+            }
+            Err(errors) => {
+                warn!(
+                    "{}:{line}: not classified ({} invalid annotations)",
+                    args.path.display(),
+                    errors.len()
+                );
+                (Vec::new(), Vec::new(), vec![INVALID])
+            }
+        };
+
+        for flag in &flags {
+            *flagged.entry(flag).or_default() += 1;
+        }
+
+        writer.write_record(record.iter().map(String::from).chain([
+            nodes.join("; "),
+            codes.join("; "),
+            flags.join("; "),
+        ]))?;
+    }
+
+    writer.flush()?;
+    info!("classified {rows} rows into `{}`", args.output.display());
+
+    println!("{} {rows} samples\n", "Classified".bold());
+    println!("{:<48}{:>6}{:>8}", "Node".bold(), "Direct", "Total");
+    summarize(&graph, &counts, graph.root().name().inner(), 0);
+
+    if !flagged.is_empty() {
+        println!("\n{:<48}{:>6}", "Flag".bold(), "Count");
+
+        for flag in [
+            AMBIGUOUS,
+            CONFLICT,
+            INCOMPLETE,
+            UNCLASSIFIED,
+            INAPPLICABLE,
+            INVALID,
+        ] {
+            if let Some(count) = flagged.get(flag) {
+                println!("  {flag:<46}{count:>6}");
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+//! Harmonization of cohort metadata files.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize as _;
+use ecc::CharacteristicSet;
+use ecc::harmonize::Harmonizer;
+use tracing::info;
+
+use crate::config::Config;
+use crate::tree;
+
+/// Harmonizes a cohort metadata file into canonical characteristic values.
+///
+/// Missing value codes are blanked, values are recoded according to the
+/// manifest, spellings of `true`/`false` and the case of categorical values
+/// are normalized, and numerical values are converted into the units of their
+/// characteristic. Binary values derived from a numerical column by a
+/// threshold are filled in when empty and reported when they disagree with
+/// it. The transformed table is written with the same delimiter as the input,
+/// and every change is written to a tab-separated provenance log (if
+/// requested). Cells that cannot be harmonized are reported and left
+/// unchanged, and the exit code is `1`.
+#[derive(Parser)]
+pub struct Args {
+    /// The cohort metadata file.
+    path: PathBuf,
+
+    /// The dataset manifest mapping the columns to characteristics.
+    #[arg(long)]
+    manifest: PathBuf,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    ecc: Option<PathBuf>,
+
+    /// The file to write the harmonized table to.
+    #[arg(short, long)]
+    output: PathBuf,
+
+    /// The file to write the provenance log to.
+    #[arg(long)]
+    log: Option<PathBuf>,
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.ecc))?
+        .into_iter()
+        .map(|(_, c)| c)
+        .collect::<CharacteristicSet>();
+    let manifest = super::load_manifest(&args.manifest, &characteristics)?;
+    let harmonizer = Harmonizer::new(&manifest, &characteristics)?;
+
+    let mut reader = super::reader(&args.path)?;
+    let headers = reader
+        .headers()
+        .context("reading the header row")?
+        .iter()
+        .map(String::from)
+        .collect::<Vec<_>>();
+
+    let rows = reader
+        .records()
+        .map(|record| record.map(|record| record.iter().map(String::from).collect()))
+        .collect::<Result<Vec<Vec<String>>, _>>()
+        .with_context(|| format!("reading file: {}", args.path.display()))?;
+
+    let table = harmonizer.table(&headers, rows);
+
+    let mut writer = csv::WriterBuilder::new()
+        .delimiter(super::delimiter(&args.path))
+        .from_path(&args.output)
+        .with_context(|| format!("creating file: {}", args.output.display()))?;
+    writer.write_record(&headers)?;
+    for row in &table.rows {
+        writer.write_record(row)?;
+    }
+    writer.flush()?;
+
+    if let Some(path) = &args.log {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(b'\t')
+            .from_path(path)
+            .with_context(|| format!("creating file: {}", path.display()))?;
+        writer.write_record(["line", "column", "from", "to", "reason"])?;
+        for change in &table.changes {
+            // NOTE: the header occupies the first line.
+            writer.write_record([
+                (change.row + 2).to_string(),
+                change.column.clone(),
+                change.from.clone(),
+                change.to.clone(),
+                change.reason.to_string(),
+            ])?;
+        }
+        writer.flush()?;
+    }
+
+    info!(
+        "harmonized {} rows with {} changes into `{}`",
+        table.rows.len(),
+        table.changes.len(),
+        args.output.display()
+    );
+
+    if table.failures.is_empty() {
+        return Ok(());
+    }
+
+    for failure in &table.failures {
+        println!(
+            "{}:{}: {}: column `{}`: {}",
+            args.path.display(),
+            failure.row + 2,
+            "error".red(),
+            failure.column,
+            failure.error
+        );
+    }
+
+    std::process::exit(crate::EXIT_FAILURE.into());
+}
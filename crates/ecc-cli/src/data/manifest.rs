@@ -0,0 +1,123 @@
+//! Generation of dataset manifests.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use ecc::Characteristic;
+use ecc::dataset::Column;
+use ecc::dataset::DatasetManifest;
+use tracing::info;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::tree;
+
+/// Suggests a dataset manifest for a cohort metadata file.
+///
+/// Each column header is compared against the identifier and name of every
+/// characteristic (ignoring case and punctuation), and the column is mapped to
+/// the most similar characteristic if the similarity meets the threshold. The
+/// suggested manifest should be reviewed before use.
+#[derive(Parser)]
+pub struct Args {
+    /// The cohort metadata file.
+    path: PathBuf,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    ecc: Option<PathBuf>,
+
+    /// The minimum similarity (between `0` and `1`) for a header to be mapped
+    /// to a characteristic.
+    #[arg(long, default_value_t = 0.8)]
+    threshold: f64,
+
+    /// The codes that stand in for missing values (may be repeated).
+    #[arg(long, value_name = "CODE")]
+    missing: Vec<String>,
+
+    /// The file to write the manifest to.
+    ///
+    /// Defaults to stdout.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// Normalizes a header or name for comparison.
+fn normalize(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Finds the characteristic most similar to a header.
+///
+/// Returns the characteristic along with the similarity.
+fn suggest<'a>(
+    header: &str,
+    characteristics: &'a [Characteristic],
+) -> Option<(&'a Characteristic, f64)> {
+    let header = normalize(header);
+
+    characteristics
+        .iter()
+        .filter(|c| c.identifier().is_some())
+        .filter_map(|c| {
+            let similarity = c
+                .identifier()
+                .map(|identifier| identifier.to_string())
+                .into_iter()
+                .chain(c.name().map(String::from))
+                .map(|candidate| strsim::jaro_winkler(&header, &normalize(&candidate)))
+                .max_by(f64::total_cmp)?;
+
+            Some((c, similarity))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.ecc))?
+        .into_iter()
+        .map(|(_, c)| c)
+        .collect::<Vec<_>>();
+
+    let headers = super::reader(&args.path)?
+        .headers()
+        .context("reading the header row")?
+        .clone();
+
+    let mut manifest = DatasetManifest {
+        missing: args.missing,
+        columns: Vec::new(),
+    };
+
+    for header in &headers {
+        match suggest(header, &characteristics) {
+            Some((characteristic, similarity)) if similarity >= args.threshold => {
+                // SAFETY: only characteristics with identifiers are suggested.
+                let identifier = characteristic.identifier().unwrap().clone();
+                info!("mapped column `{header}` to `{identifier}` (similarity {similarity:.2})");
+                manifest.columns.push(Column::new(header, identifier));
+            }
+            _ => warn!("no characteristic is similar to column `{header}`"),
+        }
+    }
+
+    let yaml = serde_yaml::to_string(&manifest).context("serializing manifest")?;
+
+    match args.output {
+        Some(path) => std::fs::write(&path, yaml)
+            .with_context(|| format!("writing manifest: {}", path.display())),
+        None => {
+            print!("{yaml}");
+            Ok(())
+        }
+    }
+}
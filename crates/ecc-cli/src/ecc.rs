@@ -0,0 +1,90 @@
+//! Characteristic-related facilities.
+
+use clap::Parser;
+use clap::Subcommand;
+
+use crate::config::Config;
+
+mod assign_id;
+pub mod diff;
+pub mod export;
+mod import_issue;
+mod init;
+mod list;
+mod migrate;
+mod promote;
+mod rfc_body;
+mod show;
+mod stale;
+pub mod stats;
+mod stewards;
+
+/// Work with the composable characteristics within the ECC.
+#[derive(Parser)]
+pub struct Args {
+    /// The command to run.
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The command to run.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Assigns the next available identifier to a characteristic.
+    AssignId(assign_id::Args),
+
+    /// Summarizes the changes to the characteristics between two revisions.
+    Diff(diff::Args),
+
+    /// Exports the characteristic tree into a machine-readable format.
+    Export(export::Args),
+
+    /// Creates a draft characteristic from a proposal in a GitHub issue.
+    ImportIssue(import_issue::Args),
+
+    /// Creates a new draft characteristic.
+    Init(init::Args),
+
+    /// Lists the characteristics within the tree.
+    List(list::Args),
+
+    /// Upgrades characteristic files to the current schema version.
+    Migrate(migrate::Args),
+
+    /// Promotes a characteristic to the next state within its lifecycle.
+    Promote(promote::Args),
+
+    /// Renders a characteristic as the body of an RFC issue.
+    RfcBody(rfc_body::Args),
+
+    /// Shows a single characteristic.
+    Show(show::Args),
+
+    /// Reports drafts that have not been updated recently.
+    Stale(stale::Args),
+
+    /// Prints statistics about the characteristic tree.
+    Stats(stats::Args),
+
+    /// Groups the open drafts and proposals by steward.
+    Stewards(stewards::Args),
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    match args.command {
+        Command::AssignId(args) => assign_id::main(args, config),
+        Command::Diff(args) => diff::main(args),
+        Command::Export(args) => export::main(args, config),
+        Command::ImportIssue(args) => import_issue::main(args, config),
+        Command::Init(args) => init::main(args, config),
+        Command::List(args) => list::main(args, config),
+        Command::Migrate(args) => migrate::main(args, config),
+        Command::Promote(args) => promote::main(args, config),
+        Command::RfcBody(args) => rfc_body::main(args, config),
+        Command::Show(args) => show::main(args, config),
+        Command::Stale(args) => stale::main(args, config),
+        Command::Stats(args) => stats::main(args, config),
+        Command::Stewards(args) => stewards::main(args, config),
+    }
+}
@@ -0,0 +1,146 @@
+//! Assignment of identifiers to characteristics.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::Parser;
+use ecc::Category;
+use ecc::Identifier;
+use tracing::info;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The top-level key for identifiers within a characteristic file.
+const KEY: &str = "identifier:";
+
+/// Assigns the next available identifier to a characteristic.
+///
+/// The characteristic tree is scanned to find the highest identifier in use
+/// within the characteristic's category, and the next number is written into
+/// the file. The rest of the file is left untouched.
+#[derive(Parser)]
+pub struct Args {
+    /// The characteristic file to assign an identifier to.
+    file: PathBuf,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// The category of the characteristic.
+    ///
+    /// If not provided, the category is inferred from the directory the file
+    /// resides in (e.g., a file within `ecc/molec` is molecular). The
+    /// directory names for each category can be configured in `ecc.toml`.
+    #[arg(long)]
+    category: Option<Category>,
+
+    /// Prints the identifier that would be assigned without modifying the
+    /// file.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Inserts an identifier into the contents of a characteristic file.
+///
+/// If a top-level `identifier` key without a value exists, it is replaced.
+/// Otherwise, the identifier is inserted after the `name` key (or the `state`
+/// key if no name exists).
+fn insert(contents: &str, identifier: &Identifier) -> anyhow::Result<String> {
+    let line = format!("{KEY} {identifier}");
+    let mut lines = contents.lines().map(String::from).collect::<Vec<_>>();
+
+    if let Some(existing) = lines.iter_mut().find(|line| line.starts_with(KEY)) {
+        let value = existing[KEY.len()..].trim();
+
+        if !(value.is_empty() || value == "~" || value == "null") {
+            bail!("the characteristic already has an identifier: {value}");
+        }
+
+        *existing = line;
+    } else {
+        let position = lines
+            .iter()
+            .position(|line| line.starts_with("name:"))
+            .or_else(|| lines.iter().position(|line| line.starts_with("state:")))
+            .map(|i| i + 1)
+            .unwrap_or(0);
+
+        lines.insert(position, line);
+    }
+
+    let mut result = lines.join("\n");
+
+    if contents.ends_with('\n') {
+        result.push('\n');
+    }
+
+    Ok(result)
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let contents = tree::read(&args.file)?;
+    let characteristic = ecc::io::from_str(&contents)
+        .with_context(|| format!("parsing characteristic: {}", args.file.display()))?;
+
+    if let Some(identifier) = characteristic.identifier() {
+        bail!("the characteristic already has an identifier: {identifier}");
+    }
+
+    let category = match args.category.or_else(|| config.infer_category(&args.file)) {
+        Some(category) => category,
+        None => bail!(
+            "unable to infer the category from `{}`; please provide `--category`",
+            args.file.display()
+        ),
+    };
+
+    let characteristics = tree::load(&config.characteristics(args.root))?;
+    let mut used = HashMap::new();
+
+    for (path, characteristic) in &characteristics {
+        if let Some(identifier) = characteristic.identifier() {
+            if let Some(other) = used.insert(identifier, path) {
+                warn!(
+                    "identifier {identifier} is used by both `{}` and `{}`",
+                    other.display(),
+                    path.display()
+                );
+            }
+        }
+    }
+
+    let Some(identifier) = Identifier::next_available(category, used.keys().copied()) else {
+        bail!("no identifiers left in category {}", category.code());
+    };
+
+    let updated = insert(&contents, &identifier)?;
+
+    // Ensure the file still parses and contains the new identifier before
+    // writing it out.
+    let reparsed = ecc::io::from_str(&updated)
+        .context("parsing the characteristic after assigning the identifier")?;
+
+    if reparsed.identifier() != Some(&identifier) {
+        bail!("the identifier could not be written into the file");
+    }
+
+    if args.dry_run {
+        println!("{identifier}");
+        return Ok(());
+    }
+
+    std::fs::write(&args.file, updated)
+        .with_context(|| format!("writing file: {}", args.file.display()))?;
+    info!("assigned {identifier} to `{}`", args.file.display());
+    println!("{identifier}");
+
+    Ok(())
+}
@@ -0,0 +1,193 @@
+//! Differences between two revisions of the characteristic tree.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::bail;
+use clap::Parser;
+use colored::Colorize as _;
+use ecc::Characteristic;
+use ecc::diff::Change;
+use ecc::diff::Modification;
+use ecc::diff::ValueChange;
+
+use crate::git;
+use crate::tree;
+
+/// Summarizes the changes to the characteristics between two revisions.
+///
+/// Either two directories can be compared directly, or a single directory can
+/// be compared against itself at a given git revision by passing `--base`.
+#[derive(Parser)]
+pub struct Args {
+    /// The characteristic directories to compare.
+    ///
+    /// When two directories are provided, the first is treated as the tree
+    /// before the changes and the second as the tree after the changes. When
+    /// `--base` is provided, only the directory after the changes should be
+    /// given.
+    #[arg(required = true, num_args = 1..=2)]
+    paths: Vec<PathBuf>,
+
+    /// A git revision to compare the directory against.
+    ///
+    /// The revision is checked out into a temporary worktree that is removed
+    /// once the comparison is complete.
+    #[arg(long)]
+    base: Option<String>,
+}
+
+/// Loads the characteristics within a directory.
+fn load(path: &Path) -> anyhow::Result<Vec<Characteristic>> {
+    Ok(tree::load(path)?.into_iter().map(|(_, c)| c).collect())
+}
+
+/// Gets a short label for a characteristic.
+fn label(characteristic: &Characteristic) -> String {
+    let identifier = characteristic
+        .identifier()
+        .map(|identifier| identifier.to_string())
+        .unwrap_or_else(|| String::from("<unassigned>"));
+    let name = characteristic.name().unwrap_or("<unnamed>");
+
+    format!("{} {name}", identifier.bold())
+}
+
+/// Formats a list of options.
+fn options(options: &[&str]) -> String {
+    options
+        .iter()
+        .map(|option| format!("`{option}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Describes a single modification.
+pub fn describe(modification: &Modification<'_>) -> String {
+    /// Formats an optional value.
+    fn or_none(value: Option<impl ToString>) -> String {
+        value
+            .map(|v| format!("`{}`", v.to_string()))
+            .unwrap_or_else(|| String::from("none"))
+    }
+
+    match modification {
+        Modification::State { from, to } => format!("state: {from} → {to}"),
+        Modification::Identifier { from, to } => {
+            format!("identifier: {} → {}", or_none(*from), or_none(*to))
+        }
+        Modification::Name { from, to } => {
+            format!("name: {} → {}", or_none(*from), or_none(*to))
+        }
+        Modification::Description => String::from("description changed"),
+        Modification::Values(ValueChange::Kind { from, to }) => format!(
+            "values: {} → {}",
+            or_none(from.map(|kind| kind.name())),
+            or_none(to.map(|kind| kind.name()))
+        ),
+        Modification::Values(ValueChange::Options { added, removed }) => {
+            let mut parts = Vec::new();
+
+            if !added.is_empty() {
+                parts.push(format!("added {}", options(added)));
+            }
+
+            if !removed.is_empty() {
+                parts.push(format!("removed {}", options(removed)));
+            }
+
+            format!("values: {}", parts.join("; "))
+        }
+        Modification::Values(ValueChange::Details) => String::from("values: details changed"),
+        Modification::References => String::from("references changed"),
+    }
+}
+
+/// Prints a summary of the changes.
+fn print(changes: &[Change<'_>]) {
+    if changes.is_empty() {
+        println!("No changes to characteristics.");
+        return;
+    }
+
+    let added = changes
+        .iter()
+        .filter_map(|change| match change {
+            Change::Added(c) => Some(*c),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let removed = changes
+        .iter()
+        .filter_map(|change| match change {
+            Change::Removed(c) => Some(*c),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let modified = changes
+        .iter()
+        .filter_map(|change| match change {
+            Change::Modified {
+                after,
+                modifications,
+                ..
+            } => Some((*after, modifications)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if !added.is_empty() {
+        println!("{} ({})", "Added".green().bold(), added.len());
+
+        for characteristic in added {
+            println!("  + {}", label(characteristic));
+        }
+
+        println!();
+    }
+
+    if !modified.is_empty() {
+        println!("{} ({})", "Modified".yellow().bold(), modified.len());
+
+        for (characteristic, modifications) in modified {
+            println!("  ~ {}", label(characteristic));
+
+            for modification in modifications {
+                println!("      {}", describe(modification));
+            }
+        }
+
+        println!();
+    }
+
+    if !removed.is_empty() {
+        println!("{} ({})", "Removed".red().bold(), removed.len());
+
+        for characteristic in removed {
+            println!("  - {}", label(characteristic));
+        }
+
+        println!();
+    }
+}
+
+/// The main method.
+pub fn main(args: Args) -> anyhow::Result<()> {
+    let (before, after) = match (args.base, args.paths.as_slice()) {
+        (Some(base), [path]) => {
+            let before = git::load_at(path, &base)?
+                .into_iter()
+                .map(|(_, c)| c)
+                .collect();
+
+            (before, load(path)?)
+        }
+        (None, [before, after]) => (load(before)?, load(after)?),
+        (Some(_), _) => bail!("exactly one directory must be provided when using `--base`"),
+        (None, _) => bail!("two directories must be provided when `--base` is not used"),
+    };
+
+    print(&ecc::diff::diff(&before, &after));
+
+    Ok(())
+}
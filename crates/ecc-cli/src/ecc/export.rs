@@ -0,0 +1,501 @@
+//! Exporting of the characteristic tree.
+
+use std::borrow::Borrow;
+use std::fs::File;
+use std::io::BufWriter;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::Parser;
+use clap::ValueEnum;
+use ecc::Characteristic;
+use ecc::common::Reference;
+use ecc::common::Sensitivity;
+use ecc::common::value::Kind;
+use ecc::query::Filter;
+use serde::Deserialize;
+use serde::Serializer as _;
+use serde::ser::SerializeSeq as _;
+use tracing::info;
+
+use crate::Error;
+use crate::config::Config;
+use crate::tree;
+
+/// The base name for exported files.
+const FILE_STEM: &str = "characteristics";
+
+/// The format to export to.
+#[derive(Clone, Copy, Debug, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    /// A single JSON document containing every characteristic.
+    Json,
+
+    /// Newline-delimited JSON with one characteristic per line.
+    Ndjson,
+
+    /// A flattened, comma-separated table with one characteristic per row.
+    Csv,
+
+    /// A flattened Apache Parquet table with one characteristic per row.
+    #[cfg(feature = "parquet")]
+    Parquet,
+
+    /// A FHIR R4 bundle of `CodeSystem` and `ValueSet` resources describing
+    /// the adopted characteristics (and the ontology, if configured).
+    #[cfg(feature = "fhir")]
+    Fhir,
+
+    /// GA4GH Phenopacket building blocks (`OntologyClass` and `Measurement`
+    /// templates) defining the adopted characteristics.
+    #[cfg(feature = "phenopackets")]
+    Phenopackets,
+
+    /// An RDF graph in the Turtle syntax describing the characteristics with
+    /// the ECC vocabulary (which is included).
+    #[cfg(feature = "rdf")]
+    Rdf,
+
+    /// An SSSOM mapping set (in the TSV format) of the cross-reference
+    /// mappings from the characteristics to external terminologies.
+    #[cfg(feature = "sssom")]
+    Sssom,
+
+    /// A normalized SQLite database of the characteristics, their values and
+    /// references, and the ontology (if configured).
+    #[cfg(feature = "sqlite")]
+    Sqlite,
+}
+
+impl Format {
+    /// Gets the file extension for the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Ndjson => "ndjson",
+            Format::Csv => "csv",
+            #[cfg(feature = "parquet")]
+            Format::Parquet => "parquet",
+            #[cfg(feature = "fhir")]
+            Format::Fhir => "fhir.json",
+            #[cfg(feature = "phenopackets")]
+            Format::Phenopackets => "phenopackets.json",
+            #[cfg(feature = "rdf")]
+            Format::Rdf => "ttl",
+            #[cfg(feature = "sssom")]
+            Format::Sssom => "sssom.tsv",
+            #[cfg(feature = "sqlite")]
+            Format::Sqlite => "sqlite",
+        }
+    }
+}
+
+/// Exports the characteristic tree into a machine-readable format.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long, alias = "path")]
+    root: Option<PathBuf>,
+
+    /// The format to export to.
+    ///
+    /// Defaults to the format configured in `ecc.toml` (or JSON).
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// The directory to write the exported file to.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(short, long)]
+    output_directory: Option<PathBuf>,
+
+    /// The most restricted sensitivity to export (`public`, `controlled`, or
+    /// `restricted`).
+    ///
+    /// Characteristics that are more restricted are left out of the export.
+    /// Defaults to the sensitivity configured in `ecc.toml` (or `public`).
+    #[arg(long)]
+    max_sensitivity: Option<Sensitivity>,
+
+    /// Only exports the characteristics matching the filter (e.g.,
+    /// `tag:pediatric`).
+    ///
+    /// Uses the same syntax as `ecc list --filter`.
+    #[arg(long)]
+    filter: Option<Filter>,
+
+    /// The path to the ontology directory (only used by the `fhir` and
+    /// `sqlite` formats).
+    ///
+    /// Defaults to the directory configured in `ecc.toml` (if any).
+    #[cfg(any(feature = "fhir", feature = "sqlite"))]
+    #[arg(long)]
+    ontology: Option<PathBuf>,
+}
+
+/// The columns within a flattened characteristic row.
+const COLUMNS: [&str; 14] = [
+    "identifier",
+    "name",
+    "state",
+    "rfc",
+    "description",
+    "kind",
+    "options",
+    "numerical_type",
+    "units",
+    "adoption_date",
+    "references",
+    "evidence_level",
+    "sensitivity",
+    "tags",
+];
+
+/// The separator used when joining multiple values into a single cell.
+const SEPARATOR: &str = ";";
+
+/// A flattened characteristic.
+///
+/// Each cell corresponds to the column at the same position in [`COLUMNS`].
+struct Row([Option<String>; COLUMNS.len()]);
+
+impl Row {
+    /// Flattens a characteristic into a row.
+    fn new(characteristic: &Characteristic) -> Self {
+        let values = characteristic.values();
+
+        let options = values.and_then(|kind| match kind {
+            Kind::Categorical { options } => Some(
+                options
+                    .iter()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(SEPARATOR),
+            ),
+            _ => None,
+        });
+
+        let (numerical_type, units) = match values {
+            Some(Kind::Numerical { r#type, units }) => {
+                (Some(r#type.to_string()), Some(units.as_str()))
+            }
+            Some(Kind::Fraction { scale, .. }) => (None, Some(scale.units())),
+            _ => (None, None),
+        };
+
+        let references = characteristic.references().map(|references| {
+            references
+                .map(|reference| match reference {
+                    Reference::Manuscript { url, .. } | Reference::Preprint { url, .. } => {
+                        url.as_str()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(SEPARATOR)
+        });
+
+        Self([
+            characteristic.identifier().map(|i| i.to_string()),
+            characteristic.name().map(String::from),
+            Some(characteristic.state().to_string()),
+            characteristic.rfc().map(|rfc| rfc.to_string()),
+            characteristic.description().map(String::from),
+            values.map(|kind| String::from(kind.name())),
+            options,
+            numerical_type,
+            units.map(String::from),
+            characteristic.adoption_date().map(|date| date.to_rfc3339()),
+            references,
+            characteristic
+                .evidence_level()
+                .map(|level| level.to_string()),
+            Some(characteristic.sensitivity().to_string()),
+            Some(
+                characteristic
+                    .tags()
+                    .iter()
+                    .map(|tag| tag.as_str())
+                    .collect::<Vec<_>>()
+                    .join(SEPARATOR),
+            ),
+        ])
+    }
+}
+
+/// Writes the characteristics as a single JSON document.
+///
+/// The characteristics are written as they are read, so the whole tree is
+/// never held in memory (the same is true of [`ndjson()`] and [`csv()`]).
+fn json<C: Borrow<Characteristic>>(
+    characteristics: impl IntoIterator<Item = Result<C, Error>>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let mut writer = File::create(path).map(BufWriter::new)?;
+    let mut serializer = serde_json::Serializer::pretty(&mut writer);
+    let mut seq = serializer
+        .serialize_seq(None)
+        .context("serializing to JSON")?;
+
+    for characteristic in characteristics {
+        seq.serialize_element(characteristic?.borrow())
+            .context("serializing to JSON")?;
+    }
+
+    seq.end().context("serializing to JSON")?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the characteristics as newline-delimited JSON.
+fn ndjson<C: Borrow<Characteristic>>(
+    characteristics: impl IntoIterator<Item = Result<C, Error>>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let mut writer = File::create(path).map(BufWriter::new)?;
+
+    for characteristic in characteristics {
+        serde_json::to_writer(&mut writer, characteristic?.borrow())
+            .context("serializing to JSON")?;
+        writeln!(writer)?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the characteristics as a flattened CSV table.
+fn csv<C: Borrow<Characteristic>>(
+    characteristics: impl IntoIterator<Item = Result<C, Error>>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record(COLUMNS)?;
+
+    for characteristic in characteristics {
+        let Row(cells) = Row::new(characteristic?.borrow());
+        writer.write_record(cells.iter().map(|cell| cell.as_deref().unwrap_or_default()))?;
+    }
+
+    writer.flush()?;
+    Ok(())
+}
+
+/// Writes the characteristics as a flattened Parquet table.
+#[cfg(feature = "parquet")]
+fn parquet(characteristics: &[Characteristic], path: &Path) -> anyhow::Result<()> {
+    use std::sync::Arc;
+
+    use parquet::data_type::ByteArray;
+    use parquet::data_type::ByteArrayType;
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    let schema = COLUMNS
+        .iter()
+        .map(|column| format!("OPTIONAL BYTE_ARRAY {column} (UTF8);"))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let schema = Arc::new(parse_message_type(&format!(
+        "message characteristic {{ {schema} }}"
+    ))?);
+
+    let rows = characteristics.iter().map(Row::new).collect::<Vec<_>>();
+
+    let file = File::create(path)?;
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut writer =
+        SerializedFileWriter::new(file, schema, properties).context("creating Parquet writer")?;
+    let mut row_group = writer.next_row_group()?;
+
+    for index in 0..COLUMNS.len() {
+        let Some(mut column) = row_group.next_column()? else {
+            break;
+        };
+
+        let cells = rows.iter().map(|Row(cells)| cells[index].as_deref());
+        let levels = cells
+            .clone()
+            .map(|cell| i16::from(cell.is_some()))
+            .collect::<Vec<_>>();
+        let values = cells.flatten().map(ByteArray::from).collect::<Vec<_>>();
+
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&values, Some(&levels), None)?;
+        column.close()?;
+    }
+
+    row_group.close()?;
+    writer.close()?;
+
+    Ok(())
+}
+
+/// Writes the characteristics (and the ontology, if provided) as a SQLite
+/// database.
+///
+/// Any existing file at the path is replaced.
+#[cfg(feature = "sqlite")]
+fn sqlite(
+    characteristics: &[Characteristic],
+    graph: Option<&ontology::Graph>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    if path.exists() {
+        std::fs::remove_file(path)
+            .with_context(|| format!("removing existing database: {}", path.display()))?;
+    }
+
+    let mut store = ecc_store::Store::create(path).context("creating database")?;
+    store.insert_characteristics(characteristics)?;
+
+    if let Some(graph) = graph {
+        store.insert_ontology(graph)?;
+    }
+
+    Ok(())
+}
+
+/// Loads the ontology graph (if one is provided or configured).
+#[cfg(any(feature = "fhir", feature = "sqlite"))]
+fn ontology(path: Option<PathBuf>, config: &Config) -> anyhow::Result<Option<ontology::Graph>> {
+    let graph = config
+        .ontology(path)
+        .map(|root| tree::load_ontology_graph(&root).map(|(graph, _)| graph))
+        .transpose()?;
+    Ok(graph)
+}
+
+/// Writes the characteristics to a file in the given format.
+///
+/// FHIR bundles and SQLite databases written by this function do not include
+/// the ontology (see [`crate::interop::fhir::write()`] and [`sqlite()`]).
+pub fn write(
+    characteristics: &[Characteristic],
+    format: Format,
+    path: &Path,
+) -> anyhow::Result<()> {
+    match format {
+        Format::Json => json(characteristics.iter().map(Ok), path),
+        Format::Ndjson => ndjson(characteristics.iter().map(Ok), path),
+        Format::Csv => csv(characteristics.iter().map(Ok), path),
+        #[cfg(feature = "parquet")]
+        Format::Parquet => parquet(characteristics, path),
+        #[cfg(feature = "fhir")]
+        Format::Fhir => crate::interop::fhir::write(characteristics, None, path),
+        #[cfg(feature = "phenopackets")]
+        Format::Phenopackets => crate::interop::phenopackets::write(characteristics, path),
+        #[cfg(feature = "rdf")]
+        Format::Rdf => crate::interop::rdf::write(characteristics, path),
+        #[cfg(feature = "sssom")]
+        Format::Sssom => crate::interop::sssom::write(characteristics, path),
+        #[cfg(feature = "sqlite")]
+        Format::Sqlite => sqlite(characteristics, None, path),
+    }
+    .with_context(|| format!("writing export to {}", path.display()))
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let format = args.format.unwrap_or(config.export.format);
+    let output_directory = match args.output_directory.or_else(|| {
+        config
+            .export
+            .output_directory
+            .as_deref()
+            .map(|path| config.resolve(path))
+    }) {
+        Some(path) => path,
+        None => bail!("no output directory was provided and none is configured in `ecc.toml`"),
+    };
+
+    std::fs::create_dir_all(&output_directory)
+        .with_context(|| format!("creating output directory: {}", output_directory.display()))?;
+
+    let path = output_directory
+        .join(FILE_STEM)
+        .with_extension(format.extension());
+
+    let root = config.characteristics(args.root);
+    let max_sensitivity = args
+        .max_sensitivity
+        .unwrap_or(config.export.max_sensitivity);
+    let filter = args.filter.unwrap_or_default();
+    let mut count = 0;
+    let mut withheld = 0;
+
+    // NOTE: the JSON, NDJSON, and CSV formats are written as the tree is
+    // streamed so that large trees are never loaded into memory all at once.
+    // The other formats require every characteristic up front.
+    let stream = tree::stream(&root)?.filter_map(|(_, result)| {
+        if let Ok(characteristic) = &result {
+            if !filter.matches(characteristic) {
+                return None;
+            }
+
+            if tree::is_withheld(characteristic, max_sensitivity) {
+                withheld += 1;
+                return None;
+            }
+        }
+
+        count += 1;
+        Some(result)
+    });
+
+    let result = match format {
+        Format::Json => json(stream, &path),
+        Format::Ndjson => ndjson(stream, &path),
+        Format::Csv => csv(stream, &path),
+        #[cfg(feature = "parquet")]
+        Format::Parquet => parquet(&stream.collect::<Result<Vec<_>, _>>()?, &path),
+        // NOTE: the ontology is included within FHIR bundles and SQLite
+        // databases when one is configured.
+        #[cfg(feature = "fhir")]
+        Format::Fhir => crate::interop::fhir::write(
+            &stream.collect::<Result<Vec<_>, _>>()?,
+            ontology(args.ontology, config)?.as_ref(),
+            &path,
+        ),
+        #[cfg(feature = "phenopackets")]
+        Format::Phenopackets => {
+            crate::interop::phenopackets::write(&stream.collect::<Result<Vec<_>, _>>()?, &path)
+        }
+        #[cfg(feature = "rdf")]
+        Format::Rdf => crate::interop::rdf::write(&stream.collect::<Result<Vec<_>, _>>()?, &path),
+        #[cfg(feature = "sssom")]
+        Format::Sssom => {
+            crate::interop::sssom::write(&stream.collect::<Result<Vec<_>, _>>()?, &path)
+        }
+        #[cfg(feature = "sqlite")]
+        Format::Sqlite => sqlite(
+            &stream.collect::<Result<Vec<_>, _>>()?,
+            ontology(args.ontology, config)?.as_ref(),
+            &path,
+        ),
+    };
+
+    if result.is_err() {
+        // NOTE: a characteristic that cannot be parsed is only found partway
+        // through a streamed export, so the incomplete file is removed.
+        let _ = std::fs::remove_file(&path);
+    }
+
+    result.with_context(|| format!("writing export to {}", path.display()))?;
+    info!("exported {count} characteristics to `{}`", path.display());
+
+    if withheld > 0 {
+        info!(
+            "withheld {withheld} characteristics that are more restricted than `{max_sensitivity}`"
+        );
+    }
+
+    Ok(())
+}
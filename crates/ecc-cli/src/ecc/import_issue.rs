@@ -0,0 +1,488 @@
+//! Importing of draft characteristics from GitHub issues.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use chrono::Utc;
+use clap::Parser;
+use ecc::Category;
+use ecc::Characteristic;
+use ecc::common::OptionalCommon;
+use ecc::common::Reference;
+use ecc::rfc;
+use ecc::text::Sentence;
+use nonempty::NonEmpty;
+use ontology::node::Name;
+use serde::Deserialize;
+use tracing::debug;
+use tracing::info;
+use tracing::warn;
+use url::Url;
+
+use crate::config::Config;
+use crate::net;
+use crate::net::Client;
+use crate::net::GITHUB_API_HOST;
+use crate::net::Request;
+
+/// The value GitHub renders for an issue form field that was left empty.
+const NO_RESPONSE: &str = "_No response_";
+
+/// The prefix of the titles of issues created from the issue form.
+const TITLE_PREFIX: &str = "[ECC]";
+
+/// Hosts that serve preprints rather than peer-reviewed manuscripts.
+const PREPRINT_HOSTS: &[&str] = &["arxiv.org", "biorxiv.org", "medrxiv.org"];
+
+/// Creates a draft characteristic from a proposal submitted as a GitHub issue.
+///
+/// The issue is fetched using the GitHub API and the sections of the issue
+/// form (name, category, description, and references) are used to fill in the
+/// draft. The issue is recorded as the RFC of the characteristic when it is
+/// filed against the ECC repository. Anything that cannot be parsed is left
+/// out of the draft for curation.
+#[derive(Parser)]
+pub struct Args {
+    /// The URL of the issue (e.g.,
+    /// `https://github.com/stjudecloud/ecc/issues/1`).
+    url: Url,
+
+    /// A GitHub token used to authenticate the request.
+    ///
+    /// This is only needed for private repositories or to avoid rate limits.
+    #[arg(long, env = net::GITHUB_TOKEN, hide_env_values = true)]
+    token: Option<String>,
+
+    /// The category of the characteristic.
+    ///
+    /// Overrides the category selected within the issue.
+    #[arg(long)]
+    category: Option<Category>,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// The file to write the characteristic to.
+    ///
+    /// Defaults to a file named after the characteristic within the category's
+    /// directory (e.g., `ecc/molec/my-characteristic.yml`).
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// The fields of an issue returned by the GitHub API.
+#[derive(Deserialize)]
+struct Issue {
+    /// The title of the issue.
+    title: String,
+
+    /// The body of the issue (if any).
+    body: Option<String>,
+
+    /// The URL of the issue on the web.
+    html_url: String,
+}
+
+/// The sections of an issue form.
+#[derive(Default)]
+struct Sections {
+    /// The proposed name.
+    name: Option<String>,
+
+    /// The proposed category.
+    category: Option<String>,
+
+    /// The description.
+    description: Option<String>,
+
+    /// The references.
+    references: Option<String>,
+}
+
+impl Sections {
+    /// Parses the sections from the body of an issue.
+    ///
+    /// Issue forms render each field as a level-three heading containing the
+    /// field's label followed by the response. Sections are matched by a
+    /// keyword within the label, so both the labels of the issue form and
+    /// plain headings (e.g., `### References`) are recognized.
+    fn parse(body: &str) -> Self {
+        let mut sections = Self::default();
+        let mut current: Option<(String, Vec<&str>)> = None;
+
+        for line in body.lines().chain(std::iter::once("### ")) {
+            if let Some(heading) = line.strip_prefix("### ") {
+                if let Some((label, lines)) = current.take() {
+                    sections.insert(&label, &lines.join("\n"));
+                }
+
+                current = Some((heading.trim().to_lowercase(), Vec::new()));
+            } else if let Some((_, lines)) = &mut current {
+                lines.push(line);
+            }
+        }
+
+        sections
+    }
+
+    /// Records the response for a section (unless it was left empty).
+    fn insert(&mut self, label: &str, value: &str) {
+        let value = value.trim();
+
+        if value.is_empty() || value == NO_RESPONSE {
+            return;
+        }
+
+        let field = if label.contains("name") {
+            &mut self.name
+        } else if label.contains("category") {
+            &mut self.category
+        } else if label.contains("description") {
+            &mut self.description
+        } else if label.contains("reference") {
+            &mut self.references
+        } else {
+            debug!("ignoring issue section `{label}`");
+            return;
+        };
+
+        *field = Some(value.to_string());
+    }
+}
+
+/// Parses the owner, repository, and number from the URL of an issue.
+fn parse_url(url: &Url) -> anyhow::Result<(String, String, u64)> {
+    let segments = url
+        .path_segments()
+        .map(|segments| segments.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+        .unwrap_or_default();
+
+    match (url.host_str(), segments.as_slice()) {
+        (Some("github.com"), [owner, repo, "issues", number]) => {
+            let number = number
+                .parse()
+                .with_context(|| format!("parsing issue number: `{number}`"))?;
+            Ok((owner.to_string(), repo.to_string(), number))
+        }
+        _ => bail!(
+            "invalid issue URL: `{url}`; expected `https://github.com/<owner>/<repo>/issues/<number>`"
+        ),
+    }
+}
+
+/// Fetches an issue using the GitHub API.
+async fn fetch(client: &impl Client, url: &Url) -> anyhow::Result<Issue> {
+    let (owner, repo, number) = parse_url(url)?;
+    let endpoint = format!("https://{GITHUB_API_HOST}/repos/{owner}/{repo}/issues/{number}")
+        .parse::<Url>()
+        .context("building the API URL")?;
+
+    let request = Request::get(endpoint.clone()).header("Accept", "application/vnd.github+json");
+    let response = client
+        .send(request)
+        .await
+        .and_then(|response| response.error_for_status(&endpoint))
+        .with_context(|| format!("fetching issue: {url}"))?;
+
+    Ok(response.json(&endpoint)?)
+}
+
+/// Parses a single reference from an item in a Markdown list.
+///
+/// Both Markdown links (`[Title](https://...)`) and bare URLs (optionally
+/// surrounded by the title) are accepted. The authors are left empty for
+/// curation.
+fn reference(item: &str, context: &Sentence) -> Option<Reference> {
+    let (title, url) = match item.split_once("](") {
+        Some((title, rest)) if title.starts_with('[') => {
+            let url = rest.split_once(')').map(|(url, _)| url)?;
+            (title[1..].trim().to_string(), url.trim())
+        }
+        _ => {
+            let url = item
+                .split_whitespace()
+                .find(|word| word.starts_with("http://") || word.starts_with("https://"))?;
+            let title = item.replace(url, "");
+            let title = title.trim().trim_end_matches([':', '-']).trim();
+            (title.to_string(), url.trim_end_matches(['.', ',', ')']))
+        }
+    };
+
+    let url = url.parse::<Url>().ok()?;
+    let title = if title.is_empty() {
+        url.to_string()
+    } else {
+        title
+    };
+
+    let preprint = url.host_str().is_some_and(|host| {
+        PREPRINT_HOSTS
+            .iter()
+            .any(|preprint| host == *preprint || host.ends_with(&format!(".{preprint}")))
+    });
+
+    let (authors, context, highlighted) = (String::new(), context.clone(), false);
+
+    Some(if preprint {
+        Reference::Preprint {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        }
+    } else {
+        Reference::Manuscript {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        }
+    })
+}
+
+/// Parses the references from the references section of an issue.
+fn references(section: &str, issue: &str) -> Vec<Reference> {
+    // SAFETY: this sentence is never empty.
+    let context = format!("Cited in the proposal at {issue}.")
+        .parse::<Sentence>()
+        .unwrap();
+
+    section
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .filter_map(|line| {
+            let item = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| {
+                    line.split_once(". ")
+                        .filter(|(n, _)| n.chars().all(|c| c.is_ascii_digit()))
+                        .map(|(_, item)| item)
+                })
+                .unwrap_or(line)
+                .trim();
+
+            let reference = reference(item, &context);
+
+            if reference.is_none() {
+                warn!("skipping reference without a valid URL: `{item}`");
+            }
+
+            reference
+        })
+        .collect()
+}
+
+/// Creates a draft characteristic from an issue.
+///
+/// Returns the category of the characteristic (preferring the provided
+/// category over the one selected within the issue) along with the draft.
+fn draft(
+    issue: &Issue,
+    category: Option<Category>,
+    config: &Config,
+) -> anyhow::Result<(Category, Characteristic)> {
+    let sections = Sections::parse(issue.body.as_deref().unwrap_or_default());
+
+    let name = match sections.name {
+        Some(name) => name,
+        None => {
+            let title = issue.title.trim();
+            let name = title.strip_prefix(TITLE_PREFIX).unwrap_or(title).trim();
+
+            if name.is_empty() {
+                bail!("the issue does not contain a characteristic name");
+            }
+
+            name.to_string()
+        }
+    };
+
+    if let Err(err) = Name::parse_with_policy(&name, &config.casing.policy()) {
+        warn!("the proposed name `{name}` needs curation: {err}");
+    }
+
+    let category = match (category, &sections.category) {
+        (Some(category), _) => category,
+        (None, Some(category)) => category
+            .parse()
+            .with_context(|| format!("parsing the category in the issue: `{category}`"))?,
+        (None, None) => bail!("the issue does not contain a category; provide `--category`"),
+    };
+
+    let references = sections
+        .references
+        .as_deref()
+        .map(|section| references(section, &issue.html_url))
+        .unwrap_or_default();
+
+    let rfc = match issue.html_url.parse::<rfc::Link>() {
+        Ok(link) => Some(link),
+        Err(err) => {
+            debug!("not recording the issue as the RFC: {err}");
+            None
+        }
+    };
+
+    let mut common = OptionalCommon::new();
+    common.set_name(Some(name))?;
+    common.set_rfc(rfc);
+    common.set_description(sections.description)?;
+    common.set_references(NonEmpty::from_vec(references));
+    common.touch(Utc::now());
+
+    Ok((category, Characteristic::Draft { common }))
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let credentials = net::Credentials {
+        github: args.token.clone(),
+        ..net::Credentials::from_env()
+    };
+    let client = net::client(
+        config.links.timeout(),
+        credentials,
+        net::Mode::from_env(false)?,
+    );
+
+    let issue = net::block_on(fetch(&client, &args.url))??;
+    info!("fetched issue `{}`", issue.title);
+
+    let (category, draft) = draft(&issue, args.category, config)?;
+    // SAFETY: drafts created from issues always have a name.
+    let name = draft.name().unwrap();
+
+    let file = match &args.output {
+        Some(output) => output.clone(),
+        None => super::init::default_path(config, args.root.clone(), category, name),
+    };
+
+    super::init::write(&file, &draft)?;
+    info!("created draft characteristic at `{}`", file.display());
+    println!("{}", file.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use ecc::State;
+
+    use super::*;
+
+    /// The body of an issue created from the issue form.
+    const BODY: &str = concat!(
+        "### Proposed name\n\n",
+        "BCR::ABL1-like\n\n",
+        "### Category\n\n",
+        "Molecular\n\n",
+        "### Description\n\n",
+        "A subtype with a gene expression profile similar to BCR::ABL1.\n\n",
+        "### References\n\n",
+        "- [Gene expression classification of B-ALL](https://doi.org/10.1000/example)\n",
+        "- https://www.biorxiv.org/content/10.1101/example\n\n",
+        "### Additional context\n\n",
+        "_No response_\n",
+    );
+
+    /// Gets an issue with the provided title and body.
+    fn issue(title: &str, body: &str) -> Issue {
+        Issue {
+            title: title.to_string(),
+            body: Some(body.to_string()),
+            html_url: String::from("https://github.com/stjudecloud/ecc/issues/42"),
+        }
+    }
+
+    #[test]
+    fn parses_a_draft() {
+        let (category, characteristic) = draft(
+            &issue("[ECC] BCR::ABL1-like", BODY),
+            None,
+            &Config::default(),
+        )
+        .unwrap();
+
+        assert_eq!(category, Category::Molecular);
+        assert_eq!(characteristic.state(), State::Draft);
+        assert_eq!(characteristic.name(), Some("BCR::ABL1-like"));
+        assert_eq!(
+            characteristic.description(),
+            Some("A subtype with a gene expression profile similar to BCR::ABL1.")
+        );
+        assert_eq!(
+            characteristic.rfc().map(ToString::to_string).as_deref(),
+            Some("https://github.com/stjudecloud/ecc/issues/42")
+        );
+
+        let references = characteristic.references().unwrap().collect::<Vec<_>>();
+        assert_eq!(references.len(), 2);
+        assert!(matches!(
+            references[0],
+            Reference::Manuscript { title, url, .. }
+                if title == "Gene expression classification of B-ALL"
+                    && url.as_str() == "https://doi.org/10.1000/example"
+        ));
+        assert!(matches!(
+            references[1],
+            Reference::Preprint { url, .. }
+                if url.as_str() == "https://www.biorxiv.org/content/10.1101/example"
+        ));
+
+        // NOTE: the provided category overrides the one within the issue.
+        let (category, _) = draft(
+            &issue("[ECC] BCR::ABL1-like", BODY),
+            Some(Category::Morphological),
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(category, Category::Morphological);
+    }
+
+    #[test]
+    fn missing_sections() {
+        // NOTE: the title is used when the name section is missing.
+        let (_, characteristic) = draft(
+            &issue("[ECC] BCR::ABL1-like", "### Category\n\nMolecular\n"),
+            None,
+            &Config::default(),
+        )
+        .unwrap();
+        assert_eq!(characteristic.name(), Some("BCR::ABL1-like"));
+        assert_eq!(characteristic.description(), None);
+        assert!(characteristic.references().is_none());
+
+        let err = draft(
+            &issue("[ECC]", "### Category\n\nMolecular\n"),
+            None,
+            &Config::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the issue does not contain a characteristic name"
+        );
+
+        let err = draft(
+            &issue(
+                "[ECC] BCR::ABL1-like",
+                "### Proposed name\n\n_No response_\n",
+            ),
+            None,
+            &Config::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the issue does not contain a category; provide `--category`"
+        );
+    }
+}
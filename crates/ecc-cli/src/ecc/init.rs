@@ -0,0 +1,624 @@
+//! Initialization of draft characteristics.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use chrono::Utc;
+use clap::Parser;
+use convert_case::Case;
+use convert_case::Casing as _;
+use dialoguer::Confirm;
+use dialoguer::Input;
+use dialoguer::Select;
+use dialoguer::theme::ColorfulTheme;
+use ecc::Category;
+use ecc::Characteristic;
+use ecc::Template;
+use ecc::common::OptionalCommon;
+use ecc::common::Reference;
+use ecc::common::value::Kind;
+use ecc::common::value::kind::binary;
+use ecc::common::value::kind::fraction;
+use ecc::common::value::kind::immunophenotype;
+use ecc::common::value::kind::numerical;
+use ecc::field;
+use ecc::text::Sentence;
+use nonempty::NonEmpty;
+use ontology::node::Name;
+use ontology::node::name::CasePolicy;
+use tracing::info;
+use url::Url;
+
+use crate::config::Config;
+
+/// The kinds of values that can be selected.
+const KINDS: &[(&str, &str)] = &[
+    (
+        "binary",
+        "a yes/no determination (e.g., present or not present)",
+    ),
+    ("categorical", "one of a fixed set of options"),
+    ("numerical", "a number with units of measurement"),
+    (
+        "fraction",
+        "a part of some whole (e.g., a blast percentage)",
+    ),
+    (
+        "immunophenotype",
+        "the expression levels of a panel of markers (e.g., CD19 or TdT)",
+    ),
+    ("other", "a kind of value that is not modeled yet"),
+    ("skip", "decide on the values later"),
+];
+
+/// The kinds of references that can be selected.
+const REFERENCES: &[(&str, &str)] = &[
+    ("manuscript", "a peer-reviewed published manuscript"),
+    ("preprint", "a non-peer reviewed preprint"),
+];
+
+/// Creates a new draft characteristic.
+///
+/// Without `--interactive`, a draft containing only the name is written. With
+/// `--interactive`, the contributor is prompted for the name, category, values,
+/// and references of the characteristic.
+///
+/// With `--template`, the draft is pre-filled with the skeleton description,
+/// standard values, and placeholder references for that kind of
+/// characteristic (and the category defaults to the template's category).
+#[derive(Parser)]
+pub struct Args {
+    /// Prompts for each part of the characteristic.
+    #[arg(short, long)]
+    interactive: bool,
+
+    /// The name of the characteristic.
+    ///
+    /// The case of each word is validated using the casing policy configured
+    /// in `ecc.toml`.
+    #[arg(long, required_unless_present = "interactive")]
+    name: Option<String>,
+
+    /// The category of the characteristic.
+    #[arg(long)]
+    category: Option<Category>,
+
+    /// The template to pre-fill the characteristic from.
+    ///
+    /// One of `mutation`, `fusion`, `morphology`, or
+    /// `immunophenotype-marker`.
+    #[arg(long)]
+    template: Option<Template>,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// The file to write the characteristic to.
+    ///
+    /// Defaults to a file named after the characteristic within the category's
+    /// directory (e.g., `ecc/molec/my-characteristic.yml`).
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// Prompts for a sentence.
+fn sentence(theme: &ColorfulTheme, prompt: &str) -> anyhow::Result<Sentence> {
+    let value = Input::<String>::with_theme(theme)
+        .with_prompt(prompt)
+        .validate_with(|input: &String| input.parse::<Sentence>().map(|_| ()))
+        .interact_text()?;
+
+    // SAFETY: the input was validated above.
+    Ok(value.parse().unwrap())
+}
+
+/// Prompts for the description of a binary value.
+fn binary_value(theme: &ColorfulTheme, value: &str) -> anyhow::Result<field::Description> {
+    Ok(field::Description {
+        summary: sentence(theme, &format!("Summary of `{value}`"))?,
+        details: sentence(theme, &format!("Details of `{value}`"))?,
+    })
+}
+
+/// Prompts for the permissible values.
+fn values(theme: &ColorfulTheme) -> anyhow::Result<Option<Kind>> {
+    let items = KINDS
+        .iter()
+        .map(|(name, description)| format!("{name} ({description})"))
+        .collect::<Vec<_>>();
+
+    let kind = Select::with_theme(theme)
+        .with_prompt("Kind of values")
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    let kind = match KINDS[kind].0 {
+        "binary" => Kind::Binary {
+            description: binary::Description {
+                r#true: binary_value(theme, "true")?,
+                r#false: binary_value(theme, "false")?,
+            },
+            threshold: None,
+        },
+        "categorical" => {
+            let mut options = BTreeSet::new();
+
+            loop {
+                let option = Input::<String>::with_theme(theme)
+                    .with_prompt("Option (leave empty to finish)")
+                    .allow_empty(true)
+                    .interact_text()?;
+
+                let option = option.trim();
+
+                if option.is_empty() {
+                    if options.is_empty() {
+                        continue;
+                    }
+
+                    break;
+                }
+
+                options.insert(option.to_string());
+            }
+
+            Kind::Categorical { options }
+        }
+        "numerical" => {
+            let types = [
+                numerical::Type::Signed,
+                numerical::Type::Unsigned,
+                numerical::Type::Float,
+            ];
+
+            let r#type = Select::with_theme(theme)
+                .with_prompt("Numerical type")
+                .items(&types)
+                .default(0)
+                .interact()?;
+
+            let units = Input::<String>::with_theme(theme)
+                .with_prompt("Units of measurement")
+                .interact_text()?;
+
+            Kind::Numerical {
+                r#type: types[r#type].clone(),
+                units,
+            }
+        }
+        "fraction" => {
+            let scales = [fraction::Scale::Percent, fraction::Scale::Proportion];
+
+            let scale = Select::with_theme(theme)
+                .with_prompt("Scale")
+                .items(&scales)
+                .default(0)
+                .interact()?;
+
+            let denominator_description = Input::<String>::with_theme(theme)
+                .with_prompt("Description of the whole (the denominator)")
+                .interact_text()?;
+
+            Kind::Fraction {
+                denominator_description,
+                scale: scales[scale],
+                min: None,
+                max: None,
+            }
+        }
+        "immunophenotype" => {
+            let mut markers = BTreeSet::new();
+
+            loop {
+                let marker = Input::<String>::with_theme(theme)
+                    .with_prompt("Marker (leave empty to finish)")
+                    .allow_empty(true)
+                    .validate_with(|input: &String| {
+                        if input.trim().is_empty() {
+                            return Ok(());
+                        }
+
+                        input.trim().parse::<immunophenotype::Marker>().map(|_| ())
+                    })
+                    .interact_text()?;
+
+                let marker = marker.trim();
+
+                if marker.is_empty() {
+                    if markers.is_empty() {
+                        continue;
+                    }
+
+                    break;
+                }
+
+                // SAFETY: the input was validated above, so this will always
+                // unwrap.
+                markers.insert(marker.parse().unwrap());
+            }
+
+            Kind::Immunophenotype { markers }
+        }
+        "other" => Kind::Other {
+            description: Input::<String>::with_theme(theme)
+                .with_prompt("Description of the values")
+                .interact_text()?,
+        },
+        _ => return Ok(None),
+    };
+
+    Ok(Some(kind))
+}
+
+/// Prompts for a single reference.
+fn reference(theme: &ColorfulTheme) -> anyhow::Result<Reference> {
+    let items = REFERENCES
+        .iter()
+        .map(|(name, description)| format!("{name} ({description})"))
+        .collect::<Vec<_>>();
+
+    let kind = Select::with_theme(theme)
+        .with_prompt("Kind of reference")
+        .items(&items)
+        .default(0)
+        .interact()?;
+
+    let title = Input::<String>::with_theme(theme)
+        .with_prompt("Title")
+        .interact_text()?;
+    let authors = Input::<String>::with_theme(theme)
+        .with_prompt("Authors")
+        .interact_text()?;
+    let context = sentence(theme, "Relevance to this characteristic")?;
+    let url = Input::<String>::with_theme(theme)
+        .with_prompt("URL")
+        .validate_with(|input: &String| input.parse::<Url>().map(|_| ()))
+        .interact_text()?;
+    // SAFETY: the input was validated above.
+    let url = url.parse::<Url>().unwrap();
+    let highlighted = Confirm::with_theme(theme)
+        .with_prompt("Highlight this reference?")
+        .default(false)
+        .interact()?;
+
+    Ok(match REFERENCES[kind].0 {
+        "manuscript" => Reference::Manuscript {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        },
+        _ => Reference::Preprint {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        },
+    })
+}
+
+/// Prompts for every part of the characteristic.
+fn interact(args: &Args, policy: &CasePolicy) -> anyhow::Result<(Category, OptionalCommon)> {
+    let theme = ColorfulTheme::default();
+
+    let mut name = Input::<String>::with_theme(&theme)
+        .with_prompt("Name")
+        .validate_with(|input: &String| Name::parse_with_policy(input, policy).map(|_| ()));
+
+    if let Some(default) = &args.name {
+        name = name.with_initial_text(default);
+    }
+
+    let name = name.interact_text()?;
+
+    let template = match args.template {
+        Some(template) => Some(template),
+        None => {
+            let items = std::iter::once(String::from("none (start from scratch)"))
+                .chain(
+                    Template::ALL
+                        .iter()
+                        .map(|template| format!("{template} ({})", template.summary())),
+                )
+                .collect::<Vec<_>>();
+
+            let index = Select::with_theme(&theme)
+                .with_prompt("Template")
+                .items(&items)
+                .default(0)
+                .interact()?;
+            index.checked_sub(1).map(|index| Template::ALL[index])
+        }
+    };
+
+    let category = match args.category.or(template.map(|t| t.category())) {
+        Some(category) => category,
+        None => {
+            let index = Select::with_theme(&theme)
+                .with_prompt("Category")
+                .items(&Category::ALL)
+                .default(0)
+                .interact()?;
+            Category::ALL[index]
+        }
+    };
+
+    let description = Input::<String>::with_theme(&theme)
+        .with_prompt(match template {
+            Some(_) => "Description (Markdown; leave empty to use the template's skeleton)",
+            None => "Description (Markdown; leave empty to fill in later)",
+        })
+        .allow_empty(true)
+        .interact_text()?;
+    let description = Some(description)
+        .filter(|d| !d.trim().is_empty())
+        .or(template.map(|t| t.description()));
+
+    let values = match template {
+        Some(template) => Some(template.values()),
+        None => values(&theme)?,
+    };
+
+    let mut references = Vec::new();
+
+    while Confirm::with_theme(&theme)
+        .with_prompt("Add a reference?")
+        .default(references.is_empty())
+        .interact()?
+    {
+        references.push(reference(&theme)?);
+    }
+
+    // NOTE: the template's placeholder references are only kept if no
+    // references were provided.
+    let references =
+        NonEmpty::from_vec(references).or(template.map(|template| template.references()));
+
+    let mut common = OptionalCommon::new();
+    common.set_name(Some(name))?;
+    common.set_description(description)?;
+    common.set_values(values);
+    common.set_references(references);
+
+    Ok((category, common))
+}
+
+/// Creates the elements of a draft characteristic without prompting.
+///
+/// The category defaults to the template's category, and the draft is
+/// pre-filled from the template (if one is provided).
+fn draft(
+    name: Option<&str>,
+    category: Option<Category>,
+    template: Option<Template>,
+    policy: &CasePolicy,
+) -> anyhow::Result<(Category, OptionalCommon)> {
+    let category = match category.or(template.map(|t| t.category())) {
+        Some(category) => category,
+        None => bail!("`--category` or `--template` is required without `--interactive`"),
+    };
+
+    let name = name
+        .map(|name| Name::parse_with_policy(name, policy))
+        .transpose()
+        .context("validating the name")?
+        .map(Name::into_inner);
+
+    let common = match template {
+        Some(template) => template.common(name),
+        None => {
+            let mut common = OptionalCommon::new();
+            common.set_name(name)?;
+            common
+        }
+    };
+
+    Ok((category, common))
+}
+
+/// Serializes a characteristic, omitting any fields that are not set.
+fn serialize(characteristic: &Characteristic) -> anyhow::Result<String> {
+    let mut value = serde_yaml::to_value(characteristic).context("serializing characteristic")?;
+
+    if let serde_yaml::Value::Mapping(mapping) = &mut value {
+        mapping.retain(|_, value| !value.is_null());
+    }
+
+    serde_yaml::to_string(&value).context("serializing characteristic")
+}
+
+/// Gets the default location of a characteristic file, which is a file named
+/// after the characteristic within the category's directory.
+pub fn default_path(
+    config: &Config,
+    root: Option<PathBuf>,
+    category: Category,
+    name: &str,
+) -> PathBuf {
+    config
+        .characteristics(root)
+        .join(config.category_directory(category))
+        .join(format!("{}.yml", name.to_case(Case::Kebab)))
+}
+
+/// Writes a characteristic to a new file.
+///
+/// The file is not overwritten if it already exists, and the serialized
+/// characteristic is parsed again to ensure it will be accepted by `check`.
+pub fn write(file: &Path, characteristic: &Characteristic) -> anyhow::Result<()> {
+    if file.exists() {
+        bail!("file already exists: {}", file.display());
+    }
+
+    let contents = serialize(characteristic)?;
+
+    let reparsed = ecc::io::from_str(&contents).context("parsing the generated characteristic")?;
+
+    if &reparsed != characteristic {
+        bail!("the generated characteristic did not round trip");
+    }
+
+    if let Some(parent) = file.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("creating directory: {}", parent.display()))?;
+    }
+
+    std::fs::write(file, contents).with_context(|| format!("writing file: {}", file.display()))
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let policy = config.casing.policy();
+
+    let (category, mut common) = if args.interactive {
+        interact(&args, &policy)?
+    } else {
+        draft(args.name.as_deref(), args.category, args.template, &policy)?
+    };
+
+    let file = match &args.output {
+        Some(output) => output.clone(),
+        None => {
+            // SAFETY: a name is always present at this point.
+            let name = common.name().unwrap();
+            default_path(config, args.root.clone(), category, name)
+        }
+    };
+
+    common.touch(Utc::now());
+
+    write(&file, &Characteristic::Draft { common })?;
+    info!("created draft characteristic at `{}`", file.display());
+    println!("{}", file.display());
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_infra::tree::Tree;
+
+    use super::*;
+
+    #[test]
+    fn draft_from_name() {
+        let (category, common) = draft(
+            Some("A Characteristic Name"),
+            Some(Category::Molecular),
+            None,
+            &CasePolicy::default(),
+        )
+        .unwrap();
+
+        assert_eq!(category, Category::Molecular);
+        assert_eq!(common.name(), Some("A Characteristic Name"));
+        assert_eq!(common.description(), None);
+        assert_eq!(common.values(), None);
+        assert_eq!(common.references(), None);
+    }
+
+    #[test]
+    fn draft_from_template() {
+        let (category, common) = draft(
+            Some("A Characteristic Name"),
+            None,
+            Some(Template::Fusion),
+            &CasePolicy::default(),
+        )
+        .unwrap();
+
+        assert_eq!(category, Category::Molecular);
+        assert_eq!(common.name(), Some("A Characteristic Name"));
+        assert_eq!(
+            common.description(),
+            Some(Template::Fusion.description().as_str())
+        );
+        assert!(matches!(common.values(), Some(Kind::Binary { .. })));
+        assert_eq!(common.references(), Some(&Template::Fusion.references()));
+
+        // NOTE: the provided category overrides the template's category.
+        let (category, common) = draft(
+            None,
+            Some(Category::Molecular),
+            Some(Template::Morphology),
+            &CasePolicy::default(),
+        )
+        .unwrap();
+
+        assert_eq!(category, Category::Molecular);
+        assert_eq!(common.name(), None);
+        assert!(matches!(
+            common.values(),
+            Some(Kind::Categorical { options }) if options.contains("Indeterminate")
+        ));
+    }
+
+    #[test]
+    fn draft_errors() {
+        let err = draft(
+            Some("A Characteristic Name"),
+            None,
+            None,
+            &CasePolicy::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "`--category` or `--template` is required without `--interactive`"
+        );
+
+        let err = draft(
+            Some("Tumor in Situ"),
+            Some(Category::Morphological),
+            None,
+            &CasePolicy::default(),
+        )
+        .unwrap_err();
+        assert_eq!(err.to_string(), "validating the name");
+
+        // NOTE: the words configured as lowercase are accepted.
+        let policy = CasePolicy::default().with_lowercase_words(["in", "Situ"]);
+        let (_, common) = draft(
+            Some("Tumor in situ"),
+            Some(Category::Morphological),
+            None,
+            &policy,
+        )
+        .unwrap();
+        assert_eq!(common.name(), Some("Tumor in situ"));
+    }
+
+    #[test]
+    fn write_draft() {
+        let tree = Tree::new();
+        let config = Config::load(Some(&tree.config())).unwrap();
+
+        let file = default_path(&config, None, Category::Molecular, "A Characteristic Name");
+        assert_eq!(
+            file,
+            tree.characteristics()
+                .join("molec/a-characteristic-name.yml")
+        );
+
+        let characteristic =
+            Template::Fusion.characteristic(Some(String::from("A Characteristic Name")));
+        write(&file, &characteristic).unwrap();
+
+        // NOTE: fields that are not set are omitted from the file.
+        let contents = std::fs::read_to_string(&file).unwrap();
+        assert!(!contents.contains("null"));
+        assert_eq!(ecc::io::from_str(&contents).unwrap(), characteristic);
+
+        let err = write(&file, &characteristic).unwrap_err();
+        assert!(err.to_string().starts_with("file already exists"));
+    }
+}
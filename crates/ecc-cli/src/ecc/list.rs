@@ -0,0 +1,167 @@
+//! Listing of the characteristics within the tree.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use colored::Colorize as _;
+use ecc::Characteristic;
+use ecc::query::Filter;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The column to sort the characteristics by.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum Sort {
+    /// The identifier (characteristics without one are listed last).
+    #[default]
+    Identifier,
+
+    /// The name.
+    Name,
+
+    /// The state (in the order of the lifecycle).
+    State,
+
+    /// The adoption date (characteristics that have not been adopted are
+    /// listed last).
+    Adopted,
+}
+
+/// Lists the characteristics within the tree.
+///
+/// The identifier, name, state, and adoption date of each characteristic are
+/// printed as a table.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long, alias = "path")]
+    root: Option<PathBuf>,
+
+    /// Only lists the characteristics matching the filter (e.g.,
+    /// `state:adopted category:molec`).
+    ///
+    /// Filters are space-separated `key:value` terms with the keys `state`,
+    /// `category`, `kind`, `tag`, `adopted-after`, and `adopted-before`.
+    #[arg(long)]
+    filter: Option<Filter>,
+
+    /// The column to sort by.
+    #[arg(long, value_enum, default_value_t)]
+    sort: Sort,
+
+    /// Reverses the order.
+    #[arg(long)]
+    reverse: bool,
+}
+
+/// A row of the table.
+struct Row {
+    /// The identifier (or `-` if one has not been assigned).
+    identifier: String,
+
+    /// The name (or `-` if the characteristic does not have one).
+    name: String,
+
+    /// The state.
+    state: &'static str,
+
+    /// The adoption date (or `-` if the characteristic has not been adopted).
+    adopted: String,
+}
+
+impl Row {
+    /// Creates a row for a characteristic.
+    fn new(characteristic: &Characteristic) -> Self {
+        Self {
+            identifier: characteristic
+                .identifier()
+                .map(|identifier| identifier.to_string())
+                .unwrap_or_else(|| String::from("-")),
+            name: characteristic.name().unwrap_or("-").to_string(),
+            state: characteristic.state().as_str(),
+            adopted: characteristic
+                .adoption_date()
+                .map(|date| date.format("%Y-%m-%d").to_string())
+                .unwrap_or_else(|| String::from("-")),
+        }
+    }
+}
+
+/// Sorts the characteristics by a column.
+fn sort(characteristics: &mut [Characteristic], by: Sort) {
+    match by {
+        // NOTE: [`None`] sorts before [`Some`], so the key is flipped to list
+        // the characteristics without an identifier (or adoption date) last.
+        Sort::Identifier => {
+            characteristics.sort_by(|a, b| {
+                (a.identifier().is_none(), a.identifier())
+                    .cmp(&(b.identifier().is_none(), b.identifier()))
+            });
+        }
+        Sort::Name => characteristics.sort_by(|a, b| a.name().cmp(&b.name())),
+        Sort::State => characteristics.sort_by_key(Characteristic::state),
+        Sort::Adopted => {
+            characteristics.sort_by(|a, b| {
+                (a.adoption_date().is_none(), a.adoption_date())
+                    .cmp(&(b.adoption_date().is_none(), b.adoption_date()))
+            });
+        }
+    }
+}
+
+/// Prints the rows as a table.
+fn table(rows: &[Row]) {
+    let width = |header: &str, column: fn(&Row) -> &str| {
+        rows.iter()
+            .map(|row| column(row).chars().count())
+            .chain([header.len()])
+            .max()
+            .unwrap_or_default()
+    };
+
+    let identifier = width("IDENTIFIER", |row| &row.identifier);
+    let name = width("NAME", |row| &row.name);
+    let state = width("STATE", |row| row.state);
+
+    println!(
+        "{}",
+        format!(
+            "{:<identifier$}  {:<name$}  {:<state$}  ADOPTED",
+            "IDENTIFIER", "NAME", "STATE"
+        )
+        .bold()
+    );
+
+    for row in rows {
+        println!(
+            "{:<identifier$}  {:<name$}  {:<state$}  {}",
+            row.identifier, row.name, row.state, row.adopted
+        );
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let filter = args.filter.unwrap_or_default();
+
+    let mut characteristics = tree::load(&config.characteristics(args.root))?
+        .into_iter()
+        .map(|(_, characteristic)| characteristic)
+        .filter(|characteristic| filter.matches(characteristic))
+        .collect::<Vec<_>>();
+
+    sort(&mut characteristics, args.sort);
+
+    if args.reverse {
+        characteristics.reverse();
+    }
+
+    let rows = characteristics.iter().map(Row::new).collect::<Vec<_>>();
+    table(&rows);
+
+    Ok(())
+}
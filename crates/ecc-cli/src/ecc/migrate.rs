@@ -0,0 +1,89 @@
+//! Migration of characteristic files to the current schema version.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use ecc::migrate::SchemaVersion;
+use tracing::info;
+
+use crate::config::Config;
+use crate::tree;
+
+/// Upgrades characteristic files to the current schema version.
+///
+/// Each file is upgraded through every intermediate schema version and
+/// rewritten in place. Files that are already at the current version are left
+/// untouched. Note that comments within migrated files are not preserved.
+#[derive(Parser)]
+pub struct Args {
+    /// The characteristic files or directories to migrate.
+    ///
+    /// Directories are searched recursively for `.yml` and `.yaml` files.
+    /// Defaults to the directory configured in `ecc.toml`.
+    paths: Vec<PathBuf>,
+
+    /// Reports the files that need to be migrated without modifying them.
+    ///
+    /// The exit code is `1` if any file needs to be migrated.
+    #[arg(long)]
+    check: bool,
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let paths = if args.paths.is_empty() {
+        vec![config.characteristics(None)]
+    } else {
+        args.paths
+    };
+
+    let files = tree::select(&paths, &[], &[])?;
+    let mut outdated = 0;
+
+    for file in files {
+        let contents = tree::read(&file)?;
+        let mut document = serde_yaml::from_str::<serde_yaml::Value>(&contents)
+            .with_context(|| format!("parsing file: {}", file.display()))?;
+
+        let Some(from) = ecc::migrate::migrate(&mut document)
+            .with_context(|| format!("migrating file: {}", file.display()))?
+        else {
+            continue;
+        };
+
+        outdated += 1;
+
+        if args.check {
+            println!(
+                "{}: schema version {from} → {}",
+                file.display(),
+                SchemaVersion::CURRENT
+            );
+            continue;
+        }
+
+        ecc::io::from_value(document.clone()).with_context(|| {
+            format!("the migrated characteristic is invalid: {}", file.display())
+        })?;
+
+        let contents = serde_yaml::to_string(&document).context("serializing characteristic")?;
+        std::fs::write(&file, contents)
+            .with_context(|| format!("writing file: {}", file.display()))?;
+        info!(
+            "migrated `{}` from schema version {from} to {}",
+            file.display(),
+            SchemaVersion::CURRENT
+        );
+    }
+
+    if args.check && outdated > 0 {
+        println!(
+            "\n{outdated} files need to be migrated; run `ecc-cli ecc migrate` to upgrade them"
+        );
+        std::process::exit(crate::EXIT_FAILURE.into());
+    }
+
+    info!("{outdated} files migrated");
+    Ok(())
+}
@@ -0,0 +1,181 @@
+//! Promotion of characteristics through their lifecycle.
+
+use std::ffi::OsString;
+use std::fs::OpenOptions;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use chrono::DateTime;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use clap::Parser;
+use ecc::Characteristic;
+use ecc::State;
+use ecc::event::Event;
+use tracing::info;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The top-level key for the state within a characteristic file.
+const STATE: &str = "state:";
+
+/// The top-level keys that are only allowed within drafts.
+const DRAFT_ONLY: [&str; 2] = ["created_at:", "updated_at:"];
+
+/// Promotes a characteristic to the next state within its lifecycle.
+///
+/// Drafts are proposed, proposals become provisional, and provisional
+/// characteristics are adopted. The `state` field is rewritten in place (along
+/// with the fields that the new state adds or removes), and the transition is
+/// appended to the event log configured in `ecc.toml`.
+#[derive(Parser)]
+pub struct Args {
+    /// The characteristic file to promote.
+    file: PathBuf,
+
+    /// When the transition happened (e.g., `2024-01-01T00:00:00Z`).
+    ///
+    /// This is used as the adoption date when adopting a characteristic.
+    /// Defaults to now.
+    #[arg(long)]
+    date: Option<DateTime<Utc>>,
+
+    /// The path to the event log.
+    ///
+    /// Defaults to the log configured in `ecc.toml`.
+    #[arg(long)]
+    events: Option<PathBuf>,
+
+    /// Prints the event that would be logged without modifying any files.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// Rewrites the contents of a characteristic file in a new state.
+///
+/// The rest of the file is left untouched apart from the draft-only fields,
+/// which are removed once a draft is proposed, and the adoption date, which is
+/// added once a characteristic is adopted.
+fn rewrite(contents: &str, state: State, date: &DateTime<Utc>) -> anyhow::Result<String> {
+    let mut lines = Vec::new();
+    let mut found = false;
+
+    for line in contents.lines() {
+        if line.starts_with(STATE) {
+            found = true;
+            lines.push(format!("{STATE} {state}"));
+
+            if state == State::Adopted {
+                lines.push(format!(
+                    "adoption_date: {}",
+                    date.to_rfc3339_opts(SecondsFormat::Secs, true)
+                ));
+            }
+
+            continue;
+        }
+
+        if DRAFT_ONLY.iter().any(|key| line.starts_with(key)) {
+            continue;
+        }
+
+        lines.push(line.to_string());
+    }
+
+    if !found {
+        bail!("no top-level `state` key was found");
+    }
+
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    Ok(updated)
+}
+
+/// Gets the path that a promoted file is staged at before it replaces the
+/// original (a hidden file within the same directory, so that renaming it
+/// never crosses file systems).
+fn staged(path: &Path) -> PathBuf {
+    let mut name = OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(".promote");
+    path.with_file_name(name)
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let contents = tree::read(&args.file)?;
+    let characteristic = ecc::io::from_str(&contents)
+        .with_context(|| format!("parsing characteristic: {}", args.file.display()))?;
+
+    let Some(state) = characteristic.state().next() else {
+        bail!("`{}` has already been adopted", args.file.display());
+    };
+
+    if let Characteristic::Draft { common } = &characteristic {
+        let missing = common.missing_fields();
+
+        if !missing.is_empty() {
+            bail!(
+                "`{}` cannot be proposed until it has the following fields: {}",
+                args.file.display(),
+                missing
+                    .iter()
+                    .map(|field| format!("`{field}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
+    let date = args.date.unwrap_or_else(Utc::now);
+    let updated = rewrite(&contents, state, &date)?;
+
+    // Ensure the file still parses and is in the new state before writing it
+    // out.
+    let promoted = ecc::io::from_str(&updated)
+        .with_context(|| format!("parsing the characteristic after promoting it to `{state}`"))?;
+
+    if promoted.state() != state {
+        bail!("the state could not be written into the file");
+    }
+
+    let Some(event) = Event::entered(&promoted, date) else {
+        bail!("the characteristic has no identifier or RFC to log");
+    };
+
+    if args.dry_run {
+        ecc::event::append(std::io::stdout(), &event)?;
+        return Ok(());
+    }
+
+    // NOTE: the log is opened before anything is written so that a log that
+    // cannot be opened leaves the characteristic untouched. The promoted file
+    // is staged alongside the original and only moved into place once the
+    // event has been appended, so the transition is never on disk without
+    // being logged.
+    let log = args.events.unwrap_or_else(|| config.event_log());
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&log)
+        .with_context(|| format!("opening event log: {}", log.display()))?;
+
+    let staged = staged(&args.file);
+    std::fs::write(&staged, updated)
+        .with_context(|| format!("writing file: {}", staged.display()))?;
+
+    if let Err(err) = ecc::event::append(file, &event) {
+        let _ = std::fs::remove_file(&staged);
+        return Err(err).with_context(|| format!("appending to event log: {}", log.display()));
+    }
+
+    std::fs::rename(&staged, &args.file)
+        .with_context(|| format!("writing file: {}", args.file.display()))?;
+    info!("promoted `{}` to `{state}`", args.file.display());
+    info!("logged the transition to `{}`", log.display());
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+//! Rendering of characteristics as the bodies of RFC issues.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use ecc::Category;
+use tracing::info;
+
+use crate::config::Config;
+use crate::render;
+use crate::tree;
+
+/// Renders a characteristic as the Markdown body of an RFC issue.
+///
+/// The body contains the characteristic, description, evaluation, and
+/// references sections so that the proposal can be kept in sync with the
+/// characteristic file.
+#[derive(Parser)]
+pub struct Args {
+    /// The characteristic file to render.
+    file: PathBuf,
+
+    /// The category of the characteristic.
+    ///
+    /// If not provided, the category is inferred from the directory the file
+    /// resides in (e.g., a file within `ecc/molec` is molecular).
+    #[arg(long)]
+    category: Option<Category>,
+
+    /// The file to write the body to.
+    ///
+    /// Defaults to standard output.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let contents = tree::read(&args.file)?;
+    let characteristic = ecc::io::from_str(&contents)
+        .with_context(|| format!("parsing characteristic: {}", args.file.display()))?;
+
+    let category = args.category.or_else(|| config.infer_category(&args.file));
+    let body = render::rfc::render(&characteristic, category);
+
+    match &args.output {
+        Some(output) => {
+            std::fs::write(output, body)
+                .with_context(|| format!("writing file: {}", output.display()))?;
+            info!("wrote RFC body to `{}`", output.display());
+        }
+        None => print!("{body}"),
+    }
+
+    Ok(())
+}
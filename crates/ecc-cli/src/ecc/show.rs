@@ -0,0 +1,79 @@
+//! Showing of a single characteristic.
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use ecc::Characteristic;
+use ecc::Identifier;
+
+use crate::Error;
+use crate::config::Config;
+use crate::render;
+use crate::tree;
+
+/// The format to show a characteristic in.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum Format {
+    /// Styled text wrapped to the width of the terminal.
+    #[default]
+    Terminal,
+
+    /// A Markdown document.
+    Markdown,
+
+    /// A JSON document.
+    Json,
+}
+
+/// Shows a single characteristic.
+///
+/// The characteristic is found by its identifier (e.g., `ECC-MORPH-000001`)
+/// or by its name (case insensitive).
+#[derive(Parser)]
+pub struct Args {
+    /// The identifier or name of the characteristic.
+    characteristic: String,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long, alias = "path")]
+    root: Option<PathBuf>,
+
+    /// The format to show the characteristic in.
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+}
+
+/// Finds a characteristic by its identifier or name.
+fn find(characteristics: Vec<(PathBuf, Characteristic)>, query: &str) -> Option<Characteristic> {
+    let query = query.trim();
+    let mut characteristics = characteristics.into_iter().map(|(_, c)| c);
+
+    match query.parse::<Identifier>() {
+        Ok(identifier) => characteristics.find(|c| c.identifier() == Some(&identifier)),
+        Err(_) => characteristics.find(|c| {
+            c.name()
+                .is_some_and(|name| name.eq_ignore_ascii_case(query))
+        }),
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.root))?;
+
+    let characteristic =
+        find(characteristics, &args.characteristic).ok_or_else(|| Error::NoCharacteristic {
+            query: args.characteristic.clone(),
+        })?;
+
+    match args.format {
+        Format::Terminal => print!("{}", render::Terminal::detect().render(&characteristic)),
+        Format::Markdown => print!("{}", render::markdown::render(&characteristic)),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&characteristic)?),
+    }
+
+    Ok(())
+}
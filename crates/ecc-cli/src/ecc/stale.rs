@@ -0,0 +1,203 @@
+//! Reporting of drafts that have not been updated recently.
+
+use std::path::PathBuf;
+
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
+use clap::Parser;
+use clap::ValueEnum;
+use colored::Colorize as _;
+use ecc::Characteristic;
+use serde::Serialize;
+use tracing::warn;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The format to print the report in.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    /// A human-readable table.
+    Table,
+
+    /// A triage comment for each stale draft, ready to be posted to its RFC
+    /// issue.
+    Markdown,
+
+    /// A JSON array (including the triage comment for each stale draft).
+    Json,
+}
+
+/// Reports drafts that have not been updated recently.
+///
+/// A draft is stale when it has gone longer than the configured number of days
+/// without being updated (or, if it has never been updated, since it was
+/// created). Drafts without either timestamp cannot be aged and are skipped.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long, alias = "path")]
+    root: Option<PathBuf>,
+
+    /// The number of days a draft can go without being updated.
+    ///
+    /// Defaults to the number configured in `ecc.toml`.
+    #[arg(long, value_name = "DAYS")]
+    max_age_days: Option<i64>,
+
+    /// The format to print the report in.
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+/// A draft that has not been updated recently.
+#[derive(Serialize)]
+struct Stale {
+    /// The path to the draft.
+    path: PathBuf,
+
+    /// The name of the draft (if it has one).
+    name: Option<String>,
+
+    /// The link to the RFC of the draft (if it has one).
+    rfc: Option<String>,
+
+    /// When the draft was last touched.
+    last_touched: DateTime<Utc>,
+
+    /// The number of days since the draft was last touched.
+    days: i64,
+
+    /// A triage comment for the RFC issue.
+    comment: String,
+}
+
+impl Stale {
+    /// Creates the report for a stale draft.
+    fn new(
+        path: PathBuf,
+        characteristic: &Characteristic,
+        last_touched: DateTime<Utc>,
+        untouched: TimeDelta,
+        max_age_days: i64,
+    ) -> Self {
+        let days = untouched.num_days();
+        let name = characteristic.name().map(String::from);
+
+        let comment = format!(
+            "### Stale draft\n\nThe draft {} (`{}`) has not been updated in {days} days (last \
+             updated on {}), which is longer than the {max_age_days} days that drafts can go \
+             without changes.\n\nIf the characteristic is still being worked on, please update \
+             the draft. Otherwise, consider closing this RFC and removing the draft.\n",
+            name.as_deref()
+                .map(|name| format!("**{name}**"))
+                .unwrap_or_else(|| String::from("without a name")),
+            path.display(),
+            last_touched.format("%Y-%m-%d"),
+        );
+
+        Self {
+            path,
+            name,
+            rfc: characteristic.rfc().map(|rfc| rfc.to_string()),
+            last_touched,
+            days,
+            comment,
+        }
+    }
+}
+
+/// Prints the stale drafts as a table.
+fn table(stale: &[Stale], max_age_days: i64) {
+    if stale.is_empty() {
+        println!("no drafts have gone more than {max_age_days} days without being updated");
+        return;
+    }
+
+    println!(
+        "{}",
+        format!("{:>6}  {:<10}  {:<40}  RFC", "DAYS", "TOUCHED", "NAME").bold()
+    );
+
+    for draft in stale {
+        println!(
+            "{:>6}  {:<10}  {:<40}  {}",
+            draft.days,
+            draft.last_touched.format("%Y-%m-%d"),
+            draft
+                .name
+                .clone()
+                .unwrap_or_else(|| draft.path.display().to_string()),
+            draft.rfc.as_deref().unwrap_or("-")
+        );
+    }
+}
+
+/// Prints a triage comment for each stale draft.
+///
+/// Each comment is preceded by an HTML comment naming the RFC that it should
+/// be posted to (or the path of the draft if it has no RFC).
+fn markdown(stale: &[Stale]) {
+    for (i, draft) in stale.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        match &draft.rfc {
+            Some(rfc) => println!("<!-- rfc: {rfc} -->"),
+            None => println!("<!-- path: {} -->", draft.path.display()),
+        }
+
+        print!("{}", draft.comment);
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let max_age_days = args.max_age_days.unwrap_or(config.drafts.max_age_days);
+    let max_age = TimeDelta::days(max_age_days);
+    let now = Utc::now();
+
+    let mut stale = Vec::new();
+    let mut undated = 0;
+
+    for (path, characteristic) in tree::load(&config.characteristics(args.root))? {
+        let Characteristic::Draft { common } = &characteristic else {
+            continue;
+        };
+
+        let (Some(last_touched), Some(untouched)) =
+            (common.last_touched().copied(), common.untouched_for(now))
+        else {
+            undated += 1;
+            continue;
+        };
+
+        if untouched > max_age {
+            stale.push(Stale::new(
+                path,
+                &characteristic,
+                last_touched,
+                untouched,
+                max_age_days,
+            ));
+        }
+    }
+
+    if undated > 0 {
+        warn!("skipped {undated} drafts without `created_at` or `updated_at` timestamps");
+    }
+
+    stale.sort_by(|a, b| b.days.cmp(&a.days).then_with(|| a.path.cmp(&b.path)));
+
+    match args.format {
+        Format::Table => table(&stale, max_age_days),
+        Format::Markdown => markdown(&stale),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&stale)?),
+    }
+
+    Ok(())
+}
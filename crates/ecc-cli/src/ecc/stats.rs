@@ -0,0 +1,327 @@
+//! Statistics about the characteristic tree.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use colored::Colorize as _;
+use ecc::Characteristic;
+use ecc::State;
+use ecc::common::Sensitivity;
+use ecc::common::Tag;
+use ecc::completeness::Criterion;
+use ontology::Node;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The category used for characteristics without an identifier.
+const UNASSIGNED: &str = "unassigned";
+
+/// The format to print statistics in.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    /// A human-readable table.
+    Table,
+
+    /// A JSON document.
+    Json,
+}
+
+/// Prints statistics about the characteristic tree.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long, alias = "path")]
+    root: Option<PathBuf>,
+
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml` (if any).
+    #[arg(long)]
+    ontology: Option<PathBuf>,
+
+    /// The format to print the statistics in.
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+/// Statistics about the ontology.
+#[derive(Serialize)]
+struct OntologyStats {
+    /// The total number of nodes.
+    nodes: usize,
+
+    /// The number of nodes at each depth (the root is at depth zero).
+    by_depth: BTreeMap<usize, usize>,
+}
+
+/// A characteristic that does not meet every completeness criterion.
+#[derive(Serialize)]
+struct Incomplete {
+    /// The label of the characteristic.
+    characteristic: String,
+
+    /// The completeness score.
+    score: f64,
+
+    /// The criteria that are not met.
+    missing: Vec<Criterion>,
+}
+
+/// Statistics about the characteristic tree.
+#[derive(Serialize)]
+struct Stats {
+    /// The total number of characteristics.
+    characteristics: usize,
+
+    /// The number of characteristics in each state.
+    by_state: BTreeMap<State, usize>,
+
+    /// The number of characteristics in each category.
+    by_category: BTreeMap<String, usize>,
+
+    /// The number of characteristics with each sensitivity.
+    by_sensitivity: BTreeMap<Sensitivity, usize>,
+
+    /// The number of characteristics with each tag.
+    by_tag: BTreeMap<Tag, usize>,
+
+    /// The number of adoptions in each month (formatted as `YYYY-MM`).
+    adoptions_by_month: BTreeMap<String, usize>,
+
+    /// The characteristics that have no references.
+    missing_references: Vec<String>,
+
+    /// The mean completeness score of the characteristics.
+    mean_completeness: f64,
+
+    /// The characteristics that do not meet every completeness criterion
+    /// (least complete first).
+    incomplete: Vec<Incomplete>,
+
+    /// Statistics about the ontology (if provided).
+    ontology: Option<OntologyStats>,
+}
+
+/// Computes the depth of every node within the ontology.
+///
+/// Nodes whose ancestry cannot be resolved (because a parent is missing or
+/// there is a cycle) are not counted.
+fn depths(nodes: &[Node]) -> BTreeMap<usize, usize> {
+    let parents = nodes
+        .iter()
+        .map(|node| (node.name().inner(), node.parent().inner()))
+        .collect::<HashMap<_, _>>();
+
+    let mut result = BTreeMap::new();
+
+    for node in nodes {
+        let mut depth = 0;
+        let mut current = node.parent().inner();
+
+        while !current.is_empty() && depth <= nodes.len() {
+            match parents.get(current) {
+                Some(parent) => {
+                    depth += 1;
+                    current = parent;
+                }
+                None => break,
+            }
+        }
+
+        if current.is_empty() {
+            *result.entry(depth).or_default() += 1;
+        }
+    }
+
+    result
+}
+
+/// Gets the label of a characteristic (its identifier, name, or path).
+fn label(path: &Path, characteristic: &Characteristic) -> String {
+    characteristic
+        .identifier()
+        .map(|identifier| identifier.to_string())
+        .or_else(|| characteristic.name().map(String::from))
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+/// Computes the statistics for a set of characteristics.
+fn compute(characteristics: &[(PathBuf, Characteristic)], nodes: Option<&[Node]>) -> Stats {
+    let mut stats = Stats {
+        characteristics: characteristics.len(),
+        by_state: BTreeMap::new(),
+        by_category: BTreeMap::new(),
+        by_sensitivity: BTreeMap::new(),
+        by_tag: BTreeMap::new(),
+        adoptions_by_month: BTreeMap::new(),
+        missing_references: Vec::new(),
+        mean_completeness: 0.0,
+        incomplete: Vec::new(),
+        ontology: nodes.map(|nodes| OntologyStats {
+            nodes: nodes.len(),
+            by_depth: depths(nodes),
+        }),
+    };
+
+    for (path, characteristic) in characteristics {
+        *stats.by_state.entry(characteristic.state()).or_default() += 1;
+
+        let category = characteristic
+            .identifier()
+            .map(|identifier| identifier.category().to_string())
+            .unwrap_or_else(|| String::from(UNASSIGNED));
+        *stats.by_category.entry(category).or_default() += 1;
+        *stats
+            .by_sensitivity
+            .entry(characteristic.sensitivity())
+            .or_default() += 1;
+
+        for tag in characteristic.tags() {
+            *stats.by_tag.entry(tag.clone()).or_default() += 1;
+        }
+
+        if let Some(date) = characteristic.adoption_date() {
+            *stats
+                .adoptions_by_month
+                .entry(date.format("%Y-%m").to_string())
+                .or_default() += 1;
+        }
+
+        if characteristic.references().is_none() {
+            stats.missing_references.push(label(path, characteristic));
+        }
+
+        let score = characteristic.completeness();
+        stats.mean_completeness += score.value();
+
+        if !score.is_complete() {
+            stats.incomplete.push(Incomplete {
+                characteristic: label(path, characteristic),
+                score: score.value(),
+                missing: score.missing().to_vec(),
+            });
+        }
+    }
+
+    if !characteristics.is_empty() {
+        stats.mean_completeness /= characteristics.len() as f64;
+    }
+
+    stats.incomplete.sort_by(|a, b| a.score.total_cmp(&b.score));
+
+    stats
+}
+
+/// Prints a section of the table.
+fn section<'a>(title: &str, rows: impl IntoIterator<Item = (String, &'a usize)>) {
+    println!("{}", title.bold());
+
+    for (key, count) in rows {
+        println!("  {key:<24}{count:>6}");
+    }
+
+    println!();
+}
+
+/// Prints the statistics as a table.
+fn table(stats: &Stats) {
+    println!("{} {}\n", "Characteristics:".bold(), stats.characteristics);
+
+    section(
+        "By state",
+        stats.by_state.iter().map(|(k, v)| (k.to_string(), v)),
+    );
+    section(
+        "By category",
+        stats.by_category.iter().map(|(k, v)| (k.clone(), v)),
+    );
+    section(
+        "By sensitivity",
+        stats.by_sensitivity.iter().map(|(k, v)| (k.to_string(), v)),
+    );
+
+    if !stats.by_tag.is_empty() {
+        section(
+            "By tag",
+            stats.by_tag.iter().map(|(k, v)| (k.to_string(), v)),
+        );
+    }
+
+    if !stats.adoptions_by_month.is_empty() {
+        section(
+            "Adoptions by month",
+            stats.adoptions_by_month.iter().map(|(k, v)| (k.clone(), v)),
+        );
+    }
+
+    println!(
+        "{} ({})",
+        "Missing references".bold(),
+        stats.missing_references.len()
+    );
+
+    for label in &stats.missing_references {
+        println!("  {label}");
+    }
+
+    println!();
+
+    println!(
+        "{} {:.2}\n",
+        "Mean completeness:".bold(),
+        stats.mean_completeness
+    );
+    println!("{} ({})", "Least complete".bold(), stats.incomplete.len());
+
+    for incomplete in &stats.incomplete {
+        println!(
+            "  {:<24}{:>6.2}  missing: {}",
+            incomplete.characteristic,
+            incomplete.score,
+            incomplete
+                .missing
+                .iter()
+                .map(|criterion| criterion.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    println!();
+
+    if let Some(ontology) = &stats.ontology {
+        println!("{} {}\n", "Ontology nodes:".bold(), ontology.nodes);
+        section(
+            "Nodes by depth",
+            ontology.by_depth.iter().map(|(k, v)| (k.to_string(), v)),
+        );
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.root))?;
+    let nodes = config
+        .ontology(args.ontology)
+        .as_deref()
+        .map(tree::load_ontology)
+        .transpose()?
+        .map(|nodes| nodes.into_iter().map(|(_, node)| node).collect::<Vec<_>>());
+
+    let stats = compute(&characteristics, nodes.as_deref());
+
+    match args.format {
+        Format::Table => table(&stats),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&stats)?),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,174 @@
+//! Reporting of open characteristics by steward.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use colored::Colorize as _;
+use ecc::Characteristic;
+use ecc::State;
+use serde::Serialize;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The format to print the report in.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    /// A human-readable list.
+    Table,
+
+    /// A JSON document.
+    Json,
+}
+
+/// Groups the open drafts and proposals by steward.
+///
+/// A characteristic with more than one steward is listed under each of them,
+/// and characteristics without a steward are listed as unassigned.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(long, alias = "path")]
+    root: Option<PathBuf>,
+
+    /// The format to print the report in.
+    #[arg(short, long, value_enum, default_value_t = Format::Table)]
+    format: Format,
+}
+
+/// An open characteristic owed by a steward.
+#[derive(Serialize)]
+struct Open {
+    /// The path to the characteristic.
+    path: PathBuf,
+
+    /// The state of the characteristic.
+    state: State,
+
+    /// The identifier of the characteristic (if it has one).
+    identifier: Option<String>,
+
+    /// The name of the characteristic (if it has one).
+    name: Option<String>,
+}
+
+/// The open characteristics of a steward.
+#[derive(Serialize)]
+struct Group {
+    /// The name of the steward.
+    name: String,
+
+    /// The open characteristics.
+    characteristics: Vec<Open>,
+}
+
+/// The open characteristics grouped by steward.
+#[derive(Default, Serialize)]
+struct Report {
+    /// The open characteristics of each steward (keyed by GitHub handle).
+    stewards: BTreeMap<String, Group>,
+
+    /// The open characteristics without a steward.
+    unassigned: Vec<Open>,
+}
+
+/// Groups the open characteristics by the GitHub handles of their stewards.
+fn group(characteristics: Vec<(PathBuf, Characteristic)>) -> Report {
+    let mut report = Report::default();
+
+    for (path, characteristic) in characteristics {
+        if !matches!(characteristic.state(), State::Draft | State::Proposed) {
+            continue;
+        }
+
+        let open = || Open {
+            path: path.clone(),
+            state: characteristic.state(),
+            identifier: characteristic.identifier().map(|id| id.to_string()),
+            name: characteristic.name().map(String::from),
+        };
+
+        match characteristic.stewards() {
+            Some(stewards) => {
+                for steward in stewards {
+                    report
+                        .stewards
+                        .entry(steward.github().to_string())
+                        .or_insert_with(|| Group {
+                            name: steward.name().to_string(),
+                            characteristics: Vec::new(),
+                        })
+                        .characteristics
+                        .push(open());
+                }
+            }
+            None => report.unassigned.push(open()),
+        }
+    }
+
+    report
+}
+
+/// Prints a group of open characteristics.
+fn section(heading: &str, characteristics: &[Open]) {
+    println!("{} ({})", heading.bold(), characteristics.len());
+
+    for open in characteristics {
+        println!(
+            "  {:<8}  {:<16}  {}",
+            open.state.as_str(),
+            open.identifier.as_deref().unwrap_or("-"),
+            open.name
+                .clone()
+                .unwrap_or_else(|| open.path.display().to_string())
+        );
+    }
+}
+
+/// Prints the report as a list.
+fn table(report: &Report) {
+    if report.stewards.is_empty() && report.unassigned.is_empty() {
+        println!("no open drafts or proposals");
+        return;
+    }
+
+    let mut sections = report
+        .stewards
+        .iter()
+        .map(|(handle, group)| {
+            (
+                format!("@{handle} ({})", group.name),
+                &group.characteristics,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    if !report.unassigned.is_empty() {
+        sections.push((String::from("Unassigned"), &report.unassigned));
+    }
+
+    for (i, (heading, characteristics)) in sections.into_iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+
+        section(&heading, characteristics);
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.root))?;
+    let report = group(characteristics);
+
+    match args.format {
+        Format::Table => table(&report),
+        Format::Json => println!("{}", serde_json::to_string_pretty(&report)?),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+//! Errors caused by the input to the command line tool.
+//!
+//! These are mistakes the user can fix (e.g., a missing directory or a file
+//! that cannot be parsed), so they are reported as a plain message rather than
+//! with a backtrace (see [`report`]).
+
+use std::path::PathBuf;
+
+use ontology::graph::Issue;
+
+use crate::config::FILE_NAME;
+
+/// Joins a list of paths for display.
+fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("`{}`", path.display()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Joins a list of structural issues within an ontology for display.
+fn join_issues(issues: &[Issue]) -> String {
+    issues
+        .iter()
+        .map(|issue| issue.to_string())
+        .collect::<Vec<_>>()
+        .join("\n* ")
+}
+
+/// An error caused by the input to the command line tool.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A file could not be read.
+    #[error("reading file `{}`: {source}", path.display())]
+    Io {
+        /// The path of the file.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: std::io::Error,
+    },
+
+    /// Files could not be discovered (e.g., a path does not exist or a glob
+    /// is invalid).
+    #[error(transparent)]
+    Discovery(#[from] ecc_check::Error),
+
+    /// No files were found within the provided paths.
+    #[error("no {kind} files matched within: {}", join_paths(.paths))]
+    NoFiles {
+        /// The kind of file that was searched for.
+        kind: &'static str,
+
+        /// The paths that were searched.
+        paths: Vec<PathBuf>,
+    },
+
+    /// No characteristic matched an identifier or name.
+    #[error("no characteristic has the identifier or name `{query}`")]
+    NoCharacteristic {
+        /// The identifier or name that was searched for.
+        query: String,
+    },
+
+    /// No ontology directory was provided or configured.
+    #[error("no ontology directory was provided and none is configured in `{FILE_NAME}`")]
+    NoOntology,
+
+    /// A characteristic could not be parsed.
+    #[error("parsing characteristic `{}`: {source}", path.display())]
+    Characteristic {
+        /// The path of the characteristic.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: ecc::io::Error,
+    },
+
+    /// An ontology node could not be parsed.
+    #[error("parsing node `{}`: {source}", path.display())]
+    Node {
+        /// The path of the node.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: serde_yaml::Error,
+    },
+
+    /// The ontology is structurally invalid.
+    #[error("the ontology is invalid:\n\n* {}", join_issues(.0))]
+    Ontology(Vec<Issue>),
+
+    /// The configuration file could not be parsed.
+    #[error("parsing configuration `{}`: {source}", path.display())]
+    Config {
+        /// The path of the configuration file.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: toml::de::Error,
+    },
+
+    /// The configuration file refers to a lint that does not exist.
+    #[error(
+        "unknown lint `{lint}` in `{}`; expected one of: {expected}",
+        path.display()
+    )]
+    UnknownLint {
+        /// The name of the lint.
+        lint: String,
+
+        /// The path of the configuration file.
+        path: PathBuf,
+
+        /// The names of the lints that exist.
+        expected: String,
+    },
+}
+
+/// Prints an error that ended the program.
+///
+/// Errors caused by the input (an [`Error`] or [`ecc_check::Error`] anywhere
+/// within the chain) are
+/// printed as their chain of messages. Any other error is unexpected, so it is
+/// printed along with its backtrace (if one was captured).
+pub fn report(err: &anyhow::Error) {
+    if !err
+        .chain()
+        .any(|cause| cause.is::<Error>() || cause.is::<ecc_check::Error>())
+    {
+        eprintln!("Error: {err:?}");
+        return;
+    }
+
+    eprintln!("Error: {err}");
+
+    let mut previous = err.to_string();
+    let mut causes = Vec::new();
+
+    for cause in err.chain().skip(1) {
+        let message = cause.to_string();
+
+        // NOTE: most errors include their source within their own message, so
+        // the source is only printed if it adds something new.
+        if !previous.ends_with(&message) {
+            causes.push(message.clone());
+        }
+
+        previous = message;
+    }
+
+    if !causes.is_empty() {
+        eprintln!("\nCaused by:");
+
+        for cause in causes {
+            eprintln!("    {cause}");
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Generation of repository files from the characteristic tree.
+
+use clap::Parser;
+use clap::Subcommand;
+
+use crate::config::Config;
+
+mod code_owners;
+
+/// Generates repository files from the characteristic tree.
+#[derive(Parser)]
+pub struct Args {
+    /// The command to run.
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The command to run.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Generates the review routing from the stewards of each characteristic.
+    CodeOwners(code_owners::Args),
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    match args.command {
+        Command::CodeOwners(args) => code_owners::main(args, config),
+    }
+}
@@ -0,0 +1,145 @@
+//! Generation of review routing from the stewards of each characteristic.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use clap::ValueEnum;
+use tracing::info;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The comment at the top of generated files.
+const HEADER: &str = "Generated by `ecc-cli generate code-owners` from the stewards of each \
+                      characteristic; do not edit by hand.";
+
+/// The format of the review routing.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    /// A GitHub `CODEOWNERS` file.
+    Codeowners,
+
+    /// A YAML mapping from each path to the GitHub handles of its reviewers.
+    Yaml,
+}
+
+/// Generates the review routing from the stewards of each characteristic.
+///
+/// Each characteristic file is routed to the GitHub handles of its stewards.
+/// Paths are relative to the directory containing `ecc.toml` (which is
+/// expected to be the root of the repository), and characteristics without
+/// stewards are omitted.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The format of the review routing.
+    #[arg(short, long, value_enum, default_value_t = Format::Codeowners)]
+    format: Format,
+
+    /// The file to write the review routing to.
+    ///
+    /// Defaults to standard output.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+
+    /// Reports whether the output file is out of date without modifying it.
+    ///
+    /// The exit code is `1` if the output file does not match the generated
+    /// review routing.
+    #[arg(long, requires = "output")]
+    check: bool,
+}
+
+/// Gets the GitHub handles of the stewards of each characteristic (keyed by
+/// path).
+fn routes(
+    characteristics: Vec<(PathBuf, ecc::Characteristic)>,
+    root: &Path,
+) -> BTreeMap<String, Vec<String>> {
+    characteristics
+        .into_iter()
+        .filter_map(|(path, characteristic)| {
+            let handles = characteristic
+                .stewards()?
+                .iter()
+                .map(|steward| format!("@{}", steward.github()))
+                .collect();
+
+            Some((tree::relative(&path, root), handles))
+        })
+        .collect()
+}
+
+/// Renders the routes as a GitHub `CODEOWNERS` file.
+///
+/// Paths are anchored to the root of the repository, and spaces within paths
+/// are escaped.
+fn codeowners(routes: &BTreeMap<String, Vec<String>>) -> String {
+    let mut contents = format!("# {HEADER}\n");
+
+    for (path, handles) in routes {
+        contents.push_str(&format!(
+            "/{} {}\n",
+            path.replace(' ', "\\ "),
+            handles.join(" ")
+        ));
+    }
+
+    contents
+}
+
+/// Renders the routes as a YAML mapping.
+fn yaml(routes: &BTreeMap<String, Vec<String>>) -> anyhow::Result<String> {
+    let mapping = serde_yaml::to_string(routes).context("serializing review routing")?;
+    Ok(format!("# {HEADER}\n{mapping}"))
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let characteristics = tree::load(&config.characteristics(args.path))?;
+    let routes = routes(characteristics, config.root());
+
+    let contents = match args.format {
+        Format::Codeowners => codeowners(&routes),
+        Format::Yaml => yaml(&routes)?,
+    };
+
+    let Some(output) = args.output else {
+        print!("{contents}");
+        return Ok(());
+    };
+
+    if args.check {
+        let current = std::fs::read_to_string(&output).unwrap_or_default();
+
+        if current != contents {
+            println!(
+                "`{}` is out of date; run `ecc-cli generate code-owners --output {}` to \
+                 regenerate it",
+                output.display(),
+                output.display()
+            );
+            std::process::exit(crate::EXIT_FAILURE.into());
+        }
+
+        info!("`{}` is up to date", output.display());
+        return Ok(());
+    }
+
+    std::fs::write(&output, contents)
+        .with_context(|| format!("writing file: {}", output.display()))?;
+    info!(
+        "wrote review routing for {} characteristics to `{}`",
+        routes.len(),
+        output.display()
+    );
+
+    Ok(())
+}
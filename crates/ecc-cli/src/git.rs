@@ -0,0 +1,182 @@
+//! Facilities for working with git repositories.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+
+use anyhow::Context;
+use anyhow::bail;
+use ecc::Characteristic;
+use tracing::info;
+
+use crate::tree;
+
+/// Runs a git command within a directory and returns the trimmed standard
+/// output.
+pub fn run(directory: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(directory)
+        .args(args)
+        .output()
+        .context("running `git`")?;
+
+    if !output.status.success() {
+        bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Gets the root of the repository containing a path along with the path
+/// relative to that root.
+fn locate(path: &Path) -> anyhow::Result<(PathBuf, PathBuf)> {
+    let repository = run(path, &["rev-parse", "--show-toplevel"])
+        .map(PathBuf::from)
+        .with_context(|| format!("locating the repository for `{}`", path.display()))?;
+    let relative = path
+        .canonicalize()
+        .with_context(|| format!("resolving path: {}", path.display()))?
+        .strip_prefix(repository.canonicalize()?)
+        .context("resolving the path relative to the repository")?
+        .to_path_buf();
+
+    Ok((repository, relative))
+}
+
+/// A temporary git worktree that is removed when dropped.
+struct Worktree {
+    /// The repository the worktree belongs to.
+    repository: PathBuf,
+
+    /// The path to the worktree.
+    path: PathBuf,
+}
+
+impl Worktree {
+    /// Checks out a revision of the repository into a temporary worktree.
+    fn checkout(repository: &Path, revision: &str) -> anyhow::Result<Self> {
+        let path = std::env::temp_dir().join(format!("ecc-worktree-{}", std::process::id()));
+        let path_str = path.to_string_lossy();
+
+        info!("checking out `{revision}` to `{path_str}`");
+        run(
+            repository,
+            &["worktree", "add", "--detach", &path_str, revision],
+        )?;
+
+        Ok(Self {
+            repository: repository.to_path_buf(),
+            path,
+        })
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        let path = self.path.to_string_lossy();
+
+        if let Err(err) = run(&self.repository, &["worktree", "remove", "--force", &path]) {
+            tracing::warn!("unable to remove worktree `{path}`: {err}");
+        }
+    }
+}
+
+/// Runs a function against a directory as it existed at a git revision.
+///
+/// The revision is checked out into a temporary worktree that is removed once
+/// the function returns. If the directory did not exist at the revision, the
+/// function is not run and the default value is returned.
+fn at<T: Default>(
+    path: &Path,
+    revision: &str,
+    f: impl FnOnce(&Path) -> anyhow::Result<T>,
+) -> anyhow::Result<T> {
+    let (repository, relative) = locate(path)?;
+    let worktree = Worktree::checkout(&repository, revision)?;
+    let directory = worktree.path.join(relative);
+
+    // NOTE: the directory may not have existed at the revision, in which case
+    // it contained no characteristics.
+    if !directory.exists() {
+        return Ok(T::default());
+    }
+
+    f(&directory)
+}
+
+/// Loads the characteristics within a directory as they existed at a git
+/// revision.
+///
+/// The revision is checked out into a temporary worktree that is removed once
+/// the characteristics are loaded. The returned paths point into the removed
+/// worktree and should only be used for display.
+pub fn load_at(path: &Path, revision: &str) -> anyhow::Result<Vec<(PathBuf, Characteristic)>> {
+    at(path, revision, |directory| Ok(tree::load(directory)?))
+}
+
+/// Loads the characteristics within a directory as they existed at a git
+/// revision, skipping files that cannot be parsed.
+///
+/// See [`load_at()`] for how the revision is checked out.
+pub fn load_valid_at(
+    path: &Path,
+    revision: &str,
+) -> anyhow::Result<Vec<(PathBuf, Characteristic)>> {
+    at(path, revision, |directory| {
+        Ok(tree::stream(directory)?
+            .filter_map(|(path, result)| result.ok().map(|characteristic| (path, characteristic)))
+            .collect())
+    })
+}
+
+/// Gets the files within a directory that differ from a git revision.
+///
+/// Both modified and deleted files are included, as are untracked files that
+/// are not ignored. The returned paths are joined onto the provided directory
+/// (so they match the paths produced by [`tree::files()`]).
+pub fn changed(path: &Path, revision: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let (repository, relative) = locate(path)?;
+    let pathspec = if relative.as_os_str().is_empty() {
+        String::from(".")
+    } else {
+        relative.to_string_lossy().to_string()
+    };
+
+    let modified = run(
+        &repository,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            revision,
+            "--",
+            &pathspec,
+        ],
+    )?;
+    let untracked = run(
+        &repository,
+        &[
+            "ls-files",
+            "--others",
+            "--exclude-standard",
+            "--",
+            &pathspec,
+        ],
+    )?;
+
+    let mut files = modified
+        .lines()
+        .chain(untracked.lines())
+        .filter_map(|line| Path::new(line).strip_prefix(&relative).ok())
+        .map(|file| path.join(file))
+        .collect::<Vec<_>>();
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
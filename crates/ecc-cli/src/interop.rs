@@ -0,0 +1,10 @@
+//! Interoperability with external standards.
+
+#[cfg(feature = "fhir")]
+pub mod fhir;
+#[cfg(feature = "phenopackets")]
+pub mod phenopackets;
+#[cfg(feature = "rdf")]
+pub mod rdf;
+#[cfg(feature = "sssom")]
+pub mod sssom;
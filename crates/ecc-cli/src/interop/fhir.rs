@@ -0,0 +1,560 @@
+//! Rendering of the characteristic tree into [FHIR R4] terminology resources.
+//!
+//! The adopted characteristics are rendered into a single `CodeSystem` (one
+//! concept per characteristic) whose properties capture the kind of values
+//! each characteristic takes on. Characteristics with a fixed set of values
+//! (binary and categorical characteristics) are additionally rendered into a
+//! `CodeSystem` of their own containing the permissible values along with a
+//! `ValueSet` that includes that system, and the characteristic's concept maps
+//! to the value set through its `value-set` property. The ontology (if any) is
+//! rendered into an `is-a` hierarchy `CodeSystem`.
+//!
+//! All of the resources are collected into a `collection` `Bundle`.
+//!
+//! [FHIR R4]: https://hl7.org/fhir/R4/terminologies.html
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use anyhow::Context;
+use ecc::Characteristic;
+use ecc::common::value::Kind;
+use ontology::Graph;
+use serde_json::Value;
+use serde_json::json;
+
+/// The base of the canonical URLs of the rendered resources.
+pub const BASE_URL: &str = "https://github.com/stjudecloud/ecc/fhir";
+
+/// The identifier of the characteristic code system.
+const CHARACTERISTICS: &str = "ecc-characteristics";
+
+/// The identifier of the ontology code system.
+const ONTOLOGY: &str = "ecc-ontology";
+
+/// The publisher of the rendered resources.
+const PUBLISHER: &str = "St. Jude Children's Research Hospital";
+
+/// Gets the canonical URL of a resource.
+fn canonical(resource: &str, id: &str) -> String {
+    format!("{BASE_URL}/{resource}/{id}")
+}
+
+/// Gets the canonical URL of the code system containing the permissible
+/// values of a characteristic.
+pub fn values_url(identifier: &str) -> String {
+    canonical("CodeSystem", &format!("{identifier}-values"))
+}
+
+/// Gets the canonical URL of the value set of a characteristic.
+pub fn value_set_url(identifier: &str) -> String {
+    canonical("ValueSet", identifier)
+}
+
+/// Renders a property value.
+///
+/// The value is placed within the `value[x]` field named by `r#type` (e.g.,
+/// `valueCode`).
+fn property(code: &str, r#type: &str, value: impl Into<Value>) -> Value {
+    json!({ "code": code, format!("value{type}"): value.into() })
+}
+
+/// Renders the permissible values of a characteristic as concepts.
+///
+/// Returns [`None`] for characteristics that do not have a fixed set of
+/// values.
+fn values(kind: &Kind) -> Option<Vec<Value>> {
+    match kind {
+        Kind::Binary { description, .. } => Some(
+            [
+                ("true", &description.r#true),
+                ("false", &description.r#false),
+            ]
+            .into_iter()
+            .map(|(code, field)| {
+                json!({
+                    "code": code,
+                    "display": field.summary.as_str(),
+                    "definition": field.details.as_str().trim(),
+                })
+            })
+            .collect(),
+        ),
+        Kind::Categorical { options } => Some(
+            options
+                .iter()
+                .map(|option| json!({ "code": option, "display": option }))
+                .collect(),
+        ),
+        Kind::Numerical { .. }
+        | Kind::Fraction { .. }
+        | Kind::Immunophenotype { .. }
+        | Kind::Other { .. } => None,
+    }
+}
+
+/// Renders a characteristic as a concept within the characteristic code
+/// system.
+///
+/// Returns [`None`] if the characteristic has not been adopted.
+fn concept(characteristic: &Characteristic) -> Option<Value> {
+    let Characteristic::Adopted { common, .. } = characteristic else {
+        return None;
+    };
+
+    let identifier = common.identifier().to_string();
+    let kind = common.values();
+
+    let mut properties = vec![property("kind", "Code", kind.name())];
+
+    match kind {
+        Kind::Numerical { r#type, units } => {
+            properties.push(property("numerical-type", "Code", r#type.to_string()));
+            properties.push(property("units", "String", units.as_str()));
+        }
+        Kind::Fraction {
+            denominator_description,
+            scale,
+            min,
+            max,
+        } => {
+            properties.push(property("scale", "Code", scale.to_string()));
+            properties.push(property("units", "String", scale.units()));
+            properties.push(property(
+                "denominator",
+                "String",
+                denominator_description.as_str(),
+            ));
+            properties.push(property("minimum", "Decimal", min.unwrap_or(0.0)));
+            properties.push(property(
+                "maximum",
+                "Decimal",
+                max.unwrap_or(scale.maximum()),
+            ));
+        }
+        Kind::Binary { .. } | Kind::Categorical { .. } => {
+            properties.push(property("value-set", "String", value_set_url(&identifier)));
+        }
+        Kind::Immunophenotype { markers } => {
+            properties.extend(
+                markers
+                    .iter()
+                    .map(|marker| property("marker", "String", marker.as_str())),
+            );
+        }
+        // NOTE: only drafts can use other kinds of values, and drafts are
+        // never rendered.
+        Kind::Other { .. } => {}
+    }
+
+    properties.push(property("rfc", "String", common.rfc().as_str()));
+
+    if let Some(date) = characteristic.adoption_date() {
+        properties.push(property("adoption-date", "DateTime", date.to_rfc3339()));
+    }
+
+    Some(json!({
+        "code": identifier,
+        "display": common.name(),
+        "definition": common.description().trim(),
+        "property": properties,
+    }))
+}
+
+/// Renders the code system containing every adopted characteristic.
+pub fn characteristics(characteristics: &[Characteristic]) -> Value {
+    let concepts = characteristics
+        .iter()
+        .filter_map(concept)
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceType": "CodeSystem",
+        "id": CHARACTERISTICS,
+        "url": canonical("CodeSystem", CHARACTERISTICS),
+        "name": "EccCharacteristics",
+        "title": "Encyclopedia of Composable Characteristics",
+        "status": "active",
+        "publisher": PUBLISHER,
+        "caseSensitive": true,
+        "content": "complete",
+        "count": concepts.len(),
+        "property": [
+            {
+                "code": "kind",
+                "description": "The kind of values the characteristic takes on.",
+                "type": "code",
+            },
+            {
+                "code": "numerical-type",
+                "description": "The type of a numerical characteristic.",
+                "type": "code",
+            },
+            {
+                "code": "units",
+                "description": "The units of measurement of a numerical characteristic.",
+                "type": "string",
+            },
+            {
+                "code": "scale",
+                "description": "The scale of a fraction (`percent` or `proportion`).",
+                "type": "code",
+            },
+            {
+                "code": "denominator",
+                "description": "The whole that a fraction is a part of.",
+                "type": "string",
+            },
+            {
+                "code": "minimum",
+                "description": "The smallest permissible value of a fraction.",
+                "type": "decimal",
+            },
+            {
+                "code": "maximum",
+                "description": "The largest permissible value of a fraction.",
+                "type": "decimal",
+            },
+            {
+                "code": "marker",
+                "description": "A marker assessed by an immunophenotypic characteristic.",
+                "type": "string",
+            },
+            {
+                "code": "value-set",
+                "description": "The canonical URL of the value set containing the permissible \
+                                values.",
+                "type": "string",
+            },
+            {
+                "code": "rfc",
+                "description": "The URL of the request for comments.",
+                "type": "string",
+            },
+            {
+                "code": "adoption-date",
+                "description": "The date the characteristic was adopted.",
+                "type": "dateTime",
+            },
+        ],
+        "concept": concepts,
+    })
+}
+
+/// Renders the code system and value set containing the permissible values of
+/// an adopted characteristic.
+///
+/// Returns [`None`] if the characteristic has not been adopted or does not
+/// have a fixed set of values.
+pub fn value_set(characteristic: &Characteristic) -> Option<(Value, Value)> {
+    let Characteristic::Adopted { common, .. } = characteristic else {
+        return None;
+    };
+
+    let concepts = values(common.values())?;
+    let identifier = common.identifier().to_string();
+    let system = values_url(&identifier);
+
+    let code_system = json!({
+        "resourceType": "CodeSystem",
+        "id": format!("{identifier}-values"),
+        "url": system,
+        "title": format!("{} (values)", common.name()),
+        "status": "active",
+        "publisher": PUBLISHER,
+        "caseSensitive": true,
+        "valueSet": value_set_url(&identifier),
+        "content": "complete",
+        "count": concepts.len(),
+        "concept": concepts,
+    });
+
+    let value_set = json!({
+        "resourceType": "ValueSet",
+        "id": identifier,
+        "url": value_set_url(&identifier),
+        "title": common.name(),
+        "status": "active",
+        "publisher": PUBLISHER,
+        "description": common.description().trim(),
+        "compose": {
+            "include": [{ "system": system }],
+        },
+    });
+
+    Some((code_system, value_set))
+}
+
+/// Renders the ontology as an `is-a` hierarchy code system.
+///
+/// The concepts are listed depth first (see [`Graph::preorder()`]).
+pub fn ontology(graph: &Graph) -> Value {
+    let concepts = graph
+        .preorder()
+        .into_iter()
+        .map(|node| {
+            let mut concept = json!({
+                "code": node.code(),
+                "display": node.name().inner(),
+            });
+
+            if let Some(parent) = graph.parent(node.name().inner()) {
+                concept["property"] = json!([property("parent", "Code", parent.code())]);
+            }
+
+            concept
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceType": "CodeSystem",
+        "id": ONTOLOGY,
+        "url": canonical("CodeSystem", ONTOLOGY),
+        "name": "EccOntology",
+        "title": "Encyclopedia of Composable Characteristics Ontology",
+        "status": "active",
+        "publisher": PUBLISHER,
+        "caseSensitive": true,
+        "hierarchyMeaning": "is-a",
+        "content": "complete",
+        "count": concepts.len(),
+        "property": [{
+            "code": "parent",
+            "uri": "http://hl7.org/fhir/concept-properties#parent",
+            "type": "code",
+        }],
+        "concept": concepts,
+    })
+}
+
+/// Renders the characteristics (and the ontology, if provided) into a bundle
+/// of terminology resources.
+pub fn bundle(characteristics: &[Characteristic], graph: Option<&Graph>) -> Value {
+    let mut resources = vec![self::characteristics(characteristics)];
+
+    for (code_system, value_set) in characteristics.iter().filter_map(value_set) {
+        resources.push(code_system);
+        resources.push(value_set);
+    }
+
+    resources.extend(graph.map(ontology));
+
+    let entries = resources
+        .into_iter()
+        .map(|resource| json!({ "fullUrl": resource["url"].clone(), "resource": resource }))
+        .collect::<Vec<_>>();
+
+    json!({
+        "resourceType": "Bundle",
+        "type": "collection",
+        "entry": entries,
+    })
+}
+
+/// Writes the characteristics (and the ontology, if provided) as a FHIR
+/// bundle.
+pub fn write(
+    characteristics: &[Characteristic],
+    graph: Option<&Graph>,
+    path: &Path,
+) -> anyhow::Result<()> {
+    let writer = File::create(path).map(BufWriter::new)?;
+    serde_json::to_writer_pretty(writer, &bundle(characteristics, graph))
+        .context("serializing FHIR bundle")
+}
+
+#[cfg(test)]
+mod tests {
+    use test_infra::fixtures;
+
+    use super::*;
+
+    /// Gets an adopted characteristic with the provided identifier and values.
+    fn adopted(identifier: &str, values: &str) -> Characteristic {
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier(identifier));
+            common.set_values(serde_yaml::from_str(values).unwrap());
+        })
+    }
+
+    /// Gets an adopted characteristic of each kind.
+    fn characteristics() -> Vec<Characteristic> {
+        vec![
+            fixtures::adopted_characteristic(),
+            adopted("ECC-MORPH-000002", "kind: categorical\noptions: [Foo, Bar]"),
+            adopted(
+                "ECC-MORPH-000003",
+                "kind: numerical\ntype: float\nunits: cm",
+            ),
+            adopted(
+                "ECC-MORPH-000004",
+                "kind: fraction\ndenominator_description: Nucleated cells.\nscale: percent",
+            ),
+            adopted(
+                "ECC-MORPH-000005",
+                "kind: immunophenotype\nmarkers: [CD19, TdT]",
+            ),
+            adopted(
+                "ECC-MORPH-000006",
+                "kind: other\ndescription: A karyotype in ISCN notation.",
+            ),
+        ]
+    }
+
+    /// Gets the expected concept of a characteristic with the provided
+    /// properties (followed by the properties shared by every concept).
+    fn concept(identifier: &str, mut properties: Vec<Value>) -> Value {
+        properties.push(property(
+            "rfc",
+            "String",
+            "https://github.com/stjudecloud/ecc/issues/1",
+        ));
+        properties.push(property(
+            "adoption-date",
+            "DateTime",
+            "2024-01-01T00:00:00+00:00",
+        ));
+
+        json!({
+            "code": identifier,
+            "display": "A Characteristic Name",
+            "definition": "A description of the characteristic.",
+            "property": properties,
+        })
+    }
+
+    #[test]
+    fn concepts() {
+        let code_system = super::characteristics(&characteristics());
+        assert_eq!(code_system["count"], 6);
+        assert_eq!(
+            code_system["concept"],
+            json!([
+                concept(
+                    "ECC-MORPH-000001",
+                    vec![
+                        property("kind", "Code", "binary"),
+                        property(
+                            "value-set",
+                            "String",
+                            format!("{BASE_URL}/ValueSet/ECC-MORPH-000001")
+                        ),
+                    ]
+                ),
+                concept(
+                    "ECC-MORPH-000002",
+                    vec![
+                        property("kind", "Code", "categorical"),
+                        property(
+                            "value-set",
+                            "String",
+                            format!("{BASE_URL}/ValueSet/ECC-MORPH-000002")
+                        ),
+                    ]
+                ),
+                concept(
+                    "ECC-MORPH-000003",
+                    vec![
+                        property("kind", "Code", "numerical"),
+                        property("numerical-type", "Code", "float"),
+                        property("units", "String", "cm"),
+                    ]
+                ),
+                concept(
+                    "ECC-MORPH-000004",
+                    vec![
+                        property("kind", "Code", "fraction"),
+                        property("scale", "Code", "percent"),
+                        property("units", "String", "%"),
+                        property("denominator", "String", "Nucleated cells."),
+                        property("minimum", "Decimal", 0.0),
+                        property("maximum", "Decimal", 100.0),
+                    ]
+                ),
+                concept(
+                    "ECC-MORPH-000005",
+                    vec![
+                        property("kind", "Code", "immunophenotype"),
+                        property("marker", "String", "CD19"),
+                        property("marker", "String", "TdT"),
+                    ]
+                ),
+                concept("ECC-MORPH-000006", vec![property("kind", "Code", "other")]),
+            ])
+        );
+
+        // NOTE: characteristics that have not been adopted are left out.
+        let code_system = super::characteristics(&[fixtures::proposed_characteristic()]);
+        assert_eq!(code_system["concept"], json!([]));
+    }
+
+    #[test]
+    fn value_sets() {
+        let characteristics = characteristics();
+
+        let (code_system, value_set) = value_set(&characteristics[0]).unwrap();
+        assert_eq!(
+            code_system,
+            json!({
+                "resourceType": "CodeSystem",
+                "id": "ECC-MORPH-000001-values",
+                "url": format!("{BASE_URL}/CodeSystem/ECC-MORPH-000001-values"),
+                "title": "A Characteristic Name (values)",
+                "status": "active",
+                "publisher": PUBLISHER,
+                "caseSensitive": true,
+                "valueSet": format!("{BASE_URL}/ValueSet/ECC-MORPH-000001"),
+                "content": "complete",
+                "count": 2,
+                "concept": [
+                    {
+                        "code": "true",
+                        "display": "The feature is present.",
+                        "definition": "The feature was observed.",
+                    },
+                    {
+                        "code": "false",
+                        "display": "The feature is absent.",
+                        "definition": "The feature was not observed.",
+                    },
+                ],
+            })
+        );
+        assert_eq!(
+            value_set,
+            json!({
+                "resourceType": "ValueSet",
+                "id": "ECC-MORPH-000001",
+                "url": format!("{BASE_URL}/ValueSet/ECC-MORPH-000001"),
+                "title": "A Characteristic Name",
+                "status": "active",
+                "publisher": PUBLISHER,
+                "description": "A description of the characteristic.",
+                "compose": {
+                    "include": [{
+                        "system": format!("{BASE_URL}/CodeSystem/ECC-MORPH-000001-values"),
+                    }],
+                },
+            })
+        );
+
+        let (code_system, _) = super::value_set(&characteristics[1]).unwrap();
+        assert_eq!(
+            code_system["concept"],
+            json!([
+                { "code": "Bar", "display": "Bar" },
+                { "code": "Foo", "display": "Foo" },
+            ])
+        );
+
+        // NOTE: only characteristics with a fixed set of values have a value
+        // set.
+        for characteristic in &characteristics[2..] {
+            assert_eq!(super::value_set(characteristic), None);
+        }
+
+        let resources = bundle(&characteristics, None);
+        assert_eq!(resources["entry"].as_array().unwrap().len(), 5);
+    }
+}
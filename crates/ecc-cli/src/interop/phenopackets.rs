@@ -0,0 +1,277 @@
+//! Rendering of the characteristic tree into [GA4GH Phenopacket] building
+//! blocks.
+//!
+//! Each adopted characteristic is rendered as an `OntologyClass` (using the
+//! `ECC` namespace prefix) along with a template of the `Measurement` that
+//! records it: the characteristic is the assay, and the value is either one
+//! of the `OntologyClass`es listed as the characteristic's permissible values
+//! (for binary and categorical characteristics) or a `Quantity` in the
+//! characteristic's units (for numerical characteristics). The `Resource`
+//! describing the `ECC` namespace is included so that it can be copied into
+//! the `MetaData` of phenopackets that use the definitions.
+//!
+//! [GA4GH Phenopacket]: https://phenopacket-schema.readthedocs.io/en/latest/
+
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use anyhow::Context;
+use ecc::Characteristic;
+use ecc::common::value::Kind;
+use serde_json::Value;
+use serde_json::json;
+
+/// The namespace prefix of ECC terms.
+pub const NAMESPACE_PREFIX: &str = "ECC";
+
+/// The prefix that turns a term's local identifier into an IRI.
+pub const IRI_PREFIX: &str = "https://github.com/stjudecloud/ecc/terms/";
+
+/// The version of the resource used when no characteristic has been adopted.
+const UNRELEASED: &str = "unreleased";
+
+/// Renders an `OntologyClass`.
+fn class(local: &str, label: &str) -> Value {
+    json!({ "id": format!("{NAMESPACE_PREFIX}:{local}"), "label": label })
+}
+
+/// Renders the `Resource` describing the `ECC` namespace.
+///
+/// The version of the resource is the date of the most recent adoption.
+pub fn resource(characteristics: &[Characteristic]) -> Value {
+    let version = characteristics
+        .iter()
+        .filter_map(Characteristic::adoption_date)
+        .max()
+        .map(|date| date.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| String::from(UNRELEASED));
+
+    json!({
+        "id": NAMESPACE_PREFIX.to_lowercase(),
+        "name": "Encyclopedia of Composable Characteristics",
+        "url": "https://github.com/stjudecloud/ecc",
+        "version": version,
+        "namespacePrefix": NAMESPACE_PREFIX,
+        "iriPrefix": IRI_PREFIX,
+    })
+}
+
+/// Renders the definition of an adopted characteristic.
+///
+/// Returns [`None`] if the characteristic has not been adopted.
+pub fn definition(characteristic: &Characteristic) -> Option<Value> {
+    let Characteristic::Adopted { common, .. } = characteristic else {
+        return None;
+    };
+
+    let identifier = common.identifier().to_string();
+    let term = class(&identifier, common.name());
+
+    let values = match common.values() {
+        Kind::Binary { description, .. } => vec![
+            class(
+                &format!("{identifier}/true"),
+                description.r#true.summary.as_str(),
+            ),
+            class(
+                &format!("{identifier}/false"),
+                description.r#false.summary.as_str(),
+            ),
+        ],
+        Kind::Categorical { options } => options
+            .iter()
+            .map(|option| class(&format!("{identifier}/{option}"), option))
+            .collect(),
+        Kind::Immunophenotype { markers } => markers
+            .iter()
+            .map(|marker| class(&format!("{identifier}/{marker}"), marker.as_str()))
+            .collect(),
+        Kind::Numerical { .. } | Kind::Fraction { .. } | Kind::Other { .. } => Vec::new(),
+    };
+
+    let mut definition = json!({
+        "term": term,
+        "description": common.description().trim(),
+        "kind": common.values().name(),
+        "measurement": {
+            "assay": term,
+        },
+    });
+
+    match common.values() {
+        Kind::Numerical { units, .. } => {
+            definition["measurement"]["value"] = json!({
+                "quantity": {
+                    "unit": class(&format!("{identifier}/units"), units),
+                },
+            });
+        }
+        Kind::Fraction {
+            scale, min, max, ..
+        } => {
+            definition["measurement"]["value"] = json!({
+                "quantity": {
+                    "unit": class(&format!("{identifier}/units"), scale.units()),
+                    "referenceRange": {
+                        "unit": class(&format!("{identifier}/units"), scale.units()),
+                        "low": min.unwrap_or(0.0),
+                        "high": max.unwrap_or(scale.maximum()),
+                    },
+                },
+            });
+        }
+        // NOTE: the value of the measurement is one of the permissible
+        // values, so no single value is rendered within the template.
+        Kind::Binary { .. } | Kind::Categorical { .. } => {
+            definition["values"] = Value::from(values);
+        }
+        // NOTE: each marker is measured as its own component of the
+        // measurement.
+        Kind::Immunophenotype { .. } => {
+            definition["markers"] = Value::from(values);
+        }
+        // NOTE: only drafts can use other kinds of values, and drafts are
+        // never rendered.
+        Kind::Other { .. } => {}
+    }
+
+    Some(definition)
+}
+
+/// Renders the definitions of the adopted characteristics along with the
+/// resource describing their namespace.
+pub fn definitions(characteristics: &[Characteristic]) -> Value {
+    json!({
+        "resources": [resource(characteristics)],
+        "characteristics": characteristics
+            .iter()
+            .filter_map(definition)
+            .collect::<Vec<_>>(),
+    })
+}
+
+/// Writes the definitions of the adopted characteristics.
+pub fn write(characteristics: &[Characteristic], path: &Path) -> anyhow::Result<()> {
+    let writer = File::create(path).map(BufWriter::new)?;
+    serde_json::to_writer_pretty(writer, &definitions(characteristics))
+        .context("serializing phenopacket definitions")
+}
+
+#[cfg(test)]
+mod tests {
+    use test_infra::fixtures;
+
+    use super::*;
+
+    /// Gets an adopted characteristic with the provided identifier and values.
+    fn adopted(identifier: &str, values: &str) -> Characteristic {
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier(identifier));
+            common.set_values(serde_yaml::from_str(values).unwrap());
+        })
+    }
+
+    /// Gets the expected definition of a characteristic without the
+    /// kind-specific fields.
+    fn definition(identifier: &str, kind: &str) -> Value {
+        let term = json!({ "id": format!("ECC:{identifier}"), "label": "A Characteristic Name" });
+
+        json!({
+            "term": term,
+            "description": "A description of the characteristic.",
+            "kind": kind,
+            "measurement": { "assay": term },
+        })
+    }
+
+    #[test]
+    fn definitions() {
+        let characteristics = [
+            fixtures::adopted_characteristic(),
+            adopted("ECC-MORPH-000002", "kind: categorical\noptions: [Foo, Bar]"),
+            adopted(
+                "ECC-MORPH-000003",
+                "kind: numerical\ntype: float\nunits: cm",
+            ),
+            adopted(
+                "ECC-MORPH-000004",
+                "kind: fraction\ndenominator_description: Nucleated cells.\nscale: percent",
+            ),
+            adopted(
+                "ECC-MORPH-000005",
+                "kind: immunophenotype\nmarkers: [CD19, TdT]",
+            ),
+            adopted(
+                "ECC-MORPH-000006",
+                "kind: other\ndescription: A karyotype in ISCN notation.",
+            ),
+            fixtures::proposed_characteristic_with(|common| {
+                common.set_identifier(fixtures::identifier("ECC-MORPH-000007"));
+            }),
+        ];
+
+        let mut binary = definition("ECC-MORPH-000001", "binary");
+        binary["values"] = json!([
+            { "id": "ECC:ECC-MORPH-000001/true", "label": "The feature is present." },
+            { "id": "ECC:ECC-MORPH-000001/false", "label": "The feature is absent." },
+        ]);
+
+        let mut categorical = definition("ECC-MORPH-000002", "categorical");
+        categorical["values"] = json!([
+            { "id": "ECC:ECC-MORPH-000002/Bar", "label": "Bar" },
+            { "id": "ECC:ECC-MORPH-000002/Foo", "label": "Foo" },
+        ]);
+
+        let mut numerical = definition("ECC-MORPH-000003", "numerical");
+        numerical["measurement"]["value"] = json!({
+            "quantity": {
+                "unit": { "id": "ECC:ECC-MORPH-000003/units", "label": "cm" },
+            },
+        });
+
+        let unit = json!({ "id": "ECC:ECC-MORPH-000004/units", "label": "%" });
+        let mut fraction = definition("ECC-MORPH-000004", "fraction");
+        fraction["measurement"]["value"] = json!({
+            "quantity": {
+                "unit": unit,
+                "referenceRange": { "unit": unit, "low": 0.0, "high": 100.0 },
+            },
+        });
+
+        let mut immunophenotype = definition("ECC-MORPH-000005", "immunophenotype");
+        immunophenotype["markers"] = json!([
+            { "id": "ECC:ECC-MORPH-000005/CD19", "label": "CD19" },
+            { "id": "ECC:ECC-MORPH-000005/TdT", "label": "TdT" },
+        ]);
+
+        // NOTE: the proposed characteristic is left out.
+        assert_eq!(
+            super::definitions(&characteristics),
+            json!({
+                "resources": [{
+                    "id": "ecc",
+                    "name": "Encyclopedia of Composable Characteristics",
+                    "url": "https://github.com/stjudecloud/ecc",
+                    "version": "2024-01-01",
+                    "namespacePrefix": "ECC",
+                    "iriPrefix": IRI_PREFIX,
+                }],
+                "characteristics": [
+                    binary,
+                    categorical,
+                    numerical,
+                    fraction,
+                    immunophenotype,
+                    definition("ECC-MORPH-000006", "other"),
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn unreleased() {
+        let resource = resource(&[fixtures::proposed_characteristic()]);
+        assert_eq!(resource["version"], UNRELEASED);
+    }
+}
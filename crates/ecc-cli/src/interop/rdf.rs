@@ -0,0 +1,319 @@
+//! Rendering of the characteristic tree into [RDF] using the [Turtle] syntax.
+//!
+//! Characteristics are described using a small OWL vocabulary for ECC itself
+//! ([`VOCABULARY`], under the [`NAMESPACE`] namespace) that models the states
+//! of the lifecycle, the kinds of permissible values, and references. Each
+//! characteristic with an identifier becomes an `ecc:Characteristic` named by
+//! its identifier within the [`TERMS`] namespace (e.g.,
+//! `<https://github.com/stjudecloud/ecc/terms/ECC-MORPH-000001>`), while its
+//! values and references are described by blank nodes. Drafts without an
+//! identifier have no stable name, so they are not rendered.
+//!
+//! [RDF]: https://www.w3.org/TR/rdf11-concepts/
+//! [Turtle]: https://www.w3.org/TR/turtle/
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::Context;
+use ecc::Category;
+use ecc::Characteristic;
+use ecc::State;
+use ecc::common::Reference;
+use ecc::common::value::Kind;
+
+/// The namespace of the ECC vocabulary.
+pub const NAMESPACE: &str = "https://github.com/stjudecloud/ecc/ns#";
+
+/// The namespace of the characteristics.
+///
+/// This matches the IRI prefix used by the other interoperability formats.
+pub const TERMS: &str = "https://github.com/stjudecloud/ecc/terms/";
+
+/// The OWL vocabulary describing characteristics.
+pub const VOCABULARY: &str = include_str!("rdf/vocabulary.ttl");
+
+/// Renders a string literal.
+fn literal(s: &str) -> String {
+    let mut literal = String::with_capacity(s.len() + 2);
+    literal.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            c => literal.push(c),
+        }
+    }
+
+    literal.push('"');
+    literal
+}
+
+/// Gets the individual within the vocabulary for a state.
+fn state(state: State) -> &'static str {
+    match state {
+        State::Draft => "ecc:draft",
+        State::Proposed => "ecc:proposed",
+        State::Provisional => "ecc:provisional",
+        State::Adopted => "ecc:adopted",
+    }
+}
+
+/// Gets the individual within the vocabulary for a category.
+fn category(category: Category) -> &'static str {
+    match category {
+        Category::Molecular => "ecc:molecular",
+        Category::Morphological => "ecc:morphological",
+    }
+}
+
+/// Renders a number as an `xsd:decimal` literal.
+fn decimal(value: f64) -> String {
+    format!("\"{value}\"^^xsd:decimal")
+}
+
+/// Renders the permissible values of a characteristic as a blank node.
+fn values(kind: &Kind) -> String {
+    match kind {
+        Kind::Binary {
+            description,
+            threshold,
+        } => {
+            let value = |value: bool, description: &ecc::field::Description| {
+                format!(
+                    "[ a ecc:PermissibleValue ; ecc:booleanValue {value} ; rdfs:label {}@en ; \
+                     dcterms:description {}@en ]",
+                    literal(description.summary.as_str()),
+                    literal(description.details.as_str())
+                )
+            };
+
+            let threshold = threshold
+                .as_ref()
+                .map(|threshold| {
+                    format!(
+                        " ;\n        ecc:derivedFrom term:{} ; ecc:comparator {} ; ecc:threshold \
+                         {} ; ecc:thresholdUnits {}",
+                        threshold.source,
+                        literal(threshold.comparator.as_str()),
+                        decimal(threshold.value),
+                        literal(&threshold.units)
+                    )
+                })
+                .unwrap_or_default();
+
+            format!(
+                "[ a ecc:BinaryValues ;\n        ecc:permissibleValue {},\n            \
+                 {}{threshold} ]",
+                value(true, &description.r#true),
+                value(false, &description.r#false)
+            )
+        }
+        Kind::Categorical { options } => {
+            let options = options
+                .iter()
+                .map(|option| {
+                    format!(
+                        "[ a ecc:PermissibleValue ; rdfs:label {}@en ]",
+                        literal(option)
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            if options.is_empty() {
+                return String::from("[ a ecc:CategoricalValues ]");
+            }
+
+            format!(
+                "[ a ecc:CategoricalValues ;\n        ecc:permissibleValue {} ]",
+                options.join(",\n            ")
+            )
+        }
+        Kind::Numerical { r#type, units } => format!(
+            "[ a ecc:NumericalValues ; ecc:numericalType {} ; ecc:units {} ]",
+            literal(&r#type.to_string()),
+            literal(units)
+        ),
+        Kind::Fraction {
+            denominator_description,
+            scale,
+            min,
+            max,
+        } => format!(
+            "[ a ecc:FractionValues ; ecc:scale {} ; ecc:denominator {}@en ; ecc:minimum {} ; \
+             ecc:maximum {} ]",
+            literal(&scale.to_string()),
+            literal(denominator_description),
+            decimal(min.unwrap_or(0.0)),
+            decimal(max.unwrap_or(scale.maximum()))
+        ),
+        Kind::Immunophenotype { markers } => format!(
+            "[ a ecc:ImmunophenotypeValues ; ecc:marker {} ]",
+            markers
+                .iter()
+                .map(|marker| literal(marker.as_str()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Kind::Other { description } => format!(
+            "[ a ecc:OtherValues ; dcterms:description {}@en ]",
+            literal(description)
+        ),
+    }
+}
+
+/// Renders a reference as a blank node.
+fn reference(reference: &Reference) -> String {
+    let (class, title, authors, context, url, highlighted) = match reference {
+        Reference::Manuscript {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        } => ("ecc:Manuscript", title, authors, context, url, highlighted),
+        Reference::Preprint {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        } => ("ecc:Preprint", title, authors, context, url, highlighted),
+    };
+
+    format!(
+        "[ a {class} ; dcterms:title {} ; ecc:authors {} ; ecc:context {}@en ; rdfs:seeAlso \
+         <{url}> ; ecc:highlighted {highlighted} ]",
+        literal(title),
+        literal(authors),
+        literal(context.as_str())
+    )
+}
+
+/// Renders a characteristic as a Turtle subject.
+///
+/// Returns [`None`] if the characteristic has no identifier.
+pub fn characteristic(characteristic: &Characteristic) -> Option<String> {
+    let identifier = characteristic.identifier()?;
+
+    let mut predicates = vec![
+        String::from("a ecc:Characteristic"),
+        format!("ecc:identifier {}", literal(&identifier.to_string())),
+        format!("ecc:state {}", state(characteristic.state())),
+        format!("ecc:category {}", category(identifier.category())),
+    ];
+
+    if let Some(name) = characteristic.name() {
+        let mut labels = vec![format!("{}@en", literal(name))];
+        labels.extend(
+            characteristic
+                .translations()
+                .iter()
+                .map(|(language, name)| format!("{}@{}", literal(name), language.as_str())),
+        );
+        predicates.push(format!("rdfs:label {}", labels.join(", ")));
+    }
+
+    if let Some(description) = characteristic.description() {
+        predicates.push(format!(
+            "dcterms:description {}@en",
+            literal(description.trim())
+        ));
+    }
+
+    if let Some(rfc) = characteristic.rfc() {
+        predicates.push(format!("ecc:rfc <{rfc}>"));
+    }
+
+    if let Some(date) = characteristic.adoption_date() {
+        predicates.push(format!(
+            "ecc:adoptionDate \"{}\"^^xsd:dateTime",
+            date.to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        ));
+    }
+
+    if let Some(kind) = characteristic.values() {
+        predicates.push(format!("ecc:values {}", values(kind)));
+    }
+
+    if let Some(references) = characteristic.references() {
+        let references = references.map(reference).collect::<Vec<_>>();
+        predicates.push(format!("ecc:reference {}", references.join(",\n        ")));
+    }
+
+    Some(format!(
+        "term:{identifier}\n    {} .\n",
+        predicates.join(" ;\n    ")
+    ))
+}
+
+/// Renders the vocabulary followed by every characteristic with an
+/// identifier.
+pub fn render(characteristics: &[Characteristic]) -> String {
+    let mut out = String::from(VOCABULARY);
+
+    let _ = writeln!(out, "\n# Characteristics\n");
+    let _ = writeln!(out, "@prefix term: <{TERMS}> .\n");
+
+    for turtle in characteristics.iter().filter_map(characteristic) {
+        let _ = writeln!(out, "{turtle}");
+    }
+
+    out
+}
+
+/// Writes the vocabulary and characteristics to a Turtle file.
+pub fn write(characteristics: &[Characteristic], path: &Path) -> anyhow::Result<()> {
+    std::fs::write(path, render(characteristics)).context("writing Turtle document")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn vocabulary() {
+        assert!(VOCABULARY.contains(&format!("@prefix ecc: <{NAMESPACE}> .")));
+
+        for state in State::ALL {
+            assert!(VOCABULARY.contains(&format!("{} a owl:NamedIndividual", super::state(state))));
+        }
+
+        for category in Category::ALL {
+            assert!(VOCABULARY.contains(&format!(
+                "{} a owl:NamedIndividual",
+                super::category(category)
+            )));
+        }
+    }
+
+    #[test]
+    fn characteristic() {
+        let characteristic = serde_yaml::from_str::<Characteristic>(
+            "state: adopted\nidentifier: ECC-MORPH-000001\nname: A \"Quoted\" Name\nrfc: \
+             https://github.com/stjudecloud/ecc/issues/1\ndescription: A description.\nvalues:\n  \
+             kind: categorical\n  options: [Foo, Bar]\nadoption_date: 2024-01-01T00:00:00Z\n",
+        )
+        .unwrap();
+
+        let turtle = super::characteristic(&characteristic).unwrap();
+        assert!(turtle.starts_with("term:ECC-MORPH-000001\n    a ecc:Characteristic ;\n"));
+        assert!(turtle.contains("rdfs:label \"A \\\"Quoted\\\" Name\"@en ;"));
+        assert!(turtle.contains("ecc:state ecc:adopted ;"));
+        assert!(turtle.contains("ecc:category ecc:morphological ;"));
+        assert!(turtle.contains("ecc:adoptionDate \"2024-01-01T00:00:00Z\"^^xsd:dateTime ;"));
+        assert!(turtle.contains(
+            "ecc:values [ a ecc:CategoricalValues ;\n        ecc:permissibleValue [ a \
+             ecc:PermissibleValue ; rdfs:label \"Bar\"@en ],"
+        ));
+        assert!(turtle.ends_with("] .\n"));
+
+        let draft =
+            serde_yaml::from_str::<Characteristic>("state: draft\nname: A Draft\n").unwrap();
+        assert_eq!(super::characteristic(&draft), None);
+    }
+}
@@ -0,0 +1,170 @@
+//! Rendering of the cross-reference mappings into an [SSSOM] mapping set.
+//!
+//! Each mapping of a characteristic with an identifier becomes one row of the
+//! SSSOM TSV table, with the characteristic as the subject (e.g.,
+//! `ECC:ECC-MORPH-000001`), the SKOS predicate of the mapping, and the external
+//! term as the object. The mapping set metadata (including a `curie_map`
+//! covering every prefix used within the table) is written as a commented YAML
+//! block at the top of the file. Drafts without an identifier have no stable
+//! name, so their mappings are not rendered.
+//!
+//! [SSSOM]: https://mapping-commons.github.io/sssom/
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::Context;
+use chrono::NaiveDate;
+use chrono::Utc;
+use ecc::Characteristic;
+
+/// The namespace of the characteristics.
+///
+/// This matches the IRI prefix used by the other interoperability formats.
+pub const TERMS: &str = "https://github.com/stjudecloud/ecc/terms/";
+
+/// The identifier of the mapping set.
+const MAPPING_SET_ID: &str = "https://github.com/stjudecloud/ecc/mappings/ecc.sssom.tsv";
+
+/// The license of the mapping set.
+///
+/// The registry is dual-licensed, but SSSOM only accepts a single license.
+const LICENSE: &str = "https://spdx.org/licenses/MIT.html";
+
+/// The justification for every mapping (each is curated by hand within a
+/// characteristic file).
+const JUSTIFICATION: &str = "semapv:ManualMappingCuration";
+
+/// The columns of the table.
+const COLUMNS: [&str; 6] = [
+    "subject_id",
+    "subject_label",
+    "predicate_id",
+    "object_id",
+    "object_label",
+    "mapping_justification",
+];
+
+/// The prefixes that are always declared within the `curie_map`.
+const BUILTIN: [(&str, &str); 3] = [
+    ("ECC", TERMS),
+    ("semapv", "https://w3id.org/semapv/vocab/"),
+    ("skos", "http://www.w3.org/2004/02/skos/core#"),
+];
+
+/// Gets the IRI prefix for the prefix of an external term.
+///
+/// The common oncology terminologies expand to their canonical IRIs, and any
+/// other prefix expands through the Bioregistry resolver.
+fn expansion(prefix: &str) -> String {
+    match prefix {
+        "NCIT" => String::from("http://purl.obolibrary.org/obo/NCIT_"),
+        "SNOMEDCT" => String::from("http://snomed.info/id/"),
+        "ICDO" => String::from("https://bioregistry.io/icdo:"),
+        prefix => format!("https://bioregistry.io/{prefix}:"),
+    }
+}
+
+/// Sanitizes a value for a TSV cell.
+fn cell(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Renders the mapping set as an SSSOM TSV document.
+pub fn render(characteristics: &[Characteristic], date: NaiveDate) -> String {
+    let mut rows = Vec::new();
+    let mut prefixes = BUILTIN
+        .iter()
+        .map(|(prefix, iri)| (prefix.to_string(), iri.to_string()))
+        .collect::<BTreeMap<_, _>>();
+
+    for characteristic in characteristics {
+        let Some(identifier) = characteristic.identifier() else {
+            continue;
+        };
+
+        for mapping in characteristic.mappings() {
+            let prefix = mapping.term().prefix();
+            prefixes
+                .entry(prefix.to_string())
+                .or_insert_with(|| expansion(prefix));
+
+            rows.push([
+                format!("ECC:{identifier}"),
+                cell(characteristic.name().unwrap_or_default()),
+                mapping.predicate().curie().to_string(),
+                mapping.term().to_string(),
+                cell(mapping.label().unwrap_or_default()),
+                JUSTIFICATION.to_string(),
+            ]);
+        }
+    }
+
+    rows.sort();
+
+    let mut out = String::from("# curie_map:\n");
+
+    for (prefix, iri) in &prefixes {
+        let _ = writeln!(out, "#   {prefix}: {iri}");
+    }
+
+    let _ = writeln!(out, "# mapping_set_id: {MAPPING_SET_ID}");
+    let _ = writeln!(
+        out,
+        "# mapping_set_title: Encyclopedia of Composable Characteristics"
+    );
+    let _ = writeln!(out, "# license: {LICENSE}");
+    let _ = writeln!(out, "# mapping_date: {date}");
+    let _ = writeln!(out, "{}", COLUMNS.join("\t"));
+
+    for row in rows {
+        let _ = writeln!(out, "{}", row.join("\t"));
+    }
+
+    out
+}
+
+/// Writes the mapping set to an SSSOM TSV file (dated today).
+pub fn write(characteristics: &[Characteristic], path: &Path) -> anyhow::Result<()> {
+    std::fs::write(path, render(characteristics, Utc::now().date_naive()))
+        .context("writing SSSOM mapping set")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render() {
+        let characteristic = serde_yaml::from_str::<Characteristic>(
+            "state: adopted\nidentifier: ECC-MORPH-000001\nname: Acute Myeloid Leukemia\nrfc: \
+             https://github.com/stjudecloud/ecc/issues/1\ndescription: A description.\nvalues:\n  \
+             kind: categorical\n  options: [Foo, Bar]\nmappings:\n  - predicate: exact-match\n    \
+             term: NCIT:C3171\n    label: Acute Myeloid Leukemia\n  - predicate: broad-match\n    \
+             term: MONDO:0018874\nadoption_date: 2024-01-01T00:00:00Z\n",
+        )
+        .unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let tsv = super::render(&[characteristic], date);
+        let lines = tsv.lines().collect::<Vec<_>>();
+
+        assert_eq!(lines[0], "# curie_map:");
+        assert_eq!(lines[1], format!("#   ECC: {TERMS}"));
+        assert!(lines.contains(&"#   MONDO: https://bioregistry.io/MONDO:"));
+        assert!(lines.contains(&"#   NCIT: http://purl.obolibrary.org/obo/NCIT_"));
+        assert!(lines.contains(&"# mapping_date: 2024-01-01"));
+        assert_eq!(lines[lines.len() - 3], COLUMNS.join("\t"));
+        assert_eq!(
+            lines[lines.len() - 2],
+            "ECC:ECC-MORPH-000001\tAcute Myeloid \
+             Leukemia\tskos:broadMatch\tMONDO:0018874\t\tsemapv:ManualMappingCuration"
+        );
+        assert_eq!(
+            lines[lines.len() - 1],
+            "ECC:ECC-MORPH-000001\tAcute Myeloid Leukemia\tskos:exactMatch\tNCIT:C3171\tAcute \
+             Myeloid Leukemia\tsemapv:ManualMappingCuration"
+        );
+    }
+}
@@ -1,16 +1,104 @@
 //! The main binary for building and deploying the Encyclopedia of Composable
 //! Characteristics (ECC) and associated ontologies.
 
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::ArgAction;
+use clap::ArgMatches;
+use clap::CommandFactory as _;
+use clap::FromArgMatches as _;
 use clap::Parser;
 use clap::Subcommand;
+use clap::ValueEnum;
+use tracing::level_filters::LevelFilter;
+use tracing_subscriber::EnvFilter;
 
+pub mod build;
 pub mod check;
+pub mod completions;
+pub mod config;
+pub mod coverage;
+pub mod data;
+pub mod ecc;
+pub mod error;
+pub mod generate;
+pub mod git;
+pub mod interop;
+pub mod man;
+pub mod net;
 pub mod ontology;
+pub mod release;
+pub mod render;
+pub mod review;
+pub mod search;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod sign;
+pub mod tree;
+pub mod verify;
+
+pub use error::Error;
+
+/// The exit code when validation fails (e.g., a characteristic is invalid).
+pub const EXIT_FAILURE: u8 = 1;
+
+/// The exit code when the tool itself fails (e.g., an I/O error or an invalid
+/// glob).
+///
+/// This matches the exit code used by `clap` for invalid arguments.
+pub const EXIT_ERROR: u8 = 2;
+
+/// The levels of logging from quietest to most verbose.
+const LEVELS: [LevelFilter; 6] = [
+    LevelFilter::OFF,
+    LevelFilter::ERROR,
+    LevelFilter::WARN,
+    LevelFilter::INFO,
+    LevelFilter::DEBUG,
+    LevelFilter::TRACE,
+];
+
+/// The index of the default level within [`LEVELS`].
+const DEFAULT_LEVEL: usize = 3;
+
+/// The format of log messages.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable log lines.
+    #[default]
+    Plain,
+
+    /// Newline-delimited JSON objects.
+    Json,
+}
 
 /// A tool for building and deploy the Encyclopedia of Composable
 /// Characteristics (ECC) and associated ontologies.
 #[derive(Parser)]
 pub struct Args {
+    /// The path to the configuration file.
+    ///
+    /// If not provided, an `ecc.toml` is searched for in the current directory
+    /// and its ancestors (up to the root of the git repository).
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// Increases the verbosity of logging (may be repeated).
+    ///
+    /// Logging defaults to the `INFO` level. If the `RUST_LOG` environment
+    /// variable is set, it takes precedence over this flag.
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    verbose: u8,
+
+    /// Decreases the verbosity of logging (may be repeated).
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    quiet: u8,
+
+    /// The format of log messages.
+    #[arg(long, global = true, value_enum, default_value_t)]
+    log_format: LogFormat,
+
     /// The command to run.
     #[command(subcommand)]
     command: Command,
@@ -19,23 +107,126 @@ pub struct Args {
 /// The command to run.
 #[derive(Subcommand)]
 pub enum Command {
+    /// Builds the publishable data bundle.
+    Build(build::Args),
+
     /// Checks the composable characteristic tree is valid.
     Check(check::Args),
 
+    /// Prints shell completions.
+    Completions(completions::Args),
+
+    /// Reports how the characteristics are bound to the ontology.
+    Coverage(coverage::Args),
+
+    /// Work with data annotated using the composable characteristics.
+    Data(data::Args),
+
+    /// Work with the composable characteristics.
+    Ecc(ecc::Args),
+
+    /// Generates repository files from the characteristic tree.
+    Generate(generate::Args),
+
+    /// Generates man pages.
+    Man(man::Args),
+
     /// Build and maintain ontologies.
     Ontology(ontology::Args),
+
+    /// Prepares a data release.
+    Release(release::Args),
+
+    /// Summarizes the changes since a git revision as a review comment.
+    Review(review::Args),
+
+    /// Serves a read-only HTTP API over the characteristic tree.
+    #[cfg(feature = "serve")]
+    Serve(serve::Args),
+
+    /// Verifies a downloaded bundle against its manifest.
+    Verify(verify::Args),
 }
 
-#[allow(clippy::missing_docs_in_private_items)]
-fn main() -> anyhow::Result<()> {
-    let args = Args::parse();
+impl Args {
+    /// Gets the maximum level of logging.
+    fn level(&self) -> LevelFilter {
+        let index = (DEFAULT_LEVEL + usize::from(self.verbose))
+            .saturating_sub(usize::from(self.quiet))
+            .min(LEVELS.len() - 1);
+        LEVELS[index]
+    }
+}
+
+/// Gets the names of the (nested) subcommands that were invoked (e.g., `ecc
+/// init`).
+fn command_name(matches: &ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+
+    while let Some((name, matches)) = current.subcommand() {
+        names.push(name);
+        current = matches;
+    }
+
+    names.join(" ")
+}
+
+/// Initializes logging.
+///
+/// Logs are written to stderr so that they do not interfere with output
+/// intended for other programs.
+fn init_logging(args: &Args) {
+    let filter = match std::env::var(EnvFilter::DEFAULT_ENV) {
+        Ok(_) => EnvFilter::from_default_env(),
+        Err(_) => EnvFilter::default().add_directive(args.level().into()),
+    };
 
-    tracing_subscriber::fmt()
-        .with_max_level(tracing::Level::DEBUG)
-        .init();
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr);
+
+    match args.log_format {
+        LogFormat::Plain => builder.init(),
+        LogFormat::Json => builder.json().init(),
+    }
+}
+
+/// Runs the tool.
+fn run() -> anyhow::Result<()> {
+    let matches = Args::command().get_matches();
+    let args = Args::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+
+    init_logging(&args);
+
+    let _span = tracing::info_span!("run", command = command_name(&matches)).entered();
+    let config = config::Config::load(args.config.as_deref())?;
 
     match args.command {
-        Command::Check(args) => check::main(args),
-        Command::Ontology(args) => ontology::main(args),
+        Command::Build(args) => build::main(args, &config),
+        Command::Check(args) => check::main(args, &config),
+        Command::Completions(args) => completions::main(args),
+        Command::Coverage(args) => coverage::main(args, &config),
+        Command::Data(args) => data::main(args, &config),
+        Command::Ecc(args) => ecc::main(args, &config),
+        Command::Generate(args) => generate::main(args, &config),
+        Command::Man(args) => man::main(args),
+        Command::Ontology(args) => ontology::main(args, &config),
+        Command::Release(args) => release::main(args, &config),
+        Command::Review(args) => review::main(args, &config),
+        #[cfg(feature = "serve")]
+        Command::Serve(args) => serve::main(args, &config),
+        Command::Verify(args) => verify::main(args),
+    }
+}
+
+#[allow(clippy::missing_docs_in_private_items)]
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            error::report(&err);
+            ExitCode::from(EXIT_ERROR)
+        }
     }
 }
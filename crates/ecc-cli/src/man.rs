@@ -0,0 +1,40 @@
+//! Generation of man pages.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::CommandFactory as _;
+use clap::Parser;
+use tracing::info;
+
+/// Generates man pages for the tool.
+///
+/// Without an output directory, the page for the top-level command is printed
+/// to stdout. With an output directory, a page is written for every
+/// subcommand (e.g., `ecc-cli-check.1`).
+#[derive(Parser)]
+pub struct Args {
+    /// The directory to write the man pages to.
+    #[arg(short, long)]
+    output_directory: Option<PathBuf>,
+}
+
+/// The main method.
+pub fn main(args: Args) -> anyhow::Result<()> {
+    let command = crate::Args::command();
+
+    match args.output_directory {
+        Some(directory) => {
+            std::fs::create_dir_all(&directory)
+                .with_context(|| format!("creating output directory: {}", directory.display()))?;
+            clap_mangen::generate_to(command, &directory)
+                .with_context(|| format!("writing man pages to {}", directory.display()))?;
+            info!("wrote man pages to `{}`", directory.display());
+        }
+        None => clap_mangen::Man::new(command)
+            .render(&mut std::io::stdout())
+            .context("rendering man page")?,
+    }
+
+    Ok(())
+}
@@ -0,0 +1,270 @@
+//! Access to web services (e.g., GitHub and NCBI).
+//!
+//! Every subcommand that needs the network goes through a [`Client`] rather
+//! than building its own HTTP stack. The default client ([`client()`]) is
+//! composed of layers, each of which is a [`Client`] wrapping another:
+//!
+//! * [`Fixtures`]: records responses to (or replays them from) files, or
+//!   refuses to send requests when offline (see [`Mode`]),
+//! * [`Retrying`]: retries transient failures with exponential backoff,
+//! * [`Limited`]: spaces out requests to the same host, and
+//! * [`Http`]: sends the request, authenticating with the [`Credentials`] for
+//!   the host (if any).
+//!
+//! Clients are asynchronous so that many requests can be in flight at once.
+//! Synchronous code drives them with [`block_on()`].
+
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use serde::de::DeserializeOwned;
+use url::Url;
+
+mod fixture;
+mod http;
+mod limit;
+#[cfg(test)]
+pub mod mock;
+mod retry;
+
+pub use fixture::FIXTURES;
+pub use fixture::FIXTURES_MODE;
+pub use fixture::Fixtures;
+pub use fixture::Mode;
+pub use http::Http;
+pub use limit::Limited;
+pub use retry::Retrying;
+
+/// The host of the GitHub API.
+pub const GITHUB_API_HOST: &str = "api.github.com";
+
+/// The host of the NCBI E-utilities API.
+pub const NCBI_HOST: &str = "eutils.ncbi.nlm.nih.gov";
+
+/// The environment variable holding a GitHub token.
+pub const GITHUB_TOKEN: &str = "GITHUB_TOKEN";
+
+/// The environment variable holding an NCBI API key.
+pub const NCBI_API_KEY: &str = "NCBI_API_KEY";
+
+/// An error when sending a request.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The request could not be sent or the response could not be read.
+    #[error("requesting `{url}`: {reason}")]
+    Transport {
+        /// The URL requested.
+        url: Url,
+
+        /// The reason the request failed.
+        reason: String,
+    },
+
+    /// The server responded with an unsuccessful status.
+    #[error("requesting `{url}`: unexpected status {status}")]
+    Status {
+        /// The URL requested.
+        url: Url,
+
+        /// The status code returned.
+        status: u16,
+    },
+
+    /// The body of the response was not the expected JSON.
+    #[error("parsing the response from `{url}`: {source}")]
+    Json {
+        /// The URL requested.
+        url: Url,
+
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+
+    /// The request was not sent because the client is offline.
+    #[error("cannot request `{url}` while offline")]
+    Offline {
+        /// The URL requested.
+        url: Url,
+    },
+
+    /// The recorded response could not be read or written.
+    #[error("recorded response `{}`: {reason}", path.display())]
+    Fixture {
+        /// The path to the recorded response.
+        path: PathBuf,
+
+        /// The reason the recorded response could not be read or written.
+        reason: String,
+    },
+}
+
+/// The method of a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Method {
+    /// A `GET` request.
+    Get,
+
+    /// A `HEAD` request.
+    Head,
+}
+
+/// A request.
+#[derive(Clone, Debug)]
+pub struct Request {
+    /// The method.
+    pub method: Method,
+
+    /// The URL.
+    pub url: Url,
+
+    /// The headers (in addition to any added by the client).
+    pub headers: Vec<(String, String)>,
+}
+
+impl Request {
+    /// Creates a `GET` request.
+    pub fn get(url: Url) -> Self {
+        Self {
+            method: Method::Get,
+            url,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Creates a `HEAD` request.
+    pub fn head(url: Url) -> Self {
+        Self {
+            method: Method::Head,
+            url,
+            headers: Vec::new(),
+        }
+    }
+
+    /// Adds a header.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+/// A response.
+#[derive(Clone, Debug, Default)]
+pub struct Response {
+    /// The status code.
+    pub status: u16,
+
+    /// The headers (keyed by lowercase name).
+    pub headers: BTreeMap<String, String>,
+
+    /// The body.
+    pub body: String,
+}
+
+impl Response {
+    /// Gets a header by its (case-insensitive) name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+
+    /// Fails if the status is not successful (i.e., not `2xx`).
+    pub fn error_for_status(self, url: &Url) -> Result<Self, Error> {
+        match self.status {
+            200..=299 => Ok(self),
+            status => Err(Error::Status {
+                url: url.clone(),
+                status,
+            }),
+        }
+    }
+
+    /// Parses the body as JSON.
+    pub fn json<T: DeserializeOwned>(&self, url: &Url) -> Result<T, Error> {
+        serde_json::from_str(&self.body).map_err(|source| Error::Json {
+            url: url.clone(),
+            source,
+        })
+    }
+}
+
+/// A client for web services.
+pub trait Client {
+    /// Sends a request.
+    ///
+    /// Responses with an unsuccessful status are still returned as responses
+    /// (see [`Response::error_for_status()`]). Redirects are not followed.
+    fn send(&self, request: Request) -> impl Future<Output = Result<Response, Error>> + Send;
+}
+
+/// The credentials used to authenticate with web services.
+#[derive(Clone, Default)]
+pub struct Credentials {
+    /// A GitHub token.
+    pub github: Option<String>,
+
+    /// An NCBI API key.
+    pub ncbi: Option<String>,
+}
+
+impl Credentials {
+    /// Reads the credentials from the environment (see [`GITHUB_TOKEN`] and
+    /// [`NCBI_API_KEY`]).
+    pub fn from_env() -> Self {
+        let var = |name| std::env::var(name).ok().filter(|value| !value.is_empty());
+
+        Self {
+            github: var(GITHUB_TOKEN),
+            ncbi: var(NCBI_API_KEY),
+        }
+    }
+
+    /// Adds the credentials for the host of a request (if there are any).
+    pub fn authenticate(&self, request: &mut Request) {
+        match (request.url.host_str(), &self.github, &self.ncbi) {
+            (Some(GITHUB_API_HOST), Some(token), _) => request
+                .headers
+                .push((String::from("Authorization"), format!("Bearer {token}"))),
+            (Some(NCBI_HOST), _, Some(key)) => {
+                request.url.query_pairs_mut().append_pair("api_key", key);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl std::fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // NOTE: the secrets themselves are never printed.
+        let redact = |secret: &Option<String>| secret.as_ref().map(|_| "<redacted>");
+
+        f.debug_struct("Credentials")
+            .field("github", &redact(&self.github))
+            .field("ncbi", &redact(&self.ncbi))
+            .finish()
+    }
+}
+
+/// The default client.
+pub type DefaultClient = Fixtures<Retrying<Limited<Http>>>;
+
+/// Creates the default client.
+pub fn client(timeout: Duration, credentials: Credentials, mode: Mode) -> DefaultClient {
+    let limits = limit::defaults(&credentials);
+    let http = Http::new(timeout, credentials);
+    Fixtures::new(Retrying::new(Limited::new(http, limits)), mode)
+}
+
+/// Runs a future to completion on the current thread.
+pub fn block_on<F: Future>(future: F) -> anyhow::Result<F::Output> {
+    use anyhow::Context as _;
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("starting the async runtime")?;
+
+    Ok(runtime.block_on(future))
+}
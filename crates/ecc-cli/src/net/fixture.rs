@@ -0,0 +1,253 @@
+//! Recording and replaying of responses (and refusing to send requests at
+//! all when offline).
+
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ecc::canonical;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::debug;
+use url::Url;
+
+use super::Client;
+use super::Error;
+use super::Method;
+use super::Request;
+use super::Response;
+
+/// The environment variable holding the directory of recorded responses.
+pub const FIXTURES: &str = "ECC_HTTP_FIXTURES";
+
+/// The environment variable selecting whether responses are recorded to or
+/// replayed from [`FIXTURES`] (`record` or `replay`, the default).
+pub const FIXTURES_MODE: &str = "ECC_HTTP_MODE";
+
+/// How requests are handled.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Mode {
+    /// Requests are sent over the network.
+    #[default]
+    Online,
+
+    /// Requests are never sent and fail with [`Error::Offline`].
+    Offline,
+
+    /// Requests are sent over the network and the responses are written to
+    /// the directory.
+    Record(PathBuf),
+
+    /// Requests are never sent and are answered from the responses within
+    /// the directory.
+    Replay(PathBuf),
+}
+
+impl Mode {
+    /// Gets the mode from the environment (see [`FIXTURES`] and
+    /// [`FIXTURES_MODE`]).
+    ///
+    /// Being offline takes precedence over the environment.
+    pub fn from_env(offline: bool) -> anyhow::Result<Self> {
+        if offline {
+            return Ok(Self::Offline);
+        }
+
+        let Some(directory) = std::env::var_os(FIXTURES).filter(|value| !value.is_empty()) else {
+            return Ok(Self::Online);
+        };
+
+        let directory = PathBuf::from(directory);
+
+        match std::env::var(FIXTURES_MODE).as_deref() {
+            Ok("record") => Ok(Self::Record(directory)),
+            Ok("replay") | Ok("") | Err(_) => Ok(Self::Replay(directory)),
+            Ok(mode) => anyhow::bail!(
+                "invalid value for `{FIXTURES_MODE}`: `{mode}` (expected `record` or `replay`)"
+            ),
+        }
+    }
+}
+
+/// A recorded response.
+#[derive(Serialize, Deserialize)]
+struct Fixture {
+    /// The method of the request.
+    method: String,
+
+    /// The URL of the request.
+    url: Url,
+
+    /// The status code.
+    status: u16,
+
+    /// The headers (keyed by lowercase name).
+    #[serde(default)]
+    headers: BTreeMap<String, String>,
+
+    /// The body.
+    #[serde(default)]
+    body: String,
+}
+
+/// Gets the name of a method as used within fixtures.
+fn method(method: Method) -> &'static str {
+    match method {
+        Method::Get => "get",
+        Method::Head => "head",
+    }
+}
+
+/// Gets the path of the fixture for a request relative to the directory of
+/// fixtures.
+///
+/// Fixtures are grouped by host and named by the method and a hash of the
+/// URL (e.g., `doi.org/head-1a2b3c4d5e6f7a8b.json`). The request is also
+/// stored within the fixture so that it can be identified.
+pub fn path(request: &Request) -> PathBuf {
+    let method = method(request.method);
+    // NOTE: the first eight bytes (sixteen hex digits) of the digest are
+    // plenty to tell requests apart.
+    let hash = canonical::sha256(format!("{} {}", method, request.url).as_bytes());
+    let hash = &hash[..16];
+
+    Path::new(request.url.host_str().unwrap_or("localhost")).join(format!("{method}-{hash}.json"))
+}
+
+/// A client that records responses to (or replays them from) files, or that
+/// refuses to send requests at all when offline (see [`Mode`]).
+///
+/// Replaying lets tests exercise code that talks to web services without
+/// depending on the network. Credentials are added by the inner client, so
+/// they never appear within recorded requests.
+#[derive(Clone)]
+pub struct Fixtures<C> {
+    /// The client that sends the requests.
+    inner: C,
+
+    /// How requests are handled.
+    mode: Mode,
+}
+
+impl<C> Fixtures<C> {
+    /// Creates a new client.
+    pub fn new(inner: C, mode: Mode) -> Self {
+        Self { inner, mode }
+    }
+}
+
+/// Reads the fixture for a request.
+fn replay(directory: &Path, request: &Request) -> Result<Response, Error> {
+    let path = directory.join(path(request));
+    debug!("replaying `{}` from `{}`", request.url, path.display());
+
+    let fixture = std::fs::read_to_string(&path)
+        .map_err(|err| err.to_string())
+        .and_then(|contents| {
+            serde_json::from_str::<Fixture>(&contents).map_err(|err| err.to_string())
+        })
+        .map_err(|reason| Error::Fixture {
+            path: path.clone(),
+            reason,
+        })?;
+
+    Ok(Response {
+        status: fixture.status,
+        headers: fixture.headers,
+        body: fixture.body,
+    })
+}
+
+/// Writes the fixture for a request.
+fn record(directory: &Path, request: &Request, response: &Response) -> Result<(), Error> {
+    let path = directory.join(path(request));
+    debug!("recording `{}` to `{}`", request.url, path.display());
+
+    let fixture = Fixture {
+        method: String::from(method(request.method)),
+        url: request.url.clone(),
+        status: response.status,
+        headers: response.headers.clone(),
+        body: response.body.clone(),
+    };
+
+    let error = |reason: String| Error::Fixture {
+        path: path.clone(),
+        reason,
+    };
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|err| error(err.to_string()))?;
+    }
+
+    let contents = serde_json::to_string_pretty(&fixture).map_err(|err| error(err.to_string()))?;
+    std::fs::write(&path, contents + "\n").map_err(|err| error(err.to_string()))
+}
+
+impl<C: Client + Sync> Client for Fixtures<C> {
+    async fn send(&self, request: Request) -> Result<Response, Error> {
+        match &self.mode {
+            Mode::Online => self.inner.send(request).await,
+            Mode::Offline => Err(Error::Offline { url: request.url }),
+            Mode::Replay(directory) => replay(directory, &request),
+            Mode::Record(directory) => {
+                let response = self.inner.send(request.clone()).await?;
+                record(directory, &request, &response)?;
+                Ok(response)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::mock::Mock;
+
+    /// Gets the URL used within the tests.
+    fn url() -> Url {
+        "https://doi.org/10.1000/182".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn records_and_replays() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let mock = Mock::new();
+        let mut response = Response {
+            status: 302,
+            ..Default::default()
+        };
+        response.headers.insert(
+            String::from("location"),
+            String::from("https://www.doi.org/"),
+        );
+        mock.respond(&url(), response);
+
+        let recorder = Fixtures::new(mock, Mode::Record(directory.path().to_path_buf()));
+        recorder.send(Request::head(url())).await.unwrap();
+
+        // NOTE: the mock is not consulted when replaying, so a request that
+        // was never recorded fails.
+        let replayer = Fixtures::new(Mock::new(), Mode::Replay(directory.path().to_path_buf()));
+        let response = replayer.send(Request::head(url())).await.unwrap();
+
+        assert_eq!(response.status, 302);
+        assert_eq!(response.header("Location"), Some("https://www.doi.org/"));
+
+        let err = replayer.send(Request::get(url())).await.unwrap_err();
+        assert!(matches!(err, Error::Fixture { .. }));
+    }
+
+    #[tokio::test]
+    async fn offline() {
+        let mock = Mock::new();
+        mock.respond(&url(), Response::default());
+
+        let client = Fixtures::new(mock.clone(), Mode::Offline);
+        let err = client.send(Request::head(url())).await.unwrap_err();
+
+        assert!(matches!(err, Error::Offline { .. }));
+        assert!(mock.requests().is_empty());
+    }
+}
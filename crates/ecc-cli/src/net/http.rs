@@ -0,0 +1,129 @@
+//! Sending requests over HTTP.
+
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::debug;
+
+use super::Client;
+use super::Credentials;
+use super::Error;
+use super::Method;
+use super::Request;
+use super::Response;
+
+/// The user agent sent with every request.
+const USER_AGENT: &str = concat!("ecc-cli/", env!("CARGO_PKG_VERSION"));
+
+/// A client that sends requests over HTTP.
+///
+/// Requests are sent on a blocking thread so that they do not hold up the
+/// async runtime.
+#[derive(Clone)]
+pub struct Http {
+    /// The HTTP agent.
+    agent: ureq::Agent,
+
+    /// The credentials for authenticating requests.
+    credentials: Arc<Credentials>,
+}
+
+impl Http {
+    /// Creates a new client.
+    pub fn new(timeout: Duration, credentials: Credentials) -> Self {
+        let agent = ureq::Agent::config_builder()
+            .max_redirects(0)
+            .http_status_as_error(false)
+            .timeout_global(Some(timeout))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+
+        Self {
+            agent,
+            credentials: Arc::new(credentials),
+        }
+    }
+}
+
+/// Sends a request and reads the response (blocking the current thread).
+fn send(agent: &ureq::Agent, request: &Request) -> Result<Response, ureq::Error> {
+    let url = request.url.as_str();
+
+    let mut response = match request.method {
+        Method::Get => {
+            let mut builder = agent.get(url);
+
+            for (name, value) in &request.headers {
+                builder = builder.header(name, value);
+            }
+
+            builder.call()?
+        }
+        Method::Head => {
+            let mut builder = agent.head(url);
+
+            for (name, value) in &request.headers {
+                builder = builder.header(name, value);
+            }
+
+            builder.call()?
+        }
+    };
+
+    let headers = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            let value = value.to_str().ok()?;
+            Some((name.as_str().to_ascii_lowercase(), value.to_string()))
+        })
+        .collect();
+
+    let body = match request.method {
+        Method::Get => response.body_mut().read_to_string()?,
+        Method::Head => String::new(),
+    };
+
+    Ok(Response {
+        status: response.status().as_u16(),
+        headers,
+        body,
+    })
+}
+
+impl Client for Http {
+    fn send(&self, mut request: Request) -> impl Future<Output = Result<Response, Error>> + Send {
+        let agent = self.agent.clone();
+        let credentials = self.credentials.clone();
+
+        async move {
+            // NOTE: errors report the URL as requested (rather than after
+            // authenticating), so API keys never appear within messages.
+            let url = request.url.clone();
+            credentials.authenticate(&mut request);
+
+            // NOTE: the query is not logged, as it may contain an API key.
+            debug!(
+                "requesting `{}{}`",
+                request.url.host_str().unwrap_or_default(),
+                request.url.path()
+            );
+
+            let result = tokio::task::spawn_blocking(move || send(&agent, &request)).await;
+
+            match result {
+                Ok(Ok(response)) => Ok(response),
+                Ok(Err(err)) => Err(Error::Transport {
+                    url,
+                    reason: err.to_string(),
+                }),
+                Err(err) => Err(Error::Transport {
+                    url,
+                    reason: err.to_string(),
+                }),
+            }
+        }
+    }
+}
@@ -0,0 +1,113 @@
+//! Limiting the rate of requests to each host.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use tokio::time::Instant;
+
+use super::Client;
+use super::Credentials;
+use super::Error;
+use super::NCBI_HOST;
+use super::Request;
+use super::Response;
+
+/// Gets the minimum interval between requests to each host.
+///
+/// NCBI allows three requests per second without an API key and ten with one.
+pub fn defaults(credentials: &Credentials) -> HashMap<String, Duration> {
+    let ncbi = match credentials.ncbi {
+        Some(_) => Duration::from_millis(100),
+        None => Duration::from_millis(334),
+    };
+
+    HashMap::from([(String::from(NCBI_HOST), ncbi)])
+}
+
+/// A client that spaces out the requests to each host.
+///
+/// Hosts without a configured interval are not limited.
+#[derive(Clone)]
+pub struct Limited<C> {
+    /// The client that sends the requests.
+    inner: C,
+
+    /// The minimum interval between requests to each host.
+    intervals: Arc<HashMap<String, Duration>>,
+
+    /// The earliest time the next request to each host may be sent.
+    next: Arc<Mutex<HashMap<String, Instant>>>,
+}
+
+impl<C> Limited<C> {
+    /// Creates a new client.
+    pub fn new(inner: C, intervals: HashMap<String, Duration>) -> Self {
+        Self {
+            inner,
+            intervals: Arc::new(intervals),
+            next: Default::default(),
+        }
+    }
+
+    /// Reserves the next slot for a host and gets when it begins.
+    fn reserve(&self, host: &str) -> Option<Instant> {
+        let interval = *self.intervals.get(host)?;
+        let now = Instant::now();
+
+        // SAFETY: the lock is never held across a panic.
+        let mut next = self.next.lock().unwrap();
+        let slot = next
+            .get(host)
+            .copied()
+            .filter(|slot| *slot > now)
+            .unwrap_or(now);
+        next.insert(host.to_string(), slot + interval);
+
+        Some(slot)
+    }
+}
+
+impl<C: Client + Sync> Client for Limited<C> {
+    fn send(&self, request: Request) -> impl Future<Output = Result<Response, Error>> + Send {
+        let slot = request.url.host_str().and_then(|host| self.reserve(host));
+
+        async move {
+            if let Some(slot) = slot {
+                tokio::time::sleep_until(slot).await;
+            }
+
+            self.inner.send(request).await
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::net::mock::Mock;
+
+    #[tokio::test(start_paused = true)]
+    async fn spaces_out_requests() {
+        let mock = Mock::new();
+        let url = format!("https://{NCBI_HOST}/entrez/eutils/esummary.fcgi")
+            .parse::<url::Url>()
+            .unwrap();
+        mock.respond(&url, Response::default());
+
+        let client = Limited::new(
+            mock.clone(),
+            HashMap::from([(String::from(NCBI_HOST), Duration::from_secs(1))]),
+        );
+
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.send(Request::get(url.clone())).await.unwrap();
+        }
+
+        assert_eq!(start.elapsed().as_secs(), 2);
+        assert_eq!(mock.requests().len(), 3);
+    }
+}
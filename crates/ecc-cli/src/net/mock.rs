@@ -0,0 +1,100 @@
+//! A client with canned responses for tests.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use url::Url;
+
+use super::Client;
+use super::Error;
+use super::Request;
+use super::Response;
+
+/// A canned result.
+type Canned = Result<Response, String>;
+
+/// The state shared between clones of a mock.
+#[derive(Default)]
+struct State {
+    /// The results queued for each URL.
+    ///
+    /// The last result for a URL is repeated once the others are used up.
+    results: HashMap<String, VecDeque<Canned>>,
+
+    /// The requests that were sent.
+    requests: Vec<Request>,
+}
+
+/// A client that returns canned responses and records the requests sent.
+///
+/// Clones share the same responses and requests, so a clone can be handed to
+/// the code under test and the original inspected afterward. Requests to URLs
+/// without a canned response fail with [`Error::Transport`].
+#[derive(Clone, Default)]
+pub struct Mock {
+    /// The shared state.
+    state: Arc<Mutex<State>>,
+}
+
+impl Mock {
+    /// Creates a mock without any responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a result for a URL.
+    fn push(&self, url: &Url, result: Canned) {
+        // SAFETY: the lock is never held across a panic.
+        let mut state = self.state.lock().unwrap();
+        state
+            .results
+            .entry(url.to_string())
+            .or_default()
+            .push_back(result);
+    }
+
+    /// Queues a response for a URL.
+    pub fn respond(&self, url: &Url, response: Response) {
+        self.push(url, Ok(response));
+    }
+
+    /// Queues a transport failure for a URL.
+    pub fn fail(&self, url: &Url, reason: &str) {
+        self.push(url, Err(reason.to_string()));
+    }
+
+    /// Gets the requests that were sent.
+    pub fn requests(&self) -> Vec<Request> {
+        // SAFETY: the lock is never held across a panic.
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Client for Mock {
+    fn send(&self, request: Request) -> impl Future<Output = Result<Response, Error>> + Send {
+        // SAFETY: the lock is never held across a panic.
+        let mut state = self.state.lock().unwrap();
+        let url = request.url.clone();
+        state.requests.push(request);
+
+        let result = match state.results.get_mut(url.as_str()) {
+            Some(results) if results.len() > 1 => results.pop_front(),
+            Some(results) => results.front().cloned(),
+            None => None,
+        };
+
+        let result = match result {
+            Some(Ok(response)) => Ok(response),
+            Some(Err(reason)) => Err(Error::Transport { url, reason }),
+            None => Err(Error::Transport {
+                reason: String::from("no response was provided to the mock"),
+                url,
+            }),
+        };
+
+        std::future::ready(result)
+    }
+}
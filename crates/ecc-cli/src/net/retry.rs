@@ -0,0 +1,184 @@
+//! Retrying of transient failures.
+
+use std::time::Duration;
+
+use tracing::debug;
+
+use super::Client;
+use super::Error;
+use super::Request;
+use super::Response;
+
+/// The number of times a request is retried by default.
+const RETRIES: u32 = 3;
+
+/// The delay before the first retry by default (doubled for each retry).
+const BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// The longest the client will wait before retrying (even if the server asks
+/// for longer).
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// Gets whether a response indicates a transient failure.
+///
+/// GitHub reports exceeding its rate limit with a `403` status and no
+/// remaining requests.
+fn is_transient(response: &Response) -> bool {
+    match response.status {
+        429 | 502 | 503 | 504 => true,
+        403 => response.header("x-ratelimit-remaining") == Some("0"),
+        _ => false,
+    }
+}
+
+/// Gets how long the server asked the client to wait (if it did).
+fn retry_after(response: &Response) -> Option<Duration> {
+    response
+        .header("retry-after")
+        .and_then(|value| value.trim().parse().ok())
+        .map(Duration::from_secs)
+}
+
+/// A client that retries requests that fail transiently (e.g., network errors
+/// or exceeded rate limits) with exponential backoff.
+#[derive(Clone)]
+pub struct Retrying<C> {
+    /// The client that sends the requests.
+    inner: C,
+
+    /// The number of times a request is retried.
+    retries: u32,
+
+    /// The delay before the first retry.
+    base_delay: Duration,
+}
+
+impl<C> Retrying<C> {
+    /// Creates a new client with the default policy.
+    pub fn new(inner: C) -> Self {
+        Self {
+            inner,
+            retries: RETRIES,
+            base_delay: BASE_DELAY,
+        }
+    }
+
+    /// Sets the number of times a request is retried.
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Sets the delay before the first retry.
+    pub fn with_base_delay(mut self, base_delay: Duration) -> Self {
+        self.base_delay = base_delay;
+        self
+    }
+}
+
+impl<C: Client + Sync> Client for Retrying<C> {
+    async fn send(&self, request: Request) -> Result<Response, Error> {
+        let mut attempt = 0;
+
+        loop {
+            let result = self.inner.send(request.clone()).await;
+
+            let delay = match &result {
+                Ok(response) if is_transient(response) => retry_after(response),
+                Err(Error::Transport { .. }) => None,
+                _ => return result,
+            };
+
+            if attempt == self.retries {
+                return result;
+            }
+
+            let delay = delay
+                .unwrap_or(self.base_delay * 2u32.pow(attempt))
+                .min(MAX_DELAY);
+            attempt += 1;
+
+            debug!(
+                "retrying `{}` in {delay:?} (attempt {attempt} of {})",
+                request.url, self.retries
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use url::Url;
+
+    use super::*;
+    use crate::net::mock::Mock;
+
+    /// Gets the URL used within the tests.
+    fn url() -> Url {
+        "https://api.github.com/repos/stjudecloud/ecc/issues/1"
+            .parse()
+            .unwrap()
+    }
+
+    /// Gets a response with a status.
+    fn status(status: u16) -> Response {
+        Response {
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn retries_transient_failures() {
+        let mock = Mock::new();
+        mock.respond(&url(), status(503));
+        mock.respond(&url(), status(200));
+
+        let client = Retrying::new(mock.clone());
+        let response = client.send(Request::get(url())).await.unwrap();
+
+        assert_eq!(response.status, 200);
+        assert_eq!(mock.requests().len(), 2);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn gives_up() {
+        let mock = Mock::new();
+        mock.fail(&url(), "connection reset");
+
+        let client = Retrying::new(mock.clone()).with_retries(2);
+        let err = client.send(Request::get(url())).await.unwrap_err();
+
+        assert!(matches!(err, Error::Transport { .. }));
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn does_not_retry_permanent_failures() {
+        let mock = Mock::new();
+        mock.respond(&url(), status(404));
+
+        let client = Retrying::new(mock.clone());
+        let response = client.send(Request::get(url())).await.unwrap();
+
+        assert_eq!(response.status, 404);
+        assert_eq!(mock.requests().len(), 1);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn honors_retry_after() {
+        let mock = Mock::new();
+        let mut limited = status(429);
+        limited
+            .headers
+            .insert(String::from("retry-after"), String::from("7"));
+        mock.respond(&url(), limited);
+        mock.respond(&url(), status(200));
+
+        let start = tokio::time::Instant::now();
+        Retrying::new(mock).send(Request::get(url())).await.unwrap();
+
+        assert_eq!(start.elapsed().as_secs(), 7);
+    }
+}
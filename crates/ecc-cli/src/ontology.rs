@@ -3,7 +3,17 @@
 use clap::Parser;
 use clap::Subcommand;
 
+use crate::config::Config;
+
+mod add_node;
+mod check;
+mod export;
 mod init;
+mod move_node;
+
+pub use init::directory::DEFAULT_MAX_PATH_LENGTH;
+pub use init::directory::Layout;
+pub use init::directory::PathPolicy;
 
 /// Build and maintain ontologies related to the ECC.
 #[derive(Parser)]
@@ -16,13 +26,29 @@ pub struct Args {
 /// The command to run.
 #[derive(Subcommand)]
 pub enum Command {
+    /// Adds a new node beneath an existing parent.
+    AddNode(add_node::Args),
+
+    /// Checks that an ontology directory is structurally valid.
+    Check(check::Args),
+
+    /// Exports an ontology to another format.
+    Export(export::Args),
+
     /// Initializes an ontology directory from an existing map.
     Init(init::Args),
+
+    /// Moves a node (and its descendants) beneath a new parent.
+    MoveNode(move_node::Args),
 }
 
 /// The main method.
-pub fn main(args: Args) -> anyhow::Result<()> {
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
     match args.command {
-        Command::Init(args) => init::main(args),
+        Command::AddNode(args) => add_node::main(args, config),
+        Command::Check(args) => check::main(args, config),
+        Command::Export(args) => export::main(args, config),
+        Command::Init(args) => init::main(args, config),
+        Command::MoveNode(args) => move_node::main(args, config),
     }
 }
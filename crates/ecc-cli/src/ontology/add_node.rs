@@ -0,0 +1,72 @@
+//! Addition of nodes to an ontology directory.
+
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::Parser;
+use ontology::Graph;
+use ontology::node::Builder;
+use ontology::node::Name;
+use tracing::info;
+
+use super::init::directory::Directory;
+use crate::config::Config;
+use crate::tree;
+
+/// Adds a new node beneath an existing parent.
+///
+/// The node file is written to the location dictated by its ancestors, and
+/// the ontology directory is verified afterwards.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The name of the new node.
+    #[arg(long)]
+    name: Name,
+
+    /// The name of the parent node.
+    #[arg(long)]
+    parent: Name,
+
+    /// The short code for the new node.
+    #[arg(long)]
+    code: String,
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let path = config.require_ontology(args.path)?;
+    let (graph, _) = tree::load_ontology_graph(&path)?;
+
+    if graph.get(args.parent.inner()).is_none() {
+        bail!("parent node does not exist: {}", args.parent);
+    }
+
+    let node = Builder::default()
+        .name(args.name)
+        .parent(args.parent)
+        .code(args.code)
+        .try_build()
+        .context("building node")?;
+
+    let mut nodes = graph.nodes().cloned().collect::<Vec<_>>();
+    nodes.push(node.clone());
+
+    let graph = Graph::try_new(nodes).map_err(crate::Error::Ontology)?;
+    let policy = config.ontology_paths();
+    let file = path.join(Directory::relative_path(&graph, &node, policy));
+
+    if file.exists() {
+        bail!("file already exists: {}", file.display());
+    }
+
+    Directory::write_node(&file, &node)?;
+    info!("added node `{}` at `{}`", node.name(), file.display());
+
+    Directory::verify(&path, policy).context("verifying the ontology directory")
+}
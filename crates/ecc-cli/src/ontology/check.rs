@@ -0,0 +1,289 @@
+//! Checking of an ontology directory.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::PathBuf;
+
+use clap::Parser;
+use codespan_reporting::diagnostic::Diagnostic;
+use codespan_reporting::diagnostic::Label;
+use codespan_reporting::diagnostic::Severity as DiagnosticSeverity;
+use codespan_reporting::files::SimpleFiles;
+use codespan_reporting::term;
+use codespan_reporting::term::termcolor::ColorChoice;
+use codespan_reporting::term::termcolor::StandardStream;
+use colored::Colorize as _;
+use ontology::Graph;
+use ontology::Node;
+use ontology::graph::Issue;
+use tracing::info;
+
+use super::init::directory::Directory;
+use super::init::directory::Layout;
+use super::init::directory::PathPolicy;
+use crate::config::Config;
+use crate::config::SIMILAR_NAMES;
+use crate::config::Severity;
+use crate::tree;
+
+/// Checks that an ontology directory is structurally valid.
+///
+/// Every node file is parsed, the graph is rebuilt, and the following problems
+/// are reported:
+///
+/// * duplicate node names or codes,
+/// * nodes whose parent does not exist (orphans),
+/// * missing or multiple root nodes,
+/// * cycles between nodes,
+/// * replacements of obsolete nodes that do not exist or are obsolete,
+/// * node files that are not located where the layout dictates,
+/// * nodes that violate a constraint declared by one of their ancestors, and
+/// * node names that are near misses of one another (reported by the
+///   `similar-names` lint).
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The layout of the node files.
+    ///
+    /// Defaults to the layout configured in `ecc.toml`.
+    #[arg(long, value_enum)]
+    layout: Option<Layout>,
+}
+
+/// A node loaded from the ontology directory.
+struct Source {
+    /// The path to the file.
+    path: PathBuf,
+
+    /// The identifier of the file within the diagnostic files.
+    file: usize,
+
+    /// The contents of the file.
+    contents: String,
+}
+
+/// Gets the span of the value for a top-level key within a node file.
+///
+/// If the value is a block (i.e., it starts on the following line), the span
+/// of the key is returned instead. If the key cannot be found, an empty span at
+/// the start of the file is returned.
+fn span(contents: &str, key: &str) -> Range<usize> {
+    let prefix = format!("{key}:");
+    let mut offset = 0;
+
+    for line in contents.split_inclusive('\n') {
+        if let Some(value) = line.strip_prefix(&prefix) {
+            if value.trim().is_empty() {
+                return offset..offset + key.len();
+            }
+
+            let start = offset + prefix.len() + (value.len() - value.trim_start().len());
+            let end = offset + line.trim_end().len();
+            return start..end.max(start);
+        }
+
+        offset += line.len();
+    }
+
+    0..0
+}
+
+/// Gets the key most relevant to an issue.
+fn key(issue: &Issue) -> &'static str {
+    match issue {
+        Issue::DuplicateName { .. } => "name",
+        Issue::DuplicateCode { .. } => "code",
+        Issue::MissingParent { .. }
+        | Issue::NoRoot
+        | Issue::MultipleRoots { .. }
+        | Issue::Cycle { .. } => "parent",
+        Issue::MissingReplacement { .. } | Issue::ObsoleteReplacement { .. } => "replaced_by",
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let path = config.require_ontology(args.path)?;
+    let policy = PathPolicy {
+        layout: args.layout.unwrap_or(config.directories.ontology_layout),
+        ..config.ontology_paths()
+    };
+    let similar_names = config.severity(SIMILAR_NAMES);
+    let max_name_distance = config.check.max_name_distance;
+    let paths = tree::files(&path)?;
+    info!("checking {} ontology files", paths.len());
+
+    let writer = StandardStream::stdout(ColorChoice::Always);
+    let config = term::Config::default();
+
+    let mut files = SimpleFiles::new();
+    let mut diagnostics = Vec::new();
+    let mut sources = Vec::new();
+    let mut nodes = Vec::new();
+
+    for path in paths {
+        let contents = tree::read(&path)?;
+        let file = files.add(path.display().to_string(), contents.clone());
+
+        match serde_yaml::from_str::<Node>(&contents) {
+            Ok(node) => {
+                sources.push(Source {
+                    path,
+                    file,
+                    contents,
+                });
+                nodes.push(node);
+            }
+            Err(err) => {
+                let index = match err.location() {
+                    Some(location) => location.index(),
+                    None => contents.len(),
+                };
+
+                diagnostics.push(
+                    Diagnostic::error()
+                        .with_message("unable to parse node")
+                        .with_labels(vec![
+                            Label::primary(file, index..index).with_message(err.to_string()),
+                        ]),
+                );
+            }
+        }
+    }
+
+    let issues = ontology::graph::check(&nodes);
+
+    for issue in &issues {
+        let key = key(issue);
+        let labels = issue
+            .indexes()
+            .into_iter()
+            .map(|i| {
+                let source = &sources[i];
+                Label::primary(source.file, span(&source.contents, key))
+            })
+            .collect();
+
+        diagnostics.push(
+            Diagnostic::error()
+                .with_message(issue.to_string())
+                .with_labels(labels),
+        );
+    }
+
+    // NOTE: the expected locations of files can only be computed once the
+    // graph is structurally sound.
+    if issues.is_empty() {
+        // SAFETY: the graph was just checked and has no issues.
+        let graph = Graph::try_new(nodes.clone()).unwrap();
+
+        for (source, node) in sources.iter().zip(&nodes) {
+            let expected = path.join(Directory::relative_path(&graph, node, policy));
+
+            if expected != source.path {
+                diagnostics.push(
+                    Diagnostic::error()
+                        .with_message(format!(
+                            "node `{}` is not located where the layout dictates",
+                            node.name()
+                        ))
+                        .with_labels(vec![
+                            Label::primary(source.file, span(&source.contents, "parent"))
+                                .with_message(format!("expected `{}`", expected.display())),
+                        ]),
+                );
+            }
+        }
+
+        let files_by_name = sources
+            .iter()
+            .zip(&nodes)
+            .map(|(source, node)| (node.name().inner(), source))
+            .collect::<HashMap<_, _>>();
+
+        for violation in ontology::constraint::evaluate(&graph) {
+            let node = files_by_name[violation.node.as_str()];
+            let ancestor = files_by_name[violation.ancestor.as_str()];
+
+            diagnostics.push(
+                Diagnostic::error()
+                    .with_message(violation.to_string())
+                    .with_labels(vec![
+                        Label::primary(node.file, span(&node.contents, "name")),
+                        Label::secondary(ancestor.file, span(&ancestor.contents, "constraints"))
+                            .with_message("constraint declared here"),
+                    ]),
+            );
+        }
+    }
+
+    let diagnostic = match similar_names {
+        Severity::Allow => None,
+        Severity::Warn => Some(Diagnostic::warning()),
+        Severity::Deny => Some(Diagnostic::error()),
+    };
+
+    if let Some(diagnostic) = diagnostic {
+        let names = nodes
+            .iter()
+            .map(|node| node.name().inner())
+            .collect::<Vec<_>>();
+
+        for near_miss in ecc::text::similar::near_misses(&names, max_name_distance) {
+            let (first, second) = (&sources[near_miss.first], &sources[near_miss.second]);
+
+            diagnostics.push(
+                diagnostic
+                    .clone()
+                    .with_message(
+                        near_miss.describe(names[near_miss.first], names[near_miss.second]),
+                    )
+                    .with_labels(vec![
+                        Label::primary(first.file, span(&first.contents, "name")),
+                        Label::primary(second.file, span(&second.contents, "name")),
+                    ])
+                    .with_notes(vec![format!(
+                        "the lint `{SIMILAR_NAMES}` reported this problem"
+                    )]),
+            );
+        }
+    }
+
+    for diagnostic in &diagnostics {
+        term::emit(&mut writer.lock(), &config, &files, diagnostic)?;
+    }
+
+    let problems = diagnostics
+        .iter()
+        .filter(|diagnostic| diagnostic.severity >= DiagnosticSeverity::Error)
+        .count();
+    let warnings = diagnostics.len() - problems;
+
+    if problems == 0 {
+        print!(
+            "{} {} nodes.. {}",
+            "Checked".bold(),
+            nodes.len(),
+            "OK".green()
+        );
+
+        if warnings > 0 {
+            print!(" {}", format!("({warnings} warnings)").yellow());
+        }
+
+        println!();
+        return Ok(());
+    }
+
+    println!(
+        "{} {} nodes.. {} ({problems} problems)",
+        "Checked".bold(),
+        nodes.len(),
+        "FAIL".red(),
+    );
+    std::process::exit(crate::EXIT_FAILURE.into());
+}
@@ -0,0 +1,173 @@
+//! Exporting of an ontology to other formats.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+use clap::Parser;
+use clap::ValueEnum;
+use ontology::Graph;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The format to export an ontology in.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum Format {
+    /// A Graphviz DOT digraph.
+    #[default]
+    Dot,
+
+    /// A Mermaid flowchart.
+    Mermaid,
+
+    /// A tab-separated value file in the format read by `ontology init`.
+    Tsv,
+}
+
+/// Exports an ontology to another format.
+///
+/// Nodes are written depth first, and siblings are sorted by their order and
+/// then by name (siblings without an order come last).
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The format to export the ontology in.
+    #[arg(short, long, value_enum, default_value_t)]
+    format: Format,
+}
+
+/// Renders the graph as a Graphviz DOT digraph.
+///
+/// Nodes are identified by their codes and labeled with their names, and
+/// obsolete nodes are drawn with dashed outlines.
+fn dot(graph: &Graph) -> String {
+    let quote = |value: &str| format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""));
+
+    // NOTE: writing to a [`String`] cannot fail, so the results are ignored
+    // throughout this module.
+    let mut out = String::from("digraph ontology {\n");
+
+    for node in graph.preorder() {
+        let style = if node.obsolete() {
+            ", style=dashed"
+        } else {
+            ""
+        };
+        let _ = writeln!(
+            out,
+            "    {} [label={}{style}];",
+            quote(node.code()),
+            quote(node.name().inner())
+        );
+    }
+
+    for node in graph.preorder() {
+        for child in graph.children(node.name().inner()) {
+            let _ = writeln!(
+                out,
+                "    {} -> {};",
+                quote(node.code()),
+                quote(child.code())
+            );
+        }
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+/// Renders the graph as a Mermaid flowchart.
+///
+/// Nodes are identified by their position (as codes may contain characters
+/// that Mermaid does not allow) and labeled with their names.
+fn mermaid(graph: &Graph) -> String {
+    let nodes = graph.preorder();
+    let ids = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, node)| (node.name().inner(), format!("n{i}")))
+        .collect::<HashMap<_, _>>();
+    let id = |name: &str| ids[name].as_str();
+
+    let mut out = String::from("flowchart TD\n");
+
+    for node in &nodes {
+        let _ = writeln!(
+            out,
+            "    {}[\"{}\"]",
+            id(node.name().inner()),
+            node.name().inner().replace('"', "#quot;")
+        );
+    }
+
+    for node in &nodes {
+        for child in graph.children(node.name().inner()) {
+            let _ = writeln!(
+                out,
+                "    {} --> {}",
+                id(node.name().inner()),
+                id(child.name().inner())
+            );
+        }
+    }
+
+    out
+}
+
+/// Renders the graph as a tab-separated value file.
+fn tsv(graph: &Graph) -> String {
+    let mut out = String::from(
+        "name\tparent\tcode\torder\tobsolete\treplaced_by\tcharacteristics\ttranslations\n",
+    );
+
+    for node in graph.preorder() {
+        let _ = writeln!(
+            out,
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            node.name().inner(),
+            node.parent().inner(),
+            node.code(),
+            node.order()
+                .map(|order| order.to_string())
+                .unwrap_or_default(),
+            node.obsolete(),
+            node.replaced_by()
+                .map(|name| name.inner())
+                .unwrap_or_default(),
+            node.characteristics()
+                .iter()
+                .map(|binding| match binding.value() {
+                    Some(value) => format!("{}={value}", binding.identifier()),
+                    None => binding.identifier().to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("; "),
+            node.translations()
+                .iter()
+                .map(|(language, name)| format!("{language}={name}"))
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+    }
+
+    out
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let (graph, _) = tree::load_ontology_graph(&config.require_ontology(args.path)?)?;
+
+    let output = match args.format {
+        Format::Dot => dot(&graph),
+        Format::Mermaid => mermaid(&graph),
+        Format::Tsv => tsv(&graph),
+    };
+
+    print!("{output}");
+    Ok(())
+}
@@ -1,103 +1,1002 @@
 //! Initialization of an ontology directory.
 
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
+use std::collections::HashSet;
+use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::Context;
-use anyhow::anyhow;
 use anyhow::bail;
+use calamine::Reader as _;
+use calamine::Xlsx;
 use clap::Parser;
+use clap::ValueEnum;
+use colored::Colorize as _;
+use ecc::CharacteristicSet;
+use ecc::Identifier;
+use ecc::annotate::Annotation;
+use ecc::annotate::AnnotationSet;
+use ecc::text::LanguageTag;
+use ontology::Graph;
 use ontology::Node;
-use petgraph::graph::DiGraph;
+use ontology::graph::SiblingOrder;
+use ontology::node::Binding;
+use ontology::node::Builder;
+use ontology::node::Name;
+use tracing::info;
+use tracing::warn;
 
 pub mod directory;
 
 use directory::Directory;
+use directory::Layout;
+use directory::PathPolicy;
+
+use crate::config::Config;
+use crate::tree;
+
+/// The fields of a node, each of which must be provided by a column.
+const FIELDS: &[&str] = &["name", "parent", "code"];
+
+/// The fields of a node that are read from a column only if it is present.
+const OPTIONAL_FIELDS: &[&str] = &[
+    "order",
+    "obsolete",
+    "replaced_by",
+    "characteristics",
+    "translations",
+];
+
+/// The byte order mark that some editors write at the start of a file.
+const BOM: char = '\u{feff}';
 
 /// Initializes a directory from a pre-existing ontology mapping.
 #[derive(Parser)]
 pub struct Args {
-    /// The tab-separated value file containing the existing ontology.
+    /// The file containing the existing ontology.
+    ///
+    /// Either a tab-separated value file or an Excel workbook (see
+    /// `--format`). The file (or sheet) must have a header row with the
+    /// following columns (in any order; the names are matched
+    /// case-insensitively):
+    ///
+    /// * `name`: the name of the node (in title case).
+    /// * `parent`: the name of the parent node (empty for the root node).
+    /// * `code`: the short code for the node.
+    ///
+    /// The following columns are optional:
+    ///
+    /// * `order`: the position of the node among its siblings (a non-negative
+    ///   integer). Siblings are sorted by their order and then by name, and
+    ///   siblings without an order come last.
+    /// * `obsolete`: whether the node has been removed from the classification
+    ///   (`true` or `false`; empty is `false`).
+    /// * `replaced_by`: the name of the node that replaces an obsolete node.
+    /// * `characteristics`: the characteristics the node is bound to, separated
+    ///   by semicolons. Each is an identifier optionally followed by the value
+    ///   it is bound to (e.g., `ECC-MOLEC-000001=true; ECC-MORPH-000002`).
+    ///   Bindings are validated against the composable characteristics (see
+    ///   `--ecc`).
+    /// * `translations`: translations of the name into other languages,
+    ///   separated by semicolons. Each is a BCP 47 language tag followed by `=`
+    ///   and the translated name (e.g., `de=Leukämie; pt-BR=Leucemia`).
     ///
-    /// See the example file or the definition for nodes within the ontology
-    /// package to learn about the required columns and their individual
-    /// requirements.
-    tsv: PathBuf,
+    /// Other columns are ignored. Use `--map-column` if the columns are named
+    /// differently.
+    input: PathBuf,
+
+    /// The format of the input file.
+    ///
+    /// Defaults to `xlsx` for files with an `.xlsx` extension and `tsv`
+    /// otherwise.
+    #[arg(long, value_enum)]
+    format: Option<InputFormat>,
+
+    /// The sheet to read from an Excel workbook.
+    ///
+    /// Defaults to the first sheet in the workbook.
+    #[arg(long)]
+    sheet: Option<String>,
+
+    /// The path to the composable characteristic directory.
+    ///
+    /// Only read when a `characteristics` column is present. Defaults to the
+    /// directory configured in `ecc.toml`.
+    #[arg(long)]
+    ecc: Option<PathBuf>,
 
     /// The directory to output the ontology files.
     #[clap(short)]
     output_directory: PathBuf,
+
+    /// Reads a field from a differently named column (e.g.,
+    /// `name=Diagnosis`). May be repeated.
+    #[arg(long, value_name = "FIELD=COLUMN")]
+    map_column: Vec<ColumnMapping>,
+
+    /// Skips invalid rows (and their descendants) and prints a summary of the
+    /// skipped rows instead of failing.
+    #[arg(long)]
+    report: bool,
+
+    /// How to handle rows whose names differ only in whitespace or case.
+    #[arg(long, value_enum, default_value_t)]
+    on_duplicate: OnDuplicate,
+
+    /// How to lay out the node files.
+    ///
+    /// Defaults to the layout configured in `ecc.toml`.
+    #[arg(long, value_enum)]
+    layout: Option<Layout>,
+
+    /// Writes files for obsolete nodes (and their descendants), which are
+    /// skipped by default.
+    #[arg(long)]
+    include_obsolete: bool,
 }
 
-/// The main method.
-pub fn main(args: Args) -> anyhow::Result<()> {
-    let mut reader = File::open(&args.tsv)
-        .with_context(|| format!("opening file: {}", args.tsv.display()))
-        .map(BufReader::new)
-        .map(|reader| {
-            csv::ReaderBuilder::new()
-                .delimiter(b'\t')
-                .from_reader(reader)
+/// The format of the input file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum InputFormat {
+    /// A tab-separated value file.
+    Tsv,
+
+    /// An Excel workbook.
+    Xlsx,
+}
+
+impl InputFormat {
+    /// Infers the format of a file from its extension.
+    fn infer(path: &Path) -> Self {
+        match path.extension().and_then(|extension| extension.to_str()) {
+            Some(extension) if extension.eq_ignore_ascii_case("xlsx") => Self::Xlsx,
+            _ => Self::Tsv,
+        }
+    }
+}
+
+/// How to handle duplicated rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OnDuplicate {
+    /// Fails with a report of every duplicated row.
+    #[default]
+    Error,
+
+    /// Keeps the first row and skips the rest.
+    Skip,
+
+    /// Combines the rows, filling in fields that are empty in the first row.
+    ///
+    /// Fails if the rows have conflicting values for a field.
+    Merge,
+}
+
+/// A mapping from a node field to a column in the file.
+#[derive(Clone, Debug)]
+pub struct ColumnMapping {
+    /// The node field.
+    field: &'static str,
+
+    /// The column name.
+    column: String,
+}
+
+impl std::str::FromStr for ColumnMapping {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((field, column)) = s.split_once('=') else {
+            return Err(format!("invalid mapping: `{s}`; expected `FIELD=COLUMN`"));
+        };
+
+        let field = field.trim().to_lowercase();
+        let Some(field) = FIELDS.iter().chain(OPTIONAL_FIELDS).find(|f| **f == field) else {
+            return Err(format!(
+                "unknown field: `{field}`; expected one of: {}",
+                FIELDS
+                    .iter()
+                    .chain(OPTIONAL_FIELDS)
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        };
+
+        let column = column.trim();
+
+        if column.is_empty() {
+            return Err(format!("missing column name for field `{field}`"));
+        }
+
+        Ok(Self {
+            field,
+            column: column.to_string(),
+        })
+    }
+}
+
+/// A row that was not included in the ontology.
+struct Skipped {
+    /// The line number of the row within the file.
+    line: u64,
+
+    /// The name of the node (if it could be read).
+    name: Option<String>,
+
+    /// The reason the row was skipped.
+    reason: String,
+}
+
+/// A table read from the input file.
+struct Table {
+    /// The header row.
+    headers: Vec<String>,
+
+    /// The line (or row) number and cells of each non-empty record.
+    records: Vec<(u64, Vec<String>)>,
+}
+
+impl Table {
+    /// Reads a table from a tab-separated value file.
+    fn from_tsv(path: &Path) -> anyhow::Result<Self> {
+        let contents = tree::read(path)?;
+
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(b'\t')
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(contents.trim_start_matches(BOM).as_bytes());
+
+        let headers = reader
+            .headers()
+            .context("reading the header row")?
+            .iter()
+            .map(String::from)
+            .collect();
+
+        let mut records = Vec::new();
+
+        for result in reader.records() {
+            let record = result.with_context(|| format!("reading file: {}", path.display()))?;
+            let line = record.position().map(|p| p.line()).unwrap_or_default();
+            records.push((line, record.iter().map(String::from).collect()));
+        }
+
+        Ok(Self { headers, records })
+    }
+
+    /// Reads a table from a sheet of an Excel workbook.
+    ///
+    /// The first row of the used area of the sheet is the header row, and
+    /// records are numbered by their row within the sheet.
+    fn from_xlsx(path: &Path, sheet: Option<&str>) -> anyhow::Result<Self> {
+        let mut workbook = calamine::open_workbook::<Xlsx<_>, _>(path)
+            .with_context(|| format!("opening workbook: {}", path.display()))?;
+
+        let sheet = match sheet {
+            Some(sheet) => sheet.to_string(),
+            None => workbook
+                .sheet_names()
+                .first()
+                .cloned()
+                .with_context(|| format!("no sheets in workbook: {}", path.display()))?,
+        };
+
+        let range = workbook.worksheet_range(&sheet).with_context(|| {
+            format!(
+                "reading sheet `{sheet}` (found sheets: {})",
+                workbook
+                    .sheet_names()
+                    .iter()
+                    .map(|name| format!("`{name}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
         })?;
 
-    let mut nodes = Vec::new();
-    let mut indexes = HashMap::new();
+        let first = range
+            .start()
+            .map(|(row, _)| u64::from(row))
+            .unwrap_or_default();
+        let mut rows = range.rows().enumerate().map(|(i, cells)| {
+            let cells = cells
+                .iter()
+                .map(|cell| cell.to_string().trim().to_string())
+                .collect::<Vec<_>>();
+            (first + i as u64 + 1, cells)
+        });
+
+        let Some((_, headers)) = rows.next() else {
+            bail!("sheet `{sheet}` is empty");
+        };
+
+        Ok(Self {
+            headers,
+            records: rows.collect(),
+        })
+    }
+}
+
+/// Finds the index of the column for each field.
+fn columns(
+    headers: &[String],
+    mappings: &[ColumnMapping],
+) -> anyhow::Result<HashMap<&'static str, usize>> {
+    let headers = headers
+        .iter()
+        .map(|header| header.trim_start_matches(BOM).trim().to_lowercase())
+        .collect::<Vec<_>>();
+
+    let mut result = HashMap::new();
+    let mut missing = Vec::new();
+
+    for field in FIELDS.iter().chain(OPTIONAL_FIELDS) {
+        let column = mappings
+            .iter()
+            .rev()
+            .find(|mapping| mapping.field == *field)
+            .map(|mapping| mapping.column.to_lowercase())
+            .unwrap_or_else(|| field.to_string());
+
+        match headers.iter().position(|header| *header == column) {
+            Some(index) => {
+                result.insert(*field, index);
+            }
+            None if OPTIONAL_FIELDS.contains(field) => {}
+            None => missing.push(format!("`{column}` (for `{field}`)")),
+        }
+    }
+
+    if !missing.is_empty() {
+        bail!(
+            "missing columns: {}; found columns: {}",
+            missing.join(", "),
+            headers
+                .iter()
+                .map(|header| format!("`{header}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+
+    Ok(result)
+}
+
+/// A row read from the file.
+#[derive(Clone)]
+struct Row {
+    /// The line number of the row within the file.
+    line: u64,
+
+    /// The name of the node.
+    name: String,
+
+    /// The name of the parent node.
+    parent: String,
+
+    /// The short code for the node.
+    code: String,
+
+    /// The position of the node among its siblings (empty if not set).
+    order: String,
+
+    /// Whether the node is obsolete (empty if not set).
+    obsolete: String,
+
+    /// The name of the replacement node (empty if not set).
+    replaced_by: String,
+
+    /// The characteristic bindings (empty if not set).
+    characteristics: String,
+
+    /// The translated names (empty if not set).
+    translations: String,
+}
+
+impl Row {
+    /// Reads a row from a record, collapsing any runs of whitespace.
+    fn read(line: u64, record: &[String], columns: &HashMap<&'static str, usize>) -> Self {
+        let field = |name: &str| {
+            columns
+                .get(name)
+                .and_then(|index| record.get(*index))
+                .map(String::as_str)
+                .unwrap_or_default()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        Self {
+            line,
+            name: field("name"),
+            parent: field("parent"),
+            code: field("code"),
+            order: field("order"),
+            obsolete: field("obsolete"),
+            replaced_by: field("replaced_by"),
+            characteristics: field("characteristics"),
+            translations: field("translations"),
+        }
+    }
+
+    /// Gets the field values along with their names.
+    fn fields(&self) -> [(&'static str, &str); 8] {
+        [
+            ("name", &self.name),
+            ("parent", &self.parent),
+            ("code", &self.code),
+            ("order", &self.order),
+            ("obsolete", &self.obsolete),
+            ("replaced_by", &self.replaced_by),
+            ("characteristics", &self.characteristics),
+            ("translations", &self.translations),
+        ]
+    }
+
+    /// Parses the row into a node.
+    fn parse(&self) -> Result<Node, String> {
+        if self.name.is_empty() {
+            return Err(String::from("the name is empty"));
+        }
+
+        let name = self
+            .name
+            .parse::<Name>()
+            .map_err(|err| format!("invalid name: {err}"))?;
+        let parent = self
+            .parent
+            .parse::<Name>()
+            .map_err(|err| format!("invalid parent: {err}"))?;
+
+        let mut builder = Builder::default()
+            .name(name)
+            .parent(parent)
+            .code(self.code.as_str());
+
+        if !self.order.is_empty() {
+            let order = self
+                .order
+                .parse::<u32>()
+                .map_err(|err| format!("invalid order `{}`: {err}", self.order))?;
+            builder = builder.order(order);
+        }
+
+        let obsolete = match self.obsolete.to_lowercase().as_str() {
+            "" | "false" => false,
+            "true" => true,
+            _ => return Err(format!("invalid obsolete flag `{}`", self.obsolete)),
+        };
+        builder = builder.obsolete(obsolete);
+
+        if !self.replaced_by.is_empty() {
+            let replacement = self
+                .replaced_by
+                .parse::<Name>()
+                .map_err(|err| format!("invalid replacement: {err}"))?;
+            builder = builder.replaced_by(replacement);
+        }
+
+        for binding in bindings(&self.characteristics)? {
+            builder = builder.characteristic(binding);
+        }
+
+        for (language, translation) in translations(&self.translations)? {
+            builder = builder.translation(language, translation);
+        }
+
+        builder.try_build().map_err(|err| err.to_string())
+    }
+}
+
+/// Parses the characteristic bindings of a row.
+///
+/// Bindings are separated by semicolons, and each is an identifier optionally
+/// followed by `=` and the value it is bound to.
+fn bindings(value: &str) -> Result<Vec<Binding>, String> {
+    let mut result = Vec::<Binding>::new();
+
+    for binding in value.split(';').map(str::trim).filter(|b| !b.is_empty()) {
+        let (identifier, value) = match binding.split_once('=') {
+            Some((identifier, value)) => (identifier.trim(), Some(value.trim())),
+            None => (binding, None),
+        };
 
-    for result in reader.deserialize() {
-        let node: Node = result?;
-        nodes.push(node)
+        let identifier = identifier
+            .parse::<Identifier>()
+            .map_err(|err| format!("invalid characteristic `{identifier}`: {err}"))?;
+
+        if result.iter().any(|b| b.identifier() == &identifier) {
+            return Err(format!(
+                "characteristic `{identifier}` is bound more than once"
+            ));
+        }
+
+        let binding = Binding::new(identifier);
+        result.push(match value {
+            Some(value) if !value.is_empty() => binding.with_value(value),
+            _ => binding,
+        });
     }
 
-    let mut graph = DiGraph::new();
+    Ok(result)
+}
+
+/// Parses the translated names of a row.
+///
+/// Translations are separated by semicolons, and each is a language tag
+/// followed by `=` and the translated name.
+fn translations(value: &str) -> Result<Vec<(LanguageTag, String)>, String> {
+    let mut result = Vec::<(LanguageTag, String)>::new();
+
+    for translation in value.split(';').map(str::trim).filter(|t| !t.is_empty()) {
+        let Some((language, name)) = translation.split_once('=') else {
+            return Err(format!(
+                "invalid translation `{translation}`: expected a language tag followed by `=` and \
+                 the translated name"
+            ));
+        };
 
-    for node in &nodes {
-        let name = node.name().inner().to_string();
+        let language = language
+            .trim()
+            .parse::<LanguageTag>()
+            .map_err(|err| format!("invalid translation `{translation}`: {err}"))?;
+        let name = name.trim();
+
+        if name.is_empty() {
+            return Err(format!("the translation into `{language}` is empty"));
+        }
 
-        if indexes.contains_key(&name) {
-            bail!("attempted to insert node twice: {name}");
+        if result.iter().any(|(l, _)| l == &language) {
+            return Err(format!(
+                "the name is translated into `{language}` more than once"
+            ));
         }
 
-        let index = graph.add_node(node.clone());
-        indexes.insert(name, index);
+        result.push((language, name.to_string()));
     }
 
-    let mut root = None;
+    Ok(result)
+}
 
-    for node in nodes {
-        let name = node.name().inner().to_string();
-        let node_index = indexes
-            .get(&name)
-            .cloned()
-            .ok_or(anyhow!("specified node does not exist: {name}"))?;
+/// Validates the characteristic bindings of a node against the composable
+/// characteristics.
+fn validate(node: &Node, characteristics: &CharacteristicSet) -> Result<(), String> {
+    let mut problems = Vec::new();
 
-        let parent = node.parent().inner().to_string();
+    for binding in node.characteristics() {
+        match binding.value() {
+            Some(value) => {
+                let annotations =
+                    AnnotationSet::new(vec![Annotation::new(binding.identifier().clone(), value)]);
 
-        if parent.is_empty() {
-            if root.is_some() {
-                bail!("found multiple roots: {} and {}", root.unwrap(), name);
+                if let Err(errors) = annotations.validate_against(characteristics) {
+                    problems.extend(errors.into_iter().map(|err| err.to_string()));
+                }
+            }
+            None if characteristics.get(binding.identifier()).is_none() => {
+                problems.push(format!(
+                    "unknown characteristic: `{}`",
+                    binding.identifier()
+                ));
             }
+            None => {}
+        }
+    }
+
+    if problems.is_empty() {
+        Ok(())
+    } else {
+        Err(problems.join("; "))
+    }
+}
+
+/// Normalizes a value for comparison between rows.
+fn normalize(value: &str) -> String {
+    value.to_lowercase()
+}
+
+/// A row that duplicates an earlier row.
+struct Duplicate {
+    /// The line number of the earlier row.
+    first: u64,
+
+    /// The duplicated row.
+    row: Row,
+
+    /// The fields that disagree along with the value in each row.
+    conflicts: Vec<(&'static str, String, String)>,
+}
+
+impl std::fmt::Display for Duplicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} duplicates `{}` from line {}",
+            self.row.line, self.row.name, self.first
+        )?;
+
+        if self.conflicts.is_empty() {
+            return write!(f, " (no conflicting fields)");
+        }
+
+        for (field, first, duplicate) in &self.conflicts {
+            write!(
+                f,
+                "\n  {field}: `{first}` (line {}) vs. `{duplicate}`",
+                self.first
+            )?;
+        }
+
+        Ok(())
+    }
+}
 
-            root = Some(name);
+/// Combines the rows whose names differ only in whitespace or case.
+///
+/// The first occurrence of each name is kept. Returns the remaining rows, the
+/// duplicates, and whether any of the duplicates could not be merged.
+fn deduplicate(rows: Vec<Row>, mode: OnDuplicate) -> (Vec<Row>, Vec<Duplicate>, bool) {
+    let mut kept: Vec<Row> = Vec::new();
+    let mut index = HashMap::new();
+    let mut duplicates = Vec::new();
+    let mut unresolved = false;
+
+    for row in rows {
+        let Some(&i) = index.get(&normalize(&row.name)) else {
+            index.insert(normalize(&row.name), kept.len());
+            kept.push(row);
             continue;
+        };
+
+        let first = &mut kept[i];
+        let mut conflicts = Vec::new();
+
+        for ((field, a), (_, b)) in first.fields().into_iter().zip(row.fields()) {
+            if normalize(a) != normalize(b) {
+                conflicts.push((field, a.to_string(), b.to_string()));
+            }
+        }
+
+        if mode == OnDuplicate::Merge {
+            // NOTE: a field that is empty in either row does not conflict.
+            conflicts.retain(|(_, a, b)| !a.is_empty() && !b.is_empty());
+
+            if conflicts.is_empty() {
+                if first.parent.is_empty() {
+                    first.parent = row.parent.clone();
+                }
+
+                if first.code.is_empty() {
+                    first.code = row.code.clone();
+                }
+
+                if first.order.is_empty() {
+                    first.order = row.order.clone();
+                }
+
+                if first.obsolete.is_empty() {
+                    first.obsolete = row.obsolete.clone();
+                }
+
+                if first.replaced_by.is_empty() {
+                    first.replaced_by = row.replaced_by.clone();
+                }
+
+                if first.characteristics.is_empty() {
+                    first.characteristics = row.characteristics.clone();
+                }
+
+                if first.translations.is_empty() {
+                    first.translations = row.translations.clone();
+                }
+            } else {
+                unresolved = true;
+            }
         }
 
-        let parent_index = indexes
-            .get(&parent)
-            .cloned()
-            .ok_or(anyhow!("specified parent node does not exist: {parent}"))?;
+        duplicates.push(Duplicate {
+            first: first.line,
+            row,
+            conflicts,
+        });
+    }
+
+    // Point the parents (and replacements) of every row at the kept spelling
+    // of each name.
+    let names = kept
+        .iter()
+        .map(|row| (normalize(&row.name), row.name.clone()))
+        .collect::<HashMap<_, _>>();
+
+    for row in &mut kept {
+        if let Some(name) = names.get(&normalize(&row.parent)) {
+            row.parent = name.clone();
+        }
 
-        graph.add_edge(parent_index, node_index, ());
+        if let Some(name) = names.get(&normalize(&row.replaced_by)) {
+            row.replaced_by = name.clone();
+        }
     }
 
-    let root = root.ok_or(anyhow!("unable to identify root!"))?;
-    // SAFETY: this node is guaranteed to be in the indexes, because we already
-    // looked it up in the operations earlier on. So this will always unwrap.
-    let root = *indexes.get(&root).unwrap();
+    (kept, duplicates, unresolved)
+}
+
+/// Removes the nodes whose parents were skipped (transitively) and records
+/// them as skipped.
+fn prune_orphans(nodes: &mut Vec<(u64, Node)>, skipped: &mut Vec<Skipped>) {
+    let mut removed = skipped
+        .iter()
+        .filter_map(|row| row.name.clone())
+        .collect::<HashSet<_>>();
 
-    Directory::scaffold_from_graph(args.output_directory, root, graph)
-        .context("scaffolding the ontology directory")?;
+    loop {
+        let (orphans, kept): (Vec<_>, Vec<_>) = std::mem::take(nodes)
+            .into_iter()
+            .partition(|(_, node)| removed.contains(node.parent().inner()));
+        *nodes = kept;
+
+        if orphans.is_empty() {
+            break;
+        }
+
+        for (line, node) in orphans {
+            removed.insert(node.name().inner().to_string());
+            skipped.push(Skipped {
+                line,
+                name: Some(node.name().inner().to_string()),
+                reason: format!("the parent `{}` was skipped", node.parent()),
+            });
+        }
+    }
+}
+
+/// Prints the rows that were skipped.
+fn print_skipped(skipped: &[Skipped]) {
+    for row in skipped {
+        let name = row
+            .name
+            .as_deref()
+            .map(|name| format!(" (`{name}`)"))
+            .unwrap_or_default();
+        println!(
+            "{} line {}{name}: {}",
+            "skipped".yellow(),
+            row.line,
+            row.reason
+        );
+    }
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let table = match args
+        .format
+        .unwrap_or_else(|| InputFormat::infer(&args.input))
+    {
+        InputFormat::Tsv => Table::from_tsv(&args.input)?,
+        InputFormat::Xlsx => Table::from_xlsx(&args.input, args.sheet.as_deref())?,
+    };
+
+    let columns = columns(&table.headers, &args.map_column)?;
+
+    let mut rows = Vec::new();
+
+    for (line, record) in &table.records {
+        if record.iter().all(String::is_empty) {
+            continue;
+        }
+
+        rows.push(Row::read(*line, record, &columns));
+    }
+
+    let (rows, duplicates, unresolved) = deduplicate(rows, args.on_duplicate);
+
+    if !duplicates.is_empty() {
+        for duplicate in &duplicates {
+            eprintln!("{}: {duplicate}", args.input.display());
+        }
+
+        match args.on_duplicate {
+            OnDuplicate::Error => bail!(
+                "{} duplicated rows in `{}`; use `--on-duplicate` to skip or merge them",
+                duplicates.len(),
+                args.input.display()
+            ),
+            OnDuplicate::Merge if unresolved => bail!(
+                "unable to merge duplicated rows with conflicting fields in `{}`",
+                args.input.display()
+            ),
+            _ => info!("resolved {} duplicated rows", duplicates.len()),
+        }
+    }
+
+    // NOTE: the characteristics are only needed to validate bindings, so they
+    // are not loaded for ontologies without any.
+    let characteristics = if rows.iter().any(|row| !row.characteristics.is_empty()) {
+        Some(
+            tree::load(&config.characteristics(args.ecc.clone()))?
+                .into_iter()
+                .map(|(_, c)| c)
+                .collect::<CharacteristicSet>(),
+        )
+    } else {
+        None
+    };
+
+    let mut nodes = Vec::new();
+    let mut skipped = Vec::new();
+
+    for row in rows {
+        let result = row.parse().and_then(|node| match &characteristics {
+            Some(characteristics) => validate(&node, characteristics).map(|_| node),
+            None => Ok(node),
+        });
+
+        match result {
+            Ok(node) => nodes.push((row.line, node)),
+            Err(reason) => skipped.push(Skipped {
+                line: row.line,
+                name: Some(row.name).filter(|name| !name.is_empty()),
+                reason,
+            }),
+        }
+    }
+
+    if !args.report && !skipped.is_empty() {
+        for row in &skipped {
+            eprintln!("{}:{}: {}", args.input.display(), row.line, row.reason);
+        }
+
+        bail!(
+            "{} invalid rows in `{}`; use `--report` to skip them",
+            skipped.len(),
+            args.input.display()
+        );
+    }
+
+    prune_orphans(&mut nodes, &mut skipped);
+
+    let total = nodes.len() + skipped.len() + duplicates.len();
+    let nodes = nodes.into_iter().map(|(_, node)| node).collect::<Vec<_>>();
+    let graph = Graph::try_new(nodes)
+        .map_err(crate::Error::Ontology)?
+        .with_sibling_order(SiblingOrder::OrderThenName);
+
+    let policy = PathPolicy {
+        layout: args.layout.unwrap_or(config.directories.ontology_layout),
+        ..config.ontology_paths()
+    };
+    let manifest = Directory::scaffold_from_graph(
+        args.output_directory,
+        &graph,
+        policy,
+        args.include_obsolete,
+    )
+    .context("scaffolding the ontology directory")?;
+
+    for placement in &manifest {
+        for warning in &placement.warnings {
+            warn!("{}: {warning}", placement.path.display());
+        }
+    }
+
+    if !skipped.is_empty() {
+        skipped.sort_by_key(|row| row.line);
+        print_skipped(&skipped);
+        warn!("skipped {} of {total} rows", skipped.len());
+    }
+
+    info!("initialized ontology with {} nodes", graph.len());
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads the rows of a tab-separated value file.
+    fn rows(contents: &str) -> Vec<Row> {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("ontology.tsv");
+        std::fs::write(&path, contents).unwrap();
+
+        let table = Table::from_tsv(&path).unwrap();
+        let columns = columns(&table.headers, &[]).unwrap();
+        table
+            .records
+            .iter()
+            .map(|(line, record)| Row::read(*line, record, &columns))
+            .collect()
+    }
+
+    #[test]
+    fn malformed() {
+        let rows = rows(
+            "\u{feff}Name\tParent\tCode\tOrder\tObsolete\tTranslations\nLeukemia\t\tLEUK\nB-cell  \
+             Leukemia\tLeukemia\tBLL\tfirst\n\tLeukemia\tTLL\nMyeloid \
+             Leukemia\tLeukemia\tAML\t\tmaybe\nLymphoma\t\tLYM\t\t\tde\n",
+        );
+
+        // NOTE: short rows are padded, and runs of whitespace are collapsed.
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0].line, 2);
+        assert!(rows[0].parse().is_ok());
+        assert_eq!(rows[1].name, "B-cell Leukemia");
+
+        let errors = rows[1..]
+            .iter()
+            .map(|row| row.parse().unwrap_err())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors[0],
+            "invalid order `first`: invalid digit found in string"
+        );
+        assert_eq!(errors[1], "the name is empty");
+        assert_eq!(errors[2], "invalid obsolete flag `maybe`");
+        assert!(errors[3].starts_with("invalid translation `de`"));
+    }
+
+    #[test]
+    fn missing_columns() {
+        let err = columns(&[String::from("Name"), String::from("Code")], &[]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "missing columns: `parent` (for `parent`); found columns: `name`, `code`"
+        );
+
+        let mapping = "parent=Parent Name".parse::<ColumnMapping>().unwrap();
+        let columns = columns(
+            &[
+                String::from("Name"),
+                String::from("Parent Name"),
+                String::from("Code"),
+            ],
+            &[mapping],
+        )
+        .unwrap();
+        assert_eq!(columns["parent"], 1);
+    }
+
+    #[test]
+    fn duplicates() {
+        let contents = "name\tparent\tcode\torder\nLeukemia\t\tLEUK\t\nB-cell \
+                        Leukemia\tLeukemia\tBLL\t\nb-cell  leukemia\tLEUKEMIA\t\t1\nT-cell \
+                        Leukemia\tb-cell leukemia\tTLL\t\n";
+
+        let (kept, duplicates, unresolved) = deduplicate(rows(contents), OnDuplicate::Error);
+        assert_eq!(kept.len(), 3);
+        assert!(!unresolved);
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(
+            duplicates[0].to_string(),
+            "line 4 duplicates `b-cell leukemia` from line 3\n  code: `BLL` (line 3) vs. ``\n  \
+             order: `` (line 3) vs. `1`"
+        );
+
+        // NOTE: the parent of a row points at the kept spelling of the name.
+        assert_eq!(kept[2].parent, "B-cell Leukemia");
+
+        let (kept, duplicates, unresolved) = deduplicate(rows(contents), OnDuplicate::Skip);
+        assert_eq!(duplicates.len(), 1);
+        assert!(!unresolved);
+        assert_eq!(kept[1].order, "");
+
+        let (kept, duplicates, unresolved) = deduplicate(rows(contents), OnDuplicate::Merge);
+        assert!(duplicates[0].conflicts.is_empty());
+        assert!(!unresolved);
+        assert_eq!(kept[1].code, "BLL");
+        assert_eq!(kept[1].order, "1");
+
+        let (_, duplicates, unresolved) = deduplicate(
+            rows("name\tparent\tcode\nLeukemia\t\tLEUK\nLEUKEMIA\t\tLK\n"),
+            OnDuplicate::Merge,
+        );
+        assert!(unresolved);
+        assert_eq!(
+            duplicates[0].to_string(),
+            "line 3 duplicates `LEUKEMIA` from line 2\n  code: `LEUK` (line 2) vs. `LK`"
+        );
+    }
+}
@@ -1,107 +1,270 @@
 //! Scaffolding of an ontology directory.
 
-use std::collections::VecDeque;
 use std::fs::File;
 use std::io::BufWriter;
+use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::Context;
 use anyhow::bail;
+use clap::ValueEnum;
 use convert_case::Boundary;
 use convert_case::Case;
 use convert_case::Casing as _;
+use ontology::Graph;
 use ontology::Node;
-use petgraph::Direction;
-use petgraph::graph::DiGraph;
-use petgraph::graph::NodeIndex;
-use petgraph::visit::Bfs;
+use serde::Deserialize;
+
+use crate::tree;
+
+/// How node files are laid out within an ontology directory.
+///
+/// Node files are read the same way regardless of the layout, so the layout
+/// only determines where files are written and where they are expected to be.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize, ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum Layout {
+    /// Each node is nested within a directory for each of its ancestors (e.g.,
+    /// `leukemia/b-cell-lymphoblastic-leukemia.yml`).
+    #[default]
+    Nested,
+
+    /// Every node is at the top of the directory and named after the node
+    /// (e.g., `b-cell-lymphoblastic-leukemia.yml`).
+    Flat,
+
+    /// Every node is at the top of the directory and named after its short
+    /// code (e.g., `BLL.yml`).
+    ByCode,
+}
+
+/// The default limit on the length (in characters) of a node file's path
+/// within the ontology directory.
+///
+/// Windows limits paths to 260 characters by default, so this leaves room for
+/// the location of the ontology directory itself.
+pub const DEFAULT_MAX_PATH_LENGTH: usize = 200;
+
+/// Where node files are written within an ontology directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PathPolicy {
+    /// How node files are laid out.
+    pub layout: Layout,
+
+    /// The limit on the length (in characters) of a node file's path relative
+    /// to the ontology directory.
+    ///
+    /// When a node's path would be longer, its file (and directory) is named
+    /// after its short code instead of its name.
+    pub max_length: usize,
+}
+
+impl Default for PathPolicy {
+    fn default() -> Self {
+        Self {
+            layout: Layout::default(),
+            max_length: DEFAULT_MAX_PATH_LENGTH,
+        }
+    }
+}
+
+/// The location of a node's file within an ontology directory.
+#[derive(Clone, Debug)]
+pub struct Placement {
+    /// The path relative to the ontology directory.
+    pub path: PathBuf,
+
+    /// Problems with the path, such as the name of the node being abbreviated
+    /// or the path being longer than the limit.
+    pub warnings: Vec<String>,
+}
 
 /// Ontology directory structure operations.
 pub struct Directory;
 
 impl Directory {
     /// Scaffolds a directory structure from a graph.
+    ///
+    /// Nodes are written depth first in the sibling order of the graph.
+    /// Obsolete nodes (and their descendants) are skipped unless
+    /// `include_obsolete` is set. Returns the placement of every node that
+    /// was written (i.e., the manifest of the scaffolded directory).
     pub fn scaffold_from_graph(
         path: PathBuf,
-        root_index: NodeIndex,
-        graph: DiGraph<Node, ()>,
-    ) -> anyhow::Result<()> {
-        let mut bfs = Bfs::new(&graph, root_index);
-        // SAFETY: the root is always expected to be in the graph.
-        let root_name = graph.node_weight(root_index).unwrap().name().inner();
-
-        while let Some(index) = bfs.next(&graph) {
-            // This should always unwrap because we're walking with a BFS
-            // specifically built on the graph in question (and we're not
-            // modifying it).
-            let node = graph.node_weight(index).unwrap();
-
-            let mut current_node = node.clone();
-            let mut current_index = index;
-            let mut path_elements = VecDeque::new();
-
-            loop {
-                let mut neighbors = graph
-                    .neighbors_directed(current_index, Direction::Incoming)
-                    .collect::<Vec<_>>();
-
-                if neighbors.is_empty() {
-                    let current_node_name = current_node.name().inner();
-
-                    if current_node_name != root_name {
-                        bail!("found a root node named {current_node_name}");
-                    }
-
-                    // NOTE: if we get here, we've reached the root node.
-                    break;
+        graph: &Graph,
+        policy: PathPolicy,
+        include_obsolete: bool,
+    ) -> anyhow::Result<Vec<Placement>> {
+        let mut manifest = Vec::new();
+
+        for node in graph.preorder() {
+            if !include_obsolete && Self::is_obsolete(graph, node) {
+                continue;
+            }
+
+            let placement = Self::place(graph, node, policy);
+            Self::write_node(&path.join(&placement.path), node)?;
+            manifest.push(placement);
+        }
+
+        Ok(manifest)
+    }
+
+    /// Returns whether a node or any of its ancestors is obsolete.
+    fn is_obsolete(graph: &Graph, node: &Node) -> bool {
+        node.obsolete()
+            || graph
+                .ancestors(node.name().inner())
+                .iter()
+                .any(|ancestor| ancestor.obsolete())
+    }
+
+    /// Writes a node to a file, creating any missing parent directories.
+    pub fn write_node(file: &Path, node: &Node) -> anyhow::Result<()> {
+        // SAFETY: node files are always nested within the ontology directory,
+        // so the parent will always be present and this will unwrap.
+        std::fs::create_dir_all(file.parent().unwrap())
+            .with_context(|| format!("creating directory: {}", file.display()))?;
+
+        let writer = File::create(file)
+            .map(BufWriter::new)
+            .with_context(|| format!("opening writer to {}", file.display()))?;
+
+        serde_yaml::to_writer(writer, node).context("serializing node")
+    }
+
+    /// Verifies that the ontology directory is structurally valid and that
+    /// every node file is located where the policy dictates.
+    pub fn verify(path: &Path, policy: PathPolicy) -> anyhow::Result<()> {
+        let (graph, paths) = tree::load_ontology_graph(path)?;
+
+        for node in graph.nodes() {
+            let expected = path.join(Self::relative_path(&graph, node, policy));
+            let actual = &paths[node.name().inner()];
+
+            if &expected != actual {
+                bail!(
+                    "node `{}` is located at `{}` but was expected at `{}`",
+                    node.name(),
+                    actual.display(),
+                    expected.display()
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Gets the path of a node's file relative to the ontology directory.
+    ///
+    /// See [`Directory::place()`].
+    pub fn relative_path(graph: &Graph, node: &Node, policy: PathPolicy) -> PathBuf {
+        Self::place(graph, node, policy).path
+    }
+
+    /// Places a node's file within the ontology directory.
+    ///
+    /// See [`Layout`] for where each node is placed. File names are made safe
+    /// for Windows by removing reserved characters and suffixing reserved
+    /// names (e.g., `con`). If a node's path would be longer than the limit,
+    /// its file is named after its short code instead (see [`PathPolicy`]).
+    ///
+    /// Each ancestor's directory is named the same way as the ancestor's own
+    /// file, so the placement of a node never depends on its descendants.
+    pub fn place(graph: &Graph, node: &Node, policy: PathPolicy) -> Placement {
+        let nodes = match policy.layout {
+            Layout::Nested => {
+                let mut nodes = graph.ancestors(node.name().inner());
+                nodes.push(node);
+                nodes
+            }
+            Layout::Flat | Layout::ByCode => vec![node],
+        };
+
+        let mut directory = PathBuf::new();
+        let mut warnings = Vec::new();
+
+        for (i, current) in nodes.iter().enumerate() {
+            let last = i + 1 == nodes.len();
+
+            let (mut stem, sanitized) = match policy.layout {
+                Layout::ByCode => sanitize(current.code().to_string()),
+                Layout::Nested | Layout::Flat => {
+                    sanitize(path_name(current.name().inner().to_string()))
                 }
+            };
 
-                assert!(neighbors.len() == 1, "there should only be one parent!");
+            if policy.layout != Layout::ByCode
+                && length(&directory.join(format!("{stem}.yml"))) > policy.max_length
+            {
+                stem = sanitize(current.code().to_string()).0;
 
-                // SAFETY: we just checked that there is exactly one neighbor.
-                current_index = neighbors.pop().unwrap();
-                current_node = graph.node_weight(current_index).unwrap().clone();
-                // SAFETY: this should always unwrap, as the node is clearly
-                // connected as the parent within the graph.
-                path_elements.push_front(current_node.name().inner().to_string());
+                if last {
+                    warnings.push(format!(
+                        "the file for `{}` is named after its code (`{}`) to keep its path within \
+                         {} characters",
+                        current.name(),
+                        current.code(),
+                        policy.max_length
+                    ));
+                }
+            } else if sanitized && last {
+                warnings.push(format!(
+                    "the file name for `{}` was changed to `{stem}.yml` to be valid on Windows",
+                    current.name()
+                ));
             }
 
-            path_elements.push_back(format!("{}.yml", node.name().inner()));
-
-            let file = path_elements
-                .into_iter()
-                .map(|path| {
-                    clean_path_name(path)
-                        .from_case(Case::Title)
-                        // This keeps gene names together instead of splitting
-                        // them (e.g., `kmt2a` instead of `kmt-2-a`).
-                        .without_boundaries(&[Boundary::DigitUpper, Boundary::DigitLower])
-                        .to_case(Case::Kebab)
-                })
-                .fold(path.clone(), |mut acc, part| {
-                    acc.push(part);
-                    acc
-                });
-
-            // SAFETY: because we pass in a path to the function, the parent
-            // will always be present and this will unwrap.
-            std::fs::create_dir_all(file.parent().unwrap())
-                .with_context(|| format!("creating directory: {}", file.display()))?;
-
-            let writer = File::create(&file)
-                .map(BufWriter::new)
-                .with_context(|| format!("opening writer to {}", file.display()))?;
-
-            serde_yaml::to_writer(writer, node).context("serializing node")?;
+            if last {
+                directory.push(format!("{stem}.yml"));
+            } else {
+                directory.push(stem);
+            }
         }
 
-        Ok(())
+        let path = directory;
+        let length = length(&path);
+
+        if length > policy.max_length {
+            warnings.push(format!(
+                "the path for `{}` is {length} characters long, which is longer than the limit of \
+                 {}",
+                node.name(),
+                policy.max_length
+            ));
+        }
+
+        Placement { path, warnings }
     }
 }
 
 /// Characters to remove from file names.
 const CHARS_TO_REMOVE: &[char] = &[',', ';'];
 
+/// Characters that are not allowed within file names on Windows.
+const RESERVED_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// File names that are reserved on Windows (regardless of their extension).
+const RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// Gets the length of a path in characters.
+fn length(path: &Path) -> usize {
+    path.to_string_lossy().chars().count()
+}
+
+/// Converts a name to a kebab case path component.
+fn path_name(name: String) -> String {
+    clean_path_name(name)
+        .from_case(Case::Title)
+        // This keeps gene names together instead of splitting them (e.g.,
+        // `kmt2a` instead of `kmt-2-a`).
+        .without_boundaries(&[Boundary::DigitUpper, Boundary::DigitLower])
+        .to_case(Case::Kebab)
+}
+
 /// Cleans a file name of unwanted sequences.
 fn clean_path_name(mut name: String) -> String {
     for c in CHARS_TO_REMOVE {
@@ -110,3 +273,109 @@ fn clean_path_name(mut name: String) -> String {
 
     name
 }
+
+/// Makes a file stem valid on Windows.
+///
+/// Reserved and control characters are removed, trailing dots and spaces are
+/// trimmed, and reserved names are suffixed with `-node`. Returns the stem and
+/// whether it was changed.
+fn sanitize(stem: String) -> (String, bool) {
+    let mut result = stem
+        .chars()
+        .filter(|c| !RESERVED_CHARS.contains(c) && !c.is_control())
+        .collect::<String>()
+        .trim_end_matches(['.', ' '])
+        .to_string();
+
+    let base = result.split('.').next().unwrap_or_default();
+
+    if RESERVED_NAMES
+        .iter()
+        .any(|name| name.eq_ignore_ascii_case(base))
+    {
+        result.push_str("-node");
+    }
+
+    let changed = result != stem;
+    (result, changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use ontology::node::Builder;
+    use ontology::node::Name;
+
+    use super::*;
+
+    /// Creates a node.
+    fn node(name: &str, parent: &str, code: &str) -> Node {
+        Builder::default()
+            .name(name.parse::<Name>().unwrap())
+            .parent(parent.parse::<Name>().unwrap())
+            .code(code)
+            .try_build()
+            .unwrap()
+    }
+
+    #[test]
+    fn sanitize() {
+        assert_eq!(
+            super::sanitize(String::from("bcr::abl1")),
+            (String::from("bcrabl1"), true)
+        );
+        assert_eq!(
+            super::sanitize(String::from("Con")),
+            (String::from("Con-node"), true)
+        );
+        assert_eq!(
+            super::sanitize(String::from("leukemia")),
+            (String::from("leukemia"), false)
+        );
+    }
+
+    #[test]
+    fn place() {
+        let graph = Graph::try_new(vec![
+            node("Leukemia", "", "LEUK"),
+            node("B-cell Lymphoblastic Leukemia", "Leukemia", "BLL"),
+            node(
+                "B-cell Lymphoblastic Leukemia with Very Long Name",
+                "B-cell Lymphoblastic Leukemia",
+                "BLLLONG",
+            ),
+        ])
+        .unwrap();
+        let leaf = graph
+            .get("B-cell Lymphoblastic Leukemia with Very Long Name")
+            .unwrap();
+
+        let placement = Directory::place(&graph, leaf, PathPolicy::default());
+        assert_eq!(
+            placement.path,
+            Path::new(
+                "leukemia/b-cell-lymphoblastic-leukemia/\
+                 b-cell-lymphoblastic-leukemia-with-very-long-name.yml"
+            )
+        );
+        assert!(placement.warnings.is_empty());
+
+        let policy = PathPolicy {
+            max_length: 50,
+            ..Default::default()
+        };
+        let placement = Directory::place(&graph, leaf, policy);
+        assert_eq!(
+            placement.path,
+            Path::new("leukemia/b-cell-lymphoblastic-leukemia/BLLLONG.yml")
+        );
+        assert_eq!(placement.warnings.len(), 1);
+
+        let policy = PathPolicy {
+            max_length: 20,
+            ..Default::default()
+        };
+        let placement = Directory::place(&graph, leaf, policy);
+        assert_eq!(placement.path, Path::new("leukemia/BLL/BLLLONG.yml"));
+        assert_eq!(placement.warnings.len(), 2);
+    }
+}
@@ -0,0 +1,167 @@
+//! Moving of nodes within an ontology directory.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use clap::Parser;
+use ontology::Graph;
+use ontology::node::Builder;
+use ontology::node::Name;
+use tracing::debug;
+use tracing::info;
+
+use super::init::directory::Directory;
+use crate::config::Config;
+use crate::tree;
+
+/// Moves a node (and all of its descendants) beneath a new parent.
+///
+/// The moved node's file is updated with the new parent, every file in the
+/// subtree is relocated to where its new ancestors dictate, any directories
+/// left empty are removed, and the ontology directory is verified afterwards.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The name of the node to move.
+    #[arg(long)]
+    name: Name,
+
+    /// The name of the new parent node.
+    #[arg(long)]
+    parent: Name,
+}
+
+/// Removes a directory if it (recursively) contains only empty directories.
+fn prune(dir: &Path) -> anyhow::Result<()> {
+    if !dir.is_dir() {
+        return Ok(());
+    }
+
+    for entry in
+        std::fs::read_dir(dir).with_context(|| format!("reading directory: {}", dir.display()))?
+    {
+        let path = entry?.path();
+
+        if path.is_dir() {
+            prune(&path)?;
+        }
+    }
+
+    if std::fs::read_dir(dir)?.next().is_none() {
+        debug!("removing empty directory `{}`", dir.display());
+        std::fs::remove_dir(dir)
+            .with_context(|| format!("removing directory: {}", dir.display()))?;
+    }
+
+    Ok(())
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let root = config.require_ontology(args.path)?;
+    let (graph, paths) = tree::load_ontology_graph(&root)?;
+    let name = args.name.inner();
+
+    let Some(node) = graph.get(name) else {
+        bail!("node does not exist: {}", args.name);
+    };
+
+    if node.parent().inner().is_empty() {
+        bail!("the root node cannot be moved");
+    }
+
+    if graph.get(args.parent.inner()).is_none() {
+        bail!("parent node does not exist: {}", args.parent);
+    }
+
+    if node.parent() == &args.parent {
+        bail!("`{}` is already a child of `{}`", args.name, args.parent);
+    }
+
+    let moved = Builder::default()
+        .name(node.name().clone())
+        .parent(args.parent.clone())
+        .code(node.code())
+        .try_build()
+        .context("building node")?;
+
+    // NOTE: moving a node beneath one of its own descendants introduces a
+    // cycle, which is caught when the graph is rebuilt.
+    let nodes = graph
+        .nodes()
+        .map(|node| {
+            if node.name().inner() == name {
+                moved.clone()
+            } else {
+                node.clone()
+            }
+        })
+        .collect::<Vec<_>>();
+    let updated = Graph::try_new(nodes).map_err(crate::Error::Ontology)?;
+
+    let policy = config.ontology_paths();
+    let subtree = std::iter::once(node)
+        .chain(graph.descendants(name))
+        .map(|node| {
+            let from = paths[node.name().inner()].clone();
+            let to = root.join(Directory::relative_path(&updated, node, policy));
+            (node, from, to)
+        })
+        .collect::<Vec<_>>();
+
+    // Ensure nothing would be overwritten before touching the filesystem.
+    // NOTE: in the flat layouts, files do not move at all.
+    for (_, from, to) in &subtree {
+        if to != from && to.exists() {
+            bail!("file already exists: {}", to.display());
+        }
+    }
+
+    for (node, from, to) in &subtree {
+        if node.name().inner() == name {
+            Directory::write_node(to, &moved)?;
+
+            if from != to {
+                std::fs::remove_file(from)
+                    .with_context(|| format!("removing file: {}", from.display()))?;
+            }
+        } else if from != to {
+            // SAFETY: node files are always nested within the ontology
+            // directory, so the parent will always be present.
+            std::fs::create_dir_all(to.parent().unwrap())
+                .with_context(|| format!("creating directory: {}", to.display()))?;
+            std::fs::rename(from, to)
+                .with_context(|| format!("moving file: {} to {}", from.display(), to.display()))?;
+        }
+
+        debug!("moved `{}` to `{}`", from.display(), to.display());
+    }
+
+    // Remove the moved node's old directory along with any of its ancestors
+    // that were left empty.
+    let (_, from, _) = &subtree[0];
+    prune(&from.with_extension(""))?;
+
+    for dir in from.ancestors().skip(1) {
+        if dir == root || !dir.starts_with(&root) {
+            break;
+        }
+
+        prune(dir)?;
+    }
+
+    info!(
+        "moved `{}` ({} nodes) beneath `{}`",
+        args.name,
+        subtree.len(),
+        args.parent
+    );
+
+    Directory::verify(&root, policy).context("verifying the ontology directory")
+}
@@ -0,0 +1,418 @@
+//! Preparation of data releases.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use chrono::NaiveDate;
+use chrono::Utc;
+use clap::Parser;
+use clap::ValueEnum;
+use ecc::Characteristic;
+use ecc::common::Sensitivity;
+use serde::Serialize;
+use tracing::info;
+use tracing::warn;
+
+use crate::build;
+use crate::config::Config;
+use crate::git;
+use crate::sign;
+use crate::tree;
+
+/// The heading written at the top of a new changelog.
+const CHANGELOG_HEADING: &str = "# Changelog";
+
+/// The name of the release manifest within the release directory.
+const MANIFEST: &str = "release.json";
+
+/// The part of the version to increment.
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+pub enum Bump {
+    /// Increments the major version (e.g., `1.2.3` → `2.0.0`).
+    Major,
+
+    /// Increments the minor version (e.g., `1.2.3` → `1.3.0`).
+    #[default]
+    Minor,
+
+    /// Increments the patch version (e.g., `1.2.3` → `1.2.4`).
+    Patch,
+}
+
+/// Prepares a data release.
+///
+/// The characteristics adopted since the last release tag are gathered, the
+/// data release version is bumped, a section is added to the changelog, and
+/// the release bundle (along with a release manifest) is written to a
+/// directory named after the version. If a signing key is provided, the
+/// bundle manifest is signed with a detached Ed25519 signature (see
+/// [`crate::sign`]). The release is not tagged; the command to do so is
+/// printed once the release has been prepared.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml` (if any).
+    #[arg(long)]
+    ontology: Option<PathBuf>,
+
+    /// The tag of the previous release.
+    ///
+    /// Defaults to the most recent tag with the prefix configured in
+    /// `ecc.toml`.
+    #[arg(long)]
+    since: Option<String>,
+
+    /// The part of the version to increment.
+    #[arg(long, value_enum, default_value_t)]
+    bump: Bump,
+
+    /// The version of the data release.
+    ///
+    /// Overrides the version computed by `--bump`.
+    #[arg(long, conflicts_with = "bump")]
+    data_version: Option<Version>,
+
+    /// The most restricted sensitivity to release (`public`, `controlled`, or
+    /// `restricted`).
+    ///
+    /// Characteristics that are more restricted are left out of the release.
+    /// Defaults to the sensitivity configured in `ecc.toml` (or `public`).
+    #[arg(long)]
+    max_sensitivity: Option<Sensitivity>,
+
+    /// The directory to write release bundles to.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    #[arg(short, long)]
+    output_directory: Option<PathBuf>,
+
+    /// The PEM-encoded Ed25519 private key to sign the bundle manifest with.
+    #[arg(long, value_name = "PEM")]
+    sign_key: Option<PathBuf>,
+
+    /// Prints the changelog section without writing anything.
+    #[arg(long)]
+    dry_run: bool,
+}
+
+/// A data release version.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Version {
+    /// The major version.
+    major: u64,
+
+    /// The minor version.
+    minor: u64,
+
+    /// The patch version.
+    patch: u64,
+}
+
+impl Version {
+    /// Increments a part of the version.
+    fn bump(self, bump: Bump) -> Self {
+        match bump {
+            Bump::Major => Self {
+                major: self.major + 1,
+                minor: 0,
+                patch: 0,
+            },
+            Bump::Minor => Self {
+                minor: self.minor + 1,
+                patch: 0,
+                ..self
+            },
+            Bump::Patch => Self {
+                patch: self.patch + 1,
+                ..self
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl std::str::FromStr for Version {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts = s
+            .split('.')
+            .map(|part| part.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| format!("invalid version: `{s}`; expected `MAJOR.MINOR.PATCH`"))?;
+
+        match parts.as_slice() {
+            [major, minor, patch] => Ok(Self {
+                major: *major,
+                minor: *minor,
+                patch: *patch,
+            }),
+            _ => Err(format!(
+                "invalid version: `{s}`; expected `MAJOR.MINOR.PATCH`"
+            )),
+        }
+    }
+}
+
+/// A characteristic adopted within a release.
+#[derive(Serialize)]
+struct Adopted<'a> {
+    /// The identifier.
+    identifier: String,
+
+    /// The name.
+    name: &'a str,
+
+    /// The adoption date.
+    adoption_date: String,
+
+    /// The content hash of the characteristic.
+    content_hash: String,
+}
+
+/// The manifest describing a release.
+#[derive(Serialize)]
+struct Manifest<'a> {
+    /// The version of the data release.
+    version: String,
+
+    /// The tag of the previous release (if any).
+    previous: Option<&'a str>,
+
+    /// The date the release was prepared.
+    date: NaiveDate,
+
+    /// The version of the tool that prepared the release.
+    tool_version: &'static str,
+
+    /// The characteristics adopted since the previous release.
+    adopted: &'a [Adopted<'a>],
+}
+
+/// Finds the most recent release tag reachable from the current commit (if
+/// any).
+fn latest_tag(directory: &Path, prefix: &str) -> Option<String> {
+    let pattern = format!("{prefix}*");
+    git::run(
+        directory,
+        &["describe", "--tags", "--abbrev=0", "--match", &pattern],
+    )
+    .ok()
+    .filter(|tag| !tag.is_empty())
+}
+
+/// Renders the changelog section for a release.
+fn changelog_section(version: Version, date: NaiveDate, adopted: &[Adopted<'_>]) -> String {
+    let mut out = String::new();
+
+    // NOTE: writing to a [`String`] cannot fail, so the results are ignored.
+    let _ = writeln!(out, "## {version} ({date})");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "### Adopted");
+    let _ = writeln!(out);
+
+    if adopted.is_empty() {
+        let _ = writeln!(out, "No characteristics were adopted in this release.");
+    }
+
+    for characteristic in adopted {
+        let _ = writeln!(
+            out,
+            "- `{}` {}",
+            characteristic.identifier, characteristic.name
+        );
+    }
+
+    out
+}
+
+/// Inserts a section into the changelog above the most recent release.
+fn insert_section(changelog: &str, version: Version, section: &str) -> anyhow::Result<String> {
+    let heading = format!("## {version} ");
+
+    if changelog.lines().any(|line| line.starts_with(&heading)) {
+        bail!("the changelog already contains a section for version `{version}`");
+    }
+
+    if changelog.trim().is_empty() {
+        return Ok(format!("{CHANGELOG_HEADING}\n\n{section}"));
+    }
+
+    let mut result = String::new();
+    let mut inserted = false;
+
+    for line in changelog.lines() {
+        if !inserted && line.starts_with("## ") {
+            result.push_str(section);
+            result.push('\n');
+            inserted = true;
+        }
+
+        result.push_str(line);
+        result.push('\n');
+    }
+
+    if !inserted {
+        result = format!("{}\n\n{section}", result.trim_end());
+    }
+
+    Ok(result)
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let path = config.characteristics(args.path);
+    let previous = args
+        .since
+        .or_else(|| latest_tag(&path, &config.release.tag_prefix));
+
+    let version = match (args.data_version, &previous) {
+        (Some(version), _) => version,
+        (None, Some(tag)) => tag
+            .strip_prefix(config.release.tag_prefix.as_str())
+            .unwrap_or(tag)
+            .parse::<Version>()
+            .map_err(anyhow::Error::msg)
+            .with_context(|| format!("parsing the version of tag `{tag}`"))?
+            .bump(args.bump),
+        (None, None) => Version::default().bump(args.bump),
+    };
+
+    let before = match &previous {
+        Some(tag) => {
+            info!("gathering characteristics adopted since `{tag}`");
+            git::load_at(&path, tag)?
+        }
+        None => {
+            info!("no previous release found; gathering all adopted characteristics");
+            Vec::new()
+        }
+    };
+
+    let already_adopted = before
+        .iter()
+        .filter(|(_, c)| matches!(c, Characteristic::Adopted { .. }))
+        .filter_map(|(_, c)| c.identifier().map(|i| i.to_string()))
+        .collect::<HashSet<_>>();
+
+    let mut characteristics = tree::load(&path)?
+        .into_iter()
+        .filter(|(_, c)| !matches!(c, Characteristic::Draft { .. }))
+        .collect::<Vec<_>>();
+    tree::withhold(
+        &mut characteristics,
+        args.max_sensitivity
+            .unwrap_or(config.export.max_sensitivity),
+    );
+
+    let mut adopted = characteristics
+        .iter()
+        .filter_map(|(_, characteristic)| {
+            let identifier = characteristic.identifier()?.to_string();
+            let date = characteristic.adoption_date()?;
+
+            (!already_adopted.contains(&identifier)).then(|| Adopted {
+                identifier,
+                name: characteristic.name().unwrap_or_default(),
+                adoption_date: date.date_naive().to_string(),
+                content_hash: characteristic.content_hash(),
+            })
+        })
+        .collect::<Vec<_>>();
+    adopted.sort_by(|a, b| a.identifier.cmp(&b.identifier));
+
+    if adopted.is_empty() {
+        warn!("no characteristics were adopted since the previous release");
+    }
+
+    let date = Utc::now().date_naive();
+    let section = changelog_section(version, date, &adopted);
+
+    if args.dry_run {
+        print!("{section}");
+        return Ok(());
+    }
+
+    let changelog_path = config.resolve(&config.release.changelog);
+    let changelog = match std::fs::read_to_string(&changelog_path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(err) => {
+            return Err(err)
+                .with_context(|| format!("reading changelog: {}", changelog_path.display()));
+        }
+    };
+    let changelog = insert_section(&changelog, version, &section)?;
+
+    let directory = args
+        .output_directory
+        .unwrap_or_else(|| config.resolve(&config.release.output_directory))
+        .join(version.to_string());
+
+    if directory.exists() {
+        bail!("release directory already exists: {}", directory.display());
+    }
+
+    // NOTE: the key is read before anything is written so that a bad key
+    // does not leave a partial release behind.
+    let key = args
+        .sign_key
+        .as_deref()
+        .map(sign::signing_key)
+        .transpose()?;
+
+    build::write(
+        &path,
+        &characteristics,
+        config.ontology(args.ontology).as_deref(),
+        Some(version.to_string()),
+        directory.clone(),
+    )?;
+
+    if let Some(key) = &key {
+        let signature = sign::sign(&directory.join(build::MANIFEST), key)?;
+        info!("signed the bundle manifest at `{}`", signature.display());
+    }
+
+    let manifest = Manifest {
+        version: version.to_string(),
+        previous: previous.as_deref(),
+        date,
+        tool_version: env!("CARGO_PKG_VERSION"),
+        adopted: &adopted,
+    };
+
+    let manifest_path = directory.join(MANIFEST);
+    std::fs::write(&manifest_path, serde_json::to_vec_pretty(&manifest)?)
+        .with_context(|| format!("writing {}", manifest_path.display()))?;
+
+    std::fs::write(&changelog_path, changelog)
+        .with_context(|| format!("writing changelog: {}", changelog_path.display()))?;
+    info!("updated changelog at `{}`", changelog_path.display());
+
+    info!(
+        "prepared release {version} with {} newly adopted characteristics",
+        adopted.len()
+    );
+    println!(
+        "Tag the release with: git tag {}{version}",
+        config.release.tag_prefix
+    );
+
+    Ok(())
+}
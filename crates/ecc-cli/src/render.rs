@@ -0,0 +1,7 @@
+//! Rendering of characteristics for human consumption.
+
+pub mod markdown;
+pub mod rfc;
+pub mod terminal;
+
+pub use terminal::Terminal;
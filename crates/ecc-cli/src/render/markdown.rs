@@ -0,0 +1,258 @@
+//! Markdown rendering of characteristics.
+
+use std::fmt::Write as _;
+
+use ecc::Characteristic;
+use ecc::common::Applicability;
+use ecc::common::Expression;
+use ecc::common::Reference;
+use ecc::common::value::Kind;
+use ecc::field;
+
+/// Renders a field description as a bulleted list item.
+fn description(out: &mut String, label: &str, description: &field::Description) {
+    // NOTE: writing to a [`String`] cannot fail, so the results are ignored
+    // throughout this module.
+    let _ = writeln!(out, "- **{label}**: {}", description.summary);
+    let _ = writeln!(out);
+
+    for line in description.details.as_str().trim().lines() {
+        if line.is_empty() {
+            let _ = writeln!(out);
+        } else {
+            let _ = writeln!(out, "  {line}");
+        }
+    }
+
+    let _ = writeln!(out);
+}
+
+/// Renders the permissible values of a characteristic (without a heading).
+pub(super) fn values(out: &mut String, kind: &Kind) {
+    let _ = writeln!(out, "This characteristic is **{}**.", kind.name());
+    let _ = writeln!(out);
+
+    match kind {
+        Kind::Binary {
+            description: d,
+            threshold,
+        } => {
+            if let Some(threshold) = threshold {
+                let _ = writeln!(
+                    out,
+                    "It is derived from `{}` and is `true` when `{threshold}`.",
+                    threshold.source
+                );
+                let _ = writeln!(out);
+            }
+
+            description(out, "True", &d.r#true);
+            description(out, "False", &d.r#false);
+        }
+        Kind::Categorical { options } => {
+            for option in options {
+                let _ = writeln!(out, "- `{option}`");
+            }
+
+            let _ = writeln!(out);
+        }
+        Kind::Numerical { r#type, units } => {
+            let _ = writeln!(out, "- **Type**: {type}");
+            let _ = writeln!(out, "- **Units**: {units}");
+            let _ = writeln!(out);
+        }
+        Kind::Fraction {
+            denominator_description,
+            scale,
+            min,
+            max,
+        } => {
+            let _ = writeln!(out, "- **Scale**: {scale} ({})", scale.units());
+            let _ = writeln!(out, "- **Denominator**: {denominator_description}");
+            let _ = writeln!(
+                out,
+                "- **Range**: {} to {}",
+                min.unwrap_or(0.0),
+                max.unwrap_or(scale.maximum())
+            );
+            let _ = writeln!(out);
+        }
+        Kind::Immunophenotype { markers } => {
+            let _ = writeln!(
+                out,
+                "Each marker is `negative`, `dim`, `moderate`, `bright`, or `partial`:"
+            );
+            let _ = writeln!(out);
+
+            for marker in markers {
+                let _ = writeln!(out, "- `{marker}`");
+            }
+
+            let _ = writeln!(out);
+        }
+        Kind::Other { description } => {
+            let _ = writeln!(out, "{}", description.trim());
+            let _ = writeln!(out);
+        }
+    }
+}
+
+/// Renders a single reference as a bulleted list item.
+fn reference(out: &mut String, reference: &Reference) {
+    let (kind, title, authors, context, url, highlighted) = match reference {
+        Reference::Manuscript {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        } => ("Manuscript", title, authors, context, url, highlighted),
+        Reference::Preprint {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        } => ("Preprint", title, authors, context, url, highlighted),
+    };
+
+    let star = if *highlighted { " ⭐" } else { "" };
+
+    let _ = writeln!(out, "- [{title}]({url}){star}");
+    let _ = writeln!(out, "  _{kind}; {authors}_");
+    let _ = writeln!(out);
+
+    for line in context.as_str().trim().lines() {
+        if line.is_empty() {
+            let _ = writeln!(out);
+        } else {
+            let _ = writeln!(out, "  {line}");
+        }
+    }
+
+    let _ = writeln!(out);
+}
+
+/// Renders the definition of a characteristic as a code block followed by a
+/// list of the characteristics that it refers to.
+///
+/// Each characteristic links to its page within the bundle (see
+/// [`crate::build`]).
+fn definition(out: &mut String, definition: &Expression) {
+    let _ = writeln!(out, "```text");
+    let _ = write!(out, "{}", definition.pretty());
+    let _ = writeln!(out, "```");
+    let _ = writeln!(out);
+    let _ = writeln!(out, "Refers to:");
+    let _ = writeln!(out);
+
+    for identifier in definition.identifiers() {
+        let _ = writeln!(out, "- [`{identifier}`]({identifier}.md)");
+    }
+
+    let _ = writeln!(out);
+}
+
+/// Renders items as a comma-separated list of inline code spans.
+fn codes<T: std::fmt::Display>(items: &[T]) -> String {
+    items
+        .iter()
+        .map(|item| format!("`{item}`"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Renders the samples that a characteristic applies to as a list with one
+/// item per constrained axis.
+fn applicability(out: &mut String, applicability: &Applicability) {
+    if !applicability.nodes().is_empty() {
+        let _ = writeln!(out, "- Ontology subtrees: {}", codes(applicability.nodes()));
+    }
+
+    if !applicability.specimen_types().is_empty() {
+        let _ = writeln!(
+            out,
+            "- Specimen types: {}",
+            codes(applicability.specimen_types())
+        );
+    }
+
+    if !applicability.age_groups().is_empty() {
+        let _ = writeln!(out, "- Age groups: {}", codes(applicability.age_groups()));
+    }
+
+    let _ = writeln!(out);
+}
+
+/// Renders a characteristic as a Markdown document.
+pub fn render(characteristic: &Characteristic) -> String {
+    let mut out = String::new();
+
+    let _ = writeln!(
+        out,
+        "# {}",
+        characteristic.name().unwrap_or("Unnamed Characteristic")
+    );
+    let _ = writeln!(out);
+    let _ = writeln!(out, "| Field | Value |");
+    let _ = writeln!(out, "| --- | --- |");
+
+    if let Some(identifier) = characteristic.identifier() {
+        let _ = writeln!(out, "| Identifier | `{identifier}` |");
+    }
+
+    let _ = writeln!(out, "| State | {} |", characteristic.state());
+
+    if let Some(level) = characteristic.evidence_level() {
+        let _ = writeln!(out, "| Evidence Level | `{level}` |");
+    }
+
+    if let Some(rfc) = characteristic.rfc() {
+        let _ = writeln!(out, "| RFC | <{rfc}> |");
+    }
+
+    if let Some(date) = characteristic.adoption_date() {
+        let _ = writeln!(out, "| Adopted | {} |", date.date_naive());
+    }
+
+    let _ = writeln!(out);
+
+    if let Some(description) = characteristic.description() {
+        let _ = writeln!(out, "{}", description.trim());
+        let _ = writeln!(out);
+    }
+
+    if let Some(expression) = characteristic.definition() {
+        let _ = writeln!(out, "## Definition");
+        let _ = writeln!(out);
+        definition(&mut out, expression);
+    }
+
+    if let Some(kind) = characteristic.values() {
+        let _ = writeln!(out, "## Values");
+        let _ = writeln!(out);
+        values(&mut out, kind);
+    }
+
+    if let Some(block) = characteristic.applicability() {
+        let _ = writeln!(out, "## Applies To");
+        let _ = writeln!(out);
+        applicability(&mut out, block);
+    }
+
+    if let Some(references) = characteristic.references() {
+        let _ = writeln!(out, "## References");
+        let _ = writeln!(out);
+
+        for r in references {
+            reference(&mut out, r);
+        }
+    }
+
+    // Ensure the document ends with exactly one newline.
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    out.push('\n');
+
+    out
+}
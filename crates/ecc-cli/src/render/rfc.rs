@@ -0,0 +1,132 @@
+//! Rendering of characteristics as the body of an RFC issue.
+//!
+//! The body uses level-three headings for each section (as GitHub does when
+//! rendering issue forms), and sections that are not yet filled in contain
+//! `_No response_`. This keeps the body readable by `ecc import-issue`.
+
+use std::fmt::Write as _;
+
+use ecc::Category;
+use ecc::Characteristic;
+use ecc::common::Reference;
+
+use super::markdown;
+
+/// The placeholder for a section that has not been filled in.
+const NO_RESPONSE: &str = "_No response_";
+
+/// Renders a single reference as a bulleted list item.
+///
+/// Each reference is kept on a single line so that it can be parsed back out
+/// of the issue.
+fn reference(out: &mut String, reference: &Reference) {
+    let (kind, title, authors, context, url, highlighted) = match reference {
+        Reference::Manuscript {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        } => ("manuscript", title, authors, context, url, highlighted),
+        Reference::Preprint {
+            title,
+            authors,
+            context,
+            url,
+            highlighted,
+        } => ("preprint", title, authors, context, url, highlighted),
+    };
+
+    let star = if *highlighted { " ⭐" } else { "" };
+    let authors = if authors.trim().is_empty() {
+        String::new()
+    } else {
+        format!("; {}", authors.trim())
+    };
+    let context = context.as_str().split_whitespace().collect::<Vec<_>>();
+
+    // NOTE: writing to a [`String`] cannot fail, so the results are ignored
+    // throughout this module.
+    let _ = writeln!(
+        out,
+        "- [{title}]({url}){star} ({kind}{authors}): {}",
+        context.join(" ")
+    );
+}
+
+/// Writes a section heading.
+fn heading(out: &mut String, label: &str) {
+    let _ = writeln!(out, "### {label}");
+    let _ = writeln!(out);
+}
+
+/// Renders a characteristic as the Markdown body of an RFC issue.
+///
+/// The body contains the characteristic, description, evaluation, and
+/// references sections.
+pub fn render(characteristic: &Characteristic, category: Option<Category>) -> String {
+    let mut out = String::new();
+
+    heading(&mut out, "Characteristic");
+    let _ = writeln!(out, "| Field | Value |");
+    let _ = writeln!(out, "| --- | --- |");
+    let _ = writeln!(
+        out,
+        "| Name | {} |",
+        characteristic.name().unwrap_or(NO_RESPONSE)
+    );
+
+    if let Some(category) = category {
+        let _ = writeln!(out, "| Category | {category} |");
+    }
+
+    if let Some(identifier) = characteristic.identifier() {
+        let _ = writeln!(out, "| Identifier | `{identifier}` |");
+    }
+
+    let _ = writeln!(out, "| State | {} |", characteristic.state());
+    let _ = writeln!(out);
+
+    heading(&mut out, "Description");
+
+    match characteristic.description() {
+        Some(description) => {
+            let _ = writeln!(out, "{}", description.trim());
+        }
+        None => {
+            let _ = writeln!(out, "{NO_RESPONSE}");
+        }
+    }
+
+    let _ = writeln!(out);
+
+    heading(&mut out, "Evaluation");
+
+    match characteristic.values() {
+        Some(kind) => markdown::values(&mut out, kind),
+        None => {
+            let _ = writeln!(out, "{NO_RESPONSE}");
+            let _ = writeln!(out);
+        }
+    }
+
+    heading(&mut out, "References");
+
+    match characteristic.references() {
+        Some(references) => {
+            for r in references {
+                reference(&mut out, r);
+            }
+        }
+        None => {
+            let _ = writeln!(out, "{NO_RESPONSE}");
+        }
+    }
+
+    // Ensure the document ends with exactly one newline.
+    let trimmed = out.trim_end().len();
+    out.truncate(trimmed);
+    out.push('\n');
+
+    out
+}
@@ -0,0 +1,308 @@
+//! Terminal rendering of characteristics.
+
+use std::fmt::Write as _;
+
+use colored::Colorize as _;
+use ecc::Characteristic;
+use ecc::common::Reference;
+use ecc::common::value::Kind;
+
+/// The widest that text is wrapped to (even on wider terminals), as long lines
+/// are hard to read.
+const MAX_WIDTH: usize = 100;
+
+/// The number of spaces that the body of a section is indented by.
+const INDENT: &str = "  ";
+
+/// A target for rendering text to a terminal.
+///
+/// Text is wrapped to the width of the terminal and styled with colors (which
+/// are disabled when the output is not a terminal or `NO_COLOR` is set). Each
+/// method appends to a [`String`] so that the building blocks can be reused to
+/// render anything, not just characteristics (see [`Terminal::render()`]).
+#[derive(Clone, Copy, Debug)]
+pub struct Terminal {
+    /// The width to wrap text to.
+    width: usize,
+}
+
+impl Terminal {
+    /// Creates a target that wraps text to a width.
+    pub fn new(width: usize) -> Self {
+        Self { width }
+    }
+
+    /// Creates a target that wraps text to the width of the terminal (or 80
+    /// columns if it cannot be determined), up to a maximum width.
+    pub fn detect() -> Self {
+        Self::new(textwrap::termwidth().min(MAX_WIDTH))
+    }
+
+    /// Renders a heading.
+    ///
+    /// Every heading except the first is preceded by a blank line.
+    pub fn heading(&self, out: &mut String, text: &str) {
+        // NOTE: writing to a [`String`] cannot fail, so the results are ignored
+        // throughout this module.
+        if !out.is_empty() {
+            let _ = writeln!(out);
+        }
+
+        let _ = writeln!(out, "{}", text.bold().underline());
+    }
+
+    /// Renders a labeled value, with the labels of consecutive fields padded to
+    /// a width.
+    pub fn field(&self, out: &mut String, label: &str, width: usize, value: &str) {
+        let label = format!("{label:<width$}");
+        let _ = writeln!(out, "{INDENT}{}  {value}", label.bold());
+    }
+
+    /// Renders a paragraph wrapped to the width of the terminal.
+    ///
+    /// Blank lines within the text are kept as paragraph breaks.
+    pub fn paragraph(&self, out: &mut String, text: &str, indent: &str) {
+        let options = textwrap::Options::new(self.width)
+            .initial_indent(indent)
+            .subsequent_indent(indent);
+
+        for (i, paragraph) in text.trim().split("\n\n").enumerate() {
+            if i > 0 {
+                let _ = writeln!(out);
+            }
+
+            let paragraph = paragraph.split_whitespace().collect::<Vec<_>>().join(" ");
+
+            for line in textwrap::wrap(&paragraph, &options) {
+                let _ = writeln!(out, "{line}");
+            }
+        }
+    }
+
+    /// Renders a list item, with the marker hanging in front of the wrapped
+    /// text.
+    pub fn item(&self, out: &mut String, marker: &str, text: &str) {
+        let initial = format!("{INDENT}{marker} ");
+        let subsequent = " ".repeat(INDENT.len() + marker.chars().count() + 1);
+        let options = textwrap::Options::new(self.width)
+            .initial_indent(&initial)
+            .subsequent_indent(&subsequent);
+
+        for line in textwrap::wrap(text, &options) {
+            let _ = writeln!(out, "{line}");
+        }
+    }
+
+    /// Renders a table with a header row.
+    ///
+    /// Every column except the last is padded to its widest cell, and the
+    /// last column is wrapped to the remaining width of the terminal.
+    pub fn table(&self, out: &mut String, headers: &[&str], rows: &[Vec<String>]) {
+        let Some(last) = headers.len().checked_sub(1) else {
+            return;
+        };
+
+        let widths = (0..last)
+            .map(|column| {
+                rows.iter()
+                    .map(|row| row[column].chars().count())
+                    .chain([headers[column].len()])
+                    .max()
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<_>>();
+
+        let offset = INDENT.len() + widths.iter().map(|width| width + 2).sum::<usize>();
+        let remaining = self.width.saturating_sub(offset).max(20);
+
+        let cells = |cells: &[String], style: fn(&str) -> String| {
+            let mut line = String::from(INDENT);
+
+            for (cell, width) in cells.iter().zip(&widths) {
+                let _ = write!(line, "{}  ", style(&format!("{cell:<width$}")));
+            }
+
+            line
+        };
+
+        let headers = headers.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+        let _ = writeln!(
+            out,
+            "{}{}",
+            cells(&headers, |cell| cell.bold().to_string()),
+            headers[last].bold()
+        );
+
+        for row in rows {
+            let prefix = cells(row, |cell| cell.to_string());
+            let wrapped = textwrap::wrap(&row[last], remaining);
+            let mut lines = wrapped.iter();
+
+            let _ = writeln!(out, "{prefix}{}", lines.next().map(|l| &**l).unwrap_or(""));
+
+            for line in lines {
+                let _ = writeln!(out, "{}{line}", " ".repeat(offset));
+            }
+        }
+    }
+
+    /// Renders the permissible values of a characteristic.
+    fn values(&self, out: &mut String, kind: &Kind) {
+        match kind {
+            Kind::Binary {
+                description,
+                threshold,
+            } => {
+                if let Some(threshold) = threshold {
+                    self.field(out, "Derived", 7, &threshold.to_string());
+                }
+
+                let rows = [
+                    ("true", &description.r#true),
+                    ("false", &description.r#false),
+                ]
+                .into_iter()
+                .map(|(value, description)| {
+                    vec![
+                        value.to_string(),
+                        format!("{} {}", description.summary, description.details)
+                            .trim()
+                            .to_string(),
+                    ]
+                })
+                .collect::<Vec<_>>();
+
+                self.table(out, &["VALUE", "DESCRIPTION"], &rows);
+            }
+            Kind::Categorical { options } => {
+                for option in options {
+                    self.item(out, "•", option);
+                }
+            }
+            Kind::Numerical { r#type, units } => {
+                self.field(out, "Type", 5, &r#type.to_string());
+                self.field(out, "Units", 5, &units.to_string());
+            }
+            Kind::Fraction {
+                denominator_description,
+                scale,
+                min,
+                max,
+            } => {
+                self.field(out, "Scale", 5, &format!("{scale} ({})", scale.units()));
+                self.field(
+                    out,
+                    "Range",
+                    5,
+                    &format!(
+                        "{} to {}",
+                        min.unwrap_or(0.0),
+                        max.unwrap_or(scale.maximum())
+                    ),
+                );
+                self.paragraph(out, denominator_description, INDENT);
+            }
+            Kind::Immunophenotype { markers } => {
+                for marker in markers {
+                    self.item(out, "•", marker.as_str());
+                }
+            }
+            Kind::Other { description } => self.paragraph(out, description, INDENT),
+        }
+    }
+
+    /// Renders a single reference.
+    ///
+    /// Highlighted references are marked with a star.
+    fn reference(&self, out: &mut String, reference: &Reference) {
+        let (kind, title, authors, context, url, highlighted) = match reference {
+            Reference::Manuscript {
+                title,
+                authors,
+                context,
+                url,
+                highlighted,
+            } => ("Manuscript", title, authors, context, url, highlighted),
+            Reference::Preprint {
+                title,
+                authors,
+                context,
+                url,
+                highlighted,
+            } => ("Preprint", title, authors, context, url, highlighted),
+        };
+
+        let marker = if *highlighted {
+            "★".yellow().to_string()
+        } else {
+            String::from("•")
+        };
+
+        let _ = writeln!(out, "{INDENT}{marker} {}", title.bold());
+
+        let indent = " ".repeat(INDENT.len() + 2);
+        let _ = writeln!(out, "{indent}{}", format!("{kind}; {authors}").dimmed());
+        let _ = writeln!(out, "{indent}{}", url.as_str().cyan().underline());
+        self.paragraph(out, context.as_str(), &indent);
+    }
+
+    /// Renders a characteristic.
+    pub fn render(&self, characteristic: &Characteristic) -> String {
+        let mut out = String::new();
+
+        self.heading(
+            &mut out,
+            characteristic.name().unwrap_or("Unnamed Characteristic"),
+        );
+
+        let mut fields = Vec::new();
+
+        if let Some(identifier) = characteristic.identifier() {
+            fields.push(("Identifier", identifier.to_string()));
+        }
+
+        fields.push(("State", characteristic.state().to_string()));
+
+        if let Some(date) = characteristic.adoption_date() {
+            fields.push(("Adopted", date.date_naive().to_string()));
+        }
+
+        if let Some(rfc) = characteristic.rfc() {
+            fields.push(("RFC", rfc.to_string()));
+        }
+
+        let width = fields
+            .iter()
+            .map(|(label, _)| label.len())
+            .max()
+            .unwrap_or_default();
+
+        for (label, value) in fields {
+            self.field(&mut out, label, width, &value);
+        }
+
+        if let Some(description) = characteristic.description() {
+            self.heading(&mut out, "Description");
+            self.paragraph(&mut out, description, INDENT);
+        }
+
+        if let Some(kind) = characteristic.values() {
+            self.heading(&mut out, &format!("Values ({})", kind.name()));
+            self.values(&mut out, kind);
+        }
+
+        if let Some(references) = characteristic.references() {
+            self.heading(&mut out, "References");
+
+            for (i, r) in references.enumerate() {
+                if i > 0 {
+                    let _ = writeln!(out);
+                }
+
+                self.reference(&mut out, r);
+            }
+        }
+
+        out
+    }
+}
@@ -0,0 +1,360 @@
+//! Generation of review comments for pull requests.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use clap::ValueEnum;
+use ecc::Characteristic;
+use ecc::diff::Change;
+use tracing::info;
+
+use crate::check;
+use crate::config::Config;
+use crate::ecc::diff::describe;
+use crate::git;
+use crate::tree;
+
+/// The marker at the top of every review comment.
+///
+/// This lets a bot find (and update) the comment it posted previously rather
+/// than posting a new comment for every push.
+const MARKER: &str = "<!-- ecc-cli review -->";
+
+/// The format of the review.
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum Format {
+    /// The Markdown body of a GitHub pull request comment.
+    GithubComment,
+}
+
+/// Summarizes the changes to the characteristics since a git revision for
+/// review.
+///
+/// The changes to each characteristic (as reported by `ecc-cli ecc diff`) and
+/// the lint results for each changed file (as reported by `ecc-cli check`) are
+/// combined into a single comment with a collapsible section per file. The
+/// exit code is `0` even when files fail the check so that the comment can
+/// always be posted; use `ecc-cli check` to gate the pull request.
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The git revision to review the changes against (e.g., the base branch
+    /// of the pull request).
+    #[arg(long)]
+    base: String,
+
+    /// The format of the review.
+    #[arg(short, long, value_enum, default_value_t = Format::GithubComment)]
+    format: Format,
+
+    /// The file to write the review to.
+    ///
+    /// Defaults to standard output.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+/// The status of a changed file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Status {
+    /// The file adds a characteristic.
+    Added,
+
+    /// The file modifies a characteristic.
+    Modified,
+
+    /// The file was deleted.
+    Deleted,
+
+    /// The file changed without changing its characteristic (e.g., only the
+    /// formatting changed).
+    Unchanged,
+
+    /// The file could not be parsed as a characteristic.
+    Invalid,
+}
+
+impl Status {
+    /// Gets the status as a string slice.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Status::Added => "added",
+            Status::Modified => "modified",
+            Status::Deleted => "deleted",
+            Status::Unchanged => "unchanged",
+            Status::Invalid => "invalid",
+        }
+    }
+}
+
+/// The review of a single changed file.
+struct Section {
+    /// The path to the file (relative to the directory containing
+    /// `ecc.toml`).
+    path: String,
+
+    /// The status of the file.
+    status: Status,
+
+    /// A description of each change to the characteristic within the file.
+    changes: Vec<String>,
+
+    /// The problems found within the file.
+    diagnostics: Vec<ecc_check::Diagnostic>,
+}
+
+impl Section {
+    /// Gets the number of errors within the file.
+    fn errors(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|d| d.severity == ecc_check::Severity::Error)
+            .count()
+    }
+
+    /// Gets the number of warnings within the file.
+    fn warnings(&self) -> usize {
+        self.diagnostics.len() - self.errors()
+    }
+}
+
+/// Formats a count of things (e.g., `1 error` or `2 errors`).
+fn count(n: usize, noun: &str) -> String {
+    match n {
+        1 => format!("1 {noun}"),
+        n => format!("{n} {noun}s"),
+    }
+}
+
+/// Gets a short Markdown label for a characteristic.
+fn label(characteristic: &Characteristic) -> String {
+    let identifier = characteristic
+        .identifier()
+        .map(|identifier| format!("`{identifier}`"))
+        .unwrap_or_else(|| String::from("*unassigned*"));
+    let name = characteristic.name().unwrap_or("*unnamed*");
+
+    format!("{identifier} {name}")
+}
+
+/// Describes a change to a characteristic as a Markdown list item.
+fn describe_change(change: &Change<'_>) -> String {
+    match change {
+        Change::Added(characteristic) => format!("- Added {}", label(characteristic)),
+        Change::Removed(characteristic) => format!("- Removed {}", label(characteristic)),
+        Change::Modified {
+            after,
+            modifications,
+            ..
+        } => {
+            let mut description = format!("- Modified {}", label(after));
+
+            for modification in modifications {
+                let _ = write!(description, "\n  - {}", describe(modification));
+            }
+
+            description
+        }
+    }
+}
+
+/// Gets the position of a characteristic within a slice by identity.
+fn position(characteristics: &[Characteristic], characteristic: &Characteristic) -> Option<usize> {
+    characteristics
+        .iter()
+        .position(|c| std::ptr::eq(c, characteristic))
+}
+
+/// Reviews each changed characteristic file.
+fn review(directory: &Path, base: &str, config: &Config) -> anyhow::Result<Vec<Section>> {
+    let files = git::changed(directory, base)?
+        .into_iter()
+        .filter(|file| tree::is_yaml(file))
+        .collect::<Vec<_>>();
+
+    // NOTE: files that cannot be parsed are reported by the lints rather than
+    // failing the review.
+    let (before_paths, before) = git::load_valid_at(directory, base)?
+        .into_iter()
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+    let (after_paths, after) = tree::stream(directory)?
+        .filter_map(|(path, result)| result.ok().map(|characteristic| (path, characteristic)))
+        .unzip::<_, _, Vec<_>, Vec<_>>();
+
+    let changes = ecc::diff::diff(&before, &after);
+    let mut sections = Vec::new();
+
+    for file in files {
+        let path = tree::relative(&file, config.root());
+
+        if !file.exists() {
+            let suffix = file.strip_prefix(directory).unwrap_or(&file);
+            let changes = changes
+                .iter()
+                .filter(|change| match change {
+                    Change::Removed(characteristic) => position(&before, characteristic)
+                        .is_some_and(|i| before_paths[i].ends_with(suffix)),
+                    _ => false,
+                })
+                .map(describe_change)
+                .collect();
+
+            sections.push(Section {
+                path,
+                status: Status::Deleted,
+                changes,
+                diagnostics: Vec::new(),
+            });
+            continue;
+        }
+
+        let report = check::lint(&file, tree::read(&file)?, false, config)?;
+        let mut status = match report.characteristic {
+            Some(_) => Status::Unchanged,
+            None => Status::Invalid,
+        };
+
+        let changes = changes
+            .iter()
+            .filter(|change| {
+                let (Change::Added(characteristic)
+                | Change::Modified {
+                    after: characteristic,
+                    ..
+                }) = change
+                else {
+                    return false;
+                };
+
+                position(&after, characteristic).is_some_and(|i| after_paths[i] == file)
+            })
+            .inspect(|change| {
+                status = match change {
+                    Change::Added(_) => Status::Added,
+                    _ => Status::Modified,
+                }
+            })
+            .map(describe_change)
+            .collect();
+
+        sections.push(Section {
+            path,
+            status,
+            changes,
+            diagnostics: report.diagnostics,
+        });
+    }
+
+    Ok(sections)
+}
+
+/// Renders the review as the Markdown body of a GitHub comment.
+fn github_comment(sections: &[Section], base: &str) -> String {
+    let mut body = format!("{MARKER}\n### ECC review\n\n");
+
+    if sections.is_empty() {
+        let _ = writeln!(body, "No characteristic files changed since `{base}`.");
+        return body;
+    }
+
+    let tally = |status| sections.iter().filter(|s| s.status == status).count();
+    let errors = sections.iter().map(Section::errors).sum::<usize>();
+    let warnings = sections.iter().map(Section::warnings).sum::<usize>();
+
+    let _ = writeln!(
+        body,
+        "Reviewed {} against `{base}`.\n\n| Added | Modified | Deleted | Invalid | Errors | \
+         Warnings |\n| ---: | ---: | ---: | ---: | ---: | ---: |\n| {} | {} | {} | {} | {errors} \
+         | {warnings} |",
+        count(sections.len(), "changed file"),
+        tally(Status::Added),
+        tally(Status::Modified),
+        tally(Status::Deleted),
+        tally(Status::Invalid),
+    );
+
+    for section in sections {
+        let errors = section.errors();
+
+        let _ = writeln!(
+            body,
+            "\n<details{}>\n<summary><code>{}</code> ({}; {}, {})</summary>\n",
+            if errors > 0 { " open" } else { "" },
+            section.path,
+            section.status.as_str(),
+            count(errors, "error"),
+            count(section.warnings(), "warning"),
+        );
+
+        match (section.status, section.changes.is_empty()) {
+            (Status::Unchanged, _) => {
+                let _ = writeln!(body, "The characteristic itself is unchanged.\n");
+            }
+            (_, false) => {
+                let _ = writeln!(body, "#### Changes\n\n{}\n", section.changes.join("\n"));
+            }
+            (_, true) => {}
+        }
+
+        if !section.diagnostics.is_empty() {
+            let _ = writeln!(body, "#### Problems\n");
+
+            for diagnostic in &section.diagnostics {
+                let severity = match diagnostic.severity {
+                    ecc_check::Severity::Error => "Error",
+                    ecc_check::Severity::Warning => "Warning",
+                };
+
+                let _ = write!(body, "- **{severity}**");
+
+                if let Some(path) = &diagnostic.path {
+                    let _ = write!(body, " at `{path}`");
+                }
+
+                let _ = write!(body, ": {}", diagnostic.message.replace('\n', " "));
+
+                if let Some(lint) = diagnostic.lint {
+                    let _ = write!(body, " (`{lint}`)");
+                }
+
+                body.push('\n');
+            }
+
+            body.push('\n');
+        } else if section.status != Status::Deleted {
+            let _ = writeln!(body, "No problems found.\n");
+        }
+
+        let _ = writeln!(body, "</details>");
+    }
+
+    body
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let directory = config.characteristics(args.path);
+    let sections = review(&directory, &args.base, config)?;
+
+    let body = match args.format {
+        Format::GithubComment => github_comment(&sections, &args.base),
+    };
+
+    match &args.output {
+        Some(output) => {
+            std::fs::write(output, body)
+                .with_context(|| format!("writing file: {}", output.display()))?;
+            info!("wrote review to `{}`", output.display());
+        }
+        None => print!("{body}"),
+    }
+
+    Ok(())
+}
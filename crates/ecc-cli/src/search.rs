@@ -0,0 +1,403 @@
+//! A compact search index over the characteristic and ontology trees.
+//!
+//! The index is built once (by `ecc-cli build` and at `ecc-cli serve` startup)
+//! and serialized as JSON so that the published site can search the bundle
+//! without a server. Documents are characteristics and ontology nodes. Each
+//! document is tokenized into lowercase alphanumeric terms, and each term maps
+//! to a list of `[document, weight]` postings where the weight reflects the
+//! field the term was found in:
+//!
+//! * identifiers and codes (the "aliases" of a document) weigh the most,
+//! * then names,
+//! * then descriptions and permissible values.
+//!
+//! A document matches a query when every query term is a prefix of one of its
+//! terms (so that results update as the user types). The score of a match is
+//! the sum of the best weight for each query term multiplied by the boost of
+//! the document, which prefers characteristics further along in the adoption
+//! process.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use ecc::Characteristic;
+use ecc::common::value::Kind;
+use ontology::Node;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The weight of a term found within an identifier or code.
+const ALIAS_WEIGHT: u16 = 8;
+
+/// The weight of a term found within a name.
+const NAME_WEIGHT: u16 = 4;
+
+/// The weight of a term found within a description or permissible value.
+const TEXT_WEIGHT: u16 = 1;
+
+/// The kind of a document within the index.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocumentKind {
+    /// A characteristic.
+    Characteristic,
+
+    /// An ontology node.
+    Node,
+}
+
+/// A document within the index.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Document {
+    /// The kind of the document.
+    pub kind: DocumentKind,
+
+    /// The position of the document within the characteristics or nodes the
+    /// index was built from.
+    pub position: usize,
+
+    /// The key of the document (the identifier of a characteristic, falling
+    /// back to its name for drafts, or the code of a node).
+    pub key: String,
+
+    /// The title of the document.
+    pub title: String,
+
+    /// The state of the characteristic (if the document is a characteristic).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+
+    /// The multiplier applied to the score of the document.
+    pub boost: f32,
+}
+
+/// A ranked match for a query.
+#[derive(Clone, Debug)]
+pub struct Hit<'a> {
+    /// The matched document.
+    pub document: &'a Document,
+
+    /// The score of the match.
+    pub score: f32,
+}
+
+/// A search index.
+#[derive(Debug, Default, Deserialize, Serialize)]
+pub struct Index {
+    /// The documents.
+    documents: Vec<Document>,
+
+    /// The postings for each term as `(document, weight)` pairs.
+    terms: BTreeMap<String, Vec<(u32, u16)>>,
+}
+
+/// Splits text into lowercase alphanumeric terms.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(str::to_lowercase)
+}
+
+/// Gets the boost of a characteristic's document.
+///
+/// Adopted characteristics are preferred over provisional ones, which are
+/// preferred over proposed ones and drafts.
+fn boost(characteristic: &Characteristic) -> f32 {
+    match characteristic {
+        Characteristic::Adopted { .. } => 2.0,
+        Characteristic::Provisional { .. } => 1.5,
+        Characteristic::Proposed { .. } => 1.2,
+        Characteristic::Draft { .. } => 1.0,
+    }
+}
+
+impl Index {
+    /// Builds an index over a set of characteristics and ontology nodes.
+    pub fn build(characteristics: &[Characteristic], nodes: &[Node]) -> Self {
+        let mut index = Self::default();
+
+        for (position, characteristic) in characteristics.iter().enumerate() {
+            let identifier = characteristic.identifier().map(|i| i.to_string());
+            let name = characteristic.name().unwrap_or_default();
+
+            let document = index.push(Document {
+                kind: DocumentKind::Characteristic,
+                position,
+                key: identifier.clone().unwrap_or_else(|| name.to_string()),
+                title: name.to_string(),
+                state: Some(characteristic.state().to_string()),
+                boost: boost(characteristic),
+            });
+
+            if let Some(identifier) = identifier {
+                index.add(document, &identifier, ALIAS_WEIGHT);
+            }
+
+            index.add(document, name, NAME_WEIGHT);
+            index.add(
+                document,
+                characteristic.description().unwrap_or_default(),
+                TEXT_WEIGHT,
+            );
+
+            if let Some(Kind::Categorical { options }) = characteristic.values() {
+                for option in options {
+                    index.add(document, option, TEXT_WEIGHT);
+                }
+            }
+        }
+
+        for (position, node) in nodes.iter().enumerate() {
+            let document = index.push(Document {
+                kind: DocumentKind::Node,
+                position,
+                key: node.code().to_string(),
+                title: node.name().inner().to_string(),
+                state: None,
+                boost: 1.0,
+            });
+
+            index.add(document, node.code(), ALIAS_WEIGHT);
+            index.add(document, node.name().inner(), NAME_WEIGHT);
+        }
+
+        index
+    }
+
+    /// Adds a document and returns its index.
+    fn push(&mut self, document: Document) -> u32 {
+        self.documents.push(document);
+        // SAFETY: the registry is nowhere near `u32::MAX` documents.
+        u32::try_from(self.documents.len() - 1).unwrap()
+    }
+
+    /// Adds the terms within some text to a document.
+    fn add(&mut self, document: u32, text: &str, weight: u16) {
+        for term in tokenize(text) {
+            self.add_term(document, term, weight);
+        }
+    }
+
+    /// Adds a single term to a document.
+    ///
+    /// Only the highest weight is kept when a document contains a term more
+    /// than once.
+    fn add_term(&mut self, document: u32, term: String, weight: u16) {
+        let postings = self.terms.entry(term).or_default();
+
+        match postings.last_mut() {
+            Some((last, existing)) if *last == document => *existing = (*existing).max(weight),
+            _ => postings.push((document, weight)),
+        }
+    }
+
+    /// Gets the documents within the index.
+    pub fn documents(&self) -> &[Document] {
+        &self.documents
+    }
+
+    /// Searches the index.
+    ///
+    /// Only the documents for which `keep` returns `true` are considered.
+    /// Results are ordered from the best match to the worst (ties are broken
+    /// by title). At most `limit` results are returned.
+    pub fn search(
+        &self,
+        query: &str,
+        limit: usize,
+        keep: impl Fn(&Document) -> bool,
+    ) -> Vec<Hit<'_>> {
+        let terms = tokenize(query).collect::<Vec<_>>();
+
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<u32, (usize, u16)> = HashMap::new();
+
+        for (i, term) in terms.iter().enumerate() {
+            let mut best: HashMap<u32, u16> = HashMap::new();
+
+            for (_, postings) in self
+                .terms
+                .range(term.clone()..)
+                .take_while(|(candidate, _)| candidate.starts_with(term.as_str()))
+            {
+                for &(document, weight) in postings {
+                    let entry = best.entry(document).or_default();
+                    *entry = (*entry).max(weight);
+                }
+            }
+
+            for (document, weight) in best {
+                // NOTE: a document only stays in the running if it matched
+                // every previous query term.
+                if let Some((matched, total)) = scores.get_mut(&document) {
+                    if *matched == i {
+                        *matched += 1;
+                        *total += weight;
+                    }
+                } else if i == 0 {
+                    scores.insert(document, (1, weight));
+                }
+            }
+        }
+
+        let mut hits = scores
+            .into_iter()
+            .filter(|(_, (matched, _))| *matched == terms.len())
+            .map(|(document, (_, total))| {
+                let document = &self.documents[document as usize];
+                Hit {
+                    document,
+                    score: f32::from(total) * document.boost,
+                }
+            })
+            .filter(|hit| keep(hit.document))
+            .collect::<Vec<_>>();
+
+        hits.sort_by(|a, b| {
+            b.score
+                .total_cmp(&a.score)
+                .then_with(|| a.document.title.cmp(&b.document.title))
+        });
+        hits.truncate(limit);
+        hits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ecc::common::Common;
+    use test_infra::fixtures;
+
+    use super::*;
+
+    /// Gets the common elements of a characteristic with an identifier and a
+    /// name.
+    fn common(identifier: &str, name: &str) -> Common {
+        let mut common = fixtures::common();
+        common.set_identifier(fixtures::identifier(identifier));
+        common.set_name(name).unwrap();
+        common
+    }
+
+    /// Gets the keys of the documents hit by a query.
+    fn keys(index: &Index, query: &str, limit: usize) -> Vec<String> {
+        index
+            .search(query, limit, |_| true)
+            .into_iter()
+            .map(|hit| hit.document.key.clone())
+            .collect()
+    }
+
+    #[test]
+    fn prefixes() {
+        let index = Index::build(
+            &[Characteristic::Proposed {
+                common: common("ECC-MORPH-000001", "Blast Percentage"),
+            }],
+            &[],
+        );
+
+        assert_eq!(keys(&index, "bla", 10), ["ECC-MORPH-000001"]);
+        assert_eq!(keys(&index, "PERCENT", 10), ["ECC-MORPH-000001"]);
+        assert_eq!(keys(&index, "ecc-morph-000001", 10), ["ECC-MORPH-000001"]);
+
+        // NOTE: query terms only match the start of a term.
+        assert!(keys(&index, "last", 10).is_empty());
+        assert!(keys(&index, "", 10).is_empty());
+    }
+
+    #[test]
+    fn every_term() {
+        let index = Index::build(
+            &[
+                Characteristic::Proposed {
+                    common: common("ECC-MORPH-000001", "Blast Percentage"),
+                },
+                Characteristic::Proposed {
+                    common: common("ECC-MORPH-000002", "Blast Morphology"),
+                },
+            ],
+            &[],
+        );
+
+        assert_eq!(keys(&index, "blast", 10).len(), 2);
+        assert_eq!(keys(&index, "blast perc", 10), ["ECC-MORPH-000001"]);
+        assert_eq!(keys(&index, "morphology blast", 10), ["ECC-MORPH-000002"]);
+        assert!(keys(&index, "blast fusion", 10).is_empty());
+    }
+
+    #[test]
+    fn nodes() {
+        let index = Index::build(
+            &[fixtures::adopted_characteristic()],
+            &fixtures::small_ontology_nodes(),
+        );
+
+        let hits = index.search("t-cell", 10, |_| true);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].document.kind, DocumentKind::Node);
+        assert_eq!(hits[0].document.key, "TLL");
+        assert_eq!(hits[0].document.title, "T-cell Lymphoblastic Leukemia");
+        assert_eq!(hits[0].document.position, 4);
+
+        // NOTE: a code is weighed more heavily than a name.
+        assert_eq!(keys(&index, "bll", 10), ["BLL"]);
+        assert_eq!(keys(&index, "leuk", 10)[0], "LEUK");
+    }
+
+    #[test]
+    fn limit() {
+        let index = Index::build(&[], &fixtures::small_ontology_nodes());
+
+        assert_eq!(keys(&index, "leukemia", 10).len(), 5);
+        assert_eq!(keys(&index, "leukemia", 2).len(), 2);
+        assert!(keys(&index, "leukemia", 0).is_empty());
+
+        // NOTE: documents that are not kept do not count towards the limit.
+        assert_eq!(
+            index
+                .search("leukemia", 2, |document| document.key.starts_with('B'))
+                .len(),
+            2
+        );
+    }
+
+    #[test]
+    fn prefers_adopted() {
+        let index = Index::build(
+            &[
+                Characteristic::Draft {
+                    common: common("ECC-MORPH-000001", "Draft Marker").into(),
+                },
+                Characteristic::Proposed {
+                    common: common("ECC-MORPH-000002", "Proposed Marker"),
+                },
+                Characteristic::Provisional {
+                    common: common("ECC-MORPH-000003", "Provisional Marker"),
+                },
+                fixtures::adopted_characteristic_with(|common| {
+                    common.set_identifier(fixtures::identifier("ECC-MORPH-000004"));
+                    common.set_name("Adopted Marker").unwrap();
+                }),
+            ],
+            &[],
+        );
+
+        assert_eq!(
+            keys(&index, "marker", 10),
+            [
+                "ECC-MORPH-000004",
+                "ECC-MORPH-000003",
+                "ECC-MORPH-000002",
+                "ECC-MORPH-000001"
+            ]
+        );
+
+        let hits = index.search("marker", 10, |_| true);
+        assert_eq!(hits[0].document.state.as_deref(), Some("adopted"));
+        assert_eq!(hits[0].score, f32::from(NAME_WEIGHT) * 2.0);
+    }
+}
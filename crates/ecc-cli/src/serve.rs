@@ -0,0 +1,452 @@
+//! A local, read-only HTTP API over the characteristic and ontology trees.
+
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context;
+use axum::Json;
+use axum::Router;
+use axum::extract::Path;
+use axum::extract::Query;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::routing::get;
+use clap::Parser;
+use ecc::Characteristic;
+use ecc::Identifier;
+use ecc::common::Sensitivity;
+use ecc::query::Filter;
+use ontology::Node;
+use serde::Deserialize;
+use serde::Serialize;
+use tracing::info;
+
+use crate::config::Config;
+use crate::search::DocumentKind;
+use crate::search::Index;
+use crate::tree;
+
+/// Serves a read-only HTTP API over the characteristic and ontology trees.
+///
+/// The trees are loaded once at startup. The following endpoints are exposed:
+///
+/// * `GET /characteristics?filter=...`
+/// * `GET /characteristics/{identifier}`
+/// * `GET /ontology/nodes/{code}`
+/// * `GET /search?q=...&limit=...&filter=...`
+///
+/// The optional `filter` selects characteristics using the same syntax as
+/// `ecc list --filter` (e.g., `state:adopted category:molec`).
+#[derive(Parser)]
+pub struct Args {
+    /// The path to the composable characteristic directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml`.
+    path: Option<PathBuf>,
+
+    /// The path to the ontology directory.
+    ///
+    /// Defaults to the directory configured in `ecc.toml` (if any).
+    #[arg(long)]
+    ontology: Option<PathBuf>,
+
+    /// The most restricted sensitivity to serve (`public`, `controlled`, or
+    /// `restricted`).
+    ///
+    /// Characteristics that are more restricted are left out of every
+    /// response. Defaults to the sensitivity configured in `ecc.toml` (or
+    /// `public`).
+    #[arg(long)]
+    max_sensitivity: Option<Sensitivity>,
+
+    /// The address to listen on.
+    #[arg(long, default_value = "127.0.0.1:8080")]
+    address: SocketAddr,
+}
+
+/// The data served by the API.
+struct Data {
+    /// The characteristics.
+    characteristics: Vec<Characteristic>,
+
+    /// The ontology nodes.
+    nodes: Vec<Node>,
+
+    /// The search index over the characteristics and ontology nodes.
+    index: Index,
+}
+
+/// The shared state of the server.
+type Shared = Arc<Data>;
+
+/// An error response.
+type ErrorResponse = (StatusCode, String);
+
+/// The query parameters for listing characteristics.
+#[derive(Deserialize)]
+struct ListParams {
+    /// The filter to apply.
+    filter: Option<String>,
+}
+
+/// Parses the filter within the query parameters (if any).
+fn filter(filter: Option<&str>) -> Result<Filter, ErrorResponse> {
+    filter
+        .map(str::parse)
+        .transpose()
+        .map(Option::unwrap_or_default)
+        .map_err(|err: ecc::query::ParseError| (StatusCode::BAD_REQUEST, err.to_string()))
+}
+
+/// Lists every characteristic matching the filter.
+async fn characteristics(
+    State(data): State<Shared>,
+    Query(params): Query<ListParams>,
+) -> Result<Json<Vec<Characteristic>>, ErrorResponse> {
+    let filter = filter(params.filter.as_deref())?;
+
+    Ok(Json(
+        data.characteristics
+            .iter()
+            .filter(|characteristic| filter.matches(characteristic))
+            .cloned()
+            .collect(),
+    ))
+}
+
+/// Gets a single characteristic by its identifier.
+async fn characteristic(
+    State(data): State<Shared>,
+    Path(identifier): Path<String>,
+) -> Result<Json<Characteristic>, ErrorResponse> {
+    let identifier = identifier
+        .parse::<Identifier>()
+        .map_err(|err| (StatusCode::BAD_REQUEST, err.to_string()))?;
+
+    data.characteristics
+        .iter()
+        .find(|c| c.identifier() == Some(&identifier))
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| {
+            (
+                StatusCode::NOT_FOUND,
+                format!("characteristic not found: {identifier}"),
+            )
+        })
+}
+
+/// Gets a single ontology node by its short code.
+async fn node(
+    State(data): State<Shared>,
+    Path(code): Path<String>,
+) -> Result<Json<Node>, ErrorResponse> {
+    data.nodes
+        .iter()
+        .find(|node| node.code() == code)
+        .cloned()
+        .map(Json)
+        .ok_or_else(|| (StatusCode::NOT_FOUND, format!("node not found: {code}")))
+}
+
+/// The query parameters for a search.
+#[derive(Deserialize)]
+struct SearchParams {
+    /// The search query.
+    q: String,
+
+    /// The maximum number of results.
+    #[serde(default = "default_limit")]
+    limit: usize,
+
+    /// The filter applied to the matching characteristics.
+    filter: Option<String>,
+}
+
+/// Gets the default maximum number of search results.
+fn default_limit() -> usize {
+    20
+}
+
+/// The results of a search.
+#[derive(Serialize)]
+struct SearchResults {
+    /// The matching characteristics (best match first).
+    characteristics: Vec<Characteristic>,
+
+    /// The matching ontology nodes (best match first).
+    nodes: Vec<Node>,
+}
+
+/// Searches the characteristics and ontology nodes.
+///
+/// See [`crate::search`] for how results are matched and ranked. When a
+/// filter is provided, characteristics that do not match it are left out (the
+/// ontology nodes are unaffected).
+async fn search(
+    State(data): State<Shared>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<SearchResults>, ErrorResponse> {
+    let filter = filter(params.filter.as_deref())?;
+
+    let mut results = SearchResults {
+        characteristics: Vec::new(),
+        nodes: Vec::new(),
+    };
+
+    // NOTE: the filter is applied within the search so that characteristics
+    // that do not match it do not count towards the limit.
+    let hits = data.index.search(&params.q, params.limit, |document| {
+        document.kind == DocumentKind::Node
+            || filter.matches(&data.characteristics[document.position])
+    });
+
+    for hit in hits {
+        match hit.document.kind {
+            DocumentKind::Characteristic => results
+                .characteristics
+                .push(data.characteristics[hit.document.position].clone()),
+            DocumentKind::Node => results
+                .nodes
+                .push(data.nodes[hit.document.position].clone()),
+        }
+    }
+
+    Ok(Json(results))
+}
+
+/// Builds the router for the API.
+fn router(data: Data) -> Router {
+    Router::new()
+        .route("/characteristics", get(characteristics))
+        .route("/characteristics/{identifier}", get(characteristic))
+        .route("/ontology/nodes/{code}", get(node))
+        .route("/search", get(search))
+        .with_state(Arc::new(data))
+}
+
+/// Loads the data served by the API.
+fn load(args: &Args, config: &Config) -> anyhow::Result<Data> {
+    let mut characteristics = tree::load(&config.characteristics(args.path.clone()))?;
+    tree::withhold(
+        &mut characteristics,
+        args.max_sensitivity
+            .unwrap_or(config.export.max_sensitivity),
+    );
+    let characteristics = characteristics
+        .into_iter()
+        .map(|(_, c)| c)
+        .collect::<Vec<_>>();
+    let nodes = match config.ontology(args.ontology.clone()) {
+        Some(path) => tree::load_ontology(&path)?
+            .into_iter()
+            .map(|(_, node)| node)
+            .collect(),
+        None => Vec::new(),
+    };
+
+    info!(
+        "loaded {} characteristics and {} ontology nodes",
+        characteristics.len(),
+        nodes.len()
+    );
+
+    let index = Index::build(&characteristics, &nodes);
+
+    Ok(Data {
+        characteristics,
+        nodes,
+        index,
+    })
+}
+
+/// The main method.
+pub fn main(args: Args, config: &Config) -> anyhow::Result<()> {
+    let app = router(load(&args, config)?);
+
+    tokio::runtime::Runtime::new()
+        .context("starting the async runtime")?
+        .block_on(async {
+            let listener = tokio::net::TcpListener::bind(args.address)
+                .await
+                .with_context(|| format!("binding to {}", args.address))?;
+            info!("listening on http://{}", args.address);
+            axum::serve(listener, app).await.context("serving the API")
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::body::Body;
+    use axum::http::Request;
+    use http_body_util::BodyExt as _;
+    use test_infra::fixtures;
+    use test_infra::tree::Tree;
+    use tower::ServiceExt as _;
+
+    use super::*;
+
+    /// Gets a tree with a public and a restricted characteristic.
+    fn tree() -> Tree {
+        Tree::new()
+            .with_characteristics([
+                fixtures::adopted_characteristic(),
+                fixtures::adopted_characteristic_with(|common| {
+                    common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+                    common.set_name("A Restricted Characteristic").unwrap();
+                    common.set_sensitivity(Sensitivity::Restricted);
+                }),
+            ])
+            .with_ontology(&fixtures::small_ontology())
+    }
+
+    /// Builds the router for a tree.
+    fn serve(tree: &Tree, args: &[&str]) -> Router {
+        let args = Args::parse_from(std::iter::once("serve").chain(args.iter().copied()));
+        let config = Config::load(Some(&tree.config())).unwrap();
+        router(load(&args, &config).unwrap())
+    }
+
+    /// Sends a `GET` request and returns the status and body.
+    async fn get(app: &Router, uri: &str) -> (StatusCode, String) {
+        let response = app
+            .clone()
+            .oneshot(Request::get(uri).body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    /// Gets the identifiers of the characteristics within a response.
+    fn identifiers(characteristics: &serde_json::Value) -> Vec<&str> {
+        characteristics
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|characteristic| characteristic["identifier"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn routes() {
+        let app = serve(&tree(), &[]);
+
+        let (status, body) = get(&app, "/characteristics/ECC-MORPH-000001").await;
+        assert_eq!(status, StatusCode::OK);
+        let characteristic = serde_json::from_str::<Characteristic>(&body).unwrap();
+        assert_eq!(characteristic, fixtures::adopted_characteristic());
+
+        let (status, body) = get(&app, "/ontology/nodes/BLL").await;
+        assert_eq!(status, StatusCode::OK);
+        let node = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(node["name"], "B-cell Lymphoblastic Leukemia");
+
+        let (status, body) = get(&app, "/search?q=lymphoblastic%20leuk&limit=2").await;
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert!(identifiers(&body["characteristics"]).is_empty());
+        assert_eq!(body["nodes"].as_array().unwrap().len(), 2);
+
+        let (status, body) = get(&app, "/search?q=characteristic&filter=state:adopted").await;
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(identifiers(&body["characteristics"]), ["ECC-MORPH-000001"]);
+    }
+
+    #[tokio::test]
+    async fn errors() {
+        let app = serve(&tree(), &[]);
+
+        assert_eq!(
+            get(&app, "/characteristics/ECC-MORPH-999999").await,
+            (
+                StatusCode::NOT_FOUND,
+                String::from("characteristic not found: ECC-MORPH-999999")
+            )
+        );
+        assert_eq!(
+            get(&app, "/ontology/nodes/MISSING").await,
+            (
+                StatusCode::NOT_FOUND,
+                String::from("node not found: MISSING")
+            )
+        );
+        assert_eq!(
+            get(&app, "/characteristics/foo").await.0,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            get(&app, "/characteristics?filter=color:red").await.0,
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(get(&app, "/search").await.0, StatusCode::BAD_REQUEST);
+        assert_eq!(get(&app, "/nodes").await.0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn withholds_restricted() {
+        let app = serve(&tree(), &[]);
+
+        let (status, body) = get(&app, "/characteristics").await;
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(identifiers(&body), ["ECC-MORPH-000001"]);
+
+        let (status, _) = get(&app, "/characteristics/ECC-MORPH-000002").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+
+        let (status, body) = get(&app, "/search?q=characteristic").await;
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(identifiers(&body["characteristics"]), ["ECC-MORPH-000001"]);
+
+        let app = serve(&tree(), &["--max-sensitivity", "restricted"]);
+        let (status, _) = get(&app, "/characteristics/ECC-MORPH-000002").await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn filters_before_limiting() {
+        let mut characteristics = Vec::new();
+
+        for (number, name) in [(1, "First"), (2, "Second"), (3, "Third")] {
+            characteristics.push(fixtures::adopted_characteristic_with(|common| {
+                common.set_identifier(fixtures::identifier(&format!("ECC-MORPH-00000{number}")));
+                common
+                    .set_name(format!("{name} Adopted Characteristic"))
+                    .unwrap();
+            }));
+        }
+
+        for (number, name) in [(4, "First"), (5, "Second")] {
+            characteristics.push(fixtures::proposed_characteristic_with(|common| {
+                common.set_identifier(fixtures::identifier(&format!("ECC-MORPH-00000{number}")));
+                common
+                    .set_name(format!("{name} Proposed Characteristic"))
+                    .unwrap();
+            }));
+        }
+
+        let tree = Tree::new()
+            .with_characteristics(characteristics)
+            .with_ontology(&fixtures::small_ontology());
+        let app = serve(&tree, &[]);
+
+        // NOTE: every adopted characteristic outranks the proposed ones, so
+        // they fill the limit unless the filter is applied first.
+        let (status, body) = get(
+            &app,
+            "/search?q=characteristic&filter=state:proposed&limit=2",
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        let body = serde_json::from_str::<serde_json::Value>(&body).unwrap();
+        assert_eq!(
+            identifiers(&body["characteristics"]),
+            ["ECC-MORPH-000004", "ECC-MORPH-000005"]
+        );
+    }
+}
@@ -0,0 +1,167 @@
+//! Detached Ed25519 signatures over bundle manifests.
+//!
+//! A signature is written next to the manifest it covers (with the
+//! [`SIGNATURE_EXTENSION`] appended) as the hex-encoded 64-byte Ed25519
+//! signature. Keys are read from PEM files, which can be generated with
+//! OpenSSL:
+//!
+//! ```bash
+//! openssl genpkey -algorithm ed25519 -out key.pem
+//! openssl pkey -in key.pem -pubout -out key.pub.pem
+//! ```
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use anyhow::bail;
+use ed25519_dalek::Signature;
+use ed25519_dalek::Signer as _;
+use ed25519_dalek::SigningKey;
+use ed25519_dalek::Verifier as _;
+use ed25519_dalek::VerifyingKey;
+use ed25519_dalek::pkcs8::DecodePrivateKey as _;
+use ed25519_dalek::pkcs8::DecodePublicKey as _;
+
+/// The extension appended to a file to get the path of its signature.
+pub const SIGNATURE_EXTENSION: &str = "sig";
+
+/// Gets the path of the signature for a file.
+pub fn signature_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(SIGNATURE_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Reads a PKCS#8 private key from a PEM file.
+pub fn signing_key(path: &Path) -> anyhow::Result<SigningKey> {
+    let pem = std::fs::read_to_string(path)
+        .with_context(|| format!("reading private key: {}", path.display()))?;
+    SigningKey::from_pkcs8_pem(&pem)
+        .map_err(|err| anyhow::anyhow!("{err}"))
+        .with_context(|| format!("parsing private key: {}", path.display()))
+}
+
+/// Reads a public key from a PEM file.
+pub fn verifying_key(path: &Path) -> anyhow::Result<VerifyingKey> {
+    let pem = std::fs::read_to_string(path)
+        .with_context(|| format!("reading public key: {}", path.display()))?;
+    VerifyingKey::from_public_key_pem(&pem)
+        .map_err(|err| anyhow::anyhow!("{err}"))
+        .with_context(|| format!("parsing public key: {}", path.display()))
+}
+
+/// Signs a file and writes the detached signature next to it.
+///
+/// Returns the path of the signature.
+pub fn sign(path: &Path, key: &SigningKey) -> anyhow::Result<PathBuf> {
+    let contents =
+        std::fs::read(path).with_context(|| format!("reading file: {}", path.display()))?;
+
+    let signature = key
+        .sign(&contents)
+        .to_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect::<String>();
+
+    let signature_path = signature_path(path);
+    std::fs::write(&signature_path, format!("{signature}\n"))
+        .with_context(|| format!("writing signature: {}", signature_path.display()))?;
+
+    Ok(signature_path)
+}
+
+/// Decodes a hex-encoded signature.
+fn decode(hex: &str) -> anyhow::Result<Signature> {
+    let hex = hex.trim();
+
+    if hex.len() != Signature::BYTE_SIZE * 2 || !hex.is_ascii() {
+        bail!("expected {} hex-encoded bytes", Signature::BYTE_SIZE);
+    }
+
+    let mut bytes = [0; Signature::BYTE_SIZE];
+
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+            .with_context(|| format!("invalid hex at offset {}", i * 2))?;
+    }
+
+    Ok(Signature::from_bytes(&bytes))
+}
+
+/// Verifies the detached signature of a file.
+///
+/// Fails if the signature is missing, malformed, or does not match.
+pub fn verify(path: &Path, key: &VerifyingKey) -> anyhow::Result<()> {
+    let contents =
+        std::fs::read(path).with_context(|| format!("reading file: {}", path.display()))?;
+
+    let signature_path = signature_path(path);
+    let signature = std::fs::read_to_string(&signature_path)
+        .with_context(|| format!("reading signature: {}", signature_path.display()))?;
+    let signature = decode(&signature)
+        .with_context(|| format!("parsing signature: {}", signature_path.display()))?;
+
+    key.verify(&contents, &signature)
+        .map_err(|_| anyhow::anyhow!("signature does not match: {}", signature_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use ed25519_dalek::pkcs8::EncodePrivateKey as _;
+    use ed25519_dalek::pkcs8::EncodePublicKey as _;
+    use ed25519_dalek::pkcs8::spki::der::pem::LineEnding;
+
+    use super::*;
+
+    /// Gets a deterministic signing key.
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    #[test]
+    fn round_trip() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let private = directory.path().join("key.pem");
+        std::fs::write(&private, key(1).to_pkcs8_pem(LineEnding::LF).unwrap()).unwrap();
+        let public = directory.path().join("key.pub.pem");
+        std::fs::write(
+            &public,
+            key(1)
+                .verifying_key()
+                .to_public_key_pem(LineEnding::LF)
+                .unwrap(),
+        )
+        .unwrap();
+
+        let manifest = directory.path().join("manifest.json");
+        std::fs::write(&manifest, "{}\n").unwrap();
+
+        let signature = sign(&manifest, &signing_key(&private).unwrap()).unwrap();
+        assert_eq!(signature, directory.path().join("manifest.json.sig"));
+        verify(&manifest, &verifying_key(&public).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn rejects() {
+        let directory = tempfile::tempdir().unwrap();
+
+        let manifest = directory.path().join("manifest.json");
+        std::fs::write(&manifest, "{}\n").unwrap();
+        sign(&manifest, &key(1)).unwrap();
+
+        let err = verify(&manifest, &key(2).verifying_key()).unwrap_err();
+        assert!(err.to_string().starts_with("signature does not match"));
+
+        std::fs::write(&manifest, "{\"files\":[]}\n").unwrap();
+        let err = verify(&manifest, &key(1).verifying_key()).unwrap_err();
+        assert!(err.to_string().starts_with("signature does not match"));
+
+        std::fs::write(signature_path(&manifest), "abc\n").unwrap();
+        let err = verify(&manifest, &key(1).verifying_key()).unwrap_err();
+        assert!(err.to_string().starts_with("parsing signature"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Loading of composable characteristic and ontology trees from disk.
+
+use std::collections::HashMap;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ecc::Characteristic;
+use ecc::common::Sensitivity;
+pub use ecc_check::files::EXTENSIONS;
+pub use ecc_check::files::files;
+pub use ecc_check::files::is_yaml;
+pub use ecc_check::files::select;
+use ontology::Graph;
+use ontology::Node;
+use ontology::graph::SiblingOrder;
+use tracing::info;
+
+use crate::Error;
+
+/// Streams the characteristics within a directory.
+///
+/// Each file is read through a buffered reader as the iterator advances (see
+/// [`ecc::io::stream()`]), so memory use is bounded by the largest file rather
+/// than the size of the tree. Characteristics in the legacy flat format are
+/// upgraded as they are loaded so that older revisions of the tree remain
+/// loadable.
+pub fn stream(
+    root: &Path,
+) -> Result<impl Iterator<Item = (PathBuf, Result<Characteristic, Error>)> + use<>, Error> {
+    let stream = ecc::io::stream(files(root)?).map(|(path, result)| {
+        let result = result.map_err(|source| match source {
+            ecc::io::Error::Io { path, source } => Error::Io { path, source },
+            source => Error::Characteristic {
+                path: path.clone(),
+                source,
+            },
+        });
+
+        (path, result)
+    });
+
+    Ok(stream)
+}
+
+/// Loads all characteristics within a directory.
+///
+/// Unlike the `check` subcommand, loading stops at the first characteristic
+/// that cannot be parsed (see [`stream()`]).
+pub fn load(root: &Path) -> Result<Vec<(PathBuf, Characteristic)>, Error> {
+    stream(root)?
+        .map(|(path, result)| result.map(|characteristic| (path, characteristic)))
+        .collect()
+}
+
+/// Whether a characteristic is more restricted than the most restricted
+/// sensitivity that may be published.
+pub fn is_withheld(characteristic: &Characteristic, max_sensitivity: Sensitivity) -> bool {
+    characteristic.sensitivity() > max_sensitivity
+}
+
+/// Removes the characteristics that are more restricted than the most
+/// restricted sensitivity that may be published (see [`is_withheld()`]).
+pub fn withhold(
+    characteristics: &mut Vec<(PathBuf, Characteristic)>,
+    max_sensitivity: Sensitivity,
+) {
+    let before = characteristics.len();
+    characteristics.retain(|(_, characteristic)| !is_withheld(characteristic, max_sensitivity));
+    let withheld = before - characteristics.len();
+
+    if withheld > 0 {
+        info!(
+            "withheld {withheld} characteristics that are more restricted than `{max_sensitivity}`"
+        );
+    }
+}
+
+/// Gets a path relative to a root with forward slashes (e.g.,
+/// `ecc/morph/foo.yml`).
+///
+/// Paths outside of the root are returned as is.
+pub fn relative(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Loads all ontology nodes within a directory.
+pub fn load_ontology(root: &Path) -> Result<Vec<(PathBuf, Node)>, Error> {
+    files(root)?
+        .into_iter()
+        .map(|path| {
+            let contents = read(&path)?;
+            let node = match serde_yaml::from_str::<Node>(&contents) {
+                Ok(node) => node,
+                Err(source) => return Err(Error::Node { path, source }),
+            };
+
+            Ok((path, node))
+        })
+        .collect()
+}
+
+/// Reads a file to a string.
+pub fn read(path: &Path) -> Result<String, Error> {
+    std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Loads the ontology graph within a directory along with the path to each
+/// node's file (keyed by node name).
+///
+/// Siblings are sorted by their order and then by name so that everything
+/// rendered from the graph is ordered in the same way.
+pub fn load_ontology_graph(root: &Path) -> Result<(Graph, HashMap<String, PathBuf>), Error> {
+    let mut paths = HashMap::new();
+    let mut nodes = Vec::new();
+
+    for (path, node) in load_ontology(root)? {
+        paths.insert(node.name().inner().to_string(), path);
+        nodes.push(node);
+    }
+
+    let graph = Graph::try_new(nodes)
+        .map_err(Error::Ontology)?
+        .with_sibling_order(SiblingOrder::OrderThenName);
+    Ok((graph, paths))
+}
@@ -0,0 +1,134 @@
+//! Verification of downloaded bundles.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use clap::Parser;
+use colored::Colorize as _;
+use ecc::Characteristic;
+use ecc::canonical;
+use serde::Deserialize;
+use tracing::warn;
+
+use crate::build;
+use crate::build::ManifestFile;
+use crate::sign;
+
+/// Verifies a bundle against its manifest.
+///
+/// Every file listed in the manifest is checked for its size and SHA-256
+/// checksum, except for characteristic documents, which are parsed and checked
+/// for their content hash instead. This way, a cached copy of a characteristic
+/// can be confirmed to match the registry even if it was reformatted.
+///
+/// If a public key is provided, the detached signature of the manifest is
+/// verified first (see [`crate::sign`]). The exit code is `1` when the
+/// signature or any file fails verification.
+#[derive(Parser)]
+pub struct Args {
+    /// The bundle directory (containing `manifest.json`).
+    bundle: PathBuf,
+
+    /// The PEM-encoded Ed25519 public key to verify the manifest signature
+    /// with.
+    #[arg(long, value_name = "PEM")]
+    public_key: Option<PathBuf>,
+}
+
+/// The parts of the bundle manifest needed for verification.
+#[derive(Deserialize)]
+struct Manifest {
+    /// The files within the bundle.
+    files: Vec<ManifestFile>,
+}
+
+/// Verifies a single file.
+///
+/// Returns a description of the problem if verification failed.
+fn verify(root: &Path, file: &ManifestFile) -> Option<String> {
+    let contents = match std::fs::read(root.join(&file.path)) {
+        Ok(contents) => contents,
+        Err(err) => return Some(format!("unable to read file: {err}")),
+    };
+
+    if let Some(expected) = &file.content_hash {
+        let actual = match serde_json::from_slice::<Characteristic>(&contents) {
+            Ok(characteristic) => characteristic.content_hash(),
+            Err(err) => return Some(format!("unable to parse characteristic: {err}")),
+        };
+
+        return (&actual != expected)
+            .then(|| format!("content hash mismatch (expected `{expected}`, found `{actual}`)"));
+    }
+
+    if contents.len() != file.bytes {
+        return Some(format!(
+            "size mismatch (expected {} bytes, found {})",
+            file.bytes,
+            contents.len()
+        ));
+    }
+
+    let actual = canonical::sha256(&contents);
+
+    if actual != file.sha256 {
+        return Some(format!(
+            "checksum mismatch (expected `{}`, found `{actual}`)",
+            file.sha256
+        ));
+    }
+
+    None
+}
+
+/// The main method.
+pub fn main(args: Args) -> anyhow::Result<()> {
+    let path = args.bundle.join(build::MANIFEST);
+    let mut failed = 0;
+
+    match &args.public_key {
+        Some(key) => {
+            let key = sign::verifying_key(key)?;
+            let name = format!("{}.{}", build::MANIFEST, sign::SIGNATURE_EXTENSION);
+
+            match sign::verify(&path, &key) {
+                Ok(()) => println!("{}.. {}", name.bold(), "OK".green()),
+                Err(err) => {
+                    failed += 1;
+                    println!("{}.. {} ({err:#})", name.bold(), "FAIL".red());
+                }
+            }
+        }
+        None if sign::signature_path(&path).exists() => {
+            warn!("the manifest is signed, but no `--public-key` was provided to verify it");
+        }
+        None => {}
+    }
+
+    let manifest =
+        std::fs::read(&path).with_context(|| format!("reading manifest: {}", path.display()))?;
+    let manifest = serde_json::from_slice::<Manifest>(&manifest)
+        .with_context(|| format!("parsing manifest: {}", path.display()))?;
+
+    for file in &manifest.files {
+        match verify(&args.bundle, file) {
+            None => println!("{}.. {}", file.path.bold(), "OK".green()),
+            Some(problem) => {
+                failed += 1;
+                println!("{}.. {} ({problem})", file.path.bold(), "FAIL".red());
+            }
+        }
+    }
+
+    if failed == 0 {
+        return Ok(());
+    }
+
+    println!(
+        "\n{} {failed} of {} checks failed verification",
+        "Summary:".bold(),
+        manifest.files.len() + usize::from(args.public_key.is_some())
+    );
+    std::process::exit(crate::EXIT_FAILURE.into());
+}
@@ -0,0 +1,48 @@
+//! Integration tests for `ecc-cli ecc assign-id`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// The contents of a draft without an identifier.
+const DRAFT: &str = "state: draft\nname: A Draft\n";
+
+#[test]
+fn assigns() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    let file = tree.write("ecc/morph/a-draft.yml", DRAFT);
+
+    ecc_cli(&tree)
+        .args(["ecc", "assign-id"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout("ECC-MORPH-000002\n");
+
+    assert_eq!(
+        std::fs::read_to_string(&file).unwrap(),
+        "state: draft\nname: A Draft\nidentifier: ECC-MORPH-000002\n"
+    );
+}
+
+#[test]
+fn exhausted() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic_with(|common| {
+        common.set_identifier(fixtures::identifier("ECC-MORPH-999999"));
+    }));
+    let file = tree.write("ecc/morph/a-draft.yml", DRAFT);
+
+    ecc_cli(&tree)
+        .args(["ecc", "assign-id"])
+        .arg(&file)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "no identifiers left in category MORPH",
+        ));
+
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), DRAFT);
+}
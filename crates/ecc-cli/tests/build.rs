@@ -0,0 +1,94 @@
+//! Integration tests for `ecc-cli build`.
+
+mod common;
+
+use common::ecc_cli;
+use ecc::common::Sensitivity;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+#[test]
+fn draft_pages() {
+    let tree = Tree::new()
+        .with_characteristic(fixtures::adopted_characteristic())
+        .with_ontology(&fixtures::small_ontology());
+    tree.write(
+        "ecc/morph/foo.yml",
+        "state: draft\nname: A Morphological Draft\n",
+    );
+    tree.write(
+        "ecc/molec/foo.yml",
+        "state: draft\nname: A Molecular Draft\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["build", "--include-drafts", "--output-directory", "bundle"])
+        .assert()
+        .success();
+
+    let pages = tree.path().join("bundle/characteristics");
+
+    for (stem, name) in [
+        ("ECC-MORPH-000001", "A Characteristic Name"),
+        ("morph-foo", "A Morphological Draft"),
+        ("molec-foo", "A Molecular Draft"),
+    ] {
+        let page = std::fs::read_to_string(pages.join(format!("{stem}.md"))).unwrap();
+        assert!(page.starts_with(&format!("# {name}\n")), "{stem}");
+        assert!(pages.join(format!("{stem}.json")).exists(), "{stem}");
+    }
+}
+
+#[test]
+fn withholds_restricted() {
+    let tree = Tree::new()
+        .with_characteristics([
+            fixtures::adopted_characteristic(),
+            fixtures::adopted_characteristic_with(|common| {
+                common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+                common.set_name("A Restricted Characteristic").unwrap();
+                common.set_sensitivity(Sensitivity::Restricted);
+            }),
+        ])
+        .with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .args(["build", "--output-directory", "bundle"])
+        .assert()
+        .success();
+
+    let bundle = tree.path().join("bundle");
+    assert!(
+        bundle
+            .join("characteristics/ECC-MORPH-000001.json")
+            .exists()
+    );
+    assert!(
+        !bundle
+            .join("characteristics/ECC-MORPH-000002.json")
+            .exists()
+    );
+
+    for file in ["index.json", "search.json", "manifest.json"] {
+        let contents = std::fs::read_to_string(bundle.join(file)).unwrap();
+        assert!(!contents.contains("ECC-MORPH-000002"), "{file}");
+        assert!(!contents.contains("Restricted"), "{file}");
+    }
+
+    ecc_cli(&tree)
+        .args([
+            "build",
+            "--max-sensitivity",
+            "restricted",
+            "--output-directory",
+            "everything",
+        ])
+        .assert()
+        .success();
+
+    assert!(
+        tree.path()
+            .join("everything/characteristics/ECC-MORPH-000002.json")
+            .exists()
+    );
+}
@@ -0,0 +1,593 @@
+//! Integration tests for `ecc-cli check`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::CONFIG;
+use test_infra::tree::Tree;
+
+#[test]
+fn valid() {
+    let tree = Tree::new().with_characteristics([
+        fixtures::proposed_characteristic(),
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+            common.set_name("Another Characteristic Name").unwrap();
+        }),
+    ]);
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a-characteristic-name.yml.. OK"))
+        .stdout(predicate::str::contains("2 passed, 0 failed"));
+}
+
+#[test]
+fn invalid() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    tree.write("ecc/morph/broken.yml", "name: Broken\nstate: adopted\n");
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("broken.yml.. FAIL"))
+        .stdout(predicate::str::contains("error[E0009]"))
+        .stdout(predicate::str::contains("missing field `adoption_date`"))
+        .stdout(predicate::str::contains("1 passed, 1 failed"));
+}
+
+#[test]
+fn blank_text() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    let yaml = serde_yaml::to_string(&fixtures::proposed_characteristic_with(|common| {
+        common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+    }))
+    .unwrap();
+
+    tree.write(
+        "ecc/morph/blank-name.yml",
+        yaml.replace("name: A Characteristic Name", "name: '  '"),
+    );
+    tree.write(
+        "ecc/morph/blank-description.yml",
+        yaml.replace(
+            "description: A description of the characteristic.",
+            "description: ''",
+        ),
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("blank-name.yml.. FAIL"))
+        .stdout(predicate::str::contains("`name` must not be empty"))
+        .stdout(predicate::str::contains("blank-description.yml.. FAIL"))
+        .stdout(predicate::str::contains("`description` must not be empty"))
+        .stdout(predicate::str::contains("1 passed, 2 failed"));
+}
+
+#[test]
+fn cache() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(cached)").not());
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "a-characteristic-name.yml.. OK (cached)",
+        ));
+
+    ecc_cli(&tree)
+        .args(["check", "--no-cache"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("(cached)").not());
+
+    // NOTE: changing a file invalidates its entry within the cache.
+    tree.write(
+        "ecc/morph/a-characteristic-name.yml",
+        "name: Broken\nstate: adopted\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("a-characteristic-name.yml.. FAIL"));
+}
+
+#[test]
+fn lints() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    tree.write(
+        "ecc/morph/a-draft.yml",
+        "state: draft\nname: A Draft\nvalues:\n  kind: categorical\n  options:\n    - Yes\n    - \
+         No\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a-draft.yml.. OK (1 warnings)"))
+        .stdout(predicate::str::contains("use `kind: binary` instead"))
+        .stdout(predicate::str::contains("the lint `categorical-as-binary`"))
+        .stdout(predicate::str::contains("2 passed, 0 failed, 1 warnings"));
+
+    // NOTE: files with warnings are not cached, and changing the severity of a
+    // lint invalidates the cache.
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[lints]\ncategorical-as-binary = \"deny\"\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("a-draft.yml.. FAIL"))
+        .stdout(predicate::str::contains("a-characteristic-name.yml.. OK (cached)").not());
+
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[lints]\ncategorical-as-binary = \"allow\"\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a-draft.yml.. OK\n"));
+}
+
+#[test]
+fn similar_names() {
+    let tree = Tree::new().with_characteristics([
+        fixtures::adopted_characteristic(),
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+            common.set_name("A Characteristic Names").unwrap();
+        }),
+    ]);
+
+    // NOTE: names are compared even when every file is within the cache.
+    for _ in 0..2 {
+        ecc_cli(&tree)
+            .arg("check")
+            .assert()
+            .success()
+            .stdout(predicate::str::contains(
+                "`A Characteristic Name` and `A Characteristic Names` differ by a single edit",
+            ))
+            .stdout(predicate::str::contains("2 passed, 0 failed, 1 warnings"));
+    }
+
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[lints]\nsimilar-names = \"deny\"\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("0 passed, 2 failed"));
+}
+
+#[test]
+fn definitions() {
+    let tree = Tree::new().with_characteristics([
+        fixtures::adopted_characteristic(),
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+            common.set_name("A Composite Characteristic").unwrap();
+            common.set_definition(Some(
+                "ECC-MORPH-000001 = true AND NOT ECC-MORPH-000003"
+                    .parse()
+                    .unwrap(),
+            ));
+        }),
+    ]);
+
+    // NOTE: definitions are checked even when every file is within the cache.
+    for _ in 0..2 {
+        ecc_cli(&tree)
+            .arg("check")
+            .assert()
+            .code(1)
+            .stdout(predicate::str::contains("Checking definitions.."))
+            .stdout(predicate::str::contains(
+                "`ECC-MORPH-000003` is not a known characteristic",
+            ))
+            .stdout(predicate::str::contains("the lint `definition-reference`"))
+            .stdout(predicate::str::contains("1 passed, 1 failed"));
+    }
+
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[lints]\ndefinition-reference = \"warn\"\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 passed, 0 failed, 1 warnings"));
+}
+
+#[test]
+fn tags() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    tree.write(
+        "ecc/morph/a-draft.yml",
+        "state: draft\nname: A Draft\ntags:\n  - pediatric\n  - teen\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("a-draft.yml.. FAIL"))
+        .stdout(predicate::str::contains(
+            "the tag `teen` is not within the controlled vocabulary",
+        ));
+
+    // NOTE: changing the vocabulary invalidates the cache.
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[tags]\nvocabulary = [\"pediatric\", \
+         \"teen\"]\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a-draft.yml.. OK\n"));
+
+    ecc_cli(&tree)
+        .args(["ecc", "list", "--filter", "tag:teen"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("A Draft"))
+        .stdout(predicate::str::contains("A Characteristic Name").not());
+}
+
+#[test]
+fn completeness() {
+    let tree = Tree::new().with_characteristics([
+        fixtures::proposed_characteristic(),
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+            common.set_name("Another Characteristic Name").unwrap();
+        }),
+    ]);
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[check.min_completeness]\nadopted = 0.8\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("a-characteristic-name.yml.. OK"))
+        .stdout(predicate::str::contains(
+            "the characteristic is 0.67 complete, but adopted characteristics must be at least \
+             0.80 complete; missing: `references`",
+        ))
+        .stdout(predicate::str::contains("1 passed, 1 failed"));
+
+    ecc_cli(&tree)
+        .args(["ecc", "stats"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Mean completeness: 0.67"))
+        .stdout(predicate::str::contains("Least complete (2)"));
+}
+
+#[test]
+fn gene_symbols() {
+    let tree = Tree::new();
+    tree.write(
+        "ecc/molec/h3-k27m.yml",
+        "state: draft\nname: H3 K27M\nmolecular:\n  genes: [H3F3A]\n  variants: [p.K27M]\n",
+    );
+
+    // NOTE: gene symbols are only checked against a configured symbol list.
+    ecc_cli(&tree)
+        .args(["check", "--no-cache"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("h3-k27m.yml.. OK\n"));
+
+    tree.write("hgnc.txt", "symbol\tprev_symbol\nH3-3A\tH3F3A\n");
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[check]\ngene_symbols = \"hgnc.txt\"\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["check", "--no-cache"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "the gene symbol `H3F3A` is a previous HGNC symbol; use the approved symbol `H3-3A` \
+             instead",
+        ));
+
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[check]\ngene_symbols = \"missing.txt\"\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["check", "--no-cache"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("missing.txt"));
+}
+
+#[test]
+fn gene_symbols_cache() {
+    let tree = Tree::new();
+    tree.write(
+        "ecc/molec/pax5.yml",
+        "state: draft\nname: PAX5 Alteration\nmolecular:\n  genes: [PAX5]\n",
+    );
+    tree.write("hgnc.txt", "symbol\tprev_symbol\nPAX5\t\n");
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[check]\ngene_symbols = \"hgnc.txt\"\n",
+    );
+
+    for _ in 0..2 {
+        ecc_cli(&tree).arg("check").assert().success();
+    }
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("pax5.yml.. OK (cached)"));
+
+    // NOTE: swapping one symbol for another keeps the path and the number of
+    // symbols the same, but still invalidates the cache.
+    tree.write("hgnc.txt", "symbol\tprev_symbol\nPAX6\t\n");
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .stdout(predicate::str::contains("(cached)").not())
+        .stdout(predicate::str::contains("PAX5"));
+}
+
+/// Creates a tree with characteristics whose RFCs link to issues that exist,
+/// redirect, and do not exist.
+fn tree_with_links() -> Tree {
+    let characteristic = |id: &str, name: &str, issue: u32| {
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier(id));
+            common.set_name(name).unwrap();
+            common.set_rfc(
+                format!("https://github.com/stjudecloud/ecc/issues/{issue}")
+                    .parse()
+                    .unwrap(),
+            );
+        })
+    };
+
+    Tree::new().with_characteristics([
+        characteristic("ECC-MORPH-000001", "An Existing Issue", 1),
+        characteristic("ECC-MORPH-000002", "A Moved Issue", 2),
+        characteristic("ECC-MORPH-000003", "A Missing Issue", 999),
+    ])
+}
+
+#[test]
+fn verify_links() {
+    let tree = tree_with_links();
+
+    ecc_cli(&tree)
+        .args(["check", "--verify-links"])
+        .env("ECC_HTTP_FIXTURES", common::http_fixtures())
+        .env("ECC_HTTP_MODE", "replay")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "a-missing-issue.yml: https://github.com/stjudecloud/ecc/issues/999.. FAIL (not found \
+             (status 404))",
+        ))
+        .stdout(predicate::str::contains("issues/1..").not())
+        .stdout(predicate::str::contains("issues/2..").not())
+        .stdout(predicate::str::contains("2 passed, 1 failed"));
+}
+
+#[test]
+fn verify_links_offline() {
+    let tree = tree_with_links();
+
+    // NOTE: nothing is cached yet, so every link is skipped.
+    ecc_cli(&tree)
+        .args(["check", "--verify-links", "--offline"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "skipped 3 uncached links (offline)",
+        ))
+        .stdout(predicate::str::contains("3 passed, 0 failed"));
+
+    ecc_cli(&tree)
+        .args(["check", "--verify-links"])
+        .env("ECC_HTTP_FIXTURES", common::http_fixtures())
+        .assert()
+        .code(1);
+
+    // NOTE: the cached results are still used when offline.
+    ecc_cli(&tree)
+        .args(["check", "--verify-links", "--offline"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("issues/999.. FAIL"))
+        .stdout(predicate::str::contains("skipped").not());
+}
+
+#[test]
+fn missing_directory() {
+    let tree = Tree::new();
+
+    ecc_cli(&tree)
+        .args(["check", "does-not-exist"])
+        .assert()
+        .code(2);
+}
+
+/// Gets a tree with a `.yml` file, a `.yaml` file, and a broken file.
+fn tree_with_extensions() -> Tree {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    let yaml = serde_yaml::to_string(&fixtures::proposed_characteristic_with(|common| {
+        common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+        common.set_name("Another Characteristic Name").unwrap();
+    }))
+    .unwrap();
+
+    tree.write("ecc/morph/another-characteristic-name.yaml", yaml);
+    tree.write("ecc/molec/broken.yml", "name: Broken\nstate: adopted\n");
+    tree
+}
+
+#[test]
+fn include_exclude() {
+    let tree = tree_with_extensions();
+
+    ecc_cli(&tree)
+        .args(["check", "--exclude", "**/broken.yml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("a-characteristic-name.yml.. OK"))
+        .stdout(predicate::str::contains(
+            "another-characteristic-name.yaml.. OK",
+        ))
+        .stdout(predicate::str::contains("broken.yml").not())
+        .stdout(predicate::str::contains("2 passed, 0 failed"));
+
+    ecc_cli(&tree)
+        .args(["check", "--include", "*.yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "another-characteristic-name.yaml.. OK",
+        ))
+        .stdout(predicate::str::contains("a-characteristic-name.yml").not())
+        .stdout(predicate::str::contains("1 passed, 0 failed"));
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("broken.yml.. FAIL"))
+        .stdout(predicate::str::contains("2 passed, 1 failed"));
+
+    // NOTE: a check that matches no files fails rather than passing.
+    ecc_cli(&tree)
+        .args(["check", "--include", "*.json"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "no characteristic files matched within",
+        ));
+}
+
+#[test]
+fn multiple_paths() {
+    let tree = tree_with_extensions();
+
+    ecc_cli(&tree)
+        .args([
+            "check",
+            "ecc/morph/another-characteristic-name.yaml",
+            "ecc/morph/a-characteristic-name.yml",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 passed, 0 failed"));
+
+    ecc_cli(&tree)
+        .args(["check", "ecc/morph", "ecc/molec"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("2 passed, 1 failed"));
+}
+
+#[test]
+fn max_warnings() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    tree.write(
+        "ecc/morph/a-draft.yml",
+        "state: draft\nname: A Draft\nvalues:\n  kind: categorical\n  options:\n    - Yes\n    - \
+         No\n",
+    );
+
+    // NOTE: staying at the limit passes.
+    ecc_cli(&tree)
+        .args(["check", "--max-warnings", "1"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2 passed, 0 failed, 1 warnings"))
+        .stdout(predicate::str::contains("too many warnings").not());
+
+    ecc_cli(&tree)
+        .args(["check", "--max-warnings", "0"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("2 passed, 0 failed, 1 warnings"))
+        .stdout(predicate::str::contains("too many warnings (1 > 0)"));
+}
+
+#[test]
+fn other_kind() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    tree.write(
+        "ecc/morph/another-characteristic-name.yml",
+        "state: proposed\nidentifier: ECC-MORPH-000002\nname: Another Characteristic Name\nrfc: \
+         https://github.com/stjudecloud/ecc/issues/1\ndescription: A description of the \
+         characteristic.\nvalues:\n  kind: other\n  description: A count of cells.\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "another-characteristic-name.yml.. FAIL",
+        ))
+        .stdout(predicate::str::contains(
+            "only drafts can use `kind: other`",
+        ))
+        .stdout(predicate::str::contains("1 passed, 1 failed"));
+
+    // NOTE: the rule is not a lint, so it cannot be allowed.
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\n\n[lints]\nother-kind = \"allow\"\n",
+    );
+
+    ecc_cli(&tree)
+        .arg("check")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unknown lint `other-kind`"));
+}
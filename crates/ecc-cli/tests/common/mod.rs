@@ -0,0 +1,34 @@
+//! Common operations for the command line integration tests.
+
+use std::path::PathBuf;
+
+use assert_cmd::Command;
+use test_infra::tree::Tree;
+
+/// Gets a command that runs `ecc-cli` against a tree.
+///
+/// Colors are disabled so that the output can be matched as plain text, and
+/// recorded HTTP responses are only used when a test asks for them.
+pub fn ecc_cli(tree: &Tree) -> Command {
+    // SAFETY: the binary is always built for the integration tests.
+    let mut command = Command::cargo_bin("ecc-cli").unwrap();
+    command
+        .env("NO_COLOR", "1")
+        .env_remove("ECC_HTTP_FIXTURES")
+        .env_remove("ECC_HTTP_MODE")
+        .arg("--config")
+        .arg(tree.config())
+        .current_dir(tree.path());
+    command
+}
+
+/// Gets the directory of recorded HTTP responses.
+///
+/// Responses are recorded by running a command with `ECC_HTTP_FIXTURES` set to
+/// this directory and `ECC_HTTP_MODE=record`.
+#[allow(dead_code)]
+pub fn http_fixtures() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("http-fixtures")
+}
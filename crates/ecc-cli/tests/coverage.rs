@@ -0,0 +1,46 @@
+//! Integration tests for `ecc-cli coverage`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets the molecular characteristic bound within the small ontology.
+fn molecular() -> ecc::Characteristic {
+    fixtures::adopted_characteristic_with(|common| {
+        common.set_identifier(fixtures::identifier("ECC-MOLEC-000001"));
+        common.set_name("A Molecular Characteristic").unwrap();
+    })
+}
+
+#[test]
+fn covered() {
+    let tree = Tree::new()
+        .with_characteristics([fixtures::adopted_characteristic(), molecular()])
+        .with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .args(["coverage", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(r#""adopted": 2"#))
+        .stdout(predicate::str::contains(r#""invalid": []"#));
+}
+
+#[test]
+fn invalid_binding() {
+    let tree = Tree::new()
+        .with_characteristic(fixtures::adopted_characteristic())
+        .with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .arg("coverage")
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("Invalid bindings (2)"))
+        .stdout(predicate::str::contains(
+            "`ECC-MOLEC-000001` does not exist",
+        ));
+}
@@ -0,0 +1,231 @@
+//! Integration tests for `ecc-cli data`.
+
+mod common;
+
+use common::ecc_cli;
+use ecc::dataset::DatasetManifest;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with an adopted characteristic and a small ontology.
+fn tree() -> Tree {
+    Tree::new()
+        .with_characteristic(fixtures::adopted_characteristic())
+        .with_ontology(&fixtures::small_ontology())
+}
+
+#[test]
+fn check() {
+    let tree = tree();
+    tree.write(
+        "samples.tsv",
+        "sample\tA Characteristic Name\nS1\ttrue\nS2\tfalse\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["data", "check", "samples.tsv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "samples.tsv:1:1: warning: column `sample` does not map to a characteristic",
+        ))
+        .stdout(predicate::str::contains("Checked 2 rows.. OK"));
+}
+
+#[test]
+fn check_invalid() {
+    let tree = tree();
+    tree.write(
+        "samples.tsv",
+        "sample\tECC-MORPH-000001\nS1\ttrue\nS2\tmaybe\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["data", "check", "samples.tsv"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "samples.tsv:3:2: error: invalid value `maybe` for characteristic `ECC-MORPH-000001`: \
+             expected `true` or `false`",
+        ))
+        .stdout(predicate::str::contains(
+            "Checked 2 rows.. FAIL (1 problems)",
+        ));
+}
+
+#[test]
+fn check_missing_file() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args(["data", "check", "missing.tsv"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("missing.tsv"));
+}
+
+#[test]
+fn classify() {
+    let tree = tree();
+    tree.write(
+        "samples.tsv",
+        "sample\tECC-MORPH-000001\nS1\ttrue\nS2\tfalse\nS3\tmaybe\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["data", "classify", "samples.tsv", "-o", "classified.tsv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Classified 3 samples"))
+        .stdout(predicate::str::contains("invalid"));
+
+    let classified = std::fs::read_to_string(tree.path().join("classified.tsv")).unwrap();
+    let rows = classified.lines().collect::<Vec<_>>();
+    assert_eq!(
+        rows[0],
+        "sample\tECC-MORPH-000001\tontology_node\tontology_code\tontology_flags"
+    );
+    assert!(rows[1].starts_with("S1\ttrue\tB-cell Lymphoblastic Leukemia\tBLL\t"));
+    assert!(rows[2].starts_with("S2\tfalse\tT-cell Lymphoblastic Leukemia\tTLL\t"));
+    assert_eq!(rows[3], "S3\tmaybe\t\t\tinvalid");
+}
+
+#[test]
+fn classify_missing_file() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args(["data", "classify", "missing.tsv", "-o", "classified.tsv"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("missing.tsv"));
+
+    assert!(!tree.path().join("classified.tsv").exists());
+}
+
+#[test]
+fn manifest() {
+    let tree = tree();
+    tree.write(
+        "samples.tsv",
+        "sample\ta characteristic-name\nS1\ttrue\nS2\tNA\n",
+    );
+
+    ecc_cli(&tree)
+        .args([
+            "data",
+            "manifest",
+            "samples.tsv",
+            "--missing",
+            "NA",
+            "-o",
+            "manifest.yml",
+        ])
+        .assert()
+        .success();
+
+    let manifest = std::fs::read_to_string(tree.path().join("manifest.yml")).unwrap();
+    let manifest = serde_yaml::from_str::<DatasetManifest>(&manifest).unwrap();
+    assert_eq!(manifest.missing, ["NA"]);
+    assert_eq!(manifest.columns.len(), 1);
+    assert_eq!(manifest.columns[0].column, "a characteristic-name");
+    assert_eq!(
+        manifest.columns[0].identifier,
+        fixtures::identifier("ECC-MORPH-000001")
+    );
+
+    // NOTE: the suggested manifest can be used to check the file.
+    ecc_cli(&tree)
+        .args(["data", "check", "samples.tsv", "--manifest", "manifest.yml"])
+        .assert()
+        .success();
+}
+
+#[test]
+fn manifest_missing_file() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args(["data", "manifest", "missing.tsv", "-o", "manifest.yml"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("missing.tsv"));
+
+    assert!(!tree.path().join("manifest.yml").exists());
+}
+
+/// The manifest for the harmonization tests.
+const HARMONIZE_MANIFEST: &str = "columns:
+  - column: fusion
+    identifier: ECC-MORPH-000001
+    missing: [NA]
+    recode:
+      detected: 'true'
+";
+
+#[test]
+fn harmonize() {
+    let tree = tree();
+    tree.write("manifest.yml", HARMONIZE_MANIFEST);
+    tree.write(
+        "samples.tsv",
+        "sample\tfusion\nS1\tdetected\nS2\tFALSE\nS3\tNA\nS4\ttrue\n",
+    );
+
+    ecc_cli(&tree)
+        .args([
+            "data",
+            "harmonize",
+            "samples.tsv",
+            "--manifest",
+            "manifest.yml",
+            "-o",
+            "harmonized.tsv",
+            "--log",
+            "changes.tsv",
+        ])
+        .assert()
+        .success();
+
+    assert_eq!(
+        std::fs::read_to_string(tree.path().join("harmonized.tsv")).unwrap(),
+        "sample\tfusion\nS1\ttrue\nS2\tfalse\nS3\t\nS4\ttrue\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(tree.path().join("changes.tsv")).unwrap(),
+        "line\tcolumn\tfrom\tto\treason\n2\tfusion\tdetected\ttrue\trecoded by \
+         manifest\n3\tfusion\tFALSE\tfalse\tnormalized boolean\n4\tfusion\tNA\t\tmissing value \
+         code\n"
+    );
+}
+
+#[test]
+fn harmonize_unmapped() {
+    let tree = tree();
+    tree.write("manifest.yml", HARMONIZE_MANIFEST);
+    tree.write("samples.tsv", "sample\tfusion\nS1\tdetected\nS2\tunsure\n");
+
+    ecc_cli(&tree)
+        .args([
+            "data",
+            "harmonize",
+            "samples.tsv",
+            "--manifest",
+            "manifest.yml",
+            "-o",
+            "harmonized.tsv",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "samples.tsv:3: error: column `fusion`: invalid value `unsure`: expected `true` or \
+             `false`",
+        ));
+
+    // NOTE: the cells that cannot be harmonized are left unchanged.
+    assert_eq!(
+        std::fs::read_to_string(tree.path().join("harmonized.tsv")).unwrap(),
+        "sample\tfusion\nS1\ttrue\nS2\tunsure\n"
+    );
+}
@@ -0,0 +1,55 @@
+//! Integration tests for how errors are reported.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::CONFIG;
+use test_infra::tree::Tree;
+
+#[test]
+fn unparseable_characteristic() {
+    let tree = Tree::new()
+        .with_characteristic(fixtures::adopted_characteristic())
+        .with_ontology(&fixtures::small_ontology());
+    tree.write("ecc/morph/broken.yml", "name: Broken\nstate: adopted\n");
+
+    ecc_cli(&tree)
+        .env("RUST_BACKTRACE", "1")
+        .arg("coverage")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("Error: parsing characteristic `"))
+        .stderr(predicate::str::contains("missing field `adoption_date`"))
+        .stderr(predicate::str::contains("backtrace").not());
+}
+
+#[test]
+fn unknown_lint() {
+    let tree = Tree::new();
+    tree.write(CONFIG, "[lints]\nmade-up = \"deny\"\n");
+
+    ecc_cli(&tree)
+        .env("RUST_BACKTRACE", "1")
+        .arg("check")
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unknown lint `made-up`"))
+        .stderr(predicate::str::contains("backtrace").not());
+}
+
+#[test]
+fn missing_path() {
+    let tree = Tree::new();
+
+    ecc_cli(&tree)
+        .env("RUST_BACKTRACE", "1")
+        .args(["check", "does-not-exist"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "Error: path does not exist: does-not-exist",
+        ))
+        .stderr(predicate::str::contains("backtrace").not());
+}
@@ -0,0 +1,143 @@
+//! Integration tests for `ecc-cli ecc export`.
+
+mod common;
+
+use common::ecc_cli;
+use ecc::Characteristic;
+use ecc::common::EvidenceLevel;
+use ecc::common::Sensitivity;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with two characteristics.
+fn tree() -> Tree {
+    Tree::new().with_characteristics([
+        fixtures::proposed_characteristic(),
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+            common.set_name("Another Characteristic Name").unwrap();
+        }),
+    ])
+}
+
+#[test]
+fn json() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args([
+            "ecc",
+            "export",
+            "--format",
+            "json",
+            "--output-directory",
+            "dist",
+        ])
+        .assert()
+        .success();
+
+    let json = std::fs::read_to_string(tree.path().join("dist/characteristics.json")).unwrap();
+    let characteristics = serde_json::from_str::<Vec<Characteristic>>(&json).unwrap();
+    assert_eq!(characteristics.len(), 2);
+    assert!(json.starts_with("[\n  {\n"));
+}
+
+#[test]
+fn ndjson() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args([
+            "ecc",
+            "export",
+            "--format",
+            "ndjson",
+            "--output-directory",
+            "dist",
+        ])
+        .assert()
+        .success();
+
+    let ndjson = std::fs::read_to_string(tree.path().join("dist/characteristics.ndjson")).unwrap();
+    assert_eq!(ndjson.lines().count(), 2);
+}
+
+#[test]
+fn evidence_level() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic_with(|common| {
+        common.set_evidence_level(Some(EvidenceLevel::ConsensusGuideline));
+    }));
+
+    ecc_cli(&tree)
+        .args([
+            "ecc",
+            "export",
+            "--format",
+            "csv",
+            "--output-directory",
+            "dist",
+        ])
+        .assert()
+        .success();
+
+    let csv = std::fs::read_to_string(tree.path().join("dist/characteristics.csv")).unwrap();
+    let mut lines = csv.lines();
+    assert!(lines.next().unwrap().contains(",evidence_level,"));
+    assert!(lines.next().unwrap().contains(",consensus-guideline,"));
+}
+
+#[test]
+fn unparseable_characteristic() {
+    let tree = tree();
+    tree.write("ecc/morph/zzz-broken.yml", "state: [draft\n");
+
+    ecc_cli(&tree)
+        .args([
+            "ecc",
+            "export",
+            "--format",
+            "json",
+            "--output-directory",
+            "dist",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("zzz-broken.yml"));
+
+    assert!(!tree.path().join("dist/characteristics.json").exists());
+}
+
+#[test]
+fn max_sensitivity() {
+    let tree = Tree::new().with_characteristics([
+        fixtures::proposed_characteristic(),
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+            common.set_name("A Germline Characteristic Name").unwrap();
+            common.set_sensitivity(Sensitivity::Restricted);
+        }),
+    ]);
+    let export = |args: &[&str]| {
+        ecc_cli(&tree)
+            .args(["ecc", "export", "--format", "ndjson", "-o", "dist"])
+            .args(args)
+            .assert()
+            .success();
+
+        std::fs::read_to_string(tree.path().join("dist/characteristics.ndjson")).unwrap()
+    };
+
+    let ndjson = export(&[]);
+    assert_eq!(ndjson.lines().count(), 1);
+    assert!(!ndjson.contains("Germline"));
+
+    assert_eq!(
+        export(&["--max-sensitivity", "controlled"]).lines().count(),
+        1
+    );
+
+    let ndjson = export(&["--max-sensitivity", "restricted"]);
+    assert_eq!(ndjson.lines().count(), 2);
+    assert!(ndjson.contains("\"sensitivity\":\"restricted\""));
+}
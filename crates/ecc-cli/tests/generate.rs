@@ -0,0 +1,83 @@
+//! Integration tests for `ecc-cli generate`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with an adopted characteristic without stewards and a draft
+/// with two stewards.
+fn tree() -> Tree {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    tree.write(
+        "ecc/morph/a-stewarded-draft.yml",
+        "state: draft\nname: A Stewarded Draft\nstewards:\n  - name: Jane Smith\n    github: \
+         jsmith\n    email: jane.smith@example.com\n  - name: John Doe\n    github: jdoe\n    \
+         email: john.doe@example.com\n",
+    );
+    tree
+}
+
+#[test]
+fn code_owners() {
+    ecc_cli(&tree())
+        .args(["generate", "code-owners"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "# Generated by `ecc-cli generate code-owners`",
+        ))
+        .stdout(predicate::str::contains(
+            "\n/ecc/morph/a-stewarded-draft.yml @jsmith @jdoe\n",
+        ))
+        .stdout(predicate::str::contains("@").count(2));
+
+    ecc_cli(&tree())
+        .args(["generate", "code-owners", "--format", "yaml"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "ecc/morph/a-stewarded-draft.yml:\n- '@jsmith'\n- '@jdoe'\n",
+        ));
+}
+
+#[test]
+fn check() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args([
+            "generate",
+            "code-owners",
+            "--output",
+            "CODEOWNERS",
+            "--check",
+        ])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("`CODEOWNERS` is out of date"));
+
+    ecc_cli(&tree)
+        .args(["generate", "code-owners", "--output", "CODEOWNERS"])
+        .assert()
+        .success();
+    assert!(
+        std::fs::read_to_string(tree.path().join("CODEOWNERS"))
+            .unwrap()
+            .contains("@jsmith @jdoe")
+    );
+
+    ecc_cli(&tree)
+        .args([
+            "generate",
+            "code-owners",
+            "--output",
+            "CODEOWNERS",
+            "--check",
+        ])
+        .assert()
+        .success();
+}
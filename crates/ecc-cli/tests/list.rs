@@ -0,0 +1,65 @@
+//! Integration tests for `ecc-cli ecc list`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with a proposed and an adopted characteristic.
+fn tree() -> Tree {
+    Tree::new().with_characteristics([
+        fixtures::proposed_characteristic(),
+        fixtures::adopted_characteristic_with(|common| {
+            common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+            common.set_name("Another Characteristic Name").unwrap();
+        }),
+    ])
+}
+
+#[test]
+fn list() {
+    ecc_cli(&tree())
+        .args(["ecc", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "IDENTIFIER        NAME                         STATE     ADOPTED\nECC-MORPH-000001  \
+             A Characteristic Name        proposed  -\nECC-MORPH-000002  Another Characteristic \
+             Name  adopted   2024-01-01\n",
+        ));
+}
+
+#[test]
+fn sort() {
+    ecc_cli(&tree())
+        .args(["ecc", "list", "--sort", "state", "--reverse"])
+        .assert()
+        .success()
+        .stdout(predicate::str::is_match("(?s)000002.*000001").unwrap());
+}
+
+#[test]
+fn filter() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args(["ecc", "list", "--filter", "state:adopted category:morph"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ECC-MORPH-000001").not())
+        .stdout(predicate::str::contains("ECC-MORPH-000002"));
+
+    ecc_cli(&tree)
+        .args(["ecc", "list", "--filter", "category:molec"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("ECC-").not());
+
+    ecc_cli(&tree)
+        .args(["ecc", "list", "--filter", "status:adopted"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("unknown filter key `status`"));
+}
@@ -0,0 +1,602 @@
+//! Integration tests for `ecc-cli ontology`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::CONFIG;
+use test_infra::tree::Tree;
+
+/// A small ontology in the format read by `ontology init`.
+const TSV: &str = "\
+name\tparent\tcode
+Leukemia\t\tLEUK
+B-cell Lymphoblastic Leukemia\tLeukemia\tBLL
+T-cell Lymphoblastic Leukemia\tLeukemia\tTLL
+";
+
+#[test]
+fn check() {
+    let tree = Tree::new().with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checked 5 nodes.. OK"));
+}
+
+#[test]
+fn check_constraint_violation() {
+    let graph = fixtures::small_ontology_with(|nodes| {
+        nodes[3] = fixtures::node(
+            "B-cell Acute Lymphoblastic Leukemia, BCR::ABL1",
+            "B-cell Lymphoblastic Leukemia",
+            "BALLBCRABL1",
+        )
+        .try_build()
+        .unwrap();
+    });
+    let tree = Tree::new().with_ontology(&graph);
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("`ECC-MOLEC-000001` must be bound"))
+        .stdout(predicate::str::contains("constraint declared here"))
+        .stdout(predicate::str::contains(
+            "Checked 5 nodes.. FAIL (1 problems)",
+        ));
+}
+
+#[test]
+fn check_similar_names() {
+    let graph = fixtures::small_ontology_with(|nodes| {
+        nodes.push(
+            fixtures::node("B-cell Lymphoblastic Leukaemia", "Leukemia", "BLL2")
+                .try_build()
+                .unwrap(),
+        );
+    });
+    let tree = Tree::new().with_ontology(&graph);
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "`B-cell Lymphoblastic Leukaemia` and `B-cell Lymphoblastic Leukemia` differ by a \
+             single edit",
+        ))
+        .stdout(predicate::str::contains(
+            "Checked 6 nodes.. OK (1 warnings)",
+        ));
+
+    tree.write(
+        CONFIG,
+        "[directories]\nontology = \"ontology\"\n\n[lints]\nsimilar-names = \"deny\"\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "Checked 6 nodes.. FAIL (1 problems)",
+        ));
+}
+
+#[test]
+fn init() {
+    let tree = Tree::new();
+    let tsv = tree.write("ontology.tsv", TSV);
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success();
+
+    assert!(tree.ontology().join("leukemia.yml").exists());
+    assert!(
+        tree.ontology()
+            .join("leukemia/t-cell-lymphoblastic-leukemia.yml")
+            .exists()
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checked 3 nodes.. OK"));
+}
+
+#[test]
+fn init_invalid_rows() {
+    let tree = Tree::new();
+    let tsv = tree.write("ontology.tsv", format!("{TSV}\tLeukemia\tEMPTY\n"));
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(":5: the name is empty"))
+        .stderr(predicate::str::contains("1 invalid rows"));
+
+    ecc_cli(&tree)
+        .args(["ontology", "init", "--report"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "skipped line 5: the name is empty",
+        ));
+}
+
+#[test]
+fn export() {
+    let tree = Tree::new();
+    let tsv = tree.write(
+        "ontology.tsv",
+        "name\tparent\tcode\torder\nLeukemia\t\tLEUK\t\nT-cell Lymphoblastic \
+         Leukemia\tLeukemia\tTLL\t\nB-cell Lymphoblastic Leukemia\tLeukemia\tBLL\t\nMixed \
+         Phenotype Acute Leukemia\tLeukemia\tMPAL\t1\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success();
+
+    ecc_cli(&tree)
+        .args(["ontology", "export", "--format", "tsv"])
+        .assert()
+        .success()
+        .stdout(
+            [
+                "name\tparent\tcode\torder\tobsolete\treplaced_by\tcharacteristics\ttranslations",
+                "Leukemia\t\tLEUK\t\tfalse\t\t\t",
+                "Mixed Phenotype Acute Leukemia\tLeukemia\tMPAL\t1\tfalse\t\t\t",
+                "B-cell Lymphoblastic Leukemia\tLeukemia\tBLL\t\tfalse\t\t\t",
+                "T-cell Lymphoblastic Leukemia\tLeukemia\tTLL\t\tfalse\t\t\t",
+                "",
+            ]
+            .join("\n"),
+        );
+
+    ecc_cli(&tree)
+        .args(["ontology", "export", "--format", "dot"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "    \"LEUK\" -> \"MPAL\";\n    \"LEUK\" -> \"BLL\";\n    \"LEUK\" -> \"TLL\";\n",
+        ));
+
+    ecc_cli(&tree)
+        .args(["ontology", "export", "--format", "mermaid"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "flowchart TD\n    n0[\"Leukemia\"]\n    n1[\"Mixed Phenotype Acute Leukemia\"]\n",
+        ));
+}
+
+#[test]
+fn init_translations() {
+    let tree = Tree::new();
+    let tsv = tree.write(
+        "ontology.tsv",
+        "name\tparent\tcode\ttranslations\nLeukemia\t\tLEUK\tde=Leukämie; pt-br = \
+         Leucemia\nLymphoma\tLeukemia\tLYM\t\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success();
+
+    ecc_cli(&tree)
+        .args(["ontology", "export", "--format", "tsv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "Leukemia\t\tLEUK\t\tfalse\t\t\tde=Leukämie; pt-BR=Leucemia\n",
+        ));
+
+    let tsv = tree.write(
+        "invalid.tsv",
+        "name\tparent\tcode\ttranslations\nLeukemia\t\tLEUK\t\nAcute Lymphoblastic \
+         Leukemia\tLeukemia\tALL\ten_US=Leukemia\nMyeloma\tLeukemia\tMYE\tde\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.path().join("invalid"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "invalid.tsv:3: invalid translation `en_US=Leukemia`: `en_US` is not a well-formed \
+             BCP 47 language tag",
+        ))
+        .stderr(predicate::str::contains(
+            "invalid.tsv:4: invalid translation `de`: expected a language tag followed by `=` and \
+             the translated name",
+        ));
+}
+
+#[test]
+fn init_obsolete() {
+    let tree = Tree::new();
+    let tsv = tree.write(
+        "ontology.tsv",
+        format!(
+            "{}\tobsolete\treplaced_by\n{}Lymphoblastic Leukemia\tLeukemia\tLL\ttrue\tB-cell \
+             Lymphoblastic Leukemia\n",
+            TSV.lines().next().unwrap(),
+            TSV.lines()
+                .skip(1)
+                .map(|line| format!("{line}\t\t\n"))
+                .collect::<String>()
+        ),
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success();
+
+    assert!(
+        !tree
+            .ontology()
+            .join("leukemia/lymphoblastic-leukemia.yml")
+            .exists()
+    );
+
+    let output = tree.path().join("with-obsolete");
+
+    ecc_cli(&tree)
+        .args(["ontology", "init", "--include-obsolete"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .success();
+
+    let node = std::fs::read_to_string(output.join("leukemia/lymphoblastic-leukemia.yml")).unwrap();
+    assert!(node.contains("obsolete: true"));
+    assert!(node.contains("replaced_by: B-cell Lymphoblastic Leukemia"));
+
+    let tsv = tree.write(
+        "invalid.tsv",
+        format!(
+            "{}\tobsolete\treplaced_by\n{}Lymphoblastic Leukemia\tLeukemia\tLL\ttrue\tMissing\n",
+            TSV.lines().next().unwrap(),
+            TSV.lines()
+                .skip(1)
+                .map(|line| format!("{line}\t\t\n"))
+                .collect::<String>()
+        ),
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.path().join("invalid"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "replacement `Missing` of node `Lymphoblastic Leukemia` does not exist",
+        ));
+}
+
+#[test]
+fn init_layouts() {
+    let tree = Tree::new();
+    let tsv = tree.write("ontology.tsv", TSV);
+
+    ecc_cli(&tree)
+        .args(["ontology", "init", "--layout", "by-code"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success();
+
+    assert!(tree.ontology().join("TLL.yml").exists());
+
+    ecc_cli(&tree)
+        .args(["ontology", "check", "--layout", "by-code"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checked 3 nodes.. OK"));
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains(
+            "not located where the layout dictates",
+        ));
+
+    let flat = tree.path().join("flat");
+
+    ecc_cli(&tree)
+        .args(["ontology", "init", "--layout", "flat"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(&flat)
+        .assert()
+        .success();
+
+    assert!(flat.join("t-cell-lymphoblastic-leukemia.yml").exists());
+
+    // NOTE: the layout can also be configured.
+    tree.write(
+        CONFIG,
+        "[directories]\ncharacteristics = \"ecc\"\nontology = \"flat\"\nontology_layout = \
+         \"flat\"\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "move-node", "--name"])
+        .arg("T-cell Lymphoblastic Leukemia")
+        .arg("--parent")
+        .arg("B-cell Lymphoblastic Leukemia")
+        .assert()
+        .success();
+
+    assert!(flat.join("t-cell-lymphoblastic-leukemia.yml").exists());
+    assert!(
+        std::fs::read_to_string(flat.join("t-cell-lymphoblastic-leukemia.yml"))
+            .unwrap()
+            .contains("parent: B-cell Lymphoblastic Leukemia")
+    );
+}
+
+#[test]
+fn init_xlsx() {
+    let tree = Tree::new();
+    let workbook =
+        std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/ontology.xlsx");
+
+    ecc_cli(&tree)
+        .args(["ontology", "init", "--sheet", "Ontology"])
+        .arg(&workbook)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success();
+
+    assert!(
+        tree.ontology()
+            .join("leukemia/t-cell-lymphoblastic-leukemia.yml")
+            .exists()
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "export", "--format", "tsv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "B-cell Lymphoblastic Leukemia\tLeukemia\tBLL\t1\t",
+        ));
+
+    // NOTE: the first sheet is read by default.
+    ecc_cli(&tree)
+        .args(["ontology", "init", "--format", "xlsx"])
+        .arg(&workbook)
+        .arg("-o")
+        .arg(tree.path().join("notes"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("missing columns"));
+
+    ecc_cli(&tree)
+        .args(["ontology", "init", "--sheet", "Missing"])
+        .arg(&workbook)
+        .arg("-o")
+        .arg(tree.path().join("missing"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "found sheets: `Notes`, `Ontology`",
+        ));
+}
+
+#[test]
+fn init_characteristics() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    let tsv = tree.write(
+        "ontology.tsv",
+        "name\tparent\tcode\tcharacteristics\nLeukemia\t\tLEUK\t\nB-cell Lymphoblastic \
+         Leukemia\tLeukemia\tBLL\tECC-MORPH-000001=true\nT-cell Lymphoblastic \
+         Leukemia\tLeukemia\tTLL\tECC-MORPH-000001 = false;\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.ontology())
+        .assert()
+        .success();
+
+    ecc_cli(&tree)
+        .args(["ontology", "export", "--format", "tsv"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "B-cell Lymphoblastic Leukemia\tLeukemia\tBLL\t\tfalse\t\tECC-MORPH-000001=true\t\n",
+        ));
+
+    let tsv = tree.write(
+        "invalid.tsv",
+        "name\tparent\tcode\tcharacteristics\nLeukemia\t\tLEUK\t\nB-cell Lymphoblastic \
+         Leukemia\tLeukemia\tBLL\tECC-MORPH-000001=maybe\nT-cell Lymphoblastic \
+         Leukemia\tLeukemia\tTLL\tECC-MOLEC-000009\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ontology", "init"])
+        .arg(&tsv)
+        .arg("-o")
+        .arg(tree.path().join("invalid"))
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid.tsv:3:"))
+        .stderr(predicate::str::contains(
+            "invalid.tsv:4: unknown characteristic: `ECC-MOLEC-000009`",
+        ));
+}
+
+#[test]
+fn add_node() {
+    let tree = Tree::new().with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .args(["ontology", "add-node", "--name", "Acute Myeloid Leukemia"])
+        .args(["--parent", "Leukemia", "--code", "AML"])
+        .assert()
+        .success();
+
+    let file = tree.ontology().join("leukemia/acute-myeloid-leukemia.yml");
+    let contents = std::fs::read_to_string(file).unwrap();
+    assert!(contents.contains("name: Acute Myeloid Leukemia"));
+    assert!(contents.contains("parent: Leukemia"));
+    assert!(contents.contains("code: AML"));
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checked 6 nodes.. OK"));
+}
+
+#[test]
+fn add_node_duplicate() {
+    let tree = Tree::new().with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .args([
+            "ontology",
+            "add-node",
+            "--name",
+            "T-cell Lymphoblastic Leukemia",
+        ])
+        .args([
+            "--parent",
+            "B-cell Lymphoblastic Leukemia",
+            "--code",
+            "TLL2",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "node name `T-cell Lymphoblastic Leukemia` is used 2 times",
+        ));
+
+    assert!(
+        !tree
+            .ontology()
+            .join("leukemia/b-cell-lymphoblastic-leukemia/t-cell-lymphoblastic-leukemia.yml")
+            .exists()
+    );
+}
+
+#[test]
+fn move_node() {
+    let tree = Tree::new().with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .args([
+            "ontology",
+            "move-node",
+            "--name",
+            "B-cell Lymphoblastic Leukemia",
+        ])
+        .args(["--parent", "T-cell Lymphoblastic Leukemia"])
+        .assert()
+        .success();
+
+    // NOTE: the descendants of the moved node are relocated along with it,
+    // and the directories left empty are removed.
+    let ontology = tree.ontology();
+    let moved = ontology.join("leukemia/t-cell-lymphoblastic-leukemia");
+    assert!(moved.join("b-cell-lymphoblastic-leukemia.yml").exists());
+    assert_eq!(
+        std::fs::read_dir(moved.join("b-cell-lymphoblastic-leukemia"))
+            .unwrap()
+            .count(),
+        2
+    );
+    assert!(
+        !ontology
+            .join("leukemia/b-cell-lymphoblastic-leukemia.yml")
+            .exists()
+    );
+    assert!(
+        !ontology
+            .join("leukemia/b-cell-lymphoblastic-leukemia")
+            .exists()
+    );
+
+    let contents =
+        std::fs::read_to_string(moved.join("b-cell-lymphoblastic-leukemia.yml")).unwrap();
+    assert!(contents.contains("parent: T-cell Lymphoblastic Leukemia"));
+
+    ecc_cli(&tree)
+        .args(["ontology", "check"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Checked 5 nodes.. OK"));
+}
+
+#[test]
+fn move_node_under_descendant() {
+    let graph = fixtures::small_ontology();
+    let tree = Tree::new().with_ontology(&graph);
+
+    ecc_cli(&tree)
+        .args([
+            "ontology",
+            "move-node",
+            "--name",
+            "B-cell Lymphoblastic Leukemia",
+        ])
+        .args([
+            "--parent",
+            "B-cell Acute Lymphoblastic Leukemia, ETV6::RUNX1",
+        ])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "found a cycle between nodes: B-cell Lymphoblastic Leukemia → B-cell Acute \
+             Lymphoblastic Leukemia, ETV6::RUNX1",
+        ));
+
+    // NOTE: no files are moved when the move is refused.
+    for node in graph.nodes() {
+        assert!(tree.path().join(Tree::node_path(&graph, node)).exists());
+    }
+}
@@ -0,0 +1,159 @@
+//! Integration tests for `ecc-cli ecc promote`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+#[test]
+fn lifecycle() {
+    let tree = Tree::new();
+    let file = tree.write(
+        "ecc/morph/a-characteristic-name.yml",
+        "state: draft\nidentifier: ECC-MORPH-000001\nname: A Characteristic Name\nrfc: \
+         https://github.com/stjudecloud/ecc/issues/1\ndescription: A description of the \
+         characteristic.\nvalues:\n  kind: categorical\n  options: [Foo, Bar]\ncreated_at: \
+         2024-01-01T00:00:00Z\n",
+    );
+
+    for date in [
+        "2024-02-01T00:00:00Z",
+        "2024-03-01T00:00:00Z",
+        "2024-04-01T00:00:00Z",
+    ] {
+        ecc_cli(&tree)
+            .args(["ecc", "promote"])
+            .arg(&file)
+            .args(["--date", date])
+            .assert()
+            .success();
+    }
+
+    let contents = std::fs::read_to_string(&file).unwrap();
+    assert!(contents.starts_with("state: adopted\nadoption_date: 2024-04-01T00:00:00Z\n"));
+    assert!(!contents.contains("created_at"));
+
+    let log = std::fs::read_to_string(tree.path().join("events.jsonl")).unwrap();
+    let events = ecc::event::read(log.as_bytes()).unwrap();
+    assert_eq!(
+        events
+            .iter()
+            .map(|event| (event.state().as_str(), event.date().to_rfc3339()))
+            .collect::<Vec<_>>(),
+        [
+            ("proposed", String::from("2024-02-01T00:00:00+00:00")),
+            ("provisional", String::from("2024-03-01T00:00:00+00:00")),
+            ("adopted", String::from("2024-04-01T00:00:00+00:00")),
+        ]
+    );
+
+    ecc_cli(&tree)
+        .args(["ecc", "promote"])
+        .arg(&file)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("has already been adopted"));
+}
+
+#[test]
+fn incomplete_draft() {
+    let tree = Tree::new();
+    let file = tree.write("ecc/morph/a-draft.yml", "state: draft\nname: A Draft\n");
+
+    ecc_cli(&tree)
+        .args(["ecc", "promote"])
+        .arg(&file)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "cannot be proposed until it has the following fields: `identifier`, `rfc`, \
+             `description`, `values`",
+        ));
+    assert!(!tree.path().join("events.jsonl").exists());
+}
+
+#[test]
+fn other_kind_draft() {
+    let tree = Tree::new();
+    let contents = "state: draft\nidentifier: ECC-MORPH-000001\nname: A Characteristic Name\nrfc: \
+                    https://github.com/stjudecloud/ecc/issues/1\ndescription: A description of the \
+                    characteristic.\nvalues:\n  kind: other\n  description: A count of cells.\n";
+    let file = tree.write("ecc/morph/a-characteristic-name.yml", contents);
+
+    // NOTE: only drafts can use other kinds of values, so the kind must be
+    // replaced before the draft is proposed.
+    ecc_cli(&tree)
+        .args(["ecc", "promote"])
+        .arg(&file)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "cannot be proposed until it has the following fields: `values.kind`",
+        ));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), contents);
+    assert!(!tree.path().join("events.jsonl").exists());
+
+    let file = tree.write(
+        "ecc/morph/a-draft.yml",
+        "state: draft\nname: A Draft\nvalues:\n  kind: other\n  description: A count of cells.\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ecc", "promote"])
+        .arg(&file)
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "cannot be proposed until it has the following fields: `identifier`, `rfc`, \
+             `description`, `values.kind`",
+        ));
+}
+
+#[test]
+fn dry_run() {
+    let tree = Tree::new().with_characteristic(fixtures::proposed_characteristic());
+    let file = tree
+        .characteristics()
+        .join("morph/a-characteristic-name.yml");
+
+    ecc_cli(&tree)
+        .args(["ecc", "promote", "--dry-run", "--date", "2024-01-01T00:00:00Z"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            r#"{"event":"provisional","identifier":"ECC-MORPH-000001","date":"2024-01-01T00:00:00Z""#,
+        ));
+    assert!(
+        std::fs::read_to_string(&file)
+            .unwrap()
+            .contains("state: proposed")
+    );
+}
+
+#[test]
+fn unwritable_log() {
+    let tree = Tree::new().with_characteristic(fixtures::proposed_characteristic());
+    let file = tree
+        .characteristics()
+        .join("morph/a-characteristic-name.yml");
+    let before = std::fs::read_to_string(&file).unwrap();
+
+    ecc_cli(&tree)
+        .args(["ecc", "promote"])
+        .arg(&file)
+        .arg("--events")
+        .arg(tree.path().join("missing/events.jsonl"))
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains("opening event log"));
+
+    // NOTE: the transition is never written without being logged.
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), before);
+    assert_eq!(
+        std::fs::read_dir(file.parent().unwrap()).unwrap().count(),
+        1
+    );
+}
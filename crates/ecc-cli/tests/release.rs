@@ -0,0 +1,91 @@
+//! Integration tests for `ecc-cli release`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with a proposed and an adopted characteristic.
+fn tree() -> Tree {
+    Tree::new()
+        .with_characteristics([
+            fixtures::adopted_characteristic(),
+            fixtures::proposed_characteristic_with(|common| {
+                common.set_identifier(fixtures::identifier("ECC-MORPH-000002"));
+                common.set_name("Another Characteristic Name").unwrap();
+            }),
+        ])
+        .with_ontology(&fixtures::small_ontology())
+}
+
+#[test]
+fn release() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .arg("release")
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "Tag the release with: git tag v0.1.0\n",
+        ));
+
+    let directory = tree.path().join("release/0.1.0");
+
+    let manifest = std::fs::read_to_string(directory.join("release.json")).unwrap();
+    let manifest = serde_json::from_str::<serde_json::Value>(&manifest).unwrap();
+    assert_eq!(manifest["version"], "0.1.0");
+    assert_eq!(manifest["previous"], serde_json::Value::Null);
+
+    // NOTE: only the adopted characteristic is listed within the release.
+    let adopted = manifest["adopted"].as_array().unwrap();
+    assert_eq!(adopted.len(), 1);
+    assert_eq!(adopted[0]["identifier"], "ECC-MORPH-000001");
+    assert_eq!(adopted[0]["name"], "A Characteristic Name");
+    assert_eq!(adopted[0]["adoption_date"], "2024-01-01");
+
+    assert!(directory.join("manifest.json").exists());
+    assert!(!directory.join("manifest.json.sig").exists());
+    assert!(
+        directory
+            .join("characteristics/ECC-MORPH-000001.json")
+            .exists()
+    );
+    assert!(
+        directory
+            .join("characteristics/ECC-MORPH-000002.json")
+            .exists()
+    );
+
+    let changelog = std::fs::read_to_string(tree.path().join("CHANGELOG.md")).unwrap();
+    assert!(changelog.starts_with("# Changelog\n\n## 0.1.0 ("));
+    assert!(changelog.contains("### Adopted\n\n- `ECC-MORPH-000001` A Characteristic Name\n"));
+
+    // NOTE: a release cannot be prepared twice for the same version.
+    ecc_cli(&tree)
+        .args(["release", "--data-version", "0.1.0"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "the changelog already contains a section for version `0.1.0`",
+        ));
+}
+
+#[test]
+fn dry_run() {
+    let tree = tree();
+
+    ecc_cli(&tree)
+        .args(["release", "--bump", "major", "--dry-run"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with("## 1.0.0 ("))
+        .stdout(predicate::str::contains(
+            "- `ECC-MORPH-000001` A Characteristic Name\n",
+        ));
+
+    assert!(!tree.path().join("release").exists());
+    assert!(!tree.path().join("CHANGELOG.md").exists());
+}
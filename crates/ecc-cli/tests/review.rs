@@ -0,0 +1,92 @@
+//! Integration tests for `ecc-cli review`.
+
+mod common;
+
+use std::process::Command;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Runs a git command within a tree.
+fn git(tree: &Tree, args: &[&str]) {
+    let status = Command::new("git")
+        .args(["-c", "user.name=ECC", "-c", "user.email=ecc@example.com"])
+        .args(args)
+        .current_dir(tree.path())
+        .status()
+        .unwrap();
+    assert!(status.success(), "`git {}` failed", args.join(" "));
+}
+
+/// Gets a tree with a committed adopted characteristic and draft followed by
+/// uncommitted changes that modify the adopted characteristic, delete the
+/// draft, add a new draft, and add a file that is not a valid characteristic.
+fn tree() -> Tree {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    tree.write(
+        "ecc/morph/a-removed-draft.yml",
+        "state: draft\nname: A Removed Draft\n",
+    );
+
+    git(&tree, &["init", "--quiet"]);
+    git(&tree, &["add", "."]);
+    git(&tree, &["commit", "--quiet", "-m", "Initial commit"]);
+
+    let tree = tree.with_characteristic(fixtures::adopted_characteristic_with(|common| {
+        common
+            .set_description("An updated description of the characteristic.")
+            .unwrap();
+    }));
+    std::fs::remove_file(tree.path().join("ecc/morph/a-removed-draft.yml")).unwrap();
+    tree.write(
+        "ecc/morph/a-new-draft.yml",
+        "state: draft\nname: A New Draft\n",
+    );
+    tree.write("ecc/morph/a-broken-file.yml", "state: unknown\n");
+    tree
+}
+
+#[test]
+fn github_comment() {
+    ecc_cli(&tree())
+        .args(["review", "--base", "HEAD", "--format", "github-comment"])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "<!-- ecc-cli review -->\n### ECC review\n\nReviewed 4 changed files against `HEAD`.",
+        ))
+        .stdout(predicate::str::contains(
+            "<details open>\n<summary><code>ecc/morph/a-broken-file.yml</code> (invalid; 1 error, \
+             0 warnings)</summary>",
+        ))
+        .stdout(predicate::str::contains(
+            "<code>ecc/morph/a-new-draft.yml</code> (added;",
+        ))
+        .stdout(predicate::str::contains("- Added *unassigned* A New Draft"))
+        .stdout(predicate::str::contains(
+            "- Modified `ECC-MORPH-000001` A Characteristic Name\n  - description changed",
+        ))
+        .stdout(predicate::str::contains(
+            "<code>ecc/morph/a-removed-draft.yml</code> (deleted; 0 errors, 0 warnings)",
+        ))
+        .stdout(predicate::str::contains(
+            "- Removed *unassigned* A Removed Draft",
+        ));
+}
+
+#[test]
+fn unchanged() {
+    let tree = tree();
+    git(&tree, &["add", "."]);
+    git(&tree, &["commit", "--quiet", "-m", "Changes"]);
+
+    ecc_cli(&tree)
+        .args(["review", "--base", "HEAD"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "No characteristic files changed since `HEAD`.",
+        ));
+}
@@ -0,0 +1,91 @@
+//! Integration tests for `ecc-cli ecc rfc-body`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::tree::Tree;
+
+#[test]
+fn rfc_body() {
+    let tree = Tree::new();
+    let file = tree.write(
+        "ecc/morph/a-characteristic-name.yml",
+        "state: draft\nname: A Characteristic Name\ndescription: A description of the \
+         characteristic.\nvalues:\n  kind: categorical\n  options: [Foo, Bar]\nreferences:\n  - \
+         kind: manuscript\n    title: A Manuscript\n    authors: Doe J, Roe R\n    context: \
+         Describes the characteristic.\n    url: https://doi.org/10.1000/example\n    \
+         highlighted: true\n  - kind: preprint\n    title: A Preprint\n    authors: ''\n    \
+         context: Supports the characteristic.\n    url: \
+         https://www.biorxiv.org/content/10.1101/example\n    highlighted: false\ncreated_at: \
+         2024-01-01T00:00:00Z\n",
+    );
+
+    ecc_cli(&tree)
+        .args(["ecc", "rfc-body"])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(concat!(
+            "### Characteristic\n\n",
+            "| Field | Value |\n",
+            "| --- | --- |\n",
+            "| Name | A Characteristic Name |\n",
+            "| Category | morphological |\n",
+            "| State | draft |\n\n",
+            "### Description\n\n",
+            "A description of the characteristic.\n\n",
+            "### Evaluation\n\n",
+            "This characteristic is **categorical**.\n\n",
+            "- `Bar`\n",
+            "- `Foo`\n\n",
+            "### References\n\n",
+            "- [A Manuscript](https://doi.org/10.1000/example) ⭐ (manuscript; Doe J, Roe R): \
+             Describes the characteristic.\n",
+            "- [A Preprint](https://www.biorxiv.org/content/10.1101/example) (preprint): Supports \
+             the characteristic.\n",
+        )));
+}
+
+#[test]
+fn unfilled() {
+    let tree = Tree::new();
+    let file = tree.write(
+        "a-characteristic-name.yml",
+        "state: draft\nname: A Characteristic Name\ncreated_at: 2024-01-01T00:00:00Z\n",
+    );
+
+    ecc_cli(&tree)
+        .args([
+            "ecc",
+            "rfc-body",
+            "--category",
+            "molecular",
+            "--output",
+            "body.md",
+        ])
+        .arg(&file)
+        .assert()
+        .success()
+        .stdout(predicate::str::is_empty());
+
+    // NOTE: sections that are not yet filled in are rendered the same way
+    // GitHub renders empty issue form fields.
+    assert_eq!(
+        std::fs::read_to_string(tree.path().join("body.md")).unwrap(),
+        concat!(
+            "### Characteristic\n\n",
+            "| Field | Value |\n",
+            "| --- | --- |\n",
+            "| Name | A Characteristic Name |\n",
+            "| Category | molecular |\n",
+            "| State | draft |\n\n",
+            "### Description\n\n",
+            "_No response_\n\n",
+            "### Evaluation\n\n",
+            "_No response_\n\n",
+            "### References\n\n",
+            "_No response_\n",
+        )
+    );
+}
@@ -0,0 +1,114 @@
+//! Integration tests for `ecc-cli ecc show`.
+
+mod common;
+
+use common::ecc_cli;
+use ecc::Characteristic;
+use ecc::common::Applicability;
+use ecc::common::applicability::SpecimenType;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+#[test]
+fn terminal() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    ecc_cli(&tree)
+        .args(["ecc", "show", "ECC-MORPH-000001"])
+        .assert()
+        .success()
+        .stdout(predicate::str::diff(
+            "A Characteristic Name
+  Identifier  ECC-MORPH-000001
+  State       adopted
+  Adopted     \
+             2024-01-01
+  RFC         https://github.com/stjudecloud/ecc/issues/1
+
+Description
+  A description of the characteristic.
+
+Values (binary)
+  VALUE  DESCRIPTION
+  true   The feature is present. The feature was observed.
+  false  The feature is absent. The feature was not observed.
+",
+        ));
+}
+
+#[test]
+fn markdown() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    for query in ["ECC-MORPH-000001", "a characteristic name"] {
+        ecc_cli(&tree)
+            .args(["ecc", "show", query, "--format", "markdown"])
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("A Characteristic Name"))
+            .stdout(predicate::str::contains("| State | adopted |"));
+    }
+}
+
+#[test]
+fn applicability() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic_with(|common| {
+        common.set_applicability(Some(Applicability::new(
+            vec![String::from("Leukemia")],
+            vec![SpecimenType::Blood, SpecimenType::BoneMarrow],
+            Vec::new(),
+        )));
+    }));
+
+    ecc_cli(&tree)
+        .args(["ecc", "show", "ECC-MORPH-000001", "--format", "markdown"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "## Applies To\n\n- Ontology subtrees: `Leukemia`\n- Specimen types: `blood`, \
+             `bone-marrow`\n",
+        ));
+}
+
+#[test]
+fn json() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    let output = ecc_cli(&tree)
+        .args(["ecc", "show", "ECC-MORPH-000001", "--format", "json"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let characteristic = serde_json::from_slice::<Characteristic>(&output.stdout).unwrap();
+    assert_eq!(characteristic, fixtures::adopted_characteristic());
+}
+
+#[test]
+fn not_found() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    ecc_cli(&tree)
+        .args(["ecc", "show", "ECC-MORPH-000002"])
+        .assert()
+        .code(2)
+        .stderr(predicate::str::contains(
+            "no characteristic has the identifier or name `ECC-MORPH-000002`",
+        ));
+}
+
+#[test]
+fn root() {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    // NOTE: `--path` is kept as an alias of `--root`.
+    for flag in ["--root", "--path"] {
+        ecc_cli(&tree)
+            .args(["ecc", "show", "ECC-MORPH-000001", flag])
+            .arg(tree.characteristics())
+            .assert()
+            .success()
+            .stdout(predicate::str::contains("A Characteristic Name"));
+    }
+}
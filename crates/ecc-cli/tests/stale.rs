@@ -0,0 +1,76 @@
+//! Integration tests for `ecc-cli ecc stale`.
+
+mod common;
+
+use chrono::TimeDelta;
+use chrono::Utc;
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with an adopted characteristic and three drafts: one that was
+/// updated recently, one that was last updated long ago, and one without
+/// timestamps.
+fn tree() -> Tree {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+    let recent = (Utc::now() - TimeDelta::days(3)).to_rfc3339();
+
+    tree.write(
+        "ecc/morph/a-recent-draft.yml",
+        format!("state: draft\nname: A Recent Draft\ncreated_at: {recent}\n"),
+    );
+    tree.write(
+        "ecc/morph/a-stale-draft.yml",
+        "state: draft\nname: A Stale Draft\nrfc: https://github.com/stjudecloud/ecc/issues/7\n\
+         created_at: 2023-01-01T00:00:00Z\nupdated_at: 2024-01-01T00:00:00Z\n",
+    );
+    tree.write(
+        "ecc/morph/an-undated-draft.yml",
+        "state: draft\nname: An Undated Draft\n",
+    );
+    tree
+}
+
+#[test]
+fn table() {
+    ecc_cli(&tree())
+        .args(["ecc", "stale"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("2024-01-01  A Stale Draft"))
+        .stdout(predicate::str::contains(
+            "https://github.com/stjudecloud/ecc/issues/7",
+        ))
+        .stdout(predicate::str::contains("A Recent Draft").not())
+        .stdout(predicate::str::contains("An Undated Draft").not());
+
+    ecc_cli(&tree())
+        .args(["ecc", "stale", "--max-age-days", "100000"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "no drafts have gone more than 100000 days without being updated",
+        ));
+}
+
+#[test]
+fn markdown() {
+    ecc_cli(&tree())
+        .args([
+            "ecc",
+            "stale",
+            "--format",
+            "markdown",
+            "--max-age-days",
+            "30",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::starts_with(
+            "<!-- rfc: https://github.com/stjudecloud/ecc/issues/7 -->\n### Stale draft\n",
+        ))
+        .stdout(predicate::str::contains(
+            "(last updated on 2024-01-01), which is longer than the 30 days",
+        ));
+}
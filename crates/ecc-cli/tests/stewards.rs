@@ -0,0 +1,58 @@
+//! Integration tests for `ecc-cli ecc stewards`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with an adopted characteristic, a draft with a steward, and a
+/// draft without a steward.
+fn tree() -> Tree {
+    let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+
+    tree.write(
+        "ecc/morph/a-stewarded-draft.yml",
+        "state: draft\nname: A Stewarded Draft\nstewards:\n  - name: Jane Smith\n    github: \
+         jsmith\n    email: jane.smith@example.com\n",
+    );
+    tree.write(
+        "ecc/morph/an-unassigned-draft.yml",
+        "state: draft\nname: An Unassigned Draft\n",
+    );
+    tree
+}
+
+#[test]
+fn table() {
+    ecc_cli(&tree())
+        .args(["ecc", "stewards"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("@jsmith (Jane Smith) (1)"))
+        .stdout(predicate::str::contains("A Stewarded Draft"))
+        .stdout(predicate::str::contains("Unassigned (1)"))
+        .stdout(predicate::str::contains("An Unassigned Draft"))
+        .stdout(predicate::str::contains("adopted").not());
+}
+
+#[test]
+fn json() {
+    ecc_cli(&tree())
+        .args(["ecc", "stewards", "--format", "json"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"jsmith\": {"))
+        .stdout(predicate::str::contains("\"name\": \"Jane Smith\""))
+        .stdout(predicate::str::contains("\"unassigned\": ["));
+}
+
+#[test]
+fn empty() {
+    ecc_cli(&Tree::new().with_characteristic(fixtures::adopted_characteristic()))
+        .args(["ecc", "stewards"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("no open drafts or proposals"));
+}
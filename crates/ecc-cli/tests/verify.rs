@@ -0,0 +1,74 @@
+//! Integration tests for `ecc-cli verify`.
+
+mod common;
+
+use common::ecc_cli;
+use predicates::prelude::*;
+use test_infra::fixtures;
+use test_infra::tree::Tree;
+
+/// Gets a tree with a bundle built into the `bundle` directory.
+fn tree() -> Tree {
+    let tree = Tree::new()
+        .with_characteristic(fixtures::adopted_characteristic())
+        .with_ontology(&fixtures::small_ontology());
+
+    ecc_cli(&tree)
+        .args(["build", "--output-directory", "bundle"])
+        .assert()
+        .success();
+
+    tree
+}
+
+#[test]
+fn verify() {
+    let tree = tree();
+
+    // NOTE: reformatting a characteristic document does not change its
+    // content hash.
+    let path = tree
+        .path()
+        .join("bundle/characteristics/ECC-MORPH-000001.json");
+    let characteristic =
+        serde_json::from_str::<serde_json::Value>(&std::fs::read_to_string(&path).unwrap())
+            .unwrap();
+    std::fs::write(&path, serde_json::to_string(&characteristic).unwrap()).unwrap();
+
+    ecc_cli(&tree)
+        .args(["verify", "bundle"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("index.json.. OK"))
+        .stdout(predicate::str::contains(
+            "characteristics/ECC-MORPH-000001.json.. OK",
+        ))
+        .stdout(predicate::str::contains("FAIL").not());
+}
+
+#[test]
+fn tampered() {
+    let tree = tree();
+
+    let index = tree.path().join("bundle/index.json");
+    let contents = std::fs::read_to_string(&index).unwrap();
+    std::fs::write(&index, contents.replace("adopted", "proposed")).unwrap();
+    std::fs::remove_file(
+        tree.path()
+            .join("bundle/characteristics/ECC-MORPH-000001.md"),
+    )
+    .unwrap();
+
+    ecc_cli(&tree)
+        .args(["verify", "bundle"])
+        .assert()
+        .code(1)
+        .stdout(predicate::str::contains("index.json.. FAIL (size mismatch"))
+        .stdout(predicate::str::contains(
+            "characteristics/ECC-MORPH-000001.md.. FAIL (unable to read file",
+        ))
+        .stdout(predicate::str::contains(
+            "characteristics/ECC-MORPH-000001.json.. OK",
+        ))
+        .stdout(predicate::str::contains("Summary: 2 of"));
+}
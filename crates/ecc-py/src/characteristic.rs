@@ -0,0 +1,287 @@
+//! Bindings for characteristics.
+
+use std::path::PathBuf;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::to_python;
+
+/// The separator used when joining multiple values into a single cell.
+const SEPARATOR: &str = ";";
+
+/// A characteristic identifier (e.g., `ECC-MORPH-000001`).
+#[pyclass(module = "ecc", frozen, eq, hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Identifier(ecc::Identifier);
+
+#[pymethods]
+impl Identifier {
+    /// Parses an identifier.
+    #[new]
+    fn new(value: &str) -> PyResult<Self> {
+        value
+            .parse::<ecc::Identifier>()
+            .map(Self)
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// The category (e.g., `morphological`).
+    #[getter]
+    fn category(&self) -> String {
+        self.0.category().to_string()
+    }
+
+    /// The number within the category.
+    #[getter]
+    fn number(&self) -> u64 {
+        self.0.number().get()
+    }
+
+    /// Formats the identifier.
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    /// Gets the representation within Python.
+    fn __repr__(&self) -> String {
+        format!("Identifier('{}')", self.0)
+    }
+}
+
+/// The kind of values that a characteristic takes on.
+#[pyclass(module = "ecc", frozen)]
+#[derive(Clone)]
+pub struct Kind(ecc::common::value::Kind);
+
+#[pymethods]
+impl Kind {
+    /// The name of the kind (`binary`, `categorical`, or `numerical`).
+    #[getter]
+    fn name(&self) -> &'static str {
+        self.0.name()
+    }
+
+    /// The permissible values (for categorical and binary characteristics).
+    #[getter]
+    fn options(&self) -> Option<Vec<String>> {
+        match &self.0 {
+            ecc::common::value::Kind::Binary { .. } => {
+                Some(vec![String::from("true"), String::from("false")])
+            }
+            ecc::common::value::Kind::Categorical { options } => {
+                Some(options.iter().cloned().collect())
+            }
+            ecc::common::value::Kind::Numerical { .. }
+            | ecc::common::value::Kind::Fraction { .. }
+            | ecc::common::value::Kind::Immunophenotype { .. }
+            | ecc::common::value::Kind::Other { .. } => None,
+        }
+    }
+
+    /// The threshold that the characteristic is derived from (for derived
+    /// binary characteristics), such as `ECC-MOLEC-000042 >= 0.01 %`.
+    #[getter]
+    fn threshold(&self) -> Option<String> {
+        match &self.0 {
+            ecc::common::value::Kind::Binary {
+                threshold: Some(threshold),
+                ..
+            } => Some(threshold.to_string()),
+            _ => None,
+        }
+    }
+
+    /// The markers within the panel (for immunophenotypic characteristics).
+    #[getter]
+    fn markers(&self) -> Option<Vec<String>> {
+        match &self.0 {
+            ecc::common::value::Kind::Immunophenotype { markers } => {
+                Some(markers.iter().map(ToString::to_string).collect())
+            }
+            _ => None,
+        }
+    }
+
+    /// The numerical type (for numerical characteristics).
+    #[getter]
+    fn numerical_type(&self) -> Option<String> {
+        match &self.0 {
+            ecc::common::value::Kind::Numerical { r#type, .. } => Some(r#type.to_string()),
+            _ => None,
+        }
+    }
+
+    /// The units of measurement (for numerical and fractional
+    /// characteristics).
+    #[getter]
+    fn units(&self) -> Option<String> {
+        match &self.0 {
+            ecc::common::value::Kind::Numerical { units, .. } => Some(units.clone()),
+            ecc::common::value::Kind::Fraction { scale, .. } => Some(scale.units().to_string()),
+            _ => None,
+        }
+    }
+
+    /// The description of the whole (for fractional characteristics).
+    #[getter]
+    fn denominator_description(&self) -> Option<String> {
+        match &self.0 {
+            ecc::common::value::Kind::Fraction {
+                denominator_description,
+                ..
+            } => Some(denominator_description.clone()),
+            _ => None,
+        }
+    }
+
+    /// Converts the kind into a dictionary.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.0)
+    }
+
+    /// Gets the representation within Python.
+    fn __repr__(&self) -> String {
+        format!("Kind('{}')", self.0.name())
+    }
+}
+
+/// A composable characteristic.
+#[pyclass(module = "ecc", frozen)]
+#[derive(Clone)]
+pub struct Characteristic(ecc::Characteristic);
+
+impl From<ecc::Characteristic> for Characteristic {
+    fn from(characteristic: ecc::Characteristic) -> Self {
+        Self(characteristic)
+    }
+}
+
+#[pymethods]
+impl Characteristic {
+    /// Parses a characteristic from a YAML string.
+    #[staticmethod]
+    fn from_yaml(source: &str) -> PyResult<Self> {
+        ecc::io::from_str(source)
+            .map(Self)
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// Reads a characteristic from a YAML file.
+    #[staticmethod]
+    fn read(path: PathBuf) -> PyResult<Self> {
+        ecc::io::from_path(path)
+            .map(Self)
+            .map_err(|err| PyValueError::new_err(err.to_string()))
+    }
+
+    /// The state (`draft`, `proposed`, `provisional`, or `adopted`).
+    #[getter]
+    fn state(&self) -> &'static str {
+        self.0.state().as_str()
+    }
+
+    /// The identifier (if one has been assigned).
+    #[getter]
+    fn identifier(&self) -> Option<Identifier> {
+        self.0.identifier().cloned().map(Identifier)
+    }
+
+    /// The name.
+    #[getter]
+    fn name(&self) -> Option<&str> {
+        self.0.name()
+    }
+
+    /// Gets the name to display in a language (a BCP 47 language tag such as
+    /// `de` or `pt-BR`), falling back to the English name.
+    fn display_name(&self, language: &str) -> PyResult<Option<&str>> {
+        let language = language
+            .parse::<ecc::text::LanguageTag>()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        Ok(self.0.display_name(&language))
+    }
+
+    /// The evidence level (e.g., `single-study`), if it has been graded.
+    #[getter]
+    fn evidence_level(&self) -> Option<&'static str> {
+        self.0.evidence_level().map(|level| level.as_str())
+    }
+
+    /// The sensitivity (`public`, `controlled`, or `restricted`).
+    #[getter]
+    fn sensitivity(&self) -> &'static str {
+        self.0.sensitivity().as_str()
+    }
+
+    /// The description.
+    #[getter]
+    fn description(&self) -> Option<&str> {
+        self.0.description()
+    }
+
+    /// The URL of the associated RFC.
+    #[getter]
+    fn rfc(&self) -> Option<&str> {
+        self.0.rfc().map(|rfc| rfc.as_str())
+    }
+
+    /// The permissible values.
+    #[getter]
+    fn values(&self) -> Option<Kind> {
+        self.0.values().cloned().map(Kind)
+    }
+
+    /// The adoption date as an RFC 3339 string (if adopted).
+    #[getter]
+    fn adoption_date(&self) -> Option<String> {
+        self.0.adoption_date().map(|date| date.to_rfc3339())
+    }
+
+    /// Converts the characteristic into a (nested) dictionary.
+    fn to_dict(&self, py: Python<'_>) -> PyResult<PyObject> {
+        to_python(py, &self.0)
+    }
+
+    /// Converts the characteristic into a flat dictionary suitable for a row
+    /// within a `pandas.DataFrame`.
+    ///
+    /// Multiple values (e.g., options) are joined with `;`.
+    fn to_record<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let values = self.values();
+        let record = PyDict::new(py);
+
+        record.set_item("identifier", self.identifier().map(|i| i.__str__()))?;
+        record.set_item("name", self.name())?;
+        record.set_item("state", self.state())?;
+        record.set_item("rfc", self.rfc())?;
+        record.set_item("kind", values.as_ref().map(|kind| kind.name()))?;
+        record.set_item(
+            "options",
+            values
+                .as_ref()
+                .and_then(Kind::options)
+                .map(|options| options.join(SEPARATOR)),
+        )?;
+        record.set_item(
+            "numerical_type",
+            values.as_ref().and_then(Kind::numerical_type),
+        )?;
+        record.set_item("units", values.as_ref().and_then(Kind::units))?;
+        record.set_item("adoption_date", self.adoption_date())?;
+
+        Ok(record)
+    }
+
+    /// Gets the representation within Python.
+    fn __repr__(&self) -> String {
+        match (self.0.identifier(), self.0.name()) {
+            (Some(identifier), Some(name)) => {
+                format!("Characteristic('{identifier}', '{name}')")
+            }
+            (None, Some(name)) => format!("Characteristic('{name}')"),
+            _ => String::from("Characteristic()"),
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Bindings for the ontology.
+
+use std::path::PathBuf;
+
+use ontology::graph::SiblingOrder;
+use pyo3::exceptions::PyIOError;
+use pyo3::exceptions::PyKeyError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+
+/// A node within the ontology.
+#[pyclass(module = "ecc", frozen)]
+#[derive(Clone)]
+pub struct Node(ontology::Node);
+
+#[pymethods]
+impl Node {
+    /// The name.
+    #[getter]
+    fn name(&self) -> &str {
+        self.0.name().inner()
+    }
+
+    /// Gets the name to display in a language (a BCP 47 language tag such as
+    /// `de` or `pt-BR`), falling back to the English name.
+    fn display_name(&self, language: &str) -> PyResult<&str> {
+        let language = language
+            .parse::<ecc::text::LanguageTag>()
+            .map_err(|err| PyValueError::new_err(err.to_string()))?;
+        Ok(self.0.display_name(&language))
+    }
+
+    /// The name of the parent node.
+    #[getter]
+    fn parent(&self) -> &str {
+        self.0.parent().inner()
+    }
+
+    /// The short code.
+    #[getter]
+    fn code(&self) -> &str {
+        self.0.code()
+    }
+
+    /// The position among its siblings (or `None` if it is not set).
+    #[getter]
+    fn order(&self) -> Option<u32> {
+        self.0.order()
+    }
+
+    /// Whether the node has been removed from the classification.
+    #[getter]
+    fn obsolete(&self) -> bool {
+        self.0.obsolete()
+    }
+
+    /// The name of the node that replaces this one (or `None` if it is not
+    /// replaced).
+    #[getter]
+    fn replaced_by(&self) -> Option<&str> {
+        self.0.replaced_by().map(|name| name.inner())
+    }
+
+    /// Gets the representation within Python.
+    fn __repr__(&self) -> String {
+        format!("Node('{}')", self.0.name().inner())
+    }
+}
+
+/// An ontology graph.
+#[pyclass(module = "ecc", frozen)]
+pub struct Graph(ontology::Graph);
+
+impl Graph {
+    /// Gets a node by name, raising a `KeyError` if it does not exist.
+    fn node(&self, name: &str) -> PyResult<&ontology::Node> {
+        self.0
+            .get(name)
+            .ok_or_else(|| PyKeyError::new_err(name.to_string()))
+    }
+}
+
+/// Wraps a set of nodes.
+fn wrap(nodes: Vec<&ontology::Node>) -> Vec<Node> {
+    nodes.into_iter().cloned().map(Node).collect()
+}
+
+#[pymethods]
+impl Graph {
+    /// Loads the ontology within a directory.
+    ///
+    /// Siblings are sorted by their order and then by name.
+    #[staticmethod]
+    fn load(directory: PathBuf) -> PyResult<Self> {
+        let nodes = ecc_check::files::files(&directory)
+            .map_err(|err| PyIOError::new_err(err.to_string()))?
+            .into_iter()
+            .map(|path| {
+                let contents = std::fs::read_to_string(&path)
+                    .map_err(|err| PyIOError::new_err(format!("{}: {err}", path.display())))?;
+                serde_yaml::from_str::<ontology::Node>(&contents)
+                    .map_err(|err| PyValueError::new_err(format!("{}: {err}", path.display())))
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+
+        ontology::Graph::try_new(nodes)
+            .map(|graph| Self(graph.with_sibling_order(SiblingOrder::OrderThenName)))
+            .map_err(|issues| {
+                let issues = issues
+                    .iter()
+                    .map(|issue| issue.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                PyValueError::new_err(format!("invalid ontology: {issues}"))
+            })
+    }
+
+    /// The root node.
+    #[getter]
+    fn root(&self) -> Node {
+        Node(self.0.root().clone())
+    }
+
+    /// Gets a node by name (or `None` if it does not exist).
+    fn get(&self, name: &str) -> Option<Node> {
+        self.0.get(name).cloned().map(Node)
+    }
+
+    /// Gets a node by name, following its replacement if it has one (or
+    /// `None` if it does not exist).
+    fn resolve(&self, name: &str) -> Option<Node> {
+        self.0.resolve(name).cloned().map(Node)
+    }
+
+    /// Gets every node.
+    fn nodes(&self) -> Vec<Node> {
+        wrap(self.0.nodes().collect())
+    }
+
+    /// Gets the parent of a node (or `None` for the root).
+    fn parent(&self, name: &str) -> PyResult<Option<Node>> {
+        self.node(name)?;
+        Ok(self.0.parent(name).cloned().map(Node))
+    }
+
+    /// Gets the children of a node.
+    fn children(&self, name: &str) -> PyResult<Vec<Node>> {
+        self.node(name)?;
+        Ok(wrap(self.0.children(name)))
+    }
+
+    /// Gets the descendants of a node.
+    fn descendants(&self, name: &str) -> PyResult<Vec<Node>> {
+        self.node(name)?;
+        Ok(wrap(self.0.descendants(name)))
+    }
+
+    /// Gets the ancestors of a node.
+    fn ancestors(&self, name: &str) -> PyResult<Vec<Node>> {
+        self.node(name)?;
+        Ok(wrap(self.0.ancestors(name)))
+    }
+
+    /// Returns whether a node is a descendant of (or the same as) another
+    /// node.
+    fn is_a(&self, name: &str, ancestor: &str) -> PyResult<bool> {
+        self.node(name)?;
+        self.node(ancestor)?;
+
+        Ok(name == ancestor
+            || self
+                .0
+                .ancestors(name)
+                .iter()
+                .any(|node| node.name().inner() == ancestor))
+    }
+
+    /// Gets the number of nodes.
+    fn __len__(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether a node exists.
+    fn __contains__(&self, name: &str) -> bool {
+        self.0.get(name).is_some()
+    }
+}
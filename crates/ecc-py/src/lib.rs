@@ -0,0 +1,99 @@
+//! Python bindings for composable characteristics.
+//!
+//! The bindings expose the characteristic data model (`Characteristic`,
+//! `Identifier`, and `Kind`) and the ontology `Graph` along with functions to
+//! load and validate characteristic trees. They are built into the `ecc`
+//! Python module with [maturin](https://www.maturin.rs/) (see
+//! `pyproject.toml`).
+
+use std::path::PathBuf;
+
+use pyo3::exceptions::PyIOError;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+mod characteristic;
+mod graph;
+
+pub use characteristic::Characteristic;
+pub use characteristic::Identifier;
+pub use characteristic::Kind;
+pub use graph::Graph;
+pub use graph::Node;
+
+/// Converts a serializable value into native Python objects (via JSON).
+pub(crate) fn to_python<T: serde::Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let json =
+        serde_json::to_string(value).map_err(|err| PyValueError::new_err(err.to_string()))?;
+    Ok(py.import("json")?.call_method1("loads", (json,))?.unbind())
+}
+
+/// Loads every characteristic within a directory.
+///
+/// Characteristics in the legacy flat format are upgraded as they are loaded.
+#[pyfunction]
+fn load(directory: PathBuf) -> PyResult<Vec<Characteristic>> {
+    ecc_check::files::files(&directory)
+        .map_err(|err| PyIOError::new_err(err.to_string()))?
+        .into_iter()
+        .map(|path| {
+            ecc::io::from_path(&path)
+                .map(Characteristic::from)
+                .map_err(|err| PyValueError::new_err(format!("{}: {err}", path.display())))
+        })
+        .collect()
+}
+
+/// Validates the characteristic files within a file or directory.
+///
+/// Returns one dictionary per problem found (with the `file`, `severity`,
+/// `message`, `field`, and `code` keys). If `all_errors` is set, every problem
+/// within each file is reported rather than only the first.
+#[pyfunction]
+#[pyo3(signature = (path, all_errors = false))]
+fn validate(py: Python<'_>, path: PathBuf, all_errors: bool) -> PyResult<Vec<Py<PyDict>>> {
+    let options = ecc_check::Options {
+        all_errors,
+        ..Default::default()
+    };
+
+    let report = ecc_check::check_paths(&[path], &options)
+        .map_err(|err| PyIOError::new_err(err.to_string()))?;
+
+    let mut problems = Vec::new();
+
+    for file in &report.files {
+        for diagnostic in &file.diagnostics {
+            let problem = PyDict::new(py);
+            problem.set_item("file", &file.path)?;
+            problem.set_item(
+                "severity",
+                match diagnostic.severity {
+                    ecc_check::report::Severity::Error => "error",
+                    ecc_check::report::Severity::Warning => "warning",
+                },
+            )?;
+            problem.set_item("message", &diagnostic.message)?;
+            problem.set_item("field", diagnostic.path.as_ref().map(|p| p.to_string()))?;
+            problem.set_item("code", diagnostic.code.map(|code| code.to_string()))?;
+            problems.push(problem.unbind());
+        }
+    }
+
+    Ok(problems)
+}
+
+/// The `ecc` Python module.
+#[pymodule]
+#[pyo3(name = "ecc")]
+fn python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Characteristic>()?;
+    m.add_class::<Identifier>()?;
+    m.add_class::<Kind>()?;
+    m.add_class::<Graph>()?;
+    m.add_class::<Node>()?;
+    m.add_function(wrap_pyfunction!(load, m)?)?;
+    m.add_function(wrap_pyfunction!(validate, m)?)?;
+    Ok(())
+}
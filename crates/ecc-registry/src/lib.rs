@@ -0,0 +1,501 @@
+//! A one-stop API over a composable characteristic repository.
+//!
+//! Downstream services usually need the characteristics, the ontology, and
+//! the checks that tie them together all at once. [`Ecc::load()`] reads both
+//! trees from the root of a repository, checks their integrity, and exposes
+//! everything through a single value:
+//!
+//! ```no_run
+//! use std::path::Path;
+//!
+//! use ecc_registry::Ecc;
+//!
+//! let ecc = Ecc::load(Path::new("."))?;
+//!
+//! let identifier = "ECC-MORPH-000001".parse()?;
+//! let characteristic = ecc.get(&identifier).unwrap();
+//! println!("{}", characteristic.name().unwrap());
+//!
+//! if let Some(node) = ecc.node("Leukemia") {
+//!     println!("{}", node.code());
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+
+use ecc::Characteristic;
+use ecc::CharacteristicSet;
+use ecc::Identifier;
+use ecc::annotate;
+use ecc::annotate::AnnotationSet;
+use ecc::common::relation;
+use ecc::query::Query;
+use nonempty::NonEmpty;
+use ontology::Graph;
+use ontology::Node;
+use ontology::classify::Classification;
+use ontology::graph::Issue;
+
+/// The directory containing the characteristics (relative to the root).
+pub const CHARACTERISTICS: &str = "ecc";
+
+/// The directory containing the ontology (relative to the root).
+pub const ONTOLOGY: &str = "ontology";
+
+/// An error related to loading or using a registry.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A directory could not be read.
+    #[error(transparent)]
+    Check(#[from] ecc_check::Error),
+
+    /// Some characteristic files failed their checks.
+    #[error(
+        "{} characteristic files failed their checks (run `ecc-cli check` for details)",
+        .0.len()
+    )]
+    Invalid(Vec<PathBuf>),
+
+    /// More than one characteristic has the same identifier.
+    #[error("more than one characteristic has the identifier `{0}`")]
+    DuplicateIdentifier(Identifier),
+
+    /// The typed relations between characteristics are inconsistent.
+    #[error(
+        "the relations between characteristics are invalid: {}",
+        .0.iter().map(|issue| issue.to_string()).collect::<Vec<_>>().join("; ")
+    )]
+    Relations(Vec<relation::Issue>),
+
+    /// A node could not be parsed.
+    #[error("parsing node `{path}`: {source}", path = .path.display())]
+    Node {
+        /// The path to the node.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: serde_yaml::Error,
+    },
+
+    /// The ontology is not structurally valid.
+    #[error(
+        "the ontology is invalid: {}",
+        .0.iter().map(|issue| issue.to_string()).collect::<Vec<_>>().join("; ")
+    )]
+    Ontology(Vec<Issue>),
+
+    /// A node binds a characteristic that does not exist.
+    #[error("node `{node}` binds unknown characteristic `{identifier}`")]
+    UnknownBinding {
+        /// The name of the node.
+        node: String,
+
+        /// The identifier of the characteristic.
+        identifier: Identifier,
+    },
+
+    /// A characteristic applies to a node that does not exist.
+    #[error("characteristic `{identifier}` applies to unknown node `{node}`")]
+    UnknownApplicability {
+        /// The identifier of the characteristic.
+        identifier: Identifier,
+
+        /// The name of the node.
+        node: String,
+    },
+
+    /// The registry was loaded without an ontology.
+    #[error("the registry does not have an ontology")]
+    NoOntology,
+
+    /// The annotations of a sample are invalid.
+    #[error(
+        "invalid annotations: {}",
+        .0.iter().map(|err| err.to_string()).collect::<Vec<_>>().join("; ")
+    )]
+    Annotations(NonEmpty<annotate::Error>),
+
+    /// The characteristics could not be exported.
+    #[error("exporting characteristics: {0}")]
+    Export(#[from] serde_json::Error),
+}
+
+/// The format to export characteristics in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    /// A single JSON array.
+    #[default]
+    Json,
+
+    /// Newline-delimited JSON (one characteristic per line).
+    Ndjson,
+}
+
+/// The characteristics and ontology of a repository.
+///
+/// Characteristics that have not been assigned an identifier (i.e., early
+/// drafts) are checked but are not part of the registry (see
+/// [`CharacteristicSet`]).
+#[derive(Clone, Debug)]
+pub struct Ecc {
+    /// The characteristics.
+    characteristics: CharacteristicSet,
+
+    /// The ontology (if the repository has one).
+    ontology: Option<Graph>,
+}
+
+impl Ecc {
+    /// Loads the registry at the root of a repository.
+    ///
+    /// The characteristics are read from the [`CHARACTERISTICS`] directory and
+    /// the ontology is read from the [`ONTOLOGY`] directory (if it exists).
+    /// See [`Ecc::load_from()`] for the checks that are run.
+    pub fn load(root: &Path) -> Result<Self, Error> {
+        let ontology = root.join(ONTOLOGY);
+        let ontology = ontology.is_dir().then_some(ontology);
+
+        Self::load_from(&root.join(CHARACTERISTICS), ontology.as_deref())
+    }
+
+    /// Loads the registry from a characteristic directory and (optionally) an
+    /// ontology directory.
+    ///
+    /// Loading fails if any of the following integrity checks fail:
+    ///
+    /// * every characteristic file passes the checks run by `ecc-cli check`,
+    /// * no two characteristics have the same identifier,
+    /// * the relations between characteristics are consistent (see
+    ///   [`CharacteristicSet::relation_issues()`]),
+    /// * the ontology is structurally valid,
+    /// * every characteristic bound by a node exists, and
+    /// * every node that a characteristic applies to exists.
+    pub fn load_from(characteristics: &Path, ontology: Option<&Path>) -> Result<Self, Error> {
+        let report = ecc_check::check_tree(characteristics, &Default::default())?;
+
+        if !report.passed() {
+            return Err(Error::Invalid(
+                report
+                    .files
+                    .into_iter()
+                    .filter(|file| !file.passed())
+                    .map(|file| file.path)
+                    .collect(),
+            ));
+        }
+
+        let mut set = CharacteristicSet::default();
+
+        for file in report.files {
+            let Some(characteristic) = file.characteristic else {
+                continue;
+            };
+
+            if let Some(replaced) = set.insert(characteristic) {
+                if let Some(identifier) = replaced.identifier() {
+                    return Err(Error::DuplicateIdentifier(identifier.clone()));
+                }
+            }
+        }
+
+        let issues = set.relation_issues();
+
+        if !issues.is_empty() {
+            return Err(Error::Relations(issues));
+        }
+
+        let ontology = ontology.map(load_ontology).transpose()?;
+
+        if let Some(graph) = &ontology {
+            for node in graph.nodes() {
+                for binding in node.characteristics() {
+                    if set.get(binding.identifier()).is_none() {
+                        return Err(Error::UnknownBinding {
+                            node: node.name().inner().to_string(),
+                            identifier: binding.identifier().clone(),
+                        });
+                    }
+                }
+            }
+
+            for characteristic in set.iter() {
+                let Some(applicability) = characteristic.applicability() else {
+                    continue;
+                };
+
+                for node in applicability.nodes() {
+                    if graph.get(node).is_none() {
+                        return Err(Error::UnknownApplicability {
+                            // SAFETY: every characteristic in the set has an
+                            // identifier.
+                            identifier: characteristic.identifier().unwrap().clone(),
+                            node: node.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(Self {
+            characteristics: set,
+            ontology,
+        })
+    }
+
+    /// Gets the characteristics.
+    pub fn characteristics(&self) -> &CharacteristicSet {
+        &self.characteristics
+    }
+
+    /// Gets a characteristic by its identifier.
+    pub fn get(&self, identifier: &Identifier) -> Option<&Characteristic> {
+        self.characteristics.get(identifier)
+    }
+
+    /// Starts a query over the characteristics.
+    pub fn query(&self) -> Query<'_> {
+        self.characteristics.query()
+    }
+
+    /// Gets the ontology (if the repository has one).
+    pub fn ontology(&self) -> Option<&Graph> {
+        self.ontology.as_ref()
+    }
+
+    /// Gets an ontology node by its name.
+    ///
+    /// Returns [`None`] if the node does not exist or the repository does not
+    /// have an ontology.
+    pub fn node(&self, name: &str) -> Option<&Node> {
+        self.ontology.as_ref().and_then(|graph| graph.get(name))
+    }
+
+    /// Classifies a sample from its annotations.
+    ///
+    /// The annotations are validated against the characteristics before the
+    /// sample is classified (see [`ontology::classify::classify()`]). Once it
+    /// is classified, every annotated characteristic must apply to the nodes
+    /// that the sample was classified under.
+    pub fn classify(&self, annotations: &AnnotationSet) -> Result<Classification<'_>, Error> {
+        let graph = self.ontology.as_ref().ok_or(Error::NoOntology)?;
+
+        annotations
+            .validate_against(&self.characteristics)
+            .map_err(Error::Annotations)?;
+
+        let classification = ontology::classify::classify(graph, annotations);
+        let context = annotate::Context {
+            lineage: classification.lineage(graph),
+            ..Default::default()
+        };
+
+        annotations
+            .validate_in(&self.characteristics, &context)
+            .map_err(Error::Annotations)?;
+
+        Ok(classification)
+    }
+
+    /// Exports the characteristics (in order of their identifiers).
+    pub fn export(&self, format: Format, mut writer: impl Write) -> Result<(), Error> {
+        match format {
+            Format::Json => {
+                let characteristics = self.characteristics.iter().collect::<Vec<_>>();
+                serde_json::to_writer_pretty(&mut writer, &characteristics)?;
+            }
+            Format::Ndjson => {
+                for characteristic in self.characteristics.iter() {
+                    serde_json::to_writer(&mut writer, characteristic)?;
+                    writer.write_all(b"\n").map_err(serde_json::Error::io)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Loads the ontology within a directory.
+fn load_ontology(root: &Path) -> Result<Graph, Error> {
+    let mut nodes = Vec::new();
+
+    for path in ecc_check::files::files(root)? {
+        let contents = std::fs::read_to_string(&path).map_err(|source| ecc_check::Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+
+        let node = serde_yaml::from_str::<Node>(&contents)
+            .map_err(|source| Error::Node { path, source })?;
+        nodes.push(node);
+    }
+
+    Graph::try_new(nodes).map_err(Error::Ontology)
+}
+
+#[cfg(test)]
+mod tests {
+    use ecc::annotate::Annotation;
+    use ecc::common::Applicability;
+    use ecc::common::Relation;
+    use test_infra::fixtures;
+    use test_infra::tree::Tree;
+
+    use super::*;
+
+    /// Creates a tree with the characteristics bound by the small ontology.
+    fn tree() -> Tree {
+        Tree::new()
+            .with_characteristics([
+                fixtures::adopted_characteristic(),
+                fixtures::adopted_characteristic_with(|common| {
+                    common.set_identifier(fixtures::identifier("ECC-MOLEC-000001"));
+                    common.set_name("A Molecular Characteristic").unwrap();
+                }),
+            ])
+            .with_ontology(&fixtures::small_ontology())
+    }
+
+    #[test]
+    fn load() {
+        let tree = tree();
+        let ecc = Ecc::load(tree.path()).unwrap();
+
+        assert_eq!(ecc.characteristics().len(), 2);
+        assert_eq!(
+            ecc.get(&fixtures::identifier("ECC-MOLEC-000001"))
+                .unwrap()
+                .name(),
+            Some("A Molecular Characteristic")
+        );
+        assert_eq!(ecc.node("Leukemia").unwrap().code(), "LEUK");
+        assert!(ecc.node("Lymphoma").is_none());
+
+        let annotations = AnnotationSet::new(vec![
+            Annotation::new(fixtures::identifier("ECC-MORPH-000001"), "true"),
+            Annotation::new(fixtures::identifier("ECC-MOLEC-000001"), "false"),
+        ]);
+        let classification = ecc.classify(&annotations).unwrap();
+        assert_eq!(
+            classification.nodes[0].name().inner(),
+            "B-cell Acute Lymphoblastic Leukemia, BCR::ABL1"
+        );
+
+        let annotations = AnnotationSet::new(vec![Annotation::new(
+            fixtures::identifier("ECC-MORPH-000001"),
+            "maybe",
+        )]);
+        assert!(matches!(
+            ecc.classify(&annotations),
+            Err(Error::Annotations(_))
+        ));
+
+        let mut out = Vec::new();
+        ecc.export(Format::Ndjson, &mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 2);
+    }
+
+    #[test]
+    fn integrity() {
+        let tree = tree();
+        tree.write("ecc/morph/broken.yml", "name: Broken\nstate: adopted\n");
+        assert!(matches!(Ecc::load(tree.path()), Err(Error::Invalid(paths)) if paths.len() == 1));
+
+        let tree = Tree::new()
+            .with_characteristic(fixtures::adopted_characteristic())
+            .with_ontology(&fixtures::small_ontology());
+        let err = Ecc::load(tree.path()).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::UnknownBinding { identifier, .. } if identifier.to_string() == "ECC-MOLEC-000001"
+        ));
+
+        let tree = Tree::new().with_characteristics([
+            fixtures::adopted_characteristic_with(|common| {
+                common.set_identifier(fixtures::identifier("ECC-MOLEC-000001"));
+            }),
+            fixtures::adopted_characteristic_with(|common| {
+                common.set_identifier(fixtures::identifier("ECC-MOLEC-000002"));
+                common.set_name("A Refined Characteristic").unwrap();
+                common.set_relations(vec![Relation::new(
+                    relation::Kind::Refines,
+                    fixtures::identifier("ECC-MOLEC-000001"),
+                )]);
+            }),
+        ]);
+        let err = Ecc::load(tree.path()).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::Relations(issues) if matches!(
+                issues.as_slice(),
+                [relation::Issue::MissingInverse { inverse: relation::Kind::RefinedBy, .. }]
+            )
+        ));
+        assert!(
+            err.to_string()
+                .contains("missing the inverse `refined-by` relation")
+        );
+
+        // NOTE: the ontology is optional.
+        let tree = Tree::new().with_characteristic(fixtures::adopted_characteristic());
+        let ecc = Ecc::load(tree.path()).unwrap();
+        assert!(ecc.ontology().is_none());
+        assert!(matches!(
+            ecc.classify(&AnnotationSet::default()),
+            Err(Error::NoOntology)
+        ));
+    }
+
+    #[test]
+    fn applicability() {
+        let applies_to = |node: &str| {
+            Some(Applicability::new(
+                vec![String::from(node)],
+                Vec::new(),
+                Vec::new(),
+            ))
+        };
+
+        let tree = Tree::new()
+            .with_characteristics([
+                fixtures::adopted_characteristic(),
+                fixtures::adopted_characteristic_with(|common| {
+                    common.set_identifier(fixtures::identifier("ECC-MOLEC-000001"));
+                    common.set_name("A Molecular Characteristic").unwrap();
+                    common.set_applicability(applies_to("Lymphoma"));
+                }),
+            ])
+            .with_ontology(&fixtures::small_ontology());
+        let err = Ecc::load(tree.path()).unwrap_err();
+        assert!(matches!(
+            &err,
+            Error::UnknownApplicability { node, .. } if node == "Lymphoma"
+        ));
+
+        let tree = Tree::new()
+            .with_characteristics([
+                fixtures::adopted_characteristic(),
+                fixtures::adopted_characteristic_with(|common| {
+                    common.set_identifier(fixtures::identifier("ECC-MOLEC-000001"));
+                    common.set_name("A Molecular Characteristic").unwrap();
+                    common.set_applicability(applies_to("T-cell Lymphoblastic Leukemia"));
+                }),
+            ])
+            .with_ontology(&fixtures::small_ontology());
+        let ecc = Ecc::load(tree.path()).unwrap();
+
+        let annotations = AnnotationSet::new(vec![
+            Annotation::new(fixtures::identifier("ECC-MORPH-000001"), "true"),
+            Annotation::new(fixtures::identifier("ECC-MOLEC-000001"), "false"),
+        ]);
+        let err = ecc.classify(&annotations).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid annotations: characteristic `ECC-MOLEC-000001` does not apply to samples \
+             classified under `B-cell Acute Lymphoblastic Leukemia, BCR::ABL1`"
+        );
+    }
+}
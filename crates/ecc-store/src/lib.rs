@@ -0,0 +1,455 @@
+//! A normalized SQLite store of the characteristic registry.
+//!
+//! The registry is written to the following tables so that it can be joined
+//! against other tables (e.g., cohort tables) directly:
+//!
+//! * `characteristics`: one row per characteristic with an identifier.
+//! * `permissible_values`: the values of binary and categorical
+//!   characteristics.
+//! * `publications`: the references of each characteristic.
+//! * `nodes` and `edges`: the ontology and its hierarchy.
+//! * `mappings`: cross-references between ECC terms and external terms.
+//!
+//! ```
+//! use ecc_store::Store;
+//!
+//! let mut store = Store::in_memory()?;
+//! store.insert_characteristics(&[])?;
+//!
+//! assert!(store.characteristics()?.is_empty());
+//! # Ok::<(), ecc_store::Error>(())
+//! ```
+
+use std::path::Path;
+
+use ecc::Characteristic;
+use ecc::Identifier;
+use ecc::common::Reference;
+use ecc::common::value::Kind;
+use ontology::Graph;
+use rusqlite::Connection;
+use rusqlite::OptionalExtension as _;
+use rusqlite::params;
+
+/// The schema of the database.
+const SCHEMA: &str = include_str!("schema.sql");
+
+/// An error related to the store.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// An error from SQLite.
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
+
+    /// A characteristic could not be serialized or deserialized.
+    #[error("serializing characteristic: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A row within the `mappings` table.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mapping {
+    /// The ECC term (a characteristic identifier or an ontology node name).
+    pub subject: String,
+
+    /// The relationship between the terms (e.g., `skos:exactMatch`).
+    pub predicate: String,
+
+    /// The external term.
+    pub object: String,
+}
+
+/// A SQLite store of the characteristic registry.
+#[derive(Debug)]
+pub struct Store {
+    /// The connection to the database.
+    connection: Connection,
+}
+
+impl Store {
+    /// Creates a new database at a path.
+    ///
+    /// The file must not already contain an ECC registry.
+    pub fn create(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let connection = Connection::open(path)?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Opens an existing database.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Ok(Self {
+            connection: Connection::open(path)?,
+        })
+    }
+
+    /// Creates a new in-memory database.
+    pub fn in_memory() -> Result<Self, Error> {
+        let connection = Connection::open_in_memory()?;
+        connection.execute_batch(SCHEMA)?;
+        Ok(Self { connection })
+    }
+
+    /// Gets the underlying connection (e.g., to run ad hoc queries).
+    pub fn connection(&self) -> &Connection {
+        &self.connection
+    }
+
+    /// Inserts a set of characteristics.
+    ///
+    /// Characteristics without an identifier (i.e., early drafts) cannot be
+    /// referenced by other tables and are skipped.
+    pub fn insert_characteristics(
+        &mut self,
+        characteristics: &[Characteristic],
+    ) -> Result<(), Error> {
+        let transaction = self.connection.transaction()?;
+
+        for characteristic in characteristics {
+            let (Some(identifier), Some(name), Some(rfc), Some(description), Some(kind)) = (
+                characteristic.identifier(),
+                characteristic.name(),
+                characteristic.rfc(),
+                characteristic.description(),
+                characteristic.values(),
+            ) else {
+                continue;
+            };
+
+            let category = identifier.category().to_string();
+            let identifier = identifier.to_string();
+
+            let (numerical_type, units) = match kind {
+                Kind::Numerical { r#type, units } => {
+                    (Some(r#type.to_string()), Some(units.as_str()))
+                }
+                Kind::Fraction { scale, .. } => (None, Some(scale.units())),
+                _ => (None, None),
+            };
+
+            transaction.execute(
+                "INSERT INTO characteristics VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+                params![
+                    identifier,
+                    name,
+                    characteristic.state().as_str(),
+                    category,
+                    rfc.as_str(),
+                    description,
+                    kind.name(),
+                    numerical_type,
+                    units,
+                    characteristic.adoption_date().map(|date| date.to_rfc3339()),
+                    serde_json::to_string(characteristic)?,
+                ],
+            )?;
+
+            match kind {
+                Kind::Binary { description, .. } => {
+                    for (value, field) in [
+                        ("true", &description.r#true),
+                        ("false", &description.r#false),
+                    ] {
+                        transaction.execute(
+                            "INSERT INTO permissible_values VALUES (?1, ?2, ?3, ?4)",
+                            params![
+                                identifier,
+                                value,
+                                field.summary.as_str(),
+                                field.details.as_str()
+                            ],
+                        )?;
+                    }
+                }
+                Kind::Categorical { options } => {
+                    for option in options {
+                        transaction.execute(
+                            "INSERT INTO permissible_values VALUES (?1, ?2, NULL, NULL)",
+                            params![identifier, option],
+                        )?;
+                    }
+                }
+                // NOTE: the values of an immunophenotypic characteristic are
+                // combinations of markers and levels rather than a fixed set.
+                Kind::Numerical { .. }
+                | Kind::Fraction { .. }
+                | Kind::Immunophenotype { .. }
+                | Kind::Other { .. } => {}
+            }
+
+            for (position, reference) in characteristic
+                .references()
+                .into_iter()
+                .flatten()
+                .enumerate()
+            {
+                let (kind, title, authors, context, url, highlighted) = match reference {
+                    Reference::Manuscript {
+                        title,
+                        authors,
+                        context,
+                        url,
+                        highlighted,
+                    } => ("manuscript", title, authors, context, url, highlighted),
+                    Reference::Preprint {
+                        title,
+                        authors,
+                        context,
+                        url,
+                        highlighted,
+                    } => ("preprint", title, authors, context, url, highlighted),
+                };
+
+                transaction.execute(
+                    "INSERT INTO publications VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                    params![
+                        identifier,
+                        position,
+                        kind,
+                        title,
+                        authors,
+                        context.as_str(),
+                        url.as_str(),
+                        highlighted
+                    ],
+                )?;
+            }
+
+            for mapping in characteristic.mappings() {
+                transaction.execute(
+                    "INSERT OR IGNORE INTO mappings VALUES (?1, ?2, ?3)",
+                    params![
+                        identifier,
+                        mapping.predicate().curie(),
+                        mapping.term().to_string()
+                    ],
+                )?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Inserts the nodes and edges of an ontology.
+    pub fn insert_ontology(&mut self, graph: &Graph) -> Result<(), Error> {
+        let transaction = self.connection.transaction()?;
+
+        for node in graph.nodes() {
+            transaction.execute(
+                "INSERT INTO nodes VALUES (?1, ?2)",
+                params![node.name().inner(), node.code()],
+            )?;
+        }
+
+        for node in graph.nodes() {
+            if let Some(parent) = graph.parent(node.name().inner()) {
+                transaction.execute(
+                    "INSERT INTO edges VALUES (?1, ?2)",
+                    params![parent.name().inner(), node.name().inner()],
+                )?;
+            }
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Inserts a set of mappings.
+    pub fn insert_mappings(&mut self, mappings: &[Mapping]) -> Result<(), Error> {
+        let transaction = self.connection.transaction()?;
+
+        for mapping in mappings {
+            transaction.execute(
+                "INSERT OR IGNORE INTO mappings VALUES (?1, ?2, ?3)",
+                params![mapping.subject, mapping.predicate, mapping.object],
+            )?;
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }
+
+    /// Loads every characteristic in the store (ordered by identifier).
+    pub fn characteristics(&self) -> Result<Vec<Characteristic>, Error> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT document FROM characteristics ORDER BY identifier")?;
+
+        let documents = statement
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        documents
+            .iter()
+            .map(|document| serde_json::from_str(document).map_err(Error::from))
+            .collect()
+    }
+
+    /// Loads a characteristic by identifier.
+    pub fn characteristic(&self, identifier: &Identifier) -> Result<Option<Characteristic>, Error> {
+        let document = self
+            .connection
+            .query_row(
+                "SELECT document FROM characteristics WHERE identifier = ?1",
+                [identifier.to_string()],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+
+        document
+            .map(|document| serde_json::from_str(&document).map_err(Error::from))
+            .transpose()
+    }
+
+    /// Gets the permissible values of a characteristic.
+    ///
+    /// Numerical characteristics (and unknown identifiers) have no permissible
+    /// values.
+    pub fn values(&self, identifier: &Identifier) -> Result<Vec<String>, Error> {
+        let mut statement = self.connection.prepare(
+            "SELECT value FROM permissible_values WHERE characteristic = ?1 ORDER BY rowid",
+        )?;
+
+        let values = statement
+            .query_map([identifier.to_string()], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(values)
+    }
+
+    /// Gets the names of the children of an ontology node.
+    pub fn children(&self, name: &str) -> Result<Vec<String>, Error> {
+        let mut statement = self
+            .connection
+            .prepare("SELECT child FROM edges WHERE parent = ?1 ORDER BY child")?;
+
+        let children = statement
+            .query_map([name], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(children)
+    }
+
+    /// Gets the mappings for an ECC term.
+    pub fn mappings(&self, subject: &str) -> Result<Vec<Mapping>, Error> {
+        let mut statement = self.connection.prepare(
+            "SELECT subject, predicate, object FROM mappings WHERE subject = ?1 ORDER BY \
+             predicate, object",
+        )?;
+
+        let mappings = statement
+            .query_map([subject], |row| {
+                Ok(Mapping {
+                    subject: row.get(0)?,
+                    predicate: row.get(1)?,
+                    object: row.get(2)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(mappings)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use ecc::common::Common;
+    use ontology::Node;
+    use ontology::node::Builder;
+    use ontology::node::Name;
+
+    use super::*;
+
+    fn node(name: &str, parent: &str, code: &str) -> Node {
+        Builder::default()
+            .name(name.parse::<Name>().unwrap())
+            .parent(parent.parse::<Name>().unwrap())
+            .code(code)
+            .try_build()
+            .unwrap()
+    }
+
+    fn characteristic() -> Characteristic {
+        let mut common = Common::new(
+            "A Characteristic Name",
+            "ECC-MORPH-000001".parse::<Identifier>().unwrap(),
+            "https://github.com/stjudecloud/ecc/issues/1"
+                .parse()
+                .unwrap(),
+            "A description.",
+            Kind::Categorical {
+                options: BTreeSet::from([String::from("Bar"), String::from("Foo")]),
+            },
+        )
+        .unwrap();
+        common.set_mappings(vec![ecc::common::Mapping::new(
+            ecc::common::mapping::Predicate::ExactMatch,
+            "NCIT:C3171".parse().unwrap(),
+            None,
+        )]);
+
+        Characteristic::Adopted {
+            common,
+            adoption_date: chrono::DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z")
+                .unwrap()
+                .to_utc(),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut store = Store::in_memory().unwrap();
+        let characteristic = characteristic();
+        store
+            .insert_characteristics(std::slice::from_ref(&characteristic))
+            .unwrap();
+
+        let identifier = characteristic.identifier().unwrap();
+        assert_eq!(
+            store.characteristics().unwrap(),
+            vec![characteristic.clone()]
+        );
+        assert_eq!(
+            store.characteristic(identifier).unwrap(),
+            Some(characteristic.clone())
+        );
+        assert_eq!(store.values(identifier).unwrap(), vec!["Bar", "Foo"]);
+        assert_eq!(
+            store.mappings("ECC-MORPH-000001").unwrap(),
+            vec![Mapping {
+                subject: String::from("ECC-MORPH-000001"),
+                predicate: String::from("skos:exactMatch"),
+                object: String::from("NCIT:C3171"),
+            }]
+        );
+
+        let missing = "ECC-MORPH-000002".parse::<Identifier>().unwrap();
+        assert!(store.characteristic(&missing).unwrap().is_none());
+    }
+
+    #[test]
+    fn ontology() {
+        let graph = Graph::try_new(vec![
+            node("Root", "", "ROOT"),
+            node("Child", "Root", "CHILD"),
+        ])
+        .unwrap();
+
+        let mut store = Store::in_memory().unwrap();
+        store.insert_ontology(&graph).unwrap();
+
+        assert_eq!(store.children("Root").unwrap(), vec!["Child"]);
+
+        store
+            .insert_mappings(&[Mapping {
+                subject: String::from("Child"),
+                predicate: String::from("skos:exactMatch"),
+                object: String::from("NCIT:C0000"),
+            }])
+            .unwrap();
+        assert_eq!(store.mappings("Child").unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,54 @@
+-- The schema of an ECC registry database.
+
+CREATE TABLE characteristics (
+    identifier TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    state TEXT NOT NULL,
+    category TEXT NOT NULL,
+    rfc TEXT NOT NULL,
+    description TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    numerical_type TEXT,
+    units TEXT,
+    adoption_date TEXT,
+    -- The full characteristic as JSON (used to load the characteristic back).
+    document TEXT NOT NULL
+);
+
+CREATE TABLE permissible_values (
+    characteristic TEXT NOT NULL REFERENCES characteristics (identifier),
+    value TEXT NOT NULL,
+    summary TEXT,
+    details TEXT,
+    PRIMARY KEY (characteristic, value)
+);
+
+CREATE TABLE publications (
+    characteristic TEXT NOT NULL REFERENCES characteristics (identifier),
+    position INTEGER NOT NULL,
+    kind TEXT NOT NULL,
+    title TEXT NOT NULL,
+    authors TEXT NOT NULL,
+    context TEXT NOT NULL,
+    url TEXT NOT NULL,
+    highlighted INTEGER NOT NULL,
+    PRIMARY KEY (characteristic, position)
+);
+
+CREATE TABLE nodes (
+    name TEXT PRIMARY KEY,
+    code TEXT NOT NULL
+);
+
+CREATE TABLE edges (
+    parent TEXT NOT NULL REFERENCES nodes (name),
+    child TEXT NOT NULL REFERENCES nodes (name),
+    PRIMARY KEY (parent, child)
+);
+
+CREATE TABLE mappings (
+    subject TEXT NOT NULL,
+    predicate TEXT NOT NULL,
+    object TEXT NOT NULL,
+    PRIMARY KEY (subject, predicate, object)
+);
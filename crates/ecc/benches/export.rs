@@ -0,0 +1,60 @@
+//! Benchmarks for reading the combined JSON export of a registry.
+#![allow(missing_docs)]
+
+use std::collections::BTreeSet;
+
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use criterion::Throughput;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use ecc::Category;
+use ecc::Characteristic;
+use ecc::Identifier;
+use ecc::borrowed;
+use ecc::common::value::Kind;
+use test_infra::fixtures::adopted_characteristic_with;
+
+/// Writes an export of `n` adopted characteristics.
+fn export(n: u64) -> String {
+    let characteristics = (1..=n)
+        .map(|i| {
+            adopted_characteristic_with(|common| {
+                common
+                    .set_name(format!("Characteristic Number {i}"))
+                    .unwrap();
+                common.set_identifier(Identifier::new(Category::Molecular, i).unwrap());
+                common.set_values(Kind::Categorical {
+                    options: (0..8)
+                        .map(|option| format!("Option {option}"))
+                        .collect::<BTreeSet<_>>(),
+                });
+            })
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string(&characteristics).unwrap()
+}
+
+/// Compares reading the export into owned and borrowed characteristics.
+fn deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("export");
+
+    for n in [1_000, 5_000] {
+        let json = export(n);
+        group.throughput(Throughput::Bytes(json.len() as u64));
+
+        group.bench_with_input(BenchmarkId::new("owned", n), &json, |b, json| {
+            b.iter(|| serde_json::from_str::<Vec<Characteristic>>(json).unwrap())
+        });
+
+        group.bench_with_input(BenchmarkId::new("borrowed", n), &json, |b, json| {
+            b.iter(|| borrowed::from_json(json).unwrap())
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
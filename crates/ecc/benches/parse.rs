@@ -0,0 +1,28 @@
+//! Benchmarks for parsing and validating characteristics.
+#![allow(missing_docs)]
+
+use criterion::Criterion;
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use ecc::Identifier;
+use test_infra::fixtures::adopted_characteristic;
+
+/// Parses identifiers.
+fn identifier(c: &mut Criterion) {
+    c.bench_function("identifier/from_str", |b| {
+        b.iter(|| black_box("ECC-MOLEC-000123").parse::<Identifier>().unwrap())
+    });
+}
+
+/// Reads a full characteristic from YAML (as when checking the tree).
+fn yaml(c: &mut Criterion) {
+    let yaml = serde_yaml::to_string(&adopted_characteristic()).unwrap();
+
+    c.bench_function("characteristic/yaml", |b| {
+        b.iter(|| ecc::io::from_str(black_box(&yaml)).unwrap())
+    });
+}
+
+criterion_group!(benches, identifier, yaml);
+criterion_main!(benches);
@@ -0,0 +1,698 @@
+//! Validation of sample-level annotations against a set of characteristics.
+//!
+//! An annotation records the value of a single characteristic for a sample
+//! (e.g., one cell of a cohort metadata file). Values are kept as the strings
+//! they were read as and are checked against the [`Kind`] declared by the
+//! characteristic:
+//!
+//! * binary values must be `true` or `false`,
+//! * categorical values must be one of the options,
+//! * numerical values must parse as the declared numerical type,
+//! * fractional values must be numbers within the bounds of the feature, and
+//! * immunophenotypic values must be marker expressions (e.g., `CD19:bright,
+//!   CD34:negative`) for markers within the declared panel.
+//!
+//! Beyond the values themselves, a sample must not assert two characteristics
+//! that are mutually exclusive with one another (see
+//! [`crate::common::relation::Kind::MutuallyExclusiveWith`]). Every annotation
+//! asserts its characteristic apart from a binary characteristic annotated as
+//! `false`.
+//!
+//! Characteristics may also declare the samples they apply to (see
+//! [`crate::common::Applicability`]). When what is known about a sample beyond
+//! its annotations is provided as a [`Context`], annotating a characteristic
+//! that does not apply to the sample is an error (e.g., a characteristic that
+//! only applies to hematologic malignancies annotated on a solid tumor).
+
+use std::collections::BTreeSet;
+use std::collections::HashSet;
+
+use nonempty::NonEmpty;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Characteristic;
+use crate::CharacteristicSet;
+use crate::Identifier;
+use crate::common::applicability::AgeGroup;
+use crate::common::applicability::SpecimenType;
+use crate::common::relation;
+use crate::common::value::Kind;
+use crate::common::value::kind::fraction;
+use crate::common::value::kind::immunophenotype::Phenotype;
+use crate::common::value::kind::numerical;
+use crate::rfc;
+
+/// An error related to an annotation.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// The annotated characteristic does not exist.
+    #[error("unknown characteristic: `{0}`")]
+    UnknownCharacteristic(Identifier),
+
+    /// The annotated characteristic does not declare its permissible values
+    /// (i.e., it is an early draft).
+    #[error("characteristic `{0}` does not declare its permissible values")]
+    MissingKind(Identifier),
+
+    /// A characteristic was annotated more than once.
+    #[error("characteristic `{0}` is annotated more than once")]
+    Duplicate(Identifier),
+
+    /// A value does not conform to the kind declared by the characteristic.
+    #[error("invalid value `{value}` for characteristic `{identifier}`: expected {expected}")]
+    InvalidValue {
+        /// The annotated characteristic.
+        identifier: Identifier,
+
+        /// The value.
+        value: String,
+
+        /// A description of the permissible values.
+        expected: String,
+    },
+
+    /// A characteristic was annotated without a characteristic it depends on.
+    #[error("characteristic `{dependent}` requires characteristic `{dependency}`")]
+    MissingDependency {
+        /// The annotated characteristic.
+        dependent: Identifier,
+
+        /// The characteristic that was not annotated.
+        dependency: Identifier,
+    },
+
+    /// Two characteristics that are mutually exclusive were both asserted.
+    #[error(
+        "characteristics `{first}` and `{second}` are mutually exclusive but were both \
+         asserted{}",
+        .rfc.as_ref().map(|rfc| format!(" (declared by `{declared_by}` in {rfc})"))
+            .unwrap_or_else(|| format!(" (declared by `{declared_by}`)"))
+    )]
+    MutuallyExclusive {
+        /// The characteristic that was annotated first.
+        first: Identifier,
+
+        /// The characteristic that was annotated second.
+        second: Identifier,
+
+        /// The characteristic that declares the relation.
+        declared_by: Identifier,
+
+        /// The RFC of the characteristic that declares the relation (if it has
+        /// one).
+        rfc: Option<Box<rfc::Link>>,
+    },
+
+    /// A characteristic was annotated on a sample that it does not apply to.
+    #[error("characteristic `{identifier}` does not apply to {reason}")]
+    NotApplicable {
+        /// The annotated characteristic.
+        identifier: Identifier,
+
+        /// A description of the samples that the characteristic was annotated
+        /// on (e.g., "`tissue` specimens").
+        reason: String,
+    },
+}
+
+/// What is known about a sample beyond its annotations.
+///
+/// Anything that is not known is not checked.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Context {
+    /// The names of the ontology nodes that the sample is classified under and
+    /// of their ancestors.
+    ///
+    /// This is empty if the sample has not been classified.
+    pub lineage: Vec<String>,
+
+    /// The type of specimen that the sample was taken from.
+    pub specimen_type: Option<SpecimenType>,
+
+    /// The age group of the patient when the sample was taken.
+    pub age_group: Option<AgeGroup>,
+}
+
+/// The value of a characteristic for a sample.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Annotation {
+    /// The identifier of the characteristic.
+    pub identifier: Identifier,
+
+    /// The value.
+    pub value: String,
+}
+
+impl Annotation {
+    /// Creates a new annotation.
+    pub fn new(identifier: Identifier, value: impl Into<String>) -> Self {
+        Self {
+            identifier,
+            value: value.into(),
+        }
+    }
+
+    /// Returns whether the annotation asserts its characteristic (i.e., it is
+    /// not a binary characteristic annotated as `false`).
+    pub(crate) fn asserts(&self, kind: &Kind) -> bool {
+        !matches!(kind, Kind::Binary { .. }) || self.value != "false"
+    }
+
+    /// Checks that the value conforms to a kind.
+    ///
+    /// On failure, a description of the permissible values is returned.
+    pub(crate) fn conforms_to(&self, kind: &Kind) -> Result<(), String> {
+        let value = self.value.as_str();
+
+        let valid = match kind {
+            Kind::Binary { .. } => matches!(value, "true" | "false"),
+            Kind::Categorical { options } => options.contains(value),
+            Kind::Numerical { r#type, .. } => match r#type {
+                numerical::Type::Signed => value.parse::<i64>().is_ok(),
+                numerical::Type::Unsigned => value.parse::<u64>().is_ok(),
+                numerical::Type::Float => value.parse::<f64>().is_ok_and(f64::is_finite),
+            },
+            Kind::Fraction {
+                scale, min, max, ..
+            } => value.parse::<f64>().is_ok_and(|value| {
+                value.is_finite()
+                    && (min.unwrap_or(0.0)..=max.unwrap_or(scale.maximum())).contains(&value)
+            }),
+            Kind::Immunophenotype { markers } => value
+                .parse::<Phenotype>()
+                .is_ok_and(|phenotype| phenotype.outside(markers).next().is_none()),
+            // NOTE: the permissible values are not modeled, so any value is
+            // accepted.
+            Kind::Other { .. } => true,
+        };
+
+        if valid {
+            return Ok(());
+        }
+
+        Err(match kind {
+            Kind::Binary { .. } => String::from("`true` or `false`"),
+            Kind::Categorical { options } => format!(
+                "one of {}",
+                options
+                    .iter()
+                    .map(|option| format!("`{option}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Kind::Numerical { r#type, .. } => String::from(match r#type {
+                numerical::Type::Signed => "a signed integer",
+                numerical::Type::Unsigned => "an unsigned integer",
+                numerical::Type::Float => "a finite number",
+            }),
+            Kind::Fraction {
+                scale, min, max, ..
+            } => fraction::describe(*scale, *min, *max),
+            Kind::Immunophenotype { markers } => format!(
+                "expression levels (e.g., `CD19:bright, CD34:negative`) for markers among {}",
+                markers
+                    .iter()
+                    .map(|marker| format!("`{marker}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            Kind::Other { description } => description.clone(),
+        })
+    }
+}
+
+/// The annotations of a single sample.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct AnnotationSet(Vec<Annotation>);
+
+impl AnnotationSet {
+    /// Creates a new annotation set.
+    pub fn new(annotations: Vec<Annotation>) -> Self {
+        Self(annotations)
+    }
+
+    /// Gets an iterator over the annotations.
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        self.0.iter()
+    }
+
+    /// Gets the value annotated for a characteristic.
+    ///
+    /// If the characteristic is annotated more than once, the first value is
+    /// returned.
+    pub fn get(&self, identifier: &Identifier) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|annotation| &annotation.identifier == identifier)
+            .map(|annotation| annotation.value.as_str())
+    }
+
+    /// Validates the annotations against a set of characteristics.
+    ///
+    /// Every problem is reported (in the order of the annotations) rather than
+    /// only the first. The applicability of the characteristics is not checked
+    /// (see [`AnnotationSet::validate_in()`]).
+    pub fn validate_against(
+        &self,
+        characteristics: &CharacteristicSet,
+    ) -> Result<(), NonEmpty<Error>> {
+        self.validate_in(characteristics, &Context::default())
+    }
+
+    /// Validates the annotations against a set of characteristics, checking
+    /// that every annotated characteristic applies to the sample.
+    ///
+    /// Every problem is reported (in the order of the annotations) rather than
+    /// only the first.
+    pub fn validate_in(
+        &self,
+        characteristics: &CharacteristicSet,
+        context: &Context,
+    ) -> Result<(), NonEmpty<Error>> {
+        let mut errors = Vec::new();
+        let mut seen = HashSet::new();
+
+        for annotation in &self.0 {
+            let identifier = &annotation.identifier;
+
+            if !seen.insert(identifier) {
+                errors.push(Error::Duplicate(identifier.clone()));
+                continue;
+            }
+
+            let Some(characteristic) = characteristics.get(identifier) else {
+                errors.push(Error::UnknownCharacteristic(identifier.clone()));
+                continue;
+            };
+
+            let Some(kind) = characteristic.values() else {
+                errors.push(Error::MissingKind(identifier.clone()));
+                continue;
+            };
+
+            if let Err(expected) = annotation.conforms_to(kind) {
+                errors.push(Error::InvalidValue {
+                    identifier: identifier.clone(),
+                    value: annotation.value.clone(),
+                    expected,
+                });
+            }
+
+            if let Some(reason) = not_applicable(characteristic, context) {
+                errors.push(Error::NotApplicable {
+                    identifier: identifier.clone(),
+                    reason,
+                });
+            }
+        }
+
+        let mut checked = HashSet::new();
+
+        for annotation in &self.0 {
+            if !checked.insert(&annotation.identifier) {
+                continue;
+            }
+
+            for dependency in characteristics.dependencies(&annotation.identifier) {
+                if !seen.contains(dependency) {
+                    errors.push(Error::MissingDependency {
+                        dependent: annotation.identifier.clone(),
+                        dependency: dependency.clone(),
+                    });
+                }
+            }
+        }
+
+        errors.extend(self.conflicts(characteristics));
+
+        match NonEmpty::from_vec(errors) {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Describes why a characteristic does not apply to a sample (if it does
+/// not).
+fn not_applicable(characteristic: &Characteristic, context: &Context) -> Option<String> {
+    let applicability = characteristic.applicability()?;
+
+    if !context.lineage.is_empty() && !applicability.applies_to_lineage(&context.lineage) {
+        return Some(format!("samples classified under `{}`", context.lineage[0]));
+    }
+
+    if let Some(specimen_type) = context.specimen_type {
+        if !applicability.applies_to_specimen_type(specimen_type) {
+            return Some(format!("`{specimen_type}` specimens"));
+        }
+    }
+
+    if let Some(age_group) = context.age_group {
+        if !applicability.applies_to_age_group(age_group) {
+            return Some(format!("samples from `{age_group}` patients"));
+        }
+    }
+
+    None
+}
+
+impl AnnotationSet {
+    /// Finds the pairs of asserted characteristics that are mutually exclusive
+    /// with one another.
+    ///
+    /// Each pair is reported once, attributed to the first characteristic (in
+    /// the order of the annotations) that declares the relation.
+    fn conflicts(&self, characteristics: &CharacteristicSet) -> Vec<Error> {
+        let asserted = self
+            .0
+            .iter()
+            .filter(|annotation| {
+                characteristics
+                    .get(&annotation.identifier)
+                    .and_then(|characteristic| characteristic.values())
+                    .is_some_and(|kind| annotation.asserts(kind))
+            })
+            .map(|annotation| &annotation.identifier)
+            .collect::<Vec<_>>();
+
+        let mut reported = BTreeSet::new();
+        let mut errors = Vec::new();
+
+        for (i, identifier) in asserted.iter().enumerate() {
+            for other in asserted.iter().skip(i + 1) {
+                if identifier == other || reported.contains(&(*identifier, *other)) {
+                    continue;
+                }
+
+                let declares = |source: &Identifier, target: &Identifier| {
+                    characteristics.get(source).is_some_and(|characteristic| {
+                        characteristic.relations().iter().any(|relation| {
+                            relation.kind() == relation::Kind::MutuallyExclusiveWith
+                                && relation.target() == target
+                        })
+                    })
+                };
+
+                let declared_by = if declares(identifier, other) {
+                    identifier
+                } else if declares(other, identifier) {
+                    other
+                } else {
+                    continue;
+                };
+
+                reported.insert((*identifier, *other));
+                reported.insert((*other, *identifier));
+
+                errors.push(Error::MutuallyExclusive {
+                    first: (*identifier).clone(),
+                    second: (*other).clone(),
+                    declared_by: (*declared_by).clone(),
+                    rfc: characteristics
+                        .get(declared_by)
+                        .and_then(|characteristic| characteristic.rfc())
+                        .cloned()
+                        .map(Box::new),
+                });
+            }
+        }
+
+        errors
+    }
+}
+
+impl FromIterator<Annotation> for AnnotationSet {
+    fn from_iter<T: IntoIterator<Item = Annotation>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn identifier(s: &str) -> Identifier {
+        s.parse().unwrap()
+    }
+
+    fn characteristics() -> CharacteristicSet {
+        let characteristics: Vec<Characteristic> = serde_yaml::from_str(
+            r#"- state: proposed
+  identifier: ECC-MORPH-000001
+  name: Foo Bar
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: categorical
+    options: [Foo, Bar]
+- state: proposed
+  identifier: ECC-MORPH-000002
+  name: Baz Quux
+  rfc: https://github.com/stjudecloud/ecc/issues/2
+  description: A description.
+  values:
+    kind: numerical
+    type: unsigned
+    units: Cells
+- state: draft
+  identifier: ECC-MORPH-000003
+  name: Some Draft"#,
+        )
+        .unwrap();
+
+        characteristics
+            .into_iter()
+            .collect::<CharacteristicSet>()
+            .with_dependency(
+                identifier("ECC-MORPH-000002"),
+                identifier("ECC-MORPH-000001"),
+            )
+    }
+
+    #[test]
+    fn valid() {
+        let annotations = AnnotationSet::new(vec![
+            Annotation::new(identifier("ECC-MORPH-000001"), "Foo"),
+            Annotation::new(identifier("ECC-MORPH-000002"), "10"),
+        ]);
+
+        assert!(annotations.validate_against(&characteristics()).is_ok());
+    }
+
+    #[test]
+    fn invalid() {
+        let annotations = AnnotationSet::new(vec![
+            Annotation::new(identifier("ECC-MORPH-000002"), "-1"),
+            Annotation::new(identifier("ECC-MORPH-000003"), "Foo"),
+            Annotation::new(identifier("ECC-MOLEC-000001"), "Foo"),
+            Annotation::new(identifier("ECC-MORPH-000002"), "1"),
+        ]);
+
+        let errors = annotations
+            .validate_against(&characteristics())
+            .unwrap_err()
+            .into_iter()
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            vec![
+                Error::InvalidValue {
+                    identifier: identifier("ECC-MORPH-000002"),
+                    value: String::from("-1"),
+                    expected: String::from("an unsigned integer"),
+                },
+                Error::MissingKind(identifier("ECC-MORPH-000003")),
+                Error::UnknownCharacteristic(identifier("ECC-MOLEC-000001")),
+                Error::Duplicate(identifier("ECC-MORPH-000002")),
+                Error::MissingDependency {
+                    dependent: identifier("ECC-MORPH-000002"),
+                    dependency: identifier("ECC-MORPH-000001"),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn categorical() {
+        let annotations =
+            AnnotationSet::new(vec![Annotation::new(identifier("ECC-MORPH-000001"), "Baz")]);
+
+        let errors = annotations
+            .validate_against(&characteristics())
+            .unwrap_err();
+        assert_eq!(
+            errors.head.to_string(),
+            "invalid value `Baz` for characteristic `ECC-MORPH-000001`: expected one of `Bar`, \
+             `Foo`"
+        );
+    }
+
+    #[test]
+    fn immunophenotype() {
+        let characteristics: Vec<Characteristic> = serde_yaml::from_str(
+            r#"- state: proposed
+  identifier: ECC-MORPH-000001
+  name: B-Lymphoblast Immunophenotype
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: immunophenotype
+    markers: [CD10, CD19, CD34]"#,
+        )
+        .unwrap();
+        let characteristics = characteristics.into_iter().collect::<CharacteristicSet>();
+
+        let annotations = AnnotationSet::new(vec![Annotation::new(
+            identifier("ECC-MORPH-000001"),
+            "CD19:bright, CD34:negative",
+        )]);
+        assert!(annotations.validate_against(&characteristics).is_ok());
+
+        let annotations = AnnotationSet::new(vec![Annotation::new(
+            identifier("ECC-MORPH-000001"),
+            "CD19:bright, CD20:dim",
+        )]);
+        let errors = annotations.validate_against(&characteristics).unwrap_err();
+        assert_eq!(
+            errors.head.to_string(),
+            "invalid value `CD19:bright, CD20:dim` for characteristic `ECC-MORPH-000001`: \
+             expected expression levels (e.g., `CD19:bright, CD34:negative`) for markers among \
+             `CD10`, `CD19`, `CD34`"
+        );
+    }
+
+    #[test]
+    fn mutually_exclusive() {
+        let characteristics: Vec<Characteristic> = serde_yaml::from_str(
+            r#"- state: proposed
+  identifier: ECC-MOLEC-000001
+  name: Foo Fusion
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: binary
+    description:
+      "true":
+        summary: Present.
+        details: It was observed.
+      "false":
+        summary: Absent.
+        details: It was not observed.
+  relations:
+    - kind: mutually-exclusive-with
+      target: ECC-MOLEC-000002
+- state: proposed
+  identifier: ECC-MOLEC-000002
+  name: Bar Fusion
+  rfc: https://github.com/stjudecloud/ecc/issues/2
+  description: A description.
+  values:
+    kind: categorical
+    options: [Bar, Baz]
+  relations:
+    - kind: mutually-exclusive-with
+      target: ECC-MOLEC-000001"#,
+        )
+        .unwrap();
+        let characteristics = characteristics.into_iter().collect::<CharacteristicSet>();
+
+        let annotations = AnnotationSet::new(vec![
+            Annotation::new(identifier("ECC-MOLEC-000002"), "Bar"),
+            Annotation::new(identifier("ECC-MOLEC-000001"), "true"),
+        ]);
+        let errors = annotations.validate_against(&characteristics).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            errors.head.to_string(),
+            "characteristics `ECC-MOLEC-000002` and `ECC-MOLEC-000001` are mutually exclusive \
+             but were both asserted (declared by `ECC-MOLEC-000002` in \
+             https://github.com/stjudecloud/ecc/issues/2)"
+        );
+
+        // NOTE: a binary characteristic annotated as `false` is not asserted.
+        let annotations = AnnotationSet::new(vec![
+            Annotation::new(identifier("ECC-MOLEC-000002"), "Bar"),
+            Annotation::new(identifier("ECC-MOLEC-000001"), "false"),
+        ]);
+        assert!(annotations.validate_against(&characteristics).is_ok());
+    }
+
+    #[test]
+    fn applicability() {
+        let characteristics: Vec<Characteristic> = serde_yaml::from_str(
+            r#"- state: proposed
+  identifier: ECC-MORPH-000001
+  name: Marrow Blasts
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: numerical
+    type: float
+    units: Percent
+  applicability:
+    nodes: [Hematologic Malignancy]
+    specimen_types: [blood, bone-marrow]
+    age_groups: [infant, child]"#,
+        )
+        .unwrap();
+        let characteristics = characteristics.into_iter().collect::<CharacteristicSet>();
+        let annotations = AnnotationSet::new(vec![Annotation::new(
+            identifier("ECC-MORPH-000001"),
+            "42.5",
+        )]);
+
+        // NOTE: nothing is known about the sample, so nothing is checked.
+        assert!(annotations.validate_against(&characteristics).is_ok());
+
+        let context = Context {
+            lineage: vec![
+                String::from("B-ALL"),
+                String::from("Hematologic Malignancy"),
+                String::from("Tumor"),
+            ],
+            specimen_type: Some(SpecimenType::BoneMarrow),
+            age_group: Some(AgeGroup::Child),
+        };
+        assert!(annotations.validate_in(&characteristics, &context).is_ok());
+
+        let solid = Context {
+            lineage: vec![String::from("Neuroblastoma"), String::from("Tumor")],
+            ..Default::default()
+        };
+        let errors = annotations
+            .validate_in(&characteristics, &solid)
+            .unwrap_err();
+        assert_eq!(
+            errors.head.to_string(),
+            "characteristic `ECC-MORPH-000001` does not apply to samples classified under \
+             `Neuroblastoma`"
+        );
+
+        let tissue = Context {
+            specimen_type: Some(SpecimenType::Tissue),
+            ..context.clone()
+        };
+        let errors = annotations
+            .validate_in(&characteristics, &tissue)
+            .unwrap_err();
+        assert_eq!(
+            errors.head.to_string(),
+            "characteristic `ECC-MORPH-000001` does not apply to `tissue` specimens"
+        );
+
+        let adult = Context {
+            age_group: Some(AgeGroup::Adult),
+            ..context
+        };
+        let errors = annotations
+            .validate_in(&characteristics, &adult)
+            .unwrap_err();
+        assert_eq!(
+            errors.head.to_string(),
+            "characteristic `ECC-MORPH-000001` does not apply to samples from `adult` patients"
+        );
+    }
+}
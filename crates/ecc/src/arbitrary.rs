@@ -0,0 +1,673 @@
+//! Random generation of valid values for property-based testing.
+//!
+//! Every type in the data model that can be generated implements
+//! [`proptest::arbitrary::Arbitrary`], so [`proptest::prelude::any()`] can be
+//! used to generate them. Generated values are always valid (e.g., identifiers
+//! are numbered within the range that can be written with six digits), so
+//! they should survive a round trip through any supported format unchanged.
+
+use chrono::DateTime;
+use chrono::Utc;
+use nonempty::NonEmpty;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::strategy::BoxedStrategy;
+use url::Url;
+
+use crate::Category;
+use crate::Characteristic;
+use crate::Identifier;
+use crate::common::Applicability;
+use crate::common::Common;
+use crate::common::EvidenceLevel;
+use crate::common::Expression;
+use crate::common::Iscn;
+use crate::common::Mapping;
+use crate::common::Molecular;
+use crate::common::OptionalCommon;
+use crate::common::Reference;
+use crate::common::Relation;
+use crate::common::Sensitivity;
+use crate::common::Steward;
+use crate::common::Tag;
+use crate::common::applicability::AgeGroup;
+use crate::common::applicability::SpecimenType;
+use crate::common::mapping;
+use crate::common::relation;
+use crate::common::validate_values;
+use crate::common::value::Kind;
+use crate::common::value::kind::binary;
+use crate::common::value::kind::fraction;
+use crate::common::value::kind::numerical;
+use crate::field;
+use crate::migrate::SchemaVersion;
+use crate::rfc;
+use crate::text::LanguageTag;
+use crate::text::Sentence;
+use crate::text::language::Translations;
+
+/// The largest number an identifier can have (as it is padded to six digits).
+pub const MAX_IDENTIFIER_NUMBER: u64 = 999_999;
+
+/// The latest adoption date that is generated (the start of 2100).
+const MAX_TIMESTAMP: i64 = 4_102_444_800;
+
+/// Generates a name (a non-empty phrase without surrounding whitespace).
+pub fn name() -> impl Strategy<Value = String> {
+    "[A-Z][A-Za-z0-9]{0,8}( [A-Za-z0-9()-]{1,8}){0,4}"
+}
+
+/// Generates a Markdown description.
+pub fn description() -> impl Strategy<Value = String> {
+    "[A-Z][a-z]{0,8}( [a-z*`]{1,8}){0,12}\\.(\n\n[A-Z][a-z]{0,8}( [a-z]{1,8}){0,12}\\.)?"
+}
+
+/// Generates an adoption date (with a precision of seconds).
+pub fn date() -> impl Strategy<Value = DateTime<Utc>> {
+    // SAFETY: every timestamp within the range is representable.
+    (0..MAX_TIMESTAMP).prop_map(|seconds| DateTime::from_timestamp(seconds, 0).unwrap())
+}
+
+/// Generates a URL for a publication.
+fn url() -> impl Strategy<Value = Url> {
+    (1..10_000u32, 1..1_000_000u32).prop_map(|(registrant, suffix)| {
+        // SAFETY: the URL is always well formed.
+        format!("https://doi.org/10.{registrant}/{suffix}")
+            .parse()
+            .unwrap()
+    })
+}
+
+/// Generates a schema version.
+fn schema_version() -> impl Strategy<Value = Option<SchemaVersion>> {
+    prop_oneof![
+        Just(None),
+        Just(Some(SchemaVersion::UNVERSIONED)),
+        Just(Some(SchemaVersion::CURRENT)),
+    ]
+}
+
+/// Generates an evidence level.
+fn evidence_level() -> impl Strategy<Value = EvidenceLevel> {
+    prop_oneof![
+        Just(EvidenceLevel::Preliminary),
+        Just(EvidenceLevel::SingleStudy),
+        Just(EvidenceLevel::MultipleStudies),
+        Just(EvidenceLevel::ConsensusGuideline),
+    ]
+}
+
+/// Generates a sensitivity.
+fn sensitivity() -> impl Strategy<Value = Sensitivity> {
+    prop_oneof![
+        Just(Sensitivity::Public),
+        Just(Sensitivity::Controlled),
+        Just(Sensitivity::Restricted),
+    ]
+}
+
+/// Generates a list of tags.
+fn tags() -> impl Strategy<Value = Vec<Tag>> {
+    collection::vec(
+        // SAFETY: the pattern only generates valid tags, so this will always
+        // unwrap.
+        "[a-z]{2,8}(-[a-z0-9]{1,8})?".prop_map(|tag| tag.parse::<Tag>().unwrap()),
+        0..3,
+    )
+}
+
+/// Generates a list of relations to other characteristics.
+fn relations() -> impl Strategy<Value = Vec<Relation>> {
+    collection::vec(
+        (
+            prop_oneof![
+                Just(relation::Kind::Supersedes),
+                Just(relation::Kind::Refines),
+                Just(relation::Kind::RefinedBy),
+                Just(relation::Kind::MutuallyExclusiveWith),
+            ],
+            any::<Identifier>(),
+        )
+            .prop_map(|(kind, target)| Relation::new(kind, target)),
+        0..3,
+    )
+}
+
+/// Generates a list of stewards.
+fn stewards() -> impl Strategy<Value = Option<NonEmpty<Steward>>> {
+    let steward = (
+        name(),
+        // SAFETY: the pattern only generates valid handles, so this will
+        // always unwrap.
+        "[a-zA-Z0-9]{1,12}(-[a-zA-Z0-9]{1,12})?".prop_map(|handle| handle.parse().unwrap()),
+        "[a-z]{1,8}@[a-z]{1,8}\\.org",
+    )
+        .prop_map(|(name, github, email)| Steward::new(name, github, email));
+
+    option::of(collection::vec(steward, 1..3))
+        .prop_map(|stewards| stewards.and_then(NonEmpty::from_vec))
+}
+
+/// Generates a list of mappings to terms within external terminologies.
+fn mappings() -> impl Strategy<Value = Vec<Mapping>> {
+    collection::vec(
+        (
+            prop::sample::select(mapping::Predicate::ALL.to_vec()),
+            // SAFETY: the pattern only generates valid compact URIs, so this
+            // will always unwrap.
+            "(NCIT|ICDO|SNOMEDCT):[A-Z0-9]{1,8}".prop_map(|term| term.parse().unwrap()),
+            option::of(name()),
+        )
+            .prop_map(|(predicate, term, label)| Mapping::new(predicate, term, label)),
+        0..3,
+    )
+}
+
+/// Generates the genes and variants of a molecular characteristic.
+fn molecular() -> impl Strategy<Value = Option<Molecular>> {
+    // SAFETY: the patterns only generate valid symbols and expressions, so
+    // these will always unwrap.
+    let gene = "[A-Z][A-Z0-9]{1,5}(-[A-Z0-9]{1,3})?".prop_map(|symbol| symbol.parse().unwrap());
+    let variant = prop_oneof![
+        "p\\.[ACDEFGHIKLMNPQRSTVWY][1-9][0-9]{0,3}[ACDEFGHIKLMNPQRSTVWY*]",
+        "c\\.[1-9][0-9]{0,3}[ACGT]>[ACGT]",
+    ]
+    .prop_map(|expression| expression.parse().unwrap());
+
+    option::of(
+        (collection::vec(gene, 1..3), collection::vec(variant, 0..3)).prop_map(
+            |(genes, variants)| {
+                // SAFETY: at least one gene is always generated.
+                Molecular::new(NonEmpty::from_vec(genes).unwrap(), variants)
+            },
+        ),
+    )
+}
+
+/// Generates the samples that a characteristic applies to.
+fn applicability() -> impl Strategy<Value = Option<Applicability>> {
+    option::of(
+        (
+            collection::vec(name(), 0..3),
+            prop::sample::subsequence(SpecimenType::ALL.to_vec(), 0..=SpecimenType::ALL.len()),
+            prop::sample::subsequence(AgeGroup::ALL.to_vec(), 0..=AgeGroup::ALL.len()),
+        )
+            .prop_map(|(nodes, specimen_types, age_groups)| {
+                Applicability::new(nodes, specimen_types, age_groups)
+            }),
+    )
+}
+
+/// Generates the cytogenetic abnormalities of a cytogenetic characteristic.
+fn cytogenetics() -> impl Strategy<Value = Vec<Iscn>> {
+    // SAFETY: the patterns only generate valid expressions, so this will
+    // always unwrap.
+    collection::vec(
+        prop_oneof![
+            r"t\(([1-9]|1[0-9]|2[0-2]|X);([1-9]|1[0-9]|2[0-2]|Y)\)\(q[1-3][0-9];p1[0-9](\.[1-9])?\)",
+            r"(inv|del|dup)\(([1-9]|1[0-9]|2[0-2])\)\(p[1-3][0-9]q[1-3][0-9]\)",
+            r"(del|i)\(([1-9]|1[0-9]|2[0-2])[pq]\)",
+            r"[+-]([1-9]|1[0-9]|2[0-2]|X|Y)",
+            r"iAMP([1-9]|1[0-9]|2[0-2])",
+        ]
+        .prop_map(|expression| expression.parse().unwrap()),
+        0..3,
+    )
+}
+
+/// Generates the definition of a composite characteristic.
+fn definition() -> impl Strategy<Value = Expression> {
+    use crate::common::expression::Literal;
+    use crate::common::expression::Operator;
+
+    let literal = prop_oneof![
+        any::<bool>().prop_map(Literal::Boolean),
+        (-10000i32..10000).prop_map(|n| Literal::Number(f64::from(n) / 100.0)),
+        r#"[A-Za-z "\\]{0,12}"#.prop_map(Literal::Text),
+    ];
+
+    let comparison = prop_oneof![
+        (
+            prop_oneof![Just(Operator::Equal), Just(Operator::NotEqual)],
+            literal
+        ),
+        (
+            prop_oneof![
+                Just(Operator::Greater),
+                Just(Operator::GreaterOrEqual),
+                Just(Operator::Less),
+                Just(Operator::LessOrEqual),
+            ],
+            (-10000i32..10000).prop_map(|n| Literal::Number(f64::from(n) / 100.0)),
+        ),
+    ];
+
+    let leaf = prop_oneof![
+        any::<Identifier>().prop_map(Expression::Asserted),
+        (any::<Identifier>(), comparison).prop_map(|(identifier, (operator, literal))| {
+            Expression::Comparison {
+                identifier,
+                operator,
+                literal,
+            }
+        }),
+    ];
+
+    leaf.prop_recursive(3, 12, 3, |inner| {
+        prop_oneof![
+            inner
+                .clone()
+                .prop_map(|expression| Expression::Not(Box::new(expression))),
+            collection::vec(inner.clone(), 2..4).prop_map(Expression::and),
+            collection::vec(inner, 2..4).prop_map(Expression::or),
+        ]
+    })
+}
+
+/// Generates translations of a name.
+pub fn translations() -> impl Strategy<Value = Translations> {
+    collection::btree_map(any::<LanguageTag>(), name(), 0..3)
+}
+
+/// Generates an optional, non-empty list of references.
+fn references() -> impl Strategy<Value = Option<NonEmpty<Reference>>> {
+    option::of(collection::vec(any::<Reference>(), 1..3))
+        .prop_map(|references| references.and_then(NonEmpty::from_vec))
+}
+
+impl Arbitrary for Identifier {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            prop_oneof![Just(Category::Molecular), Just(Category::Morphological)],
+            1..=MAX_IDENTIFIER_NUMBER,
+        )
+            // SAFETY: the number is never zero, so this will always unwrap.
+            .prop_map(|(category, n)| Identifier::new(category, n).unwrap())
+            .boxed()
+    }
+}
+
+impl Arbitrary for Sentence {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        "[A-Z][a-z]{0,8}( [a-z]{1,8}){0,8}\\."
+            // SAFETY: the sentence is never empty, so this will always unwrap.
+            .prop_map(|sentence| sentence.parse().unwrap())
+            .boxed()
+    }
+}
+
+impl Arbitrary for LanguageTag {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            Just("de"),
+            Just("es-419"),
+            Just("fr"),
+            Just("pt-BR"),
+            Just("zh-Hant-TW"),
+        ]
+        // SAFETY: every tag above is well formed, so this will always unwrap.
+        .prop_map(|tag| tag.parse().unwrap())
+        .boxed()
+    }
+}
+
+impl Arbitrary for rfc::Link {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (1..100_000u32)
+            .prop_map(|issue| {
+                // SAFETY: the link always points to an issue, so this will
+                // always unwrap.
+                format!("https://github.com/stjudecloud/ecc/issues/{issue}")
+                    .parse()
+                    .unwrap()
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for field::Description {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Sentence>(), any::<Sentence>())
+            .prop_map(|(summary, details)| Self { summary, details })
+            .boxed()
+    }
+}
+
+/// Generates a threshold that a binary feature is derived from.
+fn threshold() -> impl Strategy<Value = binary::Threshold> {
+    (
+        any::<Identifier>(),
+        prop_oneof![
+            Just(binary::Comparator::Greater),
+            Just(binary::Comparator::GreaterOrEqual),
+            Just(binary::Comparator::Less),
+            Just(binary::Comparator::LessOrEqual),
+        ],
+        0u16..1000,
+        "%|[a-z]{1,12}",
+    )
+        .prop_map(|(source, comparator, value, units)| binary::Threshold {
+            source,
+            comparator,
+            value: f64::from(value),
+            units,
+        })
+}
+
+impl Arbitrary for Kind {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            (
+                any::<field::Description>(),
+                any::<field::Description>(),
+                option::of(threshold()),
+            )
+                .prop_map(|(r#true, r#false, threshold)| Kind::Binary {
+                    description: binary::Description { r#true, r#false },
+                    threshold,
+                }),
+            collection::btree_set(name(), 1..8).prop_map(|options| Kind::Categorical { options }),
+            (
+                prop_oneof![
+                    Just(numerical::Type::Signed),
+                    Just(numerical::Type::Unsigned),
+                    Just(numerical::Type::Float),
+                ],
+                "[a-z]{1,12}",
+            )
+                .prop_map(|(r#type, units)| Kind::Numerical { r#type, units }),
+            (
+                name(),
+                prop_oneof![
+                    Just(fraction::Scale::Percent),
+                    Just(fraction::Scale::Proportion)
+                ],
+                any::<bool>(),
+                any::<bool>(),
+            )
+                .prop_map(|(denominator_description, scale, min, max)| {
+                    Kind::Fraction {
+                        denominator_description,
+                        scale,
+                        min: min.then_some(0.0),
+                        max: max.then(|| scale.maximum()),
+                    }
+                }),
+            // SAFETY: the pattern only generates valid markers, so this will
+            // always unwrap.
+            collection::btree_set(
+                "(cy)?CD[1-9][0-9]{0,2}[a-e]?".prop_map(|marker| marker.parse().unwrap()),
+                1..8
+            )
+            .prop_map(|markers| Kind::Immunophenotype { markers }),
+            "[a-z]{1,12}( [a-z]{1,12}){0,4}".prop_map(|description| Kind::Other { description }),
+        ]
+        .boxed()
+    }
+}
+
+impl Arbitrary for Reference {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<bool>(),
+            name(),
+            name(),
+            any::<Sentence>(),
+            url(),
+            any::<bool>(),
+        )
+            .prop_map(
+                |(preprint, title, authors, context, url, highlighted)| match preprint {
+                    true => Reference::Preprint {
+                        title,
+                        authors,
+                        context,
+                        url,
+                        highlighted,
+                    },
+                    false => Reference::Manuscript {
+                        title,
+                        authors,
+                        context,
+                        url,
+                        highlighted,
+                    },
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Common {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            schema_version(),
+            name(),
+            translations(),
+            any::<Identifier>(),
+            any::<rfc::Link>(),
+            description(),
+            // NOTE: only drafts can use other kinds of values.
+            any::<Kind>().prop_filter("only drafts can use `kind: other`", |values| {
+                validate_values(values).is_ok()
+            }),
+            references(),
+            sensitivity(),
+            tags(),
+            relations(),
+            (
+                stewards(),
+                mappings(),
+                molecular(),
+                cytogenetics(),
+                option::of(definition()),
+                option::of(evidence_level()),
+                applicability(),
+            ),
+        )
+            .prop_map(
+                |(
+                    schema_version,
+                    name,
+                    translations,
+                    identifier,
+                    rfc,
+                    description,
+                    values,
+                    references,
+                    sensitivity,
+                    tags,
+                    relations,
+                    (
+                        stewards,
+                        mappings,
+                        molecular,
+                        cytogenetics,
+                        definition,
+                        evidence_level,
+                        applicability,
+                    ),
+                )| Self {
+                    schema_version,
+                    name,
+                    translations,
+                    identifier,
+                    rfc,
+                    description,
+                    values,
+                    references,
+                    evidence_level,
+                    sensitivity,
+                    tags,
+                    relations,
+                    stewards,
+                    mappings,
+                    molecular,
+                    cytogenetics,
+                    definition,
+                    applicability,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for OptionalCommon {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            schema_version(),
+            option::of(name()),
+            translations(),
+            option::of(any::<Identifier>()),
+            option::of(any::<rfc::Link>()),
+            option::of(description()),
+            option::of(any::<Kind>()),
+            references(),
+            sensitivity(),
+            tags(),
+            relations(),
+            (
+                stewards(),
+                mappings(),
+                molecular(),
+                cytogenetics(),
+                option::of(definition()),
+                option::of(evidence_level()),
+                applicability(),
+                option::of(date()),
+                option::of(date()),
+            ),
+        )
+            .prop_map(
+                |(
+                    schema_version,
+                    name,
+                    translations,
+                    identifier,
+                    rfc,
+                    description,
+                    values,
+                    references,
+                    sensitivity,
+                    tags,
+                    relations,
+                    (
+                        stewards,
+                        mappings,
+                        molecular,
+                        cytogenetics,
+                        definition,
+                        evidence_level,
+                        applicability,
+                        created_at,
+                        updated_at,
+                    ),
+                )| Self {
+                    schema_version,
+                    name,
+                    translations,
+                    identifier,
+                    rfc,
+                    description,
+                    values,
+                    references,
+                    evidence_level,
+                    sensitivity,
+                    tags,
+                    relations,
+                    stewards,
+                    mappings,
+                    molecular,
+                    cytogenetics,
+                    definition,
+                    applicability,
+                    created_at,
+                    updated_at,
+                },
+            )
+            .boxed()
+    }
+}
+
+impl Arbitrary for Characteristic {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        prop_oneof![
+            any::<OptionalCommon>().prop_map(|common| Characteristic::Draft { common }),
+            any::<Common>().prop_map(|common| Characteristic::Proposed { common }),
+            any::<Common>().prop_map(|common| Characteristic::Provisional { common }),
+            (any::<Common>(), date()).prop_map(|(common, adoption_date)| {
+                Characteristic::Adopted {
+                    common,
+                    adoption_date,
+                }
+            }),
+        ]
+        .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn identifier(identifier in any::<Identifier>()) {
+            let parsed = identifier.to_string().parse::<Identifier>().unwrap();
+            prop_assert_eq!(parsed, identifier);
+        }
+
+        #[test]
+        fn kind(kind in any::<Kind>()) {
+            let yaml = serde_yaml::to_string(&kind).unwrap();
+            prop_assert_eq!(serde_yaml::from_str::<Kind>(&yaml).unwrap(), kind);
+        }
+
+        #[test]
+        fn json(characteristic in any::<Characteristic>()) {
+            let json = serde_json::to_string(&characteristic).unwrap();
+            let parsed = serde_json::from_str::<Characteristic>(&json).unwrap();
+            prop_assert_eq!(&parsed, &characteristic);
+        }
+
+        #[test]
+        fn yaml(characteristic in any::<Characteristic>()) {
+            let yaml = serde_yaml::to_string(&characteristic).unwrap();
+            let parsed = serde_yaml::from_str::<Characteristic>(&yaml).unwrap();
+            prop_assert_eq!(parsed.content_hash(), characteristic.content_hash());
+            prop_assert_eq!(&parsed, &characteristic);
+        }
+    }
+}
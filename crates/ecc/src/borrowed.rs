@@ -0,0 +1,447 @@
+//! Borrowed views of characteristics for reading large exports.
+//!
+//! Deserializing a [`Characteristic`](crate::Characteristic) allocates a
+//! string for every name, description, option, and reference, and parses
+//! every sentence, link, and URL along the way. When reading the combined
+//! JSON export of a registry with thousands of characteristics, that cost
+//! dominates.
+//!
+//! The types in this module mirror the layout of a characteristic, but their
+//! text fields borrow from the input wherever possible (a [`Cow`] only owns
+//! its contents when the JSON string contained escape sequences). Apart from
+//! identifiers, language tags, sensitivities, tags, relations, GitHub handles,
+//! mapping predicates and terms, markers, gene symbols, HGVS expressions, ISCN
+//! expressions, definitions, applicability, and schema versions, the fields
+//! are _not_ validated, so the views are intended for exports that were
+//! written by this crate.
+//!
+//! This module is only available with the `zero-copy` feature.
+
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+use serde::Deserializer;
+use serde::Serialize;
+
+use crate::Identifier;
+use crate::State;
+use crate::common::Applicability;
+use crate::common::EvidenceLevel;
+use crate::common::Expression;
+use crate::common::Iscn;
+use crate::common::Molecular;
+use crate::common::Relation;
+use crate::common::Sensitivity;
+use crate::common::Tag;
+use crate::common::mapping::Curie;
+use crate::common::mapping::Predicate;
+use crate::common::steward::Handle;
+use crate::common::value::kind::binary;
+use crate::common::value::kind::fraction;
+use crate::common::value::kind::immunophenotype;
+use crate::common::value::kind::numerical;
+use crate::migrate::SchemaVersion;
+use crate::text::LanguageTag;
+
+/// A string that is borrowed from the input whenever possible.
+///
+/// `serde` only borrows a [`Cow`] when it is the type of a field itself, not
+/// when it is nested within an [`Option`] or a [`Vec`], so this wrapper is
+/// used to deserialize those.
+#[derive(Deserialize)]
+struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+/// Deserializes an optional string, borrowing it whenever possible.
+fn optional<'de: 'a, 'a, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Cow<'a, str>>, D::Error> {
+    let value = Option::<Borrowed<'a>>::deserialize(deserializer)?;
+    Ok(value.map(|value| value.0))
+}
+
+/// Deserializes a list of strings, borrowing them whenever possible.
+fn many<'de: 'a, 'a, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<Cow<'a, str>>, D::Error> {
+    let values = Vec::<Borrowed<'a>>::deserialize(deserializer)?;
+    Ok(values.into_iter().map(|value| value.0).collect())
+}
+
+/// Deserializes translations, borrowing them whenever possible.
+fn translations<'de: 'a, 'a, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<BTreeMap<LanguageTag, Cow<'a, str>>, D::Error> {
+    let values = BTreeMap::<LanguageTag, Borrowed<'a>>::deserialize(deserializer)?;
+    Ok(values
+        .into_iter()
+        .map(|(language, value)| (language, value.0))
+        .collect())
+}
+
+/// A borrowed description of a field value.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DescriptionRef<'a> {
+    /// A summary.
+    #[serde(borrow)]
+    pub summary: Cow<'a, str>,
+
+    /// The details.
+    #[serde(borrow)]
+    pub details: Cow<'a, str>,
+}
+
+/// A borrowed description of a binary feature kind.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BinaryRef<'a> {
+    /// A description of the `true` field value.
+    #[serde(borrow)]
+    pub r#true: DescriptionRef<'a>,
+
+    /// A description of the `false` field value.
+    #[serde(borrow)]
+    pub r#false: DescriptionRef<'a>,
+}
+
+/// A borrowed kind of permissible values.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum KindRef<'a> {
+    /// A binary feature.
+    Binary {
+        /// The description.
+        #[serde(borrow)]
+        description: BinaryRef<'a>,
+
+        /// The threshold that the feature is derived from (if any).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        threshold: Option<binary::Threshold>,
+    },
+
+    /// A categorical feature.
+    Categorical {
+        /// The set of values that the feature can take on.
+        #[serde(borrow, deserialize_with = "many")]
+        options: Vec<Cow<'a, str>>,
+    },
+
+    /// A numerical feature.
+    Numerical {
+        /// The type of numerical feature.
+        r#type: numerical::Type,
+
+        /// A description of the units of measurement.
+        #[serde(borrow)]
+        units: Cow<'a, str>,
+    },
+
+    /// A fractional feature.
+    Fraction {
+        /// A description of the whole that the fraction is a part of.
+        #[serde(borrow)]
+        denominator_description: Cow<'a, str>,
+
+        /// The scale that values are written on.
+        scale: fraction::Scale,
+
+        /// The smallest permissible value (if narrower than the scale).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        min: Option<f64>,
+
+        /// The largest permissible value (if narrower than the scale).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        max: Option<f64>,
+    },
+
+    /// An immunophenotypic feature.
+    Immunophenotype {
+        /// The markers that the feature assesses.
+        markers: Vec<immunophenotype::Marker>,
+    },
+
+    /// A kind of value that is not modeled by the schema yet.
+    Other {
+        /// A description of the values that the feature can take on.
+        #[serde(borrow)]
+        description: Cow<'a, str>,
+    },
+}
+
+/// The kind of publication a reference points to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Publication {
+    /// A peer-reviewed published manuscript.
+    Manuscript,
+
+    /// A non-peer reviewed preprint.
+    Preprint,
+}
+
+/// A borrowed reference.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReferenceRef<'a> {
+    /// The kind of publication.
+    pub kind: Publication,
+
+    /// The title of the publication.
+    #[serde(borrow)]
+    pub title: Cow<'a, str>,
+
+    /// The authors of the publication.
+    #[serde(borrow)]
+    pub authors: Cow<'a, str>,
+
+    /// The contextual relevance of the publication.
+    #[serde(borrow)]
+    pub context: Cow<'a, str>,
+
+    /// A URL where the publication can be accessed.
+    #[serde(borrow)]
+    pub url: Cow<'a, str>,
+
+    /// Whether or not the publication should be highlighted.
+    pub highlighted: bool,
+}
+
+/// A borrowed view of a steward.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StewardRef<'a> {
+    /// The name of the steward.
+    #[serde(borrow)]
+    pub name: Cow<'a, str>,
+
+    /// The GitHub handle of the steward.
+    pub github: Handle,
+
+    /// The email address of the steward.
+    #[serde(borrow)]
+    pub email: Cow<'a, str>,
+}
+
+/// A borrowed view of a mapping to a term within an external terminology.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MappingRef<'a> {
+    /// How the characteristic relates to the term.
+    pub predicate: Predicate,
+
+    /// The term.
+    pub term: Curie,
+
+    /// The label of the term.
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub label: Option<Cow<'a, str>>,
+}
+
+/// A borrowed view of a characteristic.
+///
+/// Every field other than the state is optional, as drafts may be missing
+/// any of them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CharacteristicRef<'a> {
+    /// The state.
+    pub state: State,
+
+    /// The version of the schema that the characteristic was written against.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<SchemaVersion>,
+
+    /// The name.
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub name: Option<Cow<'a, str>>,
+
+    /// Translations of the name into other languages.
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "translations",
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub translations: BTreeMap<LanguageTag, Cow<'a, str>>,
+
+    /// The identifier.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identifier: Option<Identifier>,
+
+    /// A link to the RFC.
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub rfc: Option<Cow<'a, str>>,
+
+    /// A description.
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<Cow<'a, str>>,
+
+    /// The permissible values.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub values: Option<KindRef<'a>>,
+
+    /// The references.
+    #[serde(borrow, default)]
+    pub references: Option<Vec<ReferenceRef<'a>>>,
+
+    /// The evidence level.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence_level: Option<EvidenceLevel>,
+
+    /// The sensitivity.
+    #[serde(default, skip_serializing_if = "Sensitivity::is_public")]
+    pub sensitivity: Sensitivity,
+
+    /// The tags.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<Tag>,
+
+    /// The relations to other characteristics.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub relations: Vec<Relation>,
+
+    /// The stewards.
+    #[serde(borrow, default, skip_serializing_if = "Option::is_none")]
+    pub stewards: Option<Vec<StewardRef<'a>>>,
+
+    /// The mappings to terms within external terminologies.
+    #[serde(borrow, default, skip_serializing_if = "Vec::is_empty")]
+    pub mappings: Vec<MappingRef<'a>>,
+
+    /// The genes and variants (for molecular characteristics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub molecular: Option<Molecular>,
+
+    /// The cytogenetic abnormalities (for cytogenetic characteristics).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cytogenetics: Vec<Iscn>,
+
+    /// The definition in terms of other characteristics (for composite
+    /// characteristics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub definition: Option<Expression>,
+
+    /// The samples that the characteristic applies to (if it does not apply
+    /// to every sample).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub applicability: Option<Applicability>,
+
+    /// When the draft was created (for drafts).
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub created_at: Option<Cow<'a, str>>,
+
+    /// When the draft was last updated (for drafts).
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub updated_at: Option<Cow<'a, str>>,
+
+    /// The date that the characteristic was adopted (for adopted
+    /// characteristics).
+    #[serde(
+        borrow,
+        default,
+        deserialize_with = "optional",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub adoption_date: Option<Cow<'a, str>>,
+}
+
+/// Reads a JSON export of characteristics (an array) without copying the text
+/// fields.
+pub fn from_json(json: &str) -> serde_json::Result<Vec<CharacteristicRef<'_>>> {
+    serde_json::from_str(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Characteristic;
+
+    #[test]
+    fn borrows() {
+        let json = r#"[{
+            "state": "adopted",
+            "name": "Has BCR::ABL1 Fusion",
+            "translations": { "de": "Hat BCR::ABL1-Fusion" },
+            "identifier": "ECC-MOLEC-000001",
+            "rfc": "https://github.com/stjudecloud/ecc/issues/1",
+            "description": "Whether the \"BCR::ABL1\" fusion was detected.",
+            "values": { "kind": "categorical", "options": ["Detected", "Not Detected"] },
+            "references": null,
+            "adoption_date": "2024-01-01T00:00:00Z"
+        }]"#;
+
+        let characteristics = from_json(json).unwrap();
+        let characteristic = &characteristics[0];
+
+        assert_eq!(characteristic.state, State::Adopted);
+        assert_eq!(
+            characteristic.identifier.as_ref().unwrap().to_string(),
+            "ECC-MOLEC-000001"
+        );
+        assert!(matches!(characteristic.name, Some(Cow::Borrowed(_))));
+        assert!(matches!(
+            characteristic.translations.get("de"),
+            Some(Cow::Borrowed("Hat BCR::ABL1-Fusion"))
+        ));
+
+        // NOTE: strings with escape sequences cannot be borrowed.
+        assert!(matches!(characteristic.description, Some(Cow::Owned(_))));
+
+        let Some(KindRef::Categorical { options }) = &characteristic.values else {
+            panic!("expected categorical values");
+        };
+        assert!(options.iter().all(|o| matches!(o, Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn rejects_invalid_identifiers() {
+        let json = r#"[{ "state": "draft", "identifier": "ECC-MORPH-1" }]"#;
+        assert!(from_json(json).is_err());
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn matches_owned(characteristic in proptest::prelude::any::<Characteristic>()) {
+            let json = serde_json::to_string(&vec![characteristic.clone()]).unwrap();
+            let borrowed = from_json(&json).unwrap();
+            let borrowed = &borrowed[0];
+
+            proptest::prop_assert_eq!(
+                borrowed.identifier.as_ref(),
+                characteristic.identifier()
+            );
+            proptest::prop_assert_eq!(borrowed.name.as_deref(), characteristic.name());
+
+            // NOTE: the borrowed view writes the same document back out.
+            let roundtrip = serde_json::to_string(&vec![borrowed]).unwrap();
+            let parsed = serde_json::from_str::<Vec<Characteristic>>(&roundtrip).unwrap();
+            proptest::prop_assert_eq!(&parsed[0], &characteristic);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Canonical serialization and content hashing.
+//!
+//! The canonical form of a value is compact JSON with the keys of every object
+//! sorted (by their UTF-8 bytes) and no insignificant whitespace. Two values
+//! that serialize to the same data always have the same canonical form
+//! regardless of the order fields were written in, so the canonical form is a
+//! stable basis for content hashes.
+
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Digest as _;
+use sha2::Sha256;
+
+/// The prefix of a content hash identifying the algorithm used.
+pub const HASH_PREFIX: &str = "sha256:";
+
+/// Writes a JSON value in canonical form.
+fn write(value: &Value, out: &mut String) {
+    match value {
+        Value::Array(values) => {
+            out.push('[');
+
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+
+                write(value, out);
+            }
+
+            out.push(']');
+        }
+        Value::Object(map) => {
+            // NOTE: the order of the keys within a [`serde_json::Map`] depends
+            // on whether the `preserve_order` feature is enabled anywhere in
+            // the dependency graph, so the keys are always sorted here.
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+
+            out.push('{');
+
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+
+                out.push_str(&Value::from(key.as_str()).to_string());
+                out.push(':');
+                write(value, out);
+            }
+
+            out.push('}');
+        }
+        _ => out.push_str(&value.to_string()),
+    }
+}
+
+/// Serializes a value into its canonical form.
+pub fn to_string<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let value = serde_json::to_value(value)?;
+    let mut out = String::new();
+    write(&value, &mut out);
+    Ok(out)
+}
+
+/// Computes the hex-encoded SHA-256 digest of some bytes.
+pub fn sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Computes the content hash of a value.
+///
+/// The hash is the SHA-256 digest of the canonical form (hex encoded and
+/// prefixed with [`HASH_PREFIX`]).
+pub fn hash<T: Serialize + ?Sized>(value: &T) -> Result<String, serde_json::Error> {
+    let digest = sha256(to_string(value)?.as_bytes());
+    Ok(format!("{HASH_PREFIX}{digest}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical() {
+        let a: Value =
+            serde_json::from_str(r#"{"b": [1, {"d": "x", "c": null}], "a": true}"#).unwrap();
+        let b: Value =
+            serde_json::from_str(r#"{"a": true, "b": [1, {"c": null, "d": "x"}]}"#).unwrap();
+
+        assert_eq!(
+            to_string(&a).unwrap(),
+            r#"{"a":true,"b":[1,{"c":null,"d":"x"}]}"#
+        );
+        assert_eq!(hash(&a).unwrap(), hash(&b).unwrap());
+        assert!(hash(&a).unwrap().starts_with(HASH_PREFIX));
+    }
+
+    #[test]
+    fn sha256() {
+        assert_eq!(
+            super::sha256(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Categories of characteristics.
+
+use alloc::string::String;
+use alloc::string::ToString as _;
+
+/// An error when parsing a category.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "unknown category: `{}`; expected `molecular` or `morphological`",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// The category of a characteristic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub enum Category {
+    /// A molecular characteristic.
+    Molecular,
+
+    /// A morphological characteristic.
+    Morphological,
+}
+
+impl Category {
+    /// All categories.
+    pub const ALL: [Category; 2] = [Category::Molecular, Category::Morphological];
+
+    /// Gets the short code used for the category within identifiers and
+    /// directory names (e.g., `MOLEC`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Category::Molecular => "MOLEC",
+            Category::Morphological => "MORPH",
+        }
+    }
+}
+
+impl core::fmt::Display for Category {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Category::Molecular => write!(f, "molecular"),
+            Category::Morphological => write!(f, "morphological"),
+        }
+    }
+}
+
+impl core::str::FromStr for Category {
+    type Err = ParseError;
+
+    /// Parses a category from either its full name or its short code (case
+    /// insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "molecular" | "molec" => Ok(Category::Molecular),
+            "morphological" | "morph" => Ok(Category::Morphological),
+            _ => Err(ParseError(s.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("molecular".parse::<Category>(), Ok(Category::Molecular));
+        assert_eq!("MOLEC".parse::<Category>(), Ok(Category::Molecular));
+        assert_eq!("morph".parse::<Category>(), Ok(Category::Morphological));
+
+        let err = "foo".parse::<Category>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown category: `foo`; expected `molecular` or `morphological`"
+        );
+    }
+}
@@ -0,0 +1,596 @@
+//! Composable characteristics.
+
+use chrono::DateTime;
+use chrono::Utc;
+use nonempty::NonEmpty;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Identifier;
+use crate::Link;
+use crate::State;
+use crate::canonical;
+use crate::common::Applicability;
+use crate::common::Common;
+use crate::common::EvidenceLevel;
+use crate::common::Expression;
+use crate::common::Iscn;
+use crate::common::Mapping;
+use crate::common::Molecular;
+use crate::common::OptionalCommon;
+use crate::common::Reference;
+use crate::common::Relation;
+use crate::common::Sensitivity;
+use crate::common::Steward;
+use crate::common::Tag;
+use crate::common::value::Kind;
+use crate::completeness;
+use crate::migrate;
+use crate::text::LanguageTag;
+use crate::text::language::Translations;
+
+/// A composable characteristic.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "lowercase", deny_unknown_fields)]
+pub enum Characteristic {
+    /// A characteristic that is currently being drafted.
+    Draft {
+        /// The common set of elements for any characteristic.
+        ///
+        /// In this case, the set of features is captured by an
+        /// [`OptionalCommon`], indicating that fields may or may not be present
+        /// in draft phase.
+        #[serde(flatten)]
+        common: OptionalCommon,
+    },
+
+    /// A characteristic that is currently being proposed to be adopted.
+    Proposed {
+        /// The common set of elements for any characteristic.
+        #[serde(flatten)]
+        common: Common,
+    },
+
+    /// An characteristics that has been accepted in principle and has entered
+    /// the settling phase of adoption.
+    Provisional {
+        /// The common set of elements for any characteristic.
+        #[serde(flatten)]
+        common: Common,
+    },
+
+    /// A characteristic that has been adopted.
+    Adopted {
+        /// The common set of elements for any characteristic.
+        #[serde(flatten)]
+        common: Common,
+
+        /// The date that the characteristic was adopted.
+        adoption_date: DateTime<Utc>,
+    },
+}
+
+impl Characteristic {
+    /// Gets the state of the characteristic within its lifecycle.
+    pub fn state(&self) -> State {
+        match self {
+            Characteristic::Draft { .. } => State::Draft,
+            Characteristic::Proposed { .. } => State::Proposed,
+            Characteristic::Provisional { .. } => State::Provisional,
+            Characteristic::Adopted { .. } => State::Adopted,
+        }
+    }
+
+    /// Gets the characteristic's identifier (if one has been assigned).
+    pub fn identifier(&self) -> Option<&Identifier> {
+        match self {
+            Characteristic::Draft { common, .. } => common.identifier.as_ref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common }
+            | Characteristic::Adopted { common, .. } => Some(&common.identifier),
+        }
+    }
+
+    /// Gets the version of the schema that the characteristic was written
+    /// against (if it has one).
+    pub fn schema_version(&self) -> Option<migrate::SchemaVersion> {
+        match self {
+            Characteristic::Draft { common } => common.schema_version,
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common }
+            | Characteristic::Adopted { common, .. } => common.schema_version,
+        }
+    }
+
+    /// Gets the name.
+    pub fn name(&self) -> Option<&str> {
+        match self {
+            Characteristic::Draft { common } => common.name.as_deref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => Some(&common.name),
+        }
+    }
+
+    /// Gets the translations of the name.
+    pub fn translations(&self) -> &Translations {
+        match self {
+            Characteristic::Draft { common } => common.translations(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => common.translations(),
+        }
+    }
+
+    /// Gets the name to display in a language (if the characteristic has a
+    /// name).
+    ///
+    /// Falls back to the (English) name if the name has not been translated
+    /// into the language.
+    pub fn display_name(&self, language: &LanguageTag) -> Option<&str> {
+        match self {
+            Characteristic::Draft { common } => common.display_name(language),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => Some(common.display_name(language)),
+        }
+    }
+
+    /// Gets the URL for the associated RFC.
+    pub fn rfc(&self) -> Option<&Link> {
+        match self {
+            Characteristic::Draft { common } => common.rfc.as_ref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => Some(&common.rfc),
+        }
+    }
+
+    /// Gets the permissible values.
+    pub fn values(&self) -> Option<&Kind> {
+        match self {
+            Characteristic::Draft { common } => common.values.as_ref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => Some(&common.values),
+        }
+    }
+
+    /// Gets the description.
+    pub fn description(&self) -> Option<&str> {
+        match self {
+            Characteristic::Draft { common } => common.description.as_deref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => Some(common.description.as_str()),
+        }
+    }
+
+    /// Gets the references.
+    pub fn references(&self) -> Option<impl Iterator<Item = &Reference>> {
+        match self {
+            Characteristic::Draft { common } => common
+                .references
+                .as_ref()
+                .map(|publications| publications.iter()),
+            Characteristic::Proposed { common } => common
+                .references
+                .as_ref()
+                .map(|publications| publications.iter()),
+            Characteristic::Provisional { common, .. } => common
+                .references
+                .as_ref()
+                .map(|publications| publications.iter()),
+            Characteristic::Adopted { common, .. } => common
+                .references
+                .as_ref()
+                .map(|publications| publications.iter()),
+        }
+    }
+
+    /// Gets the level of the evidence supporting the characteristic (if it
+    /// has been graded).
+    pub fn evidence_level(&self) -> Option<EvidenceLevel> {
+        match self {
+            Characteristic::Draft { common } => common.evidence_level,
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => common.evidence_level,
+        }
+    }
+
+    /// Gets the sensitivity.
+    pub fn sensitivity(&self) -> Sensitivity {
+        match self {
+            Characteristic::Draft { common } => common.sensitivity,
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => common.sensitivity,
+        }
+    }
+
+    /// Gets the tags.
+    pub fn tags(&self) -> &[Tag] {
+        match self {
+            Characteristic::Draft { common } => &common.tags,
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => &common.tags,
+        }
+    }
+
+    /// Gets the relations to other characteristics.
+    pub fn relations(&self) -> &[Relation] {
+        match self {
+            Characteristic::Draft { common } => &common.relations,
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => &common.relations,
+        }
+    }
+
+    /// Gets the stewards (if any are listed).
+    pub fn stewards(&self) -> Option<&NonEmpty<Steward>> {
+        match self {
+            Characteristic::Draft { common } => common.stewards.as_ref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => common.stewards.as_ref(),
+        }
+    }
+
+    /// Gets the mappings to terms within external terminologies.
+    pub fn mappings(&self) -> &[Mapping] {
+        match self {
+            Characteristic::Draft { common } => &common.mappings,
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => &common.mappings,
+        }
+    }
+
+    /// Gets the genes and variants (if any are listed).
+    pub fn molecular(&self) -> Option<&Molecular> {
+        match self {
+            Characteristic::Draft { common } => common.molecular.as_ref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => common.molecular.as_ref(),
+        }
+    }
+
+    /// Gets the cytogenetic abnormalities.
+    pub fn cytogenetics(&self) -> &[Iscn] {
+        match self {
+            Characteristic::Draft { common } => &common.cytogenetics,
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => &common.cytogenetics,
+        }
+    }
+
+    /// Gets the definition in terms of other characteristics (if it has one).
+    pub fn definition(&self) -> Option<&Expression> {
+        match self {
+            Characteristic::Draft { common } => common.definition.as_ref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => common.definition.as_ref(),
+        }
+    }
+
+    /// Gets the samples that the characteristic applies to (if it does not
+    /// apply to every sample).
+    pub fn applicability(&self) -> Option<&Applicability> {
+        match self {
+            Characteristic::Draft { common } => common.applicability.as_ref(),
+            Characteristic::Proposed { common }
+            | Characteristic::Provisional { common, .. }
+            | Characteristic::Adopted { common, .. } => common.applicability.as_ref(),
+        }
+    }
+
+    /// Scores how complete the documentation of the characteristic is (see
+    /// [`crate::completeness`]).
+    pub fn completeness(&self) -> completeness::Score {
+        completeness::Score::of(self)
+    }
+
+    /// Gets the adoption date (if it the characteristic has been adopted).
+    pub fn adoption_date(&self) -> Option<&DateTime<Utc>> {
+        match self {
+            Characteristic::Draft { .. }
+            | Characteristic::Proposed { .. }
+            | Characteristic::Provisional { .. } => None,
+            Characteristic::Adopted { adoption_date, .. } => Some(adoption_date),
+        }
+    }
+
+    /// Computes the content hash of the characteristic.
+    ///
+    /// The hash is computed over the canonical serialization (see
+    /// [`canonical`]), so it only changes when the content of the
+    /// characteristic does.
+    pub fn content_hash(&self) -> String {
+        // SAFETY: characteristics always serialize to JSON (every map within a
+        // characteristic has string keys).
+        canonical::hash(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::LazyLock;
+
+    use nonempty::NonEmpty;
+    use url::Url;
+
+    use super::*;
+    use crate::common::Reference;
+    use crate::common::value::Kind;
+    use crate::field;
+    use crate::text::Sentence;
+
+    static RFC_LINK: LazyLock<Link> = LazyLock::new(|| {
+        "https://github.com/stjudecloud/ecc/issues/1"
+            .parse::<Link>()
+            .unwrap()
+    });
+
+    #[test]
+    fn features() {
+        let identifier = "ECC-MORPH-000001".parse::<Identifier>().unwrap();
+
+        let values = Kind::Binary {
+            description: crate::common::value::kind::binary::Description {
+                r#true: field::Description {
+                    summary: "Foo".parse::<Sentence>().unwrap(),
+                    details: "Bar".parse::<Sentence>().unwrap(),
+                },
+                r#false: field::Description {
+                    summary: "Baz".parse::<Sentence>().unwrap(),
+                    details: "Quux".parse::<Sentence>().unwrap(),
+                },
+            },
+            threshold: None,
+        };
+
+        //=======//
+        // Draft //
+        //=======//
+
+        let draft = Characteristic::Draft {
+            common: OptionalCommon {
+                schema_version: None,
+                name: Some(String::from("A Characteristic Name")),
+                translations: Translations::from([(
+                    "de".parse().unwrap(),
+                    String::from("Ein Merkmalsname"),
+                )]),
+                identifier: None,
+                rfc: Some(RFC_LINK.clone()),
+                values: Some(values.clone()),
+                description: Some(String::from("A description")),
+                references: Some(NonEmpty::new(Reference::Manuscript {
+                    title: String::from("The Discovery of Foo Bar"),
+                    authors: String::from("Jane Smith"),
+                    context: "Some context about the manuscript"
+                        .parse::<Sentence>()
+                        .unwrap(),
+                    url: "https://nature.org/the-discovery-of-foo-bar"
+                        .parse::<Url>()
+                        .unwrap(),
+                    highlighted: false,
+                })),
+                evidence_level: Some(EvidenceLevel::SingleStudy),
+                sensitivity: Sensitivity::Controlled,
+                tags: vec!["pediatric".parse().unwrap()],
+                relations: Vec::new(),
+                stewards: None,
+                mappings: Vec::new(),
+                molecular: None,
+                cytogenetics: Vec::new(),
+                definition: None,
+                applicability: None,
+                created_at: None,
+                updated_at: None,
+            },
+        };
+
+        assert!(draft.identifier().is_none());
+        assert_eq!(draft.name().unwrap(), "A Characteristic Name");
+        assert_eq!(
+            draft.display_name(&"de-CH".parse().unwrap()),
+            Some("Ein Merkmalsname")
+        );
+        assert_eq!(
+            draft.display_name(&"fr".parse().unwrap()),
+            Some("A Characteristic Name")
+        );
+        assert_eq!(
+            draft.rfc().unwrap().as_str(),
+            "https://github.com/stjudecloud/ecc/issues/1"
+        );
+        assert_eq!(draft.description().unwrap(), "A description");
+        assert_eq!(draft.values().unwrap(), &values);
+        assert_eq!(draft.references().unwrap().count(), 1);
+        assert!(draft.adoption_date().is_none());
+        assert_eq!(draft.evidence_level(), Some(EvidenceLevel::SingleStudy));
+        assert_eq!(draft.sensitivity(), Sensitivity::Controlled);
+        assert_eq!(draft.tags()[0].as_str(), "pediatric");
+        assert_eq!(draft.state(), State::Draft);
+
+        //==========//
+        // Proposed //
+        //==========//
+
+        let proposed = Characteristic::Proposed {
+            common: Common {
+                schema_version: None,
+                name: String::from("A Characteristic Name"),
+                translations: Translations::new(),
+                identifier: identifier.clone(),
+                rfc: RFC_LINK.clone(),
+                values: values.clone(),
+                description: String::from("A description"),
+                references: Some(NonEmpty::new(Reference::Manuscript {
+                    title: String::from("The Discovery of Foo Bar"),
+                    authors: String::from("Jane Smith"),
+                    context: "Some context about the manuscript"
+                        .parse::<Sentence>()
+                        .unwrap(),
+                    url: "https://nature.org/the-discovery-of-foo-bar"
+                        .parse::<Url>()
+                        .unwrap(),
+                    highlighted: false,
+                })),
+                evidence_level: None,
+                sensitivity: Sensitivity::Public,
+                tags: Vec::new(),
+                relations: Vec::new(),
+                stewards: None,
+                mappings: Vec::new(),
+                molecular: None,
+                cytogenetics: Vec::new(),
+                definition: None,
+                applicability: None,
+            },
+        };
+
+        assert_eq!(proposed.identifier().unwrap(), &identifier);
+        assert_eq!(draft.name().unwrap(), "A Characteristic Name");
+        assert_eq!(
+            proposed.rfc().unwrap().as_str(),
+            "https://github.com/stjudecloud/ecc/issues/1"
+        );
+        assert_eq!(draft.description().unwrap(), "A description");
+        assert_eq!(draft.values().unwrap(), &values);
+        assert_eq!(draft.references().unwrap().count(), 1);
+        assert!(proposed.adoption_date().is_none());
+        assert_eq!(proposed.state(), State::Proposed);
+
+        //=============//
+        // Provisional //
+        //=============//
+
+        let provisional = Characteristic::Provisional {
+            common: Common {
+                schema_version: None,
+                name: String::from("A Characteristic Name"),
+                translations: Translations::new(),
+                identifier: identifier.clone(),
+                rfc: RFC_LINK.clone(),
+                values: values.clone(),
+                description: String::from("A description"),
+                references: Some(NonEmpty::new(Reference::Manuscript {
+                    title: String::from("The Discovery of Foo Bar"),
+                    authors: String::from("Jane Smith"),
+                    context: "Some context about the manuscript"
+                        .parse::<Sentence>()
+                        .unwrap(),
+                    url: "https://nature.org/the-discovery-of-foo-bar"
+                        .parse::<Url>()
+                        .unwrap(),
+                    highlighted: false,
+                })),
+                evidence_level: None,
+                sensitivity: Sensitivity::Public,
+                tags: Vec::new(),
+                relations: Vec::new(),
+                stewards: None,
+                mappings: Vec::new(),
+                molecular: None,
+                cytogenetics: Vec::new(),
+                definition: None,
+                applicability: None,
+            },
+        };
+
+        assert_eq!(proposed.identifier().unwrap(), &identifier);
+        assert_eq!(draft.name().unwrap(), "A Characteristic Name");
+        assert_eq!(
+            provisional.rfc().unwrap().as_str(),
+            "https://github.com/stjudecloud/ecc/issues/1"
+        );
+        assert_eq!(draft.description().unwrap(), "A description");
+        assert_eq!(draft.values().unwrap(), &values);
+        assert_eq!(draft.references().unwrap().count(), 1);
+        assert!(provisional.adoption_date().is_none());
+        assert_eq!(provisional.state(), State::Provisional);
+
+        //=========//
+        // Adopted //
+        //=========//
+
+        let adopted = Characteristic::Adopted {
+            common: Common {
+                schema_version: None,
+                name: String::from("A Characteristic Name"),
+                translations: Translations::new(),
+                identifier: identifier.clone(),
+                rfc: RFC_LINK.clone(),
+                values: values.clone(),
+                description: String::from("A description"),
+                references: Some(NonEmpty::new(Reference::Manuscript {
+                    title: String::from("The Discovery of Foo Bar"),
+                    authors: String::from("Jane Smith"),
+                    context: "Some context about the manuscript"
+                        .parse::<Sentence>()
+                        .unwrap(),
+                    url: "https://nature.org/the-discovery-of-foo-bar"
+                        .parse::<Url>()
+                        .unwrap(),
+                    highlighted: false,
+                })),
+                evidence_level: None,
+                sensitivity: Sensitivity::Public,
+                tags: Vec::new(),
+                relations: Vec::new(),
+                stewards: None,
+                mappings: Vec::new(),
+                molecular: None,
+                cytogenetics: Vec::new(),
+                definition: None,
+                applicability: None,
+            },
+            adoption_date: Utc::now(),
+        };
+
+        assert_eq!(proposed.identifier().unwrap(), &identifier);
+        assert_eq!(draft.name().unwrap(), "A Characteristic Name");
+        assert_eq!(
+            adopted.rfc().unwrap().as_str(),
+            "https://github.com/stjudecloud/ecc/issues/1"
+        );
+        assert_eq!(draft.description().unwrap(), "A description");
+        assert_eq!(draft.values().unwrap(), &values);
+        assert_eq!(draft.references().unwrap().count(), 1);
+        assert!(adopted.adoption_date().is_some());
+        assert_eq!(adopted.state(), State::Adopted);
+    }
+
+    #[test]
+    fn content_hash() {
+        let a: Characteristic = serde_yaml::from_str(
+            r#"state: draft
+name: Foo Bar
+description: A description."#,
+        )
+        .unwrap();
+        let b: Characteristic = serde_yaml::from_str(
+            r#"description: A description.
+name: Foo Bar
+state: draft"#,
+        )
+        .unwrap();
+        let c: Characteristic = serde_yaml::from_str(
+            r#"state: draft
+name: Foo Bar
+description: Another description."#,
+        )
+        .unwrap();
+
+        assert_eq!(a.content_hash(), b.content_hash());
+        assert_ne!(a.content_hash(), c.content_hash());
+    }
+}
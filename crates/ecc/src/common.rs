@@ -1,40 +1,493 @@
 //! Common features for a composable characteristics.
 
+#[cfg(feature = "full")]
 use nonempty::NonEmpty;
+#[cfg(feature = "full")]
 use serde::Deserialize;
+#[cfg(feature = "full")]
+use serde::Deserializer;
+#[cfg(feature = "full")]
 use serde::Serialize;
 
+#[cfg(feature = "full")]
 use crate::Identifier;
+#[cfg(feature = "full")]
+use crate::migrate::SchemaVersion;
+#[cfg(feature = "full")]
 use crate::rfc;
+#[cfg(feature = "full")]
+use crate::text::LanguageTag;
+#[cfg(feature = "full")]
+use crate::text::language;
+#[cfg(feature = "full")]
+use crate::text::language::Translations;
 
+#[cfg(feature = "full")]
+pub mod applicability;
+#[cfg(feature = "full")]
+pub mod cytogenetics;
+pub mod evidence;
+#[cfg(feature = "full")]
+pub mod expression;
+#[cfg(feature = "full")]
+pub mod mapping;
+#[cfg(feature = "full")]
+pub mod molecular;
+#[cfg(feature = "full")]
 mod optional;
+#[cfg(feature = "full")]
 mod reference;
+#[cfg(feature = "full")]
+pub mod relation;
+pub mod sensitivity;
+#[cfg(feature = "full")]
+pub mod steward;
+pub mod tag;
 pub mod value;
 
+#[cfg(feature = "full")]
+pub use applicability::Applicability;
+#[cfg(feature = "full")]
+pub use cytogenetics::Iscn;
+pub use evidence::EvidenceLevel;
+#[cfg(feature = "full")]
+pub use expression::Expression;
+#[cfg(feature = "full")]
+pub use mapping::Mapping;
+#[cfg(feature = "full")]
+pub use molecular::Molecular;
+#[cfg(feature = "full")]
+pub use optional::MissingField;
+#[cfg(feature = "full")]
 pub use optional::OptionalCommon;
+#[cfg(feature = "full")]
 pub use reference::Reference;
+#[cfg(feature = "full")]
+pub use relation::Relation;
+pub use sensitivity::Sensitivity;
+#[cfg(feature = "full")]
+pub use steward::Steward;
+pub use tag::Tag;
+
+/// An error when setting a field within a common set of elements.
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum Error {
+    /// A text field was empty.
+    #[error("`{0}` must not be empty")]
+    Empty(&'static str),
+
+    /// A text field had surrounding whitespace.
+    #[error("`{0}` must not have surrounding whitespace")]
+    Whitespace(&'static str),
+
+    /// The permissible values used a kind that is only allowed within drafts.
+    #[error(
+        "only drafts can use `kind: other`; describe the values with a kind that the schema \
+         supports"
+    )]
+    OtherKind,
+}
+
+/// Ensures that a name is not empty and has no surrounding whitespace.
+pub fn validate_name(name: &str) -> Result<(), Error> {
+    if name.trim().is_empty() {
+        return Err(Error::Empty("name"));
+    }
+
+    if name.trim() != name {
+        return Err(Error::Whitespace("name"));
+    }
+
+    Ok(())
+}
+
+/// Ensures that a description is not empty.
+pub fn validate_description(description: &str) -> Result<(), Error> {
+    if description.trim().is_empty() {
+        return Err(Error::Empty("description"));
+    }
+
+    Ok(())
+}
+
+/// Ensures that the permissible values use a kind that the schema models
+/// (i.e., not `kind: other`), as is required outside of drafts.
+pub fn validate_values(values: &value::Kind) -> Result<(), Error> {
+    if let value::Kind::Other { .. } = values {
+        return Err(Error::OtherKind);
+    }
+
+    Ok(())
+}
+
+/// Deserializes a text field, rejecting it if it is invalid.
+#[cfg(feature = "full")]
+fn validated<'de, D: Deserializer<'de>>(
+    deserializer: D,
+    validate: fn(&str) -> Result<(), Error>,
+) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    validate(&value).map_err(serde::de::Error::custom)?;
+    Ok(value)
+}
+
+/// Deserializes a name (see [`validate_name()`]).
+#[cfg(feature = "full")]
+pub(crate) fn deserialize_name<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    validated(deserializer, validate_name)
+}
+
+/// Deserializes a description (see [`validate_description()`]).
+#[cfg(feature = "full")]
+pub(crate) fn deserialize_description<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<String, D::Error> {
+    validated(deserializer, validate_description)
+}
+
+/// Deserializes the permissible values (see [`validate_values()`]).
+#[cfg(feature = "full")]
+fn deserialize_values<'de, D: Deserializer<'de>>(deserializer: D) -> Result<value::Kind, D::Error> {
+    let values = value::Kind::deserialize(deserializer)?;
+    validate_values(&values).map_err(serde::de::Error::custom)?;
+    Ok(values)
+}
 
 /// Common features for composable characteristics in any stage.
+#[cfg(feature = "full")]
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Common {
+    /// The version of the schema that the characteristic was written against.
+    ///
+    /// Characteristics without a schema version predate versioning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) schema_version: Option<SchemaVersion>,
+
     /// The name.
-    pub name: String,
+    #[serde(deserialize_with = "deserialize_name")]
+    pub(crate) name: String,
+
+    /// Translations of the name into other languages.
+    #[serde(default, skip_serializing_if = "Translations::is_empty")]
+    pub(crate) translations: Translations,
 
     /// The provisional identifier.
-    pub identifier: Identifier,
+    pub(crate) identifier: Identifier,
 
     /// A link to the RFC for the characteristic.
     ///
     /// All discussion of the characteristic, whether in the draft phase or
     /// questions after adoption, should occur within this RFC link.
-    pub rfc: rfc::Link,
+    pub(crate) rfc: rfc::Link,
 
     /// A description.
-    pub description: String,
+    #[serde(deserialize_with = "deserialize_description")]
+    pub(crate) description: String,
 
     /// The permissible values that the characteristic takes.
-    pub values: value::Kind,
+    #[serde(deserialize_with = "deserialize_values")]
+    pub(crate) values: value::Kind,
 
     /// An optional list of publications.
-    pub references: Option<NonEmpty<Reference>>,
+    pub(crate) references: Option<NonEmpty<Reference>>,
+
+    /// How strong the evidence supporting the characteristic is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) evidence_level: Option<EvidenceLevel>,
+
+    /// How restricted the handling of the data described by the
+    /// characteristic is.
+    #[serde(default, skip_serializing_if = "Sensitivity::is_public")]
+    pub(crate) sensitivity: Sensitivity,
+
+    /// The tags from the controlled vocabulary.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tags: Vec<Tag>,
+
+    /// The typed relations to other characteristics.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) relations: Vec<Relation>,
+
+    /// The people responsible for the characteristic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) stewards: Option<NonEmpty<Steward>>,
+
+    /// The mappings to terms within external terminologies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) mappings: Vec<Mapping>,
+
+    /// The genes and variants (for molecular characteristics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) molecular: Option<Molecular>,
+
+    /// The cytogenetic abnormalities (for cytogenetic characteristics).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) cytogenetics: Vec<Iscn>,
+
+    /// The definition in terms of other characteristics (for composite
+    /// characteristics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) definition: Option<Expression>,
+
+    /// The samples that the characteristic applies to (if it does not apply
+    /// to every sample).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) applicability: Option<Applicability>,
+}
+
+#[cfg(feature = "full")]
+impl Common {
+    /// Creates a new common set of elements.
+    pub fn new(
+        name: impl Into<String>,
+        identifier: Identifier,
+        rfc: rfc::Link,
+        description: impl Into<String>,
+        values: value::Kind,
+    ) -> Result<Self, Error> {
+        let name = name.into();
+        let description = description.into();
+
+        validate_name(&name)?;
+        validate_description(&description)?;
+
+        Ok(Self {
+            schema_version: Some(SchemaVersion::CURRENT),
+            name,
+            translations: Translations::new(),
+            identifier,
+            rfc,
+            description,
+            values,
+            references: None,
+            evidence_level: None,
+            sensitivity: Sensitivity::default(),
+            tags: Vec::new(),
+            relations: Vec::new(),
+            stewards: None,
+            mappings: Vec::new(),
+            molecular: None,
+            cytogenetics: Vec::new(),
+            definition: None,
+            applicability: None,
+        })
+    }
+
+    /// Gets the version of the schema that the characteristic was written
+    /// against (if it has one).
+    pub fn schema_version(&self) -> Option<SchemaVersion> {
+        self.schema_version
+    }
+
+    /// Gets the name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the translations of the name.
+    pub fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    /// Gets the name to display in a language.
+    ///
+    /// Falls back to the (English) name if the name has not been translated
+    /// into the language (see [`language::translate()`]).
+    pub fn display_name(&self, language: &LanguageTag) -> &str {
+        language::translate(&self.translations, language).unwrap_or(&self.name)
+    }
+
+    /// Gets the provisional identifier.
+    pub fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    /// Gets the link to the RFC.
+    pub fn rfc(&self) -> &rfc::Link {
+        &self.rfc
+    }
+
+    /// Gets the description.
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Gets the permissible values.
+    pub fn values(&self) -> &value::Kind {
+        &self.values
+    }
+
+    /// Gets the references (if there are any).
+    pub fn references(&self) -> Option<&NonEmpty<Reference>> {
+        self.references.as_ref()
+    }
+
+    /// Gets the level of the evidence supporting the characteristic (if it
+    /// has been graded).
+    pub fn evidence_level(&self) -> Option<EvidenceLevel> {
+        self.evidence_level
+    }
+
+    /// Gets the sensitivity.
+    pub fn sensitivity(&self) -> Sensitivity {
+        self.sensitivity
+    }
+
+    /// Gets the tags.
+    pub fn tags(&self) -> &[Tag] {
+        &self.tags
+    }
+
+    /// Gets the relations to other characteristics.
+    pub fn relations(&self) -> &[Relation] {
+        &self.relations
+    }
+
+    /// Gets the stewards (if any are listed).
+    pub fn stewards(&self) -> Option<&NonEmpty<Steward>> {
+        self.stewards.as_ref()
+    }
+
+    /// Gets the mappings to terms within external terminologies.
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// Gets the genes and variants (if any are listed).
+    pub fn molecular(&self) -> Option<&Molecular> {
+        self.molecular.as_ref()
+    }
+
+    /// Gets the cytogenetic abnormalities.
+    pub fn cytogenetics(&self) -> &[Iscn] {
+        &self.cytogenetics
+    }
+
+    /// Gets the definition in terms of other characteristics (if it has one).
+    pub fn definition(&self) -> Option<&Expression> {
+        self.definition.as_ref()
+    }
+
+    /// Gets the samples that the characteristic applies to (if it does not
+    /// apply to every sample).
+    pub fn applicability(&self) -> Option<&Applicability> {
+        self.applicability.as_ref()
+    }
+
+    /// Sets the version of the schema.
+    pub fn set_schema_version(&mut self, schema_version: Option<SchemaVersion>) {
+        self.schema_version = schema_version;
+    }
+
+    /// Sets the name.
+    ///
+    /// The name must not be empty or have surrounding whitespace.
+    pub fn set_name(&mut self, name: impl Into<String>) -> Result<(), Error> {
+        let name = name.into();
+        validate_name(&name)?;
+        self.name = name;
+        Ok(())
+    }
+
+    /// Sets the translation of the name into a language, returning the
+    /// previous translation (if any).
+    ///
+    /// The translation must not be empty or have surrounding whitespace.
+    pub fn set_translation(
+        &mut self,
+        language: LanguageTag,
+        name: impl Into<String>,
+    ) -> Result<Option<String>, Error> {
+        let name = name.into();
+        validate_name(&name)?;
+        Ok(self.translations.insert(language, name))
+    }
+
+    /// Removes the translation of the name into a language, returning it (if
+    /// there was one).
+    pub fn remove_translation(&mut self, language: &LanguageTag) -> Option<String> {
+        self.translations.remove(language)
+    }
+
+    /// Sets the provisional identifier.
+    pub fn set_identifier(&mut self, identifier: Identifier) {
+        self.identifier = identifier;
+    }
+
+    /// Sets the link to the RFC.
+    pub fn set_rfc(&mut self, rfc: rfc::Link) {
+        self.rfc = rfc;
+    }
+
+    /// Sets the description.
+    ///
+    /// The description must not be empty.
+    pub fn set_description(&mut self, description: impl Into<String>) -> Result<(), Error> {
+        let description = description.into();
+        validate_description(&description)?;
+        self.description = description;
+        Ok(())
+    }
+
+    /// Sets the permissible values.
+    pub fn set_values(&mut self, values: value::Kind) {
+        self.values = values;
+    }
+
+    /// Sets the references.
+    pub fn set_references(&mut self, references: Option<NonEmpty<Reference>>) {
+        self.references = references;
+    }
+
+    /// Sets the level of the evidence supporting the characteristic.
+    pub fn set_evidence_level(&mut self, evidence_level: Option<EvidenceLevel>) {
+        self.evidence_level = evidence_level;
+    }
+
+    /// Sets the sensitivity.
+    pub fn set_sensitivity(&mut self, sensitivity: Sensitivity) {
+        self.sensitivity = sensitivity;
+    }
+
+    /// Sets the tags.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) {
+        self.tags = tags;
+    }
+
+    /// Sets the relations to other characteristics.
+    pub fn set_relations(&mut self, relations: Vec<Relation>) {
+        self.relations = relations;
+    }
+
+    /// Sets the stewards.
+    pub fn set_stewards(&mut self, stewards: Option<NonEmpty<Steward>>) {
+        self.stewards = stewards;
+    }
+
+    /// Sets the mappings to terms within external terminologies.
+    pub fn set_mappings(&mut self, mappings: Vec<Mapping>) {
+        self.mappings = mappings;
+    }
+
+    /// Sets the genes and variants.
+    pub fn set_molecular(&mut self, molecular: Option<Molecular>) {
+        self.molecular = molecular;
+    }
+
+    /// Sets the cytogenetic abnormalities.
+    pub fn set_cytogenetics(&mut self, cytogenetics: Vec<Iscn>) {
+        self.cytogenetics = cytogenetics;
+    }
+
+    /// Sets the definition in terms of other characteristics.
+    pub fn set_definition(&mut self, definition: Option<Expression>) {
+        self.definition = definition;
+    }
+
+    /// Sets the samples that the characteristic applies to.
+    pub fn set_applicability(&mut self, applicability: Option<Applicability>) {
+        self.applicability = applicability;
+    }
 }
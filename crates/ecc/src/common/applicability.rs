@@ -0,0 +1,339 @@
+//! The samples that characteristics apply to.
+//!
+//! Some characteristics are only meaningful for some samples (e.g., a
+//! characteristic describing marrow blasts does not apply to solid tumors).
+//! The applicability of a characteristic narrows the samples it may be
+//! annotated on along three axes: the ontology subtrees the sample is
+//! classified under, the type of specimen, and the age group of the patient.
+//! An axis without any entries is unconstrained.
+//!
+//! Ontology nodes are referred to by name. Whether they exist requires the
+//! ontology graph, so that check is left to tools that have it (e.g., the
+//! registry when it is loaded).
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An error when parsing a specimen type.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseSpecimenTypeError(String);
+
+impl std::fmt::Display for ParseSpecimenTypeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown specimen type: `{}`; expected `blood`, `bone-marrow`, `cerebrospinal-fluid`, \
+             or `tissue`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseSpecimenTypeError {}
+
+/// The type of specimen that a sample was taken from.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub enum SpecimenType {
+    /// Peripheral blood.
+    Blood,
+
+    /// Bone marrow (aspirate or biopsy).
+    BoneMarrow,
+
+    /// Cerebrospinal fluid.
+    CerebrospinalFluid,
+
+    /// Solid tissue (e.g., a tumor resection or biopsy).
+    Tissue,
+}
+
+impl SpecimenType {
+    /// All specimen types.
+    pub const ALL: [SpecimenType; 4] = [
+        SpecimenType::Blood,
+        SpecimenType::BoneMarrow,
+        SpecimenType::CerebrospinalFluid,
+        SpecimenType::Tissue,
+    ];
+
+    /// Gets the name of the specimen type (e.g., `bone-marrow`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecimenType::Blood => "blood",
+            SpecimenType::BoneMarrow => "bone-marrow",
+            SpecimenType::CerebrospinalFluid => "cerebrospinal-fluid",
+            SpecimenType::Tissue => "tissue",
+        }
+    }
+}
+
+impl std::fmt::Display for SpecimenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for SpecimenType {
+    type Err = ParseSpecimenTypeError;
+
+    /// Parses a specimen type from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SpecimenType::ALL
+            .into_iter()
+            .find(|specimen_type| specimen_type.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseSpecimenTypeError(s.to_string()))
+    }
+}
+
+/// An error when parsing an age group.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseAgeGroupError(String);
+
+impl std::fmt::Display for ParseAgeGroupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown age group: `{}`; expected `infant`, `child`, `adolescent-young-adult`, or \
+             `adult`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseAgeGroupError {}
+
+/// The age group of a patient at the time a sample was taken.
+///
+/// Age groups are ordered from the youngest to the oldest.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub enum AgeGroup {
+    /// Younger than one year.
+    Infant,
+
+    /// At least one and younger than 15 years.
+    Child,
+
+    /// At least 15 and younger than 40 years.
+    AdolescentYoungAdult,
+
+    /// At least 40 years.
+    Adult,
+}
+
+impl AgeGroup {
+    /// All age groups from the youngest to the oldest.
+    pub const ALL: [AgeGroup; 4] = [
+        AgeGroup::Infant,
+        AgeGroup::Child,
+        AgeGroup::AdolescentYoungAdult,
+        AgeGroup::Adult,
+    ];
+
+    /// Gets the name of the age group (e.g., `adolescent-young-adult`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AgeGroup::Infant => "infant",
+            AgeGroup::Child => "child",
+            AgeGroup::AdolescentYoungAdult => "adolescent-young-adult",
+            AgeGroup::Adult => "adult",
+        }
+    }
+
+    /// Gets the age (in years) that the age group starts at.
+    pub fn min_years(&self) -> f64 {
+        match self {
+            AgeGroup::Infant => 0.0,
+            AgeGroup::Child => 1.0,
+            AgeGroup::AdolescentYoungAdult => 15.0,
+            AgeGroup::Adult => 40.0,
+        }
+    }
+
+    /// Gets the age group that an age (in years) falls within.
+    ///
+    /// Returns [`None`] if the age is negative or not a number.
+    pub fn from_years(years: f64) -> Option<Self> {
+        if years.is_nan() || years < 0.0 {
+            return None;
+        }
+
+        AgeGroup::ALL
+            .into_iter()
+            .rev()
+            .find(|group| years >= group.min_years())
+    }
+}
+
+impl std::fmt::Display for AgeGroup {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for AgeGroup {
+    type Err = ParseAgeGroupError;
+
+    /// Parses an age group from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AgeGroup::ALL
+            .into_iter()
+            .find(|group| group.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseAgeGroupError(s.to_string()))
+    }
+}
+
+/// The samples that a characteristic applies to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Applicability {
+    /// The names of the ontology nodes whose subtrees the characteristic
+    /// applies to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) nodes: Vec<String>,
+
+    /// The specimen types that the characteristic applies to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) specimen_types: Vec<SpecimenType>,
+
+    /// The age groups that the characteristic applies to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) age_groups: Vec<AgeGroup>,
+}
+
+impl Applicability {
+    /// Creates a new applicability block.
+    pub fn new(
+        nodes: Vec<String>,
+        specimen_types: Vec<SpecimenType>,
+        age_groups: Vec<AgeGroup>,
+    ) -> Self {
+        Self {
+            nodes,
+            specimen_types,
+            age_groups,
+        }
+    }
+
+    /// Gets the names of the ontology nodes whose subtrees the characteristic
+    /// applies to.
+    pub fn nodes(&self) -> &[String] {
+        &self.nodes
+    }
+
+    /// Gets the specimen types that the characteristic applies to.
+    pub fn specimen_types(&self) -> &[SpecimenType] {
+        &self.specimen_types
+    }
+
+    /// Gets the age groups that the characteristic applies to.
+    pub fn age_groups(&self) -> &[AgeGroup] {
+        &self.age_groups
+    }
+
+    /// Whether a sample classified under a node applies.
+    ///
+    /// The lineage is the name of the node followed by the names of its
+    /// ancestors (in any order).
+    pub fn applies_to_lineage<S: AsRef<str>>(&self, lineage: &[S]) -> bool {
+        self.nodes.is_empty()
+            || lineage
+                .iter()
+                .any(|name| self.nodes.iter().any(|node| node == name.as_ref()))
+    }
+
+    /// Whether a specimen type applies.
+    pub fn applies_to_specimen_type(&self, specimen_type: SpecimenType) -> bool {
+        self.specimen_types.is_empty() || self.specimen_types.contains(&specimen_type)
+    }
+
+    /// Whether an age group applies.
+    pub fn applies_to_age_group(&self, age_group: AgeGroup) -> bool {
+        self.age_groups.is_empty() || self.age_groups.contains(&age_group)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "Bone-Marrow".parse::<SpecimenType>(),
+            Ok(SpecimenType::BoneMarrow)
+        );
+        assert_eq!(
+            "adolescent-young-adult".parse::<AgeGroup>(),
+            Ok(AgeGroup::AdolescentYoungAdult)
+        );
+
+        for specimen_type in SpecimenType::ALL {
+            assert_eq!(specimen_type.to_string().parse(), Ok(specimen_type));
+        }
+
+        for group in AgeGroup::ALL {
+            assert_eq!(group.to_string().parse(), Ok(group));
+        }
+
+        let err = "saliva".parse::<SpecimenType>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown specimen type: `saliva`; expected `blood`, `bone-marrow`, \
+             `cerebrospinal-fluid`, or `tissue`"
+        );
+    }
+
+    #[test]
+    fn from_years() {
+        assert_eq!(AgeGroup::from_years(0.5), Some(AgeGroup::Infant));
+        assert_eq!(AgeGroup::from_years(1.0), Some(AgeGroup::Child));
+        assert_eq!(AgeGroup::from_years(14.9), Some(AgeGroup::Child));
+        assert_eq!(
+            AgeGroup::from_years(15.0),
+            Some(AgeGroup::AdolescentYoungAdult)
+        );
+        assert_eq!(AgeGroup::from_years(72.0), Some(AgeGroup::Adult));
+        assert_eq!(AgeGroup::from_years(-1.0), None);
+        assert_eq!(AgeGroup::from_years(f64::NAN), None);
+    }
+
+    #[test]
+    fn applies() {
+        let applicability: Applicability = serde_yaml::from_str(
+            "nodes: [Hematologic Malignancy]\nspecimen_types: [blood, bone-marrow]\n",
+        )
+        .unwrap();
+
+        assert!(applicability.applies_to_lineage(&["B-ALL", "Hematologic Malignancy", "Tumor"]));
+        assert!(!applicability.applies_to_lineage(&["Neuroblastoma", "Solid Tumor", "Tumor"]));
+        assert!(applicability.applies_to_specimen_type(SpecimenType::BoneMarrow));
+        assert!(!applicability.applies_to_specimen_type(SpecimenType::Tissue));
+
+        // NOTE: an axis without any entries is unconstrained.
+        assert!(applicability.applies_to_age_group(AgeGroup::Adult));
+
+        assert!(serde_yaml::from_str::<Applicability>("sites: [marrow]\n").is_err());
+    }
+}
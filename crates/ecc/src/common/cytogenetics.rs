@@ -0,0 +1,600 @@
+//! Cytogenetic abnormalities written in the [ISCN] nomenclature.
+//!
+//! A single abnormality is parsed into its event (e.g., a translocation), the
+//! chromosomes it involves, and the bands of each chromosome it involves (if
+//! written). The common forms are supported:
+//!
+//! * Structural rearrangements with or without bands (e.g.,
+//!   `t(9;22)(q34;q11.2)`, `inv(16)(p13.1q22)`, or `inv(16)`).
+//! * Arm shorthand (e.g., `del(5q)` or `i(17q)`).
+//! * Gains and losses of whole chromosomes (e.g., `+8`, `-7`, or `+mar`).
+//! * Named abnormalities (`iAMP21` and `dmin`).
+//!
+//! Full karyotypes (e.g., `46,XY,t(9;22)(q34;q11.2)[20]`) are not parsed, and
+//! whether a band exists on its chromosome is not checked.
+//!
+//! ```
+//! use ecc::common::cytogenetics::Chromosome;
+//! use ecc::common::cytogenetics::Event;
+//! use ecc::common::cytogenetics::Iscn;
+//!
+//! let iscn = "t(9;22)(q34;q11.2)".parse::<Iscn>().unwrap();
+//! assert_eq!(iscn.event(), Event::Translocation);
+//! assert_eq!(
+//!     iscn.chromosomes(),
+//!     [Chromosome::Autosome(9), Chromosome::Autosome(22)]
+//! );
+//! assert_eq!(
+//!     iscn.bands(&Chromosome::Autosome(22)).unwrap()[0].to_string(),
+//!     "q11.2"
+//! );
+//! assert!(iscn.involves(&Chromosome::Autosome(9)));
+//! ```
+//!
+//! [ISCN]: https://doi.org/10.1159/isbn.978-3-318-06706-4
+
+/// An error when parsing an ISCN expression.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The expression.
+    value: String,
+
+    /// Why the expression is invalid.
+    reason: &'static str,
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid ISCN expression: `{}`; {}",
+            self.value, self.reason
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A chromosome.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Chromosome {
+    /// An autosome (`1` through `22`).
+    Autosome(u8),
+
+    /// The X chromosome.
+    X,
+
+    /// The Y chromosome.
+    Y,
+}
+
+impl std::fmt::Display for Chromosome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Chromosome::Autosome(n) => write!(f, "{n}"),
+            Chromosome::X => write!(f, "X"),
+            Chromosome::Y => write!(f, "Y"),
+        }
+    }
+}
+
+impl std::str::FromStr for Chromosome {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "X" => Ok(Chromosome::X),
+            "Y" => Ok(Chromosome::Y),
+            // NOTE: leading zeros (e.g., `09`) are not valid.
+            s if !s.starts_with('0') => match s.parse::<u8>() {
+                Ok(n @ 1..=22) => Ok(Chromosome::Autosome(n)),
+                _ => Err(()),
+            },
+            _ => Err(()),
+        }
+    }
+}
+
+/// An arm of a chromosome.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Arm {
+    /// The short arm (`p`).
+    P,
+
+    /// The long arm (`q`).
+    Q,
+}
+
+impl std::fmt::Display for Arm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arm::P => write!(f, "p"),
+            Arm::Q => write!(f, "q"),
+        }
+    }
+}
+
+/// A band of a chromosome (e.g., `q34`, `p13.1`, `qter`, or `cen`).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Band {
+    /// The arm (or [`None`] for the centromere).
+    arm: Option<Arm>,
+
+    /// The region and band within the arm (e.g., `34`, `13.1`, or `ter`).
+    position: String,
+}
+
+impl Band {
+    /// Gets the arm of the band (or [`None`] for the centromere).
+    pub fn arm(&self) -> Option<Arm> {
+        self.arm
+    }
+
+    /// Gets the region and band within the arm (e.g., `34`, `13.1`, or
+    /// `ter`).
+    pub fn position(&self) -> &str {
+        &self.position
+    }
+}
+
+impl std::fmt::Display for Band {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.arm {
+            Some(arm) => write!(f, "{arm}{}", self.position),
+            None => write!(f, "{}", self.position),
+        }
+    }
+}
+
+/// Parses the bands written for a single chromosome (e.g., `p13.1q22`).
+fn bands(s: &str) -> Option<Vec<Band>> {
+    let mut bands = Vec::new();
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        if let Some(remainder) = rest.strip_prefix("cen") {
+            bands.push(Band {
+                arm: None,
+                position: String::from("cen"),
+            });
+            rest = remainder;
+            continue;
+        }
+
+        let arm = match rest.as_bytes()[0] {
+            b'p' => Arm::P,
+            b'q' => Arm::Q,
+            _ => return None,
+        };
+        rest = &rest[1..];
+
+        let position = if let Some(remainder) = rest.strip_prefix("ter") {
+            rest = remainder;
+            String::from("ter")
+        } else {
+            let end = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let position = &rest[..end];
+
+            let valid = match position.split_once('.') {
+                Some((region, band)) => {
+                    !region.is_empty() && !band.is_empty() && !band.contains('.')
+                }
+                None => !position.is_empty(),
+            };
+
+            if !valid {
+                return None;
+            }
+
+            rest = &rest[end..];
+            position.to_string()
+        };
+
+        bands.push(Band {
+            arm: Some(arm),
+            position,
+        });
+    }
+
+    (!bands.is_empty()).then_some(bands)
+}
+
+/// The kind of abnormality.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Event {
+    /// A translocation (`t`).
+    Translocation,
+
+    /// An inversion (`inv`).
+    Inversion,
+
+    /// A deletion (`del`).
+    Deletion,
+
+    /// A duplication (`dup`).
+    Duplication,
+
+    /// An insertion (`ins`).
+    Insertion,
+
+    /// An isochromosome (`i`).
+    Isochromosome,
+
+    /// An isodicentric chromosome (`idic`).
+    Isodicentric,
+
+    /// A dicentric chromosome (`dic`).
+    Dicentric,
+
+    /// A derivative chromosome (`der`).
+    Derivative,
+
+    /// A ring chromosome (`r`).
+    Ring,
+
+    /// Additional material of unknown origin (`add`).
+    Addition,
+
+    /// A homogeneously staining region (`hsr`).
+    HomogeneouslyStainingRegion,
+
+    /// The gain of a whole chromosome (e.g., `+8`).
+    Gain,
+
+    /// The loss of a whole chromosome (e.g., `-7`).
+    Loss,
+
+    /// The gain of a marker chromosome of unknown origin (`+mar`).
+    Marker,
+
+    /// An intrachromosomal amplification (e.g., `iAMP21`).
+    Amplification,
+
+    /// Double minutes (`dmin`).
+    DoubleMinutes,
+}
+
+impl Event {
+    /// The structural rearrangements that are written as `event(chromosomes)`
+    /// along with their abbreviations.
+    const STRUCTURAL: [(&'static str, Event); 12] = [
+        ("t", Event::Translocation),
+        ("inv", Event::Inversion),
+        ("del", Event::Deletion),
+        ("dup", Event::Duplication),
+        ("ins", Event::Insertion),
+        ("i", Event::Isochromosome),
+        ("idic", Event::Isodicentric),
+        ("dic", Event::Dicentric),
+        ("der", Event::Derivative),
+        ("r", Event::Ring),
+        ("add", Event::Addition),
+        ("hsr", Event::HomogeneouslyStainingRegion),
+    ];
+
+    /// Gets the range of the number of chromosomes that the event can
+    /// involve.
+    fn chromosomes(&self) -> std::ops::RangeInclusive<usize> {
+        match self {
+            Event::Translocation => 2..=4,
+            Event::Insertion | Event::Dicentric => 1..=2,
+            _ => 1..=1,
+        }
+    }
+}
+
+/// A chromosome involved in an abnormality.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+struct Involvement {
+    /// The chromosome.
+    chromosome: Chromosome,
+
+    /// The arm (when written in shorthand, such as `del(5q)`).
+    arm: Option<Arm>,
+
+    /// The bands (if written).
+    bands: Vec<Band>,
+}
+
+/// A cytogenetic abnormality written in ISCN (e.g., `t(9;22)(q34;q11.2)`).
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub struct Iscn {
+    /// The expression as written.
+    value: String,
+
+    /// The kind of abnormality.
+    event: Event,
+
+    /// The chromosomes involved.
+    involvements: Vec<Involvement>,
+}
+
+impl Iscn {
+    /// Gets the expression as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Gets the kind of abnormality.
+    pub fn event(&self) -> Event {
+        self.event
+    }
+
+    /// Gets the chromosomes involved (in the order they are written).
+    ///
+    /// This is empty for marker chromosomes and double minutes.
+    pub fn chromosomes(&self) -> Vec<Chromosome> {
+        self.involvements
+            .iter()
+            .map(|involvement| involvement.chromosome)
+            .collect()
+    }
+
+    /// Gets whether a chromosome is involved.
+    pub fn involves(&self, chromosome: &Chromosome) -> bool {
+        self.involvements
+            .iter()
+            .any(|involvement| &involvement.chromosome == chromosome)
+    }
+
+    /// Gets the arm of a chromosome that is involved (if one is written in
+    /// shorthand or all of the chromosome's bands are on the same arm).
+    pub fn arm(&self, chromosome: &Chromosome) -> Option<Arm> {
+        let involvement = self
+            .involvements
+            .iter()
+            .find(|involvement| &involvement.chromosome == chromosome)?;
+
+        if involvement.arm.is_some() {
+            return involvement.arm;
+        }
+
+        let first = involvement.bands.first()?.arm?;
+        involvement
+            .bands
+            .iter()
+            .all(|band| band.arm == Some(first))
+            .then_some(first)
+    }
+
+    /// Gets the bands of a chromosome that is involved.
+    ///
+    /// Returns [`None`] if the chromosome is not involved, and an empty slice
+    /// if no bands are written.
+    pub fn bands(&self, chromosome: &Chromosome) -> Option<&[Band]> {
+        self.involvements
+            .iter()
+            .find(|involvement| &involvement.chromosome == chromosome)
+            .map(|involvement| involvement.bands.as_slice())
+    }
+}
+
+impl std::fmt::Display for Iscn {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl std::str::FromStr for Iscn {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = |reason| ParseError {
+            value: s.to_string(),
+            reason,
+        };
+
+        let whole = |event, chromosome: &str| {
+            let chromosome = chromosome
+                .parse::<Chromosome>()
+                .map_err(|_| err("expected a chromosome (`1` through `22`, `X`, or `Y`)"))?;
+
+            Ok(Self {
+                value: s.to_string(),
+                event,
+                involvements: vec![Involvement {
+                    chromosome,
+                    arm: None,
+                    bands: Vec::new(),
+                }],
+            })
+        };
+
+        let none = |event| Self {
+            value: s.to_string(),
+            event,
+            involvements: Vec::new(),
+        };
+
+        match s {
+            "dmin" => return Ok(none(Event::DoubleMinutes)),
+            "+mar" => return Ok(none(Event::Marker)),
+            _ => {}
+        }
+
+        if let Some(chromosome) = s.strip_prefix("iAMP") {
+            return whole(Event::Amplification, chromosome);
+        }
+
+        if let Some(chromosome) = s.strip_prefix('+') {
+            return whole(Event::Gain, chromosome);
+        }
+
+        if let Some(chromosome) = s.strip_prefix('-') {
+            return whole(Event::Loss, chromosome);
+        }
+
+        let Some((abbreviation, rest)) = s.split_once('(') else {
+            return Err(err(
+                "expected an abnormality such as `t(9;22)(q34;q11.2)`, `+8`, or `iAMP21`"
+            ));
+        };
+
+        let Some(event) = Event::STRUCTURAL
+            .iter()
+            .find(|(name, _)| *name == abbreviation)
+            .map(|(_, event)| *event)
+        else {
+            return Err(err("unknown abnormality; expected one of `t`, `inv`, \
+                            `del`, `dup`, `ins`, `i`, `idic`, `dic`, `der`, `r`, \
+                            `add`, or `hsr`"));
+        };
+
+        let (chromosomes, rest) = rest
+            .split_once(')')
+            .ok_or_else(|| err("unclosed parenthesis"))?;
+
+        let mut involvements = chromosomes
+            .split(';')
+            .map(|chromosome| {
+                let (chromosome, arm) = match chromosome.strip_suffix('p') {
+                    Some(chromosome) => (chromosome, Some(Arm::P)),
+                    None => match chromosome.strip_suffix('q') {
+                        Some(chromosome) => (chromosome, Some(Arm::Q)),
+                        None => (chromosome, None),
+                    },
+                };
+
+                Ok(Involvement {
+                    chromosome: chromosome.parse().map_err(|_| {
+                        err(
+                            "expected chromosomes (`1` through `22`, `X`, or `Y`) separated by \
+                             semicolons",
+                        )
+                    })?,
+                    arm,
+                    bands: Vec::new(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if !event.chromosomes().contains(&involvements.len()) {
+            return Err(err("wrong number of chromosomes for the abnormality"));
+        }
+
+        if !rest.is_empty() {
+            let bands = rest
+                .strip_prefix('(')
+                .and_then(|rest| rest.strip_suffix(')'))
+                .ok_or_else(|| err("expected the bands to be within parentheses"))?
+                .split(';')
+                .collect::<Vec<_>>();
+
+            if bands.len() != involvements.len() {
+                return Err(err("expected one set of bands for each chromosome"));
+            }
+
+            if involvements
+                .iter()
+                .any(|involvement| involvement.arm.is_some())
+            {
+                return Err(err("bands cannot follow an arm written in shorthand"));
+            }
+
+            for (involvement, bands) in involvements.iter_mut().zip(bands) {
+                involvement.bands = self::bands(bands).ok_or_else(|| {
+                    err("expected bands such as `q34`, `p13.1`, `qter`, or `cen`")
+                })?;
+            }
+        }
+
+        Ok(Self {
+            value: s.to_string(),
+            event,
+            involvements,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structural() {
+        let iscn = "inv(16)(p13.1q22)".parse::<Iscn>().unwrap();
+        assert_eq!(iscn.event(), Event::Inversion);
+        assert_eq!(iscn.chromosomes(), [Chromosome::Autosome(16)]);
+        assert_eq!(iscn.arm(&Chromosome::Autosome(16)), None);
+
+        let bands = iscn.bands(&Chromosome::Autosome(16)).unwrap();
+        assert_eq!(bands[0].arm(), Some(Arm::P));
+        assert_eq!(bands[0].position(), "13.1");
+        assert_eq!(bands[1].to_string(), "q22");
+
+        let iscn = "del(5q)".parse::<Iscn>().unwrap();
+        assert_eq!(iscn.event(), Event::Deletion);
+        assert_eq!(iscn.arm(&Chromosome::Autosome(5)), Some(Arm::Q));
+        assert_eq!(iscn.bands(&Chromosome::Autosome(5)), Some(&[][..]));
+        assert_eq!(iscn.bands(&Chromosome::Autosome(7)), None);
+
+        let iscn = "t(X;11)(q13;p15)".parse::<Iscn>().unwrap();
+        assert_eq!(
+            iscn.chromosomes(),
+            [Chromosome::X, Chromosome::Autosome(11)]
+        );
+        assert_eq!(iscn.arm(&Chromosome::X), Some(Arm::Q));
+
+        let iscn = "i(17)(q10)".parse::<Iscn>().unwrap();
+        assert_eq!(iscn.event(), Event::Isochromosome);
+        assert_eq!(iscn.to_string(), "i(17)(q10)");
+
+        assert!("der(22)(qter)".parse::<Iscn>().is_ok());
+        assert!("r(1)(p36q42)".parse::<Iscn>().is_ok());
+        assert!("inv(16)".parse::<Iscn>().is_ok());
+        assert!("t(1;19)(cen;q13.3)".parse::<Iscn>().is_ok());
+    }
+
+    #[test]
+    fn numerical() {
+        let iscn = "+8".parse::<Iscn>().unwrap();
+        assert_eq!(iscn.event(), Event::Gain);
+        assert!(iscn.involves(&Chromosome::Autosome(8)));
+
+        let iscn = "-Y".parse::<Iscn>().unwrap();
+        assert_eq!(iscn.event(), Event::Loss);
+        assert_eq!(iscn.chromosomes(), [Chromosome::Y]);
+
+        let iscn = "iAMP21".parse::<Iscn>().unwrap();
+        assert_eq!(iscn.event(), Event::Amplification);
+        assert_eq!(iscn.chromosomes(), [Chromosome::Autosome(21)]);
+
+        assert!("+mar".parse::<Iscn>().unwrap().chromosomes().is_empty());
+        assert_eq!(
+            "dmin".parse::<Iscn>().unwrap().event(),
+            Event::DoubleMinutes
+        );
+    }
+
+    #[test]
+    fn invalid() {
+        for (expression, reason) in [
+            ("t9;22", "expected an abnormality"),
+            ("foo(9)", "unknown abnormality"),
+            ("t(9;22", "unclosed parenthesis"),
+            ("t(9;23)(q34;q11)", "expected chromosomes"),
+            ("t(9)(q34)", "wrong number of chromosomes"),
+            ("t(9;22)(q34)", "one set of bands"),
+            ("t(9;22)(q34;x11)", "expected bands"),
+            ("inv(16)(p13.1.2q22)", "expected bands"),
+            ("del(5q)(q13q33)", "shorthand"),
+            ("+23", "expected a chromosome"),
+            ("+08", "expected a chromosome"),
+        ] {
+            let err = expression.parse::<Iscn>().unwrap_err();
+            assert!(err.to_string().contains(reason), "`{expression}`: {err}");
+        }
+    }
+}
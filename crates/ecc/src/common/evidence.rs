@@ -0,0 +1,140 @@
+//! Levels of the evidence supporting characteristics.
+
+use alloc::string::String;
+use alloc::string::ToString as _;
+
+/// An error when parsing an evidence level.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "unknown evidence level: `{}`; expected `preliminary`, `single-study`, \
+             `multiple-studies`, or `consensus-guideline`",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// How strong the evidence supporting a characteristic is.
+///
+/// Evidence levels are ordered from the weakest to the strongest so that data
+/// consumers can weigh annotations accordingly (and so that a minimum level
+/// can be required of adopted characteristics).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub enum EvidenceLevel {
+    /// The characteristic is supported by preliminary findings only.
+    Preliminary,
+
+    /// The characteristic is supported by a single published study.
+    SingleStudy,
+
+    /// The characteristic is supported by multiple independent studies.
+    MultipleStudies,
+
+    /// The characteristic is part of a consensus guideline.
+    ConsensusGuideline,
+}
+
+impl EvidenceLevel {
+    /// All evidence levels from the weakest to the strongest.
+    pub const ALL: [EvidenceLevel; 4] = [
+        EvidenceLevel::Preliminary,
+        EvidenceLevel::SingleStudy,
+        EvidenceLevel::MultipleStudies,
+        EvidenceLevel::ConsensusGuideline,
+    ];
+
+    /// Gets the name of the evidence level as it appears within the
+    /// `evidence_level` field of a characteristic (e.g., `single-study`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EvidenceLevel::Preliminary => "preliminary",
+            EvidenceLevel::SingleStudy => "single-study",
+            EvidenceLevel::MultipleStudies => "multiple-studies",
+            EvidenceLevel::ConsensusGuideline => "consensus-guideline",
+        }
+    }
+
+    /// Gets the definition of the evidence level.
+    pub fn definition(&self) -> &'static str {
+        match self {
+            EvidenceLevel::Preliminary => {
+                "Supported by preliminary findings (e.g., abstracts, preprints, or case reports) \
+                 that have not been established in a peer-reviewed study."
+            }
+            EvidenceLevel::SingleStudy => {
+                "Supported by a single peer-reviewed study that has not been independently \
+                 replicated."
+            }
+            EvidenceLevel::MultipleStudies => {
+                "Supported by multiple independent peer-reviewed studies with consistent findings."
+            }
+            EvidenceLevel::ConsensusGuideline => {
+                "Part of a consensus guideline or classification issued by a recognized body \
+                 (e.g., the WHO classification of tumours)."
+            }
+        }
+    }
+}
+
+impl core::fmt::Display for EvidenceLevel {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl core::str::FromStr for EvidenceLevel {
+    type Err = ParseError;
+
+    /// Parses an evidence level from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        EvidenceLevel::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "single-study".parse::<EvidenceLevel>(),
+            Ok(EvidenceLevel::SingleStudy)
+        );
+        assert_eq!(
+            "Consensus-Guideline".parse::<EvidenceLevel>(),
+            Ok(EvidenceLevel::ConsensusGuideline)
+        );
+
+        for level in EvidenceLevel::ALL {
+            assert_eq!(level.to_string().parse(), Ok(level));
+        }
+
+        let err = "anecdotal".parse::<EvidenceLevel>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown evidence level: `anecdotal`; expected `preliminary`, `single-study`, \
+             `multiple-studies`, or `consensus-guideline`"
+        );
+    }
+
+    #[test]
+    fn order() {
+        assert!(EvidenceLevel::Preliminary < EvidenceLevel::SingleStudy);
+        assert!(EvidenceLevel::MultipleStudies < EvidenceLevel::ConsensusGuideline);
+        assert!(EvidenceLevel::ALL.is_sorted());
+    }
+}
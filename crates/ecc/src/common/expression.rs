@@ -0,0 +1,1386 @@
+//! Expressions that define a characteristic in terms of others.
+//!
+//! A composite characteristic can declare its `definition` as a boolean
+//! expression over the values of other characteristics (e.g., a "high-risk"
+//! characteristic defined by a numerical value and the absence of another
+//! characteristic):
+//!
+//! ```text
+//! ECC-MOLEC-000042 >= 0.01 AND NOT (ECC-MORPH-000001 = "Favorable" OR ECC-MORPH-000003)
+//! ```
+//!
+//! The grammar is as follows (keywords are case insensitive, and `AND` binds
+//! more tightly than `OR`):
+//!
+//! ```text
+//! expression = and { "OR" and }
+//! and        = not { "AND" not }
+//! not        = "NOT" not | "(" expression ")" | comparison
+//! comparison = identifier [ operator value ]
+//! operator   = "=" | "!=" | ">" | ">=" | "<" | "<="
+//! value      = number | "true" | "false" | quoted string
+//! ```
+//!
+//! A bare identifier holds when the characteristic is asserted for a sample
+//! (i.e., it is annotated with any value other than `false`). Only numbers can
+//! be compared with `>`, `>=`, `<`, and `<=`.
+//!
+//! Expressions are evaluated against the annotations of a sample (see
+//! [`Expression::evaluate`]) using three-valued logic: a comparison involving a
+//! characteristic that is not annotated (or whose value cannot be compared) is
+//! unknown, and the result is only unknown if the known comparisons do not
+//! decide it.
+//!
+//! ```
+//! use ecc::annotate::Annotation;
+//! use ecc::annotate::AnnotationSet;
+//! use ecc::common::Expression;
+//!
+//! let expression = "ECC-MOLEC-000042 >= 0.01 and not ECC-MORPH-000003"
+//!     .parse::<Expression>()
+//!     .unwrap();
+//! assert_eq!(
+//!     expression.to_string(),
+//!     "ECC-MOLEC-000042 >= 0.01 AND NOT ECC-MORPH-000003"
+//! );
+//!
+//! let annotations = AnnotationSet::new(vec![
+//!     Annotation::new("ECC-MOLEC-000042".parse().unwrap(), "0.5"),
+//!     Annotation::new("ECC-MORPH-000003".parse().unwrap(), "false"),
+//! ]);
+//! assert_eq!(expression.evaluate(&annotations), Some(true));
+//! ```
+//!
+//! Definitions can be checked statically against the characteristics that they
+//! refer to (see [`Expression::issues`]) and for contradictions that keep them
+//! from ever holding (see [`Expression::is_satisfiable`]).
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use crate::CharacteristicSet;
+use crate::Identifier;
+use crate::State;
+use crate::annotate::Annotation;
+use crate::annotate::AnnotationSet;
+use crate::common::value::Kind;
+
+/// The most clauses that an expression is expanded into when checking whether
+/// it is satisfiable.
+///
+/// Expressions that expand into more clauses are assumed to be satisfiable.
+const MAX_CLAUSES: usize = 1024;
+
+/// The maximum depth that negations and parentheses can be nested to.
+///
+/// Expressions are parsed, evaluated, and displayed recursively, so deeper
+/// expressions are rejected rather than risking a stack overflow.
+const MAX_DEPTH: usize = 64;
+
+/// An error when parsing an expression.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The (zero-based) byte offset at which the error occurred.
+    offset: usize,
+
+    /// Why the expression is invalid.
+    reason: String,
+}
+
+impl ParseError {
+    /// Gets the (zero-based) byte offset at which the error occurred.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid expression: {} (at offset {})",
+            self.reason, self.offset
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An operator comparing the value of a characteristic to a literal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// The value equals the literal (`=`).
+    Equal,
+
+    /// The value does not equal the literal (`!=`).
+    NotEqual,
+
+    /// The value is greater than the literal (`>`).
+    Greater,
+
+    /// The value is greater than or equal to the literal (`>=`).
+    GreaterOrEqual,
+
+    /// The value is less than the literal (`<`).
+    Less,
+
+    /// The value is less than or equal to the literal (`<=`).
+    LessOrEqual,
+}
+
+impl Operator {
+    /// Gets the symbol of the operator (e.g., `>=`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Equal => "=",
+            Operator::NotEqual => "!=",
+            Operator::Greater => ">",
+            Operator::GreaterOrEqual => ">=",
+            Operator::Less => "<",
+            Operator::LessOrEqual => "<=",
+        }
+    }
+
+    /// Returns whether the operator orders its operands (and can therefore
+    /// only compare numbers).
+    pub fn is_ordering(&self) -> bool {
+        !matches!(self, Operator::Equal | Operator::NotEqual)
+    }
+
+    /// Applies the operator to the result of comparing a value to a literal.
+    fn apply(&self, ordering: std::cmp::Ordering) -> bool {
+        match self {
+            Operator::Equal => ordering.is_eq(),
+            Operator::NotEqual => ordering.is_ne(),
+            Operator::Greater => ordering.is_gt(),
+            Operator::GreaterOrEqual => ordering.is_ge(),
+            Operator::Less => ordering.is_lt(),
+            Operator::LessOrEqual => ordering.is_le(),
+        }
+    }
+}
+
+impl std::fmt::Display for Operator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A literal that the value of a characteristic is compared to.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Literal {
+    /// `true` or `false`.
+    Boolean(bool),
+
+    /// A number.
+    Number(f64),
+
+    /// A (quoted) string, such as a categorical option.
+    Text(String),
+}
+
+impl Literal {
+    /// Compares an annotated value to the literal.
+    ///
+    /// Returns [`None`] if the value is not of the same type as the literal.
+    fn compare(&self, value: &str) -> Option<std::cmp::Ordering> {
+        match self {
+            Literal::Boolean(literal) => value.parse::<bool>().ok().map(|v| v.cmp(literal)),
+            Literal::Number(literal) => value
+                .parse::<f64>()
+                .ok()
+                .and_then(|v| v.partial_cmp(literal)),
+            Literal::Text(literal) => Some(value.cmp(literal.as_str())),
+        }
+    }
+}
+
+impl Literal {
+    /// Gets the name of the type of the literal (e.g., `number`).
+    fn type_name(&self) -> &'static str {
+        match self {
+            Literal::Boolean(_) => "boolean",
+            Literal::Number(_) => "number",
+            Literal::Text(_) => "string",
+        }
+    }
+
+    /// Gets the annotated value that equals the literal.
+    fn value(&self) -> String {
+        match self {
+            Literal::Text(value) => value.clone(),
+            literal => literal.to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for Literal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Literal::Boolean(value) => write!(f, "{value}"),
+            Literal::Number(value) => write!(f, "{value}"),
+            Literal::Text(value) => {
+                write!(f, "\"")?;
+
+                for c in value.chars() {
+                    if matches!(c, '"' | '\\') {
+                        write!(f, "\\")?;
+                    }
+
+                    write!(f, "{c}")?;
+                }
+
+                write!(f, "\"")
+            }
+        }
+    }
+}
+
+/// A problem with an expression found by checking it against the
+/// characteristics that it refers to (see [`Expression::issues`]).
+#[derive(Clone, Debug, PartialEq, thiserror::Error)]
+pub enum Issue {
+    /// The expression refers to a characteristic that is not within the set.
+    #[error("`{0}` is not a known characteristic")]
+    Unknown(Identifier),
+
+    /// The expression refers to a characteristic that is not adopted.
+    #[error("`{identifier}` is {state}, but only adopted characteristics can be referred to")]
+    NotAdopted {
+        /// The characteristic.
+        identifier: Identifier,
+
+        /// The state of the characteristic.
+        state: State,
+    },
+
+    /// A comparison can never hold given the kind of its characteristic.
+    #[error("`{comparison}` can never hold: {reason}")]
+    Incompatible {
+        /// The comparison.
+        comparison: String,
+
+        /// Why the comparison can never hold.
+        reason: String,
+    },
+}
+
+/// An expression over the values of characteristics.
+#[derive(Clone, Debug, PartialEq, serde_with::SerializeDisplay, serde_with::DeserializeFromStr)]
+pub enum Expression {
+    /// The characteristic is asserted.
+    Asserted(Identifier),
+
+    /// The value of the characteristic compares to a literal.
+    Comparison {
+        /// The characteristic.
+        identifier: Identifier,
+
+        /// The operator.
+        operator: Operator,
+
+        /// The literal.
+        literal: Literal,
+    },
+
+    /// The expression does not hold.
+    Not(Box<Expression>),
+
+    /// Every expression holds (at least two).
+    And(Vec<Expression>),
+
+    /// Any expression holds (at least two).
+    Or(Vec<Expression>),
+}
+
+impl Expression {
+    /// Creates a conjunction of expressions.
+    ///
+    /// Nested conjunctions are flattened (e.g., `(a AND b) AND c` becomes `a
+    /// AND b AND c`), and a single expression is returned as is.
+    pub fn and(expressions: impl IntoIterator<Item = Expression>) -> Self {
+        let mut flattened = Vec::new();
+
+        for expression in expressions {
+            match expression {
+                Expression::And(inner) => flattened.extend(inner),
+                expression => flattened.push(expression),
+            }
+        }
+
+        match <[Expression; 1]>::try_from(flattened) {
+            Ok([expression]) => expression,
+            Err(flattened) => Expression::And(flattened),
+        }
+    }
+
+    /// Creates a disjunction of expressions.
+    ///
+    /// Nested disjunctions are flattened (e.g., `(a OR b) OR c` becomes `a OR
+    /// b OR c`), and a single expression is returned as is.
+    pub fn or(expressions: impl IntoIterator<Item = Expression>) -> Self {
+        let mut flattened = Vec::new();
+
+        for expression in expressions {
+            match expression {
+                Expression::Or(inner) => flattened.extend(inner),
+                expression => flattened.push(expression),
+            }
+        }
+
+        match <[Expression; 1]>::try_from(flattened) {
+            Ok([expression]) => expression,
+            Err(flattened) => Expression::Or(flattened),
+        }
+    }
+
+    /// Gets the characteristics referred to by the expression.
+    pub fn identifiers(&self) -> BTreeSet<&Identifier> {
+        let mut identifiers = BTreeSet::new();
+        let mut stack = vec![self];
+
+        while let Some(expression) = stack.pop() {
+            match expression {
+                Expression::Asserted(identifier) | Expression::Comparison { identifier, .. } => {
+                    identifiers.insert(identifier);
+                }
+                Expression::Not(inner) => stack.push(inner),
+                Expression::And(inner) | Expression::Or(inner) => stack.extend(inner),
+            }
+        }
+
+        identifiers
+    }
+
+    /// Evaluates the expression against the annotations of a sample.
+    ///
+    /// Returns [`None`] if the result is unknown (i.e., it depends on a
+    /// characteristic that is not annotated or whose value cannot be
+    /// compared to the literal).
+    pub fn evaluate(&self, annotations: &AnnotationSet) -> Option<bool> {
+        match self {
+            Expression::Asserted(identifier) => {
+                annotations.get(identifier).map(|value| value != "false")
+            }
+            Expression::Comparison {
+                identifier,
+                operator,
+                literal,
+            } => annotations
+                .get(identifier)
+                .and_then(|value| literal.compare(value))
+                .map(|ordering| operator.apply(ordering)),
+            Expression::Not(inner) => inner.evaluate(annotations).map(|value| !value),
+            Expression::And(inner) => {
+                let mut result = Some(true);
+
+                for expression in inner {
+                    match expression.evaluate(annotations) {
+                        Some(false) => return Some(false),
+                        Some(true) => {}
+                        None => result = None,
+                    }
+                }
+
+                result
+            }
+            Expression::Or(inner) => {
+                let mut result = Some(false);
+
+                for expression in inner {
+                    match expression.evaluate(annotations) {
+                        Some(true) => return Some(true),
+                        Some(false) => {}
+                        None => result = None,
+                    }
+                }
+
+                result
+            }
+        }
+    }
+
+    /// Checks the expression against the characteristics that it refers to.
+    ///
+    /// The following problems are reported:
+    ///
+    /// * characteristics that are not within the set or are not adopted, and
+    /// * comparisons that can never hold given the kind of their characteristic
+    ///   (e.g., comparing a categorical characteristic to a number, ordering a
+    ///   binary characteristic, or testing for a categorical option that does
+    ///   not exist).
+    ///
+    /// Comparisons involving characteristics that are not within the set or
+    /// whose values are not described are not checked.
+    pub fn issues(&self, characteristics: &CharacteristicSet) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        for identifier in self.identifiers() {
+            match characteristics.get(identifier).map(|c| c.state()) {
+                None => issues.push(Issue::Unknown(identifier.clone())),
+                Some(State::Adopted) => {}
+                Some(state) => issues.push(Issue::NotAdopted {
+                    identifier: identifier.clone(),
+                    state,
+                }),
+            }
+        }
+
+        let mut stack = vec![self];
+
+        while let Some(expression) = stack.pop() {
+            match expression {
+                Expression::Asserted(_) => {}
+                Expression::Comparison {
+                    identifier,
+                    operator,
+                    literal,
+                } => {
+                    let Some(kind) = characteristics.get(identifier).and_then(|c| c.values())
+                    else {
+                        continue;
+                    };
+
+                    if let Some(reason) = incompatibility(identifier, kind, *operator, literal) {
+                        issues.push(Issue::Incompatible {
+                            comparison: expression.to_string(),
+                            reason,
+                        });
+                    }
+                }
+                Expression::Not(inner) => stack.push(inner),
+                Expression::And(inner) | Expression::Or(inner) => {
+                    stack.extend(inner.iter().rev());
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Returns whether the expression can hold for some sample.
+    ///
+    /// The expression is expanded into a disjunction of conjunctions, and it
+    /// is unsatisfiable if every conjunction requires contradictory values of
+    /// some characteristic (e.g., `ECC-MOLEC-000042 > 5 AND ECC-MOLEC-000042 <
+    /// 3`). The kinds of the characteristics are not considered, and
+    /// expressions that expand into too many conjunctions are assumed to be
+    /// satisfiable.
+    pub fn is_satisfiable(&self) -> bool {
+        match self.clauses(true) {
+            Some(clauses) => clauses.iter().any(|clause| satisfiable(clause)),
+            None => true,
+        }
+    }
+
+    /// Expands the expression (or its negation) into a disjunction of
+    /// conjunctions of comparisons.
+    ///
+    /// Each comparison is paired with whether it must hold (`true`) or must
+    /// not hold (`false`). Returns [`None`] if there are more than
+    /// [`MAX_CLAUSES`] conjunctions.
+    fn clauses(&self, holds: bool) -> Option<Vec<Vec<(&Expression, bool)>>> {
+        match self {
+            Expression::Asserted(_) | Expression::Comparison { .. } => {
+                Some(vec![vec![(self, holds)]])
+            }
+            Expression::Not(inner) => inner.clauses(!holds),
+            Expression::And(inner) | Expression::Or(inner) => {
+                // NOTE: by De Morgan's laws, the negation of a conjunction is a
+                // disjunction of negations (and vice versa).
+                if matches!(self, Expression::And(_)) == holds {
+                    let mut clauses = vec![Vec::new()];
+
+                    for expression in inner {
+                        let operand = expression.clauses(holds)?;
+
+                        if clauses.len() * operand.len() > MAX_CLAUSES {
+                            return None;
+                        }
+
+                        clauses = clauses
+                            .iter()
+                            .flat_map(|clause| {
+                                operand.iter().map(move |other| {
+                                    clause.iter().chain(other).copied().collect::<Vec<_>>()
+                                })
+                            })
+                            .collect();
+                    }
+
+                    Some(clauses)
+                } else {
+                    let mut clauses = Vec::new();
+
+                    for expression in inner {
+                        clauses.extend(expression.clauses(holds)?);
+
+                        if clauses.len() > MAX_CLAUSES {
+                            return None;
+                        }
+                    }
+
+                    Some(clauses)
+                }
+            }
+        }
+    }
+
+    /// Renders the expression across multiple lines.
+    ///
+    /// Each operand of a conjunction or disjunction is written on its own
+    /// line, and nested conjunctions and disjunctions are parenthesized and
+    /// indented.
+    pub fn pretty(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    /// Writes the expression across multiple lines at an indentation level.
+    fn write_pretty(&self, out: &mut String, level: usize) {
+        let indent = "  ".repeat(level);
+
+        match self {
+            Expression::And(inner) | Expression::Or(inner) => {
+                let keyword = if matches!(self, Expression::And(_)) {
+                    "AND "
+                } else {
+                    "OR "
+                };
+
+                for (i, expression) in inner.iter().enumerate() {
+                    out.push_str(&indent);
+
+                    if i > 0 {
+                        out.push_str(keyword);
+                    }
+
+                    expression.write_operand(out, level);
+                }
+            }
+            expression => {
+                out.push_str(&indent);
+                expression.write_operand(out, level);
+            }
+        }
+    }
+
+    /// Writes an operand (after any indentation and keyword) across multiple
+    /// lines.
+    fn write_operand(&self, out: &mut String, level: usize) {
+        match self {
+            Expression::And(_) | Expression::Or(_) => {
+                out.push_str("(\n");
+                self.write_pretty(out, level + 1);
+                out.push_str(&"  ".repeat(level));
+                out.push_str(")\n");
+            }
+            Expression::Not(inner) => {
+                out.push_str("NOT ");
+                inner.write_operand(out, level);
+            }
+            expression => {
+                out.push_str(&expression.to_string());
+                out.push('\n');
+            }
+        }
+    }
+
+    /// Gets the precedence of the expression (higher binds more tightly).
+    fn precedence(&self) -> u8 {
+        match self {
+            Expression::Or(_) => 0,
+            Expression::And(_) => 1,
+            Expression::Not(_) => 2,
+            Expression::Asserted(_) | Expression::Comparison { .. } => 3,
+        }
+    }
+
+    /// Writes an operand, parenthesizing it if it binds less tightly than its
+    /// parent.
+    fn fmt_operand(&self, f: &mut std::fmt::Formatter<'_>, precedence: u8) -> std::fmt::Result {
+        if self.precedence() <= precedence {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::Asserted(identifier) => write!(f, "{identifier}"),
+            Expression::Comparison {
+                identifier,
+                operator,
+                literal,
+            } => write!(f, "{identifier} {operator} {literal}"),
+            Expression::Not(inner) => {
+                write!(f, "NOT ")?;
+                inner.fmt_operand(f, 1)
+            }
+            Expression::And(inner) | Expression::Or(inner) => {
+                let keyword = if matches!(self, Expression::And(_)) {
+                    " AND "
+                } else {
+                    " OR "
+                };
+
+                for (i, expression) in inner.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "{keyword}")?;
+                    }
+
+                    expression.fmt_operand(f, self.precedence())?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::str::FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+            end: s.len(),
+            depth: 0,
+        };
+
+        let expression = parser.or()?;
+
+        match parser.peek() {
+            None => Ok(expression),
+            Some((offset, token)) => Err(ParseError {
+                offset: *offset,
+                reason: format!("unexpected {token}"),
+            }),
+        }
+    }
+}
+
+/// Describes why a comparison can never hold given the kind of its
+/// characteristic (if it can't).
+fn incompatibility(
+    identifier: &Identifier,
+    kind: &Kind,
+    operator: Operator,
+    literal: &Literal,
+) -> Option<String> {
+    let expected = match kind {
+        Kind::Binary { .. } => "boolean",
+        Kind::Categorical { .. } | Kind::Immunophenotype { .. } => "string",
+        Kind::Numerical { .. } | Kind::Fraction { .. } => "number",
+        // NOTE: the permissible values are not modeled, so any comparison
+        // could hold.
+        Kind::Other { .. } => return None,
+    };
+
+    if operator.is_ordering() && expected != "number" {
+        return Some(format!(
+            "`{identifier}` is {}, so its values cannot be ordered",
+            kind.name()
+        ));
+    }
+
+    if literal.type_name() != expected {
+        return Some(format!(
+            "`{identifier}` is {}, but `{literal}` is a {}",
+            kind.name(),
+            literal.type_name()
+        ));
+    }
+
+    if operator.is_ordering() {
+        return None;
+    }
+
+    Annotation::new(identifier.clone(), literal.value())
+        .conforms_to(kind)
+        .err()
+        .map(|expected| format!("`{literal}` is not {expected}"))
+}
+
+/// Returns whether a conjunction of comparisons (each paired with whether it
+/// must hold) can hold for some sample.
+///
+/// The characteristics are independent of one another, so the conjunction is
+/// satisfiable if some value of each characteristic satisfies its
+/// comparisons. Only finitely many values need to be tried: every literal, a
+/// number between each pair of adjacent numbers (and beyond either end), and
+/// a value that equals no literal at all.
+fn satisfiable(clause: &[(&Expression, bool)]) -> bool {
+    let mut comparisons = BTreeMap::<&Identifier, Vec<(&Expression, bool)>>::new();
+
+    for &(expression, holds) in clause {
+        if let Expression::Asserted(identifier) | Expression::Comparison { identifier, .. } =
+            expression
+        {
+            comparisons
+                .entry(identifier)
+                .or_default()
+                .push((expression, holds));
+        }
+    }
+
+    comparisons.into_iter().all(|(identifier, comparisons)| {
+        let mut numbers = Vec::new();
+        let mut candidates = vec![
+            String::from("true"),
+            String::from("false"),
+            String::from("\u{1}"),
+        ];
+
+        for (expression, _) in &comparisons {
+            match expression {
+                Expression::Comparison {
+                    literal: Literal::Number(number),
+                    ..
+                } => numbers.push(*number),
+                Expression::Comparison {
+                    literal: Literal::Text(text),
+                    ..
+                } => candidates.push(text.clone()),
+                _ => {}
+            }
+        }
+
+        numbers.sort_by(f64::total_cmp);
+        numbers.dedup();
+
+        let mut points = numbers.clone();
+        points.extend(numbers.windows(2).map(|pair| pair[0] / 2.0 + pair[1] / 2.0));
+
+        match (numbers.first(), numbers.last()) {
+            (Some(first), Some(last)) => {
+                points.push(first - first.abs().max(1.0));
+                points.push(last + last.abs().max(1.0));
+            }
+            _ => points.push(0.0),
+        }
+
+        // NOTE: a number is written in two ways so that a value remains when
+        // one spelling is excluded as a string (e.g., `X = 1 AND X != "1"`).
+        for point in points {
+            candidates.push(format!("{point}"));
+            candidates.push(format!("{point:e}"));
+        }
+
+        candidates.into_iter().any(|candidate| {
+            let annotations =
+                AnnotationSet::new(vec![Annotation::new(identifier.clone(), candidate)]);
+
+            comparisons
+                .iter()
+                .all(|(expression, holds)| expression.evaluate(&annotations) == Some(*holds))
+        })
+    })
+}
+
+/// A token within an expression.
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    /// An opening parenthesis.
+    Open,
+
+    /// A closing parenthesis.
+    Close,
+
+    /// An operator.
+    Operator(Operator),
+
+    /// A quoted string (without the quotes or escapes).
+    Text(String),
+
+    /// An unquoted word (a keyword, an identifier, a number, or a boolean).
+    Word(String),
+}
+
+impl Token {
+    /// Returns whether the token is a keyword (case insensitive).
+    fn is_keyword(&self, keyword: &str) -> bool {
+        matches!(self, Token::Word(word) if word.eq_ignore_ascii_case(keyword))
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Open => write!(f, "`(`"),
+            Token::Close => write!(f, "`)`"),
+            Token::Operator(operator) => write!(f, "`{operator}`"),
+            Token::Text(text) => write!(f, "{}", Literal::Text(text.clone())),
+            Token::Word(word) => write!(f, "`{word}`"),
+        }
+    }
+}
+
+/// Splits an expression into tokens (along with their byte offsets).
+fn tokenize(s: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut chars = s.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::Open,
+            ')' => Token::Close,
+            '=' => Token::Operator(Operator::Equal),
+            '≠' => Token::Operator(Operator::NotEqual),
+            '≥' => Token::Operator(Operator::GreaterOrEqual),
+            '≤' => Token::Operator(Operator::LessOrEqual),
+            '!' | '>' | '<' => {
+                let equal = chars.next_if(|(_, c)| *c == '=').is_some();
+
+                Token::Operator(match (c, equal) {
+                    ('!', true) => Operator::NotEqual,
+                    ('>', true) => Operator::GreaterOrEqual,
+                    ('>', false) => Operator::Greater,
+                    ('<', true) => Operator::LessOrEqual,
+                    ('<', false) => Operator::Less,
+                    _ => {
+                        return Err(ParseError {
+                            offset,
+                            reason: String::from("expected `!=`"),
+                        });
+                    }
+                })
+            }
+            '"' => {
+                let mut text = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some((_, '"')) => break,
+                        Some((_, '\\')) => match chars.next() {
+                            Some((_, c @ ('"' | '\\'))) => text.push(c),
+                            _ => {
+                                return Err(ParseError {
+                                    offset,
+                                    reason: String::from(
+                                        "only `\\\"` and `\\\\` can be escaped within a string",
+                                    ),
+                                });
+                            }
+                        },
+                        Some((_, c)) => text.push(c),
+                        None => {
+                            return Err(ParseError {
+                                offset,
+                                reason: String::from("unterminated string"),
+                            });
+                        }
+                    }
+                }
+
+                Token::Text(text)
+            }
+            c => {
+                let mut word = String::from(c);
+
+                while let Some((_, c)) = chars.next_if(|(_, c)| {
+                    !c.is_whitespace()
+                        && !matches!(c, '(' | ')' | '=' | '!' | '>' | '<' | '"' | '≠' | '≥' | '≤')
+                }) {
+                    word.push(c);
+                }
+
+                Token::Word(word)
+            }
+        };
+
+        tokens.push((offset, token));
+    }
+
+    Ok(tokens)
+}
+
+/// A recursive descent parser over the tokens of an expression.
+struct Parser {
+    /// The tokens (along with their byte offsets).
+    tokens: Vec<(usize, Token)>,
+
+    /// The index of the next token.
+    position: usize,
+
+    /// The length of the expression (the offset reported at its end).
+    end: usize,
+
+    /// How deeply negations and parentheses are nested at the next token.
+    depth: usize,
+}
+
+impl Parser {
+    /// Peeks at the next token.
+    fn peek(&self) -> Option<&(usize, Token)> {
+        self.tokens.get(self.position)
+    }
+
+    /// Consumes the next token if it is a keyword.
+    fn keyword(&mut self, keyword: &str) -> bool {
+        let matches = self
+            .peek()
+            .is_some_and(|(_, token)| token.is_keyword(keyword));
+
+        if matches {
+            self.position += 1;
+        }
+
+        matches
+    }
+
+    /// Consumes the next token.
+    fn next(&mut self, expected: &str) -> Result<(usize, Token), ParseError> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or_else(|| ParseError {
+                offset: self.end,
+                reason: format!("expected {expected}, found the end of the expression"),
+            })?;
+
+        self.position += 1;
+        Ok(token)
+    }
+
+    /// Parses a disjunction.
+    fn or(&mut self) -> Result<Expression, ParseError> {
+        let mut operands = vec![self.and()?];
+
+        while self.keyword("OR") {
+            operands.push(self.and()?);
+        }
+
+        Ok(Expression::or(operands))
+    }
+
+    /// Parses a conjunction.
+    fn and(&mut self) -> Result<Expression, ParseError> {
+        let mut operands = vec![self.not()?];
+
+        while self.keyword("AND") {
+            operands.push(self.not()?);
+        }
+
+        Ok(Expression::and(operands))
+    }
+
+    /// Parses a negation, a parenthesized expression, or a comparison.
+    fn not(&mut self) -> Result<Expression, ParseError> {
+        const EXPECTED: &str = "an identifier, `NOT`, or `(`";
+
+        let nested = self
+            .peek()
+            .filter(|(_, token)| token.is_keyword("NOT") || matches!(token, Token::Open))
+            .map(|(offset, _)| *offset);
+
+        if let Some(offset) = nested {
+            if self.depth == MAX_DEPTH {
+                return Err(ParseError {
+                    offset,
+                    reason: format!(
+                        "negations and parentheses cannot be nested more than {MAX_DEPTH} levels \
+                         deep"
+                    ),
+                });
+            }
+
+            self.depth += 1;
+            let expression = self.nested();
+            self.depth -= 1;
+
+            return expression;
+        }
+
+        match self.next(EXPECTED)? {
+            (offset, Token::Word(word)) => {
+                let identifier = word.parse::<Identifier>().map_err(|_| ParseError {
+                    offset,
+                    reason: format!("`{word}` is not a characteristic identifier"),
+                })?;
+
+                self.comparison(identifier)
+            }
+            (offset, token) => Err(ParseError {
+                offset,
+                reason: format!("expected {EXPECTED}, found {token}"),
+            }),
+        }
+    }
+
+    /// Parses a negation or a parenthesized expression (one level deeper).
+    fn nested(&mut self) -> Result<Expression, ParseError> {
+        if self.keyword("NOT") {
+            return Ok(Expression::Not(Box::new(self.not()?)));
+        }
+
+        // NOTE: the next token was already checked to be `NOT` or `(`.
+        self.next("`(`")?;
+        let expression = self.or()?;
+
+        match self.next("`)`")? {
+            (_, Token::Close) => Ok(expression),
+            (offset, token) => Err(ParseError {
+                offset,
+                reason: format!("expected `)`, found {token}"),
+            }),
+        }
+    }
+
+    /// Parses the (optional) comparison following an identifier.
+    fn comparison(&mut self, identifier: Identifier) -> Result<Expression, ParseError> {
+        let Some((offset, Token::Operator(operator))) = self.peek().cloned() else {
+            return Ok(Expression::Asserted(identifier));
+        };
+
+        self.position += 1;
+
+        let literal = match self.next("a value")? {
+            (_, Token::Text(text)) => Literal::Text(text),
+            (_, Token::Word(word)) if word == "true" || word == "false" => {
+                Literal::Boolean(word == "true")
+            }
+            (at, Token::Word(word)) => match word.parse::<f64>() {
+                Ok(number) if number.is_finite() => Literal::Number(number),
+                _ => {
+                    return Err(ParseError {
+                        offset: at,
+                        reason: format!(
+                            "invalid value `{word}`; expected a number, `true`, `false`, or a \
+                             quoted string"
+                        ),
+                    });
+                }
+            },
+            (at, token) => {
+                return Err(ParseError {
+                    offset: at,
+                    reason: format!("expected a value, found {token}"),
+                });
+            }
+        };
+
+        if operator.is_ordering() && !matches!(literal, Literal::Number(_)) {
+            return Err(ParseError {
+                offset,
+                reason: format!("`{operator}` can only compare numbers"),
+            });
+        }
+
+        Ok(Expression::Comparison {
+            identifier,
+            operator,
+            literal,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::annotate::Annotation;
+
+    fn identifier(s: &str) -> Identifier {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        let expression = "ECC-MOLEC-000042 >= 0.01 and not (ECC-MORPH-000001 = \"Favorable\" or \
+                          ECC-MORPH-000003)"
+            .parse::<Expression>()
+            .unwrap();
+
+        assert_eq!(
+            expression,
+            Expression::And(vec![
+                Expression::Comparison {
+                    identifier: identifier("ECC-MOLEC-000042"),
+                    operator: Operator::GreaterOrEqual,
+                    literal: Literal::Number(0.01),
+                },
+                Expression::Not(Box::new(Expression::Or(vec![
+                    Expression::Comparison {
+                        identifier: identifier("ECC-MORPH-000001"),
+                        operator: Operator::Equal,
+                        literal: Literal::Text(String::from("Favorable")),
+                    },
+                    Expression::Asserted(identifier("ECC-MORPH-000003")),
+                ]))),
+            ])
+        );
+        assert_eq!(
+            expression.identifiers().into_iter().collect::<Vec<_>>(),
+            [
+                &identifier("ECC-MOLEC-000042"),
+                &identifier("ECC-MORPH-000001"),
+                &identifier("ECC-MORPH-000003"),
+            ]
+        );
+
+        // NOTE: `AND` binds more tightly than `OR`, and nested conjunctions
+        // are flattened.
+        let expression = "ECC-MORPH-000001 OR ECC-MORPH-000002 AND (ECC-MORPH-000003 AND \
+                          ECC-MORPH-000004)"
+            .parse::<Expression>()
+            .unwrap();
+        let Expression::Or(inner) = &expression else {
+            panic!("expected a disjunction: {expression:?}");
+        };
+        assert_eq!(inner.len(), 2);
+        assert!(matches!(&inner[1], Expression::And(and) if and.len() == 3));
+    }
+
+    #[test]
+    fn invalid() {
+        for (expression, offset, reason) in [
+            (
+                "",
+                0,
+                "expected an identifier, `NOT`, or `(`, found the end",
+            ),
+            (
+                "ECC-MORPH-1",
+                0,
+                "`ECC-MORPH-1` is not a characteristic identifier",
+            ),
+            ("ECC-MORPH-000001 >", 18, "expected a value, found the end"),
+            (
+                "ECC-MORPH-000001 > \"a\"",
+                17,
+                "`>` can only compare numbers",
+            ),
+            ("ECC-MORPH-000001 = maybe", 19, "invalid value `maybe`"),
+            ("(ECC-MORPH-000001", 17, "expected `)`, found the end"),
+            (
+                "ECC-MORPH-000001 ECC-MORPH-000002",
+                17,
+                "unexpected `ECC-MORPH-000002`",
+            ),
+            ("ECC-MORPH-000001 = \"a", 19, "unterminated string"),
+            ("ECC-MORPH-000001 ! 1", 17, "expected `!=`"),
+        ] {
+            let err = expression.parse::<Expression>().unwrap_err();
+            assert_eq!(err.offset(), offset, "{expression}");
+            assert!(err.to_string().contains(reason), "{err}");
+        }
+
+        // NOTE: deeply nested expressions are rejected instead of overflowing
+        // the stack.
+        let expression = format!("{}ECC-MORPH-000001", "NOT ".repeat(200_000));
+        let err = expression.parse::<Expression>().unwrap_err();
+        assert_eq!(err.offset(), MAX_DEPTH * 4);
+        assert!(err.to_string().contains("more than 64 levels deep"));
+
+        let expression = format!("{}ECC-MORPH-000001{}", "(".repeat(100), ")".repeat(100));
+        let err = expression.parse::<Expression>().unwrap_err();
+        assert_eq!(err.offset(), MAX_DEPTH);
+
+        let expression = format!("{}ECC-MORPH-000001", "NOT ".repeat(MAX_DEPTH));
+        assert!(expression.parse::<Expression>().is_ok());
+    }
+
+    #[test]
+    fn display() {
+        for (expression, expected) in [
+            (
+                "ECC-MORPH-000001 and (ECC-MORPH-000002 or ECC-MORPH-000003)",
+                "ECC-MORPH-000001 AND (ECC-MORPH-000002 OR ECC-MORPH-000003)",
+            ),
+            (
+                "(ECC-MORPH-000001 and ECC-MORPH-000002) or ECC-MORPH-000003",
+                "ECC-MORPH-000001 AND ECC-MORPH-000002 OR ECC-MORPH-000003",
+            ),
+            (
+                "not not ECC-MORPH-000001 ≠ \"a \\\"b\\\"\"",
+                "NOT NOT ECC-MORPH-000001 != \"a \\\"b\\\"\"",
+            ),
+            ("ECC-MOLEC-000042≥1e-2", "ECC-MOLEC-000042 >= 0.01"),
+        ] {
+            let parsed = expression.parse::<Expression>().unwrap();
+            assert_eq!(parsed.to_string(), expected);
+            assert_eq!(parsed.to_string().parse::<Expression>().unwrap(), parsed);
+        }
+
+        let expression = "ECC-MOLEC-000042 >= 0.01 AND NOT (ECC-MORPH-000001 = \"Favorable\" OR \
+                          ECC-MORPH-000003)"
+            .parse::<Expression>()
+            .unwrap();
+        assert_eq!(
+            expression.pretty(),
+            "ECC-MOLEC-000042 >= 0.01\nAND NOT (\n  ECC-MORPH-000001 = \"Favorable\"\n  OR \
+             ECC-MORPH-000003\n)\n"
+        );
+    }
+
+    #[test]
+    fn evaluate() {
+        let expression = "ECC-MOLEC-000042 >= 0.01 AND NOT (ECC-MORPH-000001 = \"Favorable\" OR \
+                          ECC-MORPH-000003)"
+            .parse::<Expression>()
+            .unwrap();
+
+        let annotations = |values: &[(&str, &str)]| {
+            values
+                .iter()
+                .map(|(id, value)| Annotation::new(identifier(id), *value))
+                .collect::<AnnotationSet>()
+        };
+
+        assert_eq!(
+            expression.evaluate(&annotations(&[
+                ("ECC-MOLEC-000042", "0.5"),
+                ("ECC-MORPH-000001", "Adverse"),
+                ("ECC-MORPH-000003", "false"),
+            ])),
+            Some(true)
+        );
+        assert_eq!(
+            expression.evaluate(&annotations(&[
+                ("ECC-MOLEC-000042", "0.5"),
+                ("ECC-MORPH-000001", "Adverse"),
+                ("ECC-MORPH-000003", "true"),
+            ])),
+            Some(false)
+        );
+
+        // NOTE: a known `false` operand decides a conjunction even when the
+        // others are unknown.
+        assert_eq!(
+            expression.evaluate(&annotations(&[("ECC-MOLEC-000042", "0.001")])),
+            Some(false)
+        );
+        assert_eq!(
+            expression.evaluate(&annotations(&[
+                ("ECC-MOLEC-000042", "0.5"),
+                ("ECC-MORPH-000001", "Adverse"),
+            ])),
+            None
+        );
+        assert_eq!(
+            expression.evaluate(&annotations(&[("ECC-MOLEC-000042", "high")])),
+            None
+        );
+    }
+
+    #[test]
+    fn issues() {
+        let characteristics: Vec<crate::Characteristic> = serde_yaml::from_str(
+            r#"- state: adopted
+  identifier: ECC-MORPH-000001
+  name: Foo Bar
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: categorical
+    options: [Favorable, Adverse]
+  adoption_date: 2024-01-01T00:00:00Z
+- state: adopted
+  identifier: ECC-MOLEC-000042
+  name: Baz Quux
+  rfc: https://github.com/stjudecloud/ecc/issues/2
+  description: A description.
+  values:
+    kind: numerical
+    type: unsigned
+    units: Days
+  adoption_date: 2024-01-01T00:00:00Z
+- state: proposed
+  identifier: ECC-MORPH-000003
+  name: Hello World
+  rfc: https://github.com/stjudecloud/ecc/issues/3
+  description: A description.
+  values:
+    kind: binary
+    description:
+      "true":
+        summary: The thing is present.
+        details: The thing is present.
+      "false":
+        summary: The thing is absent.
+        details: The thing is absent.
+"#,
+        )
+        .unwrap();
+        let characteristics = characteristics.into_iter().collect::<CharacteristicSet>();
+
+        let expression = "ECC-MOLEC-000042 >= 1 AND ECC-MORPH-000001 = \"Favorable\""
+            .parse::<Expression>()
+            .unwrap();
+        assert!(expression.issues(&characteristics).is_empty());
+
+        let expression = "ECC-MORPH-000001 = \"Unknown\" OR ECC-MORPH-000001 > 1 OR \
+                          ECC-MOLEC-000042 = 1.5 OR ECC-MORPH-000003 = 1 OR ECC-MORPH-000009"
+            .parse::<Expression>()
+            .unwrap();
+        assert_eq!(
+            expression
+                .issues(&characteristics)
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            [
+                "`ECC-MORPH-000003` is proposed, but only adopted characteristics can be referred \
+                 to",
+                "`ECC-MORPH-000009` is not a known characteristic",
+                "`ECC-MORPH-000001 = \"Unknown\"` can never hold: `\"Unknown\"` is not one of \
+                 `Adverse`, `Favorable`",
+                "`ECC-MORPH-000001 > 1` can never hold: `ECC-MORPH-000001` is categorical, so its \
+                 values cannot be ordered",
+                "`ECC-MOLEC-000042 = 1.5` can never hold: `1.5` is not an unsigned integer",
+                "`ECC-MORPH-000003 = 1` can never hold: `ECC-MORPH-000003` is binary, but `1` is \
+                 a number",
+            ]
+        );
+    }
+
+    #[test]
+    fn satisfiable() {
+        for (expression, expected) in [
+            ("ECC-MOLEC-000042 > 1 AND ECC-MOLEC-000042 < 2", true),
+            ("ECC-MOLEC-000042 > 5 AND ECC-MOLEC-000042 < 3", false),
+            ("ECC-MOLEC-000042 >= 3 AND ECC-MOLEC-000042 <= 3", true),
+            (
+                "ECC-MOLEC-000042 >= 3 AND ECC-MOLEC-000042 <= 3 AND ECC-MOLEC-000042 != 3",
+                false,
+            ),
+            ("ECC-MORPH-000001 AND NOT ECC-MORPH-000001", false),
+            ("ECC-MORPH-000001 OR NOT ECC-MORPH-000001", true),
+            (
+                "ECC-MORPH-000001 = \"Favorable\" AND ECC-MORPH-000001 = \"Adverse\"",
+                false,
+            ),
+            ("ECC-MORPH-000003 = true AND NOT ECC-MORPH-000003", false),
+            ("ECC-MORPH-000003 = false AND NOT ECC-MORPH-000003", true),
+            ("ECC-MOLEC-000042 = 1 AND ECC-MOLEC-000042 != \"1\"", true),
+            // NOTE: a number can't be compared to a string that isn't one.
+            (
+                "ECC-MOLEC-000042 > 1 AND ECC-MOLEC-000042 = \"high\"",
+                false,
+            ),
+            (
+                "NOT (ECC-MOLEC-000042 <= 5 OR ECC-MORPH-000001) AND (ECC-MOLEC-000042 < 3 OR \
+                 ECC-MORPH-000001)",
+                false,
+            ),
+            (
+                "NOT (ECC-MOLEC-000042 <= 5 OR ECC-MORPH-000001) AND (ECC-MOLEC-000042 < 3 OR \
+                 ECC-MORPH-000003)",
+                true,
+            ),
+        ] {
+            assert_eq!(
+                expression.parse::<Expression>().unwrap().is_satisfiable(),
+                expected,
+                "{expression}"
+            );
+        }
+    }
+}
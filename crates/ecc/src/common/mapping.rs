@@ -0,0 +1,279 @@
+//! Mappings from characteristics to terms within external terminologies.
+//!
+//! A mapping cross-references a characteristic with a term in another
+//! terminology (e.g., NCIt, ICD-O, or SNOMED CT) using a [SKOS] mapping
+//! predicate. Terms are written as compact URIs (e.g., `NCIT:C3171`).
+//!
+//! [SKOS]: https://www.w3.org/TR/skos-reference/#mapping
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// An error when parsing a mapping.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// An unknown mapping predicate.
+    Predicate(String),
+
+    /// An invalid compact URI.
+    Curie(String),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::Predicate(predicate) => write!(
+                f,
+                "unknown mapping predicate: `{predicate}`; expected one of {}",
+                Predicate::ALL
+                    .iter()
+                    .map(|predicate| format!("`{}`", predicate.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ParseError::Curie(curie) => write!(
+                f,
+                "invalid compact URI: `{curie}`; expected a prefix and a local identifier \
+                 separated by a colon (e.g., `NCIT:C3171`)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// How a characteristic relates to an external term.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub enum Predicate {
+    /// The terms can be used interchangeably.
+    ExactMatch,
+
+    /// The terms are similar enough to be used interchangeably in some
+    /// applications.
+    CloseMatch,
+
+    /// The external term is broader than the characteristic.
+    BroadMatch,
+
+    /// The external term is narrower than the characteristic.
+    NarrowMatch,
+
+    /// The terms are associated without being matches.
+    RelatedMatch,
+}
+
+impl Predicate {
+    /// All predicates.
+    pub const ALL: [Predicate; 5] = [
+        Predicate::ExactMatch,
+        Predicate::CloseMatch,
+        Predicate::BroadMatch,
+        Predicate::NarrowMatch,
+        Predicate::RelatedMatch,
+    ];
+
+    /// Gets the name of the predicate as it appears within a characteristic
+    /// (e.g., `exact-match`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Predicate::ExactMatch => "exact-match",
+            Predicate::CloseMatch => "close-match",
+            Predicate::BroadMatch => "broad-match",
+            Predicate::NarrowMatch => "narrow-match",
+            Predicate::RelatedMatch => "related-match",
+        }
+    }
+
+    /// Gets the SKOS property for the predicate as a compact URI (e.g.,
+    /// `skos:exactMatch`).
+    pub fn curie(&self) -> &'static str {
+        match self {
+            Predicate::ExactMatch => "skos:exactMatch",
+            Predicate::CloseMatch => "skos:closeMatch",
+            Predicate::BroadMatch => "skos:broadMatch",
+            Predicate::NarrowMatch => "skos:narrowMatch",
+            Predicate::RelatedMatch => "skos:relatedMatch",
+        }
+    }
+}
+
+impl std::fmt::Display for Predicate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Predicate {
+    type Err = ParseError;
+
+    /// Parses a predicate from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Predicate::ALL
+            .into_iter()
+            .find(|predicate| predicate.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError::Predicate(s.to_string()))
+    }
+}
+
+/// A compact URI (e.g., `NCIT:C3171`).
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub struct Curie {
+    /// The prefix (e.g., `NCIT`).
+    prefix: String,
+
+    /// The identifier within the prefix (e.g., `C3171`).
+    local: String,
+}
+
+impl Curie {
+    /// Gets the prefix (e.g., `NCIT`).
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Gets the identifier within the prefix (e.g., `C3171`).
+    pub fn local(&self) -> &str {
+        &self.local
+    }
+}
+
+impl std::fmt::Display for Curie {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.prefix, self.local)
+    }
+}
+
+impl std::str::FromStr for Curie {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let err = || ParseError::Curie(s.to_string());
+        let (prefix, local) = s.split_once(':').ok_or_else(err)?;
+
+        let valid_prefix = prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+            && prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        let valid_local = !local.is_empty() && !local.contains(char::is_whitespace);
+
+        if !valid_prefix || !valid_local {
+            return Err(err());
+        }
+
+        Ok(Self {
+            prefix: prefix.to_string(),
+            local: local.to_string(),
+        })
+    }
+}
+
+/// A mapping from a characteristic to a term within an external terminology.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Mapping {
+    /// How the characteristic relates to the term.
+    pub(crate) predicate: Predicate,
+
+    /// The term.
+    pub(crate) term: Curie,
+
+    /// The label of the term within its terminology (if recorded).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) label: Option<String>,
+}
+
+impl Mapping {
+    /// Creates a new mapping.
+    pub fn new(predicate: Predicate, term: Curie, label: Option<String>) -> Self {
+        Self {
+            predicate,
+            term,
+            label,
+        }
+    }
+
+    /// Gets how the characteristic relates to the term.
+    pub fn predicate(&self) -> Predicate {
+        self.predicate
+    }
+
+    /// Gets the term.
+    pub fn term(&self) -> &Curie {
+        &self.term
+    }
+
+    /// Gets the label of the term (if recorded).
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            "Exact-Match".parse::<Predicate>(),
+            Ok(Predicate::ExactMatch)
+        );
+
+        for predicate in Predicate::ALL {
+            assert_eq!(predicate.to_string().parse::<Predicate>(), Ok(predicate));
+        }
+
+        let curie = "NCIT:C3171".parse::<Curie>().unwrap();
+        assert_eq!(curie.prefix(), "NCIT");
+        assert_eq!(curie.local(), "C3171");
+
+        let curie = "ICDO:9861/3".parse::<Curie>().unwrap();
+        assert_eq!(curie.to_string(), "ICDO:9861/3");
+
+        for curie in ["C3171", "NCIT:", ":C3171", "1CD:C3171", "NCIT:C 3171"] {
+            assert_eq!(
+                curie.parse::<Curie>(),
+                Err(ParseError::Curie(curie.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        let mapping = serde_yaml::from_str::<Mapping>(
+            "predicate: exact-match\nterm: NCIT:C3171\nlabel: Acute Myeloid Leukemia",
+        )
+        .unwrap();
+        assert_eq!(mapping.predicate().curie(), "skos:exactMatch");
+        assert_eq!(mapping.term().to_string(), "NCIT:C3171");
+        assert_eq!(mapping.label(), Some("Acute Myeloid Leukemia"));
+
+        let err =
+            serde_yaml::from_str::<Mapping>("predicate: same-as\nterm: NCIT:C3171").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("unknown mapping predicate: `same-as`")
+        );
+    }
+}
@@ -0,0 +1,291 @@
+//! The genes and variants that molecular characteristics describe.
+//!
+//! Gene symbols are checked against the syntax of [HGNC] approved symbols when
+//! they are parsed. Whether a symbol is actually approved requires the HGNC
+//! symbol list (see [`Symbols`]), which is too large to bundle, so that check
+//! is left to tools that have it (e.g., the `unrecognized-gene-symbol` lint).
+//!
+//! [HGNC]: https://www.genenames.org
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use nonempty::NonEmpty;
+use serde::Deserialize;
+use serde::Serialize;
+
+pub mod hgvs;
+
+/// An error when parsing a gene symbol.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid gene symbol: `{}`; expected an HGNC symbol made up of uppercase letters, \
+             digits, and hyphens that starts with a letter (e.g., `PAX5` or `H3-3A`)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// An HGNC gene symbol (e.g., `PAX5`).
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub struct Symbol(String);
+
+impl Symbol {
+    /// Gets the symbol as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for Symbol {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // NOTE: approved symbols are uppercase apart from the `orf` within
+        // the symbols of open reading frames (e.g., `C1orf112`).
+        let stripped = s.replace("orf", "");
+
+        let valid = s.starts_with(|c: char| c.is_ascii_uppercase())
+            && stripped
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '-')
+            && !s.ends_with('-');
+
+        if !valid {
+            return Err(ParseError(s.to_string()));
+        }
+
+        Ok(Self(s.to_string()))
+    }
+}
+
+/// The genes and variants that a molecular characteristic describes.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Molecular {
+    /// The genes.
+    pub(crate) genes: NonEmpty<Symbol>,
+
+    /// The variants (if the characteristic describes specific variants).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) variants: Vec<hgvs::Expression>,
+}
+
+impl Molecular {
+    /// Creates a new molecular block.
+    pub fn new(genes: NonEmpty<Symbol>, variants: Vec<hgvs::Expression>) -> Self {
+        Self { genes, variants }
+    }
+
+    /// Gets the genes.
+    pub fn genes(&self) -> &NonEmpty<Symbol> {
+        &self.genes
+    }
+
+    /// Gets the variants.
+    pub fn variants(&self) -> &[hgvs::Expression] {
+        &self.variants
+    }
+}
+
+/// The status of a gene symbol within the HGNC symbol list.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status<'a> {
+    /// The symbol is approved.
+    Approved,
+
+    /// The symbol was previously approved for a gene that now has the given
+    /// symbol.
+    Previous(&'a str),
+
+    /// The symbol is an alias of the gene with the given symbol.
+    Alias(&'a str),
+
+    /// The symbol is not within the list.
+    Unrecognized,
+}
+
+/// The list of HGNC gene symbols.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Symbols {
+    /// The approved symbols.
+    approved: BTreeSet<String>,
+
+    /// The previous symbols mapped to their approved symbols.
+    previous: BTreeMap<String, String>,
+
+    /// The aliases mapped to their approved symbols.
+    aliases: BTreeMap<String, String>,
+}
+
+impl Symbols {
+    /// Parses a symbol list.
+    ///
+    /// The list is either the HGNC complete set (a tab-delimited file with a
+    /// header containing at least a `symbol` column, and optionally the
+    /// `prev_symbol` and `alias_symbol` columns), or a plain list of approved
+    /// symbols with one per line. Blank lines and lines starting with `#` are
+    /// skipped.
+    pub fn parse(contents: &str) -> Self {
+        let mut symbols = Self::default();
+        let mut lines = contents
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .peekable();
+
+        let header = lines
+            .peek()
+            .filter(|line| line.split('\t').any(|column| column == "symbol"))
+            .map(|line| line.split('\t').map(String::from).collect::<Vec<_>>());
+
+        let Some(header) = header else {
+            symbols
+                .approved
+                .extend(lines.map(|line| line.trim().to_string()));
+            return symbols;
+        };
+
+        lines.next();
+
+        let column = |name: &str| header.iter().position(|column| column == name);
+        let (symbol, previous, alias) = (
+            column("symbol"),
+            column("prev_symbol"),
+            column("alias_symbol"),
+        );
+
+        for line in lines {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let Some(approved) = symbol.and_then(|i| fields.get(i)).map(|s| s.trim()) else {
+                continue;
+            };
+
+            if approved.is_empty() {
+                continue;
+            }
+
+            symbols.approved.insert(approved.to_string());
+
+            for (index, map) in [
+                (previous, &mut symbols.previous),
+                (alias, &mut symbols.aliases),
+            ] {
+                // NOTE: the HGNC complete set separates multiple values with
+                // `|` and quotes the whole field.
+                let values = index
+                    .and_then(|i| fields.get(i))
+                    .map(|field| field.trim_matches('"'))
+                    .unwrap_or_default();
+
+                for value in values.split('|').map(str::trim).filter(|v| !v.is_empty()) {
+                    map.entry(value.to_string())
+                        .or_insert_with(|| approved.to_string());
+                }
+            }
+        }
+
+        symbols
+    }
+
+    /// Gets the number of approved symbols.
+    pub fn len(&self) -> usize {
+        self.approved.len()
+    }
+
+    /// Gets whether the list has no approved symbols.
+    pub fn is_empty(&self) -> bool {
+        self.approved.is_empty()
+    }
+
+    /// Looks up the status of a symbol.
+    pub fn status(&self, symbol: &Symbol) -> Status<'_> {
+        let symbol = symbol.as_str();
+
+        if self.approved.contains(symbol) {
+            Status::Approved
+        } else if let Some(approved) = self.previous.get(symbol) {
+            Status::Previous(approved)
+        } else if let Some(approved) = self.aliases.get(symbol) {
+            Status::Alias(approved)
+        } else {
+            Status::Unrecognized
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn symbol() {
+        for symbol in ["PAX5", "H3-3A", "C1orf112", "KMT2A"] {
+            assert_eq!(symbol.parse::<Symbol>().unwrap().as_str(), symbol);
+        }
+
+        for symbol in ["pax5", "H3 K27M", "1ABC", "H3-", "", "BRAF!"] {
+            assert_eq!(
+                symbol.parse::<Symbol>(),
+                Err(ParseError(symbol.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn symbols() {
+        let symbols = Symbols::parse(
+            "hgnc_id\tsymbol\tname\talias_symbol\tprev_symbol\nHGNC:4764\tH3-3A\tH3.3 histone \
+             A\t\"H3.3A\"\t\"H3F3A|H3F3\"\nHGNC:8619\tPAX5\tpaired box 5\tBSAP\t\n",
+        );
+        assert_eq!(symbols.len(), 2);
+
+        let status = |symbol: &str| symbols.status(&symbol.parse().unwrap());
+        assert_eq!(status("PAX5"), Status::Approved);
+        assert_eq!(status("H3F3A"), Status::Previous("H3-3A"));
+        assert_eq!(status("BSAP"), Status::Alias("PAX5"));
+        assert_eq!(status("H3"), Status::Unrecognized);
+
+        let symbols = Symbols::parse("# Approved symbols\nPAX5\n\nH3-3A\n");
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols.status(&"H3-3A".parse().unwrap()), Status::Approved);
+    }
+
+    #[test]
+    fn deserialize() {
+        let molecular = serde_yaml::from_str::<Molecular>(
+            "genes: [H3-3A, H3C2]\nvariants: [p.K27M, NM_002107.7:c.83A>T]",
+        )
+        .unwrap();
+        assert_eq!(molecular.genes().len(), 2);
+        assert_eq!(molecular.variants()[0].as_str(), "p.K27M");
+
+        let err = serde_yaml::from_str::<Molecular>("genes: [PAX5]\nvariants: [P80R]").unwrap_err();
+        assert!(err.to_string().contains("invalid HGVS expression: `P80R`"));
+
+        assert!(serde_yaml::from_str::<Molecular>("genes: []").is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! Variant descriptions written in the [HGVS] nomenclature.
+//!
+//! Only the syntax of an expression is checked: whether the positions exist
+//! within the reference sequence (or the reference sequence exists at all) is
+//! not. The reference sequence can be omitted (e.g., `p.K27M`), in which case
+//! the variant is relative to the canonical transcript of the gene that the
+//! characteristic describes.
+//!
+//! [HGVS]: https://hgvs-nomenclature.org
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// A position within a nucleotide sequence (e.g., `76`, `-14`, `*22`, or
+/// `88+1`).
+const POSITION: &str = r"(?:[-*]?[0-9]+(?:[-+][0-9]+)?|\?)";
+
+/// The one-letter amino acid codes.
+const AMINO_ACID: &str = "[ACDEFGHIKLMNPQRSTVWYUOX]";
+
+/// The three-letter amino acid codes.
+const AMINO_ACID_3: &str =
+    "(?:Ala|Arg|Asn|Asp|Cys|Gln|Glu|Gly|His|Ile|Leu|Lys|Met|Phe|Pro|Ser|Thr|Trp|Tyr|Val|Sec|Pyl|Xaa)";
+
+/// The structure of an expression.
+static EXPRESSION: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?:(?P<reference>(?:[A-Z]{1,6}_?[0-9]+(?:\.[0-9]+)?|LRG_[0-9]+(?:[tp][0-9]+)?)(?:\([A-Za-z0-9-]+\))?):)?(?P<coordinate>[a-z])\.(?P<description>.*)$",
+    )
+    .unwrap()
+});
+
+/// The description of a variant within a DNA sequence.
+static DNA: LazyLock<Regex> = LazyLock::new(|| nucleotide("[ACGTN]"));
+
+/// The description of a variant within an RNA sequence.
+static RNA: LazyLock<Regex> = LazyLock::new(|| nucleotide("[acgun]"));
+
+/// The description of a variant within a protein using one-letter amino acid
+/// codes.
+static PROTEIN: LazyLock<Regex> = LazyLock::new(|| protein(AMINO_ACID, r"\*"));
+
+/// The description of a variant within a protein using three-letter amino
+/// acid codes.
+static PROTEIN_3: LazyLock<Regex> = LazyLock::new(|| protein(AMINO_ACID_3, "Ter"));
+
+/// Builds a pattern that matches any of the alternatives in full.
+fn any_of(alternatives: &[String]) -> Regex {
+    Regex::new(&format!("^(?:{})$", alternatives.join("|"))).unwrap()
+}
+
+/// Builds the pattern for the description of a variant within a nucleotide
+/// sequence.
+fn nucleotide(base: &str) -> Regex {
+    let location = format!("{POSITION}(?:_{POSITION})?");
+    let sequence = format!("{base}+");
+
+    any_of(&[
+        String::from("="),
+        format!("{POSITION}(?:{base}>{base}|=)"),
+        format!("{location}(?:del|dup)(?:{sequence})?"),
+        format!("{location}inv"),
+        format!("{location}ins(?:{sequence}|{POSITION}_{POSITION}|[0-9]+)"),
+        format!("{location}delins{sequence}"),
+        format!(r"{location}{sequence}\[[0-9]+\]"),
+    ])
+}
+
+/// Builds the pattern for the description of a variant within a protein.
+fn protein(amino_acid: &str, stop: &str) -> Regex {
+    let residue = format!("{amino_acid}[0-9]+");
+    let range = format!("{residue}(?:_{residue})?");
+    let any = format!("(?:{amino_acid}|{stop})");
+    let length = r"(?:[0-9]+|\?)";
+
+    let change = [
+        format!(r"{any}[0-9]+(?:{any}|=|\?)"),
+        format!("{range}(?:del|dup)"),
+        format!("{residue}_{residue}ins(?:{any}+|[0-9]+)"),
+        format!("{range}delins{any}+"),
+        format!("{residue}{amino_acid}?fs(?:{stop}{length}?)?"),
+        format!("{amino_acid}1ext-[0-9]+"),
+        format!("{stop}[0-9]+{amino_acid}ext{stop}{length}"),
+    ]
+    .join("|");
+
+    // NOTE: predicted consequences are wrapped in parentheses.
+    any_of(&[
+        format!(r"\((?:{change})\)"),
+        change,
+        String::from("="),
+        String::from(r"\(=\)"),
+        String::from(r"\?"),
+        String::from(r"0\??"),
+    ])
+}
+
+/// An error when parsing an HGVS expression.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The expression does not have a coordinate type (e.g., `K27M` rather
+    /// than `p.K27M`).
+    MissingCoordinate(String),
+
+    /// The coordinate type is not one defined by HGVS.
+    UnknownCoordinate(String),
+
+    /// The description of the variant is not valid for its coordinate type.
+    Description(String),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::MissingCoordinate(value) => write!(
+                f,
+                "invalid HGVS expression: `{value}`; expected a coordinate type prefix (e.g., \
+                 `c.` or `p.`) before the description of the variant"
+            ),
+            ParseError::UnknownCoordinate(value) => write!(
+                f,
+                "invalid HGVS expression: `{value}`; expected one of the coordinate types `c.`, \
+                 `g.`, `m.`, `n.`, `o.`, `p.`, or `r.`"
+            ),
+            ParseError::Description(value) => write!(
+                f,
+                "invalid HGVS expression: `{value}`; the description of the variant is not valid \
+                 HGVS syntax"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The type of coordinates that a variant is described with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Coordinate {
+    /// A coding DNA sequence (`c.`).
+    Coding,
+
+    /// A linear genomic sequence (`g.`).
+    Genomic,
+
+    /// A mitochondrial DNA sequence (`m.`).
+    Mitochondrial,
+
+    /// A non-coding DNA sequence (`n.`).
+    NonCoding,
+
+    /// A circular genomic sequence (`o.`).
+    Circular,
+
+    /// A protein sequence (`p.`).
+    Protein,
+
+    /// An RNA sequence (`r.`).
+    Rna,
+}
+
+impl Coordinate {
+    /// Gets the coordinate type from its prefix.
+    fn from_prefix(prefix: &str) -> Option<Self> {
+        match prefix {
+            "c" => Some(Coordinate::Coding),
+            "g" => Some(Coordinate::Genomic),
+            "m" => Some(Coordinate::Mitochondrial),
+            "n" => Some(Coordinate::NonCoding),
+            "o" => Some(Coordinate::Circular),
+            "p" => Some(Coordinate::Protein),
+            "r" => Some(Coordinate::Rna),
+            _ => None,
+        }
+    }
+}
+
+/// An HGVS expression (e.g., `p.K27M` or `NM_002502.6:c.239C>G`).
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub struct Expression {
+    /// The expression.
+    value: String,
+
+    /// The type of coordinates.
+    coordinate: Coordinate,
+}
+
+impl Expression {
+    /// Gets the expression as a string slice.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Gets the type of coordinates that the variant is described with.
+    pub fn coordinate(&self) -> Coordinate {
+        self.coordinate
+    }
+}
+
+impl std::fmt::Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.value)
+    }
+}
+
+impl std::str::FromStr for Expression {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(captures) = EXPRESSION.captures(s) else {
+            return Err(ParseError::MissingCoordinate(s.to_string()));
+        };
+
+        let coordinate = Coordinate::from_prefix(&captures["coordinate"])
+            .ok_or_else(|| ParseError::UnknownCoordinate(s.to_string()))?;
+        let description = &captures["description"];
+
+        let valid = match coordinate {
+            Coordinate::Protein => PROTEIN.is_match(description) || PROTEIN_3.is_match(description),
+            Coordinate::Rna => RNA.is_match(description),
+            _ => DNA.is_match(description),
+        };
+
+        if !valid {
+            return Err(ParseError::Description(s.to_string()));
+        }
+
+        Ok(Self {
+            value: s.to_string(),
+            coordinate,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid() {
+        for (expression, coordinate) in [
+            ("p.K27M", Coordinate::Protein),
+            ("p.Lys28Met", Coordinate::Protein),
+            ("p.(Pro80Arg)", Coordinate::Protein),
+            ("NP_004004.1:p.W24*", Coordinate::Protein),
+            ("p.Trp24Ter", Coordinate::Protein),
+            ("p.Arg97ProfsTer23", Coordinate::Protein),
+            ("p.Lys23_Leu24insArgSerGln", Coordinate::Protein),
+            ("p.Cys28delinsTrpVal", Coordinate::Protein),
+            ("p.Ter110GlnextTer17", Coordinate::Protein),
+            ("p.=", Coordinate::Protein),
+            ("NM_004333.6(BRAF):c.1799T>A", Coordinate::Coding),
+            ("c.88+1G>T", Coordinate::Coding),
+            ("c.-14G>C", Coordinate::Coding),
+            ("c.4375_4379del", Coordinate::Coding),
+            ("c.5657_5660dupAGTA", Coordinate::Coding),
+            ("c.849_850ins858_895", Coordinate::Coding),
+            ("NC_000007.14:g.140753336A>T", Coordinate::Genomic),
+            ("m.8993T>G", Coordinate::Mitochondrial),
+            ("r.76a>c", Coordinate::Rna),
+        ] {
+            let parsed = expression
+                .parse::<Expression>()
+                .unwrap_or_else(|err| panic!("{err}"));
+            assert_eq!(parsed.coordinate(), coordinate);
+            assert_eq!(parsed.to_string(), expression);
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(
+            "K27M".parse::<Expression>(),
+            Err(ParseError::MissingCoordinate(String::from("K27M")))
+        );
+        assert_eq!(
+            "x.K27M".parse::<Expression>(),
+            Err(ParseError::UnknownCoordinate(String::from("x.K27M")))
+        );
+
+        for expression in [
+            "p.K27",
+            "p.Lys27M",
+            "c.1799T>U",
+            "r.76A>C",
+            "p.(K27M",
+            "c.1799 T>A",
+        ] {
+            assert_eq!(
+                expression.parse::<Expression>(),
+                Err(ParseError::Description(expression.to_string()))
+            );
+        }
+    }
+}
@@ -5,61 +5,689 @@
 //! `draft` phase so as to not upset the deserializer when information is
 //! missing.
 
+use chrono::DateTime;
+use chrono::TimeDelta;
+use chrono::Utc;
 use nonempty::NonEmpty;
 use serde::Deserialize;
+use serde::Deserializer;
 use serde::Serialize;
 
 use crate::Identifier;
+use crate::common::Applicability;
 use crate::common::Common;
+use crate::common::Error;
+use crate::common::EvidenceLevel;
+use crate::common::Expression;
+use crate::common::Iscn;
+use crate::common::Mapping;
+use crate::common::Molecular;
 use crate::common::Reference;
+use crate::common::Relation;
+use crate::common::Sensitivity;
+use crate::common::Steward;
+use crate::common::Tag;
+use crate::common::deserialize_description;
+use crate::common::deserialize_name;
+use crate::common::validate_description;
+use crate::common::validate_name;
+use crate::common::validate_values;
 use crate::common::value;
+use crate::migrate::SchemaVersion;
 use crate::rfc;
+use crate::text::LanguageTag;
+use crate::text::language;
+use crate::text::language::Translations;
+
+/// A name that is present.
+#[derive(Deserialize)]
+struct Name(#[serde(deserialize_with = "deserialize_name")] String);
+
+/// A description that is present.
+#[derive(Deserialize)]
+struct Description(#[serde(deserialize_with = "deserialize_description")] String);
+
+/// Deserializes an optional name (see [`validate_name()`]).
+fn optional_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    Ok(Option::<Name>::deserialize(deserializer)?.map(|name| name.0))
+}
+
+/// Deserializes an optional description (see [`validate_description()`]).
+fn optional_description<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    Ok(Option::<Description>::deserialize(deserializer)?.map(|description| description.0))
+}
 
 /// An "option common" feature set.
 ///
 /// This represents a [`Common`] where all of the fields are optional. This
 /// feature set should only be relevant for characteristics in the `draft` phase
 /// where all of the information might not yet be filled in.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct OptionalCommon {
+    /// The version of the schema that the characteristic was written against.
+    ///
+    /// Characteristics without a schema version predate versioning.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) schema_version: Option<SchemaVersion>,
+
     /// The name of the characteristic.
-    pub name: Option<String>,
+    #[serde(default, deserialize_with = "optional_name")]
+    pub(crate) name: Option<String>,
+
+    /// Translations of the name into other languages.
+    #[serde(default, skip_serializing_if = "Translations::is_empty")]
+    pub(crate) translations: Translations,
 
     /// The provisional identifier.
-    pub identifier: Option<Identifier>,
+    pub(crate) identifier: Option<Identifier>,
 
     /// A link to the RFC within which the characteristic has been and is being
     /// discussed.
     ///
     /// Any questions regarding the characteristic after adoption should also be
     /// organized here.
-    pub rfc: Option<rfc::Link>,
+    pub(crate) rfc: Option<rfc::Link>,
 
     /// A description.
-    pub description: Option<String>,
+    #[serde(default, deserialize_with = "optional_description")]
+    pub(crate) description: Option<String>,
 
     /// The permissible values that the characteristic takes.
-    pub values: Option<value::Kind>,
+    pub(crate) values: Option<value::Kind>,
 
     /// An optional list of publications.
-    pub references: Option<NonEmpty<Reference>>,
+    pub(crate) references: Option<NonEmpty<Reference>>,
+
+    /// How strong the evidence supporting the characteristic is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) evidence_level: Option<EvidenceLevel>,
+
+    /// How restricted the handling of the data described by the
+    /// characteristic is.
+    #[serde(default, skip_serializing_if = "Sensitivity::is_public")]
+    pub(crate) sensitivity: Sensitivity,
+
+    /// The tags from the controlled vocabulary.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) tags: Vec<Tag>,
+
+    /// The typed relations to other characteristics.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) relations: Vec<Relation>,
+
+    /// The people responsible for the characteristic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) stewards: Option<NonEmpty<Steward>>,
+
+    /// The mappings to terms within external terminologies.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) mappings: Vec<Mapping>,
+
+    /// The genes and variants (for molecular characteristics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) molecular: Option<Molecular>,
+
+    /// The cytogenetic abnormalities (for cytogenetic characteristics).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) cytogenetics: Vec<Iscn>,
+
+    /// The definition in terms of other characteristics (for composite
+    /// characteristics).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) definition: Option<Expression>,
+
+    /// The samples that the characteristic applies to (if it does not apply
+    /// to every sample).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) applicability: Option<Applicability>,
+
+    /// When the draft was created.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) created_at: Option<DateTime<Utc>>,
+
+    /// When the draft was last updated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) updated_at: Option<DateTime<Utc>>,
+}
+
+/// A field that is required outside of the `draft` phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MissingField {
+    /// The name.
+    Name,
+
+    /// The provisional identifier.
+    Identifier,
+
+    /// The link to the RFC.
+    Rfc,
+
+    /// The description.
+    Description,
+
+    /// The permissible values.
+    Values,
+
+    /// A kind of permissible values that the schema models (rather than
+    /// `kind: other`).
+    Kind,
+}
+
+impl std::fmt::Display for MissingField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissingField::Name => write!(f, "name"),
+            MissingField::Identifier => write!(f, "identifier"),
+            MissingField::Rfc => write!(f, "rfc"),
+            MissingField::Description => write!(f, "description"),
+            MissingField::Values => write!(f, "values"),
+            MissingField::Kind => write!(f, "values.kind"),
+        }
+    }
 }
 
 impl OptionalCommon {
-    /// Consumes `self` and returns a [`Common`].
+    /// Creates an empty set of elements written against the current schema
+    /// version.
+    pub fn new() -> Self {
+        Self {
+            schema_version: Some(SchemaVersion::CURRENT),
+            ..Default::default()
+        }
+    }
+
+    /// Gets the version of the schema that the characteristic was written
+    /// against (if it has one).
+    pub fn schema_version(&self) -> Option<SchemaVersion> {
+        self.schema_version
+    }
+
+    /// Gets the name (if it has been set).
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Gets the translations of the name.
+    pub fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    /// Gets the name to display in a language (if there is one).
+    ///
+    /// Falls back to the (English) name if the name has not been translated
+    /// into the language (see [`language::translate()`]).
+    pub fn display_name(&self, language: &LanguageTag) -> Option<&str> {
+        language::translate(&self.translations, language).or(self.name.as_deref())
+    }
+
+    /// Gets the provisional identifier (if one has been assigned).
+    pub fn identifier(&self) -> Option<&Identifier> {
+        self.identifier.as_ref()
+    }
+
+    /// Gets the link to the RFC (if it has been set).
+    pub fn rfc(&self) -> Option<&rfc::Link> {
+        self.rfc.as_ref()
+    }
+
+    /// Gets the description (if it has been set).
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Gets the permissible values (if they have been set).
+    pub fn values(&self) -> Option<&value::Kind> {
+        self.values.as_ref()
+    }
+
+    /// Gets the references (if there are any).
+    pub fn references(&self) -> Option<&NonEmpty<Reference>> {
+        self.references.as_ref()
+    }
+
+    /// Gets the level of the evidence supporting the characteristic (if it
+    /// has been graded).
+    pub fn evidence_level(&self) -> Option<EvidenceLevel> {
+        self.evidence_level
+    }
+
+    /// Gets the sensitivity.
+    pub fn sensitivity(&self) -> Sensitivity {
+        self.sensitivity
+    }
+
+    /// Gets the tags.
+    pub fn tags(&self) -> &[Tag] {
+        &self.tags
+    }
+
+    /// Gets the relations to other characteristics.
+    pub fn relations(&self) -> &[Relation] {
+        &self.relations
+    }
+
+    /// Gets the stewards (if any are listed).
+    pub fn stewards(&self) -> Option<&NonEmpty<Steward>> {
+        self.stewards.as_ref()
+    }
+
+    /// Gets the mappings to terms within external terminologies.
+    pub fn mappings(&self) -> &[Mapping] {
+        &self.mappings
+    }
+
+    /// Gets the genes and variants (if any are listed).
+    pub fn molecular(&self) -> Option<&Molecular> {
+        self.molecular.as_ref()
+    }
+
+    /// Gets the cytogenetic abnormalities.
+    pub fn cytogenetics(&self) -> &[Iscn] {
+        &self.cytogenetics
+    }
+
+    /// Gets the definition in terms of other characteristics (if it has one).
+    pub fn definition(&self) -> Option<&Expression> {
+        self.definition.as_ref()
+    }
+
+    /// Gets the samples that the characteristic applies to (if it does not
+    /// apply to every sample).
+    pub fn applicability(&self) -> Option<&Applicability> {
+        self.applicability.as_ref()
+    }
+
+    /// Gets when the draft was created (if it was recorded).
+    pub fn created_at(&self) -> Option<&DateTime<Utc>> {
+        self.created_at.as_ref()
+    }
+
+    /// Gets when the draft was last updated (if it was recorded).
+    pub fn updated_at(&self) -> Option<&DateTime<Utc>> {
+        self.updated_at.as_ref()
+    }
+
+    /// Gets when the draft was last touched (i.e., when it was last updated or,
+    /// failing that, when it was created).
+    pub fn last_touched(&self) -> Option<&DateTime<Utc>> {
+        self.updated_at.as_ref().or(self.created_at.as_ref())
+    }
+
+    /// Gets how long the draft has gone untouched as of a point in time.
+    ///
+    /// Returns [`None`] if neither timestamp was recorded.
+    pub fn untouched_for(&self, now: DateTime<Utc>) -> Option<TimeDelta> {
+        self.last_touched().map(|touched| now - *touched)
+    }
+
+    /// Sets the version of the schema.
+    pub fn set_schema_version(&mut self, schema_version: Option<SchemaVersion>) {
+        self.schema_version = schema_version;
+    }
+
+    /// Sets the name.
+    ///
+    /// The name must not be empty or have surrounding whitespace.
+    pub fn set_name(&mut self, name: Option<String>) -> Result<(), Error> {
+        if let Some(name) = &name {
+            validate_name(name)?;
+        }
+
+        self.name = name;
+        Ok(())
+    }
+
+    /// Sets the translation of the name into a language, returning the
+    /// previous translation (if any).
+    ///
+    /// The translation must not be empty or have surrounding whitespace.
+    pub fn set_translation(
+        &mut self,
+        language: LanguageTag,
+        name: impl Into<String>,
+    ) -> Result<Option<String>, Error> {
+        let name = name.into();
+        validate_name(&name)?;
+        Ok(self.translations.insert(language, name))
+    }
+
+    /// Removes the translation of the name into a language, returning it (if
+    /// there was one).
+    pub fn remove_translation(&mut self, language: &LanguageTag) -> Option<String> {
+        self.translations.remove(language)
+    }
+
+    /// Sets the provisional identifier.
+    pub fn set_identifier(&mut self, identifier: Option<Identifier>) {
+        self.identifier = identifier;
+    }
+
+    /// Sets the link to the RFC.
+    pub fn set_rfc(&mut self, rfc: Option<rfc::Link>) {
+        self.rfc = rfc;
+    }
+
+    /// Sets the description.
     ///
-    /// This method largely exists to statically verify that every field in
-    /// [`Common`] also exists optionally in [`OptionalCommon`]. There is not
-    /// intended use-case for it in the code.
-    #[allow(dead_code)]
-    pub fn into_common(self) -> Common {
-        Common {
-            name: self.name.expect("`name` to be present"),
-            identifier: self.identifier.expect("`identifier` to be present"),
-            rfc: self.rfc.expect("`rfc` to be present"),
-            description: self.description.expect("`description` to be present"),
-            values: self.values.expect("`values` to be present"),
-            references: self.references,
+    /// The description must not be empty.
+    pub fn set_description(&mut self, description: Option<String>) -> Result<(), Error> {
+        if let Some(description) = &description {
+            validate_description(description)?;
         }
+
+        self.description = description;
+        Ok(())
+    }
+
+    /// Sets the permissible values.
+    pub fn set_values(&mut self, values: Option<value::Kind>) {
+        self.values = values;
+    }
+
+    /// Sets the references.
+    pub fn set_references(&mut self, references: Option<NonEmpty<Reference>>) {
+        self.references = references;
+    }
+
+    /// Sets the level of the evidence supporting the characteristic.
+    pub fn set_evidence_level(&mut self, evidence_level: Option<EvidenceLevel>) {
+        self.evidence_level = evidence_level;
+    }
+
+    /// Sets the sensitivity.
+    pub fn set_sensitivity(&mut self, sensitivity: Sensitivity) {
+        self.sensitivity = sensitivity;
+    }
+
+    /// Sets the tags.
+    pub fn set_tags(&mut self, tags: Vec<Tag>) {
+        self.tags = tags;
+    }
+
+    /// Sets the relations to other characteristics.
+    pub fn set_relations(&mut self, relations: Vec<Relation>) {
+        self.relations = relations;
+    }
+
+    /// Sets the stewards.
+    pub fn set_stewards(&mut self, stewards: Option<NonEmpty<Steward>>) {
+        self.stewards = stewards;
+    }
+
+    /// Sets the mappings to terms within external terminologies.
+    pub fn set_mappings(&mut self, mappings: Vec<Mapping>) {
+        self.mappings = mappings;
+    }
+
+    /// Sets the genes and variants.
+    pub fn set_molecular(&mut self, molecular: Option<Molecular>) {
+        self.molecular = molecular;
+    }
+
+    /// Sets the cytogenetic abnormalities.
+    pub fn set_cytogenetics(&mut self, cytogenetics: Vec<Iscn>) {
+        self.cytogenetics = cytogenetics;
+    }
+
+    /// Sets the definition in terms of other characteristics.
+    pub fn set_definition(&mut self, definition: Option<Expression>) {
+        self.definition = definition;
+    }
+
+    /// Sets the samples that the characteristic applies to.
+    pub fn set_applicability(&mut self, applicability: Option<Applicability>) {
+        self.applicability = applicability;
+    }
+
+    /// Sets when the draft was created.
+    pub fn set_created_at(&mut self, created_at: Option<DateTime<Utc>>) {
+        self.created_at = created_at;
+    }
+
+    /// Sets when the draft was last updated.
+    pub fn set_updated_at(&mut self, updated_at: Option<DateTime<Utc>>) {
+        self.updated_at = updated_at;
+    }
+
+    /// Records that the draft was changed at a point in time.
+    ///
+    /// The creation time is also set if it was not recorded.
+    pub fn touch(&mut self, now: DateTime<Utc>) {
+        self.created_at.get_or_insert(now);
+        self.updated_at = Some(now);
+    }
+
+    /// Gets the fields that must be filled in before the characteristic can
+    /// leave the `draft` phase.
+    ///
+    /// Values of a kind that the schema does not model (`kind: other`) are
+    /// reported as [`MissingField::Kind`].
+    pub fn missing_fields(&self) -> Vec<MissingField> {
+        let mut missing = Vec::new();
+
+        if self.name.is_none() {
+            missing.push(MissingField::Name);
+        }
+
+        if self.identifier.is_none() {
+            missing.push(MissingField::Identifier);
+        }
+
+        if self.rfc.is_none() {
+            missing.push(MissingField::Rfc);
+        }
+
+        if self.description.is_none() {
+            missing.push(MissingField::Description);
+        }
+
+        match &self.values {
+            Some(values) if validate_values(values).is_err() => missing.push(MissingField::Kind),
+            Some(_) => {}
+            None => missing.push(MissingField::Values),
+        }
+
+        missing
+    }
+
+    /// Attempts to convert `self` into a [`Common`].
+    ///
+    /// If any required fields are missing (see [`Self::missing_fields()`]),
+    /// they are all returned. The timestamps are only tracked for drafts, so
+    /// they are dropped.
+    pub fn try_into_common(self) -> Result<Common, Vec<MissingField>> {
+        let missing = self.missing_fields();
+
+        match self {
+            Self {
+                schema_version,
+                name: Some(name),
+                translations,
+                identifier: Some(identifier),
+                rfc: Some(rfc),
+                description: Some(description),
+                values: Some(values),
+                references,
+                evidence_level,
+                sensitivity,
+                tags,
+                relations,
+                stewards,
+                mappings,
+                molecular,
+                cytogenetics,
+                definition,
+                applicability,
+                created_at: _,
+                updated_at: _,
+            } if missing.is_empty() => Ok(Common {
+                schema_version,
+                name,
+                translations,
+                identifier,
+                rfc,
+                description,
+                values,
+                references,
+                evidence_level,
+                sensitivity,
+                tags,
+                relations,
+                stewards,
+                mappings,
+                molecular,
+                cytogenetics,
+                definition,
+                applicability,
+            }),
+            _ => Err(missing),
+        }
+    }
+}
+
+impl From<Common> for OptionalCommon {
+    fn from(common: Common) -> Self {
+        Self {
+            schema_version: common.schema_version,
+            name: Some(common.name),
+            translations: common.translations,
+            identifier: Some(common.identifier),
+            rfc: Some(common.rfc),
+            description: Some(common.description),
+            values: Some(common.values),
+            references: common.references,
+            evidence_level: common.evidence_level,
+            sensitivity: common.sensitivity,
+            tags: common.tags,
+            relations: common.relations,
+            stewards: common.stewards,
+            mappings: common.mappings,
+            molecular: common.molecular,
+            cytogenetics: common.cytogenetics,
+            definition: common.definition,
+            applicability: common.applicability,
+            created_at: None,
+            updated_at: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::value::Kind;
+
+    #[test]
+    fn missing_fields() {
+        let mut common = OptionalCommon::default();
+        common
+            .set_name(Some(String::from("A Characteristic Name")))
+            .unwrap();
+
+        assert_eq!(
+            common.missing_fields(),
+            [
+                MissingField::Identifier,
+                MissingField::Rfc,
+                MissingField::Description,
+                MissingField::Values
+            ]
+        );
+
+        common.set_identifier(Some("ECC-MORPH-000001".parse().unwrap()));
+        common.set_rfc(Some(
+            "https://github.com/stjudecloud/ecc/issues/1"
+                .parse()
+                .unwrap(),
+        ));
+        common
+            .set_description(Some(String::from("A description.")))
+            .unwrap();
+
+        assert_eq!(
+            common.clone().try_into_common().unwrap_err(),
+            [MissingField::Values]
+        );
+
+        // NOTE: values of a kind that the schema does not model must be
+        // replaced before leaving the `draft` phase.
+        common.set_values(Some(Kind::Other {
+            description: String::from("A count of cells."),
+        }));
+        assert_eq!(
+            common.clone().try_into_common().unwrap_err(),
+            [MissingField::Kind]
+        );
+        assert_eq!(MissingField::Kind.to_string(), "values.kind");
+
+        common.set_values(Some(Kind::Categorical {
+            options: [String::from("Foo")].into(),
+        }));
+
+        let complete = common.clone().try_into_common().unwrap();
+        assert_eq!(complete.name(), "A Characteristic Name");
+        assert_eq!(OptionalCommon::from(complete), common);
+    }
+
+    #[test]
+    fn setters() {
+        let mut common = OptionalCommon::default();
+
+        assert_eq!(
+            common.set_name(Some(String::from("  "))),
+            Err(Error::Empty("name"))
+        );
+        assert_eq!(
+            common.set_name(Some(String::from(" Foo"))),
+            Err(Error::Whitespace("name"))
+        );
+        assert_eq!(
+            common.set_description(Some(String::new())),
+            Err(Error::Empty("description"))
+        );
+        assert!(common.name().is_none());
+        assert!(common.description().is_none());
+    }
+
+    #[test]
+    fn deserialize() {
+        let common = serde_yaml::from_str::<OptionalCommon>("name: Foo\n").unwrap();
+        assert_eq!(common.name(), Some("Foo"));
+        assert!(common.description().is_none());
+
+        for (yaml, message) in [
+            ("name: '  '\n", "`name` must not be empty"),
+            (
+                "name: ' Foo'\n",
+                "`name` must not have surrounding whitespace",
+            ),
+            ("description: ''\n", "`description` must not be empty"),
+        ] {
+            let err = serde_yaml::from_str::<OptionalCommon>(yaml).unwrap_err();
+            assert!(err.to_string().contains(message), "{err}");
+        }
+    }
+
+    #[test]
+    fn timestamps() {
+        let created = DateTime::from_timestamp(1_704_067_200, 0).unwrap();
+        let updated = created + TimeDelta::days(30);
+
+        let mut common = OptionalCommon::default();
+        assert!(common.untouched_for(updated).is_none());
+
+        common.touch(created);
+        assert_eq!(common.created_at(), Some(&created));
+        assert_eq!(common.untouched_for(updated), Some(TimeDelta::days(30)));
+
+        common.touch(updated);
+        assert_eq!(common.created_at(), Some(&created));
+        assert_eq!(common.last_touched(), Some(&updated));
+
+        let yaml = serde_yaml::to_string(&common).unwrap();
+        assert!(yaml.contains("created_at: 2024-01-01T00:00:00Z"));
+        assert!(yaml.contains("updated_at: 2024-01-31T00:00:00Z"));
     }
 }
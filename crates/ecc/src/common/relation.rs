@@ -0,0 +1,234 @@
+//! Typed relations between characteristics.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Identifier;
+
+/// An error when parsing a relation kind.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown relation kind: `{}`; expected `supersedes`, `refines`, `refined-by`, or \
+             `mutually-exclusive-with`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The kind of a relation between two characteristics.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub enum Kind {
+    /// The characteristic replaces the target, which should no longer be
+    /// used for new samples.
+    Supersedes,
+
+    /// The characteristic is a more specific form of the target (e.g., a
+    /// specific fusion refines a generic rearrangement).
+    Refines,
+
+    /// The target is a more specific form of the characteristic (i.e., the
+    /// inverse of [`Kind::Refines`]).
+    RefinedBy,
+
+    /// The characteristic and the target cannot both be recorded for the same
+    /// sample.
+    MutuallyExclusiveWith,
+}
+
+impl Kind {
+    /// All relation kinds.
+    pub const ALL: [Kind; 4] = [
+        Kind::Supersedes,
+        Kind::Refines,
+        Kind::RefinedBy,
+        Kind::MutuallyExclusiveWith,
+    ];
+
+    /// Gets the name of the kind as it appears within the `relations` field of
+    /// a characteristic (e.g., `refined-by`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Kind::Supersedes => "supersedes",
+            Kind::Refines => "refines",
+            Kind::RefinedBy => "refined-by",
+            Kind::MutuallyExclusiveWith => "mutually-exclusive-with",
+        }
+    }
+
+    /// Gets the kind that the target must declare in return (if the kind is
+    /// symmetric or has an inverse).
+    ///
+    /// A characteristic that supersedes another does not require the other to
+    /// acknowledge it, as superseded characteristics are left untouched.
+    pub fn inverse(&self) -> Option<Kind> {
+        match self {
+            Kind::Supersedes => None,
+            Kind::Refines => Some(Kind::RefinedBy),
+            Kind::RefinedBy => Some(Kind::Refines),
+            Kind::MutuallyExclusiveWith => Some(Kind::MutuallyExclusiveWith),
+        }
+    }
+}
+
+impl std::fmt::Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for Kind {
+    type Err = ParseError;
+
+    /// Parses a relation kind from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Kind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError(s.to_string()))
+    }
+}
+
+/// A typed relation from a characteristic to another characteristic.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Relation {
+    /// The kind of relation.
+    pub(crate) kind: Kind,
+
+    /// The identifier of the related characteristic.
+    pub(crate) target: Identifier,
+}
+
+impl Relation {
+    /// Creates a new relation.
+    pub fn new(kind: Kind, target: Identifier) -> Self {
+        Self { kind, target }
+    }
+
+    /// Gets the kind of relation.
+    pub fn kind(&self) -> Kind {
+        self.kind
+    }
+
+    /// Gets the identifier of the related characteristic.
+    pub fn target(&self) -> &Identifier {
+        &self.target
+    }
+}
+
+/// A problem with the relations between the characteristics of a set (see
+/// [`crate::CharacteristicSet::relation_issues()`]).
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum Issue {
+    /// A relation targets a characteristic that is not within the set.
+    #[error("`{characteristic}` {kind} unknown characteristic `{target}`")]
+    UnknownTarget {
+        /// The characteristic declaring the relation.
+        characteristic: Identifier,
+
+        /// The kind of relation.
+        kind: Kind,
+
+        /// The missing target.
+        target: Identifier,
+    },
+
+    /// A characteristic is related to itself.
+    #[error("`{identifier}` {kind} itself")]
+    SelfRelation {
+        /// The characteristic.
+        identifier: Identifier,
+
+        /// The kind of relation.
+        kind: Kind,
+    },
+
+    /// The target of a relation does not declare the inverse relation.
+    #[error(
+        "`{characteristic}` {kind} `{target}`, but `{target}` is missing the inverse `{inverse}` \
+         relation"
+    )]
+    MissingInverse {
+        /// The characteristic declaring the relation.
+        characteristic: Identifier,
+
+        /// The kind of relation.
+        kind: Kind,
+
+        /// The target of the relation.
+        target: Identifier,
+
+        /// The kind of relation the target must declare.
+        inverse: Kind,
+    },
+
+    /// Relations of a kind that must be acyclic form a cycle.
+    #[error(
+        "`{kind}` relations form a cycle: {}",
+        .identifiers.iter().map(|identifier| format!("`{identifier}`")).collect::<Vec<_>>().join(" -> ")
+    )]
+    Cycle {
+        /// The kind of relation (refinements are reported as
+        /// [`Kind::Refines`]).
+        kind: Kind,
+
+        /// The characteristics along the cycle, ending with the first one.
+        identifiers: Vec<Identifier>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("refined-by".parse::<Kind>(), Ok(Kind::RefinedBy));
+        assert_eq!("Supersedes".parse::<Kind>(), Ok(Kind::Supersedes));
+
+        for kind in Kind::ALL {
+            assert_eq!(kind.to_string().parse(), Ok(kind));
+        }
+
+        let err = "replaces".parse::<Kind>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown relation kind: `replaces`; expected `supersedes`, `refines`, `refined-by`, \
+             or `mutually-exclusive-with`"
+        );
+    }
+
+    #[test]
+    fn inverse() {
+        for kind in Kind::ALL {
+            if let Some(inverse) = kind.inverse() {
+                assert_eq!(inverse.inverse(), Some(kind));
+            }
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        let relation =
+            serde_yaml::from_str::<Relation>("kind: refines\ntarget: ECC-MOLEC-000001").unwrap();
+        assert_eq!(relation.kind(), Kind::Refines);
+        assert_eq!(relation.target().to_string(), "ECC-MOLEC-000001");
+    }
+}
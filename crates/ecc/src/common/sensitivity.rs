@@ -0,0 +1,117 @@
+//! Sensitivities of characteristics.
+
+use alloc::string::String;
+use alloc::string::ToString as _;
+
+/// An error when parsing a sensitivity.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "unknown sensitivity: `{}`; expected `public`, `controlled`, or `restricted`",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// How restricted the handling of the data described by a characteristic is
+/// (e.g., germline findings are typically restricted).
+///
+/// Sensitivities are ordered from the least to the most restricted, and
+/// characteristics without a sensitivity are public.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub enum Sensitivity {
+    /// The data can be shared openly.
+    #[default]
+    Public,
+
+    /// The data can only be shared with approved users (e.g., through a data
+    /// access committee).
+    Controlled,
+
+    /// The data must not be shared beyond the institution that holds it.
+    Restricted,
+}
+
+impl Sensitivity {
+    /// All sensitivities from the least to the most restricted.
+    pub const ALL: [Sensitivity; 3] = [
+        Sensitivity::Public,
+        Sensitivity::Controlled,
+        Sensitivity::Restricted,
+    ];
+
+    /// Gets the name of the sensitivity as it appears within the
+    /// `sensitivity` field of a characteristic (e.g., `controlled`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Sensitivity::Public => "public",
+            Sensitivity::Controlled => "controlled",
+            Sensitivity::Restricted => "restricted",
+        }
+    }
+
+    /// Returns whether the sensitivity is [`Sensitivity::Public`].
+    pub fn is_public(&self) -> bool {
+        *self == Sensitivity::Public
+    }
+}
+
+impl core::fmt::Display for Sensitivity {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl core::str::FromStr for Sensitivity {
+    type Err = ParseError;
+
+    /// Parses a sensitivity from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Sensitivity::ALL
+            .into_iter()
+            .find(|sensitivity| sensitivity.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("public".parse::<Sensitivity>(), Ok(Sensitivity::Public));
+        assert_eq!(
+            "Restricted".parse::<Sensitivity>(),
+            Ok(Sensitivity::Restricted)
+        );
+
+        for sensitivity in Sensitivity::ALL {
+            assert_eq!(sensitivity.to_string().parse(), Ok(sensitivity));
+        }
+
+        let err = "secret".parse::<Sensitivity>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown sensitivity: `secret`; expected `public`, `controlled`, or `restricted`"
+        );
+    }
+
+    #[test]
+    fn order() {
+        assert!(Sensitivity::Public < Sensitivity::Controlled);
+        assert!(Sensitivity::Controlled < Sensitivity::Restricted);
+        assert!(Sensitivity::ALL.is_sorted());
+        assert_eq!(Sensitivity::default(), Sensitivity::Public);
+    }
+}
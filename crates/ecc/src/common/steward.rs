@@ -0,0 +1,151 @@
+//! Stewards of characteristics.
+
+use serde::Deserialize;
+use serde::Serialize;
+
+/// The longest that a GitHub handle can be.
+const MAX_HANDLE_LENGTH: usize = 39;
+
+/// An error when parsing a GitHub handle.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid GitHub handle: `{}`; expected up to {MAX_HANDLE_LENGTH} letters, digits, and \
+             single hyphens that does not start or end with a hyphen (and without a leading `@`)",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A GitHub handle (e.g., `octocat`).
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    serde_with::SerializeDisplay,
+    serde_with::DeserializeFromStr,
+)]
+pub struct Handle(String);
+
+impl Handle {
+    /// Gets the handle as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl std::fmt::Display for Handle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::str::FromStr for Handle {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valid = s.len() <= MAX_HANDLE_LENGTH
+            && s.split('-')
+                .all(|word| !word.is_empty() && word.bytes().all(|b| b.is_ascii_alphanumeric()));
+
+        if valid {
+            Ok(Self(s.to_string()))
+        } else {
+            Err(ParseError(s.to_string()))
+        }
+    }
+}
+
+/// A person responsible for shepherding a characteristic through its
+/// lifecycle.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Steward {
+    /// The name of the steward.
+    pub(crate) name: String,
+
+    /// The GitHub handle of the steward.
+    pub(crate) github: Handle,
+
+    /// The email address of the steward.
+    pub(crate) email: String,
+}
+
+impl Steward {
+    /// Creates a new steward.
+    pub fn new(name: impl Into<String>, github: Handle, email: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            github,
+            email: email.into(),
+        }
+    }
+
+    /// Gets the name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Gets the GitHub handle.
+    pub fn github(&self) -> &Handle {
+        &self.github
+    }
+
+    /// Gets the email address.
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn handle() {
+        for handle in ["octocat", "Jane-Smith", "a", "stjude-cloud-2"] {
+            assert_eq!(handle.parse::<Handle>().unwrap().as_str(), handle);
+        }
+
+        for handle in [
+            "",
+            "@octocat",
+            "-octocat",
+            "octocat-",
+            "octo--cat",
+            "octo_cat",
+            "octo cat",
+            "a123456789012345678901234567890123456789",
+        ] {
+            assert_eq!(
+                handle.parse::<Handle>(),
+                Err(ParseError(handle.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn deserialize() {
+        let steward = serde_yaml::from_str::<Steward>(
+            "name: Jane Smith\ngithub: jsmith\nemail: jane.smith@example.com",
+        )
+        .unwrap();
+        assert_eq!(steward.name(), "Jane Smith");
+        assert_eq!(steward.github().as_str(), "jsmith");
+
+        let err = serde_yaml::from_str::<Steward>(
+            "name: Jane Smith\ngithub: '@jsmith'\nemail: jane.smith@example.com",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("invalid GitHub handle: `@jsmith`"));
+    }
+}
@@ -0,0 +1,102 @@
+//! Tags on characteristics.
+
+use alloc::string::String;
+use alloc::string::ToString as _;
+
+/// An error when parsing a tag.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "invalid tag: `{}`; expected lowercase letters and digits separated by single hyphens \
+             (e.g., `solid-tumor`)",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// A label that groups characteristics along an axis orthogonal to their
+/// category (e.g., `pediatric` or `solid-tumor`).
+///
+/// Tags are lowercase letters and digits separated by single hyphens. Which
+/// tags can be used is decided by the controlled vocabulary of the registry
+/// rather than by this type.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub struct Tag(String);
+
+impl Tag {
+    /// Gets the tag as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl core::borrow::Borrow<str> for Tag {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl core::fmt::Display for Tag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl core::str::FromStr for Tag {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valid = s.split('-').all(|word| {
+            !word.is_empty()
+                && word
+                    .bytes()
+                    .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit())
+        });
+
+        if valid {
+            Ok(Self(s.to_string()))
+        } else {
+            Err(ParseError(s.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        for tag in ["pediatric", "solid-tumor", "cns", "aya-15-39"] {
+            assert_eq!(tag.parse::<Tag>().unwrap().as_str(), tag);
+        }
+
+        for tag in [
+            "",
+            "Pediatric",
+            "solid tumor",
+            "solid--tumor",
+            "-cns",
+            "cns-",
+            "café",
+        ] {
+            assert_eq!(tag.parse::<Tag>(), Err(ParseError(tag.to_string())));
+        }
+
+        assert_eq!(
+            "Solid_Tumor".parse::<Tag>().unwrap_err().to_string(),
+            "invalid tag: `Solid_Tumor`; expected lowercase letters and digits separated by \
+             single hyphens (e.g., `solid-tumor`)"
+        );
+    }
+}
@@ -1,16 +1,20 @@
 //! Kinds of permissible values.
 
-use std::collections::HashSet;
-
-use serde::Deserialize;
-use serde::Serialize;
+use alloc::collections::BTreeSet;
+use alloc::string::String;
 
 pub mod binary;
+pub mod fraction;
+pub mod immunophenotype;
 pub mod numerical;
 
 /// A permissible value for a characteristic.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(tag = "kind", rename_all = "lowercase")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "kind", rename_all = "lowercase")
+)]
 pub enum Kind {
     /// A binary feature.
     ///
@@ -19,12 +23,20 @@ pub enum Kind {
     Binary {
         /// The description.
         description: binary::Description,
+
+        /// The threshold that the feature is derived from (if it is derived
+        /// from a numerical feature).
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        threshold: Option<binary::Threshold>,
     },
 
     /// A categorical feature.
     Categorical {
         /// The set of values that the feature can take on.
-        options: HashSet<String>,
+        options: BTreeSet<String>,
     },
 
     /// A numerical feature.
@@ -35,4 +47,130 @@ pub enum Kind {
         /// A description of the units of measurement.
         units: String,
     },
+
+    /// A fractional feature (e.g., a blast percentage or a variant allele
+    /// fraction).
+    Fraction {
+        /// A description of the whole that the fraction is a part of (e.g.,
+        /// "Nucleated cells within the bone marrow aspirate").
+        denominator_description: String,
+
+        /// The scale that values are written on.
+        scale: fraction::Scale,
+
+        /// The smallest permissible value (if narrower than the scale).
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        min: Option<f64>,
+
+        /// The largest permissible value (if narrower than the scale).
+        #[cfg_attr(
+            feature = "serde",
+            serde(default, skip_serializing_if = "Option::is_none")
+        )]
+        max: Option<f64>,
+    },
+
+    /// An immunophenotypic feature.
+    ///
+    /// A value records the expression level of each marker assessed within
+    /// the panel (see [`immunophenotype::Phenotype`]).
+    Immunophenotype {
+        /// The markers that the feature assesses.
+        markers: BTreeSet<immunophenotype::Marker>,
+    },
+
+    /// A kind of value that is not modeled by the schema yet.
+    ///
+    /// This allows drafts to describe their values before the schema supports
+    /// them. Only drafts may use it: `ecc-cli check` reports an error for any
+    /// other state.
+    Other {
+        /// A description of the values that the feature can take on.
+        description: String,
+    },
+}
+
+impl Kind {
+    /// Gets the name of the kind as it appears in the `kind` field.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Binary { .. } => "binary",
+            Kind::Categorical { .. } => "categorical",
+            Kind::Numerical { .. } => "numerical",
+            Kind::Fraction { .. } => "fraction",
+            Kind::Immunophenotype { .. } => "immunophenotype",
+            Kind::Other { .. } => "other",
+        }
+    }
+
+    /// Converts a fractional feature into the equivalent numerical feature
+    /// (a float in the units of its scale, such as `%`).
+    ///
+    /// Returns [`None`] for any other kind.
+    pub fn to_numerical(&self) -> Option<Kind> {
+        match self {
+            Kind::Fraction { scale, .. } => Some(Kind::Numerical {
+                r#type: numerical::Type::Float,
+                units: String::from(scale.units()),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Converts a numerical feature into the equivalent fractional feature.
+    ///
+    /// Returns [`None`] for any other kind or if the units are not those of a
+    /// fraction (see [`fraction::Scale::from_units`]).
+    pub fn to_fraction(&self, denominator_description: impl Into<String>) -> Option<Kind> {
+        match self {
+            Kind::Numerical { units, .. } => Some(Kind::Fraction {
+                denominator_description: denominator_description.into(),
+                scale: fraction::Scale::from_units(units)?,
+                min: None,
+                max: None,
+            }),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fraction() {
+        let numerical = Kind::Numerical {
+            r#type: numerical::Type::Float,
+            units: String::from("Percent"),
+        };
+
+        let fraction = numerical.to_fraction("Nucleated cells").unwrap();
+        assert_eq!(
+            fraction,
+            Kind::Fraction {
+                denominator_description: String::from("Nucleated cells"),
+                scale: fraction::Scale::Percent,
+                min: None,
+                max: None,
+            }
+        );
+        assert_eq!(
+            fraction.to_numerical(),
+            Some(Kind::Numerical {
+                r#type: numerical::Type::Float,
+                units: String::from("%"),
+            })
+        );
+
+        let cells = Kind::Numerical {
+            r#type: numerical::Type::Unsigned,
+            units: String::from("Cells"),
+        };
+        assert_eq!(cells.to_fraction("Nucleated cells"), None);
+        assert_eq!(cells.to_numerical(), None);
+    }
 }
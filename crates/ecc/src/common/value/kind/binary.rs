@@ -1,12 +1,19 @@
 //! Binary features.
+//!
+//! A binary feature can be derived from a numerical (or fractional) feature
+//! by a threshold (e.g., "MRD positive" is `ECC-MOLEC-000042 >= 0.01 %`). The
+//! threshold is stored structurally so that derived values can be computed
+//! and checked consistently (see [`Threshold::evaluate`]).
 
-use serde::Deserialize;
-use serde::Serialize;
+use alloc::string::String;
+use alloc::string::ToString as _;
 
+use crate::Identifier;
 use crate::field;
 
 /// The description of a binary feature kind.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Description {
     /// A description of the `true` field value.
     pub r#true: field::Description,
@@ -14,3 +21,152 @@ pub struct Description {
     /// A description of the `false` field value.
     pub r#false: field::Description,
 }
+
+/// An error when parsing a comparator.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseComparatorError(String);
+
+impl core::fmt::Display for ParseComparatorError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "unknown comparator: `{}`; expected one of `>`, `>=`, `<`, or `<=`",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for ParseComparatorError {}
+
+/// A comparison between a value and a threshold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub enum Comparator {
+    /// The value is greater than the threshold (`>`).
+    Greater,
+
+    /// The value is greater than or equal to the threshold (`>=`).
+    GreaterOrEqual,
+
+    /// The value is less than the threshold (`<`).
+    Less,
+
+    /// The value is less than or equal to the threshold (`<=`).
+    LessOrEqual,
+}
+
+impl Comparator {
+    /// Gets the symbol of the comparator (e.g., `>=`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Comparator::Greater => ">",
+            Comparator::GreaterOrEqual => ">=",
+            Comparator::Less => "<",
+            Comparator::LessOrEqual => "<=",
+        }
+    }
+
+    /// Compares a value to a threshold.
+    pub fn compare(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparator::Greater => value > threshold,
+            Comparator::GreaterOrEqual => value >= threshold,
+            Comparator::Less => value < threshold,
+            Comparator::LessOrEqual => value <= threshold,
+        }
+    }
+}
+
+impl core::fmt::Display for Comparator {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl core::str::FromStr for Comparator {
+    type Err = ParseComparatorError;
+
+    /// Parses a comparator from its symbol (`≥` and `≤` are also accepted).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            ">" => Ok(Comparator::Greater),
+            ">=" | "≥" => Ok(Comparator::GreaterOrEqual),
+            "<" => Ok(Comparator::Less),
+            "<=" | "≤" => Ok(Comparator::LessOrEqual),
+            _ => Err(ParseComparatorError(s.to_string())),
+        }
+    }
+}
+
+/// A threshold on a numerical (or fractional) characteristic from which a
+/// binary feature is derived.
+///
+/// The feature is `true` when the value of the source characteristic
+/// satisfies the comparison and `false` otherwise.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Threshold {
+    /// The characteristic that the feature is derived from.
+    pub source: Identifier,
+
+    /// The comparison of the source value to the threshold.
+    pub comparator: Comparator,
+
+    /// The threshold.
+    pub value: f64,
+
+    /// The units of the threshold.
+    ///
+    /// These may differ from the units of the source characteristic as long as
+    /// they can be converted (e.g., `%` for a source recorded as a
+    /// proportion).
+    pub units: String,
+}
+
+impl Threshold {
+    /// Evaluates the threshold for a source value (in the units of the
+    /// threshold).
+    pub fn evaluate(&self, value: f64) -> bool {
+        self.comparator.compare(value, self.value)
+    }
+}
+
+impl core::fmt::Display for Threshold {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{} {} {} {}",
+            self.source, self.comparator, self.value, self.units
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold() {
+        assert_eq!("≥".parse::<Comparator>(), Ok(Comparator::GreaterOrEqual));
+        assert_eq!(
+            "=>".parse::<Comparator>(),
+            Err(ParseComparatorError(String::from("=>")))
+        );
+
+        let threshold = Threshold {
+            source: Identifier::molecular(42).unwrap(),
+            comparator: Comparator::GreaterOrEqual,
+            value: 0.01,
+            units: String::from("%"),
+        };
+        assert_eq!(threshold.to_string(), "ECC-MOLEC-000042 >= 0.01 %");
+        assert!(threshold.evaluate(0.01));
+        assert!(threshold.evaluate(2.5));
+        assert!(!threshold.evaluate(0.001));
+        assert!(Comparator::Less.compare(1.0, 2.0));
+        assert!(!Comparator::LessOrEqual.compare(3.0, 2.0));
+    }
+}
@@ -0,0 +1,215 @@
+//! Fractional features.
+//!
+//! A fraction is a part of some whole (e.g., the blasts among the nucleated
+//! cells of a bone marrow aspirate, or the reads supporting a variant among
+//! all reads covering its position). The whole is described by the
+//! denominator of the feature, and values are written on one of two scales: as
+//! a percentage (`0` to `100`) or as a proportion (`0` to `1`).
+
+use alloc::string::String;
+
+/// An error when validating the bounds of a fractional feature.
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// A bound is outside of the range of the scale.
+    OutOfRange {
+        /// The name of the bound (`min` or `max`).
+        bound: &'static str,
+
+        /// The value of the bound.
+        value: f64,
+
+        /// The scale.
+        scale: Scale,
+    },
+
+    /// The minimum is greater than the maximum.
+    Inverted {
+        /// The minimum.
+        min: f64,
+
+        /// The maximum.
+        max: f64,
+    },
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Error::OutOfRange {
+                bound,
+                value,
+                scale,
+            } => write!(
+                f,
+                "`{bound}` is {value}, but a {} must be between 0 and {}",
+                scale.noun(),
+                scale.maximum()
+            ),
+            Error::Inverted { min, max } => {
+                write!(f, "`min` ({min}) must not be greater than `max` ({max})")
+            }
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// The scale on which a fraction is written.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
+pub enum Scale {
+    /// A percentage (`0` to `100`).
+    Percent,
+
+    /// A proportion (`0` to `1`).
+    Proportion,
+}
+
+impl Scale {
+    /// Gets the largest value on the scale (i.e., the whole).
+    pub fn maximum(&self) -> f64 {
+        match self {
+            Scale::Percent => 100.0,
+            Scale::Proportion => 1.0,
+        }
+    }
+
+    /// Gets the units that values on the scale are written in.
+    ///
+    /// These are the units of the equivalent numerical feature (see
+    /// [`Kind::to_numerical`](super::Kind::to_numerical)).
+    pub fn units(&self) -> &'static str {
+        match self {
+            Scale::Percent => "%",
+            Scale::Proportion => "proportion",
+        }
+    }
+
+    /// Gets the scale that values written in some units are on.
+    ///
+    /// Units are matched case-insensitively (e.g., `%`, `percent`, or
+    /// `Proportion`).
+    pub fn from_units(units: &str) -> Option<Self> {
+        const PERCENT: [&str; 3] = ["%", "percent", "percentage"];
+        const PROPORTION: [&str; 2] = ["proportion", "fraction"];
+
+        let units = units.trim();
+        let matches = |names: &[&str]| names.iter().any(|name| name.eq_ignore_ascii_case(units));
+
+        if matches(&PERCENT) {
+            Some(Scale::Percent)
+        } else if matches(&PROPORTION) {
+            Some(Scale::Proportion)
+        } else {
+            None
+        }
+    }
+
+    /// Converts a value on this scale to another scale.
+    pub fn convert(&self, value: f64, to: Scale) -> f64 {
+        value / self.maximum() * to.maximum()
+    }
+
+    /// Gets the noun for a value on the scale.
+    fn noun(&self) -> &'static str {
+        match self {
+            Scale::Percent => "percentage",
+            Scale::Proportion => "proportion",
+        }
+    }
+}
+
+impl core::fmt::Display for Scale {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Scale::Percent => write!(f, "percent"),
+            Scale::Proportion => write!(f, "proportion"),
+        }
+    }
+}
+
+/// Validates the bounds of a fractional feature.
+///
+/// Each bound must be within the range of the scale, and the minimum must not
+/// be greater than the maximum.
+pub fn validate(scale: Scale, min: Option<f64>, max: Option<f64>) -> Result<(), Error> {
+    for (bound, value) in [("min", min), ("max", max)] {
+        let Some(value) = value else {
+            continue;
+        };
+
+        if !(0.0..=scale.maximum()).contains(&value) {
+            return Err(Error::OutOfRange {
+                bound,
+                value,
+                scale,
+            });
+        }
+    }
+
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(Error::Inverted { min, max });
+        }
+    }
+
+    Ok(())
+}
+
+/// Describes the permissible values of a fractional feature (e.g., `a
+/// percentage between 0 and 100`).
+pub fn describe(scale: Scale, min: Option<f64>, max: Option<f64>) -> String {
+    alloc::format!(
+        "a {} between {} and {}",
+        scale.noun(),
+        min.unwrap_or(0.0),
+        max.unwrap_or(scale.maximum())
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scale() {
+        assert_eq!(Scale::from_units("%"), Some(Scale::Percent));
+        assert_eq!(Scale::from_units(" Percent "), Some(Scale::Percent));
+        assert_eq!(Scale::from_units("proportion"), Some(Scale::Proportion));
+        assert_eq!(Scale::from_units("Cells"), None);
+
+        assert_eq!(Scale::Percent.convert(20.0, Scale::Proportion), 0.2);
+        assert_eq!(Scale::Proportion.convert(0.01, Scale::Percent), 1.0);
+        assert_eq!(Scale::Percent.convert(5.0, Scale::Percent), 5.0);
+    }
+
+    #[test]
+    fn bounds() {
+        assert!(validate(Scale::Percent, Some(20.0), None).is_ok());
+        assert!(validate(Scale::Proportion, Some(0.0), Some(1.0)).is_ok());
+
+        assert_eq!(
+            validate(Scale::Proportion, None, Some(20.0))
+                .unwrap_err()
+                .to_string(),
+            "`max` is 20, but a proportion must be between 0 and 1"
+        );
+        assert_eq!(
+            validate(Scale::Percent, Some(50.0), Some(20.0)),
+            Err(Error::Inverted {
+                min: 50.0,
+                max: 20.0
+            })
+        );
+
+        assert_eq!(
+            describe(Scale::Percent, Some(20.0), None),
+            "a percentage between 20 and 100"
+        );
+    }
+}
@@ -0,0 +1,351 @@
+//! Immunophenotypic features.
+//!
+//! An immunophenotypic characteristic declares the panel of markers that it
+//! assesses (e.g., `CD19`, `CD34`, and `TdT`), and a value records the
+//! expression level of each assessed marker (e.g., `CD19:bright, CD34:dim`).
+//!
+//! Markers are either [CD antigens] (e.g., `CD11b` or `CD45RA`) or one of a
+//! small set of common markers without a CD designation (see [`OTHER`]).
+//! Either can be prefixed with `cy` to denote cytoplasmic expression (e.g.,
+//! `cyCD3` or `cyMPO`).
+//!
+//! [CD antigens]: https://www.hcdm.org
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::string::ToString as _;
+use alloc::vec::Vec;
+
+/// The markers without a CD designation that can be used within a panel.
+pub const OTHER: [&str; 10] = [
+    "FMC7", "HLA-DR", "IgD", "IgM", "Kappa", "Lambda", "Lysozyme", "MPO", "NG2", "TdT",
+];
+
+/// An error when parsing a marker, an expression level, or a phenotype.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// An invalid marker.
+    Marker(String),
+
+    /// An unknown expression level.
+    Level(String),
+
+    /// An expression that is not a marker and a level separated by a colon.
+    Expression(String),
+
+    /// A marker that is listed more than once within a phenotype.
+    Duplicate(String),
+}
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParseError::Marker(marker) => write!(
+                f,
+                "invalid marker: `{marker}`; expected a CD antigen (e.g., `CD19`, `CD11b`, or \
+                 `CD45RA`) or one of {}, optionally prefixed with `cy`",
+                OTHER
+                    .iter()
+                    .map(|marker| alloc::format!("`{marker}`"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ParseError::Level(level) => write!(
+                f,
+                "unknown expression level: `{level}`; expected one of {}",
+                Level::ALL
+                    .iter()
+                    .map(|level| alloc::format!("`{}`", level.as_str()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ParseError::Expression(expression) => write!(
+                f,
+                "invalid marker expression: `{expression}`; expected a marker and an expression \
+                 level separated by a colon (e.g., `CD19:bright`)"
+            ),
+            ParseError::Duplicate(marker) => {
+                write!(f, "the marker `{marker}` is listed more than once")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// A marker within an immunophenotyping panel (e.g., `CD19` or `cyMPO`).
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub struct Marker(String);
+
+impl Marker {
+    /// Gets the marker as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Gets whether the marker is assessed within the cytoplasm (i.e., it is
+    /// prefixed with `cy`).
+    pub fn is_cytoplasmic(&self) -> bool {
+        self.0.starts_with("cy")
+    }
+
+    /// Gets the number of the CD antigen (or [`None`] if the marker does not
+    /// have a CD designation).
+    pub fn cd(&self) -> Option<u16> {
+        let antigen = self.0.strip_prefix("cy").unwrap_or(&self.0);
+        let digits = antigen.strip_prefix("CD")?;
+        let end = digits
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(digits.len());
+        digits[..end].parse().ok()
+    }
+}
+
+impl core::fmt::Display for Marker {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl core::str::FromStr for Marker {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let antigen = s.strip_prefix("cy").unwrap_or(s);
+
+        let valid = OTHER.contains(&antigen)
+            || antigen.strip_prefix("CD").is_some_and(|rest| {
+                let end = rest
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(rest.len());
+                let (number, suffix) = rest.split_at(end);
+
+                // NOTE: a CD antigen can have a lowercase letter for a member
+                // of a family (e.g., `CD11b`) and an uppercase isoform (e.g.,
+                // `CD45RA`).
+                let suffix = suffix
+                    .strip_prefix(|c: char| c.is_ascii_lowercase())
+                    .unwrap_or(suffix);
+                let isoform = suffix.is_empty()
+                    || (suffix.len() == 2
+                        && suffix.starts_with('R')
+                        && suffix.ends_with(|c: char| c.is_ascii_uppercase()));
+
+                !number.is_empty() && !number.starts_with('0') && isoform
+            });
+
+        if !valid {
+            return Err(ParseError::Marker(s.to_string()));
+        }
+
+        Ok(Self(s.to_string()))
+    }
+}
+
+/// The level at which a marker is expressed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub enum Level {
+    /// The marker is not expressed.
+    Negative,
+
+    /// The marker is expressed at a lower intensity than on its normal
+    /// counterpart.
+    Dim,
+
+    /// The marker is expressed at the intensity of its normal counterpart.
+    Moderate,
+
+    /// The marker is expressed at a higher intensity than on its normal
+    /// counterpart.
+    Bright,
+
+    /// The marker is expressed by only a subset of the population.
+    Partial,
+}
+
+impl Level {
+    /// All expression levels.
+    pub const ALL: [Level; 5] = [
+        Level::Negative,
+        Level::Dim,
+        Level::Moderate,
+        Level::Bright,
+        Level::Partial,
+    ];
+
+    /// Gets the name of the level (e.g., `bright`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Level::Negative => "negative",
+            Level::Dim => "dim",
+            Level::Moderate => "moderate",
+            Level::Bright => "bright",
+            Level::Partial => "partial",
+        }
+    }
+
+    /// Gets whether the marker is expressed at all.
+    pub fn is_expressed(&self) -> bool {
+        *self != Level::Negative
+    }
+}
+
+impl core::fmt::Display for Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl core::str::FromStr for Level {
+    type Err = ParseError;
+
+    /// Parses a level from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Level::ALL
+            .into_iter()
+            .find(|level| level.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError::Level(s.to_string()))
+    }
+}
+
+/// The expression levels of the markers assessed for a sample.
+///
+/// A phenotype is written as comma-separated pairs of a marker and its level
+/// (e.g., `CD19:bright, CD10:dim, CD34:negative`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Phenotype(Vec<(Marker, Level)>);
+
+impl Phenotype {
+    /// Gets the markers and their levels (in the order they are written).
+    pub fn iter(&self) -> impl Iterator<Item = (&Marker, Level)> {
+        self.0.iter().map(|(marker, level)| (marker, *level))
+    }
+
+    /// Gets the level of a marker (or [`None`] if it was not assessed).
+    pub fn level(&self, marker: &Marker) -> Option<Level> {
+        self.0
+            .iter()
+            .find(|(candidate, _)| candidate == marker)
+            .map(|(_, level)| *level)
+    }
+
+    /// Gets the markers that are outside of a panel.
+    pub fn outside<'a>(&'a self, panel: &'a BTreeSet<Marker>) -> impl Iterator<Item = &'a Marker> {
+        self.0
+            .iter()
+            .map(|(marker, _)| marker)
+            .filter(|marker| !panel.contains(*marker))
+    }
+}
+
+impl core::fmt::Display for Phenotype {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        for (i, (marker, level)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+
+            write!(f, "{marker}:{level}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl core::str::FromStr for Phenotype {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut expressions: Vec<(Marker, Level)> = Vec::new();
+
+        for expression in s.split(',').map(str::trim) {
+            let (marker, level) = expression
+                .split_once(':')
+                .ok_or_else(|| ParseError::Expression(expression.to_string()))?;
+            let marker = marker.trim().parse::<Marker>()?;
+            let level = level.trim().parse::<Level>()?;
+
+            if expressions.iter().any(|(existing, _)| *existing == marker) {
+                return Err(ParseError::Duplicate(marker.to_string()));
+            }
+
+            expressions.push((marker, level));
+        }
+
+        Ok(Self(expressions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn marker() {
+        for marker in [
+            "CD19", "CD11b", "CD45RA", "cyCD3", "cyCD79a", "HLA-DR", "cyMPO", "TdT",
+        ] {
+            assert_eq!(marker.parse::<Marker>().unwrap().as_str(), marker);
+        }
+
+        for marker in [
+            "CD", "CD019", "cd19", "CD19AB", "CD45R", "CD45ra", "HLADR", "cy",
+        ] {
+            assert_eq!(
+                marker.parse::<Marker>(),
+                Err(ParseError::Marker(marker.to_string()))
+            );
+        }
+
+        let marker = "cyCD79a".parse::<Marker>().unwrap();
+        assert!(marker.is_cytoplasmic());
+        assert_eq!(marker.cd(), Some(79));
+        assert_eq!("MPO".parse::<Marker>().unwrap().cd(), None);
+    }
+
+    #[test]
+    fn phenotype() {
+        let phenotype = "CD19:bright, CD10:Dim,CD34:negative"
+            .parse::<Phenotype>()
+            .unwrap();
+        assert_eq!(phenotype.level(&"CD10".parse().unwrap()), Some(Level::Dim));
+        assert_eq!(phenotype.level(&"CD20".parse().unwrap()), None);
+        assert_eq!(
+            phenotype.to_string(),
+            "CD19:bright, CD10:dim, CD34:negative"
+        );
+
+        let panel = ["CD19", "CD34"]
+            .into_iter()
+            .map(|marker| marker.parse().unwrap())
+            .collect::<BTreeSet<Marker>>();
+        assert_eq!(
+            phenotype
+                .outside(&panel)
+                .map(Marker::as_str)
+                .collect::<Vec<_>>(),
+            ["CD10"]
+        );
+
+        assert_eq!(
+            "CD19 bright".parse::<Phenotype>(),
+            Err(ParseError::Expression(String::from("CD19 bright")))
+        );
+        assert_eq!(
+            "CD19:high".parse::<Phenotype>(),
+            Err(ParseError::Level(String::from("high")))
+        );
+        assert_eq!(
+            "CD19:dim, CD19:bright".parse::<Phenotype>(),
+            Err(ParseError::Duplicate(String::from("CD19")))
+        );
+    }
+}
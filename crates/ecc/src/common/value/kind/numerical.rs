@@ -1,11 +1,12 @@
 //! Numerical features.
 
-use serde::Deserialize;
-use serde::Serialize;
-
 /// A numerical feature type.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "lowercase")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "lowercase")
+)]
 pub enum Type {
     /// An signed integer.
     Signed,
@@ -16,3 +17,13 @@ pub enum Type {
     /// A float.
     Float,
 }
+
+impl core::fmt::Display for Type {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Type::Signed => write!(f, "signed"),
+            Type::Unsigned => write!(f, "unsigned"),
+            Type::Float => write!(f, "float"),
+        }
+    }
+}
@@ -0,0 +1,185 @@
+//! Scoring how complete the documentation of a characteristic is.
+//!
+//! A characteristic can match the schema while still being thinly documented
+//! (e.g., a draft without references). The completeness of a characteristic is
+//! the fraction of the applicable [`Criterion`]s that it meets, which lets
+//! curators prioritize the characteristics that need the most work and lets
+//! repositories require a minimum score before a characteristic can advance.
+
+use serde::Serialize;
+
+use crate::Characteristic;
+use crate::common::value::Kind;
+
+/// A criterion that a well-documented characteristic meets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Criterion {
+    /// Every field that is required outside of the `draft` phase is filled in
+    /// (see [`crate::common::MissingField`]).
+    RequiredFields,
+
+    /// At least one reference is listed.
+    References,
+
+    /// The meaning of each permissible value is described (the summary and
+    /// details of each binary value or the units of a numerical value).
+    ///
+    /// The options of a categorical characteristic are their own labels, so
+    /// this criterion does not apply to them.
+    ValueDescriptions,
+}
+
+impl Criterion {
+    /// All criteria.
+    pub const ALL: [Criterion; 3] = [
+        Criterion::RequiredFields,
+        Criterion::References,
+        Criterion::ValueDescriptions,
+    ];
+
+    /// Gets the name of the criterion (e.g., `value-descriptions`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Criterion::RequiredFields => "required-fields",
+            Criterion::References => "references",
+            Criterion::ValueDescriptions => "value-descriptions",
+        }
+    }
+
+    /// Checks whether a characteristic meets the criterion.
+    ///
+    /// Returns [`None`] if the criterion does not apply to the characteristic.
+    fn check(&self, characteristic: &Characteristic) -> Option<bool> {
+        match self {
+            Criterion::RequiredFields => Some(match characteristic {
+                Characteristic::Draft { common } => common.missing_fields().is_empty(),
+                _ => true,
+            }),
+            Criterion::References => Some(characteristic.references().is_some()),
+            Criterion::ValueDescriptions => match characteristic.values() {
+                Some(Kind::Categorical { .. }) => None,
+                // NOTE: the expression levels are defined by the schema.
+                Some(Kind::Binary { .. } | Kind::Immunophenotype { .. }) => Some(true),
+                Some(Kind::Numerical { units, .. }) => Some(!units.trim().is_empty()),
+                Some(Kind::Fraction {
+                    denominator_description,
+                    ..
+                }) => Some(!denominator_description.trim().is_empty()),
+                // NOTE: values that are not modeled by the schema cannot be
+                // described value by value.
+                Some(Kind::Other { .. }) | None => Some(false),
+            },
+        }
+    }
+}
+
+impl std::fmt::Display for Criterion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// The completeness of a characteristic.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Score {
+    /// The applicable criteria that the characteristic meets.
+    met: Vec<Criterion>,
+
+    /// The applicable criteria that the characteristic does not meet.
+    missing: Vec<Criterion>,
+}
+
+impl Score {
+    /// Scores a characteristic against every applicable criterion.
+    pub fn of(characteristic: &Characteristic) -> Self {
+        let mut score = Self {
+            met: Vec::new(),
+            missing: Vec::new(),
+        };
+
+        for criterion in Criterion::ALL {
+            match criterion.check(characteristic) {
+                Some(true) => score.met.push(criterion),
+                Some(false) => score.missing.push(criterion),
+                None => {}
+            }
+        }
+
+        score
+    }
+
+    /// Gets the criteria that the characteristic meets.
+    pub fn met(&self) -> &[Criterion] {
+        &self.met
+    }
+
+    /// Gets the criteria that the characteristic does not meet.
+    pub fn missing(&self) -> &[Criterion] {
+        &self.missing
+    }
+
+    /// Gets the fraction of the applicable criteria that are met (between `0`
+    /// and `1`).
+    pub fn value(&self) -> f64 {
+        let total = self.met.len() + self.missing.len();
+
+        if total == 0 {
+            return 1.0;
+        }
+
+        self.met.len() as f64 / total as f64
+    }
+
+    /// Returns whether every applicable criterion is met.
+    pub fn is_complete(&self) -> bool {
+        self.missing.is_empty()
+    }
+}
+
+impl std::fmt::Display for Score {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:.2}", self.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn score() {
+        let characteristic = serde_yaml::from_str::<Characteristic>(
+            "state: draft
+name: A Characteristic Name
+values:
+  kind: numerical
+  type: unsigned
+  units: Cells
+",
+        )
+        .unwrap();
+
+        let score = characteristic.completeness();
+        assert_eq!(score.met(), [Criterion::ValueDescriptions]);
+        assert_eq!(
+            score.missing(),
+            [Criterion::RequiredFields, Criterion::References]
+        );
+        assert_eq!(score.to_string(), "0.33");
+
+        let characteristic = serde_yaml::from_str::<Characteristic>(
+            "state: draft
+values:
+  kind: categorical
+  options: [Foo, Bar]
+",
+        )
+        .unwrap();
+
+        let score = characteristic.completeness();
+        assert!(score.met().is_empty());
+        assert_eq!(score.value(), 0.0);
+        assert!(!score.is_complete());
+    }
+}
@@ -0,0 +1,292 @@
+//! Manifests describing how the columns of a dataset map to characteristics.
+//!
+//! A manifest is written in YAML alongside a dataset (e.g., a cohort metadata
+//! file) and records which characteristic each column holds, the units each
+//! numerical column was recorded in (when they differ from the units declared
+//! by the characteristic), the codes that stand in for missing values, and the
+//! raw values that should be recoded into permissible values:
+//!
+//! ```yaml
+//! missing: [NA, unknown]
+//! columns:
+//!   - column: Diagnosis
+//!     identifier: ECC-MORPH-000001
+//!   - column: Age
+//!     identifier: ECC-MORPH-000002
+//!     units: Months
+//!     missing: ["-1"]
+//!   - column: Sex
+//!     identifier: ECC-MORPH-000003
+//!     recode:
+//!       M: Male
+//!       F: Female
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+use nonempty::NonEmpty;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::CharacteristicSet;
+use crate::Identifier;
+use crate::common::value::Kind;
+use crate::common::value::kind::immunophenotype::Phenotype;
+
+/// An error related to a dataset manifest.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// A column is described more than once.
+    #[error("column `{0}` is described more than once")]
+    DuplicateColumn(String),
+
+    /// More than one column maps to the same characteristic.
+    #[error("characteristic `{0}` is mapped to by more than one column")]
+    DuplicateIdentifier(Identifier),
+
+    /// A column maps to a characteristic that does not exist.
+    #[error("column `{column}` maps to an unknown characteristic: `{identifier}`")]
+    UnknownCharacteristic {
+        /// The column.
+        column: String,
+
+        /// The identifier.
+        identifier: Identifier,
+    },
+
+    /// A column overrides the units of a characteristic that is not numerical.
+    #[error("column `{column}` overrides the units of non-numerical characteristic `{identifier}`")]
+    UnitsOverride {
+        /// The column.
+        column: String,
+
+        /// The identifier.
+        identifier: Identifier,
+    },
+
+    /// A column recodes a value into something that is not a permissible
+    /// value of the characteristic.
+    #[error("column `{column}` recodes `{from}` into `{to}`, which is not a permissible value")]
+    InvalidRecode {
+        /// The column.
+        column: String,
+
+        /// The raw value.
+        from: String,
+
+        /// The value it is recoded into.
+        to: String,
+    },
+}
+
+/// The mapping of a single column to a characteristic.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Column {
+    /// The column header.
+    pub column: String,
+
+    /// The identifier of the characteristic held within the column.
+    pub identifier: Identifier,
+
+    /// The units the values were recorded in (if they differ from the units
+    /// declared by the characteristic).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub units: Option<String>,
+
+    /// The codes that stand in for missing values within this column (in
+    /// addition to the codes for the whole dataset).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+
+    /// The raw values within this column that should be recoded into
+    /// permissible values of the characteristic (e.g., synonyms).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub recode: BTreeMap<String, String>,
+}
+
+impl Column {
+    /// Creates a new column mapping.
+    pub fn new(column: impl Into<String>, identifier: Identifier) -> Self {
+        Self {
+            column: column.into(),
+            identifier,
+            units: None,
+            missing: Vec::new(),
+            recode: BTreeMap::new(),
+        }
+    }
+}
+
+/// A manifest describing how the columns of a dataset map to characteristics.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DatasetManifest {
+    /// The codes that stand in for missing values within every column.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing: Vec<String>,
+
+    /// The column mappings.
+    #[serde(default)]
+    pub columns: Vec<Column>,
+}
+
+impl DatasetManifest {
+    /// Gets the mapping for a column (if the column is mapped).
+    pub fn column(&self, header: &str) -> Option<&Column> {
+        self.columns.iter().find(|column| column.column == header)
+    }
+
+    /// Returns whether a value within a column is a missing value code.
+    ///
+    /// Empty values are always considered missing.
+    pub fn is_missing(&self, header: &str, value: &str) -> bool {
+        value.is_empty()
+            || self.missing.iter().any(|code| code == value)
+            || self
+                .column(header)
+                .is_some_and(|column| column.missing.iter().any(|code| code == value))
+    }
+
+    /// Validates the manifest against a set of characteristics.
+    ///
+    /// Every problem is reported rather than only the first.
+    pub fn validate_against(
+        &self,
+        characteristics: &CharacteristicSet,
+    ) -> Result<(), NonEmpty<Error>> {
+        let mut errors = Vec::new();
+        let mut headers = HashSet::new();
+        let mut identifiers = HashSet::new();
+
+        for column in &self.columns {
+            if !headers.insert(column.column.as_str()) {
+                errors.push(Error::DuplicateColumn(column.column.clone()));
+            }
+
+            if !identifiers.insert(&column.identifier) {
+                errors.push(Error::DuplicateIdentifier(column.identifier.clone()));
+            }
+
+            let Some(characteristic) = characteristics.get(&column.identifier) else {
+                errors.push(Error::UnknownCharacteristic {
+                    column: column.column.clone(),
+                    identifier: column.identifier.clone(),
+                });
+                continue;
+            };
+
+            if column.units.is_some()
+                && !matches!(
+                    characteristic.values(),
+                    Some(Kind::Numerical { .. } | Kind::Fraction { .. })
+                )
+            {
+                errors.push(Error::UnitsOverride {
+                    column: column.column.clone(),
+                    identifier: column.identifier.clone(),
+                });
+            }
+
+            for (from, to) in &column.recode {
+                let permissible = match characteristic.values() {
+                    Some(Kind::Binary { .. }) => matches!(to.as_str(), "true" | "false"),
+                    Some(Kind::Categorical { options }) => options.contains(to),
+                    Some(Kind::Immunophenotype { markers }) => to
+                        .parse::<Phenotype>()
+                        .is_ok_and(|phenotype| phenotype.outside(markers).next().is_none()),
+                    Some(Kind::Numerical { .. } | Kind::Fraction { .. } | Kind::Other { .. })
+                    | None => false,
+                };
+
+                if !permissible {
+                    errors.push(Error::InvalidRecode {
+                        column: column.column.clone(),
+                        from: from.clone(),
+                        to: to.clone(),
+                    });
+                }
+            }
+        }
+
+        match NonEmpty::from_vec(errors) {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Characteristic;
+
+    fn characteristics() -> CharacteristicSet {
+        let characteristics: Vec<Characteristic> = serde_yaml::from_str(
+            r#"- state: proposed
+  identifier: ECC-MORPH-000001
+  name: Foo Bar
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: categorical
+    options: [Foo, Bar]"#,
+        )
+        .unwrap();
+
+        characteristics.into_iter().collect()
+    }
+
+    #[test]
+    fn parse() {
+        let manifest: DatasetManifest = serde_yaml::from_str(
+            r#"missing: [NA]
+columns:
+  - column: Diagnosis
+    identifier: ECC-MORPH-000001
+    missing: [unknown]"#,
+        )
+        .unwrap();
+
+        assert!(manifest.validate_against(&characteristics()).is_ok());
+        assert!(manifest.is_missing("Diagnosis", "NA"));
+        assert!(manifest.is_missing("Diagnosis", "unknown"));
+        assert!(manifest.is_missing("Other", ""));
+        assert!(!manifest.is_missing("Other", "unknown"));
+    }
+
+    #[test]
+    fn invalid() {
+        let manifest: DatasetManifest = serde_yaml::from_str(
+            r#"columns:
+  - column: Diagnosis
+    identifier: ECC-MORPH-000001
+    units: Days
+    recode:
+      foo: Foo
+      baz: Baz
+  - column: Diagnosis
+    identifier: ECC-MORPH-000002"#,
+        )
+        .unwrap();
+
+        let errors = manifest
+            .validate_against(&characteristics())
+            .unwrap_err()
+            .into_iter()
+            .map(|err| err.to_string())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            errors,
+            vec![
+                "column `Diagnosis` overrides the units of non-numerical characteristic \
+                 `ECC-MORPH-000001`",
+                "column `Diagnosis` recodes `baz` into `Baz`, which is not a permissible value",
+                "column `Diagnosis` is described more than once",
+                "column `Diagnosis` maps to an unknown characteristic: `ECC-MORPH-000002`",
+            ]
+        );
+    }
+}
@@ -0,0 +1,369 @@
+//! Differences between two sets of characteristics.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use crate::Characteristic;
+use crate::Identifier;
+use crate::State;
+use crate::common::value::Kind;
+
+/// A change to a characteristic between two sets of characteristics.
+#[derive(Debug, PartialEq)]
+pub enum Change<'a> {
+    /// A characteristic that only exists in the newer set.
+    Added(&'a Characteristic),
+
+    /// A characteristic that only exists in the older set.
+    Removed(&'a Characteristic),
+
+    /// A characteristic that exists in both sets but was modified.
+    Modified {
+        /// The characteristic as it existed in the older set.
+        before: &'a Characteristic,
+
+        /// The characteristic as it exists in the newer set.
+        after: &'a Characteristic,
+
+        /// The modifications that were made.
+        modifications: Vec<Modification<'a>>,
+    },
+}
+
+/// A modification made to a single characteristic.
+#[derive(Debug, PartialEq)]
+pub enum Modification<'a> {
+    /// The characteristic transitioned from one state to another.
+    State {
+        /// The state before the change.
+        from: State,
+
+        /// The state after the change.
+        to: State,
+    },
+
+    /// The identifier was assigned or changed.
+    Identifier {
+        /// The identifier before the change.
+        from: Option<&'a Identifier>,
+
+        /// The identifier after the change.
+        to: Option<&'a Identifier>,
+    },
+
+    /// The name was changed.
+    Name {
+        /// The name before the change.
+        from: Option<&'a str>,
+
+        /// The name after the change.
+        to: Option<&'a str>,
+    },
+
+    /// The description was changed.
+    Description,
+
+    /// The permissible values were changed.
+    Values(ValueChange<'a>),
+
+    /// The references were changed.
+    References,
+}
+
+/// A change to the permissible values of a characteristic.
+#[derive(Debug, PartialEq)]
+pub enum ValueChange<'a> {
+    /// The kind of permissible values was changed (or the values were added to
+    /// or removed from a draft).
+    Kind {
+        /// The kind before the change.
+        from: Option<&'a Kind>,
+
+        /// The kind after the change.
+        to: Option<&'a Kind>,
+    },
+
+    /// Options were added to or removed from a categorical value set.
+    Options {
+        /// The options that were added (sorted).
+        added: Vec<&'a str>,
+
+        /// The options that were removed (sorted).
+        removed: Vec<&'a str>,
+    },
+
+    /// The values were changed in some other way, such as the descriptions of
+    /// a binary value or the units of a numerical value.
+    Details,
+}
+
+/// Computes the changes made to the permissible values.
+fn values<'a>(before: Option<&'a Kind>, after: Option<&'a Kind>) -> Option<ValueChange<'a>> {
+    match (before, after) {
+        (Some(before), Some(after)) if before == after => None,
+        (
+            Some(Kind::Categorical { options: before }),
+            Some(Kind::Categorical { options: after }),
+        ) => {
+            let added = after
+                .difference(before)
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+            let removed = before
+                .difference(after)
+                .map(String::as_str)
+                .collect::<Vec<_>>();
+
+            Some(ValueChange::Options { added, removed })
+        }
+        (Some(before), Some(after)) if before.name() == after.name() => Some(ValueChange::Details),
+        (None, None) => None,
+        (from, to) => Some(ValueChange::Kind { from, to }),
+    }
+}
+
+/// Computes the modifications between two versions of a characteristic.
+fn modifications<'a>(
+    before: &'a Characteristic,
+    after: &'a Characteristic,
+) -> Vec<Modification<'a>> {
+    let mut result = Vec::new();
+
+    if before.state() != after.state() {
+        result.push(Modification::State {
+            from: before.state(),
+            to: after.state(),
+        });
+    }
+
+    if before.identifier() != after.identifier() {
+        result.push(Modification::Identifier {
+            from: before.identifier(),
+            to: after.identifier(),
+        });
+    }
+
+    if before.name() != after.name() {
+        result.push(Modification::Name {
+            from: before.name(),
+            to: after.name(),
+        });
+    }
+
+    if before.description() != after.description() {
+        result.push(Modification::Description);
+    }
+
+    if let Some(change) = values(before.values(), after.values()) {
+        result.push(Modification::Values(change));
+    }
+
+    let before_references = before.references().map(|r| r.collect::<Vec<_>>());
+    let after_references = after.references().map(|r| r.collect::<Vec<_>>());
+
+    if before_references != after_references {
+        result.push(Modification::References);
+    }
+
+    result
+}
+
+/// Computes the changes between an older and a newer set of characteristics.
+///
+/// Characteristics are first matched by their identifiers. Any characteristics
+/// that remain unmatched (for example, drafts that have not yet been assigned
+/// an identifier) are then matched by name. Added and modified characteristics
+/// are returned in the order they appear in `after`, followed by removed
+/// characteristics in the order they appear in `before`.
+pub fn diff<'a>(before: &'a [Characteristic], after: &'a [Characteristic]) -> Vec<Change<'a>> {
+    let mut matches = vec![None; after.len()];
+    let mut matched = HashSet::new();
+
+    let by_identifier = before
+        .iter()
+        .enumerate()
+        .filter_map(|(i, c)| c.identifier().map(|identifier| (identifier, i)))
+        .collect::<HashMap<_, _>>();
+
+    for (i, characteristic) in after.iter().enumerate() {
+        if let Some(j) = characteristic
+            .identifier()
+            .and_then(|identifier| by_identifier.get(identifier))
+        {
+            matches[i] = Some(*j);
+            matched.insert(*j);
+        }
+    }
+
+    let by_name = before
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !matched.contains(i))
+        .filter_map(|(i, c)| c.name().map(|name| (name, i)))
+        .collect::<HashMap<_, _>>();
+
+    for (i, characteristic) in after.iter().enumerate() {
+        if matches[i].is_some() {
+            continue;
+        }
+
+        if let Some(j) = characteristic.name().and_then(|name| by_name.get(name)) {
+            if matched.insert(*j) {
+                matches[i] = Some(*j);
+            }
+        }
+    }
+
+    let mut changes = Vec::new();
+
+    for (characteristic, j) in after.iter().zip(matches) {
+        match j {
+            Some(j) => {
+                let modifications = modifications(&before[j], characteristic);
+
+                if !modifications.is_empty() {
+                    changes.push(Change::Modified {
+                        before: &before[j],
+                        after: characteristic,
+                        modifications,
+                    });
+                }
+            }
+            None => changes.push(Change::Added(characteristic)),
+        }
+    }
+
+    changes.extend(
+        before
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !matched.contains(i))
+            .map(|(_, characteristic)| Change::Removed(characteristic)),
+    );
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Vec<Characteristic> {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn unchanged() {
+        let before = parse(
+            r#"- state: draft
+  name: Foo Bar"#,
+        );
+
+        assert!(diff(&before, &before).is_empty());
+    }
+
+    #[test]
+    fn added_and_removed() {
+        let before = parse(
+            r#"- state: draft
+  name: Foo Bar"#,
+        );
+        let after = parse(
+            r#"- state: draft
+  name: Baz Quux"#,
+        );
+
+        let changes = diff(&before, &after);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0], Change::Added(&after[0]));
+        assert_eq!(changes[1], Change::Removed(&before[0]));
+    }
+
+    #[test]
+    fn state_transition() {
+        let before = parse(
+            r#"- state: draft
+  name: Foo Bar
+  values:
+    kind: categorical
+    options: [foo, bar]"#,
+        );
+        let after = parse(
+            r#"- state: proposed
+  name: Foo Bar
+  identifier: ECC-MORPH-000001
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: Foo bar baz
+  values:
+    kind: categorical
+    options: [foo, baz, quux]"#,
+        );
+
+        let changes = diff(&before, &after);
+        assert_eq!(changes.len(), 1);
+
+        let Change::Modified { modifications, .. } = &changes[0] else {
+            panic!("expected a modification");
+        };
+
+        assert_eq!(
+            modifications[0],
+            Modification::State {
+                from: State::Draft,
+                to: State::Proposed
+            }
+        );
+        assert!(matches!(
+            modifications[1],
+            Modification::Identifier { from: None, .. }
+        ));
+        assert_eq!(modifications[2], Modification::Description);
+        assert_eq!(
+            modifications[3],
+            Modification::Values(ValueChange::Options {
+                added: vec!["baz", "quux"],
+                removed: vec!["bar"],
+            })
+        );
+    }
+
+    #[test]
+    fn kind_change() {
+        let before = parse(
+            r#"- state: draft
+  identifier: ECC-MOLEC-000001
+  values:
+    kind: categorical
+    options: [foo, bar]"#,
+        );
+        let after = parse(
+            r#"- state: draft
+  identifier: ECC-MOLEC-000001
+  name: Foo Bar
+  values:
+    kind: numerical
+    type: float
+    units: TPM"#,
+        );
+
+        let changes = diff(&before, &after);
+        let Change::Modified { modifications, .. } = &changes[0] else {
+            panic!("expected a modification");
+        };
+
+        assert_eq!(
+            modifications[0],
+            Modification::Name {
+                from: None,
+                to: Some("Foo Bar")
+            }
+        );
+        assert!(matches!(
+            modifications[1],
+            Modification::Values(ValueChange::Kind {
+                from: Some(Kind::Categorical { .. }),
+                to: Some(Kind::Numerical { .. })
+            })
+        ));
+    }
+}
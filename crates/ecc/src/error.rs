@@ -0,0 +1,212 @@
+//! Errors for the crate as a whole.
+//!
+//! Each module reports problems with its own error type (e.g.,
+//! [`identifier::ParseError`](crate::identifier::ParseError)). [`Error`] wraps
+//! every one of them so that consumers can handle any error from the crate
+//! with a single type and match on the kind of problem.
+//!
+//! Every kind of error also has a stable [`Code`] (e.g., `E0002`) that is
+//! included in diagnostics. Codes are never renumbered or reused, so they can
+//! be referred to in documentation and matched on by other tools.
+
+use crate::annotate;
+use crate::category;
+use crate::common;
+use crate::dataset;
+use crate::harmonize;
+use crate::identifier;
+use crate::io;
+use crate::io::Segment;
+use crate::migrate;
+use crate::query;
+use crate::rfc;
+use crate::templates;
+use crate::text::language;
+use crate::text::sentence;
+use crate::value_map;
+
+/// A stable code identifying a kind of error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Code(u16);
+
+impl Code {
+    /// A set of annotations was invalid.
+    pub const ANNOTATE: Code = Code(11);
+    /// A category could not be parsed.
+    pub const CATEGORY: Code = Code(1);
+    /// A dataset description was invalid.
+    pub const DATASET: Code = Code(12);
+    /// A value could not be harmonized.
+    pub const HARMONIZE: Code = Code(13);
+    /// An identifier could not be parsed.
+    pub const IDENTIFIER: Code = Code(2);
+    /// A file could not be read.
+    pub const IO: Code = Code(7);
+    /// A language tag could not be parsed.
+    pub const LANGUAGE: Code = Code(16);
+    /// An RFC link could not be parsed.
+    pub const LINK: Code = Code(3);
+    /// A document could not be migrated to the current schema version.
+    pub const MIGRATE: Code = Code(10);
+    /// A filter could not be parsed.
+    pub const QUERY: Code = Code(15);
+    /// A document was valid YAML but did not match the schema.
+    pub const SCHEMA: Code = Code(9);
+    /// A sentence could not be parsed.
+    pub const SENTENCE: Code = Code(4);
+    /// A document was not valid YAML.
+    pub const SYNTAX: Code = Code(8);
+    /// A template could not be parsed.
+    pub const TEMPLATE: Code = Code(5);
+    /// A text field (e.g., the name) was empty or had surrounding whitespace.
+    pub const TEXT: Code = Code(6);
+    /// A value map was invalid.
+    pub const VALUE_MAP: Code = Code(14);
+
+    /// Gets the number of the code.
+    pub fn number(&self) -> u16 {
+        self.0
+    }
+
+    /// Gets the code for a schema violation at a field within a
+    /// characteristic.
+    ///
+    /// Fields whose values are parsed from a string (e.g., `identifier`)
+    /// report the code of the corresponding parse error. Any other field
+    /// reports [`Code::SCHEMA`].
+    pub fn for_field(path: &io::Path) -> Code {
+        let key = path
+            .segments()
+            .iter()
+            .rev()
+            .find_map(|segment| match segment {
+                Segment::Key(key) => Some(key.as_str()),
+                Segment::Index(_) => None,
+            });
+
+        match key {
+            Some("identifier") => Code::IDENTIFIER,
+            Some("rfc") => Code::LINK,
+            Some("summary" | "details" | "context") => Code::SENTENCE,
+            Some("schema_version") => Code::MIGRATE,
+            Some("translations") => Code::LANGUAGE,
+            _ => Code::SCHEMA,
+        }
+    }
+}
+
+impl std::fmt::Display for Code {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{:04}", self.0)
+    }
+}
+
+/// An error from any part of the crate.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// A category could not be parsed.
+    #[error(transparent)]
+    Category(#[from] category::ParseError),
+
+    /// An identifier could not be parsed.
+    #[error(transparent)]
+    Identifier(#[from] identifier::ParseError),
+
+    /// An RFC link could not be parsed.
+    #[error(transparent)]
+    Link(#[from] rfc::ParseError),
+
+    /// A sentence could not be parsed.
+    #[error(transparent)]
+    Sentence(#[from] sentence::ParseError),
+
+    /// A language tag could not be parsed.
+    #[error(transparent)]
+    Language(#[from] language::ParseError),
+
+    /// A template could not be parsed.
+    #[error(transparent)]
+    Template(#[from] templates::ParseError),
+
+    /// A text field was invalid.
+    #[error(transparent)]
+    Text(#[from] common::Error),
+
+    /// A characteristic could not be read.
+    #[error(transparent)]
+    Io(#[from] io::Error),
+
+    /// A document could not be migrated.
+    #[error(transparent)]
+    Migrate(#[from] migrate::Error),
+
+    /// A set of annotations was invalid.
+    #[error(transparent)]
+    Annotate(#[from] annotate::Error),
+
+    /// A dataset description was invalid.
+    #[error(transparent)]
+    Dataset(#[from] dataset::Error),
+
+    /// A value could not be harmonized.
+    #[error(transparent)]
+    Harmonize(#[from] harmonize::Error),
+
+    /// A value map was invalid.
+    #[error(transparent)]
+    ValueMap(#[from] value_map::Error),
+
+    /// A filter could not be parsed.
+    #[error(transparent)]
+    Query(#[from] query::ParseError),
+}
+
+impl Error {
+    /// Gets the code for the kind of error.
+    pub fn code(&self) -> Code {
+        match self {
+            Error::Category(_) => Code::CATEGORY,
+            Error::Identifier(_) => Code::IDENTIFIER,
+            Error::Link(_) => Code::LINK,
+            Error::Sentence(_) => Code::SENTENCE,
+            Error::Language(_) => Code::LANGUAGE,
+            Error::Template(_) => Code::TEMPLATE,
+            Error::Text(_) => Code::TEXT,
+            Error::Io(err) => err.code(),
+            Error::Migrate(_) => Code::MIGRATE,
+            Error::Annotate(_) => Code::ANNOTATE,
+            Error::Dataset(_) => Code::DATASET,
+            Error::Harmonize(_) => Code::HARMONIZE,
+            Error::ValueMap(_) => Code::VALUE_MAP,
+            Error::Query(_) => Code::QUERY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Identifier;
+
+    #[test]
+    fn display() {
+        assert_eq!(Code::IDENTIFIER.to_string(), "E0002");
+        assert_eq!(Code::VALUE_MAP.to_string(), "E0014");
+    }
+
+    #[test]
+    fn conversions() {
+        let err = Error::from("ECC-MORPH-1".parse::<Identifier>().unwrap_err());
+        assert!(matches!(err, Error::Identifier(_)));
+        assert_eq!(err.code(), Code::IDENTIFIER);
+
+        let err = Error::from(crate::io::from_str("state: [").unwrap_err());
+        assert_eq!(err.code(), Code::SYNTAX);
+
+        let err = Error::from(crate::io::from_str("state: draft\nidentifier: 1\n").unwrap_err());
+        assert_eq!(err.code(), Code::IDENTIFIER);
+
+        let err = Error::from(crate::io::from_str("state: unknown\n").unwrap_err());
+        assert_eq!(err.code(), Code::SCHEMA);
+    }
+}
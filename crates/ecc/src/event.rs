@@ -0,0 +1,256 @@
+//! Events recording the lifecycle transitions of characteristics.
+//!
+//! Each time a characteristic advances through its lifecycle, an [`Event`] is
+//! appended to an event log in the [JSON Lines](https://jsonlines.org) format
+//! (conventionally named [`FILE_NAME`]). The log is append-only, so downstream
+//! systems can follow changes to the registry by reading the events they have
+//! not yet seen rather than diffing the whole tree.
+//!
+//! ```
+//! use ecc::event::Event;
+//!
+//! let line = r#"{"event":"adopted","identifier":"ECC-MORPH-000001","date":"2024-01-01T00:00:00Z","rfc":"https://github.com/stjudecloud/ecc/issues/1"}"#;
+//! let events = ecc::event::read(line.as_bytes()).unwrap();
+//!
+//! assert!(matches!(events[0], Event::Adopted { .. }));
+//! assert_eq!(events[0].identifier().to_string(), "ECC-MORPH-000001");
+//! ```
+
+use std::io::BufRead;
+use std::io::Write;
+
+use chrono::DateTime;
+use chrono::Utc;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Characteristic;
+use crate::Identifier;
+use crate::Link;
+use crate::State;
+
+/// The conventional name of an event log.
+pub const FILE_NAME: &str = "events.jsonl";
+
+/// An error related to an event log.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// An error reading from or writing to the log.
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
+    /// A line of the log is not a valid event.
+    #[error("line {line}: {source}")]
+    Parse {
+        /// The line number (starting at one).
+        line: usize,
+
+        /// The underlying error.
+        source: serde_json::Error,
+    },
+
+    /// An event could not be serialized.
+    #[error("serializing event: {0}")]
+    Serialize(serde_json::Error),
+}
+
+/// A lifecycle transition of a characteristic.
+///
+/// Each event is named after the state that the characteristic entered.
+/// Drafts have not yet been assigned an identifier or an RFC, so there is no
+/// event for entering the `draft` state.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "lowercase", deny_unknown_fields)]
+pub enum Event {
+    /// A draft was proposed for adoption.
+    Proposed {
+        /// The identifier of the characteristic.
+        identifier: Identifier,
+
+        /// When the characteristic was proposed.
+        date: DateTime<Utc>,
+
+        /// The link to the RFC of the characteristic.
+        rfc: Link,
+    },
+
+    /// A proposal was accepted in principle.
+    Provisional {
+        /// The identifier of the characteristic.
+        identifier: Identifier,
+
+        /// When the proposal was accepted.
+        date: DateTime<Utc>,
+
+        /// The link to the RFC of the characteristic.
+        rfc: Link,
+    },
+
+    /// A characteristic was adopted.
+    Adopted {
+        /// The identifier of the characteristic.
+        identifier: Identifier,
+
+        /// When the characteristic was adopted.
+        date: DateTime<Utc>,
+
+        /// The link to the RFC of the characteristic.
+        rfc: Link,
+    },
+}
+
+impl Event {
+    /// Creates the event for a characteristic entering its current state.
+    ///
+    /// Returns [`None`] for drafts (see [`Event`]).
+    pub fn entered(characteristic: &Characteristic, date: DateTime<Utc>) -> Option<Self> {
+        let (Some(identifier), Some(rfc)) = (characteristic.identifier(), characteristic.rfc())
+        else {
+            return None;
+        };
+
+        let (identifier, rfc) = (identifier.clone(), rfc.clone());
+
+        match characteristic.state() {
+            State::Draft => None,
+            State::Proposed => Some(Event::Proposed {
+                identifier,
+                date,
+                rfc,
+            }),
+            State::Provisional => Some(Event::Provisional {
+                identifier,
+                date,
+                rfc,
+            }),
+            State::Adopted => Some(Event::Adopted {
+                identifier,
+                date,
+                rfc,
+            }),
+        }
+    }
+
+    /// Gets the state that the characteristic entered.
+    pub fn state(&self) -> State {
+        match self {
+            Event::Proposed { .. } => State::Proposed,
+            Event::Provisional { .. } => State::Provisional,
+            Event::Adopted { .. } => State::Adopted,
+        }
+    }
+
+    /// Gets the identifier of the characteristic.
+    pub fn identifier(&self) -> &Identifier {
+        match self {
+            Event::Proposed { identifier, .. }
+            | Event::Provisional { identifier, .. }
+            | Event::Adopted { identifier, .. } => identifier,
+        }
+    }
+
+    /// Gets when the transition happened.
+    pub fn date(&self) -> &DateTime<Utc> {
+        match self {
+            Event::Proposed { date, .. }
+            | Event::Provisional { date, .. }
+            | Event::Adopted { date, .. } => date,
+        }
+    }
+
+    /// Gets the link to the RFC of the characteristic.
+    pub fn rfc(&self) -> &Link {
+        match self {
+            Event::Proposed { rfc, .. }
+            | Event::Provisional { rfc, .. }
+            | Event::Adopted { rfc, .. } => rfc,
+        }
+    }
+}
+
+/// Appends an event to a log as a single line.
+pub fn append(mut writer: impl Write, event: &Event) -> Result<(), Error> {
+    let line = serde_json::to_string(event).map_err(Error::Serialize)?;
+    writeln!(writer, "{line}")?;
+    Ok(())
+}
+
+/// Reads every event within a log (in the order they were appended).
+///
+/// Blank lines are skipped.
+pub fn read(reader: impl BufRead) -> Result<Vec<Event>, Error> {
+    let mut events = Vec::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let event = serde_json::from_str(&line).map_err(|source| Error::Parse {
+            line: i + 1,
+            source,
+        })?;
+        events.push(event);
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets an adopted characteristic.
+    fn adopted() -> Characteristic {
+        serde_yaml::from_str(
+            "state: adopted\nidentifier: ECC-MORPH-000001\nname: A Characteristic Name\nrfc: \
+             https://github.com/stjudecloud/ecc/issues/1\ndescription: A description of the \
+             characteristic.\nvalues:\n  kind: categorical\n  options: [Foo, Bar]\nadoption_date: \
+             2024-01-01T00:00:00Z\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn entered() {
+        let date = "2024-01-01T00:00:00Z".parse().unwrap();
+        let event = Event::entered(&adopted(), date).unwrap();
+
+        assert_eq!(event.state(), State::Adopted);
+        assert_eq!(event.identifier().to_string(), "ECC-MORPH-000001");
+        assert_eq!(event.date(), &date);
+
+        let draft = serde_yaml::from_str::<Characteristic>(
+            "state: draft\nidentifier: ECC-MORPH-000001\nrfc: \
+             https://github.com/stjudecloud/ecc/issues/1\n",
+        )
+        .unwrap();
+        assert_eq!(Event::entered(&draft, date), None);
+    }
+
+    #[test]
+    fn round_trip() {
+        let date = "2024-01-01T00:00:00Z".parse().unwrap();
+        let event = Event::entered(&adopted(), date).unwrap();
+
+        let mut log = Vec::new();
+        append(&mut log, &event).unwrap();
+        append(&mut log, &event).unwrap();
+
+        assert_eq!(
+            String::from_utf8(log.clone()).unwrap().lines().next(),
+            Some(
+                r#"{"event":"adopted","identifier":"ECC-MORPH-000001","date":"2024-01-01T00:00:00Z","rfc":"https://github.com/stjudecloud/ecc/issues/1"}"#
+            )
+        );
+        assert_eq!(read(log.as_slice()).unwrap(), [event.clone(), event]);
+
+        let err = read("\n{\"event\":\"retired\"}\n".as_bytes()).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("line 2: unknown variant `retired`")
+        );
+    }
+}
@@ -1,12 +1,10 @@
 //! Fields within the data model.
 
-use serde::Deserialize;
-use serde::Serialize;
-
 use crate::text::Sentence;
 
 /// A field description.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Description {
     /// A summary.
     ///
@@ -0,0 +1,755 @@
+//! Harmonization of observed values into canonical characteristic values.
+//!
+//! Given a [`DatasetManifest`] and the characteristics it refers to, raw values
+//! are converted as follows:
+//!
+//! * missing value codes become empty values,
+//! * values listed in a column's `recode` mapping are replaced,
+//! * binary values spelled as `yes`/`no`, `y`/`n`, `t`/`f`, or `1`/`0` (in any
+//!   case) become `true`/`false`,
+//! * categorical values that only differ from an option by case become that
+//!   option,
+//! * immunophenotypic values are rewritten in their canonical form (e.g.,
+//!   `CD19:Bright,CD34:negative` becomes `CD19:bright, CD34:negative`), and
+//! * numerical and fractional values recorded in units other than the
+//!   characteristic's (see [`units`]) are converted (e.g., a percentage
+//!   recorded as a proportion).
+//!
+//! Binary characteristics that are derived from a numerical characteristic by
+//! a threshold (see [`Threshold`]) are then checked against the source column
+//! within the same row: an empty value is filled in with the derived value,
+//! and a value that disagrees with it is reported.
+//!
+//! Every change is recorded in a provenance log so that the transformation can
+//! be audited.
+
+use std::collections::HashMap;
+
+use crate::CharacteristicSet;
+use crate::Identifier;
+use crate::annotate::Annotation;
+use crate::common::value::Kind;
+use crate::common::value::kind::binary::Threshold;
+use crate::common::value::kind::immunophenotype::Phenotype;
+use crate::common::value::kind::numerical;
+use crate::dataset::Column;
+use crate::dataset::DatasetManifest;
+
+pub mod units;
+
+/// An error related to harmonization.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// The units of a column cannot be converted into the units of its
+    /// characteristic.
+    #[error("cannot convert the units of column `{column}` from `{from}` to `{to}`")]
+    Units {
+        /// The column.
+        column: String,
+
+        /// The units of the column.
+        from: String,
+
+        /// The units of the characteristic.
+        to: String,
+    },
+
+    /// A derived column refers to a source column whose characteristic is not
+    /// numerical.
+    #[error(
+        "column `{column}` is derived from `{identifier}`, which is not a numerical characteristic"
+    )]
+    Source {
+        /// The derived column.
+        column: String,
+
+        /// The identifier of the source characteristic.
+        identifier: Identifier,
+    },
+
+    /// A derived value disagrees with the threshold it is derived from.
+    #[error("value `{value}` disagrees with `{threshold}` (the source value is `{actual}`)")]
+    Derived {
+        /// The value.
+        value: String,
+
+        /// The threshold.
+        threshold: Threshold,
+
+        /// The value of the source characteristic.
+        actual: String,
+    },
+
+    /// A value could not be harmonized into a permissible value.
+    #[error("invalid value `{value}`: expected {expected}")]
+    Value {
+        /// The value.
+        value: String,
+
+        /// A description of the permissible values.
+        expected: String,
+    },
+}
+
+/// The reason a value was changed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Reason {
+    /// The value was a missing value code.
+    Missing,
+
+    /// The value was listed in the column's `recode` mapping.
+    Recode,
+
+    /// The value was an alternative spelling of `true` or `false`.
+    Boolean,
+
+    /// The value only differed from a categorical option by case.
+    Case,
+
+    /// The marker expressions were rewritten in their canonical form.
+    Phenotype,
+
+    /// The value was converted between units.
+    Units {
+        /// The units of the column.
+        from: String,
+
+        /// The units of the characteristic.
+        to: String,
+    },
+
+    /// The value was empty and was derived from a threshold.
+    Derived(Threshold),
+}
+
+impl std::fmt::Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Missing => write!(f, "missing value code"),
+            Reason::Recode => write!(f, "recoded by manifest"),
+            Reason::Boolean => write!(f, "normalized boolean"),
+            Reason::Case => write!(f, "normalized case"),
+            Reason::Phenotype => write!(f, "normalized marker expressions"),
+            Reason::Units { from, to } => write!(f, "converted from `{from}` to `{to}`"),
+            Reason::Derived(threshold) => write!(f, "derived from `{threshold}`"),
+        }
+    }
+}
+
+/// A change made to a single cell.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    /// The (zero-based) row of the cell.
+    pub row: usize,
+
+    /// The column of the cell.
+    pub column: String,
+
+    /// The raw value.
+    pub from: String,
+
+    /// The harmonized value.
+    pub to: String,
+
+    /// The reason for the change.
+    pub reason: Reason,
+}
+
+/// A cell that could not be harmonized.
+#[derive(Debug, PartialEq)]
+pub struct Failure {
+    /// The (zero-based) row of the cell.
+    pub row: usize,
+
+    /// The column of the cell.
+    pub column: String,
+
+    /// The error.
+    pub error: Error,
+}
+
+/// A harmonized table.
+#[derive(Debug, Default)]
+pub struct Table {
+    /// The rows of the table.
+    ///
+    /// Cells that could not be harmonized keep their raw value.
+    pub rows: Vec<Vec<String>>,
+
+    /// The changes that were made (in row order).
+    pub changes: Vec<Change>,
+
+    /// The cells that could not be harmonized (in row order).
+    pub failures: Vec<Failure>,
+}
+
+/// A binary column that is derived from another column by a threshold.
+#[derive(Debug)]
+struct Derivation<'a> {
+    /// The derived column.
+    column: &'a str,
+
+    /// The source column.
+    source: &'a str,
+
+    /// The threshold.
+    threshold: &'a Threshold,
+
+    /// The factor that converts source values into the units of the
+    /// threshold.
+    factor: f64,
+}
+
+/// Harmonizes values according to a dataset manifest.
+#[derive(Debug)]
+pub struct Harmonizer<'a> {
+    /// The manifest.
+    manifest: &'a DatasetManifest,
+
+    /// The characteristics.
+    characteristics: &'a CharacteristicSet,
+
+    /// The unit conversion factors for each column that requires one.
+    factors: HashMap<&'a str, f64>,
+
+    /// The derived columns whose source column is also mapped.
+    derivations: Vec<Derivation<'a>>,
+}
+
+impl<'a> Harmonizer<'a> {
+    /// Creates a new harmonizer.
+    ///
+    /// The manifest is expected to have been validated against the
+    /// characteristics. Columns referring to unknown characteristics are left
+    /// unchanged.
+    pub fn new(
+        manifest: &'a DatasetManifest,
+        characteristics: &'a CharacteristicSet,
+    ) -> Result<Self, Error> {
+        let mut factors = HashMap::new();
+
+        for column in &manifest.columns {
+            let to = match characteristics
+                .get(&column.identifier)
+                .and_then(|c| c.values())
+            {
+                Some(Kind::Numerical { units, .. }) => units.as_str(),
+                Some(Kind::Fraction { scale, .. }) => scale.units(),
+                _ => continue,
+            };
+
+            let Some(from) = column.units.as_deref() else {
+                continue;
+            };
+
+            if from == to {
+                continue;
+            }
+
+            let factor = units::factor(from, to).ok_or_else(|| Error::Units {
+                column: column.column.clone(),
+                from: from.to_string(),
+                to: to.to_string(),
+            })?;
+
+            factors.insert(column.column.as_str(), factor);
+        }
+
+        let mut derivations = Vec::new();
+
+        for column in &manifest.columns {
+            let Some(Kind::Binary {
+                threshold: Some(threshold),
+                ..
+            }) = characteristics
+                .get(&column.identifier)
+                .and_then(|c| c.values())
+            else {
+                continue;
+            };
+
+            // NOTE: derived values can only be checked when the source
+            // characteristic is recorded within the same dataset.
+            let Some(source) = manifest
+                .columns
+                .iter()
+                .find(|other| other.identifier == threshold.source)
+            else {
+                continue;
+            };
+
+            let from = match characteristics
+                .get(&source.identifier)
+                .and_then(|c| c.values())
+            {
+                Some(Kind::Numerical { units, .. }) => units.as_str(),
+                Some(Kind::Fraction { scale, .. }) => scale.units(),
+                _ => {
+                    return Err(Error::Source {
+                        column: column.column.clone(),
+                        identifier: threshold.source.clone(),
+                    });
+                }
+            };
+
+            let factor = if from == threshold.units {
+                1.0
+            } else {
+                units::factor(from, &threshold.units).ok_or_else(|| Error::Units {
+                    column: column.column.clone(),
+                    from: from.to_string(),
+                    to: threshold.units.clone(),
+                })?
+            };
+
+            derivations.push(Derivation {
+                column: column.column.as_str(),
+                source: source.column.as_str(),
+                threshold,
+                factor,
+            });
+        }
+
+        Ok(Self {
+            manifest,
+            characteristics,
+            factors,
+            derivations,
+        })
+    }
+
+    /// Harmonizes a single value within a column.
+    ///
+    /// Returns the harmonized value along with the reason it was changed (if
+    /// it was). Values within unmapped columns are returned unchanged.
+    pub fn value(&self, header: &str, value: &str) -> Result<(String, Option<Reason>), Error> {
+        if self.manifest.is_missing(header, value) {
+            let reason = (!value.is_empty()).then_some(Reason::Missing);
+            return Ok((String::new(), reason));
+        }
+
+        let Some(column) = self.manifest.column(header) else {
+            return Ok((value.to_string(), None));
+        };
+
+        let Some(kind) = self
+            .characteristics
+            .get(&column.identifier)
+            .and_then(|c| c.values())
+        else {
+            return Ok((value.to_string(), None));
+        };
+
+        let (harmonized, reason) = match column.recode.get(value) {
+            Some(recoded) => (recoded.clone(), Some(Reason::Recode)),
+            None => self.normalize(column, kind, value)?,
+        };
+
+        Annotation::new(column.identifier.clone(), harmonized.as_str())
+            .conforms_to(kind)
+            .map_err(|expected| Error::Value {
+                value: value.to_string(),
+                expected,
+            })?;
+
+        Ok((harmonized, reason))
+    }
+
+    /// Normalizes a value according to the kind of its characteristic.
+    fn normalize(
+        &self,
+        column: &Column,
+        kind: &Kind,
+        value: &str,
+    ) -> Result<(String, Option<Reason>), Error> {
+        let unchanged = Ok((value.to_string(), None));
+
+        match kind {
+            Kind::Binary { .. } => {
+                let normalized = match value.to_lowercase().as_str() {
+                    "true" | "t" | "yes" | "y" | "1" => "true",
+                    "false" | "f" | "no" | "n" | "0" => "false",
+                    _ => return unchanged,
+                };
+
+                if normalized == value {
+                    return unchanged;
+                }
+
+                Ok((normalized.to_string(), Some(Reason::Boolean)))
+            }
+            Kind::Categorical { options } => {
+                if options.contains(value) {
+                    return unchanged;
+                }
+
+                let mut matches = options
+                    .iter()
+                    .filter(|option| option.eq_ignore_ascii_case(value));
+
+                match (matches.next(), matches.next()) {
+                    (Some(option), None) => Ok((option.clone(), Some(Reason::Case))),
+                    _ => unchanged,
+                }
+            }
+            Kind::Numerical { r#type, units } => {
+                let Some(factor) = self.factors.get(column.column.as_str()) else {
+                    return unchanged;
+                };
+
+                let Ok(number) = value.parse::<f64>() else {
+                    return unchanged;
+                };
+
+                let converted = number * factor;
+                let converted = match r#type {
+                    numerical::Type::Float => converted.to_string(),
+                    numerical::Type::Signed | numerical::Type::Unsigned => {
+                        // NOTE: integers that do not survive the conversion
+                        // are reported rather than silently rounded.
+                        let rounded = converted.round();
+
+                        if (converted - rounded).abs() > 1e-9 {
+                            return Err(Error::Value {
+                                value: value.to_string(),
+                                expected: format!(
+                                    "a value that converts to a whole number of `{units}`"
+                                ),
+                            });
+                        }
+
+                        format!("{rounded}")
+                    }
+                };
+
+                Ok((
+                    converted,
+                    Some(Reason::Units {
+                        // SAFETY: conversion factors only exist for columns
+                        // with units.
+                        from: column.units.clone().unwrap(),
+                        to: units.clone(),
+                    }),
+                ))
+            }
+            Kind::Fraction { scale, .. } => {
+                let Some(factor) = self.factors.get(column.column.as_str()) else {
+                    return unchanged;
+                };
+
+                let Ok(number) = value.parse::<f64>() else {
+                    return unchanged;
+                };
+
+                Ok((
+                    (number * factor).to_string(),
+                    Some(Reason::Units {
+                        // SAFETY: conversion factors only exist for columns
+                        // with units.
+                        from: column.units.clone().unwrap(),
+                        to: scale.units().to_string(),
+                    }),
+                ))
+            }
+            Kind::Immunophenotype { .. } => {
+                let Ok(phenotype) = value.parse::<Phenotype>() else {
+                    return unchanged;
+                };
+
+                let normalized = phenotype.to_string();
+
+                if normalized == value {
+                    return unchanged;
+                }
+
+                Ok((normalized, Some(Reason::Phenotype)))
+            }
+            Kind::Other { .. } => unchanged,
+        }
+    }
+
+    /// Checks (or fills in) the derived values of a harmonized row.
+    ///
+    /// Source values that are empty or not numbers are skipped, as are derived
+    /// values that are neither empty nor `true`/`false` (they have already
+    /// been reported).
+    fn derive(&self, headers: &[String], row: usize, cells: &mut [String], table: &mut Table) {
+        let position = |column: &str| headers.iter().position(|header| header == column);
+
+        for derivation in &self.derivations {
+            let (Some(i), Some(j)) = (position(derivation.column), position(derivation.source))
+            else {
+                continue;
+            };
+
+            let Ok(actual) = cells[j].parse::<f64>() else {
+                continue;
+            };
+
+            let expected = derivation.threshold.evaluate(actual * derivation.factor);
+
+            match cells[i].as_str() {
+                "" => {
+                    let to = expected.to_string();
+
+                    table.changes.push(Change {
+                        row,
+                        column: headers[i].clone(),
+                        from: std::mem::replace(&mut cells[i], to.clone()),
+                        to,
+                        reason: Reason::Derived(derivation.threshold.clone()),
+                    });
+                }
+                value if value.parse::<bool>().is_ok_and(|value| value != expected) => {
+                    table.failures.push(Failure {
+                        row,
+                        column: headers[i].clone(),
+                        error: Error::Derived {
+                            value: value.to_string(),
+                            threshold: derivation.threshold.clone(),
+                            actual: cells[j].clone(),
+                        },
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Harmonizes a table.
+    pub fn table(&self, headers: &[String], rows: impl IntoIterator<Item = Vec<String>>) -> Table {
+        let mut table = Table::default();
+
+        for (i, row) in rows.into_iter().enumerate() {
+            let mut harmonized = Vec::with_capacity(row.len());
+
+            for (header, value) in headers.iter().zip(row) {
+                match self.value(header, &value) {
+                    Ok((new, reason)) => {
+                        if let Some(reason) = reason {
+                            table.changes.push(Change {
+                                row: i,
+                                column: header.clone(),
+                                from: value,
+                                to: new.clone(),
+                                reason,
+                            });
+                        }
+
+                        harmonized.push(new);
+                    }
+                    Err(error) => {
+                        table.failures.push(Failure {
+                            row: i,
+                            column: header.clone(),
+                            error,
+                        });
+
+                        harmonized.push(value);
+                    }
+                }
+            }
+
+            self.derive(headers, i, &mut harmonized, &mut table);
+            table.rows.push(harmonized);
+        }
+
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Characteristic;
+
+    fn characteristics() -> CharacteristicSet {
+        let characteristics: Vec<Characteristic> = serde_yaml::from_str(
+            r#"- state: proposed
+  identifier: ECC-MORPH-000001
+  name: Foo Bar
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: categorical
+    options: [Foo, Bar]
+- state: proposed
+  identifier: ECC-MORPH-000002
+  name: Baz Quux
+  rfc: https://github.com/stjudecloud/ecc/issues/2
+  description: A description.
+  values:
+    kind: numerical
+    type: unsigned
+    units: Days
+- state: proposed
+  identifier: ECC-MORPH-000003
+  name: Hello World
+  rfc: https://github.com/stjudecloud/ecc/issues/3
+  description: A description.
+  values:
+    kind: binary
+    description:
+      "true":
+        summary: The thing is present.
+        details: The thing is present.
+      "false":
+        summary: The thing is not present.
+        details: The thing is not present."#,
+        )
+        .unwrap();
+
+        characteristics.into_iter().collect()
+    }
+
+    fn manifest() -> DatasetManifest {
+        serde_yaml::from_str(
+            r#"missing: [NA]
+columns:
+  - column: category
+    identifier: ECC-MORPH-000001
+    recode:
+      F: Foo
+  - column: age
+    identifier: ECC-MORPH-000002
+    units: Weeks
+  - column: present
+    identifier: ECC-MORPH-000003"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn table() {
+        let characteristics = characteristics();
+        let manifest = manifest();
+        let harmonizer = Harmonizer::new(&manifest, &characteristics).unwrap();
+
+        let headers = ["sample", "category", "age", "present"].map(String::from);
+        let rows = vec![
+            ["S1", "F", "2", "Yes"].map(String::from).to_vec(),
+            ["S2", "bar", "NA", "false"].map(String::from).to_vec(),
+            ["S3", "Baz", "1", "maybe"].map(String::from).to_vec(),
+        ];
+
+        let table = harmonizer.table(&headers, rows);
+
+        assert_eq!(table.rows[0], vec!["S1", "Foo", "14", "true"]);
+        assert_eq!(table.rows[1], vec!["S2", "Bar", "", "false"]);
+        assert_eq!(table.rows[2], vec!["S3", "Baz", "7", "maybe"]);
+
+        let reasons = table
+            .changes
+            .iter()
+            .map(|change| (change.row, change.reason.to_string()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            reasons,
+            vec![
+                (0, String::from("recoded by manifest")),
+                (0, String::from("converted from `Weeks` to `Days`")),
+                (0, String::from("normalized boolean")),
+                (1, String::from("normalized case")),
+                (1, String::from("missing value code")),
+                (2, String::from("converted from `Weeks` to `Days`")),
+            ]
+        );
+
+        let failures = table
+            .failures
+            .iter()
+            .map(|failure| (failure.row, failure.column.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(failures, vec![(2, "category"), (2, "present")]);
+    }
+
+    #[test]
+    fn derived() {
+        let characteristics: CharacteristicSet = serde_yaml::from_str::<Vec<Characteristic>>(
+            r#"- state: proposed
+  identifier: ECC-MOLEC-000042
+  name: Minimal Residual Disease
+  rfc: https://github.com/stjudecloud/ecc/issues/42
+  description: A description.
+  values:
+    kind: numerical
+    type: float
+    units: proportion
+- state: proposed
+  identifier: ECC-MOLEC-000043
+  name: MRD Positive
+  rfc: https://github.com/stjudecloud/ecc/issues/43
+  description: A description.
+  values:
+    kind: binary
+    description:
+      "true":
+        summary: Residual disease was detected.
+        details: Residual disease was detected.
+      "false":
+        summary: Residual disease was not detected.
+        details: Residual disease was not detected.
+    threshold:
+      source: ECC-MOLEC-000042
+      comparator: ">="
+      value: 0.01
+      units: "%""#,
+        )
+        .unwrap()
+        .into_iter()
+        .collect();
+
+        let manifest: DatasetManifest = serde_yaml::from_str(
+            r#"missing: [NA]
+columns:
+  - column: mrd
+    identifier: ECC-MOLEC-000042
+    units: "%"
+  - column: positive
+    identifier: ECC-MOLEC-000043"#,
+        )
+        .unwrap();
+
+        let harmonizer = Harmonizer::new(&manifest, &characteristics).unwrap();
+        let headers = ["mrd", "positive"].map(String::from);
+        let rows = vec![
+            ["0.5", ""].map(String::from).to_vec(),
+            ["0.001", "yes"].map(String::from).to_vec(),
+            ["NA", "false"].map(String::from).to_vec(),
+            ["2", "true"].map(String::from).to_vec(),
+        ];
+
+        let table = harmonizer.table(&headers, rows);
+
+        assert_eq!(table.rows[0], vec!["0.005", "true"]);
+        assert_eq!(table.rows[2], vec!["", "false"]);
+        assert_eq!(
+            table.changes[1].reason.to_string(),
+            "derived from `ECC-MOLEC-000042 >= 0.01 %`"
+        );
+
+        assert_eq!(table.failures.len(), 1);
+        assert_eq!(table.failures[0].row, 1);
+        assert_eq!(
+            table.failures[0].error.to_string(),
+            "value `true` disagrees with `ECC-MOLEC-000042 >= 0.01 %` (the source value is \
+             `0.00001`)"
+        );
+    }
+
+    #[test]
+    fn units() {
+        let characteristics = characteristics();
+        let mut manifest = manifest();
+        manifest.columns[1].units = Some(String::from("Grams"));
+
+        assert_eq!(
+            Harmonizer::new(&manifest, &characteristics).unwrap_err(),
+            Error::Units {
+                column: String::from("age"),
+                from: String::from("Grams"),
+                to: String::from("Days"),
+            }
+        );
+    }
+}
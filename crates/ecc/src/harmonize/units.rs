@@ -0,0 +1,84 @@
+//! Units of measurement that can be converted between.
+//!
+//! Units are matched case-insensitively by their singular name, plural name,
+//! or common abbreviation. Each unit belongs to a dimension and is expressed
+//! as a multiple of the base unit of that dimension.
+
+/// A dimension of measurement.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Dimension {
+    /// Time (in days).
+    Time,
+
+    /// Mass (in grams).
+    Mass,
+
+    /// Length (in meters).
+    Length,
+
+    /// A fraction of a whole (as a proportion).
+    Fraction,
+}
+
+/// The known units as `(names, dimension, multiple of the base unit)`.
+const UNITS: &[(&[&str], Dimension, f64)] = &[
+    (&["hour", "hours", "h", "hr"], Dimension::Time, 1.0 / 24.0),
+    (&["day", "days", "d"], Dimension::Time, 1.0),
+    (&["week", "weeks", "wk"], Dimension::Time, 7.0),
+    (&["month", "months", "mo"], Dimension::Time, 365.25 / 12.0),
+    (&["year", "years", "yr", "y"], Dimension::Time, 365.25),
+    (&["milligram", "milligrams", "mg"], Dimension::Mass, 0.001),
+    (&["gram", "grams", "g"], Dimension::Mass, 1.0),
+    (&["kilogram", "kilograms", "kg"], Dimension::Mass, 1000.0),
+    (
+        &["millimeter", "millimeters", "mm"],
+        Dimension::Length,
+        0.001,
+    ),
+    (
+        &["centimeter", "centimeters", "cm"],
+        Dimension::Length,
+        0.01,
+    ),
+    (&["meter", "meters", "m"], Dimension::Length, 1.0),
+    (&["percent", "percentage", "%"], Dimension::Fraction, 0.01),
+    (&["proportion", "fraction"], Dimension::Fraction, 1.0),
+];
+
+/// Looks up a unit by name.
+///
+/// Returns the dimension of the unit and its multiple of the base unit.
+pub fn lookup(name: &str) -> Option<(Dimension, f64)> {
+    let name = name.trim().to_lowercase();
+
+    UNITS
+        .iter()
+        .find(|(names, ..)| names.contains(&name.as_str()))
+        .map(|(_, dimension, multiple)| (*dimension, *multiple))
+}
+
+/// Gets the factor that converts values in one unit into another.
+///
+/// Returns [`None`] if either unit is unknown or the units measure different
+/// dimensions.
+pub fn factor(from: &str, to: &str) -> Option<f64> {
+    let (from_dimension, from) = lookup(from)?;
+    let (to_dimension, to) = lookup(to)?;
+
+    (from_dimension == to_dimension).then(|| from / to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn factors() {
+        assert!((factor("Months", "Years").unwrap() - 1.0 / 12.0).abs() < 1e-12);
+        assert_eq!(factor("kg", "Grams"), Some(1000.0));
+        assert_eq!(factor("Days", "Days"), Some(1.0));
+        assert_eq!(factor("%", "proportion"), Some(0.01));
+        assert_eq!(factor("Days", "Grams"), None);
+        assert_eq!(factor("Cells", "Cells"), None);
+    }
+}
@@ -1,10 +1,12 @@
 //! Characteristic identifiers.
 
-use std::num::NonZeroU64;
+use alloc::format;
+use alloc::string::String;
+use alloc::string::ToString as _;
+use alloc::vec::Vec;
+use core::num::NonZeroU64;
 
-use serde::Deserialize;
-use serde::Serialize;
-use serde::de::Visitor;
+use crate::Category;
 
 /// The prefix of any serialized identifier.
 const PREFIX: &str = "ECC";
@@ -13,7 +15,7 @@ const PREFIX: &str = "ECC";
 const JOIN_CHAR: char = '-';
 
 /// A composable characteristic identifier.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Identifier {
     /// A numbered molecular characteristic.
     Molecular(NonZeroU64),
@@ -23,6 +25,20 @@ pub enum Identifier {
 }
 
 impl Identifier {
+    /// The highest number that an identifier can have (the largest number
+    /// that fits within the six digits of a serialized identifier).
+    pub const MAX_NUMBER: u64 = 999_999;
+
+    /// Creates an identifier within a category.
+    ///
+    /// If `n` is 0, [`None`] is returned, as identifiers start at 1.
+    pub fn new(category: Category, n: u64) -> Option<Self> {
+        match category {
+            Category::Molecular => Self::molecular(n),
+            Category::Morphological => Self::morphological(n),
+        }
+    }
+
     /// Creates a molecular identifier.
     ///
     /// If `n` is 0, [`None`] is returned, as identifiers start at 1.
@@ -48,17 +64,61 @@ impl Identifier {
         // always unwrap.
         Some(Self::Morphological(NonZeroU64::try_from(n).unwrap()))
     }
-}
 
-impl std::fmt::Display for Identifier {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{PREFIX}{JOIN_CHAR}")?;
+    /// Gets the next available identifier within a category.
+    ///
+    /// The next available identifier is the one numbered immediately after the
+    /// highest numbered identifier in use within the category. Numbers that
+    /// were skipped are never reused.
+    ///
+    /// If the highest number ([`Identifier::MAX_NUMBER`]) is already in use,
+    /// there are no identifiers left within the category and [`None`] is
+    /// returned.
+    pub fn next_available<'a>(
+        category: Category,
+        used: impl IntoIterator<Item = &'a Identifier>,
+    ) -> Option<Self> {
+        let max = used
+            .into_iter()
+            .filter(|identifier| identifier.category() == category)
+            .map(|identifier| identifier.number().get())
+            .max()
+            .unwrap_or(0);
+
+        if max >= Self::MAX_NUMBER {
+            return None;
+        }
+
+        // NOTE: one is always added to the current maximum, so the number is
+        // never zero.
+        Self::new(category, max + 1)
+    }
 
+    /// Gets the category of the identifier.
+    pub fn category(&self) -> Category {
         match self {
-            Identifier::Molecular(n) => write!(f, "MOLEC{JOIN_CHAR}{n:06}"),
-            Identifier::Morphological(n) => write!(f, "MORPH{JOIN_CHAR}{n:06}"),
+            Identifier::Molecular(_) => Category::Molecular,
+            Identifier::Morphological(_) => Category::Morphological,
         }
     }
+
+    /// Gets the number of the identifier.
+    pub fn number(&self) -> NonZeroU64 {
+        match self {
+            Identifier::Molecular(n) | Identifier::Morphological(n) => *n,
+        }
+    }
+}
+
+impl core::fmt::Display for Identifier {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{PREFIX}{JOIN_CHAR}{}{JOIN_CHAR}{:06}",
+            self.category().code(),
+            self.number()
+        )
+    }
 }
 
 /// An error when parsing an identifier.
@@ -101,8 +161,8 @@ pub enum ParseError {
     },
 }
 
-impl std::fmt::Display for ParseError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
             ParseError::IncorrectNumberOfParts { found, expected } => write!(
                 f,
@@ -124,12 +184,12 @@ impl std::fmt::Display for ParseError {
     }
 }
 
-impl std::error::Error for ParseError {}
+impl core::error::Error for ParseError {}
 
 /// The number of expected parts in an identifier.
 const EXPECTED_PARTS: usize = 3;
 
-impl std::str::FromStr for Identifier {
+impl core::str::FromStr for Identifier {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -183,7 +243,8 @@ impl std::str::FromStr for Identifier {
     }
 }
 
-impl Serialize for Identifier {
+#[cfg(feature = "serde")]
+impl serde::Serialize for Identifier {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: serde::Serializer,
@@ -193,12 +254,14 @@ impl Serialize for Identifier {
 }
 
 /// A visitor for deserializing identifiers.
+#[cfg(feature = "serde")]
 pub struct IdentifierVisitor;
 
-impl Visitor<'_> for IdentifierVisitor {
+#[cfg(feature = "serde")]
+impl serde::de::Visitor<'_> for IdentifierVisitor {
     type Value = Identifier;
 
-    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    fn expecting(&self, formatter: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         formatter.write_str("a valid characteristic identifier")
     }
 
@@ -211,7 +274,8 @@ impl Visitor<'_> for IdentifierVisitor {
     }
 }
 
-impl<'de> Deserialize<'de> for Identifier {
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Identifier {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
@@ -222,6 +286,7 @@ impl<'de> Deserialize<'de> for Identifier {
 
 #[cfg(test)]
 mod tests {
+    use crate::Category;
     use crate::Identifier;
 
     #[test]
@@ -234,6 +299,47 @@ mod tests {
         assert!(Identifier::molecular(0).is_none());
     }
 
+    #[test]
+    fn category() {
+        let identifier = Identifier::new(Category::Molecular, 7).unwrap();
+        assert_eq!(identifier.category(), Category::Molecular);
+        assert_eq!(identifier.number().get(), 7);
+        assert_eq!(identifier.to_string(), "ECC-MOLEC-000007");
+    }
+
+    #[test]
+    fn next_available() {
+        let used = [
+            Identifier::molecular(1).unwrap(),
+            Identifier::molecular(3).unwrap(),
+            Identifier::morphological(10).unwrap(),
+        ];
+
+        assert_eq!(
+            Identifier::next_available(Category::Molecular, &used),
+            Identifier::molecular(4)
+        );
+        assert_eq!(
+            Identifier::next_available(Category::Morphological, &used),
+            Identifier::morphological(11)
+        );
+        assert_eq!(
+            Identifier::next_available(Category::Molecular, &[]),
+            Identifier::molecular(1)
+        );
+
+        // NOTE: the serialized form only fits six digits.
+        let used = [Identifier::morphological(Identifier::MAX_NUMBER).unwrap()];
+        assert_eq!(
+            Identifier::next_available(Category::Morphological, &used),
+            None
+        );
+        assert_eq!(
+            Identifier::next_available(Category::Molecular, &used),
+            Identifier::molecular(1)
+        );
+    }
+
     #[test]
     fn display() {
         assert_eq!(
@@ -0,0 +1,237 @@
+//! Reading characteristics from YAML documents.
+//!
+//! Parse failures are reported along with the path to the offending field
+//! (e.g., `references[2].context`) so that problems can be found without
+//! inspecting the document by hand. Documents in the legacy flat layout are
+//! upgraded before they are deserialized (see [`crate::legacy`]).
+//!
+//! Many files can be read one at a time with [`stream()`], which holds at most
+//! one document in memory regardless of the number of files.
+
+use std::fs::File;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use serde_yaml::Value;
+
+use crate::Characteristic;
+use crate::error::Code;
+use crate::legacy;
+
+mod locate;
+mod path;
+
+pub use locate::locate;
+pub use locate::normalize;
+pub use path::Path;
+pub use path::Segment;
+
+/// An error related to reading a characteristic.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// An error reading a file.
+    #[error("reading file `{}`: {source}", path.display())]
+    Io {
+        /// The path to the file.
+        path: PathBuf,
+
+        /// The underlying error.
+        source: std::io::Error,
+    },
+
+    /// The document is not valid YAML.
+    #[error(transparent)]
+    Syntax(serde_yaml::Error),
+
+    /// The document is valid YAML but is not a valid characteristic.
+    #[error("{}{message}", prefix(path))]
+    Field {
+        /// The path to the offending field.
+        path: Path,
+
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl Error {
+    /// Gets the code for the kind of error (see [`crate::error`]).
+    pub fn code(&self) -> Code {
+        match self {
+            Error::Io { .. } => Code::IO,
+            Error::Syntax(_) => Code::SYNTAX,
+            Error::Field { path, .. } => Code::for_field(path),
+        }
+    }
+
+    /// Gets the path to the offending field (if the error is attributable to
+    /// a field).
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::Field { path, .. } if !path.is_root() => Some(path),
+            _ => None,
+        }
+    }
+}
+
+/// Formats the path that prefixes a field error.
+fn prefix(path: &Path) -> String {
+    if path.is_root() {
+        String::new()
+    } else {
+        format!("`{path}`: ")
+    }
+}
+
+/// Parses a characteristic from a YAML string.
+pub fn from_str(source: &str) -> Result<Characteristic, Error> {
+    let document = serde_yaml::from_str::<Value>(source).map_err(Error::Syntax)?;
+    from_value(document)
+}
+
+/// Parses a characteristic from a reader of a YAML document.
+///
+/// The reader does not need to be buffered.
+pub fn from_reader(reader: impl std::io::Read) -> Result<Characteristic, Error> {
+    let document = serde_yaml::from_reader::<_, Value>(reader).map_err(Error::Syntax)?;
+    from_value(document)
+}
+
+/// Deserializes a characteristic from a YAML document.
+pub fn from_value(mut document: Value) -> Result<Characteristic, Error> {
+    legacy::upgrade(&mut document);
+
+    serde_path_to_error::deserialize::<_, Characteristic>(document.clone()).map_err(|err| {
+        let path = Path::from(err.path());
+        let message = normalize(&err.inner().to_string()).to_string();
+
+        // NOTE: `serde` buffers the contents of internally tagged enums, so
+        // the tracked path stops at the characteristic itself for most
+        // errors. In that case, the path is recovered from the document.
+        let path = if path.is_root() {
+            locate(&document, &message)
+        } else {
+            path
+        };
+
+        Error::Field { path, message }
+    })
+}
+
+/// Reads a characteristic from a YAML file.
+pub fn from_path(path: impl Into<PathBuf>) -> Result<Characteristic, Error> {
+    let path = path.into();
+
+    let source = match std::fs::read_to_string(&path) {
+        Ok(source) => source,
+        Err(source) => return Err(Error::Io { path, source }),
+    };
+
+    from_str(&source)
+}
+
+/// An iterator that reads characteristics from files one at a time (see
+/// [`stream()`]).
+#[derive(Debug)]
+pub struct Stream<I> {
+    /// The paths that remain to be read.
+    paths: I,
+}
+
+impl<I: Iterator<Item = PathBuf>> Iterator for Stream<I> {
+    type Item = (PathBuf, Result<Characteristic, Error>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let path = self.paths.next()?;
+
+        let result = match File::open(&path) {
+            Ok(file) => from_reader(BufReader::new(file)),
+            Err(source) => Err(Error::Io {
+                path: path.clone(),
+                source,
+            }),
+        };
+
+        Some((path, result))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.paths.size_hint()
+    }
+}
+
+/// Reads characteristics from many YAML files.
+///
+/// Files are opened lazily and read through a buffered reader as the iterator
+/// advances, so only the document currently being parsed is held in memory.
+/// A file that cannot be read or parsed does not end the iteration.
+pub fn stream<I: IntoIterator<Item = PathBuf>>(paths: I) -> Stream<I::IntoIter> {
+    Stream {
+        paths: paths.into_iter(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "state: draft\nname: A Characteristic Name\n";
+
+    #[test]
+    fn paths() {
+        let yaml = format!(
+            "{HEADER}references:\n{}",
+            (0..3)
+                .map(|i| format!(
+                    "  - kind: manuscript\n    title: Title {i}\n    authors: Jane Smith\n    \
+                     context: {}\n    url: https://example.com/{i}\n    highlighted: false\n",
+                    if i == 2 { "''" } else { "Some context." }
+                ))
+                .collect::<String>()
+        );
+
+        let err = from_str(&yaml).unwrap_err();
+        assert_eq!(err.path().unwrap().to_string(), "references[2].context");
+        assert!(err.to_string().starts_with("`references[2].context`: "));
+
+        let err = from_str(&format!(
+            "{HEADER}values:\n  kind: categorical\n  options: [Foo, [Bar]]\n"
+        ))
+        .unwrap_err();
+        assert_eq!(err.path().unwrap().to_string(), "values.options[1]");
+
+        let err = from_str(&format!("{HEADER}foo: bar\n")).unwrap_err();
+        assert_eq!(err.path().unwrap().to_string(), "foo");
+
+        let err = from_str("state: foo\n").unwrap_err();
+        assert_eq!(err.path().unwrap().to_string(), "state");
+    }
+
+    #[test]
+    fn streams() {
+        let dir = tempfile::tempdir().unwrap();
+        let valid = dir.path().join("valid.yml");
+        let invalid = dir.path().join("invalid.yml");
+        std::fs::write(&valid, HEADER).unwrap();
+        std::fs::write(&invalid, "state: [draft").unwrap();
+
+        let results = stream([valid.clone(), dir.path().join("missing.yml"), invalid])
+            .map(|(path, result)| (path, result.map(|c| c.name().map(String::from))))
+            .collect::<Vec<_>>();
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].0, valid);
+        assert_eq!(
+            results[0].1.as_ref().unwrap().as_deref(),
+            Some("A Characteristic Name")
+        );
+        assert!(matches!(results[1].1, Err(Error::Io { .. })));
+        assert!(matches!(results[2].1, Err(Error::Syntax(_))));
+    }
+
+    #[test]
+    fn syntax() {
+        assert!(matches!(from_str("state: [draft"), Err(Error::Syntax(_))));
+        assert!(from_str(HEADER).is_ok());
+    }
+}
@@ -0,0 +1,291 @@
+//! Locating deserialization errors within a document.
+//!
+//! Errors raised within internally tagged enums carry neither a location nor
+//! a path, as `serde` buffers the contents of such enums before deserializing
+//! them. To recover where an error occurred, each node along the way is
+//! deserialized on its own into the type expected at its path, descending
+//! into the first node that fails with the same error.
+
+use std::collections::BTreeSet;
+
+use chrono::DateTime;
+use chrono::Utc;
+use nonempty::NonEmpty;
+use serde::de::DeserializeOwned;
+use serde_yaml::Value;
+use url::Url;
+
+use crate::Identifier;
+use crate::common::Applicability;
+use crate::common::Error;
+use crate::common::EvidenceLevel;
+use crate::common::Expression;
+use crate::common::Iscn;
+use crate::common::Mapping;
+use crate::common::Molecular;
+use crate::common::Reference;
+use crate::common::Relation;
+use crate::common::Sensitivity;
+use crate::common::Steward;
+use crate::common::Tag;
+use crate::common::applicability;
+use crate::common::mapping;
+use crate::common::molecular;
+use crate::common::relation;
+use crate::common::steward;
+use crate::common::validate_values;
+use crate::common::value::Kind;
+use crate::common::value::kind::binary;
+use crate::common::value::kind::fraction;
+use crate::common::value::kind::immunophenotype;
+use crate::common::value::kind::numerical;
+use crate::field;
+use crate::io::Path;
+use crate::io::Segment;
+use crate::migrate::SchemaVersion;
+use crate::rfc;
+use crate::text::Sentence;
+use crate::text::language::Translations;
+
+/// Removes the location and path that `serde_yaml` adds to error messages.
+pub fn normalize(message: &str) -> &str {
+    let message = match message.rfind(" at line ") {
+        Some(i)
+            if message[i..]
+                .trim_start_matches(" at line ")
+                .split(" column ")
+                .all(|n| n.parse::<usize>().is_ok()) =>
+        {
+            &message[..i]
+        }
+        _ => message,
+    };
+
+    match message.split_once(": ") {
+        Some((path, rest))
+            if !path.is_empty()
+                && path
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || matches!(c, '_' | '.' | '[' | ']')) =>
+        {
+            rest
+        }
+        _ => message,
+    }
+}
+
+/// Deserializes a value into a type, returning the error message (if any).
+fn check<T: DeserializeOwned>(value: Value) -> Option<String> {
+    serde_yaml::from_value::<T>(value)
+        .err()
+        .map(|err| normalize(&err.to_string()).to_string())
+}
+
+/// Deserializes the permissible values of a characteristic that has left the
+/// `draft` phase, returning the error message (if any).
+fn values(value: Value) -> Option<String> {
+    match serde_yaml::from_value::<Kind>(value) {
+        Ok(values) => validate_values(&values).err().map(|err| err.to_string()),
+        Err(err) => Some(normalize(&err.to_string()).to_string()),
+    }
+}
+
+/// Deserializes a value into the type expected at a path.
+///
+/// Returns [`None`] if the type at the path is not known and `Some(None)` if
+/// the value deserialized successfully.
+fn probe(path: &Path, value: Value) -> Option<Option<String>> {
+    let segments = path
+        .segments()
+        .iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => Some(key.as_str()),
+            Segment::Index(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    Some(match segments.as_slice() {
+        [Some("schema_version")] => check::<SchemaVersion>(value),
+        [Some("name" | "description")] => check::<String>(value),
+        [Some("translations")] => check::<Translations>(value),
+        [Some("translations"), Some(_)] => check::<String>(value),
+        [Some("identifier")] => check::<Identifier>(value),
+        [Some("rfc")] => check::<rfc::Link>(value),
+        [Some("adoption_date" | "created_at" | "updated_at")] => check::<DateTime<Utc>>(value),
+        [Some("values")] => values(value),
+        // NOTE: the kind is only invalid on its own when a characteristic
+        // that has left the `draft` phase uses `kind: other`.
+        [Some("values"), Some("kind")] => {
+            (value.as_str() == Some("other")).then(|| Error::OtherKind.to_string())
+        }
+        [Some("values"), Some("options")] => check::<BTreeSet<String>>(value),
+        [Some("values"), Some("options"), None] => check::<String>(value),
+        [Some("values"), Some("markers")] => check::<BTreeSet<immunophenotype::Marker>>(value),
+        [Some("values"), Some("markers"), None] => check::<immunophenotype::Marker>(value),
+        [Some("values"), Some("type")] => check::<numerical::Type>(value),
+        [Some("values"), Some("units" | "denominator_description")] => check::<String>(value),
+        [Some("values"), Some("scale")] => check::<fraction::Scale>(value),
+        [Some("values"), Some("min" | "max")] => check::<f64>(value),
+        [Some("values"), Some("threshold")] => check::<binary::Threshold>(value),
+        [Some("values"), Some("threshold"), Some("source")] => check::<Identifier>(value),
+        [Some("values"), Some("threshold"), Some("comparator")] => {
+            check::<binary::Comparator>(value)
+        }
+        [Some("values"), Some("threshold"), Some("value")] => check::<f64>(value),
+        [Some("values"), Some("threshold"), Some("units")] => check::<String>(value),
+        [Some("values"), Some("description")] => check::<binary::Description>(value),
+        [Some("values"), Some("description"), Some("true" | "false")] => {
+            check::<field::Description>(value)
+        }
+        [
+            Some("values"),
+            Some("description"),
+            Some("true" | "false"),
+            Some("summary" | "details"),
+        ] => check::<Sentence>(value),
+        [Some("references")] => check::<NonEmpty<Reference>>(value),
+        [Some("references"), None] => check::<Reference>(value),
+        [Some("references"), None, Some("title" | "authors")] => check::<String>(value),
+        [Some("references"), None, Some("context")] => check::<Sentence>(value),
+        [Some("references"), None, Some("url")] => check::<Url>(value),
+        [Some("references"), None, Some("highlighted")] => check::<bool>(value),
+        [Some("evidence_level")] => check::<EvidenceLevel>(value),
+        [Some("sensitivity")] => check::<Sensitivity>(value),
+        [Some("tags")] => check::<Vec<Tag>>(value),
+        [Some("tags"), None] => check::<Tag>(value),
+        [Some("relations")] => check::<Vec<Relation>>(value),
+        [Some("relations"), None] => check::<Relation>(value),
+        [Some("relations"), None, Some("kind")] => check::<relation::Kind>(value),
+        [Some("relations"), None, Some("target")] => check::<Identifier>(value),
+        [Some("stewards")] => check::<NonEmpty<Steward>>(value),
+        [Some("stewards"), None] => check::<Steward>(value),
+        [Some("stewards"), None, Some("name" | "email")] => check::<String>(value),
+        [Some("stewards"), None, Some("github")] => check::<steward::Handle>(value),
+        [Some("mappings")] => check::<Vec<Mapping>>(value),
+        [Some("mappings"), None] => check::<Mapping>(value),
+        [Some("mappings"), None, Some("predicate")] => check::<mapping::Predicate>(value),
+        [Some("mappings"), None, Some("term")] => check::<mapping::Curie>(value),
+        [Some("mappings"), None, Some("label")] => check::<String>(value),
+        [Some("molecular")] => check::<Molecular>(value),
+        [Some("molecular"), Some("genes")] => check::<NonEmpty<molecular::Symbol>>(value),
+        [Some("molecular"), Some("genes"), None] => check::<molecular::Symbol>(value),
+        [Some("molecular"), Some("variants")] => check::<Vec<molecular::hgvs::Expression>>(value),
+        [Some("molecular"), Some("variants"), None] => check::<molecular::hgvs::Expression>(value),
+        [Some("cytogenetics")] => check::<Vec<Iscn>>(value),
+        [Some("cytogenetics"), None] => check::<Iscn>(value),
+        [Some("definition")] => check::<Expression>(value),
+        [Some("applicability")] => check::<Applicability>(value),
+        [Some("applicability"), Some("nodes")] => check::<Vec<String>>(value),
+        [Some("applicability"), Some("nodes"), None] => check::<String>(value),
+        [Some("applicability"), Some("specimen_types")] => {
+            check::<Vec<applicability::SpecimenType>>(value)
+        }
+        [Some("applicability"), Some("specimen_types"), None] => {
+            check::<applicability::SpecimenType>(value)
+        }
+        [Some("applicability"), Some("age_groups")] => check::<Vec<applicability::AgeGroup>>(value),
+        [Some("applicability"), Some("age_groups"), None] => {
+            check::<applicability::AgeGroup>(value)
+        }
+        _ => return None,
+    })
+}
+
+/// Collects the paths and values of every node beneath a value in document
+/// order.
+fn descendants<'a>(path: &Path, value: &'a Value, out: &mut Vec<(Path, &'a Value)>) {
+    match value {
+        Value::Mapping(mapping) => {
+            for (key, child) in mapping {
+                let Some(key) = key.as_str() else {
+                    continue;
+                };
+
+                let path = path.join(Segment::Key(key.to_string()));
+                out.push((path.clone(), child));
+                descendants(&path, child, out);
+            }
+        }
+        Value::Sequence(sequence) => {
+            for (index, child) in sequence.iter().enumerate() {
+                let path = path.join(Segment::Index(index));
+                out.push((path.clone(), child));
+                descendants(&path, child, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Locates the path to the field at which a deserialization error occurred.
+///
+/// The `message` should already be normalized (see [`normalize()`]). If the
+/// error cannot be attributed to any field, the root path is returned.
+pub fn locate(document: &Value, message: &str) -> Path {
+    let mut nodes = Vec::new();
+    descendants(&Path::default(), document, &mut nodes);
+
+    let mut located = Path::default();
+
+    loop {
+        let next = nodes
+            .iter()
+            .filter(|(path, _)| {
+                path.depth() > located.depth() && path.segments().starts_with(located.segments())
+            })
+            .filter(|(path, value)| {
+                probe(path, (*value).clone())
+                    .flatten()
+                    .is_some_and(|m| m == message)
+            })
+            .min_by_key(|(path, _)| path.depth());
+
+        match next {
+            Some((path, _)) => located = path.clone(),
+            None => break,
+        }
+    }
+
+    if let Some(field) = message
+        .strip_prefix("unknown field `")
+        .and_then(|rest| rest.split_once('`'))
+        .map(|(field, _)| field)
+    {
+        let parent = match located.segments() {
+            // NOTE: fields within the common set of fields are flattened into
+            // the top-level mapping, so the unknown field may be a sibling
+            // rather than a child.
+            [] | [_] => Path::default(),
+            _ => located.clone(),
+        };
+
+        let path = parent.join(Segment::Key(field.to_string()));
+
+        if nodes.iter().any(|(p, _)| p == &path) {
+            return path;
+        }
+    }
+
+    located
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes() {
+        assert_eq!(
+            normalize("unknown variant `foo` at line 1 column 8"),
+            "unknown variant `foo`"
+        );
+        assert_eq!(
+            normalize("values.options[3]: invalid type: map, expected a string"),
+            "invalid type: map, expected a string"
+        );
+        assert_eq!(
+            normalize("invalid type: map, expected a string"),
+            "invalid type: map, expected a string"
+        );
+    }
+}
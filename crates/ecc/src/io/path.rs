@@ -0,0 +1,89 @@
+//! Paths to fields within a document.
+
+/// A segment within a [`Path`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// A key within a mapping.
+    Key(String),
+
+    /// An index within a sequence.
+    Index(usize),
+}
+
+/// The path to a node within a document (e.g., `values.options[3]`).
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Path(Vec<Segment>);
+
+impl Path {
+    /// Gets the segments of the path.
+    pub fn segments(&self) -> &[Segment] {
+        &self.0
+    }
+
+    /// Returns whether this is the path to the root of the document.
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Gets the number of segments in the path.
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns a new path with a segment appended.
+    pub fn join(&self, segment: Segment) -> Self {
+        let mut segments = self.0.clone();
+        segments.push(segment);
+        Self(segments)
+    }
+}
+
+impl std::fmt::Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, ".");
+        }
+
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                Segment::Key(key) if i == 0 => write!(f, "{key}")?,
+                Segment::Key(key) => write!(f, ".{key}")?,
+                Segment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl From<&serde_path_to_error::Path> for Path {
+    fn from(path: &serde_path_to_error::Path) -> Self {
+        Self(
+            path.iter()
+                .filter_map(|segment| match segment {
+                    serde_path_to_error::Segment::Seq { index } => Some(Segment::Index(*index)),
+                    serde_path_to_error::Segment::Map { key } => Some(Segment::Key(key.clone())),
+                    // NOTE: enum variants and unknown segments do not
+                    // correspond to a field within the document.
+                    _ => None,
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Path::default().to_string(), ".");
+
+        let path = Path::default()
+            .join(Segment::Key(String::from("references")))
+            .join(Segment::Index(2))
+            .join(Segment::Key(String::from("context")));
+        assert_eq!(path.to_string(), "references[2].context");
+    }
+}
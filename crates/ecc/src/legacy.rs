@@ -0,0 +1,177 @@
+//! Compatibility with the legacy flat characteristic format.
+//!
+//! Characteristics were originally recorded using a flat status layout
+//! containing only the `state`, `rfc`, `identifier`, and `date` fields (where
+//! `date` was the adoption date). This module detects documents in that
+//! layout and upgrades them to the current model so that the history of the
+//! tree remains loadable.
+//!
+//! Legacy documents that are missing fields required by their state in the
+//! current model (e.g., an adopted characteristic without values) cannot be
+//! represented in that state and are downgraded to drafts.
+
+use serde::Deserialize;
+use serde_yaml::Mapping;
+use serde_yaml::Value;
+
+use crate::Characteristic;
+
+/// The fields within the legacy layout.
+const FIELDS: &[&str] = &["state", "rfc", "identifier", "date"];
+
+/// The legacy name of the adoption date field.
+const DATE: &str = "date";
+
+/// The fields required by every state other than draft.
+const REQUIRED: &[&str] = &["name", "identifier", "rfc", "description", "values"];
+
+/// Returns whether a document uses the legacy layout.
+///
+/// A document is considered legacy if it contains the legacy `date` field or
+/// if it is a non-draft characteristic consisting solely of the legacy
+/// fields.
+pub fn is_legacy(document: &Value) -> bool {
+    document.as_mapping().is_some_and(is_legacy_mapping)
+}
+
+/// Returns whether a mapping uses the legacy layout.
+fn is_legacy_mapping(mapping: &Mapping) -> bool {
+    if mapping.contains_key(DATE) {
+        return true;
+    }
+
+    let state = mapping.get("state").and_then(|state| state.as_str());
+
+    matches!(state, Some("proposed" | "provisional" | "adopted"))
+        && mapping
+            .keys()
+            .all(|key| key.as_str().is_some_and(|key| FIELDS.contains(&key)))
+}
+
+/// Upgrades a legacy document to the current layout in place.
+///
+/// Returns whether the document was modified (i.e., whether it was a legacy
+/// document).
+pub fn upgrade(document: &mut Value) -> bool {
+    document.as_mapping_mut().is_some_and(upgrade_mapping)
+}
+
+/// Upgrades a legacy mapping to the current layout in place.
+pub(crate) fn upgrade_mapping(mapping: &mut Mapping) -> bool {
+    if !is_legacy_mapping(mapping) {
+        return false;
+    }
+
+    let entries = std::mem::take(mapping);
+    for (key, value) in entries {
+        match key.as_str() {
+            Some(DATE) => mapping.insert(Value::from("adoption_date"), value),
+            _ => mapping.insert(key, value),
+        };
+    }
+
+    if REQUIRED.iter().any(|field| !present(mapping, field)) {
+        mapping.insert(Value::from("state"), Value::from("draft"));
+
+        // NOTE: drafts cannot record an adoption date.
+        mapping.remove("adoption_date");
+    } else if mapping.get("state").and_then(|state| state.as_str()) != Some("adopted") {
+        // NOTE: the legacy layout recorded a date for every state, but only
+        // adopted characteristics have an adoption date.
+        mapping.remove("adoption_date");
+    }
+
+    true
+}
+
+/// Returns whether a field is present and not null.
+fn present(mapping: &Mapping, field: &str) -> bool {
+    mapping.get(field).is_some_and(|value| !value.is_null())
+}
+
+/// A characteristic that may be written in either the current or the legacy
+/// layout.
+///
+/// ```
+/// use ecc::legacy::Compat;
+///
+/// let yaml = "state: proposed\nrfc: https://github.com/stjudecloud/ecc/issues/1\n";
+/// let Compat(characteristic) = serde_yaml::from_str::<Compat>(yaml).unwrap();
+///
+/// assert_eq!(
+///     characteristic.rfc().unwrap().as_str(),
+///     "https://github.com/stjudecloud/ecc/issues/1"
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Compat(pub Characteristic);
+
+impl Compat {
+    /// Consumes `self` and returns the inner characteristic.
+    pub fn into_inner(self) -> Characteristic {
+        self.0
+    }
+}
+
+impl<'de> Deserialize<'de> for Compat {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let mut document = Value::deserialize(deserializer)?;
+        upgrade(&mut document);
+
+        serde_yaml::from_value::<Characteristic>(document)
+            .map(Compat)
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn current() {
+        let yaml = "state: draft\nname: A Characteristic Name\n";
+        let mut document = serde_yaml::from_str::<Value>(yaml).unwrap();
+
+        assert!(!is_legacy(&document));
+        assert!(!upgrade(&mut document));
+
+        let Compat(characteristic) = serde_yaml::from_str::<Compat>(yaml).unwrap();
+        assert_eq!(
+            characteristic,
+            serde_yaml::from_str::<Characteristic>(yaml).unwrap()
+        );
+    }
+
+    #[test]
+    fn flat() {
+        let yaml = "state: adopted\nidentifier: ECC-MORPH-000001\nrfc: \
+                    https://github.com/stjudecloud/ecc/issues/1\ndate: 2024-01-01T00:00:00Z\n";
+
+        let Compat(characteristic) = serde_yaml::from_str::<Compat>(yaml).unwrap();
+        assert!(matches!(characteristic, Characteristic::Draft { .. }));
+        assert_eq!(
+            characteristic.identifier().unwrap().to_string(),
+            "ECC-MORPH-000001"
+        );
+        assert!(characteristic.adoption_date().is_none());
+    }
+
+    #[test]
+    fn renamed_date() {
+        let yaml = "state: adopted\nidentifier: ECC-MORPH-000001\nname: A Characteristic \
+                    Name\nrfc: https://github.com/stjudecloud/ecc/issues/1\ndescription: A \
+                    description.\nvalues:\n  kind: categorical\n  options: [Foo]\ndate: \
+                    2024-01-01T00:00:00Z\n";
+
+        let Compat(characteristic) = serde_yaml::from_str::<Compat>(yaml).unwrap();
+        assert!(matches!(characteristic, Characteristic::Adopted { .. }));
+        assert_eq!(
+            characteristic.adoption_date().unwrap().to_rfc3339(),
+            "2024-01-01T00:00:00+00:00"
+        );
+    }
+}
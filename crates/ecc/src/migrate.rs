@@ -0,0 +1,237 @@
+//! Migration of characteristic documents between schema versions.
+//!
+//! Each characteristic file records the version of the schema it was written
+//! against in its top-level `schema_version` field. Files without the field
+//! predate versioning and are considered to be version `0`. When the on-disk
+//! format changes, the schema version is incremented and a migration step that
+//! upgrades documents from the previous version is added to [`STEPS`], so that
+//! older files can be upgraded rather than breaking the entire tree at once.
+
+use serde::Deserialize;
+use serde::Serialize;
+use serde_yaml::Mapping;
+use serde_yaml::Value;
+
+/// The name of the field containing the schema version.
+pub const FIELD: &str = "schema_version";
+
+/// A step that upgrades a document from one schema version to the next.
+///
+/// The step at index `i` upgrades a document from version `i` to version
+/// `i + 1`. Steps do not need to update the `schema_version` field itself.
+type Step = fn(&mut Mapping) -> Result<(), String>;
+
+/// The migration steps in order.
+const STEPS: &[Step] = &[
+    // NOTE: version `1` introduced the `schema_version` field itself.
+    // Unversioned documents may still use the legacy flat layout.
+    |mapping| {
+        crate::legacy::upgrade_mapping(mapping);
+        Ok(())
+    },
+];
+
+/// An error related to migrating a document.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    /// The document was not a mapping.
+    #[error("the document is not a mapping")]
+    NotAMapping,
+
+    /// The schema version was not a non-negative integer.
+    #[error("invalid schema version: `{0}`; expected a non-negative integer")]
+    InvalidVersion(String),
+
+    /// The schema version is newer than this version of the library supports.
+    #[error(
+        "unsupported schema version `{0}`; the newest supported version is `{current}`",
+        current = SchemaVersion::CURRENT
+    )]
+    Unsupported(SchemaVersion),
+
+    /// A migration step failed.
+    #[error("migrating from schema version `{from}`: {message}")]
+    Step {
+        /// The version being migrated from.
+        from: SchemaVersion,
+
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+/// The version of the schema that a characteristic was written against.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+pub struct SchemaVersion(u32);
+
+impl SchemaVersion {
+    /// The current version of the schema.
+    pub const CURRENT: SchemaVersion = SchemaVersion(STEPS.len() as u32);
+    /// The version of documents written before the schema was versioned.
+    pub const UNVERSIONED: SchemaVersion = SchemaVersion(0);
+
+    /// Gets the version as a number.
+    pub fn get(&self) -> u32 {
+        self.0
+    }
+}
+
+impl std::fmt::Display for SchemaVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for SchemaVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let version = SchemaVersion(u32::deserialize(deserializer)?);
+
+        // NOTE: older versions are accepted here, as documents are expected
+        // to be migrated before they are deserialized.
+        if version > SchemaVersion::CURRENT {
+            return Err(serde::de::Error::custom(Error::Unsupported(version)));
+        }
+
+        Ok(version)
+    }
+}
+
+/// Gets the schema version of a document.
+pub fn version(document: &Value) -> Result<SchemaVersion, Error> {
+    let mapping = document.as_mapping().ok_or(Error::NotAMapping)?;
+
+    let version = match mapping.get(FIELD) {
+        None => return Ok(SchemaVersion::UNVERSIONED),
+        Some(Value::Number(number)) => number
+            .as_u64()
+            .and_then(|n| u32::try_from(n).ok())
+            .map(SchemaVersion)
+            .ok_or_else(|| Error::InvalidVersion(number.to_string()))?,
+        Some(value) => {
+            let value = serde_yaml::to_string(value).unwrap_or_default();
+            return Err(Error::InvalidVersion(value.trim().to_string()));
+        }
+    };
+
+    if version > SchemaVersion::CURRENT {
+        return Err(Error::Unsupported(version));
+    }
+
+    Ok(version)
+}
+
+/// Migrates a document to the current schema version in place.
+///
+/// Returns the version the document was migrated from, or [`None`] if the
+/// document was already at the current version.
+pub fn migrate(document: &mut Value) -> Result<Option<SchemaVersion>, Error> {
+    let from = version(document)?;
+
+    if from == SchemaVersion::CURRENT {
+        return Ok(None);
+    }
+
+    // SAFETY: [`version()`] ensures that the document is a mapping.
+    let mapping = document.as_mapping_mut().unwrap();
+
+    for (version, step) in STEPS.iter().enumerate().skip(from.0 as usize) {
+        step(mapping).map_err(|message| Error::Step {
+            from: SchemaVersion(version as u32),
+            message,
+        })?;
+    }
+
+    set_version(mapping, SchemaVersion::CURRENT);
+    Ok(Some(from))
+}
+
+/// Sets the schema version of a document.
+///
+/// The field is placed directly after the `state` field (or first if there is
+/// no `state` field) so that it is easy to find.
+fn set_version(mapping: &mut Mapping, version: SchemaVersion) {
+    let value = Value::from(version.0);
+
+    if let Some(existing) = mapping.get_mut(FIELD) {
+        *existing = value;
+        return;
+    }
+
+    let entries = std::mem::take(mapping);
+    let position = entries
+        .keys()
+        .position(|key| key.as_str() == Some("state"))
+        .map(|i| i + 1)
+        .unwrap_or(0);
+
+    for (i, (key, existing)) in entries.into_iter().enumerate() {
+        if i == position {
+            mapping.insert(Value::from(FIELD), value.clone());
+        }
+
+        mapping.insert(key, existing);
+    }
+
+    if !mapping.contains_key(FIELD) {
+        mapping.insert(Value::from(FIELD), value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_unversioned() {
+        let mut document =
+            serde_yaml::from_str::<Value>("state: draft\nname: A Characteristic Name\n").unwrap();
+
+        assert_eq!(
+            migrate(&mut document).unwrap(),
+            Some(SchemaVersion::UNVERSIONED)
+        );
+        assert_eq!(
+            serde_yaml::to_string(&document).unwrap(),
+            format!(
+                "state: draft\nschema_version: {}\nname: A Characteristic Name\n",
+                SchemaVersion::CURRENT
+            )
+        );
+
+        // NOTE: migrating again does nothing.
+        assert_eq!(migrate(&mut document).unwrap(), None);
+    }
+
+    #[test]
+    fn migrates_legacy() {
+        let mut document = serde_yaml::from_str::<Value>(
+            "state: proposed\nrfc: https://github.com/stjudecloud/ecc/issues/1\ndate: \
+             2024-01-01T00:00:00Z\n",
+        )
+        .unwrap();
+
+        migrate(&mut document).unwrap();
+        assert_eq!(
+            serde_yaml::to_string(&document).unwrap(),
+            format!(
+                "state: draft\nschema_version: {}\nrfc: https://github.com/stjudecloud/ecc/issues/1\n",
+                SchemaVersion::CURRENT
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_versions() {
+        let document = serde_yaml::from_str::<Value>("schema_version: foo\n").unwrap();
+        assert!(matches!(version(&document), Err(Error::InvalidVersion(_))));
+
+        let document = serde_yaml::from_str::<Value>("schema_version: 999\n").unwrap();
+        assert!(matches!(version(&document), Err(Error::Unsupported(_))));
+
+        let error = serde_yaml::from_str::<SchemaVersion>("999").unwrap_err();
+        assert!(error.to_string().contains("unsupported schema version"));
+    }
+}
@@ -0,0 +1,474 @@
+//! Filtering of characteristics.
+//!
+//! A [`Filter`] is a set of criteria that a characteristic either matches or
+//! does not. Filters can be built up in code or parsed from a compact string
+//! syntax of space-separated `key:value` terms:
+//!
+//! ```
+//! use ecc::Category;
+//! use ecc::State;
+//! use ecc::query::Filter;
+//!
+//! let filter = "state:adopted category:molec".parse::<Filter>().unwrap();
+//! assert_eq!(
+//!     filter,
+//!     Filter::default()
+//!         .state(State::Adopted)
+//!         .category(Category::Molecular)
+//! );
+//! ```
+//!
+//! The following keys are supported:
+//!
+//! * `state`: the state (e.g., `adopted`),
+//! * `category`: the category (e.g., `molecular` or `molec`),
+//! * `kind`: the kind of permissible values (e.g., `binary`),
+//! * `tag`: a tag (e.g., `pediatric`),
+//! * `adopted-after`: adopted after a date (e.g., `2024-01-01`), and
+//! * `adopted-before`: adopted before a date.
+//!
+//! Terms with different keys must all match. A key may be given more than
+//! once (or with comma-separated values, e.g., `state:proposed,provisional`)
+//! to match any of the values.
+//!
+//! A [`Query`] applies a filter to a [`CharacteristicSet`] (see
+//! [`CharacteristicSet::query()`]).
+
+use chrono::DateTime;
+use chrono::NaiveDate;
+use chrono::Utc;
+
+use crate::Category;
+use crate::Characteristic;
+use crate::CharacteristicSet;
+use crate::State;
+use crate::common::Tag;
+
+/// The kinds of permissible values that can be filtered on.
+const KINDS: [&str; 6] = [
+    "binary",
+    "categorical",
+    "numerical",
+    "fraction",
+    "immunophenotype",
+    "other",
+];
+
+/// An error when parsing a filter.
+#[derive(Debug, PartialEq, Eq, thiserror::Error)]
+pub enum ParseError {
+    /// A term was not of the form `key:value`.
+    #[error("invalid filter term `{0}`: expected `key:value`")]
+    Term(String),
+
+    /// A term used a key that is not supported.
+    #[error(
+        "unknown filter key `{0}`: expected `state`, `category`, `kind`, `tag`, `adopted-after`, \
+         or `adopted-before`"
+    )]
+    Key(String),
+
+    /// A term had a value that is not valid for its key.
+    #[error("invalid value for filter key `{key}`: {reason}")]
+    Value {
+        /// The key.
+        key: String,
+
+        /// The reason the value is invalid.
+        reason: String,
+    },
+}
+
+/// A set of criteria for selecting characteristics.
+///
+/// An empty filter matches every characteristic.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Filter {
+    /// The states to match (any state if empty).
+    states: Vec<State>,
+
+    /// The categories to match (any category if empty).
+    categories: Vec<Category>,
+
+    /// The kinds of permissible values to match (any kind if empty).
+    kinds: Vec<&'static str>,
+
+    /// The tags to match (any tags if empty).
+    tags: Vec<Tag>,
+
+    /// Only characteristics adopted after this time match.
+    adopted_after: Option<DateTime<Utc>>,
+
+    /// Only characteristics adopted before this time match.
+    adopted_before: Option<DateTime<Utc>>,
+}
+
+impl Filter {
+    /// Matches characteristics in a state (in addition to any other states
+    /// already added).
+    pub fn state(mut self, state: State) -> Self {
+        if !self.states.contains(&state) {
+            self.states.push(state);
+        }
+
+        self
+    }
+
+    /// Matches characteristics in a category (in addition to any other
+    /// categories already added).
+    ///
+    /// The category of a characteristic is determined by its identifier, so
+    /// characteristics without one never match.
+    pub fn category(mut self, category: Category) -> Self {
+        if !self.categories.contains(&category) {
+            self.categories.push(category);
+        }
+
+        self
+    }
+
+    /// Matches characteristics with a tag (in addition to any other tags
+    /// already added).
+    pub fn tag(mut self, tag: Tag) -> Self {
+        if !self.tags.contains(&tag) {
+            self.tags.push(tag);
+        }
+
+        self
+    }
+
+    /// Matches characteristics adopted after a time.
+    ///
+    /// Characteristics that have not been adopted never match.
+    pub fn adopted_after(mut self, time: DateTime<Utc>) -> Self {
+        self.adopted_after = Some(time);
+        self
+    }
+
+    /// Matches characteristics adopted before a time.
+    ///
+    /// Characteristics that have not been adopted never match.
+    pub fn adopted_before(mut self, time: DateTime<Utc>) -> Self {
+        self.adopted_before = Some(time);
+        self
+    }
+
+    /// Gets whether the filter is empty (i.e., matches every characteristic).
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Gets whether a characteristic matches the filter.
+    pub fn matches(&self, characteristic: &Characteristic) -> bool {
+        if !self.states.is_empty() && !self.states.contains(&characteristic.state()) {
+            return false;
+        }
+
+        if !self.categories.is_empty()
+            && !characteristic
+                .identifier()
+                .is_some_and(|identifier| self.categories.contains(&identifier.category()))
+        {
+            return false;
+        }
+
+        if !self.kinds.is_empty()
+            && !characteristic
+                .values()
+                .is_some_and(|kind| self.kinds.contains(&kind.name()))
+        {
+            return false;
+        }
+
+        if !self.tags.is_empty()
+            && !characteristic
+                .tags()
+                .iter()
+                .any(|tag| self.tags.contains(tag))
+        {
+            return false;
+        }
+
+        if self.adopted_after.is_some() || self.adopted_before.is_some() {
+            let Some(date) = characteristic.adoption_date() else {
+                return false;
+            };
+
+            if self.adopted_after.is_some_and(|after| *date <= after)
+                || self.adopted_before.is_some_and(|before| *date >= before)
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// Parses a date (e.g., `2024-01-01`) as midnight UTC.
+fn date(key: &str, value: &str) -> Result<DateTime<Utc>, ParseError> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(|date| date.and_time(Default::default()).and_utc())
+        .map_err(|err| ParseError::Value {
+            key: key.to_string(),
+            reason: format!("`{value}` is not a date of the form `YYYY-MM-DD` ({err})"),
+        })
+}
+
+impl std::str::FromStr for Filter {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filter = Self::default();
+
+        for term in s.split_whitespace() {
+            let Some((key, values)) = term.split_once(':') else {
+                return Err(ParseError::Term(term.to_string()));
+            };
+
+            let invalid = |reason: String| ParseError::Value {
+                key: key.to_string(),
+                reason,
+            };
+
+            for value in values.split(',') {
+                filter = match key {
+                    "state" => {
+                        filter.state(value.parse().map_err(|err| invalid(format!("{err}")))?)
+                    }
+                    "category" => {
+                        filter.category(value.parse().map_err(|err| invalid(format!("{err}")))?)
+                    }
+                    "kind" => {
+                        let kind = KINDS
+                            .into_iter()
+                            .find(|kind| kind.eq_ignore_ascii_case(value))
+                            .ok_or_else(|| {
+                                invalid(format!(
+                                    "unknown kind `{value}`; expected `binary`, `categorical`, \
+                                     `numerical`, `fraction`, `immunophenotype`, or `other`"
+                                ))
+                            })?;
+
+                        if !filter.kinds.contains(&kind) {
+                            filter.kinds.push(kind);
+                        }
+
+                        filter
+                    }
+                    "tag" => filter.tag(value.parse().map_err(|err| invalid(format!("{err}")))?),
+                    "adopted-after" => filter.adopted_after(date(key, value)?),
+                    "adopted-before" => filter.adopted_before(date(key, value)?),
+                    _ => return Err(ParseError::Key(key.to_string())),
+                };
+            }
+        }
+
+        Ok(filter)
+    }
+}
+
+/// A query over the characteristics within a set.
+///
+/// Characteristics are yielded in the order of the set (i.e., by identifier).
+#[derive(Clone, Debug)]
+pub struct Query<'a> {
+    /// The set being queried.
+    set: &'a CharacteristicSet,
+
+    /// The criteria.
+    filter: Filter,
+}
+
+impl<'a> Query<'a> {
+    /// Creates a query that matches every characteristic within a set.
+    pub fn new(set: &'a CharacteristicSet) -> Self {
+        Self {
+            set,
+            filter: Filter::default(),
+        }
+    }
+
+    /// Replaces the criteria with a filter.
+    pub fn filter(mut self, filter: Filter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Matches characteristics in a state (see [`Filter::state()`]).
+    pub fn state(mut self, state: State) -> Self {
+        self.filter = self.filter.state(state);
+        self
+    }
+
+    /// Matches characteristics in a category (see [`Filter::category()`]).
+    pub fn category(mut self, category: Category) -> Self {
+        self.filter = self.filter.category(category);
+        self
+    }
+
+    /// Matches characteristics with a tag (see [`Filter::tag()`]).
+    pub fn tag(mut self, tag: Tag) -> Self {
+        self.filter = self.filter.tag(tag);
+        self
+    }
+
+    /// Matches characteristics adopted after a time (see
+    /// [`Filter::adopted_after()`]).
+    pub fn adopted_after(mut self, time: DateTime<Utc>) -> Self {
+        self.filter = self.filter.adopted_after(time);
+        self
+    }
+
+    /// Matches characteristics adopted before a time (see
+    /// [`Filter::adopted_before()`]).
+    pub fn adopted_before(mut self, time: DateTime<Utc>) -> Self {
+        self.filter = self.filter.adopted_before(time);
+        self
+    }
+
+    /// Gets an iterator over the matching characteristics.
+    pub fn iter(&self) -> impl Iterator<Item = &'a Characteristic> + '_ {
+        self.set
+            .iter()
+            .filter(|characteristic| self.filter.matches(characteristic))
+    }
+
+    /// Gets the number of matching characteristics.
+    pub fn count(&self) -> usize {
+        self.iter().count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone as _;
+
+    use super::*;
+    use crate::Identifier;
+    use crate::common::Common;
+    use crate::common::value::Kind;
+
+    /// Gets a characteristic in a state with an identifier.
+    fn characteristic(identifier: &str, adopted: Option<(i32, u32, u32)>) -> Characteristic {
+        let common = Common::new(
+            "A Characteristic Name",
+            identifier.parse::<Identifier>().unwrap(),
+            "https://github.com/stjudecloud/ecc/issues/1"
+                .parse()
+                .unwrap(),
+            "A description of the characteristic.",
+            Kind::Binary {
+                description: crate::common::value::kind::binary::Description {
+                    r#true: crate::field::Description {
+                        summary: "Present.".parse().unwrap(),
+                        details: "It was observed.".parse().unwrap(),
+                    },
+                    r#false: crate::field::Description {
+                        summary: "Absent.".parse().unwrap(),
+                        details: "It was not observed.".parse().unwrap(),
+                    },
+                },
+                threshold: None,
+            },
+        )
+        .unwrap();
+
+        match adopted {
+            Some((year, month, day)) => Characteristic::Adopted {
+                common,
+                adoption_date: Utc.with_ymd_and_hms(year, month, day, 0, 0, 0).unwrap(),
+            },
+            None => Characteristic::Proposed { common },
+        }
+    }
+
+    /// Gets the identifiers of the characteristics matched by a query.
+    fn identifiers(query: &Query<'_>) -> Vec<String> {
+        query
+            .iter()
+            .map(|characteristic| characteristic.identifier().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn query() {
+        let mut tagged = characteristic("ECC-MOLEC-000002", Some((2024, 6, 1)));
+
+        if let Characteristic::Adopted { common, .. } = &mut tagged {
+            common.set_tags(vec!["pediatric".parse().unwrap()]);
+        }
+
+        let set = [
+            characteristic("ECC-MOLEC-000001", Some((2023, 6, 1))),
+            tagged,
+            characteristic("ECC-MOLEC-000003", None),
+            characteristic("ECC-MORPH-000001", Some((2024, 6, 1))),
+        ]
+        .into_iter()
+        .collect::<CharacteristicSet>();
+
+        assert_eq!(set.query().count(), 4);
+
+        let query = set
+            .query()
+            .state(State::Adopted)
+            .category(Category::Molecular)
+            .adopted_after(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(identifiers(&query), ["ECC-MOLEC-000002"]);
+
+        let query = set.query().state(State::Proposed).state(State::Provisional);
+        assert_eq!(identifiers(&query), ["ECC-MOLEC-000003"]);
+
+        let query = set
+            .query()
+            .adopted_before(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(identifiers(&query), ["ECC-MOLEC-000001"]);
+
+        let filter = "category:morph kind:binary".parse::<Filter>().unwrap();
+        assert_eq!(
+            identifiers(&set.query().filter(filter)),
+            ["ECC-MORPH-000001"]
+        );
+
+        let filter = "tag:pediatric,solid-tumor".parse::<Filter>().unwrap();
+        assert_eq!(
+            identifiers(&set.query().filter(filter)),
+            ["ECC-MOLEC-000002"]
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert!("".parse::<Filter>().unwrap().is_empty());
+
+        let filter = "state:proposed,provisional state:proposed adopted-after:2024-01-01"
+            .parse::<Filter>()
+            .unwrap();
+        assert_eq!(
+            filter,
+            Filter::default()
+                .state(State::Proposed)
+                .state(State::Provisional)
+                .adopted_after(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap())
+        );
+
+        assert_eq!(
+            "adopted".parse::<Filter>().unwrap_err().to_string(),
+            "invalid filter term `adopted`: expected `key:value`"
+        );
+        assert!(matches!(
+            "status:adopted".parse::<Filter>(),
+            Err(ParseError::Key(_))
+        ));
+        assert_eq!(
+            "state:retired".parse::<Filter>().unwrap_err().to_string(),
+            "invalid value for filter key `state`: unknown state: `retired`; expected `draft`, \
+             `proposed`, `provisional`, or `adopted`"
+        );
+        assert!(matches!(
+            "adopted-after:2024".parse::<Filter>(),
+            Err(ParseError::Value { .. })
+        ));
+    }
+}
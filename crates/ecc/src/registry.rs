@@ -0,0 +1,214 @@
+//! Compact handles to identifiers.
+//!
+//! Tooling that works at the scale of a cohort (e.g., millions of annotations)
+//! refers to the same few thousand identifiers over and over. An [`Interner`]
+//! stores each distinct identifier once, along with its formatted string, and
+//! hands out [`IdentifierId`]s: 4-byte, [`Copy`] handles that can be compared,
+//! hashed, and stored inline (e.g., within a `SmallVec<[IdentifierId; 8]>`)
+//! without any allocation.
+//!
+//! ```
+//! use ecc::registry::Interner;
+//!
+//! let mut interner = Interner::new();
+//! let a = interner.intern_str("ECC-MOLEC-000001").unwrap();
+//! let b = interner.intern_str("ECC-MOLEC-000001").unwrap();
+//!
+//! assert_eq!(a, b);
+//! assert_eq!(interner.as_str(a), Some("ECC-MOLEC-000001"));
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::string::ToString as _;
+use alloc::vec::Vec;
+
+use crate::Identifier;
+use crate::identifier::ParseError;
+
+/// A compact handle to an identifier within an [`Interner`].
+///
+/// Handles are only meaningful to the interner that created them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IdentifierId(u32);
+
+impl IdentifierId {
+    /// Gets the position of the identifier within its interner (in the order
+    /// the identifiers were first interned).
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// An interned identifier.
+#[derive(Clone, Debug)]
+struct Entry {
+    /// The identifier.
+    identifier: Identifier,
+
+    /// The formatted identifier.
+    string: String,
+}
+
+/// Stores each distinct identifier once and hands out compact handles to
+/// them.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    /// The interned identifiers (indexed by handle).
+    entries: Vec<Entry>,
+
+    /// The handle of each interned identifier.
+    ids: BTreeMap<Identifier, IdentifierId>,
+}
+
+impl Interner {
+    /// Creates an empty interner.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Interns an identifier, returning its handle.
+    ///
+    /// Interning the same identifier again returns the same handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`u32::MAX`] distinct identifiers are interned.
+    pub fn intern(&mut self, identifier: Identifier) -> IdentifierId {
+        if let Some(id) = self.ids.get(&identifier) {
+            return *id;
+        }
+
+        let id = u32::try_from(self.entries.len())
+            .map(IdentifierId)
+            .expect("too many identifiers to intern");
+
+        self.entries.push(Entry {
+            string: identifier.to_string(),
+            identifier: identifier.clone(),
+        });
+        self.ids.insert(identifier, id);
+
+        id
+    }
+
+    /// Parses and interns an identifier, returning its handle.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`u32::MAX`] distinct identifiers are interned.
+    pub fn intern_str(&mut self, s: &str) -> Result<IdentifierId, ParseError> {
+        let identifier = s.parse::<Identifier>()?;
+        Ok(self.intern(identifier))
+    }
+
+    /// Interns many identifiers, appending their handles to a collection
+    /// (e.g., a `Vec` or a `SmallVec`).
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`u32::MAX`] distinct identifiers are interned.
+    pub fn intern_into(
+        &mut self,
+        identifiers: impl IntoIterator<Item = Identifier>,
+        ids: &mut impl Extend<IdentifierId>,
+    ) {
+        ids.extend(
+            identifiers
+                .into_iter()
+                .map(|identifier| self.intern(identifier)),
+        );
+    }
+
+    /// Gets the handle of an identifier if it has been interned.
+    pub fn get(&self, identifier: &Identifier) -> Option<IdentifierId> {
+        self.ids.get(identifier).copied()
+    }
+
+    /// Gets the identifier for a handle.
+    ///
+    /// Returns [`None`] if the handle was not created by this interner.
+    pub fn resolve(&self, id: IdentifierId) -> Option<&Identifier> {
+        self.entries.get(id.index()).map(|entry| &entry.identifier)
+    }
+
+    /// Gets the formatted identifier for a handle (without formatting it
+    /// again).
+    ///
+    /// Returns [`None`] if the handle was not created by this interner.
+    pub fn as_str(&self, id: IdentifierId) -> Option<&str> {
+        self.entries
+            .get(id.index())
+            .map(|entry| entry.string.as_str())
+    }
+
+    /// Gets the number of distinct identifiers that have been interned.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Gets whether no identifiers have been interned.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterates over the handles and identifiers in the order they were first
+    /// interned.
+    pub fn iter(&self) -> impl Iterator<Item = (IdentifierId, &Identifier)> {
+        self.entries
+            .iter()
+            .enumerate()
+            // SAFETY: there are never more than `u32::MAX` entries (see
+            // [`Interner::intern()`]), so the cast never truncates.
+            .map(|(i, entry)| (IdentifierId(i as u32), &entry.identifier))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use super::*;
+    use crate::Category;
+
+    #[test]
+    fn interns() {
+        let mut interner = Interner::new();
+        let a = interner.intern(Identifier::molecular(1).unwrap());
+        let b = interner.intern_str("ECC-MORPH-000002").unwrap();
+
+        assert_ne!(a, b);
+        assert_eq!(interner.intern_str("ECC-MOLEC-000001").unwrap(), a);
+        assert_eq!(interner.len(), 2);
+
+        assert_eq!(interner.resolve(b), Identifier::morphological(2).as_ref());
+        assert_eq!(interner.as_str(a), Some("ECC-MOLEC-000001"));
+        assert_eq!(interner.get(&Identifier::molecular(3).unwrap()), None);
+        assert_eq!(interner.resolve(IdentifierId(2)), None);
+
+        assert!(interner.intern_str("ECC-MOLEC-1").is_err());
+        assert_eq!(interner.len(), 2);
+    }
+
+    #[test]
+    fn intern_into() {
+        let mut interner = Interner::new();
+        let mut ids = vec![];
+
+        interner.intern_into(
+            [1, 2, 1].map(|n| Identifier::new(Category::Molecular, n).unwrap()),
+            &mut ids,
+        );
+
+        assert_eq!(ids.len(), 3);
+        assert_eq!(ids[0], ids[2]);
+        assert_eq!(
+            interner
+                .iter()
+                .map(|(id, _)| id.index())
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+        assert_eq!(core::mem::size_of::<IdentifierId>(), 4);
+    }
+}
@@ -4,8 +4,6 @@ use std::ops::Deref;
 use std::sync::LazyLock;
 
 use regex::Regex;
-use serde_with::DeserializeFromStr;
-use serde_with::SerializeDisplay;
 use url::Url;
 
 /// The regex that the link needs to match to be valid.
@@ -60,7 +58,11 @@ impl std::error::Error for ParseError {}
 ////////////////////////////////////////////////////////////////////////////////////////
 
 /// A link to an RFC for a composable characteristic.
-#[derive(Clone, Debug, PartialEq, Eq, SerializeDisplay, DeserializeFromStr)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
 pub struct Link(Url);
 
 impl Link {
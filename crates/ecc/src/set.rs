@@ -0,0 +1,385 @@
+//! Sets of characteristics keyed by identifier.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::btree_set;
+
+use crate::Characteristic;
+use crate::Identifier;
+use crate::common::Relation;
+use crate::common::relation;
+use crate::query::Query;
+
+/// A set of characteristics keyed by identifier.
+///
+/// Characteristics that have not been assigned an identifier (i.e., early
+/// drafts) cannot be referred to from outside of the set and are not included.
+///
+/// Beyond the characteristics themselves, the set records the dependencies
+/// between characteristics: a characteristic that depends on another may only
+/// be recorded for a sample alongside the characteristic it depends on. The
+/// typed relations that characteristics declare to one another can be checked
+/// for consistency with [`CharacteristicSet::relation_issues()`].
+#[derive(Clone, Debug, Default)]
+pub struct CharacteristicSet {
+    /// The characteristics.
+    characteristics: BTreeMap<Identifier, Characteristic>,
+
+    /// The characteristics that each characteristic depends on.
+    dependencies: BTreeMap<Identifier, BTreeSet<Identifier>>,
+}
+
+impl CharacteristicSet {
+    /// Adds a characteristic to the set.
+    ///
+    /// Returns the characteristic that was replaced (if any). Characteristics
+    /// without an identifier are handed back unchanged.
+    pub fn insert(&mut self, characteristic: Characteristic) -> Option<Characteristic> {
+        match characteristic.identifier() {
+            Some(identifier) => self
+                .characteristics
+                .insert(identifier.clone(), characteristic),
+            None => Some(characteristic),
+        }
+    }
+
+    /// Records that one characteristic depends on another.
+    pub fn with_dependency(mut self, dependent: Identifier, dependency: Identifier) -> Self {
+        self.dependencies
+            .entry(dependent)
+            .or_default()
+            .insert(dependency);
+        self
+    }
+
+    /// Gets a characteristic by its identifier.
+    pub fn get(&self, identifier: &Identifier) -> Option<&Characteristic> {
+        self.characteristics.get(identifier)
+    }
+
+    /// Gets the characteristics that a characteristic depends on.
+    pub fn dependencies(&self, identifier: &Identifier) -> btree_set::Iter<'_, Identifier> {
+        self.dependencies
+            .get(identifier)
+            .map(BTreeSet::iter)
+            .unwrap_or_default()
+    }
+
+    /// Checks the typed relations between the characteristics.
+    ///
+    /// The following problems are reported:
+    ///
+    /// * relations that target the characteristic itself or a characteristic
+    ///   that is not within the set,
+    /// * relations that have an inverse (e.g., `refines` and `refined-by`) but
+    ///   whose target does not declare it, and
+    /// * `supersedes` or refinement relations that form a cycle.
+    pub fn relation_issues(&self) -> Vec<relation::Issue> {
+        let mut issues = Vec::new();
+
+        for (identifier, characteristic) in &self.characteristics {
+            for relation in characteristic.relations() {
+                let (kind, target) = (relation.kind(), relation.target());
+
+                if target == identifier {
+                    issues.push(relation::Issue::SelfRelation {
+                        identifier: identifier.clone(),
+                        kind,
+                    });
+                    continue;
+                }
+
+                let Some(related) = self.characteristics.get(target) else {
+                    issues.push(relation::Issue::UnknownTarget {
+                        characteristic: identifier.clone(),
+                        kind,
+                        target: target.clone(),
+                    });
+                    continue;
+                };
+
+                if let Some(inverse) = kind.inverse() {
+                    let expected = Relation::new(inverse, identifier.clone());
+
+                    if !related.relations().contains(&expected) {
+                        issues.push(relation::Issue::MissingInverse {
+                            characteristic: identifier.clone(),
+                            kind,
+                            target: target.clone(),
+                            inverse,
+                        });
+                    }
+                }
+            }
+        }
+
+        for kind in [relation::Kind::Supersedes, relation::Kind::Refines] {
+            issues.extend(self.cycles(kind));
+        }
+
+        issues
+    }
+
+    /// Finds the cycles formed by `supersedes` or refinement relations.
+    ///
+    /// A `refined-by` relation is treated as a `refines` relation in the
+    /// opposite direction.
+    fn cycles(&self, kind: relation::Kind) -> Vec<relation::Issue> {
+        let mut edges = BTreeMap::<&Identifier, BTreeSet<&Identifier>>::new();
+
+        for (identifier, characteristic) in &self.characteristics {
+            for relation in characteristic.relations() {
+                let target = relation.target();
+
+                if target == identifier || !self.characteristics.contains_key(target) {
+                    continue;
+                }
+
+                match relation.kind() {
+                    k if k == kind => edges.entry(identifier).or_default().insert(target),
+                    relation::Kind::RefinedBy if kind == relation::Kind::Refines => {
+                        edges.entry(target).or_default().insert(identifier)
+                    }
+                    _ => continue,
+                };
+            }
+        }
+
+        let mut visited = BTreeSet::new();
+        let mut stack = Vec::new();
+        let mut issues = Vec::new();
+
+        for start in edges.keys() {
+            visit(start, &edges, &mut visited, &mut stack, &mut |cycle| {
+                issues.push(relation::Issue::Cycle {
+                    kind,
+                    identifiers: cycle.into_iter().cloned().collect(),
+                })
+            });
+        }
+
+        issues
+    }
+
+    /// Gets an iterator over the characteristics (ordered by identifier).
+    pub fn iter(&self) -> impl Iterator<Item = &Characteristic> {
+        self.characteristics.values()
+    }
+
+    /// Starts a query over the characteristics (see [`crate::query`]).
+    pub fn query(&self) -> Query<'_> {
+        Query::new(self)
+    }
+
+    /// Gets the number of characteristics.
+    pub fn len(&self) -> usize {
+        self.characteristics.len()
+    }
+
+    /// Returns whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.characteristics.is_empty()
+    }
+}
+
+/// Visits the nodes reachable from a node in depth-first order, calling
+/// `report` with each cycle that is found.
+fn visit<'a>(
+    node: &'a Identifier,
+    edges: &BTreeMap<&'a Identifier, BTreeSet<&'a Identifier>>,
+    visited: &mut BTreeSet<&'a Identifier>,
+    stack: &mut Vec<&'a Identifier>,
+    report: &mut impl FnMut(Vec<&'a Identifier>),
+) {
+    if let Some(i) = stack.iter().position(|other| *other == node) {
+        let mut cycle = stack[i..].to_vec();
+        cycle.push(node);
+        return report(cycle);
+    }
+
+    if !visited.insert(node) {
+        return;
+    }
+
+    stack.push(node);
+
+    for next in edges.get(node).into_iter().flatten() {
+        visit(next, edges, visited, stack, report);
+    }
+
+    stack.pop();
+}
+
+impl FromIterator<Characteristic> for CharacteristicSet {
+    fn from_iter<T: IntoIterator<Item = Characteristic>>(iter: T) -> Self {
+        let mut set = Self::default();
+
+        for characteristic in iter {
+            set.insert(characteristic);
+        }
+
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::Common;
+    use crate::common::relation::Issue;
+    use crate::common::relation::Kind;
+
+    /// Gets a proposed characteristic with relations to other
+    /// characteristics.
+    fn characteristic(identifier: &str, relations: &[(Kind, &str)]) -> Characteristic {
+        let mut common = Common::new(
+            "A Characteristic Name",
+            identifier.parse::<Identifier>().unwrap(),
+            "https://github.com/stjudecloud/ecc/issues/1"
+                .parse()
+                .unwrap(),
+            "A description of the characteristic.",
+            crate::common::value::Kind::Binary {
+                description: crate::common::value::kind::binary::Description {
+                    r#true: crate::field::Description {
+                        summary: "Present.".parse().unwrap(),
+                        details: "It was observed.".parse().unwrap(),
+                    },
+                    r#false: crate::field::Description {
+                        summary: "Absent.".parse().unwrap(),
+                        details: "It was not observed.".parse().unwrap(),
+                    },
+                },
+                threshold: None,
+            },
+        )
+        .unwrap();
+
+        common.set_relations(
+            relations
+                .iter()
+                .map(|(kind, target)| Relation::new(*kind, target.parse().unwrap()))
+                .collect(),
+        );
+
+        Characteristic::Proposed { common }
+    }
+
+    #[test]
+    fn relations() {
+        let set = [
+            characteristic("ECC-MOLEC-000001", &[(Kind::RefinedBy, "ECC-MOLEC-000002")]),
+            characteristic(
+                "ECC-MOLEC-000002",
+                &[
+                    (Kind::Refines, "ECC-MOLEC-000001"),
+                    (Kind::MutuallyExclusiveWith, "ECC-MOLEC-000003"),
+                ],
+            ),
+            characteristic(
+                "ECC-MOLEC-000003",
+                &[
+                    (Kind::MutuallyExclusiveWith, "ECC-MOLEC-000002"),
+                    (Kind::Supersedes, "ECC-MOLEC-000001"),
+                ],
+            ),
+        ]
+        .into_iter()
+        .collect::<CharacteristicSet>();
+
+        assert!(set.relation_issues().is_empty());
+    }
+
+    #[test]
+    fn relation_issues() {
+        let set = [
+            characteristic(
+                "ECC-MOLEC-000001",
+                &[
+                    (Kind::Refines, "ECC-MOLEC-000002"),
+                    (Kind::Supersedes, "ECC-MOLEC-000001"),
+                ],
+            ),
+            characteristic(
+                "ECC-MOLEC-000002",
+                &[
+                    (Kind::MutuallyExclusiveWith, "ECC-MOLEC-000009"),
+                    (Kind::Supersedes, "ECC-MOLEC-000003"),
+                ],
+            ),
+            characteristic(
+                "ECC-MOLEC-000003",
+                &[(Kind::Supersedes, "ECC-MOLEC-000002")],
+            ),
+        ]
+        .into_iter()
+        .collect::<CharacteristicSet>();
+
+        let identifier = |s: &str| s.parse::<Identifier>().unwrap();
+
+        assert_eq!(
+            set.relation_issues(),
+            [
+                Issue::MissingInverse {
+                    characteristic: identifier("ECC-MOLEC-000001"),
+                    kind: Kind::Refines,
+                    target: identifier("ECC-MOLEC-000002"),
+                    inverse: Kind::RefinedBy,
+                },
+                Issue::SelfRelation {
+                    identifier: identifier("ECC-MOLEC-000001"),
+                    kind: Kind::Supersedes,
+                },
+                Issue::UnknownTarget {
+                    characteristic: identifier("ECC-MOLEC-000002"),
+                    kind: Kind::MutuallyExclusiveWith,
+                    target: identifier("ECC-MOLEC-000009"),
+                },
+                Issue::Cycle {
+                    kind: Kind::Supersedes,
+                    identifiers: vec![
+                        identifier("ECC-MOLEC-000002"),
+                        identifier("ECC-MOLEC-000003"),
+                        identifier("ECC-MOLEC-000002"),
+                    ],
+                },
+            ]
+        );
+
+        assert_eq!(
+            set.relation_issues()[3].to_string(),
+            "`supersedes` relations form a cycle: `ECC-MOLEC-000002` -> `ECC-MOLEC-000003` -> \
+             `ECC-MOLEC-000002`"
+        );
+    }
+
+    #[test]
+    fn refinement_cycle() {
+        let set = [
+            characteristic(
+                "ECC-MOLEC-000001",
+                &[
+                    (Kind::Refines, "ECC-MOLEC-000002"),
+                    (Kind::RefinedBy, "ECC-MOLEC-000002"),
+                ],
+            ),
+            characteristic(
+                "ECC-MOLEC-000002",
+                &[
+                    (Kind::RefinedBy, "ECC-MOLEC-000001"),
+                    (Kind::Refines, "ECC-MOLEC-000001"),
+                ],
+            ),
+        ]
+        .into_iter()
+        .collect::<CharacteristicSet>();
+
+        assert!(matches!(
+            set.relation_issues().as_slice(),
+            [Issue::Cycle {
+                kind: Kind::Refines,
+                ..
+            }]
+        ));
+    }
+}
@@ -0,0 +1,129 @@
+//! States of characteristics.
+
+use alloc::string::String;
+use alloc::string::ToString as _;
+
+/// An error when parsing a state.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl core::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "unknown state: `{}`; expected `draft`, `proposed`, `provisional`, or `adopted`",
+            self.0
+        )
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// The state of a characteristic within its lifecycle.
+///
+/// States are ordered by their progression through the lifecycle (i.e., a
+/// draft comes before an adopted characteristic).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_with::SerializeDisplay, serde_with::DeserializeFromStr)
+)]
+pub enum State {
+    /// A characteristic that is currently being drafted.
+    Draft,
+
+    /// A characteristic that is currently being proposed to be adopted.
+    Proposed,
+
+    /// A characteristic that has been accepted in principle and has entered
+    /// the settling phase of adoption.
+    Provisional,
+
+    /// A characteristic that has been adopted.
+    Adopted,
+}
+
+impl State {
+    /// All states in the order of the lifecycle.
+    pub const ALL: [State; 4] = [
+        State::Draft,
+        State::Proposed,
+        State::Provisional,
+        State::Adopted,
+    ];
+
+    /// Gets the name of the state as it appears within the `state` field of a
+    /// characteristic (e.g., `adopted`).
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            State::Draft => "draft",
+            State::Proposed => "proposed",
+            State::Provisional => "provisional",
+            State::Adopted => "adopted",
+        }
+    }
+
+    /// Gets the state that follows this one within the lifecycle.
+    ///
+    /// Returns [`None`] for adopted characteristics, which are at the end of
+    /// the lifecycle.
+    pub fn next(&self) -> Option<State> {
+        match self {
+            State::Draft => Some(State::Proposed),
+            State::Proposed => Some(State::Provisional),
+            State::Provisional => Some(State::Adopted),
+            State::Adopted => None,
+        }
+    }
+}
+
+impl core::fmt::Display for State {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl core::str::FromStr for State {
+    type Err = ParseError;
+
+    /// Parses a state from its name (case insensitive).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        State::ALL
+            .into_iter()
+            .find(|state| state.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError(s.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!("adopted".parse::<State>(), Ok(State::Adopted));
+        assert_eq!("Provisional".parse::<State>(), Ok(State::Provisional));
+
+        for state in State::ALL {
+            assert_eq!(state.to_string().parse::<State>(), Ok(state));
+        }
+
+        let err = "retired".parse::<State>().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown state: `retired`; expected `draft`, `proposed`, `provisional`, or `adopted`"
+        );
+    }
+
+    #[test]
+    fn lifecycle() {
+        assert!(State::Draft < State::Proposed);
+        assert!(State::Proposed < State::Provisional);
+        assert!(State::Provisional < State::Adopted);
+        assert!(State::ALL.is_sorted());
+
+        assert_eq!(State::Draft.next(), Some(State::Proposed));
+        assert_eq!(State::Provisional.next(), Some(State::Adopted));
+        assert_eq!(State::Adopted.next(), None);
+    }
+}
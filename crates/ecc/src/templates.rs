@@ -0,0 +1,253 @@
+//! Templates for new characteristics.
+//!
+//! Each template is a pre-filled skeleton for a commonly proposed kind of
+//! characteristic, including the standard permissible values and placeholder
+//! references that contributors replace as they fill in the draft.
+
+use std::collections::BTreeSet;
+
+use nonempty::NonEmpty;
+use url::Url;
+
+use crate::Category;
+use crate::Characteristic;
+use crate::common::OptionalCommon;
+use crate::common::Reference;
+use crate::common::value::Kind;
+use crate::common::value::kind::binary;
+use crate::field;
+use crate::text::Sentence;
+
+/// The text used in place of information the contributor must fill in.
+pub const PLACEHOLDER: &str = "TODO";
+
+/// The URL used in place of a reference's URL.
+pub const PLACEHOLDER_URL: &str = "https://doi.org/10.0000/TODO";
+
+/// An error when parsing a template.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseError(String);
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown template: `{}`; expected one of {}",
+            self.0,
+            Template::ALL
+                .iter()
+                .map(|template| format!("`{template}`"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A template for a new characteristic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Template {
+    /// The presence of a molecular mutation (e.g., a single nucleotide
+    /// variant or an indel within a gene).
+    Mutation,
+
+    /// The presence of a gene fusion.
+    Fusion,
+
+    /// A morphological feature observed within a specimen.
+    Morphology,
+
+    /// The expression of an immunophenotypic marker (e.g., by flow cytometry
+    /// or immunohistochemistry).
+    ImmunophenotypeMarker,
+}
+
+impl Template {
+    /// All templates.
+    pub const ALL: [Template; 4] = [
+        Template::Mutation,
+        Template::Fusion,
+        Template::Morphology,
+        Template::ImmunophenotypeMarker,
+    ];
+
+    /// Gets the category of characteristics created from the template.
+    pub fn category(&self) -> Category {
+        match self {
+            Template::Mutation | Template::Fusion => Category::Molecular,
+            Template::Morphology | Template::ImmunophenotypeMarker => Category::Morphological,
+        }
+    }
+
+    /// Gets a short, human-readable summary of the template.
+    pub fn summary(&self) -> &'static str {
+        match self {
+            Template::Mutation => "whether a mutation is present",
+            Template::Fusion => "whether a gene fusion is present",
+            Template::Morphology => "a morphological feature observed within a specimen",
+            Template::ImmunophenotypeMarker => "the expression of an immunophenotypic marker",
+        }
+    }
+
+    /// Gets the skeleton of the description.
+    pub fn description(&self) -> String {
+        let subject = match self {
+            Template::Mutation => "the mutation, including the gene and the variants included",
+            Template::Fusion => "the fusion, including both partner genes and any breakpoints",
+            Template::Morphology => "the morphological feature and how it is assessed",
+            Template::ImmunophenotypeMarker => {
+                "the marker and the assays (e.g., flow cytometry) used to measure it"
+            }
+        };
+
+        format!(
+            "# Overview\n\n{PLACEHOLDER}: describe {subject}.\n\n## Clinical \
+             Relevance\n\n{PLACEHOLDER}: describe why this characteristic is clinically \
+             relevant.\n"
+        )
+    }
+
+    /// Gets the standard permissible values.
+    pub fn values(&self) -> Kind {
+        match self {
+            Template::Mutation => binary(
+                ("The mutation is present.", "The mutation was detected."),
+                (
+                    "The mutation is not present.",
+                    "The mutation was not detected.",
+                ),
+            ),
+            Template::Fusion => binary(
+                ("The fusion is present.", "The fusion was detected."),
+                ("The fusion is not present.", "The fusion was not detected."),
+            ),
+            Template::Morphology => categorical(&["Absent", "Indeterminate", "Present"]),
+            Template::ImmunophenotypeMarker => {
+                categorical(&["Negative", "Partial", "Positive", "Weak"])
+            }
+        }
+    }
+
+    /// Gets the placeholder references.
+    ///
+    /// A highlighted manuscript is always included so that contributors are
+    /// prompted to cite the primary evidence for the characteristic.
+    pub fn references(&self) -> NonEmpty<Reference> {
+        NonEmpty::new(Reference::Manuscript {
+            title: String::from(PLACEHOLDER),
+            authors: String::from(PLACEHOLDER),
+            context: sentence(&format!(
+                "{PLACEHOLDER}: describe how this manuscript supports the characteristic."
+            )),
+            // SAFETY: the placeholder is a valid URL.
+            url: PLACEHOLDER_URL.parse::<Url>().unwrap(),
+            highlighted: true,
+        })
+    }
+
+    /// Creates the common set of elements for a new draft from the template.
+    pub fn common(&self, name: Option<String>) -> OptionalCommon {
+        OptionalCommon {
+            name,
+            description: Some(self.description()),
+            values: Some(self.values()),
+            references: Some(self.references()),
+            ..OptionalCommon::new()
+        }
+    }
+
+    /// Creates a new draft characteristic from the template.
+    pub fn characteristic(&self, name: Option<String>) -> Characteristic {
+        Characteristic::Draft {
+            common: self.common(name),
+        }
+    }
+}
+
+impl std::fmt::Display for Template {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Template::Mutation => write!(f, "mutation"),
+            Template::Fusion => write!(f, "fusion"),
+            Template::Morphology => write!(f, "morphology"),
+            Template::ImmunophenotypeMarker => write!(f, "immunophenotype-marker"),
+        }
+    }
+}
+
+impl std::str::FromStr for Template {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Template::ALL
+            .into_iter()
+            .find(|template| template.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| ParseError(s.to_string()))
+    }
+}
+
+/// Parses a sentence that is known to be valid.
+fn sentence(s: &str) -> Sentence {
+    // SAFETY: every sentence within the templates is non-empty.
+    s.parse().unwrap()
+}
+
+/// Creates a binary kind from the summary and details of each value.
+fn binary(r#true: (&str, &str), r#false: (&str, &str)) -> Kind {
+    Kind::Binary {
+        description: binary::Description {
+            r#true: field::Description {
+                summary: sentence(r#true.0),
+                details: sentence(r#true.1),
+            },
+            r#false: field::Description {
+                summary: sentence(r#false.0),
+                details: sentence(r#false.1),
+            },
+        },
+        threshold: None,
+    }
+}
+
+/// Creates a categorical kind from a set of options.
+fn categorical(options: &[&str]) -> Kind {
+    Kind::Categorical {
+        options: options
+            .iter()
+            .map(|option| option.to_string())
+            .collect::<BTreeSet<_>>(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        for template in Template::ALL {
+            assert_eq!(template.to_string().parse::<Template>(), Ok(template));
+        }
+
+        assert_eq!(
+            "Immunophenotype-Marker".parse::<Template>(),
+            Ok(Template::ImmunophenotypeMarker)
+        );
+        assert!("foo".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn round_trip() {
+        for template in Template::ALL {
+            let characteristic = template.characteristic(Some(String::from("Foo")));
+            let yaml = serde_yaml::to_string(&characteristic).unwrap();
+
+            assert_eq!(
+                serde_yaml::from_str::<Characteristic>(&yaml).unwrap(),
+                characteristic
+            );
+            assert_eq!(characteristic.name(), Some("Foo"));
+        }
+    }
+}
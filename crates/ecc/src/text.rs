@@ -1,5 +1,8 @@
 //! Text representations.
 
+pub mod language;
 pub mod sentence;
+pub mod similar;
 
+pub use language::LanguageTag;
 pub use sentence::Sentence;
@@ -0,0 +1,324 @@
+//! Language tags and translations.
+//!
+//! Names are written in English. Partners that present the registry in other
+//! languages can add translated names keyed by a [BCP 47] language tag (e.g.,
+//! `de` or `pt-BR`), and consumers look them up with [`translate()`], falling
+//! back to the English name when no translation exists.
+//!
+//! [BCP 47]: https://www.rfc-editor.org/info/bcp47
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::string::ToString as _;
+use alloc::vec::Vec;
+
+use thiserror::Error;
+
+/// Translated names keyed by their language.
+pub type Translations = BTreeMap<LanguageTag, String>;
+
+/// A parse error related to a [`LanguageTag`].
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ParseError {
+    /// The language tag was empty.
+    #[error("the language tag was empty")]
+    Empty,
+
+    /// The language tag was not well formed.
+    #[error("`{0}` is not a well-formed BCP 47 language tag")]
+    Invalid(String),
+}
+
+/// A well-formed [BCP 47] language tag (e.g., `en`, `zh-Hant-TW`, or
+/// `pt-BR`).
+///
+/// Tags are checked for their structure but not against the language subtag
+/// registry. The case of each subtag is normalized when parsing (e.g., `pt-br`
+/// is parsed as `pt-BR`). Grandfathered tags and tags consisting only of a
+/// private use subtag (e.g., `x-klingon`) are not supported.
+///
+/// [BCP 47]: https://www.rfc-editor.org/info/bcp47
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde_with::DeserializeFromStr)
+)]
+pub struct LanguageTag(String);
+
+impl LanguageTag {
+    /// Gets the language tag as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+
+    /// Gets the primary language subtag (e.g., `pt` for `pt-BR`).
+    pub fn language(&self) -> &str {
+        self.0.split('-').next().unwrap_or_default()
+    }
+
+    /// Gets the tags to look up a translation with, from the most to the
+    /// least specific.
+    ///
+    /// The tag itself comes first, followed by the tag with its last subtag
+    /// progressively removed (e.g., `zh-Hant-TW`, `zh-Hant`, and then `zh`).
+    /// Single-character subtags are removed along with the subtag that follows
+    /// them, as they only introduce an extension.
+    pub fn fallbacks(&self) -> Vec<&str> {
+        let mut result = Vec::new();
+        let mut tag = self.as_str();
+
+        loop {
+            result.push(tag);
+
+            let Some((rest, _)) = tag.rsplit_once('-') else {
+                break;
+            };
+
+            tag = match rest.rsplit_once('-') {
+                Some((before, singleton)) if singleton.len() == 1 => before,
+                _ => rest,
+            };
+        }
+
+        result
+    }
+}
+
+impl core::borrow::Borrow<str> for LanguageTag {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl core::fmt::Display for LanguageTag {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The position within a language tag that the next subtag may fill.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Position {
+    /// Up to three extended language subtags.
+    Extlang(usize),
+
+    /// The script subtag.
+    Script,
+
+    /// The region subtag.
+    Region,
+
+    /// Any number of variant subtags.
+    Variant,
+
+    /// Any number of extensions.
+    Extension,
+}
+
+/// Gets whether every character within a subtag matches a predicate and the
+/// subtag has a length within a range.
+fn subtag(
+    subtag: &str,
+    lengths: core::ops::RangeInclusive<usize>,
+    predicate: fn(&u8) -> bool,
+) -> bool {
+    lengths.contains(&subtag.len()) && subtag.as_bytes().iter().all(predicate)
+}
+
+/// Parses the subtags of a language tag, returning the subtags with their case
+/// normalized.
+fn parse(tag: &str) -> Option<Vec<String>> {
+    let mut subtags = tag.split('-');
+    let mut result = Vec::new();
+
+    let language = subtags.next()?;
+
+    if !subtag(language, 2..=8, u8::is_ascii_alphabetic) || language.len() == 4 {
+        return None;
+    }
+
+    result.push(language.to_ascii_lowercase());
+
+    // NOTE: extended language subtags only follow two- or three-letter
+    // primary language subtags.
+    let mut position = if language.len() <= 3 {
+        Position::Extlang(0)
+    } else {
+        Position::Script
+    };
+
+    while let Some(current) = subtags.next() {
+        let next = if subtag(current, 1..=1, u8::is_ascii_alphanumeric) {
+            // NOTE: an extension or private use subtag must be followed by at
+            // least one subtag, and everything following a private use
+            // subtag is part of it.
+            let private = current.eq_ignore_ascii_case("x");
+            let (lengths, rest) = if private {
+                (1..=8, subtags.by_ref().collect::<Vec<_>>())
+            } else {
+                let mut rest = Vec::new();
+
+                while let Some(next) = subtags.clone().next() {
+                    if next.len() == 1 {
+                        break;
+                    }
+
+                    rest.push(next);
+                    subtags.next();
+                }
+
+                (2..=8, rest)
+            };
+
+            if rest.is_empty()
+                || !rest
+                    .iter()
+                    .all(|s| subtag(s, lengths.clone(), u8::is_ascii_alphanumeric))
+            {
+                return None;
+            }
+
+            result.push(current.to_ascii_lowercase());
+            result.extend(rest.into_iter().map(str::to_ascii_lowercase));
+            position = Position::Extension;
+            continue;
+        } else if position == Position::Extension {
+            return None;
+        } else if let (Position::Extlang(n @ 0..=2), true) =
+            (position, subtag(current, 3..=3, u8::is_ascii_alphabetic))
+        {
+            result.push(current.to_ascii_lowercase());
+            Position::Extlang(n + 1)
+        } else if position <= Position::Script && subtag(current, 4..=4, u8::is_ascii_alphabetic) {
+            let mut script = current.to_ascii_lowercase();
+            script[..1].make_ascii_uppercase();
+            result.push(script);
+            Position::Region
+        } else if position <= Position::Region
+            && (subtag(current, 2..=2, u8::is_ascii_alphabetic)
+                || subtag(current, 3..=3, u8::is_ascii_digit))
+        {
+            result.push(current.to_ascii_uppercase());
+            Position::Variant
+        } else if subtag(current, 5..=8, u8::is_ascii_alphanumeric)
+            || (subtag(current, 4..=4, u8::is_ascii_alphanumeric)
+                && current.as_bytes()[0].is_ascii_digit())
+        {
+            result.push(current.to_ascii_lowercase());
+            Position::Variant
+        } else {
+            return None;
+        };
+
+        position = next;
+    }
+
+    Some(result)
+}
+
+impl core::str::FromStr for LanguageTag {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        parse(s)
+            .map(|subtags| Self(subtags.join("-")))
+            .ok_or_else(|| ParseError::Invalid(s.to_string()))
+    }
+}
+
+/// Looks up the translation for a language.
+///
+/// Translations are looked up from the most to the least specific tag (see
+/// [`LanguageTag::fallbacks()`]), so a translation for `pt` is used for
+/// `pt-BR` when there is no translation specifically for `pt-BR`. Returns
+/// [`None`] if there is no translation for the language.
+pub fn translate<'a>(translations: &'a Translations, language: &LanguageTag) -> Option<&'a str> {
+    language
+        .fallbacks()
+        .into_iter()
+        .find_map(|tag| translations.get(tag))
+        .map(String::as_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parses a language tag.
+    fn tag(tag: &str) -> LanguageTag {
+        tag.parse().unwrap()
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(tag("en").as_str(), "en");
+        assert_eq!(tag("pt-br").as_str(), "pt-BR");
+        assert_eq!(tag("ZH-hant-tw").as_str(), "zh-Hant-TW");
+        assert_eq!(tag("es-419").as_str(), "es-419");
+        assert_eq!(tag("zh-yue-HK").as_str(), "zh-yue-HK");
+        assert_eq!(tag("sl-rozaj-biske").as_str(), "sl-rozaj-biske");
+        assert_eq!(tag("de-CH-1901").as_str(), "de-CH-1901");
+        assert_eq!(tag("en-US-u-ca-gregory").as_str(), "en-US-u-ca-gregory");
+        assert_eq!(tag("en-x-st-jude").as_str(), "en-x-st-jude");
+        assert_eq!(tag("pt-BR").language(), "pt");
+
+        assert_eq!("".parse::<LanguageTag>(), Err(ParseError::Empty));
+
+        for invalid in [
+            "e",
+            "english1",
+            "abcd",
+            "en-",
+            "en--US",
+            "en-US-GB",
+            "en-Latn-Latn",
+            "en-u",
+            "en-u-ca-x",
+            "en-a-b",
+            "x-klingon",
+            "en_US",
+            "日本",
+        ] {
+            assert_eq!(
+                invalid.parse::<LanguageTag>(),
+                Err(ParseError::Invalid(invalid.to_string())),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn fallbacks() {
+        assert_eq!(tag("en").fallbacks(), ["en"]);
+        assert_eq!(
+            tag("zh-Hant-TW").fallbacks(),
+            ["zh-Hant-TW", "zh-Hant", "zh"]
+        );
+        assert_eq!(
+            tag("en-US-u-ca-gregory").fallbacks(),
+            ["en-US-u-ca-gregory", "en-US-u-ca", "en-US", "en"]
+        );
+    }
+
+    #[test]
+    fn translations() {
+        let translations = Translations::from([
+            (tag("de"), String::from("Leukämie")),
+            (tag("pt"), String::from("Leucemia")),
+            (tag("pt-BR"), String::from("Leucemia (BR)")),
+        ]);
+
+        assert_eq!(translate(&translations, &tag("de-AT")), Some("Leukämie"));
+        assert_eq!(translate(&translations, &tag("pt-PT")), Some("Leucemia"));
+        assert_eq!(
+            translate(&translations, &tag("pt-BR")),
+            Some("Leucemia (BR)")
+        );
+        assert_eq!(translate(&translations, &tag("fr")), None);
+        assert_eq!(translate(&translations, &tag("DE")), Some("Leukämie"));
+    }
+}
@@ -1,7 +1,8 @@
 //! Sentences.
 
-use serde::Serialize;
-use serde_with::DeserializeFromStr;
+use alloc::string::String;
+use alloc::string::ToString as _;
+
 use thiserror::Error;
 
 /// A parse error related to a [`Sentence`].
@@ -21,10 +22,27 @@ pub enum ParseError {
 }
 
 /// A sentence.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, DeserializeFromStr)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde_with::DeserializeFromStr)
+)]
 pub struct Sentence(String);
 
-impl std::str::FromStr for Sentence {
+impl Sentence {
+    /// Gets the sentence as a string slice.
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+}
+
+impl core::fmt::Display for Sentence {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl core::str::FromStr for Sentence {
     type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -0,0 +1,276 @@
+//! Detection of names that are suspiciously similar.
+//!
+//! Names that differ only by case, whitespace, or punctuation (e.g.,
+//! `Non-Hodgkin Lymphoma` and `Non Hodgkin Lymphoma`) or by a small number of
+//! edits (e.g., `Leukemia` and `Leukaemia`) are almost always curation
+//! mistakes (i.e., the same concept was added twice).
+//!
+//! Many meaningful distinctions are also only a single edit apart (e.g.,
+//! `B-cell` and `T-cell` or `KMT2A` and `KMT2B`), so edits are only counted
+//! within words that look like misspellings: words of at least
+//! [`MIN_WORD_LENGTH`] letters that are not fully uppercase (i.e., not gene
+//! symbols or acronyms) and do not contain digits.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+/// The default number of edits within which two names are reported as near
+/// misses.
+pub const MAX_DISTANCE: usize = 1;
+
+/// The shortest word within which edits are counted.
+pub const MIN_WORD_LENGTH: usize = 4;
+
+/// A pair of names that are near misses of one another.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NearMiss {
+    /// The index of the first name.
+    pub first: usize,
+
+    /// The index of the second name (always greater than the first).
+    pub second: usize,
+
+    /// The number of edits between the names.
+    ///
+    /// A distance of zero means that the names differ only by case,
+    /// whitespace, or punctuation.
+    pub distance: usize,
+}
+
+impl NearMiss {
+    /// Describes the near miss between two names.
+    pub fn describe(&self, first: &str, second: &str) -> String {
+        match self.distance {
+            0 => {
+                format!("`{first}` and `{second}` differ only by case, whitespace, or punctuation")
+            }
+            1 => format!("`{first}` and `{second}` differ by a single edit"),
+            n => format!("`{first}` and `{second}` differ by {n} edits"),
+        }
+    }
+}
+
+/// Normalizes a name for comparison by lowercasing it and removing everything
+/// except letters and digits.
+pub fn normalize(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Gets the number of single-character insertions, deletions, or
+/// substitutions needed to turn one string into the other (i.e., the
+/// Levenshtein distance).
+pub fn distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // SAFETY: the distance is never more than the longer of the two lengths.
+    bounded(&a, &b, a.len().max(b.len())).unwrap()
+}
+
+/// Gets the distance between two strings if it is no more than a bound.
+///
+/// Only the rows of the distance matrix that can still end within the bound
+/// are computed, so this is much faster than [`distance()`] for small bounds.
+fn bounded(a: &[char], b: &[char], max: usize) -> Option<usize> {
+    if a.len().abs_diff(b.len()) > max {
+        return None;
+    }
+
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+
+    for (i, x) in a.iter().enumerate() {
+        current[0] = i + 1;
+
+        for (j, y) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(x != y);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        if current.iter().all(|&distance| distance > max) {
+            return None;
+        }
+
+        core::mem::swap(&mut previous, &mut current);
+    }
+
+    Some(previous[b.len()]).filter(|&distance| distance <= max)
+}
+
+/// Splits a name into its words (i.e., its runs of letters and digits).
+fn words(name: &str) -> Vec<&str> {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Gets whether edits within a word are counted.
+fn editable(word: &str) -> bool {
+    word.chars().count() >= MIN_WORD_LENGTH
+        && word.chars().all(char::is_alphabetic)
+        && word.chars().any(char::is_lowercase)
+}
+
+/// Gets the number of edits between two names if it is no more than a bound.
+///
+/// Names are compared word by word, and only edits within words that look
+/// like misspellings are counted (see the module-level documentation).
+fn edits(a: &[&str], b: &[&str], max: usize) -> Option<usize> {
+    if a.len() != b.len() {
+        return None;
+    }
+
+    let mut total = 0;
+
+    for (x, y) in a.iter().zip(b) {
+        if x.eq_ignore_ascii_case(y) {
+            continue;
+        }
+
+        if !editable(x) || !editable(y) {
+            return None;
+        }
+
+        let x = normalize(x).chars().collect::<Vec<_>>();
+        let y = normalize(y).chars().collect::<Vec<_>>();
+        total += bounded(&x, &y, max - total)?;
+    }
+
+    Some(total)
+}
+
+/// Finds the pairs of names that are near misses of one another.
+///
+/// Names are reported if they are equal once normalized (see [`normalize()`])
+/// or if they are within a number of edits of one another (see the
+/// module-level documentation for which edits are counted). Names that are
+/// exactly equal are not reported (duplicates are reported elsewhere), nor
+/// are names without any letters or digits. The pairs are ordered by the
+/// indexes of their names.
+pub fn near_misses<S: AsRef<str>>(names: &[S], max_distance: usize) -> Vec<NearMiss> {
+    let mut normalized = names
+        .iter()
+        .enumerate()
+        .map(|(i, name)| (i, normalize(name.as_ref())))
+        .filter(|(_, normalized)| !normalized.is_empty())
+        .map(|(i, normalized)| (i, normalized.chars().count(), normalized))
+        .collect::<Vec<_>>();
+
+    // NOTE: sorting by length means that only the names that follow a name
+    // within the bound on the difference in length need to be compared, as
+    // every edit changes the length of the normalized name by at most one.
+    normalized.sort_by_key(|(_, length, _)| *length);
+
+    let mut result = Vec::new();
+
+    for (n, (i, length, a)) in normalized.iter().enumerate() {
+        for (j, other, b) in &normalized[n + 1..] {
+            if other - length > max_distance {
+                break;
+            }
+
+            let (x, y) = (names[*i].as_ref(), names[*j].as_ref());
+
+            if x == y {
+                continue;
+            }
+
+            let distance = if a == b {
+                Some(0)
+            } else {
+                edits(&words(x), &words(y), max_distance)
+            };
+
+            if let Some(distance) = distance {
+                result.push(NearMiss {
+                    first: *i.min(j),
+                    second: *i.max(j),
+                    distance,
+                });
+            }
+        }
+    }
+
+    result.sort_by_key(|near_miss| (near_miss.first, near_miss.second));
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn distances() {
+        assert_eq!(distance("", ""), 0);
+        assert_eq!(distance("kitten", "sitting"), 3);
+        assert_eq!(distance("KMT2A", "KMT2B"), 1);
+        assert_eq!(distance("abc", ""), 3);
+
+        assert_eq!(bounded(&['a', 'b', 'c'], &['x', 'y', 'z'], 2), None);
+        assert_eq!(bounded(&['a', 'b', 'c'], &['a'], 1), None);
+        assert_eq!(bounded(&['a', 'b', 'c'], &['a', 'c'], 1), Some(1));
+    }
+
+    #[test]
+    fn near_misses() {
+        let names = [
+            "Non-Hodgkin Lymphoma",
+            "Hodgkin Lymphoma",
+            "Non Hodgkin Lymphoma",
+            "B-cell Lymphoblastic Leukemia",
+            "T-cell Lymphoblastic Leukemia",
+            "KMT2A-rearranged Leukemia",
+            "KMT2B-rearranged Leukemia",
+            "Leukemia",
+            "Leukaemia",
+            "Leukemia",
+            "---",
+            "...",
+        ];
+
+        assert_eq!(
+            super::near_misses(&names, 0),
+            [NearMiss {
+                first: 0,
+                second: 2,
+                distance: 0
+            }]
+        );
+        assert_eq!(
+            super::near_misses(&names, MAX_DISTANCE),
+            [
+                NearMiss {
+                    first: 0,
+                    second: 2,
+                    distance: 0
+                },
+                NearMiss {
+                    first: 7,
+                    second: 8,
+                    distance: 1
+                },
+                NearMiss {
+                    first: 8,
+                    second: 9,
+                    distance: 1
+                },
+            ]
+        );
+
+        assert!(super::near_misses(&["Leukemia", "Leukaemias"], 1).is_empty());
+        assert_eq!(super::near_misses(&["Leukemia", "Leukaemias"], 2).len(), 1);
+        assert!(super::near_misses(&["Acute Leukemia", "Leukemia"], 5).is_empty());
+
+        let near_miss = super::near_misses(&names, 0)[0];
+        assert_eq!(
+            near_miss.describe(names[0], names[2]),
+            "`Non-Hodgkin Lymphoma` and `Non Hodgkin Lymphoma` differ only by case, whitespace, \
+             or punctuation"
+        );
+    }
+}
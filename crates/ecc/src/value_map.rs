@@ -0,0 +1,311 @@
+//! Crosswalks between site-specific vocabularies and categorical options.
+//!
+//! A value map is curated by a site to translate the codes of its legacy data
+//! dictionary into the options of a categorical characteristic:
+//!
+//! ```yaml
+//! identifier: ECC-MORPH-000001
+//! source: Legacy Diagnosis Dictionary v2
+//! mappings:
+//!   "01": Foo
+//!   "02": Bar
+//!   "99": Bar
+//! ```
+//!
+//! Beyond checking that every mapping lands on a permissible option, a map can
+//! be checked for completeness (every option is reachable from some source
+//! code) and for round-trip conflicts (an option that is reached from more
+//! than one source code cannot be translated back unambiguously).
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::HashMap;
+
+use nonempty::NonEmpty;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::CharacteristicSet;
+use crate::Identifier;
+use crate::common::value::Kind;
+
+/// An error related to a value map.
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum Error {
+    /// The mapped characteristic does not exist.
+    #[error("unknown characteristic: `{0}`")]
+    UnknownCharacteristic(Identifier),
+
+    /// The mapped characteristic is not categorical.
+    #[error("characteristic `{0}` is not categorical")]
+    NotCategorical(Identifier),
+
+    /// A source code maps to something that is not an option.
+    #[error("source code `{source_code}` maps to `{target}`, which is not an option")]
+    UnknownOption {
+        /// The source code.
+        source_code: String,
+
+        /// The value it maps to.
+        target: String,
+    },
+
+    /// Two source codes that only differ by case or surrounding whitespace map
+    /// to different options.
+    #[error(
+        "source codes `{first}` and `{second}` are indistinguishable but map to different options"
+    )]
+    Ambiguous {
+        /// The first source code.
+        first: String,
+
+        /// The second source code.
+        second: String,
+    },
+}
+
+/// An option that is reached from more than one source code.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict<'a> {
+    /// The option.
+    pub option: &'a str,
+
+    /// The source codes that map to the option (sorted).
+    pub sources: Vec<&'a str>,
+}
+
+/// A crosswalk from site-specific codes to the options of a categorical
+/// characteristic.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ValueMap {
+    /// The identifier of the characteristic.
+    pub identifier: Identifier,
+
+    /// A description of the vocabulary the source codes come from.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// The mappings from source codes to options.
+    pub mappings: BTreeMap<String, String>,
+}
+
+impl ValueMap {
+    /// Gets the option a source code maps to.
+    pub fn get(&self, source_code: &str) -> Option<&str> {
+        self.mappings.get(source_code).map(String::as_str)
+    }
+
+    /// Validates the map against a set of characteristics.
+    ///
+    /// Every problem is reported rather than only the first.
+    pub fn validate_against(
+        &self,
+        characteristics: &CharacteristicSet,
+    ) -> Result<(), NonEmpty<Error>> {
+        let options = match characteristics
+            .get(&self.identifier)
+            .map(|characteristic| characteristic.values())
+        {
+            None => {
+                return Err(NonEmpty::new(Error::UnknownCharacteristic(
+                    self.identifier.clone(),
+                )));
+            }
+            Some(Some(Kind::Categorical { options })) => options,
+            Some(_) => {
+                return Err(NonEmpty::new(Error::NotCategorical(
+                    self.identifier.clone(),
+                )));
+            }
+        };
+
+        let mut errors = Vec::new();
+        let mut normalized = HashMap::<String, (&str, &str)>::new();
+
+        for (source_code, target) in &self.mappings {
+            if !options.contains(target) {
+                errors.push(Error::UnknownOption {
+                    source_code: source_code.clone(),
+                    target: target.clone(),
+                });
+            }
+
+            match normalized.get(&source_code.trim().to_lowercase()) {
+                Some((first, existing)) if *existing != target => {
+                    errors.push(Error::Ambiguous {
+                        first: first.to_string(),
+                        second: source_code.clone(),
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    normalized.insert(
+                        source_code.trim().to_lowercase(),
+                        (source_code.as_str(), target.as_str()),
+                    );
+                }
+            }
+        }
+
+        match NonEmpty::from_vec(errors) {
+            Some(errors) => Err(errors),
+            None => Ok(()),
+        }
+    }
+
+    /// Gets the options that no source code maps to (sorted).
+    ///
+    /// A complete map has no unmapped options. Characteristics that do not
+    /// exist or are not categorical have no options.
+    pub fn unmapped<'a>(&self, characteristics: &'a CharacteristicSet) -> Vec<&'a str> {
+        let Some(Kind::Categorical { options }) = characteristics
+            .get(&self.identifier)
+            .and_then(|characteristic| characteristic.values())
+        else {
+            return Vec::new();
+        };
+
+        let mapped = self.mappings.values().collect::<BTreeSet<_>>();
+
+        options
+            .iter()
+            .filter(|option| !mapped.contains(option))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Gets the options that are reached from more than one source code
+    /// (sorted by option).
+    pub fn conflicts(&self) -> Vec<Conflict<'_>> {
+        let mut sources = BTreeMap::<&str, Vec<&str>>::new();
+
+        for (source_code, target) in &self.mappings {
+            sources.entry(target).or_default().push(source_code);
+        }
+
+        sources
+            .into_iter()
+            .filter(|(_, sources)| sources.len() > 1)
+            .map(|(option, sources)| Conflict { option, sources })
+            .collect()
+    }
+
+    /// Inverts the map (from options to source codes).
+    ///
+    /// Fails with every round-trip conflict if an option is reached from more
+    /// than one source code.
+    pub fn invert(&self) -> Result<BTreeMap<&str, &str>, NonEmpty<Conflict<'_>>> {
+        if let Some(conflicts) = NonEmpty::from_vec(self.conflicts()) {
+            return Err(conflicts);
+        }
+
+        Ok(self
+            .mappings
+            .iter()
+            .map(|(source_code, target)| (target.as_str(), source_code.as_str()))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Characteristic;
+
+    fn characteristics() -> CharacteristicSet {
+        let characteristics: Vec<Characteristic> = serde_yaml::from_str(
+            r#"- state: proposed
+  identifier: ECC-MORPH-000001
+  name: Foo Bar
+  rfc: https://github.com/stjudecloud/ecc/issues/1
+  description: A description.
+  values:
+    kind: categorical
+    options: [Bar, Baz, Foo]"#,
+        )
+        .unwrap();
+
+        characteristics.into_iter().collect()
+    }
+
+    fn parse(yaml: &str) -> ValueMap {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn complete() {
+        let map = parse(
+            r#"identifier: ECC-MORPH-000001
+mappings:
+  "01": Foo
+  "02": Bar
+  "03": Baz"#,
+        );
+
+        let characteristics = characteristics();
+        assert!(map.validate_against(&characteristics).is_ok());
+        assert!(map.unmapped(&characteristics).is_empty());
+        assert_eq!(map.get("01"), Some("Foo"));
+        assert_eq!(map.invert().unwrap().get("Bar"), Some(&"02"));
+
+        let yaml = serde_yaml::to_string(&map).unwrap();
+        assert_eq!(parse(&yaml), map);
+    }
+
+    #[test]
+    fn incomplete() {
+        let map = parse(
+            r#"identifier: ECC-MORPH-000001
+source: Legacy Dictionary
+mappings:
+  b: Bar
+  B: Foo
+  bar: Bar
+  q: Quux"#,
+        );
+
+        let characteristics = characteristics();
+        let errors = map
+            .validate_against(&characteristics)
+            .unwrap_err()
+            .into_iter()
+            .collect::<Vec<_>>();
+        assert_eq!(
+            errors,
+            vec![
+                Error::Ambiguous {
+                    first: String::from("B"),
+                    second: String::from("b"),
+                },
+                Error::UnknownOption {
+                    source_code: String::from("q"),
+                    target: String::from("Quux"),
+                },
+            ]
+        );
+
+        assert_eq!(map.unmapped(&characteristics), vec!["Baz"]);
+        assert_eq!(
+            map.conflicts(),
+            vec![Conflict {
+                option: "Bar",
+                sources: vec!["b", "bar"],
+            }]
+        );
+        assert!(map.invert().is_err());
+    }
+
+    #[test]
+    fn not_categorical() {
+        let map = parse(
+            r#"identifier: ECC-MORPH-000002
+mappings: {}"#,
+        );
+
+        assert_eq!(
+            map.validate_against(&characteristics()).unwrap_err().head,
+            Error::UnknownCharacteristic("ECC-MORPH-000002".parse().unwrap())
+        );
+    }
+}
@@ -0,0 +1,11 @@
+#![allow(missing_docs)]
+
+use ecc::Characteristic;
+use test_infra::assert_golden_json;
+use test_infra::read_fixture_many;
+
+#[test]
+fn json() {
+    let characteristics = read_fixture_many::<Characteristic>("simple").unwrap();
+    assert_golden_json("simple.json", &characteristics);
+}
@@ -5,7 +5,10 @@ use std::num::NonZeroU64;
 use chrono::DateTime;
 use ecc::Characteristic;
 use ecc::Identifier;
+use test_infra::fixtures;
 use test_infra::read_fixture;
+use test_infra::read_fixture_many;
+use test_infra::read_fixture_one;
 
 #[test]
 fn parse() {
@@ -62,3 +65,36 @@ fn parse() {
         "https://github.com/stjudecloud/ecc/issues/3"
     );
 }
+
+#[test]
+fn formats() {
+    let expected = read_fixture_many::<Characteristic>("simple")
+        .unwrap()
+        .remove(0);
+
+    let json = read_fixture_one::<Characteristic>("adopted-json").unwrap();
+    assert_eq!(json, expected);
+
+    let toml = read_fixture_one::<Characteristic>("adopted-toml").unwrap();
+    assert_eq!(toml, expected);
+
+    let err = read_fixture_one::<Characteristic>("stream").unwrap_err();
+    assert!(err.to_string().contains("expected a single YAML document"));
+
+    let stream = read_fixture_many::<Characteristic>("stream").unwrap();
+    assert_eq!(stream.len(), 2);
+    assert_eq!(stream[0], expected);
+    assert!(matches!(stream[1], Characteristic::Draft { .. }));
+}
+
+#[test]
+fn overrides() {
+    let original = fixtures::adopted_characteristic();
+    let renamed = fixtures::adopted_characteristic_with(|common| {
+        common.set_name("Another Name").unwrap();
+    });
+
+    assert_eq!(renamed.name(), Some("Another Name"));
+    assert_eq!(renamed.identifier(), original.identifier());
+    assert_ne!(renamed.content_hash(), original.content_hash());
+}
@@ -0,0 +1,92 @@
+//! Benchmarks for parsing node names, building the ontology graph, and
+//! encoding it as a binary artifact.
+#![allow(missing_docs)]
+
+use criterion::BatchSize;
+use criterion::Criterion;
+use criterion::black_box;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use ontology::Graph;
+use ontology::Node;
+use ontology::node::Name;
+use test_infra::fixtures::node;
+
+/// The number of nodes in the generated ontology.
+const NODES: usize = 10_000;
+
+/// The number of children of each node in the generated ontology.
+const BRANCHING: usize = 10;
+
+/// Gets the name of the node with a number.
+fn name(i: usize) -> String {
+    format!("Node {i}")
+}
+
+/// Generates a balanced ontology with [`NODES`] nodes.
+fn nodes() -> Vec<Node> {
+    (0..NODES)
+        .map(|i| {
+            let parent = match i {
+                0 => String::new(),
+                _ => name((i - 1) / BRANCHING),
+            };
+
+            // SAFETY: every required field is set.
+            node(&name(i), &parent, &format!("N{i}"))
+                .try_build()
+                .unwrap()
+        })
+        .collect()
+}
+
+/// Parses node names.
+fn parse(c: &mut Criterion) {
+    c.bench_function("name/from_str", |b| {
+        b.iter(|| {
+            black_box("B-cell Acute Lymphoblastic Leukemia, BCR::ABL1")
+                .parse::<Name>()
+                .unwrap()
+        })
+    });
+}
+
+/// Builds (and structurally checks) the graph.
+fn graph(c: &mut Criterion) {
+    let nodes = nodes();
+
+    c.bench_function("graph/try_new/10k", |b| {
+        b.iter_batched(
+            || nodes.clone(),
+            |nodes| Graph::try_new(nodes).unwrap(),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+/// Decodes the graph from a binary artifact and (for comparison) from YAML.
+fn bytes(c: &mut Criterion) {
+    let graph = Graph::try_new(nodes()).unwrap();
+    let bytes = graph.to_bytes();
+    let yaml = graph
+        .nodes()
+        .map(|node| serde_yaml::to_string(node).unwrap())
+        .collect::<Vec<_>>();
+
+    c.bench_function("graph/from_bytes/10k", |b| {
+        b.iter(|| Graph::from_bytes(black_box(&bytes)).unwrap())
+    });
+
+    c.bench_function("graph/from_yaml/10k", |b| {
+        b.iter(|| {
+            let nodes = yaml
+                .iter()
+                .map(|node| serde_yaml::from_str::<Node>(black_box(node)).unwrap())
+                .collect();
+            Graph::try_new(nodes).unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, parse, graph, bytes);
+criterion_main!(benches);
@@ -0,0 +1,136 @@
+//! Random generation of valid values for property-based testing.
+//!
+//! See [`ecc::arbitrary`] for the characteristics that nodes are bound to.
+
+use ecc::Identifier;
+use proptest::arbitrary::Arbitrary;
+use proptest::collection;
+use proptest::option;
+use proptest::prelude::*;
+use proptest::strategy::BoxedStrategy;
+
+use crate::Node;
+use crate::constraint::Constraint;
+use crate::node::Binding;
+use crate::node::Builder;
+use crate::node::Name;
+
+/// Generates a word that is cased according to the default policy.
+fn word() -> impl Strategy<Value = String> {
+    prop_oneof![
+        4 => "[A-Z][a-z]{1,8}",
+        2 => "[A-Z][A-Z0-9]{1,4}",
+        1 => prop_oneof![Just("and"), Just("of"), Just("with")].prop_map(String::from),
+    ]
+}
+
+/// Generates a binding or constraint value.
+fn value() -> impl Strategy<Value = Option<String>> {
+    option::of("[A-Za-z0-9]{1,8}")
+}
+
+impl Arbitrary for Name {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        ("[A-Z][a-z]{1,8}", collection::vec(word(), 0..5))
+            // NOTE: generated words that happen to be in the lowercase list
+            // (e.g., `Or`) are cased incorrectly, so those names are skipped.
+            .prop_filter_map("incorrectly cased", |(first, rest)| {
+                std::iter::once(first)
+                    .chain(rest)
+                    .collect::<Vec<_>>()
+                    .join(" ")
+                    .parse()
+                    .ok()
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Binding {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Identifier>(), value())
+            .prop_map(|(identifier, value)| match value {
+                Some(value) => Binding::new(identifier).with_value(value),
+                None => Binding::new(identifier),
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Constraint {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (any::<Identifier>(), value())
+            .prop_map(|(requires, value)| match value {
+                Some(value) => Constraint::new(requires).with_value(value),
+                None => Constraint::new(requires),
+            })
+            .boxed()
+    }
+}
+
+impl Arbitrary for Node {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (
+            any::<Name>(),
+            any::<Name>(),
+            "[A-Z][A-Z0-9]{1,11}",
+            collection::vec(any::<Binding>(), 0..4),
+            collection::vec(any::<Constraint>(), 0..2),
+            ecc::arbitrary::translations(),
+        )
+            .prop_map(
+                |(name, parent, code, characteristics, constraints, translations)| {
+                    let builder = Builder::default().name(name).parent(parent).code(code);
+                    let builder = translations.into_iter().fold(
+                        builder,
+                        |builder, (language, translation)| {
+                            builder.translation(language, translation)
+                        },
+                    );
+                    let builder = characteristics
+                        .into_iter()
+                        .fold(builder, Builder::characteristic);
+
+                    constraints
+                        .into_iter()
+                        .fold(builder, Builder::constraint)
+                        .try_build()
+                        // SAFETY: every required field was just set, so this will
+                        // always unwrap.
+                        .unwrap()
+                },
+            )
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    proptest! {
+        #[test]
+        fn name(name in any::<Name>()) {
+            let parsed = name.to_string().parse::<Name>().unwrap();
+            prop_assert_eq!(parsed, name);
+        }
+
+        #[test]
+        fn node(node in any::<Node>()) {
+            let yaml = serde_yaml::to_string(&node).unwrap();
+            prop_assert_eq!(serde_yaml::from_str::<Node>(&yaml).unwrap(), node);
+        }
+    }
+}
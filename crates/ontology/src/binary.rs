@@ -0,0 +1,248 @@
+//! A compact binary encoding of the ontology graph.
+//!
+//! Loading the ontology from its directory means walking and parsing one YAML
+//! file per node. For consumers that only need to read the ontology (e.g., the
+//! `serve` backend or WebAssembly builds), the whole graph can instead be
+//! shipped as a single artifact produced by [`Graph::to_bytes()`] and read
+//! back with [`Graph::from_bytes()`].
+//!
+//! An artifact starts with a header (the [`MAGIC`] bytes followed by the
+//! [`VERSION`] of the encoding as a little-endian `u16`) and is followed by
+//! the nodes encoded with [`postcard`]. Artifacts written with a different
+//! version of the encoding are rejected rather than misread.
+
+use ecc::Identifier;
+use ecc::text::LanguageTag;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Graph;
+use crate::Node;
+use crate::constraint::Constraint;
+use crate::graph::Issue;
+use crate::node::Binding;
+use crate::node::Builder;
+use crate::node::Name;
+
+/// The bytes that every artifact starts with.
+pub const MAGIC: &[u8; 4] = b"ECCO";
+
+/// The version of the encoding.
+///
+/// This must be incremented whenever the encoded records change.
+pub const VERSION: u16 = 4;
+
+/// An error when decoding an artifact.
+#[derive(Debug)]
+pub enum Error {
+    /// The artifact does not start with the expected header.
+    Header,
+
+    /// The artifact was written with a different version of the encoding.
+    Version(u16),
+
+    /// The nodes could not be decoded.
+    Decode(postcard::Error),
+
+    /// A node name within the artifact is invalid.
+    Name(String),
+
+    /// An identifier within the artifact is invalid.
+    Identifier(ecc::identifier::ParseError),
+
+    /// A language tag within the artifact is invalid.
+    Language(ecc::text::language::ParseError),
+
+    /// The nodes within the artifact are not a structurally valid ontology.
+    Graph(Vec<Issue>),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Header => write!(f, "not an ontology artifact"),
+            Error::Version(version) => write!(
+                f,
+                "unsupported ontology artifact version {version} (expected {VERSION})"
+            ),
+            Error::Decode(err) => write!(f, "decoding ontology artifact: {err}"),
+            Error::Name(name) => write!(f, "invalid node name: `{name}`"),
+            Error::Identifier(err) => write!(f, "invalid identifier: {err}"),
+            Error::Language(err) => write!(f, "invalid language tag: {err}"),
+            Error::Graph(issues) => {
+                write!(f, "invalid ontology: ")?;
+
+                for (i, issue) in issues.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?;
+                    }
+
+                    write!(f, "{issue}")?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// An encoded node.
+///
+/// Nodes are not encoded directly, as their serialized form omits empty
+/// fields (which a non-self-describing format cannot represent).
+#[derive(Serialize, Deserialize)]
+struct Record {
+    /// The name of the node.
+    name: String,
+
+    /// The language tag and translated name of each translation.
+    translations: Vec<(String, String)>,
+
+    /// The name of the parent node.
+    parent: String,
+
+    /// The short code for the node.
+    code: String,
+
+    /// The identifier and value (if any) of each binding.
+    characteristics: Vec<(String, Option<String>)>,
+
+    /// The identifier and value (if any) of each constraint.
+    constraints: Vec<(String, Option<String>)>,
+
+    /// The position of the node among its siblings.
+    order: Option<u32>,
+
+    /// Whether the node is obsolete.
+    obsolete: bool,
+
+    /// The name of the replacement node (if any).
+    replaced_by: Option<String>,
+}
+
+impl Record {
+    /// Creates a record for a node.
+    fn new(node: &Node) -> Self {
+        Self {
+            name: node.name().to_string(),
+            translations: node
+                .translations()
+                .iter()
+                .map(|(language, name)| (language.to_string(), name.clone()))
+                .collect(),
+            parent: node.parent().to_string(),
+            code: node.code().to_string(),
+            characteristics: node
+                .characteristics()
+                .iter()
+                .map(|binding| {
+                    (
+                        binding.identifier().to_string(),
+                        binding.value().map(String::from),
+                    )
+                })
+                .collect(),
+            constraints: node
+                .constraints()
+                .iter()
+                .map(|constraint| {
+                    (
+                        constraint.requires().to_string(),
+                        constraint.value().map(String::from),
+                    )
+                })
+                .collect(),
+            order: node.order(),
+            obsolete: node.obsolete(),
+            replaced_by: node.replaced_by().map(|name| name.to_string()),
+        }
+    }
+
+    /// Converts the record back into a node.
+    fn into_node(self) -> Result<Node, Error> {
+        let name = |name: String| name.parse::<Name>().map_err(|_| Error::Name(name));
+        let identifier =
+            |identifier: String| identifier.parse::<Identifier>().map_err(Error::Identifier);
+
+        let mut builder = Builder::default()
+            .name(name(self.name)?)
+            .parent(name(self.parent)?)
+            .code(self.code);
+
+        for (language, translation) in self.translations {
+            let language = language.parse::<LanguageTag>().map_err(Error::Language)?;
+            builder = builder.translation(language, translation);
+        }
+
+        for (id, value) in self.characteristics {
+            let binding = Binding::new(identifier(id)?);
+            builder = builder.characteristic(match value {
+                Some(value) => binding.with_value(value),
+                None => binding,
+            });
+        }
+
+        for (id, value) in self.constraints {
+            let constraint = Constraint::new(identifier(id)?);
+            builder = builder.constraint(match value {
+                Some(value) => constraint.with_value(value),
+                None => constraint,
+            });
+        }
+
+        if let Some(order) = self.order {
+            builder = builder.order(order);
+        }
+
+        builder = builder.obsolete(self.obsolete);
+
+        if let Some(replacement) = self.replaced_by {
+            builder = builder.replaced_by(name(replacement)?);
+        }
+
+        // SAFETY: the name, parent, and code are always set above.
+        Ok(builder.try_build().unwrap())
+    }
+}
+
+impl Graph {
+    /// Encodes the graph as a compact binary artifact.
+    ///
+    /// The nodes are written in the order of [`Graph::nodes()`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let records = self.nodes().map(Record::new).collect::<Vec<_>>();
+
+        let mut bytes = Vec::from(*MAGIC);
+        bytes.extend_from_slice(&VERSION.to_le_bytes());
+
+        // SAFETY: records only contain strings, integers, booleans, and
+        // sequences, which postcard can always encode.
+        postcard::to_extend(&records, bytes).unwrap()
+    }
+
+    /// Decodes a graph from an artifact written by [`Graph::to_bytes()`].
+    ///
+    /// The graph is checked for structural issues in the same way as a graph
+    /// loaded from its directory (see [`Graph::try_new()`]).
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let rest = bytes.strip_prefix(MAGIC).ok_or(Error::Header)?;
+
+        let (version, rest) = rest.split_at_checked(2).ok_or(Error::Header)?;
+        // SAFETY: the slice is exactly two bytes long.
+        let version = u16::from_le_bytes(version.try_into().unwrap());
+
+        if version != VERSION {
+            return Err(Error::Version(version));
+        }
+
+        let records = postcard::from_bytes::<Vec<Record>>(rest).map_err(Error::Decode)?;
+        let nodes = records
+            .into_iter()
+            .map(Record::into_node)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Graph::try_new(nodes).map_err(Error::Graph)
+    }
+}
@@ -0,0 +1,354 @@
+//! Classification of samples from their characteristic annotations.
+//!
+//! The bindings of a node are read as the requirements for a sample to be
+//! classified under it: every bound characteristic must be annotated (with the
+//! bound value, if one is declared). Requirements are inherited, so a sample
+//! can only be classified under a node if it is also consistent with every
+//! ancestor.
+//!
+//! Nodes without bindings (e.g., groupings of subtypes) impose no requirements
+//! of their own. They can be traversed, but a sample is never classified under
+//! one unless it is the root, since there is no evidence for doing so.
+
+use std::collections::VecDeque;
+
+use ecc::Identifier;
+use ecc::annotate::AnnotationSet;
+
+use crate::Graph;
+use crate::Node;
+use crate::node::Binding;
+
+/// A node that could not be evaluated because the sample does not annotate
+/// some of the characteristics it binds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Undetermined<'a> {
+    /// The node.
+    pub node: &'a Node,
+
+    /// The characteristics that are bound by the node but not annotated.
+    pub missing: Vec<&'a Identifier>,
+}
+
+/// A node whose bindings are partially matched and partially contradicted by
+/// the sample.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflict<'a> {
+    /// The node.
+    pub node: &'a Node,
+
+    /// The bindings that the sample matches.
+    pub matched: Vec<&'a Binding>,
+
+    /// The bindings that the sample contradicts.
+    pub contradicted: Vec<&'a Binding>,
+}
+
+/// The result of classifying a sample.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Classification<'a> {
+    /// The most specific nodes the sample is consistent with (in breadth-first
+    /// order).
+    ///
+    /// If the sample matches no bound node, this is the root.
+    pub nodes: Vec<&'a Node>,
+
+    /// The nodes that could refine the classification if the sample annotated
+    /// more characteristics.
+    pub undetermined: Vec<Undetermined<'a>>,
+
+    /// The nodes whose bindings the sample partially contradicts.
+    pub conflicts: Vec<Conflict<'a>>,
+}
+
+impl Classification<'_> {
+    /// Whether the sample is consistent with more than one most specific node.
+    pub fn is_ambiguous(&self) -> bool {
+        self.nodes.len() > 1
+    }
+
+    /// Gets the names of the nodes that the sample is classified under
+    /// followed by the names of their ancestors (without duplicates).
+    ///
+    /// A sample that is only classified under the root is unclassified, so
+    /// this is empty for it (see [`ecc::annotate::Context::lineage`]).
+    pub fn lineage(&self, graph: &Graph) -> Vec<String> {
+        let root = graph.root().name().inner();
+
+        if self.nodes.iter().all(|node| node.name().inner() == root) {
+            return Vec::new();
+        }
+
+        let mut lineage = self
+            .nodes
+            .iter()
+            .map(|node| node.name().inner().to_string())
+            .collect::<Vec<_>>();
+
+        for node in &self.nodes {
+            for ancestor in graph.ancestors(node.name().inner()).into_iter().rev() {
+                let name = ancestor.name().inner();
+
+                if !lineage.iter().any(|other| other == name) {
+                    lineage.push(name.to_string());
+                }
+            }
+        }
+
+        lineage
+    }
+}
+
+/// The outcome of evaluating the bindings of a single node.
+enum Outcome<'a> {
+    /// Every binding is matched.
+    Matched,
+
+    /// Some bindings are not annotated and none are contradicted.
+    Undetermined(Vec<&'a Identifier>),
+
+    /// Some bindings are contradicted.
+    Contradicted {
+        /// The bindings that are matched.
+        matched: Vec<&'a Binding>,
+
+        /// The bindings that are contradicted.
+        contradicted: Vec<&'a Binding>,
+    },
+}
+
+/// Evaluates the bindings of a node against a sample.
+fn evaluate<'a>(node: &'a Node, annotations: &AnnotationSet) -> Outcome<'a> {
+    let mut matched = Vec::new();
+    let mut missing = Vec::new();
+    let mut contradicted = Vec::new();
+
+    for binding in node.characteristics() {
+        match (annotations.get(binding.identifier()), binding.value()) {
+            (None, _) => missing.push(binding.identifier()),
+            (Some(actual), Some(expected)) if actual != expected => contradicted.push(binding),
+            (Some(_), _) => matched.push(binding),
+        }
+    }
+
+    if !contradicted.is_empty() {
+        Outcome::Contradicted {
+            matched,
+            contradicted,
+        }
+    } else if !missing.is_empty() {
+        Outcome::Undetermined(missing)
+    } else {
+        Outcome::Matched
+    }
+}
+
+/// Classifies a sample from its annotations.
+///
+/// The annotations are expected to have been validated against the
+/// characteristics beforehand (see [`AnnotationSet::validate_against()`]).
+/// Values are compared exactly as they are written. Obsolete nodes (and their
+/// descendants) are never matched.
+pub fn classify<'a>(graph: &'a Graph, annotations: &AnnotationSet) -> Classification<'a> {
+    let root = graph.root();
+
+    let mut matched = Vec::new();
+    let mut undetermined = Vec::new();
+    let mut conflicts = Vec::new();
+
+    // NOTE: the root is always consistent, as the sample has to be classified
+    // somewhere within the ontology.
+    let mut queue = VecDeque::from([root]);
+
+    while let Some(parent) = queue.pop_front() {
+        for node in graph.children(parent.name().inner()) {
+            // NOTE: obsolete nodes (and their descendants) are kept only to
+            // resolve historical data, so new samples are never classified
+            // within them.
+            if node.obsolete() {
+                continue;
+            }
+
+            match evaluate(node, annotations) {
+                Outcome::Matched => {
+                    if !node.characteristics().is_empty() {
+                        matched.push(node);
+                    }
+
+                    queue.push_back(node);
+                }
+                Outcome::Undetermined(missing) => undetermined.push(Undetermined { node, missing }),
+                Outcome::Contradicted {
+                    matched,
+                    contradicted,
+                } => {
+                    if !matched.is_empty() {
+                        conflicts.push(Conflict {
+                            node,
+                            matched,
+                            contradicted,
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    // A matched node is only the most specific if none of its descendants are
+    // also matched.
+    let mut nodes = matched
+        .iter()
+        .filter(|node| {
+            let name = node.name().inner();
+            !matched.iter().any(|other| {
+                graph
+                    .ancestors(other.name().inner())
+                    .iter()
+                    .any(|a| a.name().inner() == name)
+            })
+        })
+        .copied()
+        .collect::<Vec<_>>();
+
+    if nodes.is_empty() {
+        nodes.push(root);
+    }
+
+    Classification {
+        nodes,
+        undetermined,
+        conflicts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ecc::annotate::Annotation;
+
+    use super::*;
+    use crate::node::Builder;
+    use crate::node::Name;
+
+    fn identifier(s: &str) -> Identifier {
+        s.parse().unwrap()
+    }
+
+    fn node(name: &str, parent: &str, bindings: &[(&str, &str)]) -> Node {
+        bindings
+            .iter()
+            .fold(
+                Builder::default()
+                    .name(name.parse::<Name>().unwrap())
+                    .parent(parent.parse::<Name>().unwrap())
+                    .code(name.to_uppercase()),
+                |builder, (id, value)| {
+                    builder.characteristic(Binding::new(identifier(id)).with_value(*value))
+                },
+            )
+            .try_build()
+            .unwrap()
+    }
+
+    fn graph() -> Graph {
+        Graph::try_new(vec![
+            node("Leukemia", "", &[]),
+            node("BLL", "Leukemia", &[("ECC-MORPH-000001", "B-cell")]),
+            node("TLL", "Leukemia", &[("ECC-MORPH-000001", "T-cell")]),
+            node("Fusions", "BLL", &[]),
+            node(
+                "ETV6RUNX1",
+                "Fusions",
+                &[("ECC-MOLEC-000001", "true"), ("ECC-MOLEC-000002", "false")],
+            ),
+            node("BCRABL1", "Fusions", &[("ECC-MOLEC-000003", "true")]),
+        ])
+        .unwrap()
+    }
+
+    fn annotations(annotations: &[(&str, &str)]) -> AnnotationSet {
+        AnnotationSet::new(
+            annotations
+                .iter()
+                .map(|(id, value)| Annotation::new(identifier(id), *value))
+                .collect(),
+        )
+    }
+
+    fn names<'a>(nodes: &[&'a Node]) -> Vec<&'a str> {
+        nodes.iter().map(|node| node.name().inner()).collect()
+    }
+
+    #[test]
+    fn specific() {
+        let graph = graph();
+        let result = classify(
+            &graph,
+            &annotations(&[
+                ("ECC-MORPH-000001", "B-cell"),
+                ("ECC-MOLEC-000001", "true"),
+                ("ECC-MOLEC-000002", "false"),
+                ("ECC-MOLEC-000003", "false"),
+            ]),
+        );
+
+        assert_eq!(names(&result.nodes), vec!["ETV6RUNX1"]);
+        assert!(!result.is_ambiguous());
+        assert!(result.undetermined.is_empty());
+        assert!(result.conflicts.is_empty());
+        assert_eq!(
+            result.lineage(&graph),
+            vec!["ETV6RUNX1", "Fusions", "BLL", "Leukemia"]
+        );
+    }
+
+    #[test]
+    fn undetermined() {
+        let graph = graph();
+        let result = classify(&graph, &annotations(&[("ECC-MORPH-000001", "B-cell")]));
+
+        assert_eq!(names(&result.nodes), vec!["BLL"]);
+        assert_eq!(result.undetermined.len(), 2);
+        assert_eq!(result.undetermined[0].node.name().inner(), "ETV6RUNX1");
+        assert_eq!(result.undetermined[0].missing.len(), 2);
+    }
+
+    #[test]
+    fn conflicts_and_ambiguity() {
+        let graph = graph();
+        let result = classify(
+            &graph,
+            &annotations(&[
+                ("ECC-MORPH-000001", "B-cell"),
+                ("ECC-MOLEC-000001", "true"),
+                ("ECC-MOLEC-000002", "true"),
+                ("ECC-MOLEC-000003", "true"),
+            ]),
+        );
+
+        assert_eq!(names(&result.nodes), vec!["BCRABL1"]);
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(result.conflicts[0].node.name().inner(), "ETV6RUNX1");
+        assert_eq!(result.conflicts[0].contradicted.len(), 1);
+
+        let graph = Graph::try_new(vec![
+            node("Root", "", &[]),
+            node("Foo", "Root", &[("ECC-MOLEC-000001", "true")]),
+            node("Bar", "Root", &[("ECC-MOLEC-000002", "true")]),
+        ])
+        .unwrap();
+        let result = classify(
+            &graph,
+            &annotations(&[("ECC-MOLEC-000001", "true"), ("ECC-MOLEC-000002", "true")]),
+        );
+        assert!(result.is_ambiguous());
+        assert_eq!(names(&result.nodes), vec!["Foo", "Bar"]);
+    }
+
+    #[test]
+    fn unclassified() {
+        let graph = graph();
+        let result = classify(&graph, &annotations(&[]));
+        assert_eq!(names(&result.nodes), vec!["Leukemia"]);
+        assert!(result.lineage(&graph).is_empty());
+    }
+}
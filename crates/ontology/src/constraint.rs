@@ -0,0 +1,193 @@
+//! Constraints on the characteristic bindings within a subtree.
+//!
+//! A node may declare constraints that every one of its descendants must
+//! satisfy. For example, the following requires every descendant of the node
+//! to bind `ECC-MOLEC-000001` to `true`:
+//!
+//! ```yaml
+//! name: B-cell Lymphoblastic Leukemia
+//! parent: Lymphoblastic Leukemia
+//! code: BLL
+//! constraints:
+//!   - requires: ECC-MOLEC-000001
+//!     value: "true"
+//! ```
+//!
+//! When no value is provided, binding the characteristic with any value (or
+//! without one) satisfies the constraint.
+
+use ecc::Identifier;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Graph;
+use crate::Node;
+
+/// A constraint that every descendant of a node must satisfy.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Constraint {
+    /// The identifier of the characteristic that must be bound.
+    requires: Identifier,
+
+    /// The value the characteristic must be bound to (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+}
+
+impl Constraint {
+    /// Creates a new constraint requiring a characteristic to be bound.
+    pub fn new(requires: Identifier) -> Self {
+        Self {
+            requires,
+            value: None,
+        }
+    }
+
+    /// Sets the value the characteristic must be bound to.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Gets the identifier of the characteristic that must be bound.
+    pub fn requires(&self) -> &Identifier {
+        &self.requires
+    }
+
+    /// Gets the value the characteristic must be bound to (if any).
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Checks whether a node satisfies the constraint.
+    pub fn is_satisfied_by(&self, node: &Node) -> bool {
+        node.characteristics().iter().any(|binding| {
+            binding.identifier() == &self.requires
+                && self
+                    .value
+                    .as_deref()
+                    .is_none_or(|value| binding.value() == Some(value))
+        })
+    }
+}
+
+impl std::fmt::Display for Constraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.value {
+            Some(value) => write!(f, "`{}` must be bound to `{value}`", self.requires),
+            None => write!(f, "`{}` must be bound", self.requires),
+        }
+    }
+}
+
+/// A node that does not satisfy a constraint declared by one of its
+/// ancestors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Violation {
+    /// The name of the node that violates the constraint.
+    pub node: String,
+
+    /// The name of the ancestor that declares the constraint.
+    pub ancestor: String,
+
+    /// The constraint.
+    pub constraint: Constraint,
+}
+
+impl std::fmt::Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "node `{}` violates a constraint of `{}`: {}",
+            self.node, self.ancestor, self.constraint
+        )
+    }
+}
+
+/// Evaluates the constraints declared within a graph.
+///
+/// Violations are returned in breadth-first order of the declaring nodes and,
+/// for each constraint, in breadth-first order of the violating descendants.
+pub fn evaluate(graph: &Graph) -> Vec<Violation> {
+    let mut result = Vec::new();
+
+    for ancestor in
+        std::iter::once(graph.root()).chain(graph.descendants(graph.root().name().inner()))
+    {
+        for constraint in ancestor.constraints() {
+            for node in graph.descendants(ancestor.name().inner()) {
+                if !constraint.is_satisfied_by(node) {
+                    result.push(Violation {
+                        node: node.name().inner().to_string(),
+                        ancestor: ancestor.name().inner().to_string(),
+                        constraint: constraint.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::Binding;
+    use crate::node::Builder;
+
+    fn identifier(s: &str) -> Identifier {
+        s.parse().unwrap()
+    }
+
+    fn node(name: &str, parent: &str) -> Builder {
+        Builder::default()
+            .name(name.parse::<crate::node::Name>().unwrap())
+            .parent(parent.parse::<crate::node::Name>().unwrap())
+            .code(name.to_uppercase())
+    }
+
+    #[test]
+    fn evaluate() {
+        let graph = Graph::try_new(vec![
+            node("Root", "").try_build().unwrap(),
+            node("Foo", "Root")
+                .constraint(Constraint::new(identifier("ECC-MOLEC-000001")).with_value("true"))
+                .try_build()
+                .unwrap(),
+            node("Bar", "Foo")
+                .characteristic(Binding::new(identifier("ECC-MOLEC-000001")).with_value("true"))
+                .try_build()
+                .unwrap(),
+            node("Baz", "Foo")
+                .characteristic(Binding::new(identifier("ECC-MOLEC-000001")).with_value("false"))
+                .try_build()
+                .unwrap(),
+            node("Quux", "Root").try_build().unwrap(),
+        ])
+        .unwrap();
+
+        let violations = super::evaluate(&graph);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].node, "Baz");
+        assert_eq!(violations[0].ancestor, "Foo");
+        assert_eq!(
+            violations[0].to_string(),
+            "node `Baz` violates a constraint of `Foo`: `ECC-MOLEC-000001` must be bound to `true`"
+        );
+    }
+
+    #[test]
+    fn any_value() {
+        let constraint = Constraint::new(identifier("ECC-MOLEC-000001"));
+        let bound = node("Foo", "Root")
+            .characteristic(Binding::new(identifier("ECC-MOLEC-000001")).with_value("false"))
+            .try_build()
+            .unwrap();
+        let unbound = node("Bar", "Root").try_build().unwrap();
+
+        assert!(constraint.is_satisfied_by(&bound));
+        assert!(!constraint.is_satisfied_by(&unbound));
+    }
+}
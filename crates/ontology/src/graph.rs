@@ -0,0 +1,712 @@
+//! The ontology graph.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use petgraph::Direction;
+use petgraph::graph::DiGraph;
+use petgraph::graph::NodeIndex;
+use petgraph::visit::Bfs;
+
+use crate::Node;
+
+/// A structural issue within a set of ontology nodes.
+///
+/// Indexes refer to positions within the slice of nodes that was checked.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue {
+    /// Multiple nodes share the same name.
+    DuplicateName {
+        /// The duplicated name.
+        name: String,
+
+        /// The indexes of the nodes with the name.
+        indexes: Vec<usize>,
+    },
+
+    /// Multiple nodes share the same short code.
+    DuplicateCode {
+        /// The duplicated code.
+        code: String,
+
+        /// The indexes of the nodes with the code.
+        indexes: Vec<usize>,
+    },
+
+    /// A node's parent does not exist (the node is orphaned).
+    MissingParent {
+        /// The index of the node.
+        index: usize,
+
+        /// The name of the node.
+        name: String,
+
+        /// The name of the missing parent.
+        parent: String,
+    },
+
+    /// No root node (a node without a parent) exists.
+    NoRoot,
+
+    /// More than one root node exists.
+    MultipleRoots {
+        /// The indexes of the root nodes.
+        indexes: Vec<usize>,
+
+        /// The names of the root nodes.
+        names: Vec<String>,
+    },
+
+    /// A set of nodes are each other's ancestors.
+    Cycle {
+        /// The indexes of the nodes within the cycle.
+        indexes: Vec<usize>,
+
+        /// The names of the nodes within the cycle.
+        names: Vec<String>,
+    },
+
+    /// A node's replacement does not exist.
+    MissingReplacement {
+        /// The index of the node.
+        index: usize,
+
+        /// The name of the node.
+        name: String,
+
+        /// The name of the missing replacement.
+        replacement: String,
+    },
+
+    /// A node is replaced by a node that is itself obsolete.
+    ObsoleteReplacement {
+        /// The index of the node.
+        index: usize,
+
+        /// The name of the node.
+        name: String,
+
+        /// The name of the obsolete replacement.
+        replacement: String,
+    },
+}
+
+impl Issue {
+    /// Gets the indexes of the nodes involved in the issue.
+    pub fn indexes(&self) -> Vec<usize> {
+        match self {
+            Issue::DuplicateName { indexes, .. }
+            | Issue::DuplicateCode { indexes, .. }
+            | Issue::MultipleRoots { indexes, .. }
+            | Issue::Cycle { indexes, .. } => indexes.clone(),
+            Issue::MissingParent { index, .. }
+            | Issue::MissingReplacement { index, .. }
+            | Issue::ObsoleteReplacement { index, .. } => vec![*index],
+            Issue::NoRoot => Vec::new(),
+        }
+    }
+}
+
+impl std::fmt::Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::DuplicateName { name, indexes } => {
+                write!(f, "node name `{name}` is used {} times", indexes.len())
+            }
+            Issue::DuplicateCode { code, indexes } => {
+                write!(f, "node code `{code}` is used {} times", indexes.len())
+            }
+            Issue::MissingParent { name, parent, .. } => {
+                write!(f, "parent `{parent}` of node `{name}` does not exist")
+            }
+            Issue::NoRoot => write!(f, "unable to identify the root node"),
+            Issue::MultipleRoots { names, .. } => {
+                write!(f, "found multiple roots: {}", names.join(", "))
+            }
+            Issue::Cycle { names, .. } => {
+                write!(f, "found a cycle between nodes: {}", names.join(" → "))
+            }
+            Issue::MissingReplacement {
+                name, replacement, ..
+            } => {
+                write!(
+                    f,
+                    "replacement `{replacement}` of node `{name}` does not exist"
+                )
+            }
+            Issue::ObsoleteReplacement {
+                name, replacement, ..
+            } => write!(
+                f,
+                "node `{name}` is replaced by `{replacement}`, which is itself obsolete"
+            ),
+        }
+    }
+}
+
+/// Groups the indexes of nodes by a key, keeping only groups with duplicates.
+fn duplicates<'a>(keys: impl Iterator<Item = &'a str>) -> Vec<(String, Vec<usize>)> {
+    let mut groups = HashMap::<&str, Vec<usize>>::new();
+    let mut order = Vec::new();
+
+    for (i, key) in keys.enumerate() {
+        let group = groups.entry(key).or_default();
+
+        if group.is_empty() {
+            order.push(key);
+        }
+
+        group.push(i);
+    }
+
+    order
+        .into_iter()
+        .filter_map(|key| {
+            let group = groups.remove(key)?;
+            (group.len() > 1).then(|| (key.to_string(), group))
+        })
+        .collect()
+}
+
+/// Checks a set of nodes for structural issues.
+///
+/// Unlike [`Graph::try_new`], every issue is reported rather than just the
+/// first.
+pub fn check(nodes: &[Node]) -> Vec<Issue> {
+    let mut issues = Vec::new();
+
+    for (name, indexes) in duplicates(nodes.iter().map(|node| node.name().inner())) {
+        issues.push(Issue::DuplicateName { name, indexes });
+    }
+
+    for (code, indexes) in duplicates(nodes.iter().map(|node| node.code())) {
+        issues.push(Issue::DuplicateCode { code, indexes });
+    }
+
+    // NOTE: when names are duplicated, the first node with the name is used
+    // when resolving parents.
+    let mut by_name = HashMap::new();
+
+    for (i, node) in nodes.iter().enumerate() {
+        by_name.entry(node.name().inner()).or_insert(i);
+    }
+
+    let mut roots = Vec::new();
+    let mut parents = vec![None; nodes.len()];
+
+    for (i, node) in nodes.iter().enumerate() {
+        let parent = node.parent().inner();
+
+        if parent.is_empty() {
+            roots.push(i);
+            continue;
+        }
+
+        match by_name.get(parent) {
+            Some(j) => parents[i] = Some(*j),
+            None => issues.push(Issue::MissingParent {
+                index: i,
+                name: node.name().inner().to_string(),
+                parent: parent.to_string(),
+            }),
+        }
+    }
+
+    match roots.len() {
+        0 => issues.push(Issue::NoRoot),
+        1 => {}
+        _ => issues.push(Issue::MultipleRoots {
+            names: roots
+                .iter()
+                .map(|i| nodes[*i].name().inner().to_string())
+                .collect(),
+            indexes: roots,
+        }),
+    }
+
+    // Walk up from every node. If a node is revisited during a single walk, the
+    // nodes from the first visit onwards form a cycle.
+    let mut seen_cycles = HashSet::new();
+
+    for start in 0..nodes.len() {
+        let mut path = Vec::<usize>::new();
+        let mut current = Some(start);
+
+        while let Some(i) = current {
+            if let Some(position) = path.iter().position(|j| *j == i) {
+                let cycle = path[position..].to_vec();
+                let mut key = cycle.clone();
+                key.sort();
+
+                if seen_cycles.insert(key) {
+                    issues.push(Issue::Cycle {
+                        names: cycle
+                            .iter()
+                            .map(|i| nodes[*i].name().inner().to_string())
+                            .collect(),
+                        indexes: cycle,
+                    });
+                }
+
+                break;
+            }
+
+            path.push(i);
+            current = parents[i];
+        }
+    }
+
+    for (i, node) in nodes.iter().enumerate() {
+        let Some(replacement) = node.replaced_by() else {
+            continue;
+        };
+
+        let issue = match by_name.get(replacement.inner()) {
+            Some(j) if nodes[*j].obsolete() => Issue::ObsoleteReplacement {
+                index: i,
+                name: node.name().inner().to_string(),
+                replacement: replacement.inner().to_string(),
+            },
+            Some(_) => continue,
+            None => Issue::MissingReplacement {
+                index: i,
+                name: node.name().inner().to_string(),
+                replacement: replacement.inner().to_string(),
+            },
+        };
+
+        issues.push(issue);
+    }
+
+    issues
+}
+
+/// How the children of a node are ordered.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SiblingOrder {
+    /// Siblings appear in the order that their nodes were provided.
+    #[default]
+    Insertion,
+
+    /// Siblings are sorted by their explicit order (see [`Node::order()`]) and
+    /// then by name.
+    ///
+    /// Siblings without an explicit order come after those with one.
+    OrderThenName,
+}
+
+/// An ontology graph.
+///
+/// Edges point from parents to their children.
+#[derive(Clone, Debug)]
+pub struct Graph {
+    /// The inner graph.
+    inner: DiGraph<Node, ()>,
+
+    /// The index of each node by name.
+    indexes: HashMap<String, NodeIndex>,
+
+    /// The index of the root node.
+    root: NodeIndex,
+
+    /// How the children of a node are ordered.
+    sibling_order: SiblingOrder,
+}
+
+impl Graph {
+    /// Attempts to build a graph from a set of nodes.
+    ///
+    /// If any structural issues exist, all of them are returned (see
+    /// [`check()`]).
+    pub fn try_new(nodes: Vec<Node>) -> Result<Self, Vec<Issue>> {
+        let issues = check(&nodes);
+
+        if !issues.is_empty() {
+            return Err(issues);
+        }
+
+        let mut inner = DiGraph::new();
+        let mut indexes = HashMap::new();
+
+        for node in nodes {
+            let name = node.name().inner().to_string();
+            indexes.insert(name, inner.add_node(node));
+        }
+
+        let mut root = None;
+
+        for index in inner.node_indices().collect::<Vec<_>>() {
+            let parent = inner[index].parent().inner();
+
+            match indexes.get(parent) {
+                Some(parent) => {
+                    inner.add_edge(*parent, index, ());
+                }
+                None => root = Some(index),
+            }
+        }
+
+        Ok(Self {
+            inner,
+            indexes,
+            // SAFETY: the checks above guarantee exactly one root exists.
+            root: root.unwrap(),
+            sibling_order: SiblingOrder::default(),
+        })
+    }
+
+    /// Sets how the children of a node are ordered.
+    ///
+    /// The policy applies to [`Graph::children()`] and [`Graph::preorder()`],
+    /// which exports and renderings use to walk the graph.
+    pub fn with_sibling_order(mut self, order: SiblingOrder) -> Self {
+        self.sibling_order = order;
+        self
+    }
+
+    /// Gets how the children of a node are ordered.
+    pub fn sibling_order(&self) -> SiblingOrder {
+        self.sibling_order
+    }
+
+    /// Gets the inner graph.
+    pub fn inner(&self) -> &DiGraph<Node, ()> {
+        &self.inner
+    }
+
+    /// Gets the index of the root node within the inner graph.
+    pub fn root_index(&self) -> NodeIndex {
+        self.root
+    }
+
+    /// Gets the root node.
+    pub fn root(&self) -> &Node {
+        &self.inner[self.root]
+    }
+
+    /// Gets the number of nodes in the graph.
+    pub fn len(&self) -> usize {
+        self.inner.node_count()
+    }
+
+    /// Returns whether the graph has no nodes.
+    ///
+    /// Because every graph has a root, this is always `false`.
+    pub fn is_empty(&self) -> bool {
+        self.inner.node_count() == 0
+    }
+
+    /// Gets a node by name.
+    pub fn get(&self, name: &str) -> Option<&Node> {
+        self.indexes.get(name).map(|index| &self.inner[*index])
+    }
+
+    /// Resolves a node by name, following its replacement if it has one.
+    ///
+    /// Historical data may refer to nodes that have since been made obsolete;
+    /// this returns the node that such data should now be classified under (or
+    /// the obsolete node itself if it was not replaced).
+    pub fn resolve(&self, name: &str) -> Option<&Node> {
+        let node = self.get(name)?;
+
+        match node.replaced_by() {
+            // NOTE: replacements are never obsolete themselves (see
+            // [`check()`]), so only a single replacement is followed.
+            Some(replacement) => self.get(replacement.inner()),
+            None => Some(node),
+        }
+    }
+
+    /// Gets an iterator over the nodes in the graph.
+    pub fn nodes(&self) -> impl Iterator<Item = &Node> {
+        self.inner.node_weights()
+    }
+
+    /// Gets the parent of a node by name.
+    pub fn parent(&self, name: &str) -> Option<&Node> {
+        let index = self.indexes.get(name)?;
+        self.inner
+            .neighbors_directed(*index, Direction::Incoming)
+            .next()
+            .map(|parent| &self.inner[parent])
+    }
+
+    /// Gets the children of a node by name.
+    pub fn children(&self, name: &str) -> Vec<&Node> {
+        let Some(index) = self.indexes.get(name) else {
+            return Vec::new();
+        };
+
+        let mut children = self
+            .inner
+            .neighbors_directed(*index, Direction::Outgoing)
+            .map(|child| &self.inner[child])
+            .collect::<Vec<_>>();
+
+        // NOTE: petgraph returns neighbors in reverse order of insertion.
+        children.reverse();
+
+        if self.sibling_order == SiblingOrder::OrderThenName {
+            children.sort_by(|a, b| {
+                (a.order().is_none(), a.order(), a.name().inner()).cmp(&(
+                    b.order().is_none(),
+                    b.order(),
+                    b.name().inner(),
+                ))
+            });
+        }
+
+        children
+    }
+
+    /// Gets every node in depth-first order, starting from the root.
+    ///
+    /// Each node comes before its children, and the children of a node are
+    /// ordered by the sibling order of the graph (see
+    /// [`Graph::with_sibling_order()`]).
+    pub fn preorder(&self) -> Vec<&Node> {
+        let mut result = Vec::with_capacity(self.len());
+        let mut stack = vec![self.root()];
+
+        while let Some(node) = stack.pop() {
+            result.push(node);
+            stack.extend(self.children(node.name().inner()).into_iter().rev());
+        }
+
+        result
+    }
+
+    /// Gets the descendants of a node by name in breadth-first order.
+    pub fn descendants(&self, name: &str) -> Vec<&Node> {
+        let Some(index) = self.indexes.get(name) else {
+            return Vec::new();
+        };
+
+        let mut bfs = Bfs::new(&self.inner, *index);
+        let mut result = Vec::new();
+
+        while let Some(descendant) = bfs.next(&self.inner) {
+            if descendant != *index {
+                result.push(&self.inner[descendant]);
+            }
+        }
+
+        result
+    }
+
+    /// Gets the ancestors of a node by name, starting from the root.
+    pub fn ancestors(&self, name: &str) -> Vec<&Node> {
+        let mut result = Vec::new();
+        let mut current = self.parent(name);
+
+        while let Some(node) = current {
+            result.push(node);
+            current = self.parent(node.name().inner());
+        }
+
+        result.reverse();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node::Builder;
+    use crate::node::Name;
+
+    fn node(name: &str, parent: &str, code: &str) -> Node {
+        Builder::default()
+            .name(name.parse::<Name>().unwrap())
+            .parent(parent.parse::<Name>().unwrap())
+            .code(code)
+            .try_build()
+            .unwrap()
+    }
+
+    #[test]
+    fn valid() {
+        let graph = Graph::try_new(vec![
+            node("Root", "", "ROOT"),
+            node("Foo", "Root", "FOO"),
+            node("Bar", "Foo", "BAR"),
+            node("Baz", "Foo", "BAZ"),
+        ])
+        .unwrap();
+
+        assert_eq!(graph.len(), 4);
+        assert_eq!(graph.root().name().inner(), "Root");
+        assert_eq!(graph.parent("Bar").unwrap().name().inner(), "Foo");
+        assert!(graph.parent("Root").is_none());
+        assert_eq!(
+            graph
+                .children("Foo")
+                .iter()
+                .map(|node| node.code())
+                .collect::<Vec<_>>(),
+            vec!["BAR", "BAZ"]
+        );
+        assert_eq!(
+            graph
+                .ancestors("Bar")
+                .iter()
+                .map(|node| node.code())
+                .collect::<Vec<_>>(),
+            vec!["ROOT", "FOO"]
+        );
+
+        let mut descendants = graph
+            .descendants("Root")
+            .iter()
+            .map(|node| node.code())
+            .collect::<Vec<_>>();
+        descendants.sort();
+        assert_eq!(descendants, vec!["BAR", "BAZ", "FOO"]);
+    }
+
+    #[test]
+    fn sibling_order() {
+        let nodes = vec![
+            node("Root", "", "ROOT"),
+            node("Foo", "Root", "FOO"),
+            Builder::default()
+                .name("Bar".parse::<Name>().unwrap())
+                .parent("Root".parse::<Name>().unwrap())
+                .code("BAR")
+                .order(2)
+                .try_build()
+                .unwrap(),
+            node("Baz", "Foo", "BAZ"),
+            Builder::default()
+                .name("Qux".parse::<Name>().unwrap())
+                .parent("Root".parse::<Name>().unwrap())
+                .code("QUX")
+                .order(1)
+                .try_build()
+                .unwrap(),
+            node("Alpha", "Root", "ALPHA"),
+        ];
+
+        fn codes(nodes: Vec<&Node>) -> Vec<&str> {
+            nodes.into_iter().map(|node| node.code()).collect()
+        }
+
+        let graph = Graph::try_new(nodes).unwrap();
+        assert_eq!(graph.sibling_order(), SiblingOrder::Insertion);
+        assert_eq!(
+            codes(graph.children("Root")),
+            ["FOO", "BAR", "QUX", "ALPHA"]
+        );
+        assert_eq!(
+            codes(graph.preorder()),
+            ["ROOT", "FOO", "BAZ", "BAR", "QUX", "ALPHA"]
+        );
+
+        let graph = graph.with_sibling_order(SiblingOrder::OrderThenName);
+        assert_eq!(
+            codes(graph.children("Root")),
+            ["QUX", "BAR", "ALPHA", "FOO"]
+        );
+        assert_eq!(
+            codes(graph.preorder()),
+            ["ROOT", "QUX", "BAR", "ALPHA", "FOO", "BAZ"]
+        );
+    }
+
+    #[test]
+    fn issues() {
+        let nodes = vec![
+            node("Root", "", "ROOT"),
+            node("Other Root", "", "ROOT"),
+            node("Foo", "Missing", "FOO"),
+            node("Foo", "Root", "FOO2"),
+            node("Bar", "Baz", "BAR"),
+            node("Baz", "Bar", "BAZ"),
+        ];
+
+        let issues = check(&nodes);
+
+        assert_eq!(
+            issues,
+            vec![
+                Issue::DuplicateName {
+                    name: String::from("Foo"),
+                    indexes: vec![2, 3]
+                },
+                Issue::DuplicateCode {
+                    code: String::from("ROOT"),
+                    indexes: vec![0, 1]
+                },
+                Issue::MissingParent {
+                    index: 2,
+                    name: String::from("Foo"),
+                    parent: String::from("Missing")
+                },
+                Issue::MultipleRoots {
+                    indexes: vec![0, 1],
+                    names: vec![String::from("Root"), String::from("Other Root")]
+                },
+                Issue::Cycle {
+                    indexes: vec![4, 5],
+                    names: vec![String::from("Bar"), String::from("Baz")]
+                },
+            ]
+        );
+
+        assert!(Graph::try_new(nodes).is_err());
+    }
+
+    #[test]
+    fn obsolete() {
+        let obsolete = |name: &str, replacement: &str| {
+            Builder::default()
+                .name(name.parse::<Name>().unwrap())
+                .parent("Root".parse::<Name>().unwrap())
+                .code(name.to_uppercase())
+                .obsolete(true)
+                .replaced_by(replacement.parse::<Name>().unwrap())
+                .try_build()
+                .unwrap()
+        };
+
+        let graph = Graph::try_new(vec![
+            node("Root", "", "ROOT"),
+            node("Foo", "Root", "FOO"),
+            obsolete("Bar", "Foo"),
+        ])
+        .unwrap();
+
+        assert!(graph.get("Bar").unwrap().obsolete());
+        assert_eq!(graph.resolve("Bar").unwrap().code(), "FOO");
+        assert_eq!(graph.resolve("Foo").unwrap().code(), "FOO");
+        assert!(graph.resolve("Baz").is_none());
+
+        let issues = check(&[
+            node("Root", "", "ROOT"),
+            obsolete("Foo", "Missing"),
+            obsolete("Bar", "Foo"),
+        ]);
+
+        assert_eq!(
+            issues,
+            vec![
+                Issue::MissingReplacement {
+                    index: 1,
+                    name: String::from("Foo"),
+                    replacement: String::from("Missing")
+                },
+                Issue::ObsoleteReplacement {
+                    index: 2,
+                    name: String::from("Bar"),
+                    replacement: String::from("Foo")
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn no_root() {
+        let issues = check(&[node("Foo", "Bar", "FOO"), node("Bar", "Foo", "BAR")]);
+        assert!(issues.contains(&Issue::NoRoot));
+    }
+}
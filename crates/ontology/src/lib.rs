@@ -1,5 +1,13 @@
 //! Ontology.
 
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
+pub mod binary;
+pub mod classify;
+pub mod constraint;
+pub mod graph;
 pub mod node;
+pub mod path;
 
+pub use graph::Graph;
 pub use node::Node;
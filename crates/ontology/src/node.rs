@@ -1,13 +1,20 @@
 //! Nodes within the ontology.
 
+use ecc::text::LanguageTag;
+use ecc::text::language;
+use ecc::text::language::Translations;
 use serde::Deserialize;
 use serde::Serialize;
 use serde_with::DisplayFromStr;
 use serde_with::serde_as;
 
+use crate::constraint::Constraint;
+
+pub mod binding;
 pub mod builder;
 pub mod name;
 
+pub use binding::Binding;
 pub use builder::Builder;
 pub use name::Name;
 
@@ -19,12 +26,40 @@ pub struct Node {
     #[serde_as(as = "DisplayFromStr")]
     name: Name,
 
+    /// Translations of the name into other languages.
+    #[serde(default, skip_serializing_if = "Translations::is_empty")]
+    translations: Translations,
+
     /// The name of the parent node.
     #[serde_as(as = "DisplayFromStr")]
     parent: Name,
 
     /// The short code for the node.
     code: String,
+
+    /// The characteristics the node is bound to.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    characteristics: Vec<Binding>,
+
+    /// The constraints that every descendant of the node must satisfy.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    constraints: Vec<Constraint>,
+
+    /// The position of the node among its siblings (see
+    /// [`SiblingOrder`](crate::graph::SiblingOrder)).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    order: Option<u32>,
+
+    /// Whether the node has been removed from the classification.
+    ///
+    /// Obsolete nodes are kept so that historical data remains resolvable.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    obsolete: bool,
+
+    /// The name of the node that replaces this one (if it is obsolete).
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    replaced_by: Option<Name>,
     // NOTE: if you add or remove fields here, you need to update the help
     // message in the `ontology init` subcommand to ensure each column is
     // documented.
@@ -41,6 +76,19 @@ impl Node {
         self.name
     }
 
+    /// Gets the translations of the node name.
+    pub fn translations(&self) -> &Translations {
+        &self.translations
+    }
+
+    /// Gets the name to display in a language.
+    ///
+    /// Falls back to the (English) node name if the name has not been
+    /// translated into the language (see [`language::translate()`]).
+    pub fn display_name(&self, language: &LanguageTag) -> &str {
+        language::translate(&self.translations, language).unwrap_or(self.name.inner())
+    }
+
     /// Gets the node's parent.
     pub fn parent(&self) -> &Name {
         &self.parent
@@ -60,4 +108,29 @@ impl Node {
     pub fn into_code(self) -> String {
         self.code
     }
+
+    /// Gets the characteristics the node is bound to.
+    pub fn characteristics(&self) -> &[Binding] {
+        &self.characteristics
+    }
+
+    /// Gets the constraints that every descendant of the node must satisfy.
+    pub fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Gets the position of the node among its siblings (if one is set).
+    pub fn order(&self) -> Option<u32> {
+        self.order
+    }
+
+    /// Returns whether the node has been removed from the classification.
+    pub fn obsolete(&self) -> bool {
+        self.obsolete
+    }
+
+    /// Gets the name of the node that replaces this one (if any).
+    pub fn replaced_by(&self) -> Option<&Name> {
+        self.replaced_by.as_ref()
+    }
 }
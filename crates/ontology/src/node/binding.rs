@@ -0,0 +1,47 @@
+//! Bindings between nodes and composable characteristics.
+
+use ecc::Identifier;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// A binding of a node to a composable characteristic.
+///
+/// A binding declares that samples classified under the node are described by
+/// the characteristic, optionally with a specific value (e.g., `true` for a
+/// binary characteristic or one of the options of a categorical one).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Binding {
+    /// The identifier of the characteristic.
+    identifier: Identifier,
+
+    /// The value the characteristic is bound to (if any).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    value: Option<String>,
+}
+
+impl Binding {
+    /// Creates a new binding to a characteristic without a value.
+    pub fn new(identifier: Identifier) -> Self {
+        Self {
+            identifier,
+            value: None,
+        }
+    }
+
+    /// Sets the value the characteristic is bound to.
+    pub fn with_value(mut self, value: impl Into<String>) -> Self {
+        self.value = Some(value.into());
+        self
+    }
+
+    /// Gets the identifier of the characteristic.
+    pub fn identifier(&self) -> &Identifier {
+        &self.identifier
+    }
+
+    /// Gets the value the characteristic is bound to (if any).
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+}
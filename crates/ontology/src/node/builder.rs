@@ -1,7 +1,12 @@
 //! Node builders.
 
+use ecc::text::LanguageTag;
+use ecc::text::language::Translations;
+
+use super::Binding;
 use super::Name;
 use super::Node;
+use crate::constraint::Constraint;
 
 /// An error when using a node builder.
 #[derive(Debug)]
@@ -26,11 +31,29 @@ pub struct Builder {
     /// The node.
     name: Option<Name>,
 
+    /// The translated names.
+    translations: Translations,
+
     /// The parent node.
     parent: Option<Name>,
 
     /// The short code.
     code: Option<String>,
+
+    /// The characteristic bindings.
+    characteristics: Vec<Binding>,
+
+    /// The constraints on the descendants.
+    constraints: Vec<Constraint>,
+
+    /// The position among siblings.
+    order: Option<u32>,
+
+    /// Whether the node is obsolete.
+    obsolete: bool,
+
+    /// The replacement node.
+    replaced_by: Option<Name>,
 }
 
 impl Builder {
@@ -40,6 +63,12 @@ impl Builder {
         self
     }
 
+    /// Sets the translation of the name into a language.
+    pub fn translation(mut self, language: LanguageTag, value: impl Into<String>) -> Self {
+        self.translations.insert(language, value.into());
+        self
+    }
+
     /// Sets the parent for the node.
     pub fn parent(mut self, value: impl Into<Name>) -> Self {
         self.parent = Some(value.into());
@@ -52,12 +81,52 @@ impl Builder {
         self
     }
 
+    /// Adds a characteristic binding to the node.
+    pub fn characteristic(mut self, value: Binding) -> Self {
+        self.characteristics.push(value);
+        self
+    }
+
+    /// Adds a constraint that every descendant of the node must satisfy.
+    pub fn constraint(mut self, value: Constraint) -> Self {
+        self.constraints.push(value);
+        self
+    }
+
+    /// Sets the position of the node among its siblings.
+    pub fn order(mut self, value: u32) -> Self {
+        self.order = Some(value);
+        self
+    }
+
+    /// Sets whether the node has been removed from the classification.
+    pub fn obsolete(mut self, value: bool) -> Self {
+        self.obsolete = value;
+        self
+    }
+
+    /// Sets the node that replaces this one.
+    pub fn replaced_by(mut self, value: impl Into<Name>) -> Self {
+        self.replaced_by = Some(value.into());
+        self
+    }
+
     /// Consumes self and tries to return a built node.
     pub fn try_build(self) -> Result<Node, Error> {
         let name = self.name.ok_or(Error::MissingField("name"))?;
         let parent = self.parent.ok_or(Error::MissingField("parent"))?;
         let code = self.code.ok_or(Error::MissingField("code"))?;
 
-        Ok(Node { name, parent, code })
+        Ok(Node {
+            name,
+            translations: self.translations,
+            parent,
+            code,
+            characteristics: self.characteristics,
+            constraints: self.constraints,
+            order: self.order,
+            obsolete: self.obsolete,
+            replaced_by: self.replaced_by,
+        })
     }
 }
@@ -150,15 +150,43 @@ impl std::fmt::Display for IncorrectCaseError {
 
 impl std::error::Error for IncorrectCaseError {}
 
+/// A policy for validating the case of the words within a name.
+///
+/// The default policy uses the built-in list of lowercase words.
+#[derive(Clone, Debug, Default)]
+pub struct CasePolicy {
+    /// Words that are expected to be lowercase in addition to the built-in
+    /// list.
+    lowercase_words: Vec<String>,
+}
+
+impl CasePolicy {
+    /// Adds words that are expected to be lowercase (in addition to the
+    /// built-in list).
+    pub fn with_lowercase_words(
+        mut self,
+        words: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.lowercase_words.extend(
+            words
+                .into_iter()
+                .map(|word| word.into().to_ascii_lowercase()),
+        );
+        self
+    }
+
+    /// Returns whether a (lowercased) word is expected to be lowercase.
+    fn is_lowercase_word(&self, word: &str) -> bool {
+        LOWERCASE_WORDS.contains(&word) || self.lowercase_words.iter().any(|w| w == word)
+    }
+}
+
 /// Validates the case of a word to ensure it meets the policy of the ontology
 /// node names.
-fn validate_word_case(input: AsciiString) -> Result<Case, IncorrectCaseError> {
+fn validate_word_case(input: AsciiString, policy: &CasePolicy) -> Result<Case, IncorrectCaseError> {
     // Check if the word should be lowercased.
     let lowercased = input.to_lowercase();
-    if LOWERCASE_WORDS
-        .iter()
-        .any(|word| word == &lowercased.as_str())
-    {
+    if policy.is_lowercase_word(lowercased.as_str()) {
         if lowercased == input {
             return Ok(Case::Lower(input));
         } else {
@@ -254,22 +282,9 @@ impl Name {
         self.inner.as_ref()
     }
 
-    /// Consumes `self` and returns the constitutient parts of the name.
-    pub fn into_parts(self) -> (String, impl Iterator<Item = Case>) {
-        (self.inner, self.words.into_iter())
-    }
-}
-
-impl std::fmt::Display for Name {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.inner)
-    }
-}
-
-impl std::str::FromStr for Name {
-    type Err = ParseError;
-
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
+    /// Parses a name, validating the case of each word with the provided
+    /// policy.
+    pub fn parse_with_policy(input: &str, policy: &CasePolicy) -> Result<Self, ParseError> {
         let (invalid, ascii_words): (Vec<_>, Vec<_>) = input
             .chars()
             .filter(|c| *c != ',' && *c != ';')
@@ -293,7 +308,7 @@ impl std::str::FromStr for Name {
             // SAFETY: we just partitioned the array above to make sure only
             // [`Some`] results are included in the `ascii_words` vector.
             .map(|(_, value)| value.unwrap())
-            .map(validate_word_case)
+            .map(|word| validate_word_case(word, policy))
             .partition(Result::is_err);
 
         if !invalid.is_empty() {
@@ -315,6 +330,25 @@ impl std::str::FromStr for Name {
             words,
         })
     }
+
+    /// Consumes `self` and returns the constitutient parts of the name.
+    pub fn into_parts(self) -> (String, impl Iterator<Item = Case>) {
+        (self.inner, self.words.into_iter())
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.inner)
+    }
+}
+
+impl std::str::FromStr for Name {
+    type Err = ParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        Self::parse_with_policy(input, &CasePolicy::default())
+    }
 }
 
 #[cfg(test)]
@@ -367,4 +401,13 @@ mod tests {
 
         let _ = "iAMP21".parse::<Name>().unwrap();
     }
+
+    #[test]
+    fn policy() {
+        assert!("Tumor in Situ".parse::<Name>().is_err());
+
+        let policy = CasePolicy::default().with_lowercase_words(["in", "Situ"]);
+        let name = Name::parse_with_policy("Tumor in situ", &policy).unwrap();
+        assert_eq!(name.inner(), "Tumor in situ");
+    }
 }
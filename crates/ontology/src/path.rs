@@ -0,0 +1,169 @@
+//! An invertible conversion between node names and file names.
+//!
+//! Converting a name to kebab case is lossy (e.g., `B-cell` and `B Cell` are
+//! both written as `b-cell`), so the name of a node cannot be recovered from
+//! the name of its file. [`encode()`] instead produces a file stem from which
+//! [`decode()`] recovers the original name exactly:
+//!
+//! * ASCII letters, digits, and hyphens are kept as they are,
+//! * spaces are written as underscores, and
+//! * every other character (including underscores and percent signs) is written
+//!   as a percent sign followed by its code in uppercase hexadecimal (e.g., `,`
+//!   is written as `%2C`).
+//!
+//! Encoded stems never contain characters that are reserved on Windows, never
+//! end with a dot or a space, and are never one of the names that are reserved
+//! on Windows (e.g., `Con` is written as `Co%6E`).
+
+use crate::node::Name;
+use crate::node::name::CasePolicy;
+use crate::node::name::ParseError;
+
+/// File stems that are reserved on Windows (regardless of their case).
+const RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8",
+    "com9", "lpt1", "lpt2", "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// An error when decoding a file stem.
+#[derive(Debug)]
+pub enum Error {
+    /// A percent sign is not followed by two hexadecimal digits that encode
+    /// an ASCII character.
+    Escape(String),
+
+    /// The decoded name is not a valid name.
+    Name(ParseError),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Escape(escape) => write!(f, "invalid escape sequence: `{escape}`"),
+            Error::Name(err) => write!(f, "invalid name: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Writes a character as a percent sign followed by its code.
+fn escape(out: &mut String, c: char) {
+    out.push_str(&format!("%{:02X}", c as u32));
+}
+
+/// Encodes a name as a file stem.
+///
+/// See the module-level documentation for the encoding.
+pub fn encode(name: &Name) -> String {
+    let name = name.inner();
+    let mut out = String::with_capacity(name.len());
+
+    // NOTE: names are always ASCII, so every character fits within two
+    // hexadecimal digits.
+    for c in name.chars() {
+        match c {
+            'A'..='Z' | 'a'..='z' | '0'..='9' | '-' => out.push(c),
+            ' ' => out.push('_'),
+            _ => escape(&mut out, c),
+        }
+    }
+
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(&out))
+    {
+        // SAFETY: reserved names are never empty.
+        let last = out.pop().unwrap();
+        escape(&mut out, last);
+    }
+
+    out
+}
+
+/// Decodes a file stem written by [`encode()`] back into a name.
+///
+/// The name is validated with the default case policy (see
+/// [`decode_with_policy()`]).
+pub fn decode(stem: &str) -> Result<Name, Error> {
+    decode_with_policy(stem, &CasePolicy::default())
+}
+
+/// Decodes a file stem written by [`encode()`] back into a name, validating
+/// the case of each word with the provided policy.
+pub fn decode_with_policy(stem: &str, policy: &CasePolicy) -> Result<Name, Error> {
+    let mut name = String::with_capacity(stem.len());
+    let mut chars = stem.char_indices();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '_' => name.push(' '),
+            '%' => {
+                let escape = stem.get(i..i + 3).unwrap_or(&stem[i..]);
+                let c = escape
+                    .get(1..)
+                    .filter(|hex| hex.len() == 2)
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .filter(u8::is_ascii)
+                    .ok_or_else(|| Error::Escape(escape.to_string()))?;
+
+                name.push(char::from(c));
+                chars.nth(1);
+            }
+            _ => name.push(c),
+        }
+    }
+
+    Name::parse_with_policy(&name, policy).map_err(Error::Name)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Parses a name.
+    fn name(name: &str) -> Name {
+        name.parse().unwrap()
+    }
+
+    #[test]
+    fn encoding() {
+        assert_eq!(encode(&name("B-cell Lymphoma")), "B-cell_Lymphoma");
+        assert_eq!(encode(&name("B Cell Lymphoma")), "B_Cell_Lymphoma");
+        assert_eq!(
+            encode(&name("B-cell Acute Lymphoblastic Leukemia, BCR::ABL1")),
+            "B-cell_Acute_Lymphoblastic_Leukemia%2C_BCR%3A%3AABL1"
+        );
+        assert_eq!(
+            encode(&name("Leukemia, NOS (WHO)")),
+            "Leukemia%2C_NOS_%28WHO%29"
+        );
+        assert_eq!(encode(&name("Con")), "Co%6E");
+        assert_eq!(encode(&name("Con Leukemia")), "Con_Leukemia");
+
+        for name in ["B-cell Lymphoma", "Con", "Leukemia 100% Blasts", "A_B"] {
+            let name = name.parse::<Name>().unwrap();
+            assert_eq!(decode(&encode(&name)).unwrap(), name);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        assert!(matches!(decode("Leukemia%2"), Err(Error::Escape(escape)) if escape == "%2"));
+        assert!(matches!(decode("Leukemia%ZZ"), Err(Error::Escape(escape)) if escape == "%ZZ"));
+        assert!(matches!(decode("Leukemia%FF"), Err(Error::Escape(escape)) if escape == "%FF"));
+        assert!(matches!(decode("Leukemia%"), Err(Error::Escape(escape)) if escape == "%"));
+        assert!(matches!(decode("leukemia"), Err(Error::Name(_))));
+    }
+
+    proptest! {
+        #[test]
+        fn roundtrip(name in any::<Name>()) {
+            let stem = encode(&name);
+            prop_assert!(stem.chars().all(|c| c.is_ascii_alphanumeric() || "-_%".contains(c)));
+            prop_assert_eq!(decode(&stem).unwrap(), name);
+        }
+    }
+}
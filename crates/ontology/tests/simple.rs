@@ -1,6 +1,7 @@
 #![allow(missing_docs)]
 
 use ontology::Node;
+use ontology::node::Binding;
 use ontology::node::Name;
 use ontology::node::{self};
 use test_infra::read_fixture;
@@ -21,4 +22,20 @@ fn parse() {
         .try_build()
         .unwrap();
     assert_eq!(first, expected);
+
+    let second: Node = chrs.next().unwrap();
+    let expected = node::Builder::default()
+        .name(
+            "B-cell Acute Lymphoblastic Leukemia, ETV6::RUNX1"
+                .parse::<Name>()
+                .unwrap(),
+        )
+        .parent("B-cell Lymphoblastic Leukemia".parse::<Name>().unwrap())
+        .code("BALLETV6RUNX1")
+        .characteristic(Binding::new("ECC-MOLEC-000001".parse().unwrap()).with_value("true"))
+        .characteristic(Binding::new("ECC-MORPH-000001".parse().unwrap()))
+        .try_build()
+        .unwrap();
+    assert_eq!(second, expected);
+    assert_eq!(second.characteristics()[0].value(), Some("true"));
 }
@@ -0,0 +1,129 @@
+#![allow(missing_docs)]
+
+use std::collections::HashSet;
+
+use ecc::annotate::Annotation;
+use ecc::annotate::AnnotationSet;
+use ontology::Graph;
+use ontology::binary;
+use ontology::classify::classify;
+use ontology::constraint::evaluate;
+use ontology::path;
+use test_infra::fixtures::identifier;
+use test_infra::fixtures::node;
+use test_infra::fixtures::small_ontology;
+use test_infra::fixtures::small_ontology_with;
+
+#[test]
+fn constraints() {
+    assert!(evaluate(&small_ontology()).is_empty());
+
+    let graph = small_ontology_with(|nodes| {
+        nodes.push(
+            node(
+                "B-cell Acute Lymphoblastic Leukemia, Other",
+                "B-cell Lymphoblastic Leukemia",
+                "BALLOTHER",
+            )
+            .try_build()
+            .unwrap(),
+        );
+    });
+
+    let violations = evaluate(&graph);
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        violations[0].node,
+        "B-cell Acute Lymphoblastic Leukemia, Other"
+    );
+}
+
+#[test]
+fn classification() {
+    let graph = small_ontology();
+    let annotations = AnnotationSet::new(vec![
+        Annotation::new(identifier("ECC-MORPH-000001"), "true"),
+        Annotation::new(identifier("ECC-MOLEC-000001"), "false"),
+    ]);
+
+    let result = classify(&graph, &annotations);
+    assert_eq!(result.nodes.len(), 1);
+    assert_eq!(result.nodes[0].code(), "BALLBCRABL1");
+}
+
+#[test]
+fn binary_roundtrip() {
+    let graph = small_ontology_with(|nodes| {
+        nodes[4] = node("T-cell Lymphoblastic Leukemia", "Leukemia", "TLL")
+            .order(1)
+            .translation("de".parse().unwrap(), "T-lymphoblastische Leukämie")
+            .try_build()
+            .unwrap();
+    });
+    let bytes = graph.to_bytes();
+    assert!(bytes.starts_with(binary::MAGIC));
+
+    let decoded = Graph::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        decoded.nodes().collect::<Vec<_>>(),
+        graph.nodes().collect::<Vec<_>>()
+    );
+    assert_eq!(decoded.root().name(), graph.root().name());
+}
+
+#[test]
+fn binary_invalid() {
+    let bytes = small_ontology().to_bytes();
+
+    assert!(matches!(
+        Graph::from_bytes(b"not an artifact"),
+        Err(binary::Error::Header)
+    ));
+    assert!(matches!(
+        Graph::from_bytes(b"ECCO\x01"),
+        Err(binary::Error::Header)
+    ));
+
+    let mut newer = bytes.clone();
+    newer[4..6].copy_from_slice(&(binary::VERSION + 1).to_le_bytes());
+    assert_eq!(
+        Graph::from_bytes(&newer).unwrap_err().to_string(),
+        format!(
+            "unsupported ontology artifact version {} (expected {})",
+            binary::VERSION + 1,
+            binary::VERSION
+        )
+    );
+
+    let truncated = &bytes[..bytes.len() - 4];
+    assert!(matches!(
+        Graph::from_bytes(truncated),
+        Err(binary::Error::Decode(_))
+    ));
+}
+
+#[test]
+fn file_names() {
+    let graph = small_ontology();
+    let mut stems = HashSet::new();
+
+    for node in graph.nodes() {
+        let stem = path::encode(node.name());
+        assert_eq!(&path::decode(&stem).unwrap(), node.name());
+        assert!(stems.insert(stem.to_lowercase()), "`{stem}` collides");
+    }
+}
+
+#[test]
+fn display_names() {
+    let node = node("Leukemia", "Hematologic Malignancy", "LEUK")
+        .translation("de".parse().unwrap(), "Leukämie")
+        .translation("pt-BR".parse().unwrap(), "Leucemia")
+        .try_build()
+        .unwrap();
+
+    assert_eq!(node.display_name(&"de-AT".parse().unwrap()), "Leukämie");
+    assert_eq!(node.display_name(&"pt-BR".parse().unwrap()), "Leucemia");
+    assert_eq!(node.display_name(&"pt-PT".parse().unwrap()), "Leukemia");
+    assert_eq!(node.display_name(&"en".parse().unwrap()), "Leukemia");
+}
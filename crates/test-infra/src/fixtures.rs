@@ -0,0 +1,198 @@
+//! Programmatic fixtures.
+//!
+//! Each factory returns a fully valid object. The `_with` variants take a
+//! closure that can override parts of the object before it is returned, so a
+//! test only needs to spell out what it cares about:
+//!
+//! ```
+//! let characteristic = test_infra::fixtures::adopted_characteristic_with(|common| {
+//!     common.set_name("Another Name").unwrap();
+//! });
+//!
+//! assert_eq!(characteristic.name(), Some("Another Name"));
+//! ```
+
+use chrono::DateTime;
+use ecc::Characteristic;
+use ecc::Identifier;
+use ecc::common::Common;
+use ecc::common::value::Kind;
+use ecc::common::value::kind::binary;
+use ecc::field;
+use ontology::Graph;
+use ontology::Node;
+use ontology::constraint::Constraint;
+use ontology::node;
+use ontology::node::Binding;
+use ontology::node::Name;
+
+/// Parses an identifier.
+///
+/// # Panics
+///
+/// Panics if the identifier is invalid.
+pub fn identifier(s: &str) -> Identifier {
+    s.parse()
+        .unwrap_or_else(|err| panic!("invalid identifier `{s}`: {err}"))
+}
+
+/// Creates a field description.
+fn description(summary: &str, details: &str) -> field::Description {
+    field::Description {
+        // SAFETY: the sentences are never empty.
+        summary: summary.parse().unwrap(),
+        details: details.parse().unwrap(),
+    }
+}
+
+/// Gets binary permissible values.
+pub fn binary_values() -> Kind {
+    Kind::Binary {
+        description: binary::Description {
+            r#true: description("The feature is present.", "The feature was observed."),
+            r#false: description("The feature is absent.", "The feature was not observed."),
+        },
+        threshold: None,
+    }
+}
+
+/// Gets a common set of elements (`ECC-MORPH-000001` with binary values).
+pub fn common() -> Common {
+    Common::new(
+        "A Characteristic Name",
+        identifier("ECC-MORPH-000001"),
+        // SAFETY: the link points to an issue on the repository.
+        "https://github.com/stjudecloud/ecc/issues/1"
+            .parse()
+            .unwrap(),
+        "A description of the characteristic.",
+        binary_values(),
+    )
+    // SAFETY: the name and description are valid.
+    .unwrap()
+}
+
+/// Gets a proposed characteristic.
+pub fn proposed_characteristic() -> Characteristic {
+    proposed_characteristic_with(|_| {})
+}
+
+/// Gets a proposed characteristic after applying overrides.
+pub fn proposed_characteristic_with(f: impl FnOnce(&mut Common)) -> Characteristic {
+    let mut common = common();
+    f(&mut common);
+    Characteristic::Proposed { common }
+}
+
+/// Gets an adopted characteristic (adopted at the start of 2024).
+pub fn adopted_characteristic() -> Characteristic {
+    adopted_characteristic_with(|_| {})
+}
+
+/// Gets an adopted characteristic after applying overrides.
+pub fn adopted_characteristic_with(f: impl FnOnce(&mut Common)) -> Characteristic {
+    let mut common = common();
+    f(&mut common);
+
+    Characteristic::Adopted {
+        common,
+        // SAFETY: the timestamp is representable.
+        adoption_date: DateTime::from_timestamp(1_704_067_200, 0).unwrap(),
+    }
+}
+
+/// Starts building a node.
+///
+/// # Panics
+///
+/// Panics if the name or parent are not valid node names.
+pub fn node(name: &str, parent: &str, code: &str) -> node::Builder {
+    node::Builder::default()
+        .name(
+            name.parse::<Name>()
+                .unwrap_or_else(|err| panic!("invalid name `{name}`: {err}")),
+        )
+        .parent(
+            parent
+                .parse::<Name>()
+                .unwrap_or_else(|err| panic!("invalid name `{parent}`: {err}")),
+        )
+        .code(code)
+}
+
+/// Gets the nodes of a small ontology.
+///
+/// ```text
+/// Leukemia
+/// ├── B-cell Lymphoblastic Leukemia      ECC-MORPH-000001 = true
+/// │   │                                  (descendants require ECC-MOLEC-000001)
+/// │   ├── ..., ETV6::RUNX1              ECC-MOLEC-000001 = true
+/// │   └── ..., BCR::ABL1                ECC-MOLEC-000001 = false
+/// └── T-cell Lymphoblastic Leukemia      ECC-MORPH-000001 = false
+/// ```
+pub fn small_ontology_nodes() -> Vec<Node> {
+    let bound = |builder: node::Builder, id: &str, value: &str| {
+        builder
+            .characteristic(Binding::new(identifier(id)).with_value(value))
+            .try_build()
+            // SAFETY: every required field is set.
+            .unwrap()
+    };
+
+    vec![
+        // SAFETY: every required field is set.
+        node("Leukemia", "", "LEUK").try_build().unwrap(),
+        bound(
+            node("B-cell Lymphoblastic Leukemia", "Leukemia", "BLL")
+                .constraint(Constraint::new(identifier("ECC-MOLEC-000001"))),
+            "ECC-MORPH-000001",
+            "true",
+        ),
+        bound(
+            node(
+                "B-cell Acute Lymphoblastic Leukemia, ETV6::RUNX1",
+                "B-cell Lymphoblastic Leukemia",
+                "BALLETV6RUNX1",
+            ),
+            "ECC-MOLEC-000001",
+            "true",
+        ),
+        bound(
+            node(
+                "B-cell Acute Lymphoblastic Leukemia, BCR::ABL1",
+                "B-cell Lymphoblastic Leukemia",
+                "BALLBCRABL1",
+            ),
+            "ECC-MOLEC-000001",
+            "false",
+        ),
+        bound(
+            node("T-cell Lymphoblastic Leukemia", "Leukemia", "TLL"),
+            "ECC-MORPH-000001",
+            "false",
+        ),
+    ]
+}
+
+/// Gets a small ontology (see [`small_ontology_nodes`]).
+pub fn small_ontology() -> Graph {
+    small_ontology_with(|_| {})
+}
+
+/// Gets a small ontology after applying overrides to its nodes.
+///
+/// # Panics
+///
+/// Panics if the overridden nodes are not a structurally valid ontology.
+pub fn small_ontology_with(f: impl FnOnce(&mut Vec<Node>)) -> Graph {
+    let mut nodes = small_ontology_nodes();
+    f(&mut nodes);
+
+    Graph::try_new(nodes).unwrap_or_else(|issues| {
+        let issues = issues
+            .iter()
+            .map(|issue| issue.to_string())
+            .collect::<Vec<_>>();
+        panic!("invalid ontology: {}", issues.join("; "))
+    })
+}
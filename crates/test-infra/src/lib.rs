@@ -1,24 +1,89 @@
 //! Common operations in the integration tests.
+//!
+//! Fixtures live in the `tests/fixtures` directory of each crate and are
+//! referred to by their name without an extension. The format is chosen by
+//! the extension of the (single) matching file:
+//!
+//! * `.yaml`/`.yml`: YAML, including streams of multiple documents,
+//! * `.json`: JSON, and
+//! * `.toml`: TOML.
+//!
+//! Golden files live in the `tests/golden` directory of each crate and hold
+//! the expected output of a test (e.g., a rendered document). Run the tests
+//! with the [`UPDATE_GOLDEN`] environment variable set to `1` to regenerate
+//! them, and review the changes before committing.
 #![allow(tail_expr_drop_order)]
 
+pub mod fixtures;
+pub mod tree;
+
+use std::path::Path;
 use std::path::PathBuf;
 
 use anyhow::Context as _;
 use anyhow::anyhow;
+use anyhow::bail;
 use glob::glob;
 use serde::Deserialize;
+use serde::Serialize;
 
-/// Reads an ontology node fixture within the integration tests.
-pub fn read_fixture<D>(fixture: &str) -> anyhow::Result<Vec<D>>
-where
-    D: for<'de> Deserialize<'de>,
-{
+/// The environment variable that, when set to `1`, regenerates golden files
+/// rather than comparing against them.
+pub const UPDATE_GOLDEN: &str = "UPDATE_GOLDEN";
+
+/// The format of a fixture.
+#[derive(Clone, Copy, Debug)]
+enum Format {
+    /// YAML.
+    Yaml,
+
+    /// JSON.
+    Json,
+
+    /// TOML.
+    Toml,
+}
+
+impl Format {
+    /// Gets the format of a fixture from its extension.
+    fn from_path(path: &Path) -> anyhow::Result<Self> {
+        // SAFETY: the glob used to find fixtures _requires_ that the path have
+        // an extension, so this will always unwrap.
+        let ext = path.extension().unwrap().to_string_lossy();
+
+        match ext.as_ref() {
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            "toml" => Ok(Self::Toml),
+            v => bail!("unhandled fixture extension: {v}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::Yaml => write!(f, "YAML"),
+            Format::Json => write!(f, "JSON"),
+            Format::Toml => write!(f, "TOML"),
+        }
+    }
+}
+
+/// Gets a directory within the `tests` directory of the crate under test.
+fn tests_dir(name: &str) -> PathBuf {
     // NOTE: this is the root of the crate itself, not the workspace.
     let mut path = std::env::var("CARGO_MANIFEST_DIR")
         .map(PathBuf::from)
         .expect("crate root to be available at compile time");
     path.push("tests");
-    path.push("fixtures");
+    path.push(name);
+    path
+}
+
+/// Finds the file for a fixture.
+fn locate(fixture: &str) -> anyhow::Result<PathBuf> {
+    let mut path = tests_dir("fixtures");
     path.push(fixture);
 
     let search_path = format!("{}.*", path.display());
@@ -27,31 +92,192 @@ where
         .collect::<Result<Vec<_>, _>>()
         .context("reading matching files")?;
 
-    let path = match files.len() {
-        0 => return Err(anyhow!("no files matched the pattern `{search_path}`")),
-        1 => files.pop().unwrap(),
-        v => {
-            return Err(anyhow!(
-                "expected one file matching pattern `{search_path}`, found {v} files"
-            ));
-        }
-    };
+    match files.len() {
+        0 => Err(anyhow!("no files matched the pattern `{search_path}`")),
+        // SAFETY: we just checked that there is exactly one file.
+        1 => Ok(files.pop().unwrap()),
+        v => Err(anyhow!(
+            "expected one file matching pattern `{search_path}`, found {v} files"
+        )),
+    }
+}
 
+/// Reads the contents and format of a fixture.
+fn read(fixture: &str) -> anyhow::Result<(PathBuf, Format, String)> {
+    let path = locate(fixture)?;
+    let format = Format::from_path(&path)?;
     let contents = std::fs::read_to_string(&path)
         .with_context(|| format!("reading fixture at path: {}", path.display()))?;
 
-    // SAFETY: the glob above _requires_ that the path have an extension, so
-    // this will always unwrap.
-    let ext = path
-        .extension()
-        .map(|ext| ext.to_string_lossy())
-        .unwrap()
-        .to_string();
-
-    match ext.as_str() {
-        "yaml" | "yml" => {
-            serde_yaml::from_str(&contents).context("deserializing identifier from YAML")
+    Ok((path, format, contents))
+}
+
+/// Reads a fixture holding a single value.
+///
+/// YAML fixtures must contain exactly one document.
+pub fn read_fixture_one<D>(fixture: &str) -> anyhow::Result<D>
+where
+    D: for<'de> Deserialize<'de>,
+{
+    let (path, format, contents) = read(fixture)?;
+    let context = || format!("deserializing {format} fixture: {}", path.display());
+
+    match format {
+        Format::Yaml => {
+            let mut documents = serde_yaml::Deserializer::from_str(&contents);
+
+            let Some(document) = documents.next() else {
+                bail!("YAML fixture has no documents: {}", path.display());
+            };
+
+            let value = D::deserialize(document).with_context(context)?;
+
+            if documents.next().is_some() {
+                bail!(
+                    "expected a single YAML document, found more than one (use \
+                     `read_fixture_many` instead): {}",
+                    path.display()
+                );
+            }
+
+            Ok(value)
         }
-        v => Err(anyhow!("unhandled fixture extension: {v}")),
+        Format::Json => serde_json::from_str(&contents).with_context(context),
+        Format::Toml => toml::from_str(&contents).with_context(context),
     }
 }
+
+/// Reads a fixture holding many values.
+///
+/// The values are read as follows:
+///
+/// * YAML: a single document holding a sequence, or a stream of documents that
+///   each hold one value,
+/// * JSON: an array, and
+/// * TOML: an array of tables named `fixture` (i.e., `[[fixture]]`).
+pub fn read_fixture_many<D>(fixture: &str) -> anyhow::Result<Vec<D>>
+where
+    D: for<'de> Deserialize<'de>,
+{
+    /// The layout of a TOML fixture with many values.
+    #[derive(Deserialize)]
+    struct Many<D> {
+        /// The values.
+        fixture: Vec<D>,
+    }
+
+    let (path, format, contents) = read(fixture)?;
+    let context = || format!("deserializing {format} fixture: {}", path.display());
+
+    match format {
+        Format::Yaml => {
+            let documents = serde_yaml::Deserializer::from_str(&contents).collect::<Vec<_>>();
+
+            if documents.len() == 1 {
+                // SAFETY: we just checked that there is exactly one document.
+                let document = documents.into_iter().next().unwrap();
+                return Vec::<D>::deserialize(document).with_context(context);
+            }
+
+            documents
+                .into_iter()
+                .enumerate()
+                .map(|(i, document)| {
+                    D::deserialize(document)
+                        .with_context(|| format!("{} (document {})", context(), i + 1))
+                })
+                .collect()
+        }
+        Format::Json => serde_json::from_str(&contents).with_context(context),
+        Format::Toml => toml::from_str::<Many<D>>(&contents)
+            .map(|many| many.fixture)
+            .with_context(context),
+    }
+}
+
+/// Reads a fixture holding many values.
+///
+/// This is an alias of [`read_fixture_many`].
+pub fn read_fixture<D>(fixture: &str) -> anyhow::Result<Vec<D>>
+where
+    D: for<'de> Deserialize<'de>,
+{
+    read_fixture_many(fixture)
+}
+
+/// Asserts that the output of a test matches a golden file.
+///
+/// The path is relative to the `tests/golden` directory of the crate. When the
+/// [`UPDATE_GOLDEN`] environment variable is set to `1`, the golden file is
+/// (re)written with the actual output instead. Line endings are normalized
+/// before comparing so that checkouts with CRLF line endings still match.
+///
+/// # Panics
+///
+/// Panics if the golden file does not exist or its contents differ from the
+/// actual output.
+#[track_caller]
+pub fn assert_golden(path: impl AsRef<Path>, actual: impl AsRef<str>) {
+    let path = tests_dir("golden").join(path);
+    let actual = actual.as_ref();
+
+    if std::env::var(UPDATE_GOLDEN).is_ok_and(|value| value == "1") {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .unwrap_or_else(|err| panic!("creating directory `{}`: {err}", parent.display()));
+        }
+
+        std::fs::write(&path, actual)
+            .unwrap_or_else(|err| panic!("writing golden file `{}`: {err}", path.display()));
+        return;
+    }
+
+    let expected = match std::fs::read_to_string(&path) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(err) => panic!(
+            "reading golden file `{}`: {err}\n\nrun the tests with `{UPDATE_GOLDEN}=1` to create \
+             it",
+            path.display()
+        ),
+    };
+
+    if expected == actual {
+        return;
+    }
+
+    let diff = expected
+        .lines()
+        .map(Some)
+        .chain(std::iter::repeat(None))
+        .zip(actual.lines().map(Some).chain(std::iter::repeat(None)))
+        .take_while(|(expected, actual)| expected.is_some() || actual.is_some())
+        .enumerate()
+        .filter(|(_, (expected, actual))| expected != actual)
+        .take(10)
+        .map(|(i, (expected, actual))| {
+            format!(
+                "line {}:\n  - {}\n  + {}",
+                i + 1,
+                expected.unwrap_or("<end of file>"),
+                actual.unwrap_or("<end of file>")
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    panic!(
+        "output does not match golden file `{}` (showing up to 10 differing \
+         lines):\n\n{diff}\n\nrun the tests with `{UPDATE_GOLDEN}=1` to update it",
+        path.display()
+    );
+}
+
+/// Asserts that the pretty-printed JSON of a value matches a golden file.
+///
+/// See [`assert_golden`] for details.
+#[track_caller]
+pub fn assert_golden_json<T: Serialize + ?Sized>(path: impl AsRef<Path>, value: &T) {
+    let mut json = serde_json::to_string_pretty(value).expect("value to serialize to JSON");
+    json.push('\n');
+    assert_golden(path, json);
+}
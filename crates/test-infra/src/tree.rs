@@ -0,0 +1,251 @@
+//! Temporary characteristic and ontology trees.
+//!
+//! A [`Tree`] materializes characteristics and ontology nodes into a temporary
+//! directory laid out like the real repository, so commands that read a tree
+//! from disk can be tested end to end:
+//!
+//! ```text
+//! <root>/
+//! ├── ecc.toml
+//! ├── ecc/
+//! │   ├── molec/<kebab-case name>.yml
+//! │   └── morph/<kebab-case name>.yml
+//! └── ontology/
+//!     └── <root>.yml, <root>/<child>.yml, ...
+//! ```
+//!
+//! The directory is removed when the tree is dropped.
+//!
+//! ```
+//! use test_infra::fixtures;
+//! use test_infra::tree::Tree;
+//!
+//! let tree = Tree::new()
+//!     .with_characteristic(fixtures::adopted_characteristic())
+//!     .with_ontology(&fixtures::small_ontology());
+//!
+//! assert!(
+//!     tree.characteristics()
+//!         .join("morph/a-characteristic-name.yml")
+//!         .exists()
+//! );
+//! assert!(
+//!     tree.ontology()
+//!         .join("leukemia/b-cell-lymphoblastic-leukemia.yml")
+//!         .exists()
+//! );
+//! ```
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use convert_case::Boundary;
+use convert_case::Case;
+use convert_case::Casing as _;
+use ecc::Category;
+use ecc::Characteristic;
+use ontology::Graph;
+use ontology::Node;
+use tempfile::TempDir;
+
+/// The name of the configuration file.
+pub const CONFIG: &str = "ecc.toml";
+
+/// The name of the characteristic directory.
+pub const CHARACTERISTICS: &str = "ecc";
+
+/// The name of the ontology directory.
+pub const ONTOLOGY: &str = "ontology";
+
+/// The configuration written into every tree.
+const CONFIG_CONTENTS: &str = r#"[directories]
+characteristics = "ecc"
+ontology = "ontology"
+"#;
+
+/// Characters that are removed from node file names.
+const CHARS_TO_REMOVE: &[char] = &[',', ';'];
+
+/// Converts a name into the kebab-case stem of a characteristic file.
+fn characteristic_stem(name: &str) -> String {
+    name.to_case(Case::Kebab)
+}
+
+/// Characters that are not allowed within file names on Windows.
+const RESERVED_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Converts a name into the kebab-case stem of an ontology node file.
+///
+/// Gene names are kept together (e.g., `kmt2a` instead of `kmt-2-a`).
+fn node_stem(name: &str) -> String {
+    name.replace(CHARS_TO_REMOVE, "")
+        .from_case(Case::Title)
+        .without_boundaries(&[Boundary::DigitUpper, Boundary::DigitLower])
+        .to_case(Case::Kebab)
+        .replace(RESERVED_CHARS, "")
+}
+
+/// A temporary characteristic and ontology tree.
+#[derive(Debug)]
+pub struct Tree {
+    /// The temporary directory.
+    dir: TempDir,
+}
+
+impl Tree {
+    /// Creates an empty tree containing only a configuration file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the temporary directory cannot be created.
+    pub fn new() -> Self {
+        let tree = Self {
+            dir: TempDir::new().expect("temporary directory to be created"),
+        };
+
+        tree.write(CONFIG, CONFIG_CONTENTS);
+        std::fs::create_dir_all(tree.characteristics())
+            .expect("characteristic directory to be created");
+        tree
+    }
+
+    /// Gets the root of the tree.
+    pub fn path(&self) -> &Path {
+        self.dir.path()
+    }
+
+    /// Gets the path of the configuration file.
+    pub fn config(&self) -> PathBuf {
+        self.path().join(CONFIG)
+    }
+
+    /// Gets the characteristic directory.
+    pub fn characteristics(&self) -> PathBuf {
+        self.path().join(CHARACTERISTICS)
+    }
+
+    /// Gets the ontology directory.
+    pub fn ontology(&self) -> PathBuf {
+        self.path().join(ONTOLOGY)
+    }
+
+    /// Writes a file relative to the root of the tree, creating any missing
+    /// parent directories.
+    ///
+    /// Returns the path to the file.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the file cannot be written.
+    pub fn write(&self, path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> PathBuf {
+        let path = self.path().join(path);
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .unwrap_or_else(|err| panic!("creating directory `{}`: {err}", parent.display()));
+        }
+
+        std::fs::write(&path, contents)
+            .unwrap_or_else(|err| panic!("writing file `{}`: {err}", path.display()));
+        path
+    }
+
+    /// Gets the path (relative to the root) a characteristic is written to.
+    ///
+    /// Characteristics are named after their name (or their identifier when
+    /// they have no name) within the directory of their category. Drafts
+    /// without an identifier are written directly within the characteristic
+    /// directory.
+    pub fn characteristic_path(characteristic: &Characteristic) -> PathBuf {
+        let stem = characteristic
+            .name()
+            .map(characteristic_stem)
+            .or_else(|| {
+                characteristic
+                    .identifier()
+                    .map(|id| id.to_string().to_lowercase())
+            })
+            .unwrap_or_else(|| String::from("draft"));
+
+        let mut path = PathBuf::from(CHARACTERISTICS);
+
+        if let Some(identifier) = characteristic.identifier() {
+            path.push(match identifier.category() {
+                Category::Molecular => "molec",
+                Category::Morphological => "morph",
+            });
+        }
+
+        path.push(format!("{stem}.yml"));
+        path
+    }
+
+    /// Adds a characteristic to the tree.
+    ///
+    /// Fields that are not set (e.g., within drafts) are omitted from the
+    /// file, as they would be when written by `ecc-cli ecc init`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the characteristic cannot be serialized or written.
+    pub fn with_characteristic(self, characteristic: Characteristic) -> Self {
+        let mut value = serde_yaml::to_value(&characteristic).expect("characteristic to serialize");
+
+        if let serde_yaml::Value::Mapping(mapping) = &mut value {
+            mapping.retain(|_, value| !value.is_null());
+        }
+
+        let contents = serde_yaml::to_string(&value).expect("characteristic to serialize");
+        self.write(Self::characteristic_path(&characteristic), contents);
+        self
+    }
+
+    /// Adds characteristics to the tree.
+    pub fn with_characteristics(
+        self,
+        characteristics: impl IntoIterator<Item = Characteristic>,
+    ) -> Self {
+        characteristics
+            .into_iter()
+            .fold(self, Self::with_characteristic)
+    }
+
+    /// Gets the path (relative to the root) a node is written to.
+    ///
+    /// Each node is nested within a directory for each of its ancestors
+    /// (starting with the root).
+    pub fn node_path(graph: &Graph, node: &Node) -> PathBuf {
+        std::iter::once(PathBuf::from(ONTOLOGY))
+            .chain(
+                graph
+                    .ancestors(node.name().inner())
+                    .into_iter()
+                    .map(|ancestor| PathBuf::from(node_stem(ancestor.name().inner()))),
+            )
+            .chain(std::iter::once(PathBuf::from(format!(
+                "{}.yml",
+                node_stem(node.name().inner())
+            ))))
+            .collect()
+    }
+
+    /// Adds every node within an ontology to the tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a node cannot be serialized or written.
+    pub fn with_ontology(self, graph: &Graph) -> Self {
+        for node in graph.nodes() {
+            let contents = serde_yaml::to_string(node).expect("node to serialize");
+            self.write(Self::node_path(graph, node), contents);
+        }
+
+        self
+    }
+}
+
+impl Default for Tree {
+    fn default() -> Self {
+        Self::new()
+    }
+}